@@ -2,6 +2,65 @@
 
 use crate::co::*;
 
+const_bitflag! { ACE_FLAG: u8;
+	/// [`ACE_HEADER`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-ace_header)
+	/// `AceFlags` (`u8`).
+	///
+	/// Originally has `_ACE` or `_ACE_FLAG` suffix.
+	=>
+	OBJECT_INHERIT 0x01
+	CONTAINER_INHERIT 0x02
+	NO_PROPAGATE_INHERIT 0x04
+	INHERIT_ONLY 0x08
+	INHERITED 0x10
+	SUCCESSFUL_ACCESS 0x40
+	FAILED_ACCESS 0x80
+}
+
+const_ordinary! { ACE_TYPE: u8;
+	/// [`ACE_HEADER`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-ace_header)
+	/// `AceType` (`u8`).
+	///
+	/// Originally has `_ACE_TYPE` suffix.
+	=>
+	ACCESS_ALLOWED 0x00
+	ACCESS_DENIED 0x01
+	SYSTEM_AUDIT 0x02
+	SYSTEM_ALARM 0x03
+	ACCESS_ALLOWED_COMPOUND 0x04
+	ACCESS_ALLOWED_OBJECT 0x05
+	ACCESS_DENIED_OBJECT 0x06
+	SYSTEM_AUDIT_OBJECT 0x07
+	SYSTEM_ALARM_OBJECT 0x08
+	ACCESS_ALLOWED_CALLBACK 0x09
+	ACCESS_DENIED_CALLBACK 0x0a
+	ACCESS_ALLOWED_CALLBACK_OBJECT 0x0b
+	ACCESS_DENIED_CALLBACK_OBJECT 0x0c
+	SYSTEM_AUDIT_CALLBACK 0x0d
+	SYSTEM_ALARM_CALLBACK 0x0e
+	SYSTEM_AUDIT_CALLBACK_OBJECT 0x0f
+	SYSTEM_ALARM_CALLBACK_OBJECT 0x10
+	SYSTEM_MANDATORY_LABEL 0x11
+	SYSTEM_RESOURCE_ATTRIBUTE 0x12
+	SYSTEM_SCOPED_POLICY_ID 0x13
+	SYSTEM_PROCESS_TRUST_LABEL 0x14
+	SYSTEM_ACCESS_FILTER 0x15
+}
+
+const_bitflag! { CREATE_RESTRICTED_TOKEN: u32;
+	/// [`HACCESSTOKEN::CreateRestrictedToken`](crate::prelude::advapi_Haccesstoken::CreateRestrictedToken)
+	/// `flags` (`u32`).
+	///
+	/// Originally has no prefix.
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	DISABLE_MAX_PRIVILEGE 0x1
+	SANDBOX_INERT 0x2
+	LUA_TOKEN 0x4
+	WRITE_RESTRICTED 0x8
+}
+
 const_ordinary! { EVENTLOG: u16;
 	/// [`HEVENTLOG::ReportEvent`](crate::prelude::advapi_Heventlog::ReportEvent)
 	/// `event_type` [`u16`].
@@ -14,6 +73,83 @@ const_ordinary! { EVENTLOG: u16;
 	WARNING_TYPE 0x0002
 }
 
+const_bitflag! { EVENTLOG_READ: u32;
+	/// [`HEVENTLOG::ReadEventLog`](crate::prelude::advapi_Heventlog::ReadEventLog)
+	/// `flags` (`u32`).
+	///
+	/// Originally has `EVENTLOG_` prefix and `_READ` suffix.
+	=>
+	SEQUENTIAL 0x0001
+	SEEK 0x0002
+	FORWARDS 0x0004
+	BACKWARDS 0x0008
+}
+
+const_ordinary! { EVT_FORMAT_MESSAGE: u32;
+	/// [`EVT_FORMAT_MESSAGE_FLAGS`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/ne-winevt-evt_format_message_flags)
+	/// enumeration (`u32`).
+	///
+	/// Originally has `EvtFormatMessage` prefix.
+	=>
+	EVENT 1
+	LEVEL 2
+	TASK 3
+	OPCODE 4
+	KEYWORD 5
+	CHANNEL 6
+	PROVIDER 7
+	ID 8
+	XML 9
+}
+
+const_bitflag! { EVT_QUERY: u32;
+	/// [`EVT_QUERY_FLAGS`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/ne-winevt-evt_query_flags)
+	/// enumeration (`u32`).
+	///
+	/// Originally has `EvtQuery` prefix.
+	=>
+	CHANNEL_PATH 0x1
+	FILE_PATH 0x2
+	FORWARD_DIRECTION 0x100
+	REVERSE_DIRECTION 0x200
+	TOLERATE_QUERY_ERRORS 0x1000
+}
+
+const_ordinary! { EVT_RENDER: u32;
+	/// [`EVT_RENDER_FLAGS`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/ne-winevt-evt_render_flags)
+	/// enumeration (`u32`).
+	///
+	/// Originally has `EvtRender` prefix.
+	=>
+	EVENT_VALUES 0
+	EVENT_XML 1
+	BOOKMARK 2
+}
+
+const_ordinary! { EVT_RENDER_CONTEXT: u32;
+	/// [`EVT_RENDER_CONTEXT_FLAGS`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/ne-winevt-evt_render_context_flags)
+	/// enumeration (`u32`).
+	///
+	/// Originally has `EvtRenderContext` prefix.
+	=>
+	VALUES 0
+	SYSTEM 1
+	USER 2
+}
+
+const_bitflag! { EVT_SUBSCRIBE: u32;
+	/// [`EVT_SUBSCRIBE_FLAGS`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/ne-winevt-evt_subscribe_flags)
+	/// enumeration (`u32`).
+	///
+	/// Originally has `EvtSubscribe` prefix.
+	=>
+	TO_FUTURE_EVENTS 1
+	START_AT_OLDEST_RECORD 2
+	START_AFTER_BOOKMARK 3
+	TOLERATE_QUERY_ERRORS 0x1000
+	STRICT 0x1_0000
+}
+
 const_bitflag! { KEY: u32;
 	/// [Registry access rights](https://learn.microsoft.com/en-us/windows/win32/sysinfo/registry-key-security-and-access-rights)
 	/// (`u32`).
@@ -33,6 +169,43 @@ const_bitflag! { KEY: u32;
 	ALL_ACCESS (STANDARD_RIGHTS::ALL.raw() | Self::QUERY_VALUE.0 | Self::SET_VALUE.0 | Self::CREATE_SUB_KEY.0 | Self::ENUMERATE_SUB_KEYS.0 | Self::NOTIFY.0 | Self::CREATE_LINK.0) & !ACCESS_RIGHTS::SYNCHRONIZE.raw()
 }
 
+const_bitflag! { LOGON: u32;
+	/// [`HACCESSTOKEN::CreateProcessWithToken`](crate::prelude::advapi_Haccesstoken::CreateProcessWithToken)
+	/// `logon_flags` (`u32`).
+	///
+	/// Originally has `LOGON_WITH` or `LOGON_` prefixes.
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	WITH_PROFILE 0x0000_0001
+	NETCREDENTIALS_ONLY 0x0000_0002
+	ZERO_PASSWORD_BUFFER 0x8000_0000
+}
+
+const_ordinary! { LOGON32_LOGON: u32;
+	/// [`HACCESSTOKEN::LogonUser`](crate::prelude::advapi_Haccesstoken::LogonUser)
+	/// `logon_type` (`u32`).
+	=>
+	INTERACTIVE 2
+	NETWORK 3
+	BATCH 4
+	SERVICE 5
+	UNLOCK 7
+	NETWORK_CLEARTEXT 8
+	NEW_CREDENTIALS 9
+}
+
+const_ordinary! { LOGON32_PROVIDER: u32;
+	/// [`HACCESSTOKEN::LogonUser`](crate::prelude::advapi_Haccesstoken::LogonUser)
+	/// `logon_provider` (`u32`).
+	=>
+	DEFAULT 0
+	WINNT35 1
+	WINNT40 2
+	WINNT50 3
+	VIRTUAL 4
+}
+
 const_ordinary! { REG: u32;
 	/// Registry
 	/// [value types](https://learn.microsoft.com/en-us/windows/win32/sysinfo/registry-value-types)
@@ -66,6 +239,25 @@ const_ordinary! { REG_DISPOSITION: u32;
 	OPENED_EXISTING_KEY 0x0000_0002
 }
 
+const_bitflag! { REG_NOTIFY: u32;
+	/// [`HKEY::RegNotifyChangeKeyValue`](crate::prelude::advapi_Hkey::RegNotifyChangeKeyValue)
+	/// `notify_filter` (`u32`).
+	=>
+	/// Notify the caller if a subkey is added or deleted.
+	CHANGE_NAME 0x0000_0001
+	/// Notify the caller of changes to the attributes of the key, such as the
+	/// security descriptor information.
+	CHANGE_ATTRIBUTES 0x0000_0002
+	/// Notify the caller of changes to a value of the key. This can include
+	/// adding or deleting a value, or changing an existing value.
+	CHANGE_LAST_SET 0x0000_0004
+	/// Notify the caller of changes to the security descriptor of the key.
+	CHANGE_SECURITY 0x0000_0008
+	/// Indicates that the lifetime of the registration must not be tied to the
+	/// lifetime of the thread issuing the call. Windows 8 and later.
+	THREAD_AGNOSTIC 0x1000_0000
+}
+
 const_bitflag! { REG_OPTION: u32;
 	/// [`HKEY::RegOpenKeyEx`](crate::prelude::advapi_Hkey::RegOpenKeyEx)
 	/// `options` (`u32`).
@@ -297,6 +489,16 @@ const_bitflag! { RRF: u32;
 	ZEROONFAILURE 0x2000_0000
 }
 
+const_ordinary! { SC_ACTION: u32;
+	/// [`SC_ACTION_TYPE`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-sc_action)
+	/// enumeration (`u32`).
+	=>
+	NONE 0
+	RESTART 1
+	REBOOT 2
+	RUN_COMMAND 3
+}
+
 const_bitflag! { SC_MANAGER: u32;
 	/// Service Control Manager access rights
 	/// [`flags`](https://learn.microsoft.com/en-us/windows/win32/services/service-security-and-access-rights)
@@ -360,6 +562,43 @@ const_str! { SE_PRIV;
 	DELEGATE_SESSION_USER_IMPERSONATE_NAME "SeDelegateSessionUserImpersonatePrivilege"
 }
 
+const_bitflag! { SE_GROUP: u32;
+	/// [`SID_AND_ATTRIBUTES`](crate::SID_AND_ATTRIBUTES) `Attributes` for
+	/// groups (`u32`).
+	=>
+	MANDATORY 0x0000_0001
+	ENABLED_BY_DEFAULT 0x0000_0002
+	ENABLED 0x0000_0004
+	OWNER 0x0000_0008
+	USE_FOR_DENY_ONLY 0x0000_0010
+	INTEGRITY 0x0000_0020
+	INTEGRITY_ENABLED 0x0000_0040
+	RESOURCE 0x2000_0000
+	LOGON_ID 0xc000_0000
+}
+
+const_ordinary! { SE_OBJECT_TYPE: u32;
+	/// [`SE_OBJECT_TYPE`](https://learn.microsoft.com/en-us/windows/win32/api/accctrl/ne-accctrl-se_object_type)
+	/// enumeration (`u32`).
+	///
+	/// Originally has `SE_` prefix.
+	=>
+	UNKNOWN_OBJECT_TYPE 0
+	FILE_OBJECT 1
+	SERVICE 2
+	PRINTER 3
+	REGISTRY_KEY 4
+	LMSHARE 5
+	KERNEL_OBJECT 6
+	WINDOW_OBJECT 7
+	DS_OBJECT 8
+	DS_OBJECT_ALL 9
+	PROVIDER_DEFINED_OBJECT 10
+	WMIGUID_OBJECT 11
+	REGISTRY_WOW64_32KEY 12
+	REGISTRY_WOW64_64KEY 13
+}
+
 const_bitflag! { SE_PRIV_ATTR: u32;
 	/// [Privilege attributes](https://learn.microsoft.com/en-us/windows/win32/api/securitybaseapi/nf-securitybaseapi-adjusttokenprivileges)
 	/// (`u32`).
@@ -429,6 +668,33 @@ const_bitflag! { SERVICE_CONTROL: u32;
 	USERMODEREBOOT 0x0000_0040
 }
 
+const_ordinary! { SERVICE_CONFIG: u32;
+	/// [`HSERVICE::QueryServiceConfig2`](crate::prelude::advapi_Hservice::QueryServiceConfig2)
+	/// `info_level` (`u32`).
+	=>
+	DESCRIPTION 1
+	FAILURE_ACTIONS 2
+	DELAYED_AUTO_START_INFO 3
+	FAILURE_ACTIONS_FLAG 4
+	SERVICE_SID_INFO 5
+	REQUIRED_PRIVILEGES_INFO 6
+	PRESHUTDOWN_INFO 7
+	TRIGGER_INFO 8
+}
+
+const_ordinary! { SERVICE_ENUM_STATE: u32;
+	/// [`HSC::EnumServicesStatusEx`](crate::prelude::advapi_Hsc::EnumServicesStatusEx)
+	/// and
+	/// [`HSERVICE::EnumDependentServices`](crate::prelude::advapi_Hservice::EnumDependentServices)
+	/// `service_state` (`u32`).
+	///
+	/// Originally `SERVICE_ACTIVE`, `SERVICE_INACTIVE` and `SERVICE_STATE_ALL`.
+	=>
+	ACTIVE 0x0000_0001
+	INACTIVE 0x0000_0002
+	ALL 0x0000_0003
+}
+
 const_ordinary! { SERVICE_ERROR: u32;
 	/// [`HSC::CreateService`](crate::prelude::advapi_Hsc::CreateService)
 	/// `error_control` (`u32`).
@@ -439,6 +705,15 @@ const_ordinary! { SERVICE_ERROR: u32;
 	SEVERE 0x0000_0002
 }
 
+const_ordinary! { SERVICE_SID_TYPE: u32;
+	/// [`SERVICE_SID_INFO`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_sid_info)
+	/// `dwServiceSidType` (`u32`).
+	=>
+	NONE 0x0000_0000
+	UNRESTRICTED 0x0000_0001
+	RESTRICTED 0x0000_0003
+}
+
 const_ordinary! { SERVICE_START: u32;
 	/// [`HSC::CreateService`](crate::prelude::advapi_Hsc::CreateService)
 	/// `start_type` (`u32`).
@@ -465,6 +740,29 @@ const_ordinary! { SERVICE_STATE: u32;
 	STOPPED 0x0000_0001
 }
 
+const_ordinary! { SERVICE_TRIGGER_ACTION: u32;
+	/// [`SERVICE_TRIGGER`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_trigger)
+	/// `dwAction` (`u32`).
+	=>
+	SERVICE_START 1
+	SERVICE_STOP 2
+}
+
+const_ordinary! { SERVICE_TRIGGER_TYPE: u32;
+	/// [`SERVICE_TRIGGER`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_trigger)
+	/// `dwTriggerType` (`u32`).
+	=>
+	DEVICE_INTERFACE_ARRIVAL 1
+	IP_ADDRESS_AVAILABILITY 2
+	DOMAIN_JOIN 3
+	FIREWALL_PORT_EVENT 4
+	GROUP_POLICY 5
+	NETWORK_ENDPOINT 6
+	CUSTOM_SYSTEM_STATE_CHANGE 7
+	CUSTOM 20
+	AGGREGATE 30
+}
+
 const_ordinary! { SERVICE_TYPE: u32;
 	/// [`HSC::CreateService`](crate::prelude::advapi_Hsc::CreateService)
 	/// `service_type` (`u32`).
@@ -478,6 +776,8 @@ const_ordinary! { SERVICE_TYPE: u32;
 	WIN32_OWN_PROCESS 0x0000_0010
 	WIN32_SHARE_PROCESS 0x0000_0020
 
+	DRIVER Self::KERNEL_DRIVER.0 | Self::FILE_SYSTEM_DRIVER.0 | Self::RECOGNIZER_DRIVER.0
+	WIN32 Self::WIN32_OWN_PROCESS.0 | Self::WIN32_SHARE_PROCESS.0
 	WIN32_OWN_PROCESS_INTERACTIVE Self::WIN32_OWN_PROCESS.0 | 0x0000_0100
 	WIN32_SHARE_PROCESS_INTERACTIVE Self::WIN32_SHARE_PROCESS.0 | 0x0000_0100
 }
@@ -726,3 +1026,17 @@ const_ordinary! { WELL_KNOWN_SID_TYPE: u32;
 	AuthenticationFreshKeyAuth 118
 	BuiltinDeviceOwners 119
 }
+
+const_ordinary! { WINEVENT_LEVEL: u8;
+	/// [Event level](https://learn.microsoft.com/en-us/windows/win32/wes/eventmanifestschema-leveltype-complextype)
+	/// of the [`EvtSystemProps`](crate::EvtSystemProps) (`u8`).
+	///
+	/// Originally has `WINEVENT_LEVEL_` prefix.
+	=>
+	LOG_ALWAYS 0
+	CRITICAL 1
+	ERROR 2
+	WARNING 3
+	INFO 4
+	VERBOSE 5
+}
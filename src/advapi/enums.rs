@@ -16,7 +16,7 @@ pub enum DisabPriv<'a> {
 ///
 /// This is a high-level abstraction over the [`co::REG`](crate::co::REG)
 /// constants, plus the value they carry.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum RegistryValue {
 	/// Binary value, defined as [`REG::BINARY`](crate::co::REG::BINARY).
 	Binary(Vec<u8>),
@@ -310,7 +310,8 @@ impl<'a> SvcCtlPowerEvent<'a> {
 
 /// Variant parameter for:
 ///
-/// * [`HACCESSTOKEN::GetTokenInformation`](crate::prelude::advapi_Haccesstoken::GetTokenInformation).
+/// * [`HACCESSTOKEN::GetTokenInformation`](crate::prelude::advapi_Haccesstoken::GetTokenInformation);
+/// * [`HACCESSTOKEN::SetTokenInformation`](crate::prelude::advapi_Haccesstoken::SetTokenInformation).
 ///
 /// The enum values match those in
 /// [`co::TOKEN_INFORMATION_CLASS`](crate::co::TOKEN_INFORMATION_CLASS) constant
@@ -3,12 +3,24 @@ use crate::kernel::ffi_types::*;
 extern_sys! { "advapi32";
 	AdjustTokenPrivileges(HANDLE, BOOL, PCVOID, u32, PVOID, *mut u32) -> BOOL
 	AllocateAndInitializeSid(PCVOID, u8, u32, u32, u32, u32, u32, u32, u32, u32, *mut u8) -> BOOL
+	BackupEventLogW(HANDLE, PCSTR) -> BOOL
+	ChangeServiceConfig2W(HANDLE, u32, PCVOID) -> BOOL
+	ChangeServiceConfigW(HANDLE, u32, u32, u32, PCSTR, PCSTR, *mut u32, PCSTR, PCSTR, PCSTR, PCSTR) -> BOOL
 	CheckTokenCapability(HANDLE, PCVOID, *mut BOOL) -> BOOL
 	CheckTokenMembership(HANDLE, PCVOID, *mut BOOL) -> BOOL
+	ClearEventLogW(HANDLE, PCSTR) -> BOOL
+	CloseEventLog(HANDLE) -> BOOL
 	CloseServiceHandle(HANDLE) -> BOOL
+	ControlService(HANDLE, u32, PVOID) -> BOOL
+	ControlServiceExW(HANDLE, u32, u32, PVOID) -> BOOL
+	ConvertSecurityDescriptorToStringSecurityDescriptorW(PCVOID, u32, u32, *mut PSTR, *mut u32) -> BOOL
 	ConvertSidToStringSidW(PCVOID, *mut PSTR) -> BOOL
+	ConvertStringSecurityDescriptorToSecurityDescriptorW(PCSTR, u32, *mut PVOID, *mut u32) -> BOOL
 	ConvertStringSidToSidW(PCSTR, *mut *mut u8) -> BOOL
 	CopySid(u32, PVOID, PCVOID) -> BOOL
+	CreateProcessAsUserW(HANDLE, PCSTR, PSTR, PVOID, PVOID, BOOL, u32, PVOID, PCSTR, PVOID, PVOID) -> BOOL
+	CreateProcessWithTokenW(HANDLE, u32, PCSTR, PSTR, u32, PVOID, PCSTR, PVOID, PVOID) -> BOOL
+	CreateRestrictedToken(HANDLE, u32, u32, PCVOID, u32, PCVOID, u32, PCVOID, *mut HANDLE) -> BOOL
 	CreateServiceW(HANDLE, PCSTR, PCSTR, u32, u32, u32, u32, PCSTR, PCSTR, *mut u32, PCSTR, PCSTR, PCSTR) -> HANDLE
 	CreateWellKnownSid(u32, PCVOID, PVOID, *mut u32) -> BOOL
 	DecryptFileW(PCSTR, u32) -> BOOL
@@ -17,11 +29,17 @@ extern_sys! { "advapi32";
 	DuplicateToken(HANDLE, u32, *mut HANDLE) -> BOOL
 	EncryptFileW(PCSTR) -> BOOL
 	EncryptionDisable(PCSTR, BOOL) -> BOOL
+	EnumDependentServicesW(HANDLE, u32, PVOID, u32, *mut u32, *mut u32) -> BOOL
+	EnumServicesStatusExW(HANDLE, u32, u32, u32, PVOID, u32, *mut u32, *mut u32, *mut u32, PCSTR) -> BOOL
 	EqualDomainSid(PVOID, PVOID, *mut BOOL) -> BOOL
 	EqualPrefixSid(PVOID, PVOID) -> BOOL
 	EqualSid(PVOID, PVOID) -> BOOL
 	FreeSid(PVOID)
 	GetLengthSid(PVOID) -> u32
+	GetNamedSecurityInfoW(PCSTR, u32, u32, *mut PVOID, *mut PVOID, *mut PVOID, *mut PVOID, *mut PVOID) -> u32
+	GetNumberOfEventLogRecords(HANDLE, *mut u32) -> BOOL
+	GetOldestEventLogRecord(HANDLE, *mut u32) -> BOOL
+	GetSecurityDescriptorLength(PCVOID) -> u32
 	GetSidLengthRequired(u8) -> u32
 	GetTokenInformation(HANDLE, u32, PCVOID, u32, *mut u32) -> BOOL
 	GetUserNameW(PSTR, *mut u32) -> BOOL
@@ -34,14 +52,20 @@ extern_sys! { "advapi32";
 	IsValidSecurityDescriptor(PCVOID) -> BOOL
 	IsValidSid(PVOID) -> BOOL
 	IsWellKnownSid(PVOID, u32) -> BOOL
+	LogonUserW(PCSTR, PCSTR, PCSTR, u32, u32, *mut HANDLE) -> BOOL
 	LookupAccountNameW(PCSTR, PCSTR, PVOID, *mut u32, PSTR, *mut u32, *mut u32) -> BOOL
 	LookupAccountSidW(PCSTR, PCVOID, PSTR, *mut u32, PSTR, *mut u32, *mut u32) -> BOOL
 	LookupPrivilegeNameW(PCSTR, PCVOID, PSTR, *mut u32) -> BOOL
 	LookupPrivilegeValueW(PCSTR, PCSTR, PVOID) -> BOOL
+	OpenEventLogW(PCSTR, PCSTR) -> HANDLE
 	OpenProcessToken(HANDLE, u32, *mut HANDLE) -> BOOL
 	OpenSCManagerW(PCSTR, PCSTR, u32) -> HANDLE
 	OpenServiceW(HANDLE, PCSTR, u32) -> HANDLE
 	OpenThreadToken(HANDLE, u32, BOOL, *mut HANDLE) -> BOOL
+	QueryServiceConfig2W(HANDLE, u32, PVOID, u32, *mut u32) -> BOOL
+	QueryServiceConfigW(HANDLE, PVOID, u32, *mut u32) -> BOOL
+	QueryServiceStatusEx(HANDLE, u32, PVOID, u32, *mut u32) -> BOOL
+	ReadEventLogW(HANDLE, u32, u32, PVOID, u32, *mut u32, *mut u32) -> BOOL
 	RegCloseKey(HANDLE) -> i32
 	RegConnectRegistryW(PCSTR, HANDLE, *mut HANDLE) -> i32
 	RegCopyTreeW(HANDLE, PCSTR, HANDLE) -> i32
@@ -63,6 +87,7 @@ extern_sys! { "advapi32";
 	RegisterEventSourceW(PCSTR, PCSTR) -> HANDLE
 	RegisterServiceCtrlHandlerExW(PCSTR, PFUNC, PVOID) -> HANDLE
 	RegLoadKeyW(HANDLE, PCSTR, PCSTR) -> i32
+	RegNotifyChangeKeyValue(HANDLE, BOOL, u32, HANDLE, BOOL) -> i32
 	RegOpenCurrentUser(u32, *mut HANDLE) -> i32
 	RegOpenKeyExW(HANDLE, PCSTR, u32, u32, *mut HANDLE) -> i32
 	RegOpenKeyTransactedW(HANDLE, PCSTR, u32, u32, *mut HANDLE, HANDLE, PVOID) -> i32
@@ -79,7 +104,12 @@ extern_sys! { "advapi32";
 	RegSetValueExW(HANDLE, PCSTR, u32, u32, *const u8, u32) -> i32
 	RegUnLoadKeyW(HANDLE, PCSTR) -> i32
 	ReportEventW(HANDLE, u16, u16, u32, PCVOID, u16, u32, *const PCSTR, PCVOID) -> BOOL
+	RevertToSelf() -> BOOL
+	SetNamedSecurityInfoW(PCSTR, u32, u32, PCVOID, PCVOID, PCVOID, PCVOID) -> u32
 	SetServiceStatus(HANDLE, PCVOID) -> BOOL
+	SetTokenInformation(HANDLE, u32, PCVOID, u32) -> BOOL
+	StartServiceCtrlDispatcherW(PCVOID) -> BOOL
+	StartServiceW(HANDLE, u32, *const PCSTR) -> BOOL
 }
 
 extern_sys! { "ktmw32";
@@ -89,3 +119,16 @@ extern_sys! { "ktmw32";
 	OpenTransaction(u32, PVOID) -> HANDLE
 	RollbackTransaction(HANDLE) -> BOOL
 }
+
+extern_sys! { "wevtapi";
+	EvtClose(HANDLE) -> BOOL
+	EvtCreateBookmark(PCSTR) -> HANDLE
+	EvtCreateRenderContext(u32, *const PCSTR, u32) -> HANDLE
+	EvtFormatMessage(HANDLE, HANDLE, u32, u32, PCVOID, u32, u32, PSTR, *mut u32) -> BOOL
+	EvtNext(HANDLE, u32, *mut HANDLE, u32, u32, *mut u32) -> BOOL
+	EvtOpenPublisherMetadata(HANDLE, PCSTR, PCSTR, u32, u32) -> HANDLE
+	EvtQuery(HANDLE, PCSTR, PCSTR, u32) -> HANDLE
+	EvtRender(HANDLE, HANDLE, u32, u32, PVOID, *mut u32, *mut u32) -> BOOL
+	EvtSubscribe(HANDLE, HANDLE, PCSTR, PCSTR, HANDLE, PVOID, PVOID, u32) -> HANDLE
+	EvtUpdateBookmark(HANDLE, HANDLE) -> BOOL
+}
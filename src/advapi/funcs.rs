@@ -93,6 +93,37 @@ pub fn AllocateAndInitializeSid(
 	}
 }
 
+/// [`ConvertSecurityDescriptorToStringSecurityDescriptor`](https://learn.microsoft.com/en-us/windows/win32/api/sddl/nf-sddl-convertsecuritydescriptortostringsecuritydescriptorw)
+/// function.
+///
+/// Only the parts specified in `info` are converted.
+///
+/// # Related functions
+///
+/// * [`ConvertStringSecurityDescriptorToSecurityDescriptor`](crate::ConvertStringSecurityDescriptorToSecurityDescriptor)
+/// * [`GetNamedSecurityInfo`](crate::GetNamedSecurityInfo)
+/// * [`SetNamedSecurityInfo`](crate::SetNamedSecurityInfo)
+#[must_use]
+pub fn ConvertSecurityDescriptorToStringSecurityDescriptor(
+	sd: &SecurityDescriptor,
+	info: co::SECURITY_INFORMATION,
+) -> SysResult<String> {
+	let (buf, _) = sd.serialize_aligned()?;
+	let mut pstr: *mut u16 = std::ptr::null_mut();
+	bool_to_sysresult(unsafe {
+		ffi::ConvertSecurityDescriptorToStringSecurityDescriptorW(
+			buf.as_ptr() as _,
+			SDDL_REVISION_1,
+			info.raw(),
+			&mut pstr,
+			std::ptr::null_mut(),
+		)
+	})?;
+	let sddl = unsafe { WString::from_wchars_nullt(pstr) }.to_string();
+	let _ = unsafe { LocalFreeGuard::new(HLOCAL::from_ptr(pstr as _)) }; // free returned pointer
+	Ok(sddl)
+}
+
 /// [`ConvertSidToStringSid`](https://learn.microsoft.com/en-us/windows/win32/api/sddl/nf-sddl-convertsidtostringsidw)
 /// function.
 ///
@@ -125,6 +156,34 @@ pub fn ConvertSidToStringSid(sid: &SID) -> SysResult<String> {
 	Ok(name)
 }
 
+/// [`ConvertStringSecurityDescriptorToSecurityDescriptor`](https://learn.microsoft.com/en-us/windows/win32/api/sddl/nf-sddl-convertstringsecuritydescriptortosecuritydescriptorw)
+/// function.
+///
+/// # Related functions
+///
+/// * [`ConvertSecurityDescriptorToStringSecurityDescriptor`](crate::ConvertSecurityDescriptorToStringSecurityDescriptor)
+/// * [`GetNamedSecurityInfo`](crate::GetNamedSecurityInfo)
+/// * [`SetNamedSecurityInfo`](crate::SetNamedSecurityInfo)
+#[must_use]
+pub fn ConvertStringSecurityDescriptorToSecurityDescriptor(
+	sddl: &str,
+) -> SysResult<SecurityDescriptor> {
+	let mut psd = std::ptr::null_mut();
+	let mut sd_sz = u32::default();
+	bool_to_sysresult(unsafe {
+		ffi::ConvertStringSecurityDescriptorToSecurityDescriptorW(
+			WString::from_str(sddl).as_ptr(),
+			SDDL_REVISION_1,
+			&mut psd,
+			&mut sd_sz,
+		)
+	})?;
+	let _guard = unsafe { LocalFreeGuard::new(HLOCAL::from_ptr(psd)) }; // free returned pointer
+	SecurityDescriptor::from_bytes(unsafe {
+		std::slice::from_raw_parts(psd as *const u8, sd_sz as _)
+	})
+}
+
 /// [`ConvertStringSidToSid`](https://learn.microsoft.com/en-us/windows/win32/api/sddl/nf-sddl-convertstringsidtosidw)
 /// function.
 ///
@@ -400,6 +459,58 @@ pub fn GetLengthSid(sid: &SID) -> u32 {
 	unsafe { ffi::GetLengthSid(sid as *const _ as _) }
 }
 
+/// [`GetNamedSecurityInfo`](https://learn.microsoft.com/en-us/windows/win32/api/aclapi/nf-aclapi-getnamedsecurityinfow)
+/// function.
+///
+/// The `object_name` format depends on `object_type`, as described in
+/// [`SE_OBJECT_TYPE`](https://learn.microsoft.com/en-us/windows/win32/api/accctrl/ne-accctrl-se_object_type):
+/// a file path, a registry key like `MACHINE\\SOFTWARE\\MyApp`, or a
+/// service name.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let sd = w::GetNamedSecurityInfo(
+///     "C:\\Temp\\foo.txt",
+///     co::SE_OBJECT_TYPE::FILE_OBJECT,
+///     co::SECURITY_INFORMATION::OWNER | co::SECURITY_INFORMATION::DACL,
+/// )?;
+/// println!("{}", sd.to_sddl(co::SECURITY_INFORMATION::OWNER | co::SECURITY_INFORMATION::DACL)?);
+/// # w::SysResult::Ok(())
+/// ```
+///
+/// # Related functions
+///
+/// * [`ConvertSecurityDescriptorToStringSecurityDescriptor`](crate::ConvertSecurityDescriptorToStringSecurityDescriptor)
+/// * [`ConvertStringSecurityDescriptorToSecurityDescriptor`](crate::ConvertStringSecurityDescriptorToSecurityDescriptor)
+/// * [`SetNamedSecurityInfo`](crate::SetNamedSecurityInfo)
+#[must_use]
+pub fn GetNamedSecurityInfo(
+	object_name: &str,
+	object_type: co::SE_OBJECT_TYPE,
+	info: co::SECURITY_INFORMATION,
+) -> SysResult<SecurityDescriptor> {
+	let mut psd = std::ptr::null_mut();
+	error_to_sysresult(unsafe {
+		ffi::GetNamedSecurityInfoW(
+			WString::from_str(object_name).as_ptr(),
+			object_type.raw(),
+			info.raw(),
+			std::ptr::null_mut(),
+			std::ptr::null_mut(),
+			std::ptr::null_mut(),
+			std::ptr::null_mut(),
+			&mut psd,
+		)
+	} as _)?;
+	let _guard = unsafe { LocalFreeGuard::new(HLOCAL::from_ptr(psd)) }; // free returned pointer
+	SecurityDescriptor::from_bytes(unsafe {
+		std::slice::from_raw_parts(psd as *const u8, ffi::GetSecurityDescriptorLength(psd) as _)
+	})
+}
+
 /// [`GetSidLengthRequired`](https://learn.microsoft.com/en-us/windows/win32/api/securitybaseapi/nf-securitybaseapi-getsidlengthrequired)
 /// function.
 ///
@@ -804,3 +915,64 @@ pub fn RegDisablePredefinedCache() -> SysResult<()> {
 pub fn RegDisablePredefinedCacheEx() -> SysResult<()> {
 	error_to_sysresult(unsafe { ffi::RegDisablePredefinedCacheEx() })
 }
+
+/// [`SetNamedSecurityInfo`](https://learn.microsoft.com/en-us/windows/win32/api/aclapi/nf-aclapi-setnamedsecurityinfow)
+/// function.
+///
+/// Only the parts of `sd` specified in `info` are set. If `info` contains
+/// [`SECURITY_INFORMATION::DACL`](crate::co::SECURITY_INFORMATION::DACL) and
+/// the DACL is `None`, fails with
+/// [`ERROR::INVALID_SECURITY_DESCR`](crate::co::ERROR::INVALID_SECURITY_DESCR),
+/// unless `control` has [`SE::DACL_PRESENT`](crate::co::SE::DACL_PRESENT): then
+/// a NULL DACL is set, granting full access to everyone.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let sd = w::SecurityDescriptor::from_sddl("D:P(A;OICI;FA;;;SY)(A;OICI;FA;;;BA)")?;
+/// w::SetNamedSecurityInfo(
+///     "C:\\Temp\\secrets",
+///     co::SE_OBJECT_TYPE::FILE_OBJECT,
+///     co::SECURITY_INFORMATION::DACL | co::SECURITY_INFORMATION::PROTECTED_DACL,
+///     &sd,
+/// )?;
+/// # w::SysResult::Ok(())
+/// ```
+///
+/// # Related functions
+///
+/// * [`ConvertSecurityDescriptorToStringSecurityDescriptor`](crate::ConvertSecurityDescriptorToStringSecurityDescriptor)
+/// * [`ConvertStringSecurityDescriptorToSecurityDescriptor`](crate::ConvertStringSecurityDescriptorToSecurityDescriptor)
+/// * [`GetNamedSecurityInfo`](crate::GetNamedSecurityInfo)
+pub fn SetNamedSecurityInfo(
+	object_name: &str,
+	object_type: co::SE_OBJECT_TYPE,
+	info: co::SECURITY_INFORMATION,
+	sd: &SecurityDescriptor,
+) -> SysResult<()> {
+	if info.has(co::SECURITY_INFORMATION::DACL)
+		&& sd.dacl.is_none()
+		&& !sd.control.has(co::SE::DACL_PRESENT)
+	{
+		return Err(co::ERROR::INVALID_SECURITY_DESCR); // NULL DACL must be explicit
+	}
+
+	let (buf, offsets) = sd.serialize_aligned()?;
+	let ptr_at = |off: usize| match off {
+		0 => std::ptr::null(),
+		off => unsafe { (buf.as_ptr() as *const u8).add(off) as *const std::ffi::c_void },
+	};
+	error_to_sysresult(unsafe {
+		ffi::SetNamedSecurityInfoW(
+			WString::from_str(object_name).as_ptr(),
+			object_type.raw(),
+			info.raw(),
+			ptr_at(offsets[0]),
+			ptr_at(offsets[1]),
+			ptr_at(offsets[3]),
+			ptr_at(offsets[2]),
+		)
+	} as _)
+}
@@ -7,6 +7,14 @@ use crate::decl::*;
 use crate::guard::*;
 use crate::prelude::*;
 
+handle_guard! { CloseEventLogGuard: HEVENTLOG;
+	ffi::CloseEventLog;
+	/// RAII implementation for [`HEVENTLOG`](crate::HEVENTLOG) which
+	/// automatically calls
+	/// [`CloseEventLog`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-closeeventlog)
+	/// when the object goes out of scope.
+}
+
 handle_guard! { CloseServiceHandleGuard: HSC;
 	ffi::CloseServiceHandle;
 	/// RAII implementation for [`HSC`](crate::HSC) which automatically calls
@@ -30,6 +38,13 @@ handle_guard! { DeregisterEventSourceGuard: HEVENTLOG;
 	/// when the object goes out of scope.
 }
 
+handle_guard! { EvtCloseGuard: HEVT;
+	ffi::EvtClose;
+	/// RAII implementation for [`HEVT`](crate::HEVT) which automatically calls
+	/// [`EvtClose`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/nf-winevt-evtclose)
+	/// when the object goes out of scope.
+}
+
 /// RAII implementation for [`SID`](crate::SID), returned by
 /// [`AllocateAndInitializeSid`](crate::AllocateAndInitializeSid), which
 /// automatically calls
@@ -145,6 +160,39 @@ impl RegCloseKeyGuard {
 	}
 }
 
+/// RAII implementation which automatically calls
+/// [`RevertToSelf`](https://learn.microsoft.com/en-us/windows/win32/api/securitybaseapi/nf-securitybaseapi-reverttoself)
+/// when the object goes out of scope, ending the impersonation of the current
+/// thread.
+///
+/// Returned by
+/// [`HACCESSTOKEN::impersonate`](crate::prelude::advapi_Haccesstoken::impersonate).
+pub struct RevertToSelfGuard {
+	_thread: PhantomData<*mut ()>, // impersonation is per-thread, so the guard is !Send
+}
+
+impl Drop for RevertToSelfGuard {
+	fn drop(&mut self) {
+		unsafe {
+			ffi::RevertToSelf(); // ignore errors
+		}
+	}
+}
+
+impl RevertToSelfGuard {
+	/// Constructs the guard.
+	///
+	/// # Safety
+	///
+	/// Be sure you need to call
+	/// [`RevertToSelf`](https://learn.microsoft.com/en-us/windows/win32/api/securitybaseapi/nf-securitybaseapi-reverttoself)
+	/// at the end of scope.
+	#[must_use]
+	pub const unsafe fn new() -> Self {
+		Self { _thread: PhantomData }
+	}
+}
+
 /// RAII implementation for [`SID`](crate::SID), returned by
 /// [`ConvertStringSidToSid`](crate::ConvertStringSidToSid), which automatically
 /// calls
@@ -199,6 +247,12 @@ impl Deref for SidGuard {
 	}
 }
 
+impl DerefMut for SidGuard {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		unsafe { &mut *(self.ptr.ptr() as *mut _) }
+	}
+}
+
 impl std::fmt::Display for SidGuard {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		std::fmt::Display::fmt(self.deref(), f) // delegate to the underlying SID
@@ -24,6 +24,110 @@ impl advapi_Haccesstoken for HACCESSTOKEN {}
 /// use winsafe::prelude::*;
 /// ```
 pub trait advapi_Haccesstoken: Handle {
+	/// Makes the current thread impersonate the user of this token, by calling
+	/// [`ImpersonateLoggedOnUser`](crate::prelude::advapi_Haccesstoken::ImpersonateLoggedOnUser).
+	///
+	/// In the original C implementation, you must call
+	/// [`RevertToSelf`](https://learn.microsoft.com/en-us/windows/win32/api/securitybaseapi/nf-securitybaseapi-reverttoself)
+	/// to end the impersonation; here this is done automatically by the
+	/// returned guard.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let htoken = w::HACCESSTOKEN::LogonUser(
+	///     "converter",
+	///     Some("."),
+	///     Some("password"),
+	///     co::LOGON32_LOGON::INTERACTIVE,
+	///     co::LOGON32_PROVIDER::DEFAULT,
+	/// )?;
+	///
+	/// {
+	///     let _impersonation = htoken.impersonate()?;
+	///     println!("{}", w::GetUserName()?);
+	/// } // RevertToSelf called here
+	/// # w::SysResult::Ok(())
+	/// ```
+	#[must_use]
+	fn impersonate(&self) -> SysResult<RevertToSelfGuard> {
+		self.ImpersonateLoggedOnUser()
+			.map(|_| unsafe { RevertToSelfGuard::new() })
+	}
+
+	/// Spawns a new process with a primary token derived from this one, which
+	/// can have its privileges stripped and its integrity level lowered.
+	///
+	/// The token is created with
+	/// [`CreateRestrictedToken`](crate::prelude::advapi_Haccesstoken::CreateRestrictedToken),
+	/// its integrity level set with
+	/// [`SetTokenInformation`](crate::prelude::advapi_Haccesstoken::SetTokenInformation),
+	/// and the process is created with
+	/// [`CreateProcessAsUser`](crate::prelude::advapi_Haccesstoken::CreateProcessAsUser).
+	///
+	/// This token must have been opened with
+	/// [`TOKEN::DUPLICATE`](crate::co::TOKEN::DUPLICATE),
+	/// [`TOKEN::QUERY`](crate::co::TOKEN::QUERY),
+	/// [`TOKEN::ASSIGN_PRIMARY`](crate::co::TOKEN::ASSIGN_PRIMARY) and
+	/// [`TOKEN::ADJUST_DEFAULT`](crate::co::TOKEN::ADJUST_DEFAULT) access
+	/// rights.
+	///
+	/// # Examples
+	///
+	/// Running a child process at Low integrity, with all privileges removed,
+	/// except `SeChangeNotifyPrivilege`:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let htoken = w::HPROCESS::GetCurrentProcess().OpenProcessToken(
+	///     co::TOKEN::DUPLICATE
+	///         | co::TOKEN::QUERY
+	///         | co::TOKEN::ASSIGN_PRIMARY
+	///         | co::TOKEN::ADJUST_DEFAULT,
+	/// )?;
+	///
+	/// let pi = htoken.spawn_restricted(
+	///     "converter.exe in.docx out.pdf",
+	///     co::CREATE_RESTRICTED_TOKEN::DISABLE_MAX_PRIVILEGE,
+	///     Some(co::WELL_KNOWN_SID_TYPE::LowLabel),
+	/// )?;
+	///
+	/// pi.hProcess.WaitForSingleObject(None)?;
+	/// # w::SysResult::Ok(())
+	/// ```
+	#[must_use]
+	fn spawn_restricted(
+		&self,
+		command_line: &str,
+		flags: co::CREATE_RESTRICTED_TOKEN,
+		integrity_level: Option<co::WELL_KNOWN_SID_TYPE>,
+	) -> SysResult<CloseHandlePiGuard> {
+		let htoken = self.CreateRestrictedToken(flags, &[], &[], &[])?;
+
+		if let Some(integrity_level) = integrity_level {
+			let mut sid = CreateWellKnownSid(integrity_level, None)?;
+			let mut tml = TOKEN_MANDATORY_LABEL::default();
+			tml.Label.set_Sid(Some(&mut sid));
+			tml.Label.Attributes = co::SE_GROUP::INTEGRITY.raw();
+			htoken.SetTokenInformation(&TokenInfo::IntegrityLevel(Box::new(tml)))?;
+		}
+
+		htoken.CreateProcessAsUser(
+			None,
+			Some(command_line),
+			None,
+			None,
+			false,
+			co::CREATE::NoValue,
+			None,
+			None,
+			&mut STARTUPINFO::default(),
+		)
+	}
+
 	/// [`AdjustTokenPrivileges`](https://learn.microsoft.com/en-us/windows/win32/api/securitybaseapi/nf-securitybaseapi-adjusttokenprivileges)
 	/// function.
 	///
@@ -89,6 +193,141 @@ pub trait advapi_Haccesstoken: Handle {
 		.map(|_| is_member != 0)
 	}
 
+	/// [`CreateProcessAsUser`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-createprocessasuserw)
+	/// function.
+	///
+	/// If `environment` is passed,
+	/// [`CREATE::UNICODE_ENVIRONMENT`](crate::co::CREATE::UNICODE_ENVIRONMENT)
+	/// is automatically added to `creation_flags`.
+	#[allow(clippy::too_many_arguments)]
+	#[must_use]
+	fn CreateProcessAsUser(
+		&self,
+		application_name: Option<&str>,
+		command_line: Option<&str>,
+		process_attrs: Option<&mut SECURITY_ATTRIBUTES>,
+		thread_attrs: Option<&mut SECURITY_ATTRIBUTES>,
+		inherit_handles: bool,
+		creation_flags: co::CREATE,
+		environment: Option<Vec<(&str, &str)>>,
+		current_dir: Option<&str>,
+		si: &mut STARTUPINFO,
+	) -> SysResult<CloseHandlePiGuard> {
+		let mut buf_cmd_line = WString::from_opt_str(command_line);
+		let buf_env = environment_block(environment.as_deref());
+		let mut pi = PROCESS_INFORMATION::default();
+
+		unsafe {
+			bool_to_sysresult(ffi::CreateProcessAsUserW(
+				self.ptr(),
+				WString::from_opt_str(application_name).as_ptr(),
+				buf_cmd_line.as_mut_ptr(),
+				process_attrs.map_or(std::ptr::null_mut(), |lp| lp as *mut _ as _),
+				thread_attrs.map_or(std::ptr::null_mut(), |lp| lp as *mut _ as _),
+				inherit_handles as _,
+				environment_flags(creation_flags, &buf_env).raw(),
+				buf_env
+					.as_ref()
+					.map_or(std::ptr::null_mut(), |buf| buf.as_ptr() as _),
+				WString::from_opt_str(current_dir).as_ptr(),
+				si as *mut _ as _,
+				&mut pi as *mut _ as _,
+			))
+			.map(|_| CloseHandlePiGuard::new(pi))
+		}
+	}
+
+	/// [`CreateProcessWithToken`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-createprocesswithtokenw)
+	/// function.
+	///
+	/// If `environment` is passed,
+	/// [`CREATE::UNICODE_ENVIRONMENT`](crate::co::CREATE::UNICODE_ENVIRONMENT)
+	/// is automatically added to `creation_flags`.
+	#[allow(clippy::too_many_arguments)]
+	#[must_use]
+	fn CreateProcessWithToken(
+		&self,
+		logon_flags: co::LOGON,
+		application_name: Option<&str>,
+		command_line: Option<&str>,
+		creation_flags: co::CREATE,
+		environment: Option<Vec<(&str, &str)>>,
+		current_dir: Option<&str>,
+		si: &mut STARTUPINFO,
+	) -> SysResult<CloseHandlePiGuard> {
+		let mut buf_cmd_line = WString::from_opt_str(command_line);
+		let buf_env = environment_block(environment.as_deref());
+		let mut pi = PROCESS_INFORMATION::default();
+
+		unsafe {
+			bool_to_sysresult(ffi::CreateProcessWithTokenW(
+				self.ptr(),
+				logon_flags.raw(),
+				WString::from_opt_str(application_name).as_ptr(),
+				buf_cmd_line.as_mut_ptr(),
+				environment_flags(creation_flags, &buf_env).raw(),
+				buf_env
+					.as_ref()
+					.map_or(std::ptr::null_mut(), |buf| buf.as_ptr() as _),
+				WString::from_opt_str(current_dir).as_ptr(),
+				si as *mut _ as _,
+				&mut pi as *mut _ as _,
+			))
+			.map(|_| CloseHandlePiGuard::new(pi))
+		}
+	}
+
+	/// [`CreateRestrictedToken`](https://learn.microsoft.com/en-us/windows/win32/api/securitybaseapi/nf-securitybaseapi-createrestrictedtoken)
+	/// function.
+	///
+	/// # Examples
+	///
+	/// Creating a token with the Administrators group set to deny-only:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let htoken = w::HPROCESS::GetCurrentProcess()
+	///     .OpenProcessToken(co::TOKEN::DUPLICATE | co::TOKEN::QUERY)?;
+	///
+	/// let mut admins = w::CreateWellKnownSid(
+	///     co::WELL_KNOWN_SID_TYPE::BuiltinAdministrators, None)?;
+	/// let mut sa = w::SID_AND_ATTRIBUTES::default();
+	/// sa.set_Sid(Some(&mut admins));
+	///
+	/// let hrestricted = htoken.CreateRestrictedToken(
+	///     co::CREATE_RESTRICTED_TOKEN::DISABLE_MAX_PRIVILEGE,
+	///     &[sa],
+	///     &[],
+	///     &[],
+	/// )?;
+	/// # w::SysResult::Ok(())
+	/// ```
+	#[must_use]
+	fn CreateRestrictedToken(
+		&self,
+		flags: co::CREATE_RESTRICTED_TOKEN,
+		sids_to_disable: &[SID_AND_ATTRIBUTES],
+		privileges_to_delete: &[LUID_AND_ATTRIBUTES],
+		sids_to_restrict: &[SID_AND_ATTRIBUTES],
+	) -> SysResult<CloseHandleGuard<HACCESSTOKEN>> {
+		let mut handle = HACCESSTOKEN::NULL;
+		unsafe {
+			bool_to_sysresult(ffi::CreateRestrictedToken(
+				self.ptr(),
+				flags.raw(),
+				sids_to_disable.len() as _,
+				vec_ptr(sids_to_disable) as _,
+				privileges_to_delete.len() as _,
+				vec_ptr(privileges_to_delete) as _,
+				sids_to_restrict.len() as _,
+				vec_ptr(sids_to_restrict) as _,
+				handle.as_mut(),
+			))
+			.map(|_| CloseHandleGuard::new(handle))
+		}
+	}
+
 	/// [`DuplicateToken`](https://learn.microsoft.com/en-us/windows/win32/api/securitybaseapi/nf-securitybaseapi-duplicatetoken)
 	/// function.
 	#[must_use]
@@ -305,4 +544,145 @@ pub trait advapi_Haccesstoken: Handle {
 			_ => Ok(true),
 		}
 	}
+
+	/// [`LogonUser`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-logonuserw)
+	/// function.
+	#[must_use]
+	fn LogonUser(
+		username: &str,
+		domain: Option<&str>,
+		password: Option<&str>,
+		logon_type: co::LOGON32_LOGON,
+		logon_provider: co::LOGON32_PROVIDER,
+	) -> SysResult<CloseHandleGuard<HACCESSTOKEN>> {
+		let mut handle = HACCESSTOKEN::NULL;
+		unsafe {
+			bool_to_sysresult(ffi::LogonUserW(
+				WString::from_str(username).as_ptr(),
+				WString::from_opt_str(domain).as_ptr(),
+				WString::from_opt_str(password).as_ptr(),
+				logon_type.raw(),
+				logon_provider.raw(),
+				handle.as_mut(),
+			))
+			.map(|_| CloseHandleGuard::new(handle))
+		}
+	}
+
+	/// [`SetTokenInformation`](https://learn.microsoft.com/en-us/windows/win32/api/securitybaseapi/nf-securitybaseapi-settokeninformation)
+	/// function.
+	///
+	/// The information class is inferred from the passed enum variant. Only
+	/// the following variants can be set:
+	///
+	/// * [`TokenInfo::Owner`](crate::TokenInfo::Owner)
+	/// * [`TokenInfo::PrimaryGroup`](crate::TokenInfo::PrimaryGroup)
+	/// * [`TokenInfo::DefaultDacl`](crate::TokenInfo::DefaultDacl)
+	/// * [`TokenInfo::SessionId`](crate::TokenInfo::SessionId)
+	/// * [`TokenInfo::Origin`](crate::TokenInfo::Origin)
+	/// * [`TokenInfo::VirtualizationEnabled`](crate::TokenInfo::VirtualizationEnabled)
+	/// * [`TokenInfo::IntegrityLevel`](crate::TokenInfo::IntegrityLevel)
+	/// * [`TokenInfo::UIAccess`](crate::TokenInfo::UIAccess)
+	/// * [`TokenInfo::MandatoryPolicy`](crate::TokenInfo::MandatoryPolicy)
+	///
+	/// Any other variant will fail with
+	/// [`ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER).
+	///
+	/// # Examples
+	///
+	/// Lowering the integrity level of a primary token:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let htoken = w::HPROCESS::GetCurrentProcess()
+	///     .OpenProcessToken(co::TOKEN::DUPLICATE | co::TOKEN::QUERY)?;
+	/// let hlow = htoken.CreateRestrictedToken(
+	///     co::CREATE_RESTRICTED_TOKEN::NoValue, &[], &[], &[])?;
+	///
+	/// let mut sid = w::CreateWellKnownSid(co::WELL_KNOWN_SID_TYPE::LowLabel, None)?;
+	/// let mut tml = w::TOKEN_MANDATORY_LABEL::default();
+	/// tml.Label.set_Sid(Some(&mut sid));
+	/// tml.Label.Attributes = co::SE_GROUP::INTEGRITY.raw();
+	///
+	/// hlow.SetTokenInformation(&w::TokenInfo::IntegrityLevel(Box::new(tml)))?;
+	/// # w::SysResult::Ok(())
+	/// ```
+	fn SetTokenInformation(&self, information: &TokenInfo) -> SysResult<()> {
+		let (information_class, ptr, sz) = match information {
+			TokenInfo::Owner(nfo) => (
+				co::TOKEN_INFORMATION_CLASS::Owner,
+				nfo.as_ref() as *const _ as *const std::ffi::c_void,
+				std::mem::size_of::<TOKEN_OWNER>(),
+			),
+			TokenInfo::PrimaryGroup(nfo) => (
+				co::TOKEN_INFORMATION_CLASS::PrimaryGroup,
+				nfo.as_ref() as *const _ as _,
+				std::mem::size_of::<TOKEN_PRIMARY_GROUP>(),
+			),
+			TokenInfo::DefaultDacl(nfo) => (
+				co::TOKEN_INFORMATION_CLASS::DefaultDacl,
+				nfo.as_ref() as *const _ as _,
+				std::mem::size_of::<TOKEN_DEFAULT_DACL>(),
+			),
+			TokenInfo::SessionId(nfo) => (
+				co::TOKEN_INFORMATION_CLASS::SessionId,
+				nfo.as_ref() as *const _ as _,
+				std::mem::size_of::<u32>(),
+			),
+			TokenInfo::Origin(nfo) => (
+				co::TOKEN_INFORMATION_CLASS::Origin,
+				nfo.as_ref() as *const _ as _,
+				std::mem::size_of::<TOKEN_ORIGIN>(),
+			),
+			TokenInfo::VirtualizationEnabled(nfo) => (
+				co::TOKEN_INFORMATION_CLASS::VirtualizationEnabled,
+				nfo.as_ref() as *const _ as _,
+				std::mem::size_of::<u32>(),
+			),
+			TokenInfo::IntegrityLevel(nfo) => (
+				co::TOKEN_INFORMATION_CLASS::IntegrityLevel,
+				nfo.as_ref() as *const _ as _,
+				std::mem::size_of::<TOKEN_MANDATORY_LABEL>() // the SID is appended to the struct
+					+ nfo.Label.Sid().map_or(0, |sid| unsafe {
+						ffi::GetLengthSid(sid as *mut _ as _) as usize
+					}),
+			),
+			TokenInfo::UIAccess(nfo) => (
+				co::TOKEN_INFORMATION_CLASS::UIAccess,
+				nfo.as_ref() as *const _ as _,
+				std::mem::size_of::<u32>(),
+			),
+			TokenInfo::MandatoryPolicy(nfo) => (
+				co::TOKEN_INFORMATION_CLASS::MandatoryPolicy,
+				nfo.as_ref() as *const _ as _,
+				std::mem::size_of::<TOKEN_MANDATORY_POLICY>(),
+			),
+			_ => return Err(co::ERROR::INVALID_PARAMETER),
+		};
+
+		bool_to_sysresult(unsafe {
+			ffi::SetTokenInformation(self.ptr(), information_class.raw(), ptr, sz as _)
+		})
+	}
+}
+
+/// Builds the double null-terminated environment block for process creation.
+fn environment_block(environment: Option<&[(&str, &str)]>) -> Option<WString> {
+	environment.map(|environment| {
+		WString::from_str_vec(
+			&environment
+				.iter()
+				.map(|(name, val)| format!("{}={}", name, val))
+				.collect::<Vec<_>>(),
+		)
+	})
+}
+
+/// Adds `CREATE_UNICODE_ENVIRONMENT` if an environment block is passed.
+fn environment_flags(creation_flags: co::CREATE, buf_env: &Option<WString>) -> co::CREATE {
+	match buf_env {
+		Some(_) => creation_flags | co::CREATE::UNICODE_ENVIRONMENT,
+		None => creation_flags,
+	}
 }
@@ -1,6 +1,6 @@
 #![allow(non_camel_case_types, non_snake_case)]
 
-use crate::advapi::ffi;
+use crate::advapi::{ffi, iterators::*};
 use crate::co;
 use crate::decl::*;
 use crate::guard::*;
@@ -24,6 +24,95 @@ impl advapi_Heventlog for HEVENTLOG {}
 /// use winsafe::prelude::*;
 /// ```
 pub trait advapi_Heventlog: Handle {
+	/// [`BackupEventLog`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-backupeventlogw)
+	/// function.
+	fn BackupEventLog(&self, backup_file_name: &str) -> SysResult<()> {
+		bool_to_sysresult(unsafe {
+			ffi::BackupEventLogW(self.ptr(), WString::from_str(backup_file_name).as_ptr())
+		})
+	}
+
+	/// [`ClearEventLog`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-cleareventlogw)
+	/// function.
+	fn ClearEventLog(&self, backup_file_name: Option<&str>) -> SysResult<()> {
+		bool_to_sysresult(unsafe {
+			ffi::ClearEventLogW(self.ptr(), WString::from_opt_str(backup_file_name).as_ptr())
+		})
+	}
+
+	/// [`GetNumberOfEventLogRecords`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-getnumberofeventlogrecords)
+	/// function.
+	#[must_use]
+	fn GetNumberOfEventLogRecords(&self) -> SysResult<u32> {
+		let mut num = u32::default();
+		bool_to_sysresult(unsafe { ffi::GetNumberOfEventLogRecords(self.ptr(), &mut num) })
+			.map(|_| num)
+	}
+
+	/// [`GetOldestEventLogRecord`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-getoldesteventlogrecord)
+	/// function.
+	#[must_use]
+	fn GetOldestEventLogRecord(&self) -> SysResult<u32> {
+		let mut num = u32::default();
+		bool_to_sysresult(unsafe { ffi::GetOldestEventLogRecord(self.ptr(), &mut num) })
+			.map(|_| num)
+	}
+
+	/// [`OpenEventLog`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-openeventlogw)
+	/// function.
+	#[must_use]
+	fn OpenEventLog(
+		unc_server_name: Option<&str>,
+		source_name: &str,
+	) -> SysResult<CloseEventLogGuard> {
+		unsafe {
+			ptr_to_sysresult_handle(ffi::OpenEventLogW(
+				WString::from_opt_str(unc_server_name).as_ptr(),
+				WString::from_str(source_name).as_ptr(),
+			))
+			.map(|h| CloseEventLogGuard::new(h))
+		}
+	}
+
+	/// Returns an iterator over the event records, which calls
+	/// [`ReadEventLog`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-readeventlogw)
+	/// repeatedly.
+	///
+	/// `flags` must contain either
+	/// [`EVENTLOG_READ::SEQUENTIAL`](crate::co::EVENTLOG_READ::SEQUENTIAL) or
+	/// [`EVENTLOG_READ::SEEK`](crate::co::EVENTLOG_READ::SEEK), combined with
+	/// either [`EVENTLOG_READ::FORWARDS`](crate::co::EVENTLOG_READ::FORWARDS)
+	/// or [`EVENTLOG_READ::BACKWARDS`](crate::co::EVENTLOG_READ::BACKWARDS).
+	/// When seeking, the iteration starts at `record_offset` and continues
+	/// sequentially; otherwise `record_offset` is ignored.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hlog = w::HEVENTLOG::OpenEventLog(None, "Application")?;
+	///
+	/// for rec in hlog.ReadEventLog(
+	///     co::EVENTLOG_READ::SEQUENTIAL | co::EVENTLOG_READ::BACKWARDS,
+	///     0,
+	/// ) {
+	///     let rec = rec?;
+	///     if rec.event_type == co::EVENTLOG::ERROR_TYPE {
+	///         println!("{} {}: {:?}", rec.record_number, rec.source_name, rec.strings);
+	///     }
+	/// }
+	/// # w::SysResult::Ok(())
+	/// ```
+	#[must_use]
+	fn ReadEventLog(
+		&self,
+		flags: co::EVENTLOG_READ,
+		record_offset: u32,
+	) -> impl Iterator<Item = SysResult<EventLogRecord>> + '_ {
+		HeventlogRecordIter::new(self, flags, record_offset)
+	}
+
 	/// [`RegisterEventSource`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-registereventsourcew)
 	/// function.
 	#[must_use]
@@ -0,0 +1,392 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use crate::advapi::ffi;
+use crate::advapi::utilities::event_log::*;
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::kernel::privs::*;
+use crate::prelude::*;
+
+handle! { HEVT;
+	/// Handle to a
+	/// [Windows Event Log](https://learn.microsoft.com/en-us/windows/win32/wes/windows-event-log)
+	/// object, like a query result set, an event, a render context or a
+	/// bookmark. Originally `EVT_HANDLE`.
+}
+
+impl advapi_Hevt for HEVT {}
+
+/// This trait is enabled with the `advapi` feature, and provides methods for
+/// [`HEVT`](crate::HEVT).
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+pub trait advapi_Hevt: Handle {
+	/// Renders the system properties of this event, by calling
+	/// [`HEVT::EvtRender`](crate::prelude::advapi_Hevt::EvtRender) with a
+	/// [`EVT_RENDER_CONTEXT::SYSTEM`](crate::co::EVT_RENDER_CONTEXT::SYSTEM)
+	/// context.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hquery = w::HEVT::EvtQuery(
+	///     Some("Application"),
+	///     Some("*[System[Provider[@Name='Application Error']]]"),
+	///     co::EVT_QUERY::CHANNEL_PATH | co::EVT_QUERY::REVERSE_DIRECTION,
+	/// )?;
+	///
+	/// loop {
+	///     let events = hquery.EvtNext(16, None)?;
+	///     if events.is_empty() {
+	///         break;
+	///     }
+	///     for hevent in events.iter() {
+	///         let props = hevent.render_system()?;
+	///         println!("{} {} {}", props.event_record_id, props.provider_name, props.event_id);
+	///     }
+	/// }
+	/// # w::SysResult::Ok(())
+	/// ```
+	#[must_use]
+	fn render_system(&self) -> SysResult<EvtSystemProps> {
+		let hcontext =
+			HEVT::EvtCreateRenderContext(None::<&[&str]>, co::EVT_RENDER_CONTEXT::SYSTEM)?;
+		match self.EvtRender(Some(&hcontext), co::EVT_RENDER::EVENT_VALUES)? {
+			EvtRendered::Values(values) => Ok(EvtSystemProps::from_values(values)),
+			EvtRendered::Xml(_) => Err(co::ERROR::INVALID_DATA), // never happens with EVENT_VALUES
+		}
+	}
+
+	/// [`EvtCreateBookmark`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/nf-winevt-evtcreatebookmark)
+	/// function.
+	///
+	/// Pass `None` to create an empty bookmark, or the XML previously rendered
+	/// with [`EVT_RENDER::BOOKMARK`](crate::co::EVT_RENDER::BOOKMARK).
+	#[must_use]
+	fn EvtCreateBookmark(bookmark_xml: Option<&str>) -> SysResult<EvtCloseGuard> {
+		unsafe {
+			ptr_to_sysresult_handle(ffi::EvtCreateBookmark(
+				WString::from_opt_str(bookmark_xml).as_ptr(),
+			))
+			.map(|h| EvtCloseGuard::new(h))
+		}
+	}
+
+	/// [`EvtCreateRenderContext`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/nf-winevt-evtcreaterendercontext)
+	/// function.
+	///
+	/// The XPath expressions of `value_paths` are used only with
+	/// [`EVT_RENDER_CONTEXT::VALUES`](crate::co::EVT_RENDER_CONTEXT::VALUES).
+	#[must_use]
+	fn EvtCreateRenderContext(
+		value_paths: Option<&[impl AsRef<str>]>,
+		flags: co::EVT_RENDER_CONTEXT,
+	) -> SysResult<EvtCloseGuard> {
+		let (_wstrs, pwstrs) = create_wstr_ptr_vecs(value_paths);
+		unsafe {
+			ptr_to_sysresult_handle(ffi::EvtCreateRenderContext(
+				pwstrs.len() as _,
+				vec_ptr(&pwstrs),
+				flags.raw(),
+			))
+			.map(|h| EvtCloseGuard::new(h))
+		}
+	}
+
+	/// [`EvtFormatMessage`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/nf-winevt-evtformatmessage)
+	/// function.
+	///
+	/// This handle is the publisher metadata returned by
+	/// [`HEVT::EvtOpenPublisherMetadata`](crate::prelude::advapi_Hevt::EvtOpenPublisherMetadata).
+	/// Pass `message_id` only with
+	/// [`EVT_FORMAT_MESSAGE::ID`](crate::co::EVT_FORMAT_MESSAGE::ID).
+	///
+	/// With [`EVT_FORMAT_MESSAGE::KEYWORD`](crate::co::EVT_FORMAT_MESSAGE::KEYWORD),
+	/// the keywords are separated by `'\0'`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hevent: w::HEVT; // initialized somewhere
+	/// # let hevent = w::HEVT::NULL;
+	///
+	/// let props = hevent.render_system()?;
+	/// let hpub = w::HEVT::EvtOpenPublisherMetadata(&props.provider_name, None)?;
+	/// let msg = hpub.EvtFormatMessage(Some(&hevent), 0, co::EVT_FORMAT_MESSAGE::EVENT)?;
+	/// println!("{}", msg);
+	/// # w::SysResult::Ok(())
+	/// ```
+	#[must_use]
+	fn EvtFormatMessage(
+		&self,
+		event: Option<&HEVT>,
+		message_id: u32,
+		flags: co::EVT_FORMAT_MESSAGE,
+	) -> SysResult<String> {
+		let mut buf_used = u32::default();
+		unsafe {
+			ffi::EvtFormatMessage(
+				self.ptr(),
+				event.map_or(std::ptr::null_mut(), |h| h.ptr()),
+				message_id,
+				0,
+				std::ptr::null(),
+				flags.raw(),
+				0,
+				std::ptr::null_mut(),
+				&mut buf_used, // retrieve needed buffer size, in chars
+			);
+		}
+		let get_size_err = GetLastError();
+		if get_size_err != co::ERROR::INSUFFICIENT_BUFFER {
+			return Err(get_size_err);
+		}
+
+		let mut buf = vec![0u16; buf_used as _];
+		bool_to_sysresult(unsafe {
+			ffi::EvtFormatMessage(
+				self.ptr(),
+				event.map_or(std::ptr::null_mut(), |h| h.ptr()),
+				message_id,
+				0,
+				std::ptr::null(),
+				flags.raw(),
+				buf.len() as _,
+				buf.as_mut_ptr(),
+				&mut buf_used,
+			)
+		})?;
+		Ok(String::from_utf16_lossy(&buf[..buf_used as usize])
+			.trim_end_matches('\0')
+			.to_owned())
+	}
+
+	/// [`EvtNext`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/nf-winevt-evtnext)
+	/// function.
+	///
+	/// Returns up to `count` events from this query or subscription. An empty
+	/// `Vec` means there are no more events. If `timeout_ms` is `None`, waits
+	/// indefinitely.
+	#[must_use]
+	fn EvtNext(&self, count: u32, timeout_ms: Option<u32>) -> SysResult<Vec<EvtCloseGuard>> {
+		let mut hevents = vec![std::ptr::null_mut(); count as _];
+		let mut returned = u32::default();
+
+		if unsafe {
+			ffi::EvtNext(
+				self.ptr(),
+				count,
+				hevents.as_mut_ptr(),
+				timeout_ms.unwrap_or(INFINITE),
+				0,
+				&mut returned,
+			)
+		} == 0
+		{
+			return match GetLastError() {
+				co::ERROR::NO_MORE_ITEMS => Ok(Vec::new()),
+				e => Err(e),
+			};
+		}
+
+		hevents.truncate(returned as _);
+		Ok(hevents
+			.into_iter()
+			.map(|p| unsafe { EvtCloseGuard::new(HEVT::from_ptr(p)) })
+			.collect())
+	}
+
+	/// [`EvtOpenPublisherMetadata`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/nf-winevt-evtopenpublishermetadata)
+	/// function.
+	///
+	/// The metadata is loaded for the user's locale.
+	#[must_use]
+	fn EvtOpenPublisherMetadata(
+		publisher_id: &str,
+		log_file_path: Option<&str>,
+	) -> SysResult<EvtCloseGuard> {
+		unsafe {
+			ptr_to_sysresult_handle(ffi::EvtOpenPublisherMetadata(
+				std::ptr::null_mut(),
+				WString::from_str(publisher_id).as_ptr(),
+				WString::from_opt_str(log_file_path).as_ptr(),
+				0,
+				0,
+			))
+			.map(|h| EvtCloseGuard::new(h))
+		}
+	}
+
+	/// [`EvtQuery`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/nf-winevt-evtquery)
+	/// function.
+	///
+	/// The `query` is an XPath expression or a structured XML query; `None`
+	/// returns all events. The events are retrieved with
+	/// [`HEVT::EvtNext`](crate::prelude::advapi_Hevt::EvtNext).
+	#[must_use]
+	fn EvtQuery(
+		path: Option<&str>,
+		query: Option<&str>,
+		flags: co::EVT_QUERY,
+	) -> SysResult<EvtCloseGuard> {
+		unsafe {
+			ptr_to_sysresult_handle(ffi::EvtQuery(
+				std::ptr::null_mut(),
+				WString::from_opt_str(path).as_ptr(),
+				WString::from_opt_str(query).as_ptr(),
+				flags.raw(),
+			))
+			.map(|h| EvtCloseGuard::new(h))
+		}
+	}
+
+	/// [`EvtRender`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/nf-winevt-evtrender)
+	/// function.
+	///
+	/// The `context` is used only with
+	/// [`EVT_RENDER::EVENT_VALUES`](crate::co::EVT_RENDER::EVENT_VALUES).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hevent: w::HEVT; // initialized somewhere
+	/// # let hevent = w::HEVT::NULL;
+	///
+	/// if let w::EvtRendered::Xml(xml) = hevent.EvtRender(None, co::EVT_RENDER::EVENT_XML)? {
+	///     println!("{}", xml);
+	/// }
+	/// # w::SysResult::Ok(())
+	/// ```
+	#[must_use]
+	fn EvtRender(&self, context: Option<&HEVT>, flags: co::EVT_RENDER) -> SysResult<EvtRendered> {
+		let hcontext = match flags {
+			co::EVT_RENDER::EVENT_VALUES => context.map_or(std::ptr::null_mut(), |h| h.ptr()),
+			_ => std::ptr::null_mut(),
+		};
+		let mut buf_used = u32::default();
+		let mut prop_count = u32::default();
+
+		unsafe {
+			ffi::EvtRender(
+				hcontext,
+				self.ptr(),
+				flags.raw(),
+				0,
+				std::ptr::null_mut(),
+				&mut buf_used, // retrieve needed buffer size, in bytes
+				&mut prop_count,
+			);
+		}
+		let get_size_err = GetLastError();
+		if get_size_err != co::ERROR::INSUFFICIENT_BUFFER {
+			return Err(get_size_err);
+		}
+
+		// EVT_VARIANT must be aligned.
+		let mut buf = vec![0u64; (buf_used as usize).div_ceil(std::mem::size_of::<u64>())];
+		bool_to_sysresult(unsafe {
+			ffi::EvtRender(
+				hcontext,
+				self.ptr(),
+				flags.raw(),
+				buf_used,
+				buf.as_mut_ptr() as _,
+				&mut buf_used,
+				&mut prop_count,
+			)
+		})?;
+
+		match flags {
+			co::EVT_RENDER::EVENT_VALUES => unsafe {
+				std::slice::from_raw_parts(buf.as_ptr() as *const EVT_VARIANT, prop_count as _)
+					.iter()
+					.map(|raw| EvtVariant::from_raw(raw))
+					.collect::<SysResult<Vec<_>>>()
+					.map(EvtRendered::Values)
+			},
+			_ => Ok(EvtRendered::Xml(
+				unsafe { WString::from_wchars_nullt(buf.as_ptr() as _) }.to_string(),
+			)),
+		}
+	}
+
+	/// [`EvtSubscribe`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/nf-winevt-evtsubscribe)
+	/// function.
+	///
+	/// Uses the pull subscription model: `signal_event` is set when events are
+	/// available, and they must be retrieved with
+	/// [`HEVT::EvtNext`](crate::prelude::advapi_Hevt::EvtNext) until it returns
+	/// an empty `Vec`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hsignal = w::HEVENT::CreateEvent(None, false, true, None)?;
+	/// let hsub = w::HEVT::EvtSubscribe(
+	///     &hsignal,
+	///     Some("Application"),
+	///     Some("*[System[Level<=2]]"),
+	///     None,
+	///     co::EVT_SUBSCRIBE::TO_FUTURE_EVENTS,
+	/// )?;
+	///
+	/// loop {
+	///     hsignal.WaitForSingleObject(None)?;
+	///     loop {
+	///         let events = hsub.EvtNext(16, Some(0))?;
+	///         if events.is_empty() {
+	///             break;
+	///         }
+	///         for hevent in events.iter() {
+	///             if let w::EvtRendered::Xml(xml) =
+	///                 hevent.EvtRender(None, co::EVT_RENDER::EVENT_XML)?
+	///             {
+	///                 println!("{}", xml);
+	///             }
+	///         }
+	///     }
+	/// }
+	/// # w::SysResult::Ok(())
+	/// ```
+	#[must_use]
+	fn EvtSubscribe(
+		signal_event: &HEVENT,
+		channel_path: Option<&str>,
+		query: Option<&str>,
+		bookmark: Option<&HEVT>,
+		flags: co::EVT_SUBSCRIBE,
+	) -> SysResult<EvtCloseGuard> {
+		unsafe {
+			ptr_to_sysresult_handle(ffi::EvtSubscribe(
+				std::ptr::null_mut(),
+				signal_event.ptr(),
+				WString::from_opt_str(channel_path).as_ptr(),
+				WString::from_opt_str(query).as_ptr(),
+				bookmark.map_or(std::ptr::null_mut(), |h| h.ptr()),
+				std::ptr::null_mut(),
+				std::ptr::null_mut(),
+				flags.raw(),
+			))
+			.map(|h| EvtCloseGuard::new(h))
+		}
+	}
+
+	/// [`EvtUpdateBookmark`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/nf-winevt-evtupdatebookmark)
+	/// function.
+	fn EvtUpdateBookmark(&self, event: &HEVT) -> SysResult<()> {
+		bool_to_sysresult(unsafe { ffi::EvtUpdateBookmark(self.ptr(), event.ptr()) })
+	}
+}
@@ -46,6 +46,94 @@ pub trait advapi_Hkey: Handle {
 	predef_key!(PERFORMANCE_TEXT, 0x8000_0050);
 	predef_key!(PERFORMANCE_NLSTEXT, 0x8000_0060);
 
+	/// Exports a key, along with all its values and sub keys, into a
+	/// [`RegFile`](crate::RegFile), which can be saved as a `.reg` file.
+	///
+	/// This key must be a predefined key, like
+	/// [`HKEY::CURRENT_USER`](crate::prelude::advapi_Hkey::CURRENT_USER),
+	/// otherwise fails with
+	/// [`ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER).
+	///
+	/// Values whose types are not supported by
+	/// [`RegistryValue`](crate::RegistryValue) are skipped.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let reg_file = w::HKEY::CURRENT_USER.export_tree(Some("Control Panel\\Mouse"))?;
+	/// std::fs::write("C:\\Temp\\mouse.reg", reg_file.to_bytes()?).unwrap();
+	/// # w::SysResult::Ok(())
+	/// ```
+	#[must_use]
+	fn export_tree(&self, sub_key: Option<&str>) -> SysResult<RegFile> {
+		let root_name = HKEY::predef_key_name(self.ptr()).ok_or(co::ERROR::INVALID_PARAMETER)?;
+		let path = match sub_key {
+			Some(sub_key) if !sub_key.is_empty() => format!("{}\\{}", root_name, sub_key),
+			_ => root_name.to_owned(),
+		};
+
+		let hkey = self.RegOpenKeyEx(sub_key, co::REG_OPTION::default(), co::KEY::READ)?;
+		let mut reg_file = RegFile::new();
+		hkey.export_key(path, &mut reg_file.keys)?;
+		Ok(reg_file)
+	}
+
+	/// Applies the entries of a [`RegFile`](crate::RegFile) to the registry:
+	/// keys are created, values are set, and deletions are performed. Deleting
+	/// something which doesn't exist is not an error.
+	///
+	/// The key paths must start with the name of a predefined key, either full
+	/// or abbreviated, like `HKEY_CURRENT_USER` or `HKCU`, otherwise fails with
+	/// [`ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let contents = std::fs::read("C:\\Temp\\mouse.reg").unwrap();
+	/// let reg_file = w::RegFile::from_bytes(&contents)?;
+	/// w::HKEY::import_tree(&reg_file)?;
+	/// # w::SysResult::Ok(())
+	/// ```
+	fn import_tree(reg_file: &RegFile) -> SysResult<()> {
+		for key in reg_file.keys.iter() {
+			let (root, sub_key) =
+				HKEY::split_predef_key(&key.path).ok_or(co::ERROR::INVALID_PARAMETER)?;
+
+			if key.delete {
+				if sub_key.is_empty() {
+					return Err(co::ERROR::INVALID_PARAMETER); // can't delete a predefined key
+				}
+				match root.RegDeleteTree(Some(sub_key)) {
+					Ok(_) | Err(co::ERROR::FILE_NOT_FOUND) => {},
+					Err(e) => return Err(e),
+				}
+				continue;
+			}
+
+			let hkey = if sub_key.is_empty() {
+				root.RegOpenKeyEx(None, co::REG_OPTION::default(), co::KEY::WRITE)?
+			} else {
+				root.RegCreateKeyEx(sub_key, None, co::REG_OPTION::default(), co::KEY::WRITE, None)?
+					.0
+			};
+
+			for (name, val) in key.values.iter() {
+				match val {
+					Some(val) => hkey.RegSetValueEx(Some(name), val.clone())?,
+					None => match hkey.RegDeleteValue(Some(name)) {
+						Ok(_) | Err(co::ERROR::FILE_NOT_FOUND) => {},
+						Err(e) => return Err(e),
+					},
+				}
+			}
+		}
+		Ok(())
+	}
+
 	/// [`RegConnectRegistry`](https://learn.microsoft.com/en-us/windows/win32/api/winreg/nf-winreg-regconnectregistryw)
 	/// function.
 	///
@@ -374,6 +462,58 @@ pub trait advapi_Hkey: Handle {
 		})
 	}
 
+	/// [`RegNotifyChangeKeyValue`](https://learn.microsoft.com/en-us/windows/win32/api/winreg/nf-winreg-regnotifychangekeyvalue)
+	/// function.
+	///
+	/// If `event` is `None`, the function blocks until a change is made.
+	/// Otherwise, it returns immediately, and the event is signaled when a
+	/// change is made.
+	///
+	/// The notification is triggered only once; to keep watching, call this
+	/// function again. For a higher-level abstraction, see
+	/// [`RegWatcher`](crate::RegWatcher).
+	///
+	/// # Examples
+	///
+	/// Waiting for a change with an event:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hkey = w::HKEY::CURRENT_USER.RegOpenKeyEx(
+	///     Some("Control Panel\\Mouse"),
+	///     co::REG_OPTION::default(),
+	///     co::KEY::NOTIFY,
+	/// )?;
+	///
+	/// let hevent = w::HEVENT::CreateEvent(None, false, false, None)?;
+	/// hkey.RegNotifyChangeKeyValue(
+	///     false,
+	///     co::REG_NOTIFY::CHANGE_LAST_SET,
+	///     Some(&hevent),
+	/// )?;
+	///
+	/// hevent.WaitForSingleObject(None)?;
+	/// println!("A value has changed.");
+	/// # w::SysResult::Ok(())
+	/// ```
+	fn RegNotifyChangeKeyValue(
+		&self,
+		watch_subtree: bool,
+		notify_filter: co::REG_NOTIFY,
+		event: Option<&HEVENT>,
+	) -> SysResult<()> {
+		error_to_sysresult(unsafe {
+			ffi::RegNotifyChangeKeyValue(
+				self.ptr(),
+				watch_subtree as _,
+				notify_filter.raw(),
+				event.map_or(std::ptr::null_mut(), |h| h.ptr()),
+				event.is_some() as _,
+			)
+		})
+	}
+
 	/// [`RegOpenCurrentUser`](https://learn.microsoft.com/en-us/windows/win32/api/winreg/nf-winreg-regopencurrentuser)
 	/// function.
 	#[must_use]
@@ -830,4 +970,57 @@ impl HKEY {
 		(self.0 as usize) >= (Self::CLASSES_ROOT.0 as usize)
 			&& (self.0 as usize) <= (Self::PERFORMANCE_NLSTEXT.0 as usize)
 	}
+
+	/// Full and abbreviated names of the predefined keys which can appear in
+	/// `.reg` files.
+	const PREDEF_NAMES: [(HKEY, &'static str, &'static str); 5] = [
+		(HKEY::CLASSES_ROOT, "HKEY_CLASSES_ROOT", "HKCR"),
+		(HKEY::CURRENT_USER, "HKEY_CURRENT_USER", "HKCU"),
+		(HKEY::LOCAL_MACHINE, "HKEY_LOCAL_MACHINE", "HKLM"),
+		(HKEY::USERS, "HKEY_USERS", "HKU"),
+		(HKEY::CURRENT_CONFIG, "HKEY_CURRENT_CONFIG", "HKCC"),
+	];
+
+	/// Returns the full name of the predefined key, if any.
+	#[must_use]
+	pub(in crate::advapi) fn predef_key_name(ptr: *mut std::ffi::c_void) -> Option<&'static str> {
+		Self::PREDEF_NAMES
+			.iter()
+			.find(|(hkey, _, _)| hkey.0 == ptr)
+			.map(|(_, name, _)| *name)
+	}
+
+	/// Splits a key path into its predefined root key and the sub key.
+	#[must_use]
+	pub(in crate::advapi) fn split_predef_key(path: &str) -> Option<(HKEY, &str)> {
+		let (root_name, sub_key) = path.split_once('\\').unwrap_or((path, ""));
+		Self::PREDEF_NAMES
+			.iter()
+			.find(|(_, name, abbr)| {
+				root_name.eq_ignore_ascii_case(name) || root_name.eq_ignore_ascii_case(abbr)
+			})
+			.map(|(hkey, _, _)| (unsafe { hkey.raw_copy() }, sub_key.trim_matches('\\')))
+	}
+
+	/// Recursively appends this key, and its sub keys, to the entries.
+	fn export_key(&self, path: String, keys: &mut Vec<RegFileKey>) -> SysResult<()> {
+		let mut key = RegFileKey::new(&path);
+		for name_type in self.RegEnumValue()? {
+			let (name, _) = name_type?;
+			match self.RegQueryValueEx(Some(&name)) {
+				Ok(val) => key.values.push((name, Some(val))),
+				Err(co::ERROR::CALL_NOT_IMPLEMENTED) => {}, // type not supported by RegistryValue
+				Err(e) => return Err(e),
+			}
+		}
+		keys.push(key);
+
+		for sub_key in self.RegEnumKeyEx()? {
+			let sub_key = sub_key?;
+			let hkey =
+				self.RegOpenKeyEx(Some(&sub_key), co::REG_OPTION::default(), co::KEY::READ)?;
+			hkey.export_key(format!("{}\\{}", path, sub_key), keys)?;
+		}
+		Ok(())
+	}
 }
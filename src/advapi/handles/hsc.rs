@@ -1,6 +1,7 @@
 #![allow(non_camel_case_types, non_snake_case)]
 
 use crate::advapi::ffi;
+use crate::advapi::utilities::service_config::*;
 use crate::co;
 use crate::decl::*;
 use crate::guard::*;
@@ -24,6 +25,45 @@ impl advapi_Hsc for HSC {}
 /// use winsafe::prelude::*;
 /// ```
 pub trait advapi_Hsc: Handle {
+	/// Stops a service, first stopping all its active dependent services, and
+	/// waits until they all reach the
+	/// [`SERVICE_STATE::STOPPED`](crate::co::SERVICE_STATE::STOPPED) state.
+	///
+	/// If the service is already stopped, returns immediately. If the services
+	/// don't stop within the given timeout, fails with
+	/// [`ERROR::TIMEOUT`](crate::co::ERROR::TIMEOUT).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	/// use std::time::Duration;
+	///
+	/// let hsc = w::HSC::OpenSCManager(None, co::SC_MANAGER::CONNECT)?;
+	/// hsc.stop_service("MyService", Duration::from_secs(30))?;
+	/// # w::SysResult::Ok(())
+	/// ```
+	fn stop_service(&self, service_name: &str, timeout: std::time::Duration) -> SysResult<()> {
+		let deadline = std::time::Instant::now() + timeout;
+		let hsvc = self.OpenService(
+			service_name,
+			co::SERVICE::STOP | co::SERVICE::QUERY_STATUS | co::SERVICE::ENUMERATE_DEPENDENTS,
+		)?;
+		if hsvc.QueryServiceStatusEx()?.dwCurrentState == co::SERVICE_STATE::STOPPED {
+			return Ok(());
+		}
+
+		for dependent in hsvc.EnumDependentServices(co::SERVICE_ENUM_STATE::ACTIVE)? {
+			// Returned in the order they must be stopped.
+			let hdep = self.OpenService(
+				&dependent.service_name,
+				co::SERVICE::STOP | co::SERVICE::QUERY_STATUS,
+			)?;
+			stop_and_wait(&hdep, deadline)?;
+		}
+		stop_and_wait(&hsvc, deadline)
+	}
+
 	/// [`CreateService`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-createservicew)
 	/// function.
 	#[must_use]
@@ -71,6 +111,83 @@ pub trait advapi_Hsc: Handle {
 		}
 	}
 
+	/// [`EnumServicesStatusEx`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-enumservicesstatusexw)
+	/// function.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hsc = w::HSC::OpenSCManager(None, co::SC_MANAGER::ENUMERATE_SERVICE)?;
+	///
+	/// for svc in hsc.EnumServicesStatusEx(
+	///     co::SERVICE_TYPE::WIN32,
+	///     co::SERVICE_ENUM_STATE::ACTIVE,
+	///     None,
+	/// )? {
+	///     println!("{} - PID {}", svc.service_name, svc.status.dwProcessId);
+	/// }
+	/// # w::SysResult::Ok(())
+	/// ```
+	#[must_use]
+	fn EnumServicesStatusEx(
+		&self,
+		service_type: co::SERVICE_TYPE,
+		service_state: co::SERVICE_ENUM_STATE,
+		group_name: Option<&str>,
+	) -> SysResult<Vec<ServiceEntry>> {
+		const SC_ENUM_PROCESS_INFO: u32 = 0;
+
+		let group_name_w = WString::from_opt_str(group_name);
+		let mut entries = Vec::<ServiceEntry>::new();
+		let mut resume_handle = u32::default();
+		let mut buf_sz = 0x1_0000; // 64 KB, the maximum the function can use
+		let mut buf = HGLOBAL::GlobalAlloc(co::GMEM::FIXED | co::GMEM::ZEROINIT, buf_sz as _)?;
+
+		loop {
+			let mut needed_sz = u32::default();
+			let mut num_services = u32::default();
+
+			let ret = unsafe {
+				ffi::EnumServicesStatusExW(
+					self.ptr(),
+					SC_ENUM_PROCESS_INFO,
+					service_type.raw(),
+					service_state.raw(),
+					buf.ptr() as _,
+					buf_sz,
+					&mut needed_sz,
+					&mut num_services,
+					&mut resume_handle,
+					group_name_w.as_ptr(),
+				)
+			};
+			let err = GetLastError();
+			if ret == 0 && err != co::ERROR::MORE_DATA {
+				return Err(err);
+			}
+
+			entries.extend(
+				unsafe {
+					std::slice::from_raw_parts(
+						buf.ptr() as *const ENUM_SERVICE_STATUS_PROCESS,
+						num_services as _,
+					)
+				}
+				.iter()
+				.map(|raw| unsafe { ServiceEntry::from_raw_process(raw) }),
+			);
+
+			if ret != 0 {
+				return Ok(entries);
+			} else if num_services == 0 && needed_sz > buf_sz {
+				buf_sz = needed_sz; // a single entry didn't fit
+				buf = HGLOBAL::GlobalAlloc(co::GMEM::FIXED | co::GMEM::ZEROINIT, buf_sz as _)?;
+			}
+		}
+	}
+
 	/// [`OpenSCManager`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-openscmanagerw)
 	/// function.
 	#[must_use]
@@ -106,3 +223,28 @@ pub trait advapi_Hsc: Handle {
 		}
 	}
 }
+
+/// Sends the stop control to the service, then polls its status until it's
+/// stopped or the deadline is reached.
+fn stop_and_wait(hsvc: &HSERVICE, deadline: std::time::Instant) -> SysResult<()> {
+	match hsvc.ControlService(co::SERVICE_CONTROL::STOP) {
+		Ok(_) // already stopping, or not running at all
+		| Err(co::ERROR::SERVICE_NOT_ACTIVE)
+		| Err(co::ERROR::SERVICE_CANNOT_ACCEPT_CTRL) => {},
+		Err(e) => return Err(e),
+	}
+
+	loop {
+		let status = hsvc.QueryServiceStatusEx()?;
+		if status.dwCurrentState == co::SERVICE_STATE::STOPPED {
+			return Ok(());
+		}
+
+		let now = std::time::Instant::now();
+		if now >= deadline {
+			return Err(co::ERROR::TIMEOUT);
+		}
+		let wait = std::time::Duration::from_millis((status.dwWaitHint / 10).clamp(100, 1000) as _);
+		std::thread::sleep(wait.min(deadline - now));
+	}
+}
@@ -1,6 +1,8 @@
 #![allow(non_camel_case_types, non_snake_case)]
 
 use crate::advapi::ffi;
+use crate::advapi::utilities::service_config::*;
+use crate::co;
 use crate::decl::*;
 use crate::kernel::privs::*;
 use crate::prelude::*;
@@ -22,9 +24,288 @@ impl advapi_Hservice for HSERVICE {}
 /// use winsafe::prelude::*;
 /// ```
 pub trait advapi_Hservice: Handle {
+	/// [`ChangeServiceConfig`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-changeserviceconfigw)
+	/// function.
+	///
+	/// Parameters set to `None` are left unchanged.
+	#[allow(clippy::too_many_arguments)]
+	fn ChangeServiceConfig(
+		&self,
+		service_type: Option<co::SERVICE_TYPE>,
+		start_type: Option<co::SERVICE_START>,
+		error_control: Option<co::SERVICE_ERROR>,
+		binary_path_name: Option<&str>,
+		load_order_group: Option<&str>,
+		tag_id: Option<&mut u32>,
+		dependencies: Option<&[impl AsRef<str>]>,
+		service_start_name: Option<&str>,
+		password: Option<&str>,
+		display_name: Option<&str>,
+	) -> SysResult<()> {
+		const SERVICE_NO_CHANGE: u32 = 0xffff_ffff;
+		let deps_w = dependencies.map(WString::from_str_vec);
+
+		bool_to_sysresult(unsafe {
+			ffi::ChangeServiceConfigW(
+				self.ptr(),
+				service_type.map_or(SERVICE_NO_CHANGE, |t| t.raw()),
+				start_type.map_or(SERVICE_NO_CHANGE, |t| t.raw()),
+				error_control.map_or(SERVICE_NO_CHANGE, |e| e.raw()),
+				WString::from_opt_str(binary_path_name).as_ptr(),
+				WString::from_opt_str(load_order_group).as_ptr(),
+				tag_id.map_or(std::ptr::null_mut(), |n| n),
+				deps_w.as_ref().map_or(std::ptr::null(), |w| w.as_ptr()),
+				WString::from_opt_str(service_start_name).as_ptr(),
+				WString::from_opt_str(password).as_ptr(),
+				WString::from_opt_str(display_name).as_ptr(),
+			)
+		})
+	}
+
+	/// [`ChangeServiceConfig2`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-changeserviceconfig2w)
+	/// function.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hsc = w::HSC::OpenSCManager(None, co::SC_MANAGER::ALL_ACCESS)?;
+	/// let hsvc = hsc.OpenService("MyService", co::SERVICE::CHANGE_CONFIG)?;
+	///
+	/// hsvc.ChangeServiceConfig2(&w::ServiceConfig2::Description(
+	///     "Does important things.".to_owned(),
+	/// ))?;
+	/// hsvc.ChangeServiceConfig2(&w::ServiceConfig2::FailureActions(
+	///     w::ServiceFailureActions {
+	///         reset_period_secs: 86_400,
+	///         actions: vec![
+	///             w::ScAction { action: co::SC_ACTION::RESTART, delay_ms: 5_000 },
+	///             w::ScAction { action: co::SC_ACTION::NONE, delay_ms: 0 },
+	///         ],
+	///         ..Default::default()
+	///     },
+	/// ))?;
+	/// # w::SysResult::Ok(())
+	/// ```
+	fn ChangeServiceConfig2(&self, info: &ServiceConfig2) -> SysResult<()> {
+		info.with_raw(|p| {
+			bool_to_sysresult(unsafe {
+				ffi::ChangeServiceConfig2W(self.ptr(), info.level().raw(), p)
+			})
+		})
+	}
+
+	/// [`ControlService`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-controlservice)
+	/// function.
+	///
+	/// Returns the latest status reported by the service.
+	fn ControlService(&self, control: co::SERVICE_CONTROL) -> SysResult<SERVICE_STATUS> {
+		let mut status = SERVICE_STATUS::default();
+		bool_to_sysresult(unsafe {
+			ffi::ControlService(self.ptr(), control.raw(), &mut status as *mut _ as _)
+		})
+		.map(|_| status)
+	}
+
+	/// [`ControlServiceEx`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-controlserviceexw)
+	/// function.
+	///
+	/// The `reason` is one of the
+	/// [service stop reason](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_control_status_reason_paramsw)
+	/// codes, used only when `control` is
+	/// [`SERVICE_CONTROL::STOP`](crate::co::SERVICE_CONTROL::STOP).
+	///
+	/// Returns the latest status reported by the service.
+	fn ControlServiceEx(
+		&self,
+		control: co::SERVICE_CONTROL,
+		reason: u32,
+		comment: Option<&str>,
+	) -> SysResult<SERVICE_STATUS_PROCESS> {
+		const SERVICE_CONTROL_STATUS_REASON_INFO: u32 = 1;
+
+		let comment_w = WString::from_opt_str(comment);
+		let mut params = SERVICE_CONTROL_STATUS_REASON_PARAMS::new(reason, comment_w.as_ptr());
+		bool_to_sysresult(unsafe {
+			ffi::ControlServiceExW(
+				self.ptr(),
+				control.raw(),
+				SERVICE_CONTROL_STATUS_REASON_INFO,
+				&mut params as *mut _ as _,
+			)
+		})
+		.map(|_| params.status())
+	}
+
 	/// [`DeleteService`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-deleteservice)
 	/// function.
 	fn DeleteService(&self) -> SysResult<()> {
 		bool_to_sysresult(unsafe { ffi::DeleteService(self.ptr()) })
 	}
+
+	/// [`EnumDependentServices`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-enumdependentservicesw)
+	/// function.
+	///
+	/// The services are returned in the reverse order of their start, so they
+	/// can be stopped in the returned order.
+	#[must_use]
+	fn EnumDependentServices(
+		&self,
+		service_state: co::SERVICE_ENUM_STATE,
+	) -> SysResult<Vec<ServiceEntry>> {
+		let mut needed_sz = u32::default();
+		let mut num_services = u32::default();
+
+		if unsafe {
+			ffi::EnumDependentServicesW(
+				self.ptr(),
+				service_state.raw(),
+				std::ptr::null_mut(),
+				0,
+				&mut needed_sz, // retrieve needed buffer size
+				&mut num_services,
+			)
+		} != 0
+		{
+			return Ok(Vec::new()); // no dependent services
+		}
+		let get_size_err = GetLastError();
+		if get_size_err != co::ERROR::MORE_DATA {
+			return Err(get_size_err);
+		}
+
+		let buf = HGLOBAL::GlobalAlloc(co::GMEM::FIXED | co::GMEM::ZEROINIT, needed_sz as _)?;
+		unsafe {
+			bool_to_sysresult(ffi::EnumDependentServicesW(
+				self.ptr(),
+				service_state.raw(),
+				buf.ptr() as _,
+				needed_sz,
+				&mut needed_sz,
+				&mut num_services,
+			))?;
+			Ok(std::slice::from_raw_parts(
+				buf.ptr() as *const ENUM_SERVICE_STATUS,
+				num_services as _,
+			)
+			.iter()
+			.map(|raw| ServiceEntry::from_raw(raw))
+			.collect())
+		}
+	}
+
+	/// [`QueryServiceConfig`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-queryserviceconfigw)
+	/// function.
+	#[must_use]
+	fn QueryServiceConfig(&self) -> SysResult<ServiceConfig> {
+		let mut needed_sz = u32::default();
+		unsafe {
+			ffi::QueryServiceConfigW(self.ptr(), std::ptr::null_mut(), 0, &mut needed_sz);
+		}
+		let get_size_err = GetLastError();
+		if get_size_err != co::ERROR::INSUFFICIENT_BUFFER {
+			return Err(get_size_err);
+		}
+
+		let buf = HGLOBAL::GlobalAlloc(co::GMEM::FIXED | co::GMEM::ZEROINIT, needed_sz as _)?;
+		unsafe {
+			bool_to_sysresult(ffi::QueryServiceConfigW(
+				self.ptr(),
+				buf.ptr() as _,
+				needed_sz,
+				&mut needed_sz,
+			))?;
+			Ok(ServiceConfig::from_raw(&*(buf.ptr() as *const QUERY_SERVICE_CONFIG)))
+		}
+	}
+
+	/// [`QueryServiceConfig2`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-queryserviceconfig2w)
+	/// function.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hsc = w::HSC::OpenSCManager(None, co::SC_MANAGER::CONNECT)?;
+	/// let hsvc = hsc.OpenService("Spooler", co::SERVICE::QUERY_CONFIG)?;
+	///
+	/// if let w::ServiceConfig2::Description(descr) =
+	///     hsvc.QueryServiceConfig2(co::SERVICE_CONFIG::DESCRIPTION)?
+	/// {
+	///     println!("{}", descr);
+	/// }
+	/// # w::SysResult::Ok(())
+	/// ```
+	#[must_use]
+	fn QueryServiceConfig2(&self, info_level: co::SERVICE_CONFIG) -> SysResult<ServiceConfig2> {
+		let mut needed_sz = u32::default();
+		unsafe {
+			ffi::QueryServiceConfig2W(
+				self.ptr(),
+				info_level.raw(),
+				std::ptr::null_mut(),
+				0,
+				&mut needed_sz, // retrieve needed buffer size
+			);
+		}
+		let get_size_err = GetLastError();
+		if get_size_err != co::ERROR::INSUFFICIENT_BUFFER {
+			return Err(get_size_err);
+		}
+
+		let buf = HGLOBAL::GlobalAlloc(co::GMEM::FIXED | co::GMEM::ZEROINIT, needed_sz as _)?;
+		unsafe {
+			bool_to_sysresult(ffi::QueryServiceConfig2W(
+				self.ptr(),
+				info_level.raw(),
+				buf.ptr() as _,
+				needed_sz,
+				&mut needed_sz,
+			))?;
+			ServiceConfig2::from_raw(info_level, buf.ptr() as _)
+		}
+	}
+
+	/// [`QueryServiceStatusEx`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-queryservicestatusex)
+	/// function.
+	#[must_use]
+	fn QueryServiceStatusEx(&self) -> SysResult<SERVICE_STATUS_PROCESS> {
+		const SC_STATUS_PROCESS_INFO: u32 = 0;
+
+		let mut status = SERVICE_STATUS_PROCESS::default();
+		let mut needed_sz = u32::default();
+		bool_to_sysresult(unsafe {
+			ffi::QueryServiceStatusEx(
+				self.ptr(),
+				SC_STATUS_PROCESS_INFO,
+				&mut status as *mut _ as _,
+				std::mem::size_of::<SERVICE_STATUS_PROCESS>() as _,
+				&mut needed_sz,
+			)
+		})
+		.map(|_| status)
+	}
+
+	/// [`StartService`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-startservicew)
+	/// function.
+	///
+	/// The arguments are passed to the service main function, after the
+	/// service name.
+	fn StartService(&self, args: &[impl AsRef<str>]) -> SysResult<()> {
+		let args_w = args
+			.iter()
+			.map(|arg| WString::from_str(arg.as_ref()))
+			.collect::<Vec<_>>();
+		let args_ptrs = args_w.iter().map(|arg| arg.as_ptr()).collect::<Vec<_>>();
+
+		bool_to_sysresult(unsafe {
+			ffi::StartServiceW(
+				self.ptr(),
+				args_ptrs.len() as _,
+				if args_ptrs.is_empty() { std::ptr::null() } else { args_ptrs.as_ptr() },
+			)
+		})
+	}
 }
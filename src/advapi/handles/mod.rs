@@ -1,5 +1,6 @@
 mod haccesstoken;
 mod heventlog;
+mod hevt;
 mod hkey;
 mod hprocess;
 mod hsc;
@@ -11,6 +12,7 @@ mod htransaction;
 pub mod decl {
 	pub use super::haccesstoken::HACCESSTOKEN;
 	pub use super::heventlog::HEVENTLOG;
+	pub use super::hevt::HEVT;
 	pub use super::hkey::HKEY;
 	pub use super::hsc::HSC;
 	pub use super::hservice::HSERVICE;
@@ -21,6 +23,7 @@ pub mod decl {
 pub mod traits {
 	pub use super::haccesstoken::advapi_Haccesstoken;
 	pub use super::heventlog::advapi_Heventlog;
+	pub use super::hevt::advapi_Hevt;
 	pub use super::hkey::advapi_Hkey;
 	pub use super::hprocess::advapi_Hprocess;
 	pub use super::hsc::advapi_Hsc;
@@ -1,4 +1,5 @@
 use crate::advapi::ffi;
+use crate::advapi::utilities::event_log::*;
 use crate::co;
 use crate::decl::*;
 use crate::prelude::*;
@@ -155,3 +156,99 @@ where
 		})
 	}
 }
+
+pub(in crate::advapi) struct HeventlogRecordIter<'a, H>
+where
+	H: advapi_Heventlog,
+{
+	heventlog: &'a H,
+	flags: co::EVENTLOG_READ,
+	record_offset: u32,
+	buf: Vec<u64>, // keeps records aligned
+	buf_used: usize,
+	pos: usize,
+	done: bool,
+}
+
+impl<'a, H> Iterator for HeventlogRecordIter<'a, H>
+where
+	H: advapi_Heventlog,
+{
+	type Item = SysResult<EventLogRecord>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done {
+			return None;
+		}
+
+		if self.pos >= self.buf_used {
+			let mut bytes_read = u32::default();
+			let mut min_needed = u32::default();
+			loop {
+				if unsafe {
+					ffi::ReadEventLogW(
+						self.heventlog.ptr(),
+						self.flags.raw(),
+						self.record_offset,
+						self.buf.as_mut_ptr() as _,
+						(self.buf.len() * std::mem::size_of::<u64>()) as _,
+						&mut bytes_read,
+						&mut min_needed,
+					)
+				} != 0
+				{
+					break;
+				}
+				match GetLastError() {
+					co::ERROR::INSUFFICIENT_BUFFER => {
+						let num_u64 = (min_needed as usize).div_ceil(std::mem::size_of::<u64>());
+						self.buf.resize(num_u64, 0); // record larger than the buffer
+					},
+					co::ERROR::HANDLE_EOF => {
+						self.done = true;
+						return None;
+					},
+					e => {
+						self.done = true; // no further iterations will be made
+						return Some(Err(e));
+					},
+				}
+			}
+
+			if self.flags.has(co::EVENTLOG_READ::SEEK) {
+				// Subsequent reads continue from where the first one stopped.
+				self.flags =
+					(self.flags & !co::EVENTLOG_READ::SEEK) | co::EVENTLOG_READ::SEQUENTIAL;
+			}
+			self.buf_used = bytes_read as _;
+			self.pos = 0;
+		}
+
+		let prec =
+			unsafe { (self.buf.as_ptr() as *const u8).add(self.pos) } as *const EVENTLOGRECORD;
+		self.pos += unsafe { (*prec).Length } as usize;
+		Some(unsafe { EventLogRecord::from_raw(prec) })
+	}
+}
+
+impl<'a, H> HeventlogRecordIter<'a, H>
+where
+	H: advapi_Heventlog,
+{
+	#[must_use]
+	pub(in crate::advapi) fn new(
+		heventlog: &'a H,
+		flags: co::EVENTLOG_READ,
+		record_offset: u32,
+	) -> Self {
+		Self {
+			heventlog,
+			flags,
+			record_offset,
+			buf: vec![0; 0x1_0000 / std::mem::size_of::<u64>()], // 64 KB
+			buf_used: 0,
+			pos: 0,
+			done: false,
+		}
+	}
+}
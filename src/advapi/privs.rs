@@ -1,4 +1,5 @@
 const_values_num_privs! {
+	SDDL_REVISION_1 u32 = 1
 	SECURITY_DESCRIPTOR_REVISION u32 = 1
 	SID_HASH_SIZE usize = 32
 	TOKEN_SOURCE_LENGTH usize = 8
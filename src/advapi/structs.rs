@@ -124,6 +124,22 @@ pub struct SERVICE_STATUS {
 	pub dwWaitPoint: u32,
 }
 
+/// [`SERVICE_STATUS_PROCESS`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_status_process)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct SERVICE_STATUS_PROCESS {
+	pub dwServiceType: co::SERVICE_TYPE,
+	pub dwCurrentState: co::SERVICE_STATE,
+	pub dwControlsAccepted: co::SERVICE_ACCEPT,
+	pub dwWin32ExitCode: u32,
+	pub dwServiceSpecificExitCode: u32,
+	pub dwCheckPoint: u32,
+	pub dwWaitHint: u32,
+	pub dwProcessId: u32,
+	pub dwServiceFlags: u32,
+}
+
 /// [`SERVICE_TIMECHANGE_INFO`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_timechange_info)
 /// struct.
 #[repr(C)]
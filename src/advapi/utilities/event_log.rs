@@ -0,0 +1,415 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+
+/// An event record read by
+/// [`HEVENTLOG::ReadEventLog`](crate::prelude::advapi_Heventlog::ReadEventLog).
+///
+/// Owned, pure-Rust representation of the
+/// [`EVENTLOGRECORD`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-eventlogrecord)
+/// struct.
+pub struct EventLogRecord {
+	/// The record number, used to seek within the log.
+	pub record_number: u32,
+	/// When the event was submitted, in seconds since 1970-01-01 UTC.
+	pub time_generated: u32,
+	/// When the event was written to the log, in seconds since 1970-01-01 UTC.
+	pub time_written: u32,
+	/// The event identifier. The lower 16 bits are the code shown in the Event
+	/// Viewer.
+	pub event_id: u32,
+	/// The type of the event.
+	pub event_type: co::EVENTLOG,
+	/// The category defined by the event source.
+	pub event_category: u16,
+	/// The name of the event source which reported the event.
+	pub source_name: String,
+	/// The name of the computer which generated the event.
+	pub computer_name: String,
+	/// The user the event was reported for, if any.
+	pub user_sid: Option<SidGuard>,
+	/// The insertion strings of the message.
+	pub strings: Vec<String>,
+	/// The binary data reported with the event.
+	pub data: Vec<u8>,
+}
+
+/// A value rendered by
+/// [`HEVT::EvtRender`](crate::prelude::advapi_Hevt::EvtRender).
+///
+/// Owned, pure-Rust representation of the
+/// [`EVT_VARIANT`](https://learn.microsoft.com/en-us/windows/win32/api/winevt/ns-winevt-evt_variant)
+/// struct. Handles and array types not listed here are returned as
+/// [`EvtVariant::Null`](crate::EvtVariant::Null).
+pub enum EvtVariant {
+	Null,
+	String(String),
+	AnsiString(String),
+	SByte(i8),
+	Byte(u8),
+	Int16(i16),
+	UInt16(u16),
+	Int32(i32),
+	UInt32(u32),
+	Int64(i64),
+	UInt64(u64),
+	Single(f32),
+	Double(f64),
+	Boolean(bool),
+	Binary(Vec<u8>),
+	Guid(GUID),
+	SizeT(usize),
+	FileTime(FILETIME),
+	SysTime(SYSTEMTIME),
+	Sid(SidGuard),
+	HexInt32(u32),
+	HexInt64(u64),
+	Xml(String),
+	Array(Vec<EvtVariant>),
+}
+
+/// Output of [`HEVT::EvtRender`](crate::prelude::advapi_Hevt::EvtRender).
+pub enum EvtRendered {
+	/// The event XML or the bookmark XML, rendered with
+	/// [`EVT_RENDER::EVENT_XML`](crate::co::EVT_RENDER::EVENT_XML) or
+	/// [`EVT_RENDER::BOOKMARK`](crate::co::EVT_RENDER::BOOKMARK).
+	Xml(String),
+	/// The values selected by the render context, rendered with
+	/// [`EVT_RENDER::EVENT_VALUES`](crate::co::EVT_RENDER::EVENT_VALUES).
+	Values(Vec<EvtVariant>),
+}
+
+/// The system properties of an event, returned by
+/// [`HEVT::render_system`](crate::prelude::advapi_Hevt::render_system).
+///
+/// Properties not present in the event are zero, empty or `None`.
+pub struct EvtSystemProps {
+	/// The name of the event provider.
+	pub provider_name: String,
+	/// The GUID of the event provider.
+	pub provider_guid: Option<GUID>,
+	/// The event identifier.
+	pub event_id: u16,
+	/// The high 16 bits of the event identifier, for events of classic event
+	/// sources.
+	pub qualifiers: u16,
+	/// The severity of the event.
+	pub level: co::WINEVENT_LEVEL,
+	/// The task defined by the provider.
+	pub task: u16,
+	/// The opcode defined by the provider.
+	pub opcode: u8,
+	/// The keywords bitmask of the event.
+	pub keywords: u64,
+	/// When the event was logged.
+	pub time_created: FILETIME,
+	/// The record number of the event within the log.
+	pub event_record_id: u64,
+	/// The activity the event belongs to.
+	pub activity_id: Option<GUID>,
+	/// The activity which started the activity the event belongs to.
+	pub related_activity_id: Option<GUID>,
+	/// The process which logged the event.
+	pub process_id: u32,
+	/// The thread which logged the event.
+	pub thread_id: u32,
+	/// The channel the event was logged to.
+	pub channel: String,
+	/// The name of the computer where the event was logged.
+	pub computer: String,
+	/// The user the event was logged for, if any.
+	pub user_id: Option<SidGuard>,
+	/// The version of the event definition.
+	pub version: u8,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[repr(C)]
+pub(in crate::advapi) struct EVENTLOGRECORD {
+	pub(in crate::advapi) Length: u32,
+	Reserved: u32,
+	RecordNumber: u32,
+	TimeGenerated: u32,
+	TimeWritten: u32,
+	EventID: u32,
+	EventType: u16,
+	NumStrings: u16,
+	EventCategory: u16,
+	ReservedFlags: u16,
+	ClosingRecordNumber: u32,
+	StringOffset: u32,
+	UserSidLength: u32,
+	UserSidOffset: u32,
+	DataLength: u32,
+	DataOffset: u32,
+}
+
+#[repr(C)]
+pub(in crate::advapi) struct EVT_VARIANT {
+	value: u64, // union
+	Count: u32,
+	Type: u32,
+}
+
+/// Reads a nullable, null-terminated wide string.
+#[must_use]
+unsafe fn str_at(p: *const u16) -> String {
+	if p.is_null() {
+		String::new()
+	} else {
+		WString::from_wchars_nullt(p).to_string()
+	}
+}
+
+impl EventLogRecord {
+	/// Parses an `EVENTLOGRECORD` struct, followed by its variable-length data.
+	///
+	/// # Safety
+	///
+	/// The pointer must point to an entire record, as returned by
+	/// `ReadEventLog`.
+	pub(in crate::advapi) unsafe fn from_raw(raw: *const EVENTLOGRECORD) -> SysResult<Self> {
+		let base = raw as *const u8;
+		let raw = &*raw;
+
+		let source_name =
+			WString::from_wchars_nullt(base.add(std::mem::size_of::<EVENTLOGRECORD>()) as _);
+		let computer_name = WString::from_wchars_nullt(
+			base.add(std::mem::size_of::<EVENTLOGRECORD>() + source_name.str_len() * 2 + 2) as _,
+		);
+
+		let mut strings = Vec::with_capacity(raw.NumStrings as _);
+		let mut pstr = base.add(raw.StringOffset as _) as *const u16;
+		for _ in 0..raw.NumStrings {
+			let s = WString::from_wchars_nullt(pstr);
+			pstr = pstr.add(s.str_len() + 1);
+			strings.push(s.to_string());
+		}
+
+		Ok(Self {
+			record_number: raw.RecordNumber,
+			time_generated: raw.TimeGenerated,
+			time_written: raw.TimeWritten,
+			event_id: raw.EventID,
+			event_type: co::EVENTLOG::from_raw(raw.EventType),
+			event_category: raw.EventCategory,
+			source_name: source_name.to_string(),
+			computer_name: computer_name.to_string(),
+			user_sid: if raw.UserSidLength == 0 {
+				None
+			} else {
+				Some(CopySid(&*(base.add(raw.UserSidOffset as _) as *const SID))?)
+			},
+			strings,
+			data: std::slice::from_raw_parts(base.add(raw.DataOffset as _), raw.DataLength as _)
+				.to_vec(),
+		})
+	}
+}
+
+impl EvtVariant {
+	const TYPE_NULL: u32 = 0;
+	const TYPE_STRING: u32 = 1;
+	const TYPE_ANSI_STRING: u32 = 2;
+	const TYPE_SBYTE: u32 = 3;
+	const TYPE_BYTE: u32 = 4;
+	const TYPE_INT16: u32 = 5;
+	const TYPE_UINT16: u32 = 6;
+	const TYPE_INT32: u32 = 7;
+	const TYPE_UINT32: u32 = 8;
+	const TYPE_INT64: u32 = 9;
+	const TYPE_UINT64: u32 = 10;
+	const TYPE_SINGLE: u32 = 11;
+	const TYPE_DOUBLE: u32 = 12;
+	const TYPE_BOOLEAN: u32 = 13;
+	const TYPE_BINARY: u32 = 14;
+	const TYPE_GUID: u32 = 15;
+	const TYPE_SIZE_T: u32 = 16;
+	const TYPE_FILE_TIME: u32 = 17;
+	const TYPE_SYS_TIME: u32 = 18;
+	const TYPE_SID: u32 = 19;
+	const TYPE_HEX_INT32: u32 = 20;
+	const TYPE_HEX_INT64: u32 = 21;
+	const TYPE_EVT_XML: u32 = 35;
+	const TYPE_MASK: u32 = 0x7f;
+	const ARRAY_MASK: u32 = 0x80;
+
+	/// Parses an `EVT_VARIANT` struct.
+	///
+	/// # Safety
+	///
+	/// The pointers within the struct must be valid.
+	pub(in crate::advapi) unsafe fn from_raw(raw: &EVT_VARIANT) -> SysResult<Self> {
+		let ty = raw.Type & Self::TYPE_MASK;
+		let pval = &raw.value as *const u64 as *const u8;
+
+		if raw.Type & Self::ARRAY_MASK != 0 {
+			let elem_sz = match Self::elem_size(ty) {
+				Some(sz) => sz,
+				None => return Ok(Self::Null),
+			};
+			let parr = *(pval as *const *const u8);
+			if parr.is_null() {
+				return Ok(Self::Array(Vec::new()));
+			}
+			return (0..raw.Count as usize)
+				.map(|i| Self::parse_elem(ty, parr.add(i * elem_sz)))
+				.collect::<SysResult<Vec<_>>>()
+				.map(Self::Array);
+		}
+
+		match ty {
+			Self::TYPE_BINARY => {
+				let pdata = *(pval as *const *const u8);
+				Ok(Self::Binary(if pdata.is_null() {
+					Vec::new()
+				} else {
+					std::slice::from_raw_parts(pdata, raw.Count as _).to_vec()
+				}))
+			},
+			Self::TYPE_GUID | Self::TYPE_SYS_TIME => {
+				// Scalar holds a pointer to the struct.
+				let pstruct = *(pval as *const *const u8);
+				if pstruct.is_null() {
+					Ok(Self::Null)
+				} else {
+					Self::parse_elem(ty, pstruct)
+				}
+			},
+			_ => Self::parse_elem(ty, pval),
+		}
+	}
+
+	/// Size of each element of an array, or `None` if arrays of this type are
+	/// not supported.
+	#[must_use]
+	fn elem_size(ty: u32) -> Option<usize> {
+		Some(match ty {
+			Self::TYPE_SBYTE | Self::TYPE_BYTE => 1,
+			Self::TYPE_INT16 | Self::TYPE_UINT16 => 2,
+			Self::TYPE_INT32
+			| Self::TYPE_UINT32
+			| Self::TYPE_SINGLE
+			| Self::TYPE_BOOLEAN
+			| Self::TYPE_HEX_INT32 => 4,
+			Self::TYPE_INT64
+			| Self::TYPE_UINT64
+			| Self::TYPE_DOUBLE
+			| Self::TYPE_FILE_TIME
+			| Self::TYPE_HEX_INT64 => 8,
+			Self::TYPE_GUID | Self::TYPE_SYS_TIME => 16,
+			Self::TYPE_STRING
+			| Self::TYPE_ANSI_STRING
+			| Self::TYPE_SIZE_T
+			| Self::TYPE_SID
+			| Self::TYPE_EVT_XML => std::mem::size_of::<usize>(),
+			_ => return None,
+		})
+	}
+
+	/// Parses a single value stored at the given address.
+	#[must_use]
+	unsafe fn parse_elem(ty: u32, p: *const u8) -> SysResult<Self> {
+		Ok(match ty {
+			Self::TYPE_NULL => Self::Null,
+			Self::TYPE_STRING => Self::String(str_at(*(p as *const *const u16))),
+			Self::TYPE_ANSI_STRING => {
+				let pstr = *(p as *const *const std::ffi::c_char);
+				Self::AnsiString(if pstr.is_null() {
+					String::new()
+				} else {
+					std::ffi::CStr::from_ptr(pstr)
+						.to_string_lossy()
+						.into_owned()
+				})
+			},
+			Self::TYPE_SBYTE => Self::SByte(*(p as *const i8)),
+			Self::TYPE_BYTE => Self::Byte(*p),
+			Self::TYPE_INT16 => Self::Int16(std::ptr::read_unaligned(p as *const i16)),
+			Self::TYPE_UINT16 => Self::UInt16(std::ptr::read_unaligned(p as *const u16)),
+			Self::TYPE_INT32 => Self::Int32(std::ptr::read_unaligned(p as *const i32)),
+			Self::TYPE_UINT32 => Self::UInt32(std::ptr::read_unaligned(p as *const u32)),
+			Self::TYPE_INT64 => Self::Int64(std::ptr::read_unaligned(p as *const i64)),
+			Self::TYPE_UINT64 => Self::UInt64(std::ptr::read_unaligned(p as *const u64)),
+			Self::TYPE_SINGLE => Self::Single(std::ptr::read_unaligned(p as *const f32)),
+			Self::TYPE_DOUBLE => Self::Double(std::ptr::read_unaligned(p as *const f64)),
+			Self::TYPE_BOOLEAN => Self::Boolean(std::ptr::read_unaligned(p as *const i32) != 0),
+			Self::TYPE_GUID => Self::Guid(std::ptr::read_unaligned(p as *const GUID)),
+			Self::TYPE_SIZE_T => Self::SizeT(std::ptr::read_unaligned(p as *const usize)),
+			Self::TYPE_FILE_TIME => {
+				Self::FileTime(FILETIME::from(std::ptr::read_unaligned(p as *const u64)))
+			},
+			Self::TYPE_SYS_TIME => Self::SysTime(std::ptr::read_unaligned(p as *const SYSTEMTIME)),
+			Self::TYPE_SID => {
+				let psid = *(p as *const *const SID);
+				if psid.is_null() {
+					Self::Null
+				} else {
+					Self::Sid(CopySid(&*psid)?)
+				}
+			},
+			Self::TYPE_HEX_INT32 => Self::HexInt32(std::ptr::read_unaligned(p as *const u32)),
+			Self::TYPE_HEX_INT64 => Self::HexInt64(std::ptr::read_unaligned(p as *const u64)),
+			Self::TYPE_EVT_XML => Self::Xml(str_at(*(p as *const *const u16))),
+			_ => Self::Null,
+		})
+	}
+}
+
+impl EvtSystemProps {
+	/// Builds the struct from the values rendered with a
+	/// [`EVT_RENDER_CONTEXT::SYSTEM`](crate::co::EVT_RENDER_CONTEXT::SYSTEM)
+	/// context, which follow the `EVT_SYSTEM_PROPERTY_ID` order.
+	#[must_use]
+	pub(in crate::advapi) fn from_values(values: Vec<EvtVariant>) -> Self {
+		let mut props = Self {
+			provider_name: String::new(),
+			provider_guid: None,
+			event_id: 0,
+			qualifiers: 0,
+			level: co::WINEVENT_LEVEL::LOG_ALWAYS,
+			task: 0,
+			opcode: 0,
+			keywords: 0,
+			time_created: FILETIME::default(),
+			event_record_id: 0,
+			activity_id: None,
+			related_activity_id: None,
+			process_id: 0,
+			thread_id: 0,
+			channel: String::new(),
+			computer: String::new(),
+			user_id: None,
+			version: 0,
+		};
+
+		for (id, value) in values.into_iter().enumerate() {
+			match (id, value) {
+				(0, EvtVariant::String(s)) => props.provider_name = s,
+				(1, EvtVariant::Guid(g)) => props.provider_guid = Some(g),
+				(2, EvtVariant::UInt16(n)) => props.event_id = n,
+				(3, EvtVariant::UInt16(n)) => props.qualifiers = n,
+				(4, EvtVariant::Byte(n)) => {
+					props.level = unsafe { co::WINEVENT_LEVEL::from_raw(n) }
+				},
+				(5, EvtVariant::UInt16(n)) => props.task = n,
+				(6, EvtVariant::Byte(n)) => props.opcode = n,
+				(7, EvtVariant::HexInt64(n)) | (7, EvtVariant::UInt64(n)) => props.keywords = n,
+				(8, EvtVariant::FileTime(ft)) => props.time_created = ft,
+				(9, EvtVariant::UInt64(n)) => props.event_record_id = n,
+				(10, EvtVariant::Guid(g)) => props.activity_id = Some(g),
+				(11, EvtVariant::Guid(g)) => props.related_activity_id = Some(g),
+				(12, EvtVariant::UInt32(n)) => props.process_id = n,
+				(13, EvtVariant::UInt32(n)) => props.thread_id = n,
+				(14, EvtVariant::String(s)) => props.channel = s,
+				(15, EvtVariant::String(s)) => props.computer = s,
+				(16, EvtVariant::Sid(sid)) => props.user_id = Some(sid),
+				(17, EvtVariant::Byte(n)) => props.version = n,
+				_ => {}, // property not present in the event
+			}
+		}
+		props
+	}
+}
@@ -0,0 +1,25 @@
+pub(in crate::advapi) mod event_log;
+mod reg_file;
+mod reg_settings;
+mod reg_watcher;
+mod security_descriptor;
+pub(in crate::advapi) mod service_config;
+mod service_host;
+
+pub mod decl {
+	pub use super::event_log::{EventLogRecord, EvtRendered, EvtSystemProps, EvtVariant};
+	pub use super::reg_file::{RegFile, RegFileKey};
+	pub use super::reg_settings::{RegBinder, RegMemKey};
+	pub use super::reg_watcher::{RegValueChange, RegWatcher};
+	pub use super::security_descriptor::{Ace, Acl, SecurityDescriptor};
+	pub use super::service_config::{
+		ScAction, ServiceConfig, ServiceConfig2, ServiceEntry, ServiceFailureActions,
+		ServiceTrigger, ServiceTriggerData,
+	};
+	pub use super::service_host::{ServiceHost, ServiceStatus};
+}
+
+pub mod traits {
+	pub use super::reg_settings::{RegSettingValue, RegSettings, RegStore};
+	pub use super::service_host::{HostedService, ServiceStatusSink};
+}
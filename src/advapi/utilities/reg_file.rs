@@ -0,0 +1,557 @@
+use crate::co;
+use crate::decl::*;
+
+/// A key entry of a [`RegFile`](crate::RegFile), which either sets values or
+/// deletes the whole key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegFileKey {
+	/// Full path of the key, starting with the root key name, like
+	/// `HKEY_CURRENT_USER\Software\Foo`.
+	pub path: String,
+	/// If `true`, the key is deleted along with its sub keys, written as
+	/// `[-path]`; `values` are then ignored.
+	pub delete: bool,
+	/// The values of the key, in the order they appear. An empty name stands
+	/// for the default value, written as `@`, and a `None` value is a deletion,
+	/// written as `"name"=-`.
+	pub values: Vec<(String, Option<RegistryValue>)>,
+}
+
+impl RegFileKey {
+	/// Creates a new key entry, with no values.
+	#[must_use]
+	pub fn new(path: &str) -> Self {
+		Self {
+			path: path.to_owned(),
+			delete: false,
+			values: Vec::new(),
+		}
+	}
+
+	/// Creates a new key entry which deletes the key.
+	#[must_use]
+	pub fn new_delete(path: &str) -> Self {
+		Self {
+			path: path.to_owned(),
+			delete: true,
+			values: Vec::new(),
+		}
+	}
+
+	/// Returns the value with the given name, if any. An empty name stands for
+	/// the default value.
+	#[must_use]
+	pub fn get(&self, name: &str) -> Option<&Option<RegistryValue>> {
+		self.values
+			.iter()
+			.find(|(n, _)| n.eq_ignore_ascii_case(name))
+			.map(|(_, v)| v)
+	}
+
+	/// Sets the value with the given name, replacing an existing one. An empty
+	/// name stands for the default value, and a `None` value is a deletion.
+	pub fn set(&mut self, name: &str, value: Option<RegistryValue>) {
+		match self
+			.values
+			.iter_mut()
+			.find(|(n, _)| n.eq_ignore_ascii_case(name))
+		{
+			Some((_, v)) => *v = value,
+			None => self.values.push((name.to_owned(), value)),
+		}
+	}
+}
+
+/// Registry entries in the
+/// [`.reg` file format](https://support.microsoft.com/en-us/topic/how-to-add-modify-or-delete-registry-subkeys-and-values-by-using-a-reg-file-9c7f37cf-a5e9-e1cd-c4fa-2a26218a1a23)
+/// written by Regedit, "Windows Registry Editor Version 5.00".
+///
+/// This is a pure format conversion, which does not touch the registry. To
+/// export and import registry keys, see
+/// [`HKEY::export_tree`](crate::prelude::advapi_Hkey::export_tree) and
+/// [`HKEY::import_tree`](crate::prelude::advapi_Hkey::import_tree).
+///
+/// Values of types [`REG::EXPAND_SZ`](crate::co::REG::EXPAND_SZ),
+/// [`REG::MULTI_SZ`](crate::co::REG::MULTI_SZ) and
+/// [`REG::QWORD`](crate::co::REG::QWORD) are written as `hex(2)`, `hex(7)` and
+/// `hex(b)`, respectively. [`REG::SZ`](crate::co::REG::SZ) values with line
+/// breaks or nulls are written as `hex(1)`; since a string value ends at its
+/// first null, anything after an embedded null is lost when parsed back.
+///
+/// Files of the older "REGEDIT4" format can also be parsed. Their string
+/// data, written in ANSI, is read one byte per char, like
+/// [`Encoding::Ansi`](crate::Encoding::Ansi).
+///
+/// # Examples
+///
+/// Parsing a `.reg` file:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let contents = std::fs::read("C:\\Temp\\foo.reg").unwrap();
+/// let reg_file = w::RegFile::from_bytes(&contents)?;
+///
+/// for key in reg_file.keys.iter() {
+///     println!("{}", key.path);
+///     for (name, val) in key.values.iter() {
+///         match val {
+///             Some(val) => println!("  {} = {}", name, val),
+///             None => println!("  {} deleted", name),
+///         }
+///     }
+/// }
+/// # w::SysResult::Ok(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RegFile {
+	/// The key entries, in the order they appear.
+	pub keys: Vec<RegFileKey>,
+}
+
+impl RegFile {
+	const HEADER: &'static str = "Windows Registry Editor Version 5.00";
+	const HEADER_V4: &'static str = "REGEDIT4";
+	const LINE_LEN: usize = 80; // hex lines are wrapped near this length
+
+	/// Creates a new, empty `RegFile`.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Parses the raw contents of a `.reg` file, whose encoding is guessed with
+	/// [`Encoding::guess`](crate::Encoding::guess). Regedit writes UTF-16 files
+	/// with a BOM.
+	#[must_use]
+	pub fn from_bytes(data: &[u8]) -> SysResult<Self> {
+		Self::parse(&WString::parse(data)?.to_string())
+	}
+
+	/// Serializes the entries into the raw contents of a `.reg` file, encoded as
+	/// UTF-16 little endian with a BOM, just like Regedit does.
+	#[must_use]
+	pub fn to_bytes(&self) -> SysResult<Vec<u8>> {
+		WString::from_str(self.serialize()).encode(Encoding::Utf16le, true)
+	}
+
+	/// Parses the text of a `.reg` file.
+	///
+	/// Fails with [`ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA) if
+	/// the text is malformed, and with
+	/// [`ERROR::CALL_NOT_IMPLEMENTED`](crate::co::ERROR::CALL_NOT_IMPLEMENTED)
+	/// if a value type is not supported by
+	/// [`RegistryValue`](crate::RegistryValue).
+	#[must_use]
+	pub fn parse(text: &str) -> SysResult<Self> {
+		let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+		let mut lines = Self::join_lines(text).into_iter().filter(|line| {
+			let line = line.trim();
+			!line.is_empty() && !line.starts_with(';') // skip blank lines and comments
+		});
+
+		let is_v4 = match lines.next() {
+			Some(line) if line.trim() == Self::HEADER => false,
+			Some(line) if line.trim() == Self::HEADER_V4 => true,
+			_ => return Err(co::ERROR::INVALID_DATA),
+		};
+
+		let mut keys = Vec::<RegFileKey>::new();
+		for line in lines {
+			let line = line.trim();
+			if let Some(path) = line.strip_prefix('[') {
+				let path = path.strip_suffix(']').ok_or(co::ERROR::INVALID_DATA)?;
+				keys.push(match path.strip_prefix('-') {
+					Some(path) => RegFileKey::new_delete(path.trim()),
+					None => RegFileKey::new(path.trim()),
+				});
+			} else {
+				let key = keys.last_mut().ok_or(co::ERROR::INVALID_DATA)?; // value before any key
+				let (name, rest) = if let Some(rest) = line.strip_prefix('@') {
+					(String::new(), rest)
+				} else {
+					Self::parse_quoted(line)?
+				};
+				let data = rest
+					.trim_start()
+					.strip_prefix('=')
+					.ok_or(co::ERROR::INVALID_DATA)?
+					.trim_start();
+				key.values.push((name, Self::parse_data(data, is_v4)?));
+			}
+		}
+		Ok(Self { keys })
+	}
+
+	/// Joins the hex lines continued with a trailing backslash.
+	#[must_use]
+	fn join_lines(text: &str) -> Vec<String> {
+		let mut joined = Vec::<String>::new();
+		let mut continues = false;
+
+		for line in text.lines() {
+			let line = if continues {
+				let prev = joined.last_mut().unwrap();
+				prev.push_str(line.trim_start());
+				prev
+			} else {
+				joined.push(line.to_owned());
+				joined.last_mut().unwrap()
+			};
+
+			let trimmed_len = line.trim_end().len();
+			continues = !line.starts_with('[')
+				&& !line.starts_with(';')
+				&& line[..trimmed_len].ends_with('\\');
+			if continues {
+				line.truncate(trimmed_len - 1); // remove the backslash
+			}
+		}
+		joined
+	}
+
+	/// Parses a quoted string at the beginning of the text, returning it
+	/// unescaped, along with the rest of the text.
+	#[must_use]
+	fn parse_quoted(text: &str) -> SysResult<(String, &str)> {
+		let text = text.strip_prefix('"').ok_or(co::ERROR::INVALID_DATA)?;
+		let mut s = String::new();
+		let mut chars = text.char_indices();
+
+		while let Some((_, ch)) = chars.next() {
+			match ch {
+				'"' => return Ok((s, chars.as_str())),
+				'\\' => match chars.next() {
+					Some((_, esc @ ('\\' | '"'))) => s.push(esc),
+					Some((_, other)) => {
+						s.push('\\'); // unknown escapes are kept verbatim
+						s.push(other);
+					},
+					None => break,
+				},
+				ch => s.push(ch),
+			}
+		}
+		Err(co::ERROR::INVALID_DATA) // missing closing quote
+	}
+
+	/// Parses the data after the `=` sign; `None` means a value deletion.
+	#[must_use]
+	fn parse_data(data: &str, is_v4: bool) -> SysResult<Option<RegistryValue>> {
+		if data == "-" {
+			Ok(None)
+		} else if data.starts_with('"') {
+			let (s, rest) = Self::parse_quoted(data)?;
+			if !rest.trim().is_empty() {
+				return Err(co::ERROR::INVALID_DATA);
+			}
+			Ok(Some(RegistryValue::Sz(s)))
+		} else if let Some(hex) = data.strip_prefix("dword:") {
+			let hex = hex.trim();
+			if hex.is_empty() || hex.len() > 8 {
+				return Err(co::ERROR::INVALID_DATA);
+			}
+			u32::from_str_radix(hex, 16)
+				.map(|n| Some(RegistryValue::Dword(n)))
+				.map_err(|_| co::ERROR::INVALID_DATA)
+		} else if let Some(bytes) = data.strip_prefix("hex:") {
+			Ok(Some(RegistryValue::Binary(Self::parse_hex_bytes(bytes)?)))
+		} else if let Some(rest) = data.strip_prefix("hex(") {
+			let (reg_type, bytes) = rest.split_once("):").ok_or(co::ERROR::INVALID_DATA)?;
+			let reg_type =
+				u32::from_str_radix(reg_type.trim(), 16).map_err(|_| co::ERROR::INVALID_DATA)?;
+			let mut bytes = Self::parse_hex_bytes(bytes)?;
+			if is_v4 && matches!(reg_type, 1 | 2 | 7) {
+				bytes = bytes.iter().flat_map(|b| [*b, 0x00]).collect(); // ANSI to UTF-16
+			}
+			unsafe { RegistryValue::from_raw(bytes, co::REG::from_raw(reg_type)) }.map(Some)
+		} else {
+			Err(co::ERROR::INVALID_DATA)
+		}
+	}
+
+	/// Parses comma-separated hex bytes, like `01,ab,ff`.
+	#[must_use]
+	fn parse_hex_bytes(text: &str) -> SysResult<Vec<u8>> {
+		let text = text.trim();
+		let text = text.strip_suffix(',').unwrap_or(text);
+		if text.is_empty() {
+			return Ok(Vec::new());
+		}
+		text.split(',')
+			.map(|b| {
+				let b = b.trim();
+				if b.is_empty() || b.len() > 2 {
+					Err(co::ERROR::INVALID_DATA)
+				} else {
+					u8::from_str_radix(b, 16).map_err(|_| co::ERROR::INVALID_DATA)
+				}
+			})
+			.collect()
+	}
+
+	/// Serializes the entries into the text of a `.reg` file, with `\r\n` line
+	/// endings.
+	#[must_use]
+	pub fn serialize(&self) -> String {
+		let mut out = String::new();
+		out.push_str(Self::HEADER);
+		out.push_str("\r\n\r\n");
+
+		for key in self.keys.iter() {
+			if key.delete {
+				out.push_str(&format!("[-{}]\r\n\r\n", key.path));
+				continue;
+			}
+
+			out.push_str(&format!("[{}]\r\n", key.path));
+			for (name, val) in key.values.iter() {
+				let mut line = if name.is_empty() {
+					"@=".to_owned()
+				} else {
+					format!("{}=", Self::quote(name))
+				};
+
+				match val {
+					None => line.push('-'),
+					Some(RegistryValue::Sz(s)) if !s.contains(['\r', '\n', '\0']) => {
+						line.push_str(&Self::quote(s))
+					},
+					Some(RegistryValue::Sz(s)) => {
+						Self::push_hex(&mut line, "hex(1):", &Self::str_bytes(&[s]))
+					},
+					Some(RegistryValue::Dword(n)) => line.push_str(&format!("dword:{:08x}", n)),
+					Some(RegistryValue::Binary(b)) => Self::push_hex(&mut line, "hex:", b),
+					Some(RegistryValue::ExpandSz(s)) => {
+						Self::push_hex(&mut line, "hex(2):", &Self::str_bytes(&[s]))
+					},
+					Some(RegistryValue::MultiSz(v)) => {
+						let mut bytes = Self::str_bytes(v);
+						bytes.extend_from_slice(&[0x00, 0x00]); // final terminator
+						Self::push_hex(&mut line, "hex(7):", &bytes)
+					},
+					Some(RegistryValue::Qword(n)) => {
+						Self::push_hex(&mut line, "hex(b):", &n.to_le_bytes())
+					},
+					Some(RegistryValue::None) => line.push_str("hex(0):"),
+				}
+
+				out.push_str(&line);
+				out.push_str("\r\n");
+			}
+			out.push_str("\r\n");
+		}
+		out
+	}
+
+	/// Quotes and escapes a string.
+	#[must_use]
+	fn quote(s: &str) -> String {
+		format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+	}
+
+	/// Encodes the strings as UTF-16 little endian, each one null-terminated.
+	#[must_use]
+	fn str_bytes<S: AsRef<str>>(strs: &[S]) -> Vec<u8> {
+		strs.iter()
+			.flat_map(|s| s.as_ref().encode_utf16().chain([0x0000]))
+			.flat_map(|ch| ch.to_le_bytes())
+			.collect()
+	}
+
+	/// Appends the tag and the comma-separated hex bytes, breaking long lines
+	/// with a trailing backslash, like Regedit does.
+	fn push_hex(line: &mut String, tag: &str, bytes: &[u8]) {
+		line.push_str(tag);
+		let mut line_len = line.chars().count();
+
+		for (idx, b) in bytes.iter().enumerate() {
+			line.push_str(&format!("{:02x}", b));
+			if idx + 1 < bytes.len() {
+				line.push(',');
+				line_len += 3;
+				if line_len > Self::LINE_LEN - 4 {
+					line.push_str("\\\r\n  ");
+					line_len = 2;
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn crlf(lines: &[&str]) -> String {
+		lines.iter().map(|line| format!("{}\r\n", line)).collect()
+	}
+
+	fn sample() -> RegFile {
+		let mut key = RegFileKey::new("HKEY_CURRENT_USER\\Software\\Foo");
+		key.set("", Some(RegistryValue::Sz("default".to_owned())));
+		key.set("Path", Some(RegistryValue::Sz("C:\\Temp\\\"x\"".to_owned())));
+		key.set("Count", Some(RegistryValue::Dword(42)));
+		key.set("Expand", Some(RegistryValue::ExpandSz("%A%".to_owned())));
+		key.set("Multi", Some(RegistryValue::MultiSz(vec!["a".to_owned(), "b".to_owned()])));
+		key.set("Big", Some(RegistryValue::Qword(1)));
+		key.set("Gone", None);
+
+		RegFile {
+			keys: vec![key, RegFileKey::new_delete("HKEY_CURRENT_USER\\Software\\Old")],
+		}
+	}
+
+	fn sample_text() -> String {
+		crlf(&[
+			"Windows Registry Editor Version 5.00",
+			"",
+			"[HKEY_CURRENT_USER\\Software\\Foo]",
+			"@=\"default\"",
+			"\"Path\"=\"C:\\\\Temp\\\\\\\"x\\\"\"",
+			"\"Count\"=dword:0000002a",
+			"\"Expand\"=hex(2):25,00,41,00,25,00,00,00",
+			"\"Multi\"=hex(7):61,00,00,00,62,00,00,00,00,00",
+			"\"Big\"=hex(b):01,00,00,00,00,00,00,00",
+			"\"Gone\"=-",
+			"",
+			"[-HKEY_CURRENT_USER\\Software\\Old]",
+			"",
+		])
+	}
+
+	#[test]
+	fn serialize_known() {
+		assert_eq!(sample().serialize(), sample_text());
+	}
+
+	#[test]
+	fn parse_known() {
+		let reg_file = RegFile::parse(&sample_text()).unwrap();
+		assert_eq!(reg_file, sample());
+
+		let key = &reg_file.keys[0];
+		assert_eq!(key.get(""), Some(&Some(RegistryValue::Sz("default".to_owned()))));
+		assert_eq!(key.get("count"), Some(&Some(RegistryValue::Dword(42))));
+		assert_eq!(key.get("Gone"), Some(&None));
+		assert_eq!(key.get("Missing"), None);
+		assert!(reg_file.keys[1].delete);
+	}
+
+	#[test]
+	fn round_trip() {
+		let mut key = RegFileKey::new("HKEY_LOCAL_MACHINE\\Software\\Bar");
+		key.set("Bin", Some(RegistryValue::Binary((0..100).collect()))); // wrapped lines
+		key.set("Empty", Some(RegistryValue::Binary(Vec::new())));
+		key.set("Lines", Some(RegistryValue::Sz("a\r\nb".to_owned()))); // written as hex(1)
+		key.set("Quotes \"and\" \\", Some(RegistryValue::Sz(String::new())));
+		key.set("NoMulti", Some(RegistryValue::MultiSz(Vec::new())));
+		key.set("MaxDword", Some(RegistryValue::Dword(u32::MAX)));
+		key.set("MaxQword", Some(RegistryValue::Qword(u64::MAX)));
+		key.set("None", Some(RegistryValue::None));
+		let reg_file = RegFile {
+			keys: vec![key, RegFileKey::new("HKEY_USERS\\Empty")],
+		};
+
+		let text = reg_file.serialize();
+		assert!(text.contains(",\\\r\n  "));
+		assert!(text.lines().all(|line| line.len() <= RegFile::LINE_LEN));
+		assert_eq!(RegFile::parse(&text).unwrap(), reg_file);
+	}
+
+	#[test]
+	fn parse_continuation() {
+		let text = crlf(&[
+			"Windows Registry Editor Version 5.00",
+			"[HKEY_CURRENT_USER\\Software\\Foo]",
+			"\"Data\"=hex:01,02,\\",
+			"  03,\\",
+			"    04",
+			"; a comment",
+			"\"Expand\"=hex(2):25,00,41,00,\\",
+			"  25,00,00,00",
+		]);
+		let reg_file = RegFile::parse(&text).unwrap();
+		let key = &reg_file.keys[0];
+		assert_eq!(key.get("Data"), Some(&Some(RegistryValue::Binary(vec![1, 2, 3, 4]))));
+		assert_eq!(key.get("Expand"), Some(&Some(RegistryValue::ExpandSz("%A%".to_owned()))));
+	}
+
+	#[test]
+	fn parse_escapes() {
+		let text = crlf(&[
+			"Windows Registry Editor Version 5.00",
+			"[HKEY_CURRENT_USER\\Software\\Foo]",
+			"\"a\\\"b\"=\"c:\\\\d\\\\\"",
+			"\"unknown\"=\"\\n\"",
+		]);
+		let key = &RegFile::parse(&text).unwrap().keys[0];
+		assert_eq!(
+			key.values[0],
+			("a\"b".to_owned(), Some(RegistryValue::Sz("c:\\d\\".to_owned())))
+		);
+		assert_eq!(key.values[1].1, Some(RegistryValue::Sz("\\n".to_owned()))); // kept verbatim
+	}
+
+	#[test]
+	fn parse_v4() {
+		let text = crlf(&[
+			"REGEDIT4",
+			"",
+			"[HKEY_CURRENT_USER\\Software\\Foo]",
+			"\"Name\"=\"value\"",
+			"\"Count\"=dword:00000001",
+			"\"Expand\"=hex(2):25,41,25,00",
+			"\"Multi\"=hex(7):61,00,62,00,00",
+		]);
+		let key = &RegFile::parse(&text).unwrap().keys[0];
+		assert_eq!(key.get("Name"), Some(&Some(RegistryValue::Sz("value".to_owned()))));
+		assert_eq!(key.get("Count"), Some(&Some(RegistryValue::Dword(1))));
+		assert_eq!(key.get("Expand"), Some(&Some(RegistryValue::ExpandSz("%A%".to_owned()))));
+		assert_eq!(
+			key.get("Multi"),
+			Some(&Some(RegistryValue::MultiSz(vec!["a".to_owned(), "b".to_owned()]))),
+		);
+
+		// Always written in the 5.00 format.
+		assert!(RegFile::parse(&text)
+			.unwrap()
+			.serialize()
+			.starts_with(RegFile::HEADER));
+	}
+
+	#[test]
+	fn embedded_null_is_lost() {
+		let mut key = RegFileKey::new("HKEY_CURRENT_USER\\Software\\Foo");
+		key.set("Z", Some(RegistryValue::Sz("a\0b".to_owned())));
+		let text = RegFile { keys: vec![key] }.serialize();
+		assert!(text.contains("\"Z\"=hex(1):61,00,00,00,62,00,00,00"));
+
+		let parsed = RegFile::parse(&text).unwrap();
+		assert_eq!(parsed.keys[0].get("Z"), Some(&Some(RegistryValue::Sz("a".to_owned()))));
+	}
+
+	#[test]
+	fn parse_malformed() {
+		let parse = |lines: &[&str]| {
+			let mut all = vec!["Windows Registry Editor Version 5.00"];
+			all.extend_from_slice(lines);
+			RegFile::parse(&crlf(&all)).err()
+		};
+		let invalid = Some(co::ERROR::INVALID_DATA);
+
+		assert_eq!(RegFile::parse("").err(), invalid);
+		assert_eq!(RegFile::parse("REGEDIT5\r\n").err(), invalid);
+		assert_eq!(parse(&["\"a\"=\"b\""]), invalid); // value before key
+		assert_eq!(parse(&["[HKEY_CURRENT_USER", "\"a\"=\"b\""]), invalid);
+		assert_eq!(parse(&["[HKEY_CURRENT_USER]", "\"a=\"b\""]), invalid);
+		assert_eq!(parse(&["[HKEY_CURRENT_USER]", "\"a\"=\"b\" x"]), invalid);
+		assert_eq!(parse(&["[HKEY_CURRENT_USER]", "\"a\"=dword:123456789"]), invalid);
+		assert_eq!(parse(&["[HKEY_CURRENT_USER]", "\"a\"=dword:xyz"]), invalid);
+		assert_eq!(parse(&["[HKEY_CURRENT_USER]", "\"a\"=hex:1,,2"]), invalid);
+		assert_eq!(parse(&["[HKEY_CURRENT_USER]", "\"a\"=hex:100"]), invalid);
+		assert_eq!(parse(&["[HKEY_CURRENT_USER]", "\"a\"=hex(zz):00"]), invalid);
+		assert_eq!(parse(&["[HKEY_CURRENT_USER]", "\"a\"=str:x"]), invalid);
+		assert_eq!(parse(&["[HKEY_CURRENT_USER]", "\"a\" \"b\""]), invalid);
+	}
+}
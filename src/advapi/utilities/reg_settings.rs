@@ -0,0 +1,587 @@
+use crate::co;
+use crate::decl::*;
+use crate::prelude::*;
+
+/// A type which can be stored as a single registry value, used by
+/// [`RegBinder::value`](crate::RegBinder::value).
+///
+/// Conversions from a [`RegistryValue`](crate::RegistryValue) of an
+/// unexpected type fail with
+/// [`ERROR::DATATYPE_MISMATCH`](crate::co::ERROR::DATATYPE_MISMATCH).
+pub trait RegSettingValue: Sized {
+	/// Converts the field into a registry value.
+	#[must_use]
+	fn to_registry_value(&self) -> RegistryValue;
+
+	/// Converts a registry value back into the field.
+	#[must_use]
+	fn from_registry_value(val: RegistryValue) -> SysResult<Self>;
+}
+
+impl RegSettingValue for bool {
+	fn to_registry_value(&self) -> RegistryValue {
+		RegistryValue::Dword(*self as _)
+	}
+
+	fn from_registry_value(val: RegistryValue) -> SysResult<Self> {
+		match val {
+			RegistryValue::Dword(n) => Ok(n != 0),
+			_ => Err(co::ERROR::DATATYPE_MISMATCH),
+		}
+	}
+}
+
+impl RegSettingValue for u32 {
+	fn to_registry_value(&self) -> RegistryValue {
+		RegistryValue::Dword(*self)
+	}
+
+	fn from_registry_value(val: RegistryValue) -> SysResult<Self> {
+		match val {
+			RegistryValue::Dword(n) => Ok(n),
+			_ => Err(co::ERROR::DATATYPE_MISMATCH),
+		}
+	}
+}
+
+impl RegSettingValue for i32 {
+	fn to_registry_value(&self) -> RegistryValue {
+		RegistryValue::Dword(*self as _)
+	}
+
+	fn from_registry_value(val: RegistryValue) -> SysResult<Self> {
+		u32::from_registry_value(val).map(|n| n as _)
+	}
+}
+
+impl RegSettingValue for u64 {
+	fn to_registry_value(&self) -> RegistryValue {
+		RegistryValue::Qword(*self)
+	}
+
+	fn from_registry_value(val: RegistryValue) -> SysResult<Self> {
+		match val {
+			RegistryValue::Qword(n) => Ok(n),
+			RegistryValue::Dword(n) => Ok(n as _), // widened
+			_ => Err(co::ERROR::DATATYPE_MISMATCH),
+		}
+	}
+}
+
+impl RegSettingValue for i64 {
+	fn to_registry_value(&self) -> RegistryValue {
+		RegistryValue::Qword(*self as _)
+	}
+
+	fn from_registry_value(val: RegistryValue) -> SysResult<Self> {
+		match val {
+			RegistryValue::Qword(n) => Ok(n as _),
+			RegistryValue::Dword(n) => Ok(n as i32 as _), // widened, keeping the sign
+			_ => Err(co::ERROR::DATATYPE_MISMATCH),
+		}
+	}
+}
+
+impl RegSettingValue for String {
+	fn to_registry_value(&self) -> RegistryValue {
+		RegistryValue::Sz(self.clone())
+	}
+
+	fn from_registry_value(val: RegistryValue) -> SysResult<Self> {
+		match val {
+			RegistryValue::Sz(s) | RegistryValue::ExpandSz(s) => Ok(s),
+			_ => Err(co::ERROR::DATATYPE_MISMATCH),
+		}
+	}
+}
+
+impl RegSettingValue for Vec<String> {
+	fn to_registry_value(&self) -> RegistryValue {
+		RegistryValue::MultiSz(self.clone())
+	}
+
+	fn from_registry_value(val: RegistryValue) -> SysResult<Self> {
+		match val {
+			RegistryValue::MultiSz(v) => Ok(v),
+			_ => Err(co::ERROR::DATATYPE_MISMATCH),
+		}
+	}
+}
+
+impl RegSettingValue for Vec<u8> {
+	fn to_registry_value(&self) -> RegistryValue {
+		RegistryValue::Binary(self.clone())
+	}
+
+	fn from_registry_value(val: RegistryValue) -> SysResult<Self> {
+		match val {
+			RegistryValue::Binary(b) => Ok(b),
+			_ => Err(co::ERROR::DATATYPE_MISMATCH),
+		}
+	}
+}
+
+impl RegSettingValue for RegistryValue {
+	fn to_registry_value(&self) -> RegistryValue {
+		self.clone()
+	}
+
+	fn from_registry_value(val: RegistryValue) -> SysResult<Self> {
+		Ok(val)
+	}
+}
+
+/// A storage of registry keys and values, where
+/// [`RegSettings`](crate::prelude::RegSettings) are loaded from and saved to.
+///
+/// Key paths are relative to the store root, with the sub keys separated by
+/// backslashes; an empty path is the root itself, and an empty value name is
+/// the default value.
+///
+/// This trait is implemented by [`HKEY`](crate::HKEY), which reads and writes
+/// the actual registry, and by [`RegMemKey`](crate::RegMemKey), an in-memory
+/// key tree.
+pub trait RegStore {
+	/// Retrieves a value, returning `None` if the key or the value doesn't
+	/// exist.
+	#[must_use]
+	fn query_value(&self, key_path: &str, name: &str) -> SysResult<Option<RegistryValue>>;
+
+	/// Sets a value, creating the key if it doesn't exist.
+	fn set_value(&mut self, key_path: &str, name: &str, val: RegistryValue) -> SysResult<()>;
+}
+
+impl RegStore for HKEY {
+	fn query_value(&self, key_path: &str, name: &str) -> SysResult<Option<RegistryValue>> {
+		let res = if key_path.is_empty() {
+			self.RegQueryValueEx(Some(name))
+		} else {
+			match self.RegOpenKeyEx(Some(key_path), co::REG_OPTION::default(), co::KEY::QUERY_VALUE)
+			{
+				Ok(hkey) => hkey.RegQueryValueEx(Some(name)),
+				Err(e) => Err(e),
+			}
+		};
+
+		match res {
+			Ok(val) => Ok(Some(val)),
+			Err(co::ERROR::FILE_NOT_FOUND) => Ok(None),
+			Err(e) => Err(e),
+		}
+	}
+
+	fn set_value(&mut self, key_path: &str, name: &str, val: RegistryValue) -> SysResult<()> {
+		if key_path.is_empty() {
+			self.RegSetValueEx(Some(name), val)
+		} else {
+			let (hkey, _) = self.RegCreateKeyEx(
+				key_path,
+				None,
+				co::REG_OPTION::default(),
+				co::KEY::SET_VALUE,
+				None,
+			)?;
+			hkey.RegSetValueEx(Some(name), val)
+		}
+	}
+}
+
+/// An in-memory registry key, with its values and sub keys, which implements
+/// [`RegStore`](crate::prelude::RegStore).
+///
+/// Just like in the registry, the names of values and sub keys are
+/// case-insensitive.
+///
+/// # Examples
+///
+/// ```
+/// use winsafe::{self as w, prelude::*};
+///
+/// let mut root = w::RegMemKey::new();
+/// root.set_value("Software\\MyApp", "Width", w::RegistryValue::Dword(800))?;
+///
+/// let app_key = root.sub_key("Software\\MyApp").unwrap();
+/// assert_eq!(app_key.value("width"), Some(&w::RegistryValue::Dword(800)));
+/// # w::SysResult::Ok(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RegMemKey {
+	values: Vec<(String, RegistryValue)>,
+	sub_keys: Vec<(String, RegMemKey)>,
+}
+
+impl RegStore for RegMemKey {
+	fn query_value(&self, key_path: &str, name: &str) -> SysResult<Option<RegistryValue>> {
+		Ok(self
+			.sub_key(key_path)
+			.and_then(|key| key.value(name))
+			.cloned())
+	}
+
+	fn set_value(&mut self, key_path: &str, name: &str, val: RegistryValue) -> SysResult<()> {
+		let key = key_path
+			.split('\\')
+			.filter(|name| !name.is_empty())
+			.fold(self, |key, name| key.create_sub_key(name));
+
+		match key
+			.values
+			.iter_mut()
+			.find(|(n, _)| n.eq_ignore_ascii_case(name))
+		{
+			Some((_, v)) => *v = val,
+			None => key.values.push((name.to_owned(), val)),
+		}
+		Ok(())
+	}
+}
+
+impl RegMemKey {
+	/// Creates a new, empty key.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the values of this key, in the order they were created.
+	#[must_use]
+	pub fn values(&self) -> &[(String, RegistryValue)] {
+		&self.values
+	}
+
+	/// Returns the direct sub keys of this key, in the order they were created.
+	#[must_use]
+	pub fn sub_keys(&self) -> &[(String, RegMemKey)] {
+		&self.sub_keys
+	}
+
+	/// Returns the value with the given name, if any.
+	#[must_use]
+	pub fn value(&self, name: &str) -> Option<&RegistryValue> {
+		self.values
+			.iter()
+			.find(|(n, _)| n.eq_ignore_ascii_case(name))
+			.map(|(_, v)| v)
+	}
+
+	/// Deletes the value with the given name, returning it, if any.
+	pub fn delete_value(&mut self, name: &str) -> Option<RegistryValue> {
+		self.values
+			.iter()
+			.position(|(n, _)| n.eq_ignore_ascii_case(name))
+			.map(|idx| self.values.remove(idx).1)
+	}
+
+	/// Returns the sub key at the given path, relative to this key, if any. An
+	/// empty path returns this key itself.
+	#[must_use]
+	pub fn sub_key(&self, key_path: &str) -> Option<&RegMemKey> {
+		key_path
+			.split('\\')
+			.filter(|name| !name.is_empty())
+			.try_fold(self, |key, name| {
+				key.sub_keys
+					.iter()
+					.find(|(n, _)| n.eq_ignore_ascii_case(name))
+					.map(|(_, k)| k)
+			})
+	}
+
+	/// Returns the direct sub key with the given name, creating it if it
+	/// doesn't exist.
+	pub fn create_sub_key(&mut self, name: &str) -> &mut RegMemKey {
+		let idx = match self
+			.sub_keys
+			.iter()
+			.position(|(n, _)| n.eq_ignore_ascii_case(name))
+		{
+			Some(idx) => idx,
+			None => {
+				self.sub_keys.push((name.to_owned(), RegMemKey::new()));
+				self.sub_keys.len() - 1
+			},
+		};
+		&mut self.sub_keys[idx].1
+	}
+
+	/// Deletes the direct sub key with the given name, along with its own sub
+	/// keys, returning it, if any.
+	pub fn delete_sub_key(&mut self, name: &str) -> Option<RegMemKey> {
+		self.sub_keys
+			.iter()
+			.position(|(n, _)| n.eq_ignore_ascii_case(name))
+			.map(|idx| self.sub_keys.remove(idx).1)
+	}
+}
+
+enum BindMode<'a> {
+	Load(&'a dyn RegStore),
+	Save(&'a mut dyn RegStore),
+}
+
+/// Binds the fields of a [`RegSettings`](crate::prelude::RegSettings) to
+/// registry values and sub keys. Depending on the operation, the fields are
+/// either loaded from or saved to a [`RegStore`](crate::prelude::RegStore).
+pub struct RegBinder<'a> {
+	mode: BindMode<'a>,
+	key_path: String,
+}
+
+impl<'a> RegBinder<'a> {
+	/// Returns `true` if the fields are being loaded, or `false` if they are
+	/// being saved.
+	#[must_use]
+	pub const fn is_loading(&self) -> bool {
+		matches!(self.mode, BindMode::Load(_))
+	}
+
+	/// Returns the path of the key currently bound, relative to the store root.
+	#[must_use]
+	pub fn key_path(&self) -> &str {
+		&self.key_path
+	}
+
+	/// Binds a field to a value of the current key. An empty name stands for the
+	/// default value.
+	///
+	/// When loading, if the value doesn't exist, the field is left untouched,
+	/// thus keeping its default.
+	pub fn value<T: RegSettingValue>(&mut self, name: &str, field: &mut T) -> SysResult<()> {
+		match &mut self.mode {
+			BindMode::Load(store) => {
+				if let Some(val) = store.query_value(&self.key_path, name)? {
+					*field = T::from_registry_value(val)?;
+				}
+				Ok(())
+			},
+			BindMode::Save(store) => {
+				store.set_value(&self.key_path, name, field.to_registry_value())
+			},
+		}
+	}
+
+	/// Binds a nested struct to a sub key of the current key.
+	///
+	/// When loading, if the sub key doesn't exist, the fields are left
+	/// untouched, thus keeping their defaults.
+	pub fn sub_key<T: RegSettings>(&mut self, name: &str, field: &mut T) -> SysResult<()> {
+		let prev_len = self.key_path.len();
+		if !self.key_path.is_empty() {
+			self.key_path.push('\\');
+		}
+		self.key_path.push_str(name);
+
+		let res = field.bind(self);
+		self.key_path.truncate(prev_len);
+		res
+	}
+}
+
+/// A struct whose fields are persisted in a registry key: fields are mapped
+/// to values, and nested structs are mapped to sub keys.
+///
+/// The mapping is declared once, in
+/// [`bind`](crate::prelude::RegSettings::bind), and it's used both for loading
+/// and saving. Missing values keep the defaults given by the
+/// [`Default`](std::default::Default) implementation.
+///
+/// Instead of implementing this trait by hand, as below, the struct can be
+/// declared with the [`reg_settings!`](crate::reg_settings) macro, which
+/// generates the `bind` method from the field declarations.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// #[derive(Default)]
+/// struct WindowSettings {
+///     width: u32,
+///     height: u32,
+///     maximized: bool,
+/// }
+///
+/// impl w::prelude::RegSettings for WindowSettings {
+///     fn bind(&mut self, b: &mut w::RegBinder) -> w::SysResult<()> {
+///         b.value("Width", &mut self.width)?;
+///         b.value("Height", &mut self.height)?;
+///         b.value("Maximized", &mut self.maximized)
+///     }
+/// }
+///
+/// #[derive(Default)]
+/// struct AppSettings {
+///     recent_files: Vec<String>,
+///     window: WindowSettings,
+/// }
+///
+/// impl w::prelude::RegSettings for AppSettings {
+///     fn bind(&mut self, b: &mut w::RegBinder) -> w::SysResult<()> {
+///         b.value("RecentFiles", &mut self.recent_files)?;
+///         b.sub_key("Window", &mut self.window)
+///     }
+/// }
+///
+/// let mut hkcu = w::HKEY::CURRENT_USER;
+///
+/// let mut settings = AppSettings::load_from(&hkcu, "Software\\MyApp")?;
+/// settings.window.maximized = true;
+/// settings.save_to(&mut hkcu, "Software\\MyApp")?;
+/// # w::SysResult::Ok(())
+/// ```
+pub trait RegSettings: Default {
+	/// Declares the mapping of the fields, by calling
+	/// [`RegBinder::value`](crate::RegBinder::value) and
+	/// [`RegBinder::sub_key`](crate::RegBinder::sub_key) for each one of them.
+	fn bind(&mut self, binder: &mut RegBinder) -> SysResult<()>;
+
+	/// Creates a new object with its default values, then loads the fields from
+	/// the given key of the store.
+	#[must_use]
+	fn load_from(store: &dyn RegStore, key_path: &str) -> SysResult<Self> {
+		let mut settings = Self::default();
+		settings.bind(&mut RegBinder {
+			mode: BindMode::Load(store),
+			key_path: key_path.to_owned(),
+		})?;
+		Ok(settings)
+	}
+
+	/// Saves all the fields to the given key of the store.
+	///
+	/// Although the fields are only read, `bind` needs a mutable reference.
+	fn save_to(&mut self, store: &mut dyn RegStore, key_path: &str) -> SysResult<()> {
+		self.bind(&mut RegBinder {
+			mode: BindMode::Save(store),
+			key_path: key_path.to_owned(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::co;
+	use crate::decl::*;
+	use crate::prelude::*;
+
+	reg_settings! {
+		#[derive(Debug, PartialEq)]
+		struct Window {
+			width: u32 => value "Width",
+			maximized: bool => value "Maximized",
+		}
+	}
+
+	impl Default for Window {
+		fn default() -> Self {
+			Self { width: 800, maximized: false }
+		}
+	}
+
+	reg_settings! {
+		#[derive(Debug, Default, PartialEq)]
+		struct App {
+			name: String => value "Name",
+			recent_files: Vec<String> => value "RecentFiles",
+			size: u64 => value "Size",
+			offset: i32 => value "Offset",
+			window: Window => sub_key "Window",
+		}
+	}
+
+	fn sample() -> App {
+		App {
+			name: "foo".to_owned(),
+			recent_files: vec!["a.txt".to_owned(), "b.txt".to_owned()],
+			size: 0x1_0000_0000,
+			offset: -5,
+			window: Window { width: 1024, maximized: true },
+		}
+	}
+
+	#[test]
+	fn save_maps_fields_to_values_and_sub_keys() {
+		let mut root = RegMemKey::new();
+		sample().save_to(&mut root, "Software\\App").unwrap();
+
+		let app = root.sub_key("Software\\App").unwrap();
+		assert_eq!(app.value("Name"), Some(&RegistryValue::Sz("foo".to_owned())));
+		assert_eq!(
+			app.value("RecentFiles"),
+			Some(&RegistryValue::MultiSz(vec!["a.txt".to_owned(), "b.txt".to_owned()])),
+		);
+		assert_eq!(app.value("Size"), Some(&RegistryValue::Qword(0x1_0000_0000)));
+		assert_eq!(app.value("Offset"), Some(&RegistryValue::Dword(-5i32 as u32)));
+
+		let window = app.sub_key("Window").unwrap();
+		assert_eq!(window.value("Width"), Some(&RegistryValue::Dword(1024)));
+		assert_eq!(window.value("Maximized"), Some(&RegistryValue::Dword(1)));
+		assert!(window.sub_keys().is_empty());
+	}
+
+	#[test]
+	fn load_round_trip() {
+		let mut root = RegMemKey::new();
+		sample().save_to(&mut root, "Software\\App").unwrap();
+		assert_eq!(App::load_from(&root, "Software\\App").unwrap(), sample());
+	}
+
+	#[test]
+	fn load_missing_values_keeps_defaults() {
+		let mut root = RegMemKey::new();
+		root.set_value("Software\\App\\Window", "Maximized", RegistryValue::Dword(1))
+			.unwrap();
+
+		let app = App::load_from(&root, "Software\\App").unwrap();
+		assert_eq!(app.name, "");
+		assert!(app.recent_files.is_empty());
+		assert_eq!(app.window, Window { width: 800, maximized: true });
+
+		let app = App::load_from(&root, "Software\\Other").unwrap();
+		assert_eq!(app, App::default());
+	}
+
+	#[test]
+	fn load_names_are_case_insensitive() {
+		let mut root = RegMemKey::new();
+		root.set_value("software\\app\\WINDOW", "width", RegistryValue::Dword(640))
+			.unwrap();
+		let app = App::load_from(&root, "Software\\App").unwrap();
+		assert_eq!(app.window.width, 640);
+	}
+
+	#[test]
+	fn load_widens_dword_into_qword() {
+		let mut root = RegMemKey::new();
+		root.set_value("", "Size", RegistryValue::Dword(42))
+			.unwrap();
+		assert_eq!(App::load_from(&root, "").unwrap().size, 42);
+	}
+
+	#[test]
+	fn load_type_mismatch_fails() {
+		let mut root = RegMemKey::new();
+		root.set_value("Window", "Width", RegistryValue::Sz("wide".to_owned()))
+			.unwrap();
+		assert_eq!(App::load_from(&root, "").err(), Some(co::ERROR::DATATYPE_MISMATCH));
+	}
+
+	#[test]
+	fn mem_key_overwrites_and_deletes() {
+		let mut root = RegMemKey::new();
+		root.set_value("A\\B", "X", RegistryValue::Dword(1))
+			.unwrap();
+		root.set_value("a\\b", "x", RegistryValue::Dword(2))
+			.unwrap();
+
+		let b = root.create_sub_key("A").create_sub_key("B");
+		assert_eq!(b.values().len(), 1);
+		assert_eq!(b.delete_value("X"), Some(RegistryValue::Dword(2)));
+		assert_eq!(b.delete_value("X"), None);
+
+		assert!(root.create_sub_key("a").delete_sub_key("b").is_some());
+		assert!(root.sub_key("A\\B").is_none());
+		assert!(root.sub_key("A").is_some());
+		assert!(root.sub_key("").is_some());
+	}
+}
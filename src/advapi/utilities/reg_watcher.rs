@@ -0,0 +1,174 @@
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::prelude::*;
+
+/// A change in a registry value, reported by
+/// [`RegWatcher`](crate::RegWatcher).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegValueChange {
+	/// A new value was created, with the given name and value.
+	Added(String, RegistryValue),
+	/// An existing value was modified, with the given name, old value and new
+	/// value.
+	Modified(String, RegistryValue, RegistryValue),
+	/// A value was deleted, with the given name and old value.
+	Removed(String, RegistryValue),
+}
+
+impl RegValueChange {
+	/// Compares two snapshots of the values of a key, returning the changes
+	/// from `old` to `new`. Value names are compared case-insensitively.
+	///
+	/// This function is pure, it doesn't touch the registry.
+	#[must_use]
+	pub fn diff(
+		old: &[(String, RegistryValue)],
+		new: &[(String, RegistryValue)],
+	) -> Vec<RegValueChange> {
+		let find = |vals: &[(String, RegistryValue)], name: &str| {
+			vals.iter()
+				.find(|(n, _)| n.eq_ignore_ascii_case(name))
+				.map(|(_, v)| v.clone())
+		};
+
+		let mut changes = Vec::<RegValueChange>::new();
+		for (name, new_val) in new.iter() {
+			match find(old, name) {
+				None => changes.push(Self::Added(name.clone(), new_val.clone())),
+				Some(old_val) if old_val != *new_val => {
+					changes.push(Self::Modified(name.clone(), old_val, new_val.clone()))
+				},
+				Some(_) => {},
+			}
+		}
+		for (name, old_val) in old.iter() {
+			if find(new, name).is_none() {
+				changes.push(Self::Removed(name.clone(), old_val.clone()));
+			}
+		}
+		changes
+	}
+
+	/// Returns the name of the value which changed.
+	#[must_use]
+	pub fn name(&self) -> &str {
+		match self {
+			Self::Added(name, _) | Self::Modified(name, _, _) | Self::Removed(name, _) => name,
+		}
+	}
+}
+
+/// Watches the values of a registry key, reporting which ones were added,
+/// modified or removed.
+///
+/// Internally, it uses
+/// [`HKEY::RegNotifyChangeKeyValue`](crate::prelude::advapi_Hkey::RegNotifyChangeKeyValue)
+/// with an event, and compares snapshots of the values with
+/// [`RegValueChange::diff`](crate::RegValueChange::diff). Values whose types
+/// are not supported by [`RegistryValue`](crate::RegistryValue) are ignored.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let mut watcher = w::RegWatcher::new(
+///     &w::HKEY::LOCAL_MACHINE,
+///     Some("SOFTWARE\\Policies\\MyApp"),
+/// )?;
+///
+/// loop {
+///     if let Some(changes) = watcher.wait(None)? {
+///         for change in changes.iter() {
+///             println!("Changed: {}", change.name());
+///         }
+///     }
+/// }
+/// # w::SysResult::Ok(())
+/// ```
+pub struct RegWatcher {
+	hkey: RegCloseKeyGuard,
+	hevent: CloseHandleGuard<HEVENT>,
+	snapshot: Vec<(String, RegistryValue)>,
+}
+
+impl RegWatcher {
+	/// Opens the key and starts watching it, taking the first snapshot of its
+	/// values.
+	#[must_use]
+	pub fn new(hkey: &HKEY, sub_key: Option<&str>) -> SysResult<Self> {
+		let hkey = hkey.RegOpenKeyEx(
+			sub_key,
+			co::REG_OPTION::default(),
+			co::KEY::NOTIFY | co::KEY::QUERY_VALUE,
+		)?;
+		let hevent = HEVENT::CreateEvent(None, false, false, None)?;
+
+		let mut new_self = Self { hkey, hevent, snapshot: Vec::new() };
+		new_self.arm()?;
+		new_self.snapshot = Self::take_snapshot(&new_self.hkey)?;
+		Ok(new_self)
+	}
+
+	/// Returns the event which is signaled when the key changes, so it can be
+	/// waited along with other handles. After the event is signaled, call
+	/// [`changes`](crate::RegWatcher::changes) to retrieve the changes.
+	#[must_use]
+	pub fn hevent(&self) -> &HEVENT {
+		&self.hevent
+	}
+
+	/// Returns the values of the last snapshot.
+	#[must_use]
+	pub fn values(&self) -> &[(String, RegistryValue)] {
+		&self.snapshot
+	}
+
+	/// Blocks until the key changes, then returns the changes. Returns `None`
+	/// if the timeout, in milliseconds, expires first.
+	///
+	/// The returned changes may be empty, since the notification can be
+	/// triggered when a value is rewritten with the same content.
+	#[must_use]
+	pub fn wait(&mut self, timeout_ms: Option<u32>) -> SysResult<Option<Vec<RegValueChange>>> {
+		match self.hevent.WaitForSingleObject(timeout_ms)? {
+			co::WAIT::TIMEOUT => Ok(None),
+			_ => self.changes().map(Some),
+		}
+	}
+
+	/// Watches the key again, takes a new snapshot of its values and returns
+	/// the changes since the previous snapshot.
+	#[must_use]
+	pub fn changes(&mut self) -> SysResult<Vec<RegValueChange>> {
+		self.arm()?; // before the snapshot, so no change is lost
+		let snapshot = Self::take_snapshot(&self.hkey)?;
+		let changes = RegValueChange::diff(&self.snapshot, &snapshot);
+		self.snapshot = snapshot;
+		Ok(changes)
+	}
+
+	fn arm(&self) -> SysResult<()> {
+		self.hkey.RegNotifyChangeKeyValue(
+			false,
+			co::REG_NOTIFY::CHANGE_LAST_SET | co::REG_NOTIFY::THREAD_AGNOSTIC,
+			Some(&self.hevent),
+		)
+	}
+
+	#[must_use]
+	fn take_snapshot(hkey: &HKEY) -> SysResult<Vec<(String, RegistryValue)>> {
+		let mut snapshot = Vec::<(String, RegistryValue)>::new();
+		for name_type in hkey.RegEnumValue()? {
+			let (name, _) = name_type?;
+			match hkey.RegQueryValueEx(Some(&name)) {
+				Ok(val) => snapshot.push((name, val)),
+				Err(co::ERROR::CALL_NOT_IMPLEMENTED) => {}, // type not supported by RegistryValue
+				Err(co::ERROR::FILE_NOT_FOUND) => {},       // deleted after being enumerated
+				Err(e) => return Err(e),
+			}
+		}
+		Ok(snapshot)
+	}
+}
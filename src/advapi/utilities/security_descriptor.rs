@@ -0,0 +1,694 @@
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::prelude::*;
+
+/// An access control entry, part of an [`Acl`](crate::Acl).
+///
+/// Owned, pure-Rust representation of the
+/// [`ACE`](https://learn.microsoft.com/en-us/windows/win32/secauthz/access-control-entries)
+/// structs, like
+/// [`ACCESS_ALLOWED_ACE`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-access_allowed_ace)
+/// and
+/// [`ACCESS_ALLOWED_OBJECT_ACE`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-access_allowed_object_ace).
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let users = w::CreateWellKnownSid(co::WELL_KNOWN_SID_TYPE::BuiltinUsers, None)?;
+/// let ace = w::Ace::allow(
+///     &users,
+///     co::GENERIC::READ.raw() | co::GENERIC::EXECUTE.raw(),
+///     co::ACE_FLAG::OBJECT_INHERIT | co::ACE_FLAG::CONTAINER_INHERIT,
+/// )?;
+/// # w::SysResult::Ok(())
+/// ```
+pub struct Ace {
+	pub ace_type: co::ACE_TYPE,
+	pub flags: co::ACE_FLAG,
+	/// The access rights, specific to the type of the secured object, like
+	/// [`co::GENERIC`](crate::co::GENERIC),
+	/// [`co::ACCESS_RIGHTS`](crate::co::ACCESS_RIGHTS),
+	/// [`co::KEY`](crate::co::KEY) or [`co::SERVICE`](crate::co::SERVICE).
+	pub mask: u32,
+	/// Used only by object ACE types.
+	pub object_type: Option<GUID>,
+	/// Used only by object ACE types.
+	pub inherited_object_type: Option<GUID>,
+	pub sid: SidGuard,
+	/// Trailing data of callback and resource attribute ACE types.
+	pub application_data: Vec<u8>,
+}
+
+/// An access control list, used as the DACL or the SACL of a
+/// [`SecurityDescriptor`](crate::SecurityDescriptor).
+///
+/// Owned, pure-Rust representation of the
+/// [`ACL`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-acl)
+/// struct, followed by its ACEs.
+#[derive(Default)]
+pub struct Acl {
+	/// The entries, in the order they're evaluated.
+	pub aces: Vec<Ace>,
+}
+
+/// A security descriptor, which can be converted to and from SDDL strings,
+/// and read from and written to files, registry keys and services with
+/// [`GetNamedSecurityInfo`](crate::GetNamedSecurityInfo) and
+/// [`SetNamedSecurityInfo`](crate::SetNamedSecurityInfo).
+///
+/// Owned, pure-Rust representation of a self-relative
+/// [`SECURITY_DESCRIPTOR`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-security_descriptor_relative)
+/// struct.
+///
+/// A `None` DACL with [`SE::DACL_PRESENT`](crate::co::SE::DACL_PRESENT) in
+/// `control` is a NULL DACL, which grants full access to everyone.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let sd = w::SecurityDescriptor::from_sddl("O:BAD:P(A;OICI;FA;;;SY)(A;OICI;FA;;;BA)")?;
+/// for ace in sd.dacl.as_ref().unwrap().aces.iter() {
+///     println!("{} {:#x}", ace.sid, ace.mask);
+/// }
+///
+/// println!("{}", sd.to_sddl(co::SECURITY_INFORMATION::DACL)?);
+/// # w::SysResult::Ok(())
+/// ```
+#[derive(Default)]
+pub struct SecurityDescriptor {
+	/// The control flags. When serializing,
+	/// [`SE::SELF_RELATIVE`](crate::co::SE::SELF_RELATIVE),
+	/// [`SE::DACL_PRESENT`](crate::co::SE::DACL_PRESENT) and
+	/// [`SE::SACL_PRESENT`](crate::co::SE::SACL_PRESENT) are set
+	/// automatically.
+	pub control: co::SE,
+	pub owner: Option<SidGuard>,
+	pub group: Option<SidGuard>,
+	pub sacl: Option<Acl>,
+	pub dacl: Option<Acl>,
+}
+
+const SD_HEADER_SZ: usize = 20;
+const ACL_HEADER_SZ: usize = 8;
+const ACE_HEADER_SZ: usize = 4;
+const ACL_REVISION: u8 = 2;
+const ACL_REVISION_DS: u8 = 4;
+const ACE_OBJECT_TYPE_PRESENT: u32 = 0x1;
+const ACE_INHERITED_OBJECT_TYPE_PRESENT: u32 = 0x2;
+
+/// Reads a little-endian `u16` at the given offset.
+#[must_use]
+fn read_u16(buf: &[u8], off: usize) -> Option<u16> {
+	buf.get(off..off + 2)
+		.map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+/// Reads a little-endian `u32` at the given offset.
+#[must_use]
+fn read_u32(buf: &[u8], off: usize) -> Option<u32> {
+	buf.get(off..off + 4)
+		.map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Reads a `GUID` at the given offset.
+#[must_use]
+fn read_guid(buf: &[u8], off: usize) -> Option<GUID> {
+	buf.get(off..off + std::mem::size_of::<GUID>())
+		.map(|b| unsafe { std::ptr::read_unaligned(b.as_ptr() as *const GUID) })
+}
+
+/// Converts a size or a count into the `u16` stored in the ACL and ACE headers.
+fn len_u16(len: usize) -> SysResult<u16> {
+	u16::try_from(len).map_err(|_| co::ERROR::INVALID_ACL)
+}
+
+/// Returns the raw bytes of the `SID`.
+#[must_use]
+fn sid_bytes(sid: &SID) -> &[u8] {
+	unsafe {
+		std::slice::from_raw_parts(
+			sid as *const _ as *const u8,
+			8 + 4 * sid.SubAuthorityCount as usize,
+		)
+	}
+}
+
+/// Parses a `SID` at the given offset, returning it along with its length.
+fn read_sid(buf: &[u8], off: usize) -> SysResult<(SidGuard, usize)> {
+	let sub_auth_count = *buf.get(off + 1).ok_or(co::ERROR::INVALID_SID)?;
+	let sid_sz = 8 + 4 * sub_auth_count as usize;
+	let src = buf
+		.get(off..off + sid_sz)
+		.filter(|b| b[0] == 1 && sub_auth_count <= 15) // SID_REVISION, SID_MAX_SUB_AUTHORITIES
+		.ok_or(co::ERROR::INVALID_SID)?;
+
+	let hglobal = HGLOBAL::GlobalAlloc(co::GMEM::FIXED | co::GMEM::ZEROINIT, sid_sz)?;
+	unsafe {
+		std::ptr::copy_nonoverlapping(src.as_ptr(), hglobal.ptr() as *mut u8, sid_sz);
+		Ok((SidGuard::new(hglobal), sid_sz))
+	}
+}
+
+impl Ace {
+	/// Creates a new
+	/// [`ACE_TYPE::ACCESS_ALLOWED`](crate::co::ACE_TYPE::ACCESS_ALLOWED) entry,
+	/// copying the `SID`.
+	#[must_use]
+	pub fn allow(sid: &SID, mask: u32, flags: co::ACE_FLAG) -> SysResult<Self> {
+		Self::new(co::ACE_TYPE::ACCESS_ALLOWED, flags, mask, sid)
+	}
+
+	/// Creates a new
+	/// [`ACE_TYPE::SYSTEM_AUDIT`](crate::co::ACE_TYPE::SYSTEM_AUDIT) entry,
+	/// copying the `SID`.
+	///
+	/// The `flags` should contain
+	/// [`ACE_FLAG::SUCCESSFUL_ACCESS`](crate::co::ACE_FLAG::SUCCESSFUL_ACCESS),
+	/// [`ACE_FLAG::FAILED_ACCESS`](crate::co::ACE_FLAG::FAILED_ACCESS) or both.
+	#[must_use]
+	pub fn audit(sid: &SID, mask: u32, flags: co::ACE_FLAG) -> SysResult<Self> {
+		Self::new(co::ACE_TYPE::SYSTEM_AUDIT, flags, mask, sid)
+	}
+
+	/// Creates a new
+	/// [`ACE_TYPE::ACCESS_DENIED`](crate::co::ACE_TYPE::ACCESS_DENIED) entry,
+	/// copying the `SID`.
+	#[must_use]
+	pub fn deny(sid: &SID, mask: u32, flags: co::ACE_FLAG) -> SysResult<Self> {
+		Self::new(co::ACE_TYPE::ACCESS_DENIED, flags, mask, sid)
+	}
+
+	/// Creates a new entry, copying the `SID`.
+	#[must_use]
+	pub fn new(
+		ace_type: co::ACE_TYPE,
+		flags: co::ACE_FLAG,
+		mask: u32,
+		sid: &SID,
+	) -> SysResult<Self> {
+		Ok(Self {
+			ace_type,
+			flags,
+			mask,
+			object_type: None,
+			inherited_object_type: None,
+			sid: CopySid(sid)?,
+			application_data: Vec::new(),
+		})
+	}
+
+	/// Tells whether the ACE type has the object layout, with the optional
+	/// `object_type` and `inherited_object_type` GUIDs.
+	#[must_use]
+	pub fn is_object(&self) -> bool {
+		Self::is_object_type(self.ace_type)
+	}
+
+	#[must_use]
+	fn is_object_type(ace_type: co::ACE_TYPE) -> bool {
+		matches!(
+			ace_type,
+			co::ACE_TYPE::ACCESS_ALLOWED_OBJECT
+				| co::ACE_TYPE::ACCESS_DENIED_OBJECT
+				| co::ACE_TYPE::SYSTEM_AUDIT_OBJECT
+				| co::ACE_TYPE::SYSTEM_ALARM_OBJECT
+				| co::ACE_TYPE::ACCESS_ALLOWED_CALLBACK_OBJECT
+				| co::ACE_TYPE::ACCESS_DENIED_CALLBACK_OBJECT
+				| co::ACE_TYPE::SYSTEM_AUDIT_CALLBACK_OBJECT
+				| co::ACE_TYPE::SYSTEM_ALARM_CALLBACK_OBJECT
+		)
+	}
+
+	/// Parses an ACE at the given offset, returning it along with its length.
+	fn parse(buf: &[u8], off: usize) -> SysResult<(Self, usize)> {
+		let bad = || co::ERROR::INVALID_ACL;
+		let ace_type = unsafe { co::ACE_TYPE::from_raw(*buf.get(off).ok_or_else(bad)?) };
+		let flags = unsafe { co::ACE_FLAG::from_raw(*buf.get(off + 1).ok_or_else(bad)?) };
+		let ace_sz = read_u16(buf, off + 2).ok_or_else(bad)? as usize;
+		let ace_buf = buf.get(off..off + ace_sz).ok_or_else(bad)?;
+		let mask = read_u32(ace_buf, ACE_HEADER_SZ).ok_or_else(bad)?;
+
+		let mut object_type = None;
+		let mut inherited_object_type = None;
+		let mut pos = ACE_HEADER_SZ + 4;
+		if Self::is_object_type(ace_type) {
+			let obj_flags = read_u32(ace_buf, pos).ok_or_else(bad)?;
+			pos += 4;
+			if obj_flags & ACE_OBJECT_TYPE_PRESENT != 0 {
+				object_type = Some(read_guid(ace_buf, pos).ok_or_else(bad)?);
+				pos += std::mem::size_of::<GUID>();
+			}
+			if obj_flags & ACE_INHERITED_OBJECT_TYPE_PRESENT != 0 {
+				inherited_object_type = Some(read_guid(ace_buf, pos).ok_or_else(bad)?);
+				pos += std::mem::size_of::<GUID>();
+			}
+		}
+
+		let (sid, sid_sz) = read_sid(ace_buf, pos)?;
+		Ok((
+			Self {
+				ace_type,
+				flags,
+				mask,
+				object_type,
+				inherited_object_type,
+				sid,
+				application_data: ace_buf[pos + sid_sz..].to_vec(),
+			},
+			ace_sz,
+		))
+	}
+
+	/// Appends the binary ACE to the buffer.
+	fn serialize(&self, buf: &mut Vec<u8>) -> SysResult<()> {
+		let start = buf.len();
+		buf.extend_from_slice(&[self.ace_type.raw(), self.flags.raw(), 0, 0]); // size set below
+		buf.extend_from_slice(&self.mask.to_le_bytes());
+
+		if self.is_object() {
+			let obj_flags = self.object_type.map_or(0, |_| ACE_OBJECT_TYPE_PRESENT)
+				| self
+					.inherited_object_type
+					.map_or(0, |_| ACE_INHERITED_OBJECT_TYPE_PRESENT);
+			buf.extend_from_slice(&obj_flags.to_le_bytes());
+			for guid in [self.object_type, self.inherited_object_type]
+				.iter()
+				.flatten()
+			{
+				buf.extend_from_slice(unsafe {
+					std::slice::from_raw_parts(
+						guid as *const _ as *const u8,
+						std::mem::size_of::<GUID>(),
+					)
+				});
+			}
+		}
+
+		buf.extend_from_slice(sid_bytes(&self.sid));
+		buf.extend_from_slice(&self.application_data);
+		buf.resize(start + (buf.len() - start).next_multiple_of(4), 0); // DWORD-aligned
+
+		let ace_sz = len_u16(buf.len() - start)?;
+		buf[start + 2..start + 4].copy_from_slice(&ace_sz.to_le_bytes());
+		Ok(())
+	}
+}
+
+impl Acl {
+	/// Parses a binary [`ACL`](crate::ACL) struct, followed by its ACEs.
+	pub fn from_bytes(buf: &[u8]) -> SysResult<Self> {
+		let bad = || co::ERROR::INVALID_ACL;
+		let revision = *buf.first().ok_or_else(bad)?;
+		if revision != ACL_REVISION && revision != ACL_REVISION_DS {
+			return Err(bad());
+		}
+		let acl_sz = read_u16(buf, 2).ok_or_else(bad)? as usize;
+		let ace_count = read_u16(buf, 4).ok_or_else(bad)?;
+		let acl_buf = buf.get(..acl_sz).ok_or_else(bad)?;
+
+		let mut aces = Vec::with_capacity(ace_count as _);
+		let mut pos = ACL_HEADER_SZ;
+		for _ in 0..ace_count {
+			let (ace, ace_sz) = Ace::parse(acl_buf, pos)?;
+			if ace_sz < ACE_HEADER_SZ {
+				return Err(bad());
+			}
+			aces.push(ace);
+			pos += ace_sz;
+		}
+		Ok(Self { aces })
+	}
+
+	/// Serializes the list into a binary [`ACL`](crate::ACL) struct, followed
+	/// by its ACEs.
+	///
+	/// Returns [`ERROR::INVALID_ACL`](crate::co::ERROR::INVALID_ACL) if the
+	/// list exceeds the 64 KB or the 65,535 entries of the binary format.
+	#[must_use]
+	pub fn to_bytes(&self) -> SysResult<Vec<u8>> {
+		let mut buf = Vec::new();
+		self.serialize(&mut buf)?;
+		Ok(buf)
+	}
+
+	/// Appends the binary ACL to the buffer.
+	fn serialize(&self, buf: &mut Vec<u8>) -> SysResult<()> {
+		let start = buf.len();
+		let revision = if self.aces.iter().any(|ace| ace.is_object()) {
+			ACL_REVISION_DS
+		} else {
+			ACL_REVISION
+		};
+		buf.extend_from_slice(&[revision, 0, 0, 0]); // size set below
+		buf.extend_from_slice(&len_u16(self.aces.len())?.to_le_bytes());
+		buf.extend_from_slice(&[0, 0]);
+
+		self.aces.iter().try_for_each(|ace| ace.serialize(buf))?;
+
+		let acl_sz = len_u16(buf.len() - start)?;
+		buf[start + 2..start + 4].copy_from_slice(&acl_sz.to_le_bytes());
+		Ok(())
+	}
+}
+
+impl SecurityDescriptor {
+	/// Parses a self-relative binary security descriptor.
+	pub fn from_bytes(buf: &[u8]) -> SysResult<Self> {
+		let bad = || co::ERROR::INVALID_SECURITY_DESCR;
+		if buf.len() < SD_HEADER_SZ || buf[0] != 1 {
+			return Err(bad()); // SECURITY_DESCRIPTOR_REVISION
+		}
+		let control = unsafe { co::SE::from_raw(read_u16(buf, 2).ok_or_else(bad)?) };
+		if !control.has(co::SE::SELF_RELATIVE) {
+			return Err(bad());
+		}
+		let off_owner = read_u32(buf, 4).ok_or_else(bad)? as usize;
+		let off_group = read_u32(buf, 8).ok_or_else(bad)? as usize;
+		let off_sacl = read_u32(buf, 12).ok_or_else(bad)? as usize;
+		let off_dacl = read_u32(buf, 16).ok_or_else(bad)? as usize;
+
+		let sid_at = |off: usize| -> SysResult<Option<SidGuard>> {
+			match off {
+				0 => Ok(None),
+				off => read_sid(buf, off).map(|(sid, _)| Some(sid)),
+			}
+		};
+		let acl_at = |off: usize, present: co::SE| -> SysResult<Option<Acl>> {
+			match off {
+				0 => Ok(None),
+				_ if !control.has(present) => Ok(None),
+				off => Acl::from_bytes(buf.get(off..).ok_or_else(bad)?).map(Some),
+			}
+		};
+
+		Ok(Self {
+			control: control & !co::SE::SELF_RELATIVE,
+			owner: sid_at(off_owner)?,
+			group: sid_at(off_group)?,
+			sacl: acl_at(off_sacl, co::SE::SACL_PRESENT)?,
+			dacl: acl_at(off_dacl, co::SE::DACL_PRESENT)?,
+		})
+	}
+
+	/// Parses a security descriptor from an
+	/// [SDDL](https://learn.microsoft.com/en-us/windows/win32/secauthz/security-descriptor-definition-language)
+	/// string, by calling
+	/// [`ConvertStringSecurityDescriptorToSecurityDescriptor`](crate::ConvertStringSecurityDescriptorToSecurityDescriptor).
+	pub fn from_sddl(sddl: &str) -> SysResult<Self> {
+		ConvertStringSecurityDescriptorToSecurityDescriptor(sddl)
+	}
+
+	/// Serializes the security descriptor into its self-relative binary form.
+	///
+	/// Returns [`ERROR::INVALID_ACL`](crate::co::ERROR::INVALID_ACL) if the
+	/// SACL or the DACL is too large.
+	#[must_use]
+	pub fn to_bytes(&self) -> SysResult<Vec<u8>> {
+		self.serialize().map(|(buf, _)| buf)
+	}
+
+	/// Converts the security descriptor to an
+	/// [SDDL](https://learn.microsoft.com/en-us/windows/win32/secauthz/security-descriptor-definition-language)
+	/// string, by calling
+	/// [`ConvertSecurityDescriptorToStringSecurityDescriptor`](crate::ConvertSecurityDescriptorToStringSecurityDescriptor).
+	///
+	/// Only the parts specified in `info` are converted.
+	pub fn to_sddl(&self, info: co::SECURITY_INFORMATION) -> SysResult<String> {
+		ConvertSecurityDescriptorToStringSecurityDescriptor(self, info)
+	}
+
+	/// Serializes the security descriptor into a buffer aligned to be passed
+	/// to the system, also returning the offsets of owner, group, SACL and
+	/// DACL, which are zero if absent.
+	#[must_use]
+	pub(in crate::advapi) fn serialize_aligned(&self) -> SysResult<(Vec<u64>, [usize; 4])> {
+		let (bytes, offsets) = self.serialize()?;
+		let mut buf = vec![0u64; bytes.len().div_ceil(std::mem::size_of::<u64>())];
+		unsafe {
+			std::ptr::copy_nonoverlapping(bytes.as_ptr(), buf.as_mut_ptr() as *mut u8, bytes.len());
+		}
+		Ok((buf, offsets))
+	}
+
+	/// Serializes the security descriptor into its self-relative binary form,
+	/// also returning the offsets of owner, group, SACL and DACL, which are
+	/// zero if absent.
+	#[must_use]
+	fn serialize(&self) -> SysResult<(Vec<u8>, [usize; 4])> {
+		let mut control = self.control | co::SE::SELF_RELATIVE;
+		if self.sacl.is_some() {
+			control |= co::SE::SACL_PRESENT;
+		}
+		if self.dacl.is_some() {
+			control |= co::SE::DACL_PRESENT;
+		}
+
+		let mut buf = vec![0u8; SD_HEADER_SZ];
+		buf[0] = 1; // SECURITY_DESCRIPTOR_REVISION
+		buf[2..4].copy_from_slice(&control.raw().to_le_bytes());
+
+		let mut offsets = [0usize; 4]; // same order of the header fields
+		if let Some(sacl) = &self.sacl {
+			offsets[2] = buf.len();
+			sacl.serialize(&mut buf)?;
+		}
+		if let Some(dacl) = &self.dacl {
+			offsets[3] = buf.len();
+			dacl.serialize(&mut buf)?;
+		}
+		if let Some(owner) = &self.owner {
+			offsets[0] = buf.len();
+			buf.extend_from_slice(sid_bytes(owner));
+		}
+		if let Some(group) = &self.group {
+			offsets[1] = buf.len();
+			buf.extend_from_slice(sid_bytes(group));
+		}
+
+		for (i, off) in offsets.iter().enumerate() {
+			buf[4 + i * 4..8 + i * 4].copy_from_slice(&(*off as u32).to_le_bytes());
+		}
+		Ok((buf, offsets))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[rustfmt::skip]
+	const SID_SY: [u8; 12] = [1, 1, 0, 0, 0, 0, 0, 5, 18, 0, 0, 0]; // S-1-5-18
+	#[rustfmt::skip]
+	const SID_BA: [u8; 16] = [1, 2, 0, 0, 0, 0, 0, 5, 32, 0, 0, 0, 0x20, 2, 0, 0]; // S-1-5-32-544
+
+	#[rustfmt::skip]
+	fn acl_bytes() -> Vec<u8> {
+		let mut buf = vec![
+			2, 0, 52, 0, 2, 0, 0, 0, // ACL_REVISION, size, 2 ACEs
+			0, 3, 20, 0, 0xff, 0x01, 0x1f, 0x00, // allowed, OI | CI, FILE_ALL_ACCESS
+		];
+		buf.extend_from_slice(&SID_SY);
+		buf.extend_from_slice(&[1, 0, 24, 0, 0, 0, 1, 0]); // denied, DELETE
+		buf.extend_from_slice(&SID_BA);
+		buf
+	}
+
+	#[test]
+	fn acl_round_trip() {
+		let bytes = acl_bytes();
+		let acl = Acl::from_bytes(&bytes).unwrap();
+
+		assert_eq!(acl.aces.len(), 2);
+		assert_eq!(acl.aces[0].ace_type, co::ACE_TYPE::ACCESS_ALLOWED);
+		assert_eq!(
+			acl.aces[0].flags,
+			co::ACE_FLAG::OBJECT_INHERIT | co::ACE_FLAG::CONTAINER_INHERIT,
+		);
+		assert_eq!(acl.aces[0].mask, 0x1f_01ff);
+		assert_eq!(sid_bytes(&acl.aces[0].sid), SID_SY);
+		assert_eq!(acl.aces[1].ace_type, co::ACE_TYPE::ACCESS_DENIED);
+		assert_eq!(acl.aces[1].mask, 0x1_0000);
+		assert_eq!(sid_bytes(&acl.aces[1].sid), SID_BA);
+		assert!(acl
+			.aces
+			.iter()
+			.all(|ace| !ace.is_object() && ace.application_data.is_empty()));
+
+		assert_eq!(acl.to_bytes().unwrap(), bytes);
+	}
+
+	#[test]
+	fn acl_object_round_trip() {
+		#[rustfmt::skip]
+		let mut bytes = vec![
+			4, 0, 48, 0, 1, 0, 0, 0, // ACL_REVISION_DS, size, 1 ACE
+			5, 0, 40, 0, 0x10, 0, 0, 0, // allowed object, no flags, READ_PROPERTY
+			1, 0, 0, 0, // ACE_OBJECT_TYPE_PRESENT
+		];
+		bytes.extend(1..=16u8); // object type GUID
+		bytes.extend_from_slice(&SID_SY);
+
+		let acl = Acl::from_bytes(&bytes).unwrap();
+		let ace = &acl.aces[0];
+		assert!(ace.is_object());
+		assert!(ace.object_type.is_some());
+		assert!(ace.inherited_object_type.is_none());
+		assert_eq!(sid_bytes(&ace.sid), SID_SY);
+
+		assert_eq!(acl.to_bytes().unwrap(), bytes);
+	}
+
+	#[test]
+	fn acl_empty() {
+		let bytes = Acl::default().to_bytes().unwrap();
+		assert_eq!(bytes, [2, 0, 8, 0, 0, 0, 0, 0]);
+		assert!(Acl::from_bytes(&bytes).unwrap().aces.is_empty());
+	}
+
+	#[test]
+	fn acl_malformed() {
+		let bytes = acl_bytes();
+		assert_eq!(Acl::from_bytes(&[]).err(), Some(co::ERROR::INVALID_ACL));
+		assert_eq!(Acl::from_bytes(&bytes[..40]).err(), Some(co::ERROR::INVALID_ACL));
+
+		let mut bad_revision = bytes.clone();
+		bad_revision[0] = 3;
+		assert_eq!(Acl::from_bytes(&bad_revision).err(), Some(co::ERROR::INVALID_ACL));
+
+		let mut bad_ace_sz = bytes.clone();
+		bad_ace_sz[10] = 200; // beyond the ACL
+		assert_eq!(Acl::from_bytes(&bad_ace_sz).err(), Some(co::ERROR::INVALID_ACL));
+
+		let mut bad_sid = bytes;
+		bad_sid[16] = 2; // SID revision
+		assert_eq!(Acl::from_bytes(&bad_sid).err(), Some(co::ERROR::INVALID_SID));
+	}
+
+	#[test]
+	fn acl_overflow() {
+		let ace = |application_data: Vec<u8>| Ace {
+			ace_type: co::ACE_TYPE::ACCESS_ALLOWED,
+			flags: co::ACE_FLAG::default(),
+			mask: 0x1f_01ff,
+			object_type: None,
+			inherited_object_type: None,
+			sid: read_sid(&SID_SY, 0).unwrap().0,
+			application_data,
+		};
+
+		let big_ace = Acl { aces: vec![ace(vec![0; 70_000])] };
+		assert_eq!(big_ace.to_bytes().err(), Some(co::ERROR::INVALID_ACL));
+
+		let many_aces = Acl {
+			aces: (0..4_000).map(|_| ace(Vec::new())).collect(),
+		}; // 20 bytes each
+		assert_eq!(many_aces.to_bytes().err(), Some(co::ERROR::INVALID_ACL));
+
+		let sd = SecurityDescriptor {
+			dacl: Some(many_aces),
+			..Default::default()
+		};
+		assert_eq!(sd.to_bytes().err(), Some(co::ERROR::INVALID_ACL));
+	}
+
+	#[rustfmt::skip]
+	fn sd_bytes() -> Vec<u8> {
+		let mut buf = vec![
+			1, 0, 0x04, 0x90, // revision, SELF_RELATIVE | DACL_PROTECTED | DACL_PRESENT
+			72, 0, 0, 0, // owner
+			88, 0, 0, 0, // group
+			0, 0, 0, 0, // SACL
+			20, 0, 0, 0, // DACL
+		];
+		buf.extend_from_slice(&acl_bytes());
+		buf.extend_from_slice(&SID_BA);
+		buf.extend_from_slice(&SID_SY);
+		buf
+	}
+
+	#[test]
+	fn sd_round_trip() {
+		let bytes = sd_bytes();
+		let sd = SecurityDescriptor::from_bytes(&bytes).unwrap();
+
+		assert_eq!(sd.control, co::SE::DACL_PRESENT | co::SE::DACL_PROTECTED);
+		assert_eq!(sid_bytes(sd.owner.as_ref().unwrap()), SID_BA);
+		assert_eq!(sid_bytes(sd.group.as_ref().unwrap()), SID_SY);
+		assert!(sd.sacl.is_none());
+		assert_eq!(sd.dacl.as_ref().unwrap().aces.len(), 2);
+
+		assert_eq!(sd.to_bytes().unwrap(), bytes);
+	}
+
+	#[test]
+	fn sd_built() {
+		let sd = SecurityDescriptor {
+			control: co::SE::DACL_PROTECTED,
+			owner: Some(read_sid(&SID_BA, 0).unwrap().0),
+			group: Some(read_sid(&SID_SY, 0).unwrap().0),
+			dacl: Some(Acl::from_bytes(&acl_bytes()).unwrap()),
+			..Default::default()
+		};
+		assert_eq!(sd.to_bytes().unwrap(), sd_bytes()); // DACL_PRESENT set automatically
+	}
+
+	#[test]
+	fn sd_null_dacl() {
+		let sd = SecurityDescriptor {
+			control: co::SE::DACL_PRESENT,
+			..Default::default()
+		};
+		let bytes = sd.to_bytes().unwrap();
+		assert_eq!(&bytes[..4], &[1, 0, 0x04, 0x80]);
+		assert!(bytes[4..].iter().all(|b| *b == 0));
+
+		let parsed = SecurityDescriptor::from_bytes(&bytes).unwrap();
+		assert_eq!(parsed.control, co::SE::DACL_PRESENT);
+		assert!(parsed.dacl.is_none());
+	}
+
+	#[test]
+	fn sd_malformed() {
+		let bytes = sd_bytes();
+		assert_eq!(
+			SecurityDescriptor::from_bytes(&bytes[..10]).err(),
+			Some(co::ERROR::INVALID_SECURITY_DESCR),
+		);
+
+		let mut bad_revision = bytes.clone();
+		bad_revision[0] = 2;
+		assert_eq!(
+			SecurityDescriptor::from_bytes(&bad_revision).err(),
+			Some(co::ERROR::INVALID_SECURITY_DESCR),
+		);
+
+		let mut absolute = bytes.clone();
+		absolute[3] = 0x10; // no SELF_RELATIVE
+		assert_eq!(
+			SecurityDescriptor::from_bytes(&absolute).err(),
+			Some(co::ERROR::INVALID_SECURITY_DESCR),
+		);
+
+		let mut bad_owner = bytes;
+		bad_owner[4] = 200; // beyond the buffer
+		assert_eq!(SecurityDescriptor::from_bytes(&bad_owner).err(), Some(co::ERROR::INVALID_SID),);
+	}
+
+	#[test]
+	fn set_named_implicit_null_dacl() {
+		assert_eq!(
+			SetNamedSecurityInfo(
+				"C:\\Temp\\foo.txt",
+				co::SE_OBJECT_TYPE::FILE_OBJECT,
+				co::SECURITY_INFORMATION::DACL,
+				&SecurityDescriptor::default(),
+			),
+			Err(co::ERROR::INVALID_SECURITY_DESCR),
+		);
+	}
+}
@@ -0,0 +1,556 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use crate::co;
+use crate::decl::*;
+use crate::kernel::privs::*;
+
+/// Configuration of a service, returned by
+/// [`HSERVICE::QueryServiceConfig`](crate::prelude::advapi_Hservice::QueryServiceConfig).
+///
+/// Owned, pure-Rust representation of the
+/// [`QUERY_SERVICE_CONFIG`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-query_service_configw)
+/// struct.
+#[derive(Clone, Debug)]
+pub struct ServiceConfig {
+	pub service_type: co::SERVICE_TYPE,
+	pub start_type: co::SERVICE_START,
+	pub error_control: co::SERVICE_ERROR,
+	pub binary_path_name: String,
+	pub load_order_group: String,
+	pub tag_id: u32,
+	pub dependencies: Vec<String>,
+	pub service_start_name: String,
+	pub display_name: String,
+}
+
+/// A service returned by
+/// [`HSC::EnumServicesStatusEx`](crate::prelude::advapi_Hsc::EnumServicesStatusEx)
+/// or
+/// [`HSERVICE::EnumDependentServices`](crate::prelude::advapi_Hservice::EnumDependentServices).
+///
+/// Dependent services don't report process information, so `dwProcessId` and
+/// `dwServiceFlags` are zero for them.
+#[derive(Clone)]
+pub struct ServiceEntry {
+	pub service_name: String,
+	pub display_name: String,
+	pub status: SERVICE_STATUS_PROCESS,
+}
+
+/// An action taken when a service fails, part of
+/// [`ServiceFailureActions`](crate::ServiceFailureActions).
+///
+/// Owned, pure-Rust representation of the
+/// [`SC_ACTION`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-sc_action)
+/// struct.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScAction {
+	pub action: co::SC_ACTION,
+	pub delay_ms: u32,
+}
+
+/// Actions taken when a service fails, used in
+/// [`ServiceConfig2::FailureActions`](crate::ServiceConfig2::FailureActions).
+///
+/// Owned, pure-Rust representation of the
+/// [`SERVICE_FAILURE_ACTIONS`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_failure_actionsw)
+/// struct.
+///
+/// When changing the configuration, a `None` string leaves the current value
+/// unchanged, while an empty string deletes it. An empty list of actions
+/// deletes all current actions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ServiceFailureActions {
+	/// Time without failures after which the failure count is reset to zero,
+	/// in seconds. `INFINITE` (`0xffff_ffff`) means never.
+	pub reset_period_secs: u32,
+	/// Message broadcast to server users before rebooting.
+	pub reboot_msg: Option<String>,
+	/// Command line of the process to run in response to
+	/// [`SC_ACTION::RUN_COMMAND`](crate::co::SC_ACTION::RUN_COMMAND).
+	pub command: Option<String>,
+	/// The actions taken for the first, second and subsequent failures; the
+	/// last one is repeated.
+	pub actions: Vec<ScAction>,
+}
+
+/// Data item of a [`ServiceTrigger`](crate::ServiceTrigger).
+///
+/// Owned, pure-Rust representation of the
+/// [`SERVICE_TRIGGER_SPECIFIC_DATA_ITEM`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_trigger_specific_data_item)
+/// struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ServiceTriggerData {
+	/// Binary data.
+	Binary(Vec<u8>),
+	/// A string. Multiple strings can be separated by `'\0'`.
+	String(String),
+	/// An event level.
+	Level(u8),
+	/// Event keywords, any of which must match.
+	KeywordAny(u64),
+	/// Event keywords, all of which must match.
+	KeywordAll(u64),
+}
+
+/// A trigger which starts or stops a service, used in
+/// [`ServiceConfig2::Triggers`](crate::ServiceConfig2::Triggers).
+///
+/// Owned, pure-Rust representation of the
+/// [`SERVICE_TRIGGER`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_trigger)
+/// struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServiceTrigger {
+	pub trigger_type: co::SERVICE_TRIGGER_TYPE,
+	pub action: co::SERVICE_TRIGGER_ACTION,
+	/// The event, like a device interface class or a firewall port event; see
+	/// [`SERVICE_TRIGGER`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-service_trigger)
+	/// for the well-known values.
+	pub subtype: Option<GUID>,
+	pub data: Vec<ServiceTriggerData>,
+}
+
+/// Optional configuration of a service, used in
+/// [`HSERVICE::ChangeServiceConfig2`](crate::prelude::advapi_Hservice::ChangeServiceConfig2)
+/// and
+/// [`HSERVICE::QueryServiceConfig2`](crate::prelude::advapi_Hservice::QueryServiceConfig2).
+///
+/// The variants match the [`co::SERVICE_CONFIG`](crate::co::SERVICE_CONFIG)
+/// constants.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ServiceConfig2 {
+	/// Description of the service. An empty string deletes it.
+	Description(String),
+	/// Actions taken when the service fails.
+	FailureActions(ServiceFailureActions),
+	/// Whether the failure actions are also taken when the service stops with
+	/// a non-zero exit code, rather than only when it crashes.
+	FailureActionsFlag(bool),
+	/// Whether an auto-start service is started after the others.
+	DelayedAutoStart(bool),
+	/// Type of the service SID.
+	SidType(co::SERVICE_SID_TYPE),
+	/// Privileges required by the service.
+	RequiredPrivileges(Vec<String>),
+	/// Time the system waits for the service after sending
+	/// [`SvcCtl::PreShutdown`](crate::SvcCtl::PreShutdown), in milliseconds.
+	PreshutdownTimeout(u32),
+	/// Triggers which start or stop the service. An empty list deletes them.
+	Triggers(Vec<ServiceTrigger>),
+}
+
+#[repr(C)]
+pub(in crate::advapi) struct QUERY_SERVICE_CONFIG {
+	dwServiceType: u32,
+	dwStartType: u32,
+	dwErrorControl: u32,
+	lpBinaryPathName: *mut u16,
+	lpLoadOrderGroup: *mut u16,
+	dwTagId: u32,
+	lpDependencies: *mut u16,
+	lpServiceStartName: *mut u16,
+	lpDisplayName: *mut u16,
+}
+
+#[repr(C)]
+pub(in crate::advapi) struct ENUM_SERVICE_STATUS {
+	lpServiceName: *mut u16,
+	lpDisplayName: *mut u16,
+	ServiceStatus: SERVICE_STATUS,
+}
+
+#[repr(C)]
+pub(in crate::advapi) struct ENUM_SERVICE_STATUS_PROCESS {
+	lpServiceName: *mut u16,
+	lpDisplayName: *mut u16,
+	ServiceStatusProcess: SERVICE_STATUS_PROCESS,
+}
+
+#[repr(C)]
+pub(in crate::advapi) struct SERVICE_CONTROL_STATUS_REASON_PARAMS {
+	dwReason: u32,
+	pszComment: *const u16,
+	ServiceStatus: SERVICE_STATUS_PROCESS,
+}
+
+impl SERVICE_CONTROL_STATUS_REASON_PARAMS {
+	#[must_use]
+	pub(in crate::advapi) fn new(reason: u32, comment: *const u16) -> Self {
+		Self {
+			dwReason: reason,
+			pszComment: comment,
+			ServiceStatus: SERVICE_STATUS_PROCESS::default(),
+		}
+	}
+
+	#[must_use]
+	pub(in crate::advapi) const fn status(&self) -> SERVICE_STATUS_PROCESS {
+		self.ServiceStatus
+	}
+}
+
+#[repr(C)]
+struct SC_ACTION {
+	Type: u32,
+	Delay: u32,
+}
+
+#[repr(C)]
+struct SERVICE_FAILURE_ACTIONS {
+	dwResetPeriod: u32,
+	lpRebootMsg: *mut u16,
+	lpCommand: *mut u16,
+	cActions: u32,
+	lpsaActions: *mut SC_ACTION,
+}
+
+#[repr(C)]
+struct SERVICE_TRIGGER_INFO {
+	cTriggers: u32,
+	pTriggers: *mut SERVICE_TRIGGER,
+	pReserved: *mut u8,
+}
+
+#[repr(C)]
+struct SERVICE_TRIGGER {
+	dwTriggerType: u32,
+	dwAction: u32,
+	pTriggerSubtype: *mut GUID,
+	cDataItems: u32,
+	pDataItems: *mut SERVICE_TRIGGER_SPECIFIC_DATA_ITEM,
+}
+
+#[repr(C)]
+struct SERVICE_TRIGGER_SPECIFIC_DATA_ITEM {
+	dwDataType: u32,
+	cbData: u32,
+	pData: *mut u8,
+}
+
+/// Reads a nullable, null-terminated wide string.
+#[must_use]
+unsafe fn str_at(p: *const u16) -> String {
+	if p.is_null() {
+		String::new()
+	} else {
+		WString::from_wchars_nullt(p).to_string()
+	}
+}
+
+impl ServiceConfig {
+	/// Parses a `QUERY_SERVICE_CONFIG` struct.
+	#[must_use]
+	pub(in crate::advapi) unsafe fn from_raw(raw: &QUERY_SERVICE_CONFIG) -> Self {
+		Self {
+			service_type: co::SERVICE_TYPE::from_raw(raw.dwServiceType),
+			start_type: co::SERVICE_START::from_raw(raw.dwStartType),
+			error_control: co::SERVICE_ERROR::from_raw(raw.dwErrorControl),
+			binary_path_name: str_at(raw.lpBinaryPathName),
+			load_order_group: str_at(raw.lpLoadOrderGroup),
+			tag_id: raw.dwTagId,
+			dependencies: if raw.lpDependencies.is_null() {
+				Vec::new()
+			} else {
+				parse_multi_z_str(raw.lpDependencies, None)
+			},
+			service_start_name: str_at(raw.lpServiceStartName),
+			display_name: str_at(raw.lpDisplayName),
+		}
+	}
+}
+
+impl ServiceEntry {
+	/// Parses an `ENUM_SERVICE_STATUS` struct.
+	#[must_use]
+	pub(in crate::advapi) unsafe fn from_raw(raw: &ENUM_SERVICE_STATUS) -> Self {
+		let st = &raw.ServiceStatus;
+		Self {
+			service_name: str_at(raw.lpServiceName),
+			display_name: str_at(raw.lpDisplayName),
+			status: SERVICE_STATUS_PROCESS {
+				dwServiceType: st.dwServiceType,
+				dwCurrentState: st.dwCurrentState,
+				dwControlsAccepted: st.dwControlsAccepted,
+				dwWin32ExitCode: st.dwWin32ExitCode,
+				dwServiceSpecificExitCode: st.dwServiceSpecificExitCode,
+				dwCheckPoint: st.dwCheckPoint,
+				dwWaitHint: st.dwWaitPoint,
+				dwProcessId: 0,
+				dwServiceFlags: 0,
+			},
+		}
+	}
+
+	/// Parses an `ENUM_SERVICE_STATUS_PROCESS` struct.
+	#[must_use]
+	pub(in crate::advapi) unsafe fn from_raw_process(raw: &ENUM_SERVICE_STATUS_PROCESS) -> Self {
+		Self {
+			service_name: str_at(raw.lpServiceName),
+			display_name: str_at(raw.lpDisplayName),
+			status: raw.ServiceStatusProcess,
+		}
+	}
+}
+
+impl ServiceTriggerData {
+	const TYPE_BINARY: u32 = 1;
+	const TYPE_STRING: u32 = 2;
+	const TYPE_LEVEL: u32 = 3;
+	const TYPE_KEYWORD_ANY: u32 = 4;
+	const TYPE_KEYWORD_ALL: u32 = 5;
+
+	/// Returns the data type and the raw bytes.
+	#[must_use]
+	fn to_raw(&self) -> (u32, Vec<u8>) {
+		match self {
+			Self::Binary(data) => (Self::TYPE_BINARY, data.clone()),
+			Self::String(text) => (
+				Self::TYPE_STRING,
+				text.encode_utf16()
+					.chain([0, 0]) // also works as a multi-string
+					.flat_map(|ch| ch.to_le_bytes())
+					.collect(),
+			),
+			Self::Level(level) => (Self::TYPE_LEVEL, vec![*level]),
+			Self::KeywordAny(kw) => (Self::TYPE_KEYWORD_ANY, kw.to_le_bytes().to_vec()),
+			Self::KeywordAll(kw) => (Self::TYPE_KEYWORD_ALL, kw.to_le_bytes().to_vec()),
+		}
+	}
+
+	/// Parses the data type and the raw bytes.
+	#[must_use]
+	fn from_raw(data_type: u32, data: &[u8]) -> Self {
+		let read_u64 = || {
+			let mut buf = [0u8; 8];
+			let len = data.len().min(8);
+			buf[..len].copy_from_slice(&data[..len]);
+			u64::from_le_bytes(buf)
+		};
+		match data_type {
+			Self::TYPE_STRING => {
+				let wchars = data
+					.chunks_exact(2)
+					.map(|b| u16::from_le_bytes([b[0], b[1]]))
+					.collect::<Vec<_>>();
+				let text = String::from_utf16_lossy(&wchars);
+				Self::String(text.trim_end_matches('\0').to_owned())
+			},
+			Self::TYPE_LEVEL => Self::Level(data.first().copied().unwrap_or_default()),
+			Self::TYPE_KEYWORD_ANY => Self::KeywordAny(read_u64()),
+			Self::TYPE_KEYWORD_ALL => Self::KeywordAll(read_u64()),
+			_ => Self::Binary(data.to_vec()),
+		}
+	}
+}
+
+impl ServiceConfig2 {
+	/// Returns the information level of the variant.
+	#[must_use]
+	pub const fn level(&self) -> co::SERVICE_CONFIG {
+		match self {
+			Self::Description(_) => co::SERVICE_CONFIG::DESCRIPTION,
+			Self::FailureActions(_) => co::SERVICE_CONFIG::FAILURE_ACTIONS,
+			Self::FailureActionsFlag(_) => co::SERVICE_CONFIG::FAILURE_ACTIONS_FLAG,
+			Self::DelayedAutoStart(_) => co::SERVICE_CONFIG::DELAYED_AUTO_START_INFO,
+			Self::SidType(_) => co::SERVICE_CONFIG::SERVICE_SID_INFO,
+			Self::RequiredPrivileges(_) => co::SERVICE_CONFIG::REQUIRED_PRIVILEGES_INFO,
+			Self::PreshutdownTimeout(_) => co::SERVICE_CONFIG::PRESHUTDOWN_INFO,
+			Self::Triggers(_) => co::SERVICE_CONFIG::TRIGGER_INFO,
+		}
+	}
+
+	/// Parses the buffer filled by `QueryServiceConfig2`.
+	///
+	/// # Safety
+	///
+	/// The buffer must contain the struct of the given level, with its
+	/// pointers.
+	#[must_use]
+	pub(in crate::advapi) unsafe fn from_raw(
+		level: co::SERVICE_CONFIG,
+		buf: *const u8,
+	) -> SysResult<Self> {
+		let read_u32 = || *(buf as *const u32);
+		let read_ptr = || *(buf as *const *mut u16);
+
+		Ok(match level {
+			co::SERVICE_CONFIG::DESCRIPTION => Self::Description(str_at(read_ptr())),
+			co::SERVICE_CONFIG::FAILURE_ACTIONS => {
+				let raw = &*(buf as *const SERVICE_FAILURE_ACTIONS);
+				let opt_str = |p: *mut u16| if p.is_null() { None } else { Some(str_at(p)) };
+				Self::FailureActions(ServiceFailureActions {
+					reset_period_secs: raw.dwResetPeriod,
+					reboot_msg: opt_str(raw.lpRebootMsg),
+					command: opt_str(raw.lpCommand),
+					actions: if raw.lpsaActions.is_null() {
+						Vec::new()
+					} else {
+						std::slice::from_raw_parts(raw.lpsaActions, raw.cActions as _)
+							.iter()
+							.map(|a| ScAction {
+								action: co::SC_ACTION::from_raw(a.Type),
+								delay_ms: a.Delay,
+							})
+							.collect()
+					},
+				})
+			},
+			co::SERVICE_CONFIG::FAILURE_ACTIONS_FLAG => Self::FailureActionsFlag(read_u32() != 0),
+			co::SERVICE_CONFIG::DELAYED_AUTO_START_INFO => Self::DelayedAutoStart(read_u32() != 0),
+			co::SERVICE_CONFIG::SERVICE_SID_INFO => {
+				Self::SidType(co::SERVICE_SID_TYPE::from_raw(read_u32()))
+			},
+			co::SERVICE_CONFIG::REQUIRED_PRIVILEGES_INFO => Self::RequiredPrivileges({
+				let p = read_ptr();
+				if p.is_null() {
+					Vec::new()
+				} else {
+					parse_multi_z_str(p, None)
+				}
+			}),
+			co::SERVICE_CONFIG::PRESHUTDOWN_INFO => Self::PreshutdownTimeout(read_u32()),
+			co::SERVICE_CONFIG::TRIGGER_INFO => {
+				let raw = &*(buf as *const SERVICE_TRIGGER_INFO);
+				let triggers = if raw.pTriggers.is_null() {
+					&[]
+				} else {
+					std::slice::from_raw_parts(raw.pTriggers, raw.cTriggers as _)
+				};
+				Self::Triggers(
+					triggers
+						.iter()
+						.map(|t| ServiceTrigger {
+							trigger_type: co::SERVICE_TRIGGER_TYPE::from_raw(t.dwTriggerType),
+							action: co::SERVICE_TRIGGER_ACTION::from_raw(t.dwAction),
+							subtype: t.pTriggerSubtype.as_ref().copied(),
+							data: if t.pDataItems.is_null() {
+								Vec::new()
+							} else {
+								std::slice::from_raw_parts(t.pDataItems, t.cDataItems as _)
+									.iter()
+									.map(|item| {
+										ServiceTriggerData::from_raw(
+											item.dwDataType,
+											if item.pData.is_null() {
+												&[]
+											} else {
+												std::slice::from_raw_parts(
+													item.pData,
+													item.cbData as _,
+												)
+											},
+										)
+									})
+									.collect()
+							},
+						})
+						.collect(),
+				)
+			},
+			_ => return Err(co::ERROR::INVALID_LEVEL),
+		})
+	}
+
+	/// Builds the struct expected by `ChangeServiceConfig2` and passes a
+	/// pointer to it to the closure. All the memory is kept alive until the
+	/// closure returns.
+	pub(in crate::advapi) fn with_raw<R>(
+		&self,
+		func: impl FnOnce(*const std::ffi::c_void) -> R,
+	) -> R {
+		match self {
+			Self::Description(text) => {
+				let mut text_w = WString::from_str(text);
+				let raw = unsafe { text_w.as_mut_ptr() };
+				func(&raw as *const _ as _)
+			},
+			Self::FailureActions(fa) => {
+				let mut reboot_msg_w = fa.reboot_msg.as_deref().map(WString::from_str);
+				let mut command_w = fa.command.as_deref().map(WString::from_str);
+				let mut actions = fa
+					.actions
+					.iter()
+					.map(|a| SC_ACTION { Type: a.action.raw(), Delay: a.delay_ms })
+					.collect::<Vec<_>>();
+				let raw = SERVICE_FAILURE_ACTIONS {
+					dwResetPeriod: fa.reset_period_secs,
+					lpRebootMsg: reboot_msg_w
+						.as_mut()
+						.map_or(std::ptr::null_mut(), |w| unsafe { w.as_mut_ptr() }),
+					lpCommand: command_w
+						.as_mut()
+						.map_or(std::ptr::null_mut(), |w| unsafe { w.as_mut_ptr() }),
+					cActions: actions.len() as _,
+					lpsaActions: actions.as_mut_ptr(), // never null, so an empty list deletes the actions
+				};
+				func(&raw as *const _ as _)
+			},
+			Self::FailureActionsFlag(flag) | Self::DelayedAutoStart(flag) => {
+				let raw = *flag as i32; // BOOL
+				func(&raw as *const _ as _)
+			},
+			Self::SidType(sid_type) => {
+				let raw = sid_type.raw();
+				func(&raw as *const _ as _)
+			},
+			Self::RequiredPrivileges(privs) => {
+				let mut privs_w = WString::from_str_vec(privs);
+				let raw = unsafe { privs_w.as_mut_ptr() };
+				func(&raw as *const _ as _)
+			},
+			Self::PreshutdownTimeout(timeout) => func(timeout as *const _ as _),
+			Self::Triggers(triggers) => {
+				let mut subtypes = triggers
+					.iter()
+					.map(|t| t.subtype.unwrap_or_default())
+					.collect::<Vec<_>>();
+				let mut datas = triggers
+					.iter()
+					.map(|t| t.data.iter().map(|d| d.to_raw()).collect::<Vec<_>>())
+					.collect::<Vec<_>>();
+				let mut items = datas
+					.iter_mut()
+					.map(|data| {
+						data.iter_mut()
+							.map(|(data_type, bytes)| SERVICE_TRIGGER_SPECIFIC_DATA_ITEM {
+								dwDataType: *data_type,
+								cbData: bytes.len() as _,
+								pData: bytes.as_mut_ptr(),
+							})
+							.collect::<Vec<_>>()
+					})
+					.collect::<Vec<_>>();
+				let mut raw_triggers = triggers
+					.iter()
+					.zip(subtypes.iter_mut())
+					.zip(items.iter_mut())
+					.map(|((t, subtype), items)| SERVICE_TRIGGER {
+						dwTriggerType: t.trigger_type.raw(),
+						dwAction: t.action.raw(),
+						pTriggerSubtype: if t.subtype.is_some() {
+							subtype
+						} else {
+							std::ptr::null_mut()
+						},
+						cDataItems: items.len() as _,
+						pDataItems: if items.is_empty() {
+							std::ptr::null_mut()
+						} else {
+							items.as_mut_ptr()
+						},
+					})
+					.collect::<Vec<_>>();
+				let raw = SERVICE_TRIGGER_INFO {
+					cTriggers: raw_triggers.len() as _,
+					pTriggers: if raw_triggers.is_empty() {
+						std::ptr::null_mut() // deletes the triggers
+					} else {
+						raw_triggers.as_mut_ptr()
+					},
+					pReserved: std::ptr::null_mut(),
+				};
+				func(&raw as *const _ as _)
+			},
+		}
+	}
+}
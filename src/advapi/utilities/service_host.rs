@@ -0,0 +1,950 @@
+#![allow(non_snake_case)]
+
+use std::sync::{Arc, Mutex};
+
+use crate::advapi::ffi;
+use crate::co;
+use crate::decl::*;
+use crate::kernel::{ffi_types::*, privs::*};
+use crate::prelude::*;
+
+/// A Windows service run by a [`ServiceHost`](crate::ServiceHost).
+///
+/// The control handler runs in a different thread than the service itself, so
+/// the implementor must be `Send` and `Sync`.
+pub trait HostedService: Send + Sync {
+	/// Returns the controls accepted by the service while it's running or
+	/// paused. Defaults to [`SERVICE_ACCEPT::STOP`](crate::co::SERVICE_ACCEPT::STOP).
+	#[must_use]
+	fn accepted_controls(&self) -> co::SERVICE_ACCEPT {
+		co::SERVICE_ACCEPT::STOP
+	}
+
+	/// Called while the service is in the
+	/// [`SERVICE_STATE::START_PENDING`](crate::co::SERVICE_STATE::START_PENDING)
+	/// state, receiving the arguments passed to
+	/// [`StartService`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-startservicew).
+	///
+	/// A lengthy initialization should periodically call
+	/// [`ServiceStatus::checkpoint`](crate::ServiceStatus::checkpoint). If an
+	/// error is returned, the service is stopped without calling
+	/// [`run`](crate::prelude::HostedService::run).
+	///
+	/// The default implementation does nothing.
+	fn init(&self, args: &[String], status: &ServiceStatus) -> AnyResult<()> {
+		let _ = (args, status);
+		Ok(())
+	}
+
+	/// Runs the service, after it was reported as
+	/// [`SERVICE_STATE::RUNNING`](crate::co::SERVICE_STATE::RUNNING). Must
+	/// block until the service is asked to stop.
+	///
+	/// When the method returns, the service is reported as
+	/// [`SERVICE_STATE::STOPPED`](crate::co::SERVICE_STATE::STOPPED). If an
+	/// [`ERROR`](crate::co::ERROR) is returned, it's reported as the Win32
+	/// exit code; any other error is reported as a service-specific exit code
+	/// of 1.
+	fn run(&self, status: &ServiceStatus) -> AnyResult<()>;
+
+	/// Handles a control sent by the Service Control Manager.
+	///
+	/// Before this method is called, the host already reported the pending
+	/// state of [`SvcCtl::Stop`](crate::SvcCtl::Stop),
+	/// [`SvcCtl::Shutdown`](crate::SvcCtl::Shutdown),
+	/// [`SvcCtl::PreShutdown`](crate::SvcCtl::PreShutdown),
+	/// [`SvcCtl::Pause`](crate::SvcCtl::Pause) and
+	/// [`SvcCtl::Continue`](crate::SvcCtl::Continue). When stopping, the
+	/// implementation must signal [`run`](crate::prelude::HostedService::run)
+	/// to return. After a successful pause or continue, the host reports the
+	/// new state. [`SvcCtl::Interrogate`](crate::SvcCtl::Interrogate) is
+	/// answered by the host itself.
+	///
+	/// The returned value is passed back to the Service Control Manager:
+	/// usually [`ERROR::SUCCESS`](crate::co::ERROR::SUCCESS), or
+	/// [`ERROR::CALL_NOT_IMPLEMENTED`](crate::co::ERROR::CALL_NOT_IMPLEMENTED)
+	/// if the control is not handled.
+	fn on_control(&self, ctl: SvcCtl, status: &ServiceStatus) -> co::ERROR;
+}
+
+/// Receives the status reports of a [`ServiceStatus`](crate::ServiceStatus).
+///
+/// It's implemented by [`HSERVICESTATUS`](crate::HSERVICESTATUS), which
+/// forwards the reports to the Service Control Manager. Implementing it
+/// yourself allows the state machine to be driven without a real service.
+pub trait ServiceStatusSink: Send {
+	/// Receives the new status.
+	fn set_status(&self, status: &SERVICE_STATUS) -> SysResult<()>;
+}
+
+impl ServiceStatusSink for HSERVICESTATUS {
+	fn set_status(&self, status: &SERVICE_STATUS) -> SysResult<()> {
+		self.SetServiceStatus(&mut status.clone())
+	}
+}
+
+/// The state machine of a service, which reports each state transition to a
+/// [`ServiceStatusSink`](crate::prelude::ServiceStatusSink).
+///
+/// It keeps the checkpoint counter of the pending states, and sets the
+/// accepted controls: none while starting or stopping, the mask given at
+/// construction otherwise. Transitions not allowed by the Service Control
+/// Manager fail with [`ERROR::INVALID_STATE`](crate::co::ERROR::INVALID_STATE),
+/// and any transition after the service is stopped fails with
+/// [`ERROR::SERVICE_NOT_ACTIVE`](crate::co::ERROR::SERVICE_NOT_ACTIVE).
+///
+/// # Examples
+///
+/// Driving the state machine with a sink which only records the states:
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// struct FakeSink(Arc<Mutex<Vec<co::SERVICE_STATE>>>);
+///
+/// impl w::prelude::ServiceStatusSink for FakeSink {
+///     fn set_status(&self, status: &w::SERVICE_STATUS) -> w::SysResult<()> {
+///         self.0.lock().unwrap().push(status.dwCurrentState);
+///         Ok(())
+///     }
+/// }
+///
+/// let states = Arc::new(Mutex::new(Vec::new()));
+/// let status = w::ServiceStatus::new(
+///     Box::new(FakeSink(states.clone())),
+///     co::SERVICE_TYPE::WIN32_OWN_PROCESS,
+///     co::SERVICE_ACCEPT::STOP,
+/// );
+///
+/// status.report_start_pending(3000)?;
+/// status.report_running()?;
+/// assert!(status.report_paused().is_err()); // must be PAUSE_PENDING first
+/// status.report_stop_pending(3000)?;
+/// status.report_stopped(co::ERROR::SUCCESS, 0)?;
+///
+/// assert_eq!(states.lock().unwrap().len(), 4);
+/// # w::SysResult::Ok(())
+/// ```
+pub struct ServiceStatus {
+	accepted: co::SERVICE_ACCEPT,
+	inner: Mutex<(Box<dyn ServiceStatusSink>, SERVICE_STATUS, bool)>, // sink, status, started
+}
+
+impl ServiceStatus {
+	/// Creates a new state machine, in the
+	/// [`SERVICE_STATE::STOPPED`](crate::co::SERVICE_STATE::STOPPED) state,
+	/// not yet started.
+	#[must_use]
+	pub fn new(
+		sink: Box<dyn ServiceStatusSink>,
+		service_type: co::SERVICE_TYPE,
+		accepted: co::SERVICE_ACCEPT,
+	) -> Self {
+		let status = SERVICE_STATUS {
+			dwServiceType: service_type,
+			dwCurrentState: co::SERVICE_STATE::STOPPED,
+			..Default::default()
+		};
+		Self {
+			accepted,
+			inner: Mutex::new((sink, status, false)),
+		}
+	}
+
+	/// Returns the last reported status.
+	#[must_use]
+	pub fn status(&self) -> SERVICE_STATUS {
+		self.inner.lock().unwrap().1
+	}
+
+	/// Returns the current state.
+	#[must_use]
+	pub fn state(&self) -> co::SERVICE_STATE {
+		self.status().dwCurrentState
+	}
+
+	/// Tells whether the state is one of the pending ones.
+	#[must_use]
+	fn is_pending(state: co::SERVICE_STATE) -> bool {
+		matches!(
+			state,
+			co::SERVICE_STATE::START_PENDING
+				| co::SERVICE_STATE::STOP_PENDING
+				| co::SERVICE_STATE::PAUSE_PENDING
+				| co::SERVICE_STATE::CONTINUE_PENDING
+		)
+	}
+
+	fn transition(
+		&self,
+		new_state: co::SERVICE_STATE,
+		allowed_from: &[co::SERVICE_STATE],
+		wait_hint_ms: u32,
+		exit_codes: (co::ERROR, u32),
+	) -> SysResult<()> {
+		let mut inner = self.inner.lock().unwrap();
+		let (sink, status, started) = &mut *inner;
+
+		let cur_state = status.dwCurrentState;
+		if *started && cur_state == co::SERVICE_STATE::STOPPED {
+			return Err(co::ERROR::SERVICE_NOT_ACTIVE);
+		} else if !allowed_from.contains(&cur_state) {
+			return Err(co::ERROR::INVALID_STATE);
+		}
+
+		let mut new_status = *status;
+		new_status.dwCurrentState = new_state;
+		new_status.dwControlsAccepted = match new_state {
+			co::SERVICE_STATE::START_PENDING
+			| co::SERVICE_STATE::STOP_PENDING
+			| co::SERVICE_STATE::STOPPED => co::SERVICE_ACCEPT::default(),
+			_ => self.accepted,
+		};
+		if Self::is_pending(new_state) {
+			new_status.dwCheckPoint = if new_state == cur_state {
+				status.dwCheckPoint + 1 // same pending state reported again
+			} else {
+				1
+			};
+			new_status.dwWaitPoint = wait_hint_ms;
+		} else {
+			new_status.dwCheckPoint = 0;
+			new_status.dwWaitPoint = 0;
+		}
+		new_status.dwWin32ExitCode = exit_codes.0.raw();
+		new_status.dwServiceSpecificExitCode = exit_codes.1;
+
+		sink.set_status(&new_status)?;
+		*status = new_status;
+		*started = true;
+		Ok(())
+	}
+
+	/// Reports the next checkpoint of the current pending state, with a new
+	/// wait hint.
+	///
+	/// Fails with [`ERROR::INVALID_STATE`](crate::co::ERROR::INVALID_STATE) if
+	/// the service is not in a pending state.
+	pub fn checkpoint(&self, wait_hint_ms: u32) -> SysResult<()> {
+		let cur_state = self.state();
+		if !Self::is_pending(cur_state) {
+			return Err(co::ERROR::INVALID_STATE);
+		}
+		self.transition(cur_state, &[cur_state], wait_hint_ms, (co::ERROR::SUCCESS, 0))
+	}
+
+	/// Reports the
+	/// [`SERVICE_STATE::CONTINUE_PENDING`](crate::co::SERVICE_STATE::CONTINUE_PENDING)
+	/// state. The service must be paused.
+	pub fn report_continue_pending(&self, wait_hint_ms: u32) -> SysResult<()> {
+		self.transition(
+			co::SERVICE_STATE::CONTINUE_PENDING,
+			&[co::SERVICE_STATE::PAUSED],
+			wait_hint_ms,
+			(co::ERROR::SUCCESS, 0),
+		)
+	}
+
+	/// Reports the
+	/// [`SERVICE_STATE::PAUSE_PENDING`](crate::co::SERVICE_STATE::PAUSE_PENDING)
+	/// state. The service must be running.
+	pub fn report_pause_pending(&self, wait_hint_ms: u32) -> SysResult<()> {
+		self.transition(
+			co::SERVICE_STATE::PAUSE_PENDING,
+			&[co::SERVICE_STATE::RUNNING],
+			wait_hint_ms,
+			(co::ERROR::SUCCESS, 0),
+		)
+	}
+
+	/// Reports the
+	/// [`SERVICE_STATE::PAUSED`](crate::co::SERVICE_STATE::PAUSED) state. The
+	/// service must be pausing. A failed continue can also be reverted.
+	pub fn report_paused(&self) -> SysResult<()> {
+		self.transition(
+			co::SERVICE_STATE::PAUSED,
+			&[co::SERVICE_STATE::PAUSE_PENDING, co::SERVICE_STATE::CONTINUE_PENDING],
+			0,
+			(co::ERROR::SUCCESS, 0),
+		)
+	}
+
+	/// Reports the
+	/// [`SERVICE_STATE::RUNNING`](crate::co::SERVICE_STATE::RUNNING) state. The
+	/// service must be starting, or continuing after a pause. A failed pause
+	/// can also be reverted.
+	pub fn report_running(&self) -> SysResult<()> {
+		self.transition(
+			co::SERVICE_STATE::RUNNING,
+			&[
+				co::SERVICE_STATE::START_PENDING,
+				co::SERVICE_STATE::CONTINUE_PENDING,
+				co::SERVICE_STATE::PAUSE_PENDING,
+			],
+			0,
+			(co::ERROR::SUCCESS, 0),
+		)
+	}
+
+	/// Reports the
+	/// [`SERVICE_STATE::START_PENDING`](crate::co::SERVICE_STATE::START_PENDING)
+	/// state, which must be the first one.
+	pub fn report_start_pending(&self, wait_hint_ms: u32) -> SysResult<()> {
+		self.transition(
+			co::SERVICE_STATE::START_PENDING,
+			&[co::SERVICE_STATE::STOPPED],
+			wait_hint_ms,
+			(co::ERROR::SUCCESS, 0),
+		)
+	}
+
+	/// Reports the
+	/// [`SERVICE_STATE::STOP_PENDING`](crate::co::SERVICE_STATE::STOP_PENDING)
+	/// state. If the service is already stopping, reports the next checkpoint.
+	pub fn report_stop_pending(&self, wait_hint_ms: u32) -> SysResult<()> {
+		self.transition(
+			co::SERVICE_STATE::STOP_PENDING,
+			&[
+				co::SERVICE_STATE::START_PENDING,
+				co::SERVICE_STATE::RUNNING,
+				co::SERVICE_STATE::PAUSE_PENDING,
+				co::SERVICE_STATE::PAUSED,
+				co::SERVICE_STATE::CONTINUE_PENDING,
+				co::SERVICE_STATE::STOP_PENDING,
+			],
+			wait_hint_ms,
+			(co::ERROR::SUCCESS, 0),
+		)
+	}
+
+	/// Reports the
+	/// [`SERVICE_STATE::STOPPED`](crate::co::SERVICE_STATE::STOPPED) state,
+	/// which is final.
+	///
+	/// If `service_specific_exit_code` is not zero, the Win32 exit code is
+	/// reported as
+	/// [`ERROR::SERVICE_SPECIFIC_ERROR`](crate::co::ERROR::SERVICE_SPECIFIC_ERROR).
+	pub fn report_stopped(
+		&self,
+		win32_exit_code: co::ERROR,
+		service_specific_exit_code: u32,
+	) -> SysResult<()> {
+		self.transition(
+			co::SERVICE_STATE::STOPPED,
+			&[
+				co::SERVICE_STATE::STOPPED, // failed before starting
+				co::SERVICE_STATE::START_PENDING,
+				co::SERVICE_STATE::RUNNING,
+				co::SERVICE_STATE::PAUSE_PENDING,
+				co::SERVICE_STATE::PAUSED,
+				co::SERVICE_STATE::CONTINUE_PENDING,
+				co::SERVICE_STATE::STOP_PENDING,
+			],
+			0,
+			if service_specific_exit_code != 0 {
+				(co::ERROR::SERVICE_SPECIFIC_ERROR, service_specific_exit_code)
+			} else {
+				(win32_exit_code, 0)
+			},
+		)
+	}
+}
+
+/// Runs one or more services in the current process, through
+/// [`StartServiceCtrlDispatcher`](https://learn.microsoft.com/en-us/windows/win32/api/winsvc/nf-winsvc-startservicectrldispatcherw).
+///
+/// For each service started by the Service Control Manager, the host registers
+/// the control handler, and drives its [`ServiceStatus`](crate::ServiceStatus)
+/// through the lifecycle:
+///
+/// 1. reports `START_PENDING` and calls
+///    [`HostedService::init`](crate::prelude::HostedService::init);
+/// 2. reports `RUNNING` and calls
+///    [`HostedService::run`](crate::prelude::HostedService::run);
+/// 3. reports `STOPPED` with the exit code of `run`.
+///
+/// Stop, pause and continue controls have their pending states reported before
+/// [`HostedService::on_control`](crate::prelude::HostedService::on_control) is
+/// called.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::{Arc, Condvar, Mutex};
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// #[derive(Default)]
+/// struct MyService {
+///     stop: Arc<(Mutex<bool>, Condvar)>,
+/// }
+///
+/// impl w::prelude::HostedService for MyService {
+///     fn run(&self, _: &w::ServiceStatus) -> w::AnyResult<()> {
+///         let (lock, cvar) = &*self.stop;
+///         let _stopped = cvar.wait_while(lock.lock().unwrap(), |s| !*s).unwrap();
+///         Ok(())
+///     }
+///
+///     fn on_control(&self, ctl: w::SvcCtl, _: &w::ServiceStatus) -> co::ERROR {
+///         match ctl {
+///             w::SvcCtl::Stop | w::SvcCtl::Shutdown => {
+///                 let (lock, cvar) = &*self.stop;
+///                 *lock.lock().unwrap() = true;
+///                 cvar.notify_all();
+///                 co::ERROR::SUCCESS
+///             },
+///             _ => co::ERROR::CALL_NOT_IMPLEMENTED,
+///         }
+///     }
+/// }
+///
+/// let mut host = w::ServiceHost::new();
+/// host.add("MyService", MyService::default());
+/// host.run()?; // blocks until all services stop
+/// # w::SysResult::Ok(())
+/// ```
+#[derive(Default)]
+pub struct ServiceHost {
+	services: Vec<(String, Arc<dyn HostedService>)>,
+}
+
+/// Services of the running dispatcher, since `ServiceMain` has no context
+/// parameter; it's identified by the name in its first argument.
+static SERVICES: Mutex<Vec<(String, Arc<dyn HostedService>)>> = Mutex::new(Vec::new());
+
+/// Wait hint reported for the pending states set by the host.
+const WAIT_HINT_MS: u32 = 3000;
+
+#[repr(C)]
+struct SERVICE_TABLE_ENTRY {
+	lpServiceName: *mut u16,
+	lpServiceProc: Option<extern "system" fn(u32, *mut *mut u16)>,
+}
+
+/// Context passed to the control handler.
+struct ServiceCtx {
+	service: Arc<dyn HostedService>,
+	status: ServiceStatus,
+}
+
+impl ServiceHost {
+	/// Creates a new, empty `ServiceHost`.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a service, with the name it was installed with.
+	///
+	/// If more than one service is added, they are reported as
+	/// [`SERVICE_TYPE::WIN32_SHARE_PROCESS`](crate::co::SERVICE_TYPE::WIN32_SHARE_PROCESS).
+	pub fn add(&mut self, name: &str, service: impl HostedService + 'static) -> &mut Self {
+		self.services.push((name.to_owned(), Arc::new(service)));
+		self
+	}
+
+	/// Connects the main thread to the Service Control Manager, and runs the
+	/// services as they are started. Returns when all services have stopped.
+	///
+	/// Fails with
+	/// [`ERROR::FAILED_SERVICE_CONTROLLER_CONNECT`](crate::co::ERROR::FAILED_SERVICE_CONTROLLER_CONNECT)
+	/// if the process was not started by the Service Control Manager – for
+	/// example, when running from a console.
+	pub fn run(self) -> SysResult<()> {
+		let mut names_w = self
+			.services
+			.iter()
+			.map(|(name, _)| WString::from_str(name))
+			.collect::<Vec<_>>();
+		let mut table = names_w
+			.iter_mut()
+			.map(|name_w| SERVICE_TABLE_ENTRY {
+				lpServiceName: unsafe { name_w.as_mut_ptr() },
+				lpServiceProc: Some(Self::service_main),
+			})
+			.collect::<Vec<_>>();
+		table.push(SERVICE_TABLE_ENTRY {
+			lpServiceName: std::ptr::null_mut(),
+			lpServiceProc: None, // table terminator
+		});
+
+		*SERVICES.lock().unwrap() = self.services;
+		let ret =
+			bool_to_sysresult(unsafe { ffi::StartServiceCtrlDispatcherW(table.as_ptr() as _) });
+		SERVICES.lock().unwrap().clear();
+		ret
+	}
+
+	extern "system" fn service_main(argc: u32, argv: *mut *mut u16) {
+		let args = (0..argc as usize)
+			.map(|i| unsafe { WString::from_wchars_nullt(*argv.add(i)) }.to_string())
+			.collect::<Vec<_>>();
+		let (service, service_type) = {
+			let services = SERVICES.lock().unwrap();
+			let service = match services
+				.iter()
+				.find(|(name, _)| args.first().is_some_and(|a| a.eq_ignore_ascii_case(name)))
+			{
+				Some((_, service)) => service.clone(),
+				None => return, // unknown service, should never happen
+			};
+			let service_type = if services.len() > 1 {
+				co::SERVICE_TYPE::WIN32_SHARE_PROCESS
+			} else {
+				co::SERVICE_TYPE::WIN32_OWN_PROCESS
+			};
+			(service, service_type)
+		};
+
+		let name_w = WString::from_str(&args[0]);
+		let ctx = Box::into_raw(Box::new(ServiceCtx {
+			service: service.clone(),
+			status: ServiceStatus::new(
+				Box::new(HSERVICESTATUS::NULL), // replaced below
+				service_type,
+				service.accepted_controls(),
+			),
+		}));
+
+		// The handler may still be called while the final status is being
+		// reported, so the context is never freed; a service starts only once
+		// per process.
+		let ctx = unsafe { &*ctx };
+		let hss = unsafe {
+			ffi::RegisterServiceCtrlHandlerExW(
+				name_w.as_ptr(),
+				Self::handler_proc as _,
+				ctx as *const _ as _,
+			)
+		};
+		if hss.is_null() {
+			return; // cannot report anything
+		}
+		ctx.status.inner.lock().unwrap().0 = Box::new(unsafe { HSERVICESTATUS::from_ptr(hss) });
+
+		Self::run_service(&*service, &ctx.status, &args[1..]);
+	}
+
+	/// Drives the service through its lifecycle.
+	fn run_service(service: &dyn HostedService, status: &ServiceStatus, args: &[String]) {
+		if status.report_start_pending(WAIT_HINT_MS).is_err() {
+			return;
+		}
+
+		let res = service
+			.init(args, status)
+			.and_then(|_| status.report_running().map_err(|e| e.into()))
+			.and_then(|_| service.run(status));
+
+		let _ = match res {
+			Ok(_) => status.report_stopped(co::ERROR::SUCCESS, 0),
+			Err(e) => match e.downcast_ref::<co::ERROR>() {
+				Some(err) => status.report_stopped(*err, 0),
+				None => status.report_stopped(co::ERROR::SUCCESS, 1),
+			},
+		};
+	}
+
+	extern "system" fn handler_proc(
+		control: u32,
+		event_type: u32,
+		event_data: PVOID,
+		context: PVOID,
+	) -> u32 {
+		let ctx = unsafe { &*(context as *const ServiceCtx) };
+		let ctl = unsafe { SvcCtl::from_raw(control, event_type, event_data) };
+		Self::dispatch_control(&*ctx.service, &ctx.status, ctl).raw()
+	}
+
+	/// Reports the pending states, and forwards the control to the service.
+	fn dispatch_control(
+		service: &dyn HostedService,
+		status: &ServiceStatus,
+		ctl: SvcCtl,
+	) -> co::ERROR {
+		let pending = match ctl {
+			SvcCtl::Interrogate => return co::ERROR::SUCCESS, // SCM already knows the state
+			SvcCtl::Stop | SvcCtl::Shutdown | SvcCtl::PreShutdown => {
+				status.report_stop_pending(WAIT_HINT_MS)
+			},
+			SvcCtl::Pause => status.report_pause_pending(WAIT_HINT_MS),
+			SvcCtl::Continue => status.report_continue_pending(WAIT_HINT_MS),
+			_ => Ok(()),
+		};
+		if let Err(err) = pending {
+			return err; // control not valid in the current state
+		}
+
+		let is_pause = matches!(ctl, SvcCtl::Pause);
+		let is_continue = matches!(ctl, SvcCtl::Continue);
+		let ret = service.on_control(ctl, status);
+
+		if is_pause {
+			let _ = if ret == co::ERROR::SUCCESS {
+				status.report_paused()
+			} else {
+				status.report_running() // pause failed
+			};
+		} else if is_continue {
+			let _ = if ret == co::ERROR::SUCCESS {
+				status.report_running()
+			} else {
+				status.report_paused() // continue failed
+			};
+		}
+		ret
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	struct RecordingSink(Arc<Mutex<Vec<SERVICE_STATUS>>>);
+
+	impl ServiceStatusSink for RecordingSink {
+		fn set_status(&self, status: &SERVICE_STATUS) -> SysResult<()> {
+			self.0.lock().unwrap().push(*status);
+			Ok(())
+		}
+	}
+
+	struct FailingSink;
+
+	impl ServiceStatusSink for FailingSink {
+		fn set_status(&self, _: &SERVICE_STATUS) -> SysResult<()> {
+			Err(co::ERROR::INVALID_HANDLE)
+		}
+	}
+
+	fn new_status() -> (ServiceStatus, Arc<Mutex<Vec<SERVICE_STATUS>>>) {
+		let reports = Arc::new(Mutex::new(Vec::new()));
+		let status = ServiceStatus::new(
+			Box::new(RecordingSink(reports.clone())),
+			co::SERVICE_TYPE::WIN32_OWN_PROCESS,
+			co::SERVICE_ACCEPT::STOP | co::SERVICE_ACCEPT::PAUSE_CONTINUE,
+		);
+		(status, reports)
+	}
+
+	fn states(reports: &Mutex<Vec<SERVICE_STATUS>>) -> Vec<co::SERVICE_STATE> {
+		reports
+			.lock()
+			.unwrap()
+			.iter()
+			.map(|s| s.dwCurrentState)
+			.collect()
+	}
+
+	fn running_status() -> (ServiceStatus, Arc<Mutex<Vec<SERVICE_STATUS>>>) {
+		let (status, reports) = new_status();
+		status.report_start_pending(WAIT_HINT_MS).unwrap();
+		status.report_running().unwrap();
+		reports.lock().unwrap().clear();
+		(status, reports)
+	}
+
+	/// Records the controls it receives, answering them with a fixed value.
+	struct RecordingService {
+		ret: co::ERROR,
+		init_ret: Option<co::ERROR>,
+		run_fails: bool,
+		calls: Mutex<Vec<String>>,
+	}
+
+	impl RecordingService {
+		fn new(ret: co::ERROR) -> Self {
+			Self {
+				ret,
+				init_ret: None,
+				run_fails: false,
+				calls: Mutex::new(Vec::new()),
+			}
+		}
+
+		fn calls(&self) -> Vec<String> {
+			self.calls.lock().unwrap().clone()
+		}
+	}
+
+	impl HostedService for RecordingService {
+		fn init(&self, args: &[String], status: &ServiceStatus) -> AnyResult<()> {
+			assert_eq!(status.state(), co::SERVICE_STATE::START_PENDING);
+			self.calls
+				.lock()
+				.unwrap()
+				.push(format!("init {}", args.join(" ")));
+			match self.init_ret {
+				Some(err) => Err(err.into()),
+				None => Ok(()),
+			}
+		}
+
+		fn run(&self, status: &ServiceStatus) -> AnyResult<()> {
+			assert_eq!(status.state(), co::SERVICE_STATE::RUNNING);
+			self.calls.lock().unwrap().push("run".to_owned());
+			if self.run_fails {
+				Err("not a Win32 error".into())
+			} else {
+				Ok(())
+			}
+		}
+
+		fn on_control(&self, ctl: SvcCtl, status: &ServiceStatus) -> co::ERROR {
+			let name = match ctl {
+				SvcCtl::Stop => "stop",
+				SvcCtl::Shutdown => "shutdown",
+				SvcCtl::Pause => "pause",
+				SvcCtl::Continue => "continue",
+				SvcCtl::ParamChange => "param_change",
+				_ => "other",
+			};
+			self.calls
+				.lock()
+				.unwrap()
+				.push(format!("{} {}", name, status.state().raw()));
+			self.ret
+		}
+	}
+
+	#[test]
+	fn lifecycle() {
+		let (status, reports) = new_status();
+		assert_eq!(status.state(), co::SERVICE_STATE::STOPPED);
+
+		status.report_start_pending(1000).unwrap();
+		status.checkpoint(2000).unwrap();
+		status.report_running().unwrap();
+		status.report_stop_pending(500).unwrap();
+		status.report_stop_pending(500).unwrap();
+		status.report_stopped(co::ERROR::SUCCESS, 0).unwrap();
+
+		let reports = reports.lock().unwrap();
+		let summary = reports
+			.iter()
+			.map(|s| (s.dwCurrentState, s.dwCheckPoint, s.dwWaitPoint, s.dwControlsAccepted))
+			.collect::<Vec<_>>();
+		let none = co::SERVICE_ACCEPT::default();
+		let accepted = co::SERVICE_ACCEPT::STOP | co::SERVICE_ACCEPT::PAUSE_CONTINUE;
+		assert_eq!(
+			summary,
+			[
+				(co::SERVICE_STATE::START_PENDING, 1, 1000, none),
+				(co::SERVICE_STATE::START_PENDING, 2, 2000, none),
+				(co::SERVICE_STATE::RUNNING, 0, 0, accepted),
+				(co::SERVICE_STATE::STOP_PENDING, 1, 500, none),
+				(co::SERVICE_STATE::STOP_PENDING, 2, 500, none),
+				(co::SERVICE_STATE::STOPPED, 0, 0, none),
+			]
+		);
+		assert!(reports
+			.iter()
+			.all(|s| s.dwServiceType == co::SERVICE_TYPE::WIN32_OWN_PROCESS
+				&& s.dwWin32ExitCode == 0));
+	}
+
+	#[test]
+	fn invalid_transitions() {
+		let (status, reports) = new_status();
+		assert_eq!(status.report_running(), Err(co::ERROR::INVALID_STATE));
+		assert_eq!(status.checkpoint(0), Err(co::ERROR::INVALID_STATE));
+
+		status.report_start_pending(0).unwrap();
+		status.report_running().unwrap();
+		assert_eq!(status.report_paused(), Err(co::ERROR::INVALID_STATE));
+		assert_eq!(status.report_continue_pending(0), Err(co::ERROR::INVALID_STATE));
+		assert_eq!(status.report_start_pending(0), Err(co::ERROR::INVALID_STATE));
+		assert_eq!(status.checkpoint(0), Err(co::ERROR::INVALID_STATE));
+
+		status.report_stopped(co::ERROR::SUCCESS, 0).unwrap();
+		assert_eq!(status.report_start_pending(0), Err(co::ERROR::SERVICE_NOT_ACTIVE));
+		assert_eq!(
+			status.report_stopped(co::ERROR::SUCCESS, 0),
+			Err(co::ERROR::SERVICE_NOT_ACTIVE),
+		);
+
+		assert_eq!(
+			states(&reports),
+			[
+				co::SERVICE_STATE::START_PENDING,
+				co::SERVICE_STATE::RUNNING,
+				co::SERVICE_STATE::STOPPED,
+			]
+		);
+	}
+
+	#[test]
+	fn stopped_before_starting() {
+		let (status, reports) = new_status();
+		status.report_stopped(co::ERROR::ACCESS_DENIED, 0).unwrap();
+
+		let reports = reports.lock().unwrap();
+		assert_eq!(reports.len(), 1);
+		assert_eq!(reports[0].dwWin32ExitCode, co::ERROR::ACCESS_DENIED.raw());
+		assert_eq!(status.report_start_pending(0), Err(co::ERROR::SERVICE_NOT_ACTIVE));
+	}
+
+	#[test]
+	fn service_specific_exit_code() {
+		let (status, reports) = running_status();
+		status.report_stopped(co::ERROR::ACCESS_DENIED, 42).unwrap();
+
+		let last = *reports.lock().unwrap().last().unwrap();
+		assert_eq!(last.dwWin32ExitCode, co::ERROR::SERVICE_SPECIFIC_ERROR.raw());
+		assert_eq!(last.dwServiceSpecificExitCode, 42);
+	}
+
+	#[test]
+	fn sink_failure_keeps_state() {
+		let status = ServiceStatus::new(
+			Box::new(FailingSink),
+			co::SERVICE_TYPE::WIN32_OWN_PROCESS,
+			co::SERVICE_ACCEPT::STOP,
+		);
+		assert_eq!(status.report_start_pending(0), Err(co::ERROR::INVALID_HANDLE));
+		assert_eq!(status.state(), co::SERVICE_STATE::STOPPED);
+		assert_eq!(
+			status.report_stopped(co::ERROR::SUCCESS, 0),
+			Err(co::ERROR::INVALID_HANDLE), // allowed, since not started
+		);
+	}
+
+	#[test]
+	fn dispatch_stop() {
+		let (status, reports) = running_status();
+		let service = RecordingService::new(co::ERROR::SUCCESS);
+
+		let ret = ServiceHost::dispatch_control(&service, &status, SvcCtl::Stop);
+		assert_eq!(ret, co::ERROR::SUCCESS);
+		assert_eq!(service.calls(), [format!("stop {}", co::SERVICE_STATE::STOP_PENDING.raw())]);
+		assert_eq!(states(&reports), [co::SERVICE_STATE::STOP_PENDING]);
+
+		// A second stop reports the next checkpoint.
+		ServiceHost::dispatch_control(&service, &status, SvcCtl::Shutdown);
+		assert_eq!(status.status().dwCheckPoint, 2);
+	}
+
+	#[test]
+	fn dispatch_pause_continue() {
+		let (status, reports) = running_status();
+		let service = RecordingService::new(co::ERROR::SUCCESS);
+
+		ServiceHost::dispatch_control(&service, &status, SvcCtl::Pause);
+		ServiceHost::dispatch_control(&service, &status, SvcCtl::Continue);
+
+		assert_eq!(
+			service.calls(),
+			[
+				format!("pause {}", co::SERVICE_STATE::PAUSE_PENDING.raw()),
+				format!("continue {}", co::SERVICE_STATE::CONTINUE_PENDING.raw()),
+			]
+		);
+		assert_eq!(
+			states(&reports),
+			[
+				co::SERVICE_STATE::PAUSE_PENDING,
+				co::SERVICE_STATE::PAUSED,
+				co::SERVICE_STATE::CONTINUE_PENDING,
+				co::SERVICE_STATE::RUNNING,
+			]
+		);
+	}
+
+	#[test]
+	fn dispatch_failed_pause_continue() {
+		let (status, reports) = running_status();
+		let service = RecordingService::new(co::ERROR::CALL_NOT_IMPLEMENTED);
+
+		let ret = ServiceHost::dispatch_control(&service, &status, SvcCtl::Pause);
+		assert_eq!(ret, co::ERROR::CALL_NOT_IMPLEMENTED);
+		assert_eq!(
+			states(&reports),
+			[
+				co::SERVICE_STATE::PAUSE_PENDING,
+				co::SERVICE_STATE::RUNNING, // reverted
+			]
+		);
+
+		reports.lock().unwrap().clear();
+		status.report_pause_pending(0).unwrap();
+		status.report_paused().unwrap();
+		ServiceHost::dispatch_control(&service, &status, SvcCtl::Continue);
+		assert_eq!(
+			states(&reports),
+			[
+				co::SERVICE_STATE::PAUSE_PENDING,
+				co::SERVICE_STATE::PAUSED,
+				co::SERVICE_STATE::CONTINUE_PENDING,
+				co::SERVICE_STATE::PAUSED, // reverted
+			]
+		);
+	}
+
+	#[test]
+	fn dispatch_invalid_state() {
+		let (status, reports) = new_status();
+		let service = RecordingService::new(co::ERROR::SUCCESS);
+
+		let ret = ServiceHost::dispatch_control(&service, &status, SvcCtl::Pause);
+		assert_eq!(ret, co::ERROR::INVALID_STATE);
+		assert!(service.calls().is_empty());
+		assert!(reports.lock().unwrap().is_empty());
+	}
+
+	#[test]
+	fn dispatch_no_pending_state() {
+		let (status, reports) = running_status();
+		let service = RecordingService::new(co::ERROR::SUCCESS);
+
+		let ret = ServiceHost::dispatch_control(&service, &status, SvcCtl::Interrogate);
+		assert_eq!(ret, co::ERROR::SUCCESS);
+		assert!(service.calls().is_empty()); // answered by the host
+
+		ServiceHost::dispatch_control(&service, &status, SvcCtl::ParamChange);
+		assert_eq!(service.calls(), [format!("param_change {}", co::SERVICE_STATE::RUNNING.raw())]);
+		assert!(reports.lock().unwrap().is_empty());
+	}
+
+	#[test]
+	fn run_service() {
+		let (status, reports) = new_status();
+		let service = RecordingService::new(co::ERROR::SUCCESS);
+
+		ServiceHost::run_service(&service, &status, &["a".to_owned(), "b".to_owned()]);
+		assert_eq!(service.calls(), ["init a b", "run"]);
+		assert_eq!(
+			states(&reports),
+			[
+				co::SERVICE_STATE::START_PENDING,
+				co::SERVICE_STATE::RUNNING,
+				co::SERVICE_STATE::STOPPED,
+			]
+		);
+		assert_eq!(status.status().dwWin32ExitCode, 0);
+	}
+
+	#[test]
+	fn run_service_errors() {
+		let (status, reports) = new_status();
+		let mut service = RecordingService::new(co::ERROR::SUCCESS);
+		service.init_ret = Some(co::ERROR::FILE_NOT_FOUND);
+
+		ServiceHost::run_service(&service, &status, &[]);
+		assert_eq!(service.calls(), ["init "]); // run not called
+		assert_eq!(
+			states(&reports),
+			[co::SERVICE_STATE::START_PENDING, co::SERVICE_STATE::STOPPED,]
+		);
+		assert_eq!(status.status().dwWin32ExitCode, co::ERROR::FILE_NOT_FOUND.raw());
+
+		let (status, _) = new_status();
+		let mut service = RecordingService::new(co::ERROR::SUCCESS);
+		service.run_fails = true;
+
+		ServiceHost::run_service(&service, &status, &[]);
+		let last = status.status();
+		assert_eq!(last.dwWin32ExitCode, co::ERROR::SERVICE_SPECIFIC_ERROR.raw());
+		assert_eq!(last.dwServiceSpecificExitCode, 1);
+	}
+}
@@ -1,6 +1,12 @@
 use crate::co;
 use crate::decl::*;
 
+/// Type alias to
+/// [`EDITSTREAMCALLBACK`](https://learn.microsoft.com/en-us/windows/win32/api/richedit/nc-richedit-editstreamcallback)
+/// callback function.
+pub type EDITSTREAMCALLBACK =
+	extern "system" fn(cookie: usize, buf: *mut u8, cb: i32, pcb: *mut i32) -> u32;
+
 /// Type alias to
 /// [`LPFNPSPCALLBACK`](https://learn.microsoft.com/en-us/windows/win32/api/prsht/nc-prsht-lpfnpspcallbackw)
 /// callback function.
@@ -14,6 +14,19 @@ const_ordinary! { ADRF: u32;
 	DRAWIMAGE 3
 }
 
+const_bitflag! { AURL: u32;
+	/// [`em::AutoUrlDetect`](crate::msg::em::AutoUrlDetect) `flags` (`u32`).
+	=>
+	/// Disables the URL detection (zero).
+	DISABLE 0
+	ENABLEURL 1
+	ENABLEEMAILADDR 2
+	ENABLETELNO 4
+	ENABLEEAURLS 8
+	ENABLEDRIVELETTERS 16
+	DISABLEMIXEDLGC 32
+}
+
 const_wm! { BCM;
 	/// Button control
 	/// [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-button-control-reference-messages)
@@ -233,6 +246,50 @@ const_ordinary! { CDRF: u32;
 	NOTIFYPOSTERASE 0x0000_0040
 }
 
+const_bitflag! { CFE: u32;
+	/// [`CHARFORMAT2`](crate::CHARFORMAT2) `dwEffects` (`u32`).
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	BOLD 0x0000_0001
+	ITALIC 0x0000_0002
+	UNDERLINE 0x0000_0004
+	STRIKEOUT 0x0000_0008
+	PROTECTED 0x0000_0010
+	LINK 0x0000_0020
+	HIDDEN 0x0000_0100
+	SUBSCRIPT 0x0001_0000
+	SUPERSCRIPT 0x0002_0000
+	AUTOBACKCOLOR 0x0400_0000
+	AUTOCOLOR 0x4000_0000
+}
+
+const_bitflag! { CFM: u32;
+	/// [`CHARFORMAT2`](crate::CHARFORMAT2) `dwMask` (`u32`).
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	BOLD 0x0000_0001
+	ITALIC 0x0000_0002
+	UNDERLINE 0x0000_0004
+	STRIKEOUT 0x0000_0008
+	PROTECTED 0x0000_0010
+	LINK 0x0000_0020
+	HIDDEN 0x0000_0100
+	SPACING 0x0020_0000
+	WEIGHT 0x0040_0000
+	UNDERLINETYPE 0x0080_0000
+	LCID 0x0200_0000
+	BACKCOLOR 0x0400_0000
+	CHARSET 0x0800_0000
+	OFFSET 0x1000_0000
+	FACE 0x2000_0000
+	COLOR 0x4000_0000
+	SIZE 0x8000_0000
+	SUBSCRIPT 0x0003_0000
+	SUPERSCRIPT 0x0003_0000
+}
+
 const_wm! { DTM;
 	/// Date and time picker control
 	/// [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-date-and-time-picker-control-reference-messages)
@@ -310,6 +367,21 @@ const_values_pub! { EM;
 	FILELINELENGTH EM_FIRST + 21
 	GETFILELINE EM_FIRST + 22
 	GETFILELINECOUNT EM_FIRST + 23
+	EXGETSEL WM::USER.raw() + 52
+	EXLIMITTEXT WM::USER.raw() + 53
+	EXLINEFROMCHAR WM::USER.raw() + 54
+	EXSETSEL WM::USER.raw() + 55
+	GETCHARFORMAT WM::USER.raw() + 58
+	GETEVENTMASK WM::USER.raw() + 59
+	GETPARAFORMAT WM::USER.raw() + 61
+	HIDESELECTION WM::USER.raw() + 63
+	SETBKGNDCOLOR WM::USER.raw() + 67
+	SETCHARFORMAT WM::USER.raw() + 68
+	SETEVENTMASK WM::USER.raw() + 69
+	SETPARAFORMAT WM::USER.raw() + 71
+	STREAMIN WM::USER.raw() + 73
+	STREAMOUT WM::USER.raw() + 74
+	AUTOURLDETECT WM::USER.raw() + 91
 }
 
 const_bitflag! { EMF: u32;
@@ -319,6 +391,42 @@ const_bitflag! { EMF: u32;
 	CENTERED 0x0000_0001
 }
 
+const_bitflag! { ENM: u32;
+	/// Rich edit control
+	/// [event mask](https://learn.microsoft.com/en-us/windows/win32/controls/rich-edit-control-event-mask-flags)
+	/// flags (`u32`).
+	=>
+	NONE 0x0000_0000
+	CHANGE 0x0000_0001
+	UPDATE 0x0000_0002
+	SCROLL 0x0000_0004
+	SCROLLEVENTS 0x0000_0008
+	DRAGDROPDONE 0x0000_0010
+	PARAGRAPHEXPANDED 0x0000_0020
+	PAGECHANGE 0x0000_0040
+	CLIPFORMAT 0x0000_0080
+	KEYEVENTS 0x0001_0000
+	MOUSEEVENTS 0x0002_0000
+	REQUESTRESIZE 0x0004_0000
+	SELCHANGE 0x0008_0000
+	DROPFILES 0x0010_0000
+	PROTECTED 0x0020_0000
+	CORRECTTEXT 0x0040_0000
+	IMECHANGE 0x0080_0000
+	LANGCHANGE 0x0100_0000
+	OBJECTPOSITIONS 0x0200_0000
+	LINK 0x0400_0000
+	LOWFIRTF 0x0800_0000
+}
+
+const_values_pub! { ES;
+	NOOLEDRAGDROP 0x0000_0008
+	DISABLENOSCROLL 0x0000_2000
+	SUNKEN 0x0000_4000
+	SAVESEL 0x0000_8000
+	SELECTIONBAR 0x0100_0000
+}
+
 const_bitflag! { GDT: u32;
 	/// [`NMDATETIMECHANGE`](crate::NMDATETIMECHANGE) and
 	/// [`NMDATETIMESTRING`](crate::NMDATETIMESTRING) `dwFlags` (`u32`).
@@ -341,7 +449,8 @@ const_ordinary! { GMR: u32;
 }
 
 const_bitflag! { HICF: u32;
-	/// [NMBCHOTITEM](crate::NMBCHOTITEM) `dwFlags` (`u32`).
+	/// [NMBCHOTITEM](crate::NMBCHOTITEM) and [NMTBHOTITEM](crate::NMTBHOTITEM)
+	/// `dwFlags` (`u32`).
 	=>
 	OTHER 0x0000_0000
 	ARROWKEYS 0x0000_0002
@@ -520,6 +629,38 @@ const_bitflag! { HHT: u32;
 	ONOVERFLOW 0x4000
 }
 
+const_bitflag! { HKCOMB: u16;
+	/// [`hkm::SetRules`](crate::msg::hkm::SetRules) invalid key combinations
+	/// (`u16`).
+	=>
+	/// Unmodified keys.
+	NONE 0x0001
+	/// <kbd>Shift</kbd>.
+	S 0x0002
+	/// <kbd>Ctrl</kbd>.
+	C 0x0004
+	/// <kbd>Alt</kbd>.
+	A 0x0008
+	/// <kbd>Shift</kbd> + <kbd>Ctrl</kbd>.
+	SC 0x0010
+	/// <kbd>Shift</kbd> + <kbd>Alt</kbd>.
+	SA 0x0020
+	/// <kbd>Ctrl</kbd> + <kbd>Alt</kbd>.
+	CA 0x0040
+	/// <kbd>Shift</kbd> + <kbd>Ctrl</kbd> + <kbd>Alt</kbd>.
+	SCA 0x0080
+}
+
+const_wm! { HKM;
+	/// Hot key control
+	/// [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-hot-key-control-reference-messages)
+	/// (`u32`).
+	=>
+	SETHOTKEY WM::USER.raw() + 1
+	GETHOTKEY WM::USER.raw() + 2
+	SETRULES WM::USER.raw() + 3
+}
+
 const_bitflag! { ICC: u32;
 	/// [`INITCOMMONCONTROLSEX`](crate::INITCOMMONCONTROLSEX) `icc` (`u32`).
 	=>
@@ -1503,6 +1644,99 @@ const_ordinary! { PBST: u32;
 	PAUSED 0x0003
 }
 
+const_ordinary! { PFA: u16;
+	/// [`PARAFORMAT2`](crate::PARAFORMAT2) `wAlignment` (`u16`).
+	=>
+	LEFT 1
+	RIGHT 2
+	CENTER 3
+	JUSTIFY 4
+	FULL_INTERWORD 4
+}
+
+const_bitflag! { PFM: u32;
+	/// [`PARAFORMAT2`](crate::PARAFORMAT2) `dwMask` (`u32`).
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	STARTINDENT 0x0000_0001
+	RIGHTINDENT 0x0000_0002
+	OFFSET 0x0000_0004
+	ALIGNMENT 0x0000_0008
+	TABSTOPS 0x0000_0010
+	NUMBERING 0x0000_0020
+	SPACEBEFORE 0x0000_0040
+	SPACEAFTER 0x0000_0080
+	LINESPACING 0x0000_0100
+	STYLE 0x0000_0400
+	BORDER 0x0000_0800
+	SHADING 0x0000_1000
+	NUMBERINGSTYLE 0x0000_2000
+	NUMBERINGTAB 0x0000_4000
+	NUMBERINGSTART 0x0000_8000
+	OFFSETINDENT 0x8000_0000
+}
+
+const_ordinary! { PGF: u32;
+	/// [`NMPGCALCSIZE`](crate::NMPGCALCSIZE) `dwFlag` and
+	/// [`NMPGSCROLL`](crate::NMPGSCROLL) `iDir` (`u32`).
+	=>
+	CALCWIDTH 1
+	CALCHEIGHT 2
+	SCROLLUP 1
+	SCROLLDOWN 2
+	SCROLLLEFT 4
+	SCROLLRIGHT 8
+}
+
+const_wm! { PGM;
+	/// Pager control
+	/// [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-pager-control-reference-messages)
+	/// (`u32`).
+	=>
+	SETCHILD PGM_FIRST + 1
+	RECALCSIZE PGM_FIRST + 2
+	FORWARDMOUSE PGM_FIRST + 3
+	SETBKCOLOR PGM_FIRST + 4
+	GETBKCOLOR PGM_FIRST + 5
+	SETBORDER PGM_FIRST + 6
+	GETBORDER PGM_FIRST + 7
+	SETPOS PGM_FIRST + 8
+	GETPOS PGM_FIRST + 9
+	SETBUTTONSIZE PGM_FIRST + 10
+	GETBUTTONSIZE PGM_FIRST + 11
+	GETBUTTONSTATE PGM_FIRST + 12
+	GETDROPTARGET CCM::GETDROPTARGET.0
+	SETSCROLLINFO PGM_FIRST + 13
+}
+
+const_nm! { PGN;
+	/// Pager control `WM_NOTIFY`
+	/// [notifications](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-pager-control-reference-notifications)
+	/// (`i32`).
+	=>
+	SCROLL PGN_FIRST - 1
+	CALCSIZE PGN_FIRST - 2
+	HOTITEMCHANGE PGN_FIRST - 3
+}
+
+const_ws! { PGS: u32;
+	/// Pager control
+	/// [styles](https://learn.microsoft.com/en-us/windows/win32/controls/pager-control-styles)
+	/// (`u32`).
+	=>
+	/// Creates a pager control that can be scrolled vertically. This is the
+	/// default.
+	VERT 0x0000_0000
+	/// Creates a pager control that can be scrolled horizontally.
+	HORZ 0x0000_0001
+	/// The pager control will scroll when the user hovers the mouse over one
+	/// of the buttons.
+	AUTOSCROLL 0x0000_0002
+	/// The contained window can be a drag-and-drop target.
+	DRAGNDROP 0x0000_0004
+}
+
 const_bitflag! { PSH: u32;
 	/// [`PROPSHEETHEADER`](crate::PROPSHEETHEADER) `dwFlags` (`u32`).
 	=>
@@ -1604,6 +1838,76 @@ const_wm! { RB;
 	SETBANDWIDTH WM::USER.raw() + 44
 }
 
+const_bitflag! { RBBIM: u32;
+	/// [`REBARBANDINFO`](crate::REBARBANDINFO) `fMask` (`u32`).
+	=>
+	STYLE 0x0000_0001
+	COLORS 0x0000_0002
+	TEXT 0x0000_0004
+	IMAGE 0x0000_0008
+	CHILD 0x0000_0010
+	CHILDSIZE 0x0000_0020
+	SIZE 0x0000_0040
+	BACKGROUND 0x0000_0080
+	ID 0x0000_0100
+	IDEALSIZE 0x0000_0200
+	LPARAM 0x0000_0400
+	HEADERSIZE 0x0000_0800
+	CHEVRONLOCATION 0x0000_1000
+	CHEVRONSTATE 0x0000_2000
+}
+
+const_bitflag! { RBBS: u32;
+	/// [`REBARBANDINFO`](crate::REBARBANDINFO) `fStyle` (`u32`).
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	/// The band is on a new line.
+	BREAK 0x0000_0001
+	/// The band can't be sized.
+	FIXEDSIZE 0x0000_0002
+	/// The band has an edge at the top and bottom of the child window.
+	CHILDEDGE 0x0000_0004
+	/// The band will not be visible.
+	HIDDEN 0x0000_0008
+	/// The band won't be displayed when the rebar uses the vertical
+	/// orientation.
+	NOVERT 0x0000_0010
+	/// The background bitmap does not move when the band is resized.
+	FIXEDBMP 0x0000_0020
+	/// The band can be resized by the rebar control.
+	VARIABLEHEIGHT 0x0000_0040
+	/// The band will always have a sizing grip.
+	GRIPPERALWAYS 0x0000_0080
+	/// The band will never have a sizing grip.
+	NOGRIPPER 0x0000_0100
+	/// Show a chevron button if the band is smaller than `cxIdeal`.
+	USECHEVRON 0x0000_0200
+	/// Keep the band title hidden.
+	HIDETITLE 0x0000_0400
+	/// Keep the child window at the top of the band.
+	TOPALIGN 0x0000_0800
+}
+
+const_ordinary! { RBHT: u32;
+	/// [`RBHITTESTINFO`](crate::RBHITTESTINFO) `flags` (`u32`).
+	=>
+	NOWHERE 0x0001
+	CAPTION 0x0002
+	CLIENT 0x0003
+	GRABBER 0x0004
+	CHEVRON 0x0008
+	SPLITTER 0x0010
+}
+
+const_bitflag! { RBIM: u32;
+	/// [`REBARINFO`](crate::REBARINFO) `fMask` (`u32`).
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	IMAGELIST 0x0000_0001
+}
+
 const_nm! { RBN;
 	/// Rebar control `WM_NOTIFY`
 	/// [notifications](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-rebar-control-reference-notifications)
@@ -1624,6 +1928,16 @@ const_nm! { RBN;
 	AUTOBREAK RBN_FIRST - 22
 }
 
+const_bitflag! { RBNM: u32;
+	/// [`NMREBAR`](crate::NMREBAR) `dwMask` (`u32`).
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	ID 0x0000_0001
+	STYLE 0x0000_0002
+	LPARAM 0x0000_0004
+}
+
 const_ws! { RBS: u32;
 	/// Rebar control
 	/// [styles](https://learn.microsoft.com/en-us/windows/win32/controls/rebar-control-styles)
@@ -1641,6 +1955,36 @@ const_ws! { RBS: u32;
 	DBLCLKTOGGLE 0x0000_8000
 }
 
+const_nm! { REN;
+	/// Rich edit control `WM_NOTIFY`
+	/// [notifications](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-rich-edit-control-reference-notifications)
+	/// (`i32`).
+	///
+	/// Originally with `EN` prefix.
+	=>
+	MSGFILTER 0x0700
+	REQUESTRESIZE 0x0701
+	SELCHANGE 0x0702
+	DROPFILES 0x0703
+	PROTECTED 0x0704
+	CORRECTTEXT 0x0705
+	STOPNOUNDO 0x0706
+	IMECHANGE 0x0707
+	SAVECLIPBOARD 0x0708
+	OLEOPFAILED 0x0709
+	OBJECTPOSITIONS 0x070a
+	LINK 0x070b
+	DRAGDROPDONE 0x070c
+	PARAGRAPHEXPANDED 0x070d
+	PAGECHANGE 0x070e
+	LOWFIRTF 0x070f
+	ALIGNLTR 0x0710
+	ALIGNRTL 0x0711
+	CLIPFORMAT 0x0712
+	STARTCOMPOSITION 0x0713
+	ENDCOMPOSITION 0x0714
+}
+
 const_wm! { SB;
 	/// Status bar control
 	/// [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-status-bars-reference-messages)
@@ -1697,6 +2041,50 @@ const_ordinary! { SBT: u16;
 	NOTABPARSING 0x0800
 }
 
+const_bitflag! { SCF: u32;
+	/// [`em::GetCharFormat`](crate::msg::em::GetCharFormat) and
+	/// [`em::SetCharFormat`](crate::msg::em::SetCharFormat) `scope` (`u32`).
+	=>
+	DEFAULT 0x0000
+	SELECTION 0x0001
+	WORD 0x0002
+	ALL 0x0004
+	USEUIRULES 0x0008
+	ASSOCIATEFONT 0x0010
+	NOKBUPDATE 0x0020
+	ASSOCIATEFONT2 0x0040
+	SMARTFONT 0x0080
+	CHARREPFROMLCID 0x0100
+}
+
+const_bitflag! { SEL: u16;
+	/// [`SELCHANGE`](crate::SELCHANGE) `seltyp` (`u16`).
+	=>
+	EMPTY 0x0000
+	TEXT 0x0001
+	OBJECT 0x0002
+	MULTICHAR 0x0004
+	MULTIOBJECT 0x0008
+}
+
+const_bitflag! { SF: u32;
+	/// [`em::StreamIn`](crate::msg::em::StreamIn) and
+	/// [`em::StreamOut`](crate::msg::em::StreamOut) `format` (`u32`).
+	///
+	/// Also includes the values with `SFF` prefix.
+	=>
+	TEXT 0x0001
+	RTF 0x0002
+	RTFNOOBJS 0x0003
+	TEXTIZED 0x0004
+	UNICODE 0x0010
+	USECODEPAGE 0x0020
+	NCRFORNONASCII 0x0040
+	SFF_WRITEXTRAPAR 0x0080
+	SFF_PLAINRTF 0x4000
+	SFF_SELECTION 0x8000
+}
+
 const_wm! { STM;
 	/// Static control
 	/// [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-static-control-reference-messages)
@@ -1921,6 +2309,15 @@ const_ws! { TBS: u32;
 	TRANSPARENTBKGND 0x1000
 }
 
+const_ordinary! { TBDDRET: u32;
+	/// [`TBN_DROPDOWN`](https://learn.microsoft.com/en-us/windows/win32/controls/tbn-dropdown)
+	/// return value (`u32`).
+	=>
+	DEFAULT 0
+	NODEFAULT 1
+	TREATPRESSED 2
+}
+
 const_bitflag! { TBSTATE: u8;
 	/// Toolbar button
 	/// [states](https://learn.microsoft.com/en-us/windows/win32/controls/toolbar-button-states)
@@ -1938,6 +2335,38 @@ const_bitflag! { TBSTATE: u8;
 	MARKED 0x80
 }
 
+const_ws! { TBSTYLE: u32;
+	/// Toolbar control
+	/// [styles](https://learn.microsoft.com/en-us/windows/win32/controls/toolbar-control-and-button-styles)
+	/// (`u32`).
+	///
+	/// Also includes the
+	/// [common control styles](https://learn.microsoft.com/en-us/windows/win32/controls/common-control-styles)
+	/// which apply to toolbars, originally with `CCS_` prefix.
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	TOP 0x0001
+	NOMOVEY 0x0002
+	BOTTOM 0x0003
+	NORESIZE 0x0004
+	NOPARENTALIGN 0x0008
+	ADJUSTABLE 0x0020
+	NODIVIDER 0x0040
+	VERT 0x0080
+	LEFT Self::VERT.0 | Self::TOP.0
+	RIGHT Self::VERT.0 | Self::BOTTOM.0
+	NOMOVEX Self::VERT.0 | Self::NOMOVEY.0
+	TOOLTIPS 0x0100
+	WRAPABLE 0x0200
+	ALTDRAG 0x0400
+	FLAT 0x0800
+	LIST 0x1000
+	CUSTOMERASE 0x2000
+	REGISTERDROP 0x4000
+	TRANSPARENT 0x8000
+}
+
 const_wsex! { TBSTYLE_EX;
 	/// Extended toolbar control
 	/// [styles](https://learn.microsoft.com/en-us/windows/win32/controls/toolbar-extended-styles)
@@ -2203,8 +2632,36 @@ const_nm! { TRBN;
 	THUMBPOSCHANGING TRBN_FIRST - 1
 }
 
+const_ordinary! { TTDT: u32;
+	/// [`ttm::GetDelayTime`](crate::msg::ttm::GetDelayTime) and
+	/// [`ttm::SetDelayTime`](crate::msg::ttm::SetDelayTime) `which` (`u32`).
+	=>
+	AUTOMATIC 0
+	RESHOW 1
+	AUTOPOP 2
+	INITIAL 3
+}
+
+const_bitflag! { TTF: u32;
+	/// [`TTTOOLINFO`](crate::TTTOOLINFO) and
+	/// [`NMTTDISPINFO`](crate::NMTTDISPINFO) `uFlags` (`u32`).
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	IDISHWND 0x0001
+	CENTERTIP 0x0002
+	RTLREADING 0x0004
+	SUBCLASS 0x0010
+	TRACK 0x0020
+	ABSOLUTE 0x0080
+	TRANSPARENT 0x0100
+	PARSELINKS 0x1000
+	DI_SETITEM 0x8000
+}
+
 const_ordinary! { TTI: i32;
-	/// [`EDITBALLOONTIP`](crate::EDITBALLOONTIP) ttiIcon (`i32`).
+	/// [`EDITBALLOONTIP`](crate::EDITBALLOONTIP) ttiIcon and
+	/// [`ttm::SetTitle`](crate::msg::ttm::SetTitle) `icon` (`i32`).
 	=>
 	ERROR 3
 	INFO 1
@@ -2215,6 +2672,74 @@ const_ordinary! { TTI: i32;
 	ERROR_LARGE 6
 }
 
+const_wm! { TTM;
+	/// Tooltip control
+	/// [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-tooltip-control-reference-messages)
+	/// (`u32`).
+	=>
+	ACTIVATE WM::USER.raw() + 1
+	ADDTOOL WM::USER.raw() + 50
+	ADJUSTRECT WM::USER.raw() + 31
+	DELTOOL WM::USER.raw() + 51
+	ENUMTOOLS WM::USER.raw() + 58
+	GETBUBBLESIZE WM::USER.raw() + 30
+	GETCURRENTTOOL WM::USER.raw() + 59
+	GETDELAYTIME WM::USER.raw() + 21
+	GETMARGIN WM::USER.raw() + 27
+	GETMAXTIPWIDTH WM::USER.raw() + 25
+	GETTEXT WM::USER.raw() + 56
+	GETTIPBKCOLOR WM::USER.raw() + 22
+	GETTIPTEXTCOLOR WM::USER.raw() + 23
+	GETTITLE WM::USER.raw() + 35
+	GETTOOLCOUNT WM::USER.raw() + 13
+	GETTOOLINFO WM::USER.raw() + 53
+	HITTEST WM::USER.raw() + 55
+	NEWTOOLRECT WM::USER.raw() + 52
+	POP WM::USER.raw() + 28
+	POPUP WM::USER.raw() + 34
+	RELAYEVENT WM::USER.raw() + 7
+	SETDELAYTIME WM::USER.raw() + 3
+	SETMARGIN WM::USER.raw() + 26
+	SETMAXTIPWIDTH WM::USER.raw() + 24
+	SETTIPBKCOLOR WM::USER.raw() + 19
+	SETTIPTEXTCOLOR WM::USER.raw() + 20
+	SETTITLE WM::USER.raw() + 33
+	SETTOOLINFO WM::USER.raw() + 54
+	SETWINDOWTHEME CCM_FIRST + 0xb
+	TRACKACTIVATE WM::USER.raw() + 17
+	TRACKPOSITION WM::USER.raw() + 18
+	UPDATE WM::USER.raw() + 29
+	UPDATETIPTEXT WM::USER.raw() + 57
+	WINDOWFROMPOINT WM::USER.raw() + 16
+}
+
+const_nm! { TTN;
+	/// Tooltip control `WM_NOTIFY`
+	/// [notifications](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-tooltip-control-reference-notifications)
+	/// (`i32`).
+	=>
+	GETDISPINFO TTN_FIRST - 10
+	LINKCLICK TTN_FIRST - 3
+	POP TTN_FIRST - 2
+	SHOW TTN_FIRST - 1
+}
+
+const_ws! { TTS: u32;
+	/// Tooltip control
+	/// [styles](https://learn.microsoft.com/en-us/windows/win32/controls/tooltip-styles)
+	/// (`u32`).
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	ALWAYSTIP 0x01
+	NOPREFIX 0x02
+	NOANIMATE 0x10
+	NOFADE 0x20
+	BALLOON 0x40
+	CLOSE 0x80
+	USEVISUALSTYLE 0x100
+}
+
 const_ordinary! { TVC: u32;
 	/// [`NMTREEVIEW`](crate::NMTREEVIEW) `action` (`u32`).
 	=>
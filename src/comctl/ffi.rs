@@ -22,6 +22,7 @@ extern_sys! { "comctl32";
 	ImageList_Remove(HANDLE, i32) -> BOOL
 	ImageList_ReplaceIcon(HANDLE, i32, HANDLE) -> i32
 	ImageList_SetBkColor(HANDLE, u32) -> u32
+	ImageList_SetIconSize(HANDLE, i32, i32) -> BOOL
 	ImageList_SetImageCount(HANDLE, u32) -> BOOL
 	ImageList_Write(HANDLE, COMPTR) -> BOOL
 	InitCommonControls()
@@ -3,14 +3,20 @@ pub mod cb;
 pub mod dtm;
 pub mod em;
 pub mod hdm;
+pub mod hkm;
+pub mod ipm;
+pub mod lm;
 pub mod lvm;
 pub mod mcm;
 pub mod pbm;
+pub mod pgm;
+pub mod rb;
 pub mod sb;
 pub mod stm;
 pub mod tbm;
 pub mod tcm;
 pub mod trbm;
+pub mod ttm;
 pub mod tvm;
 pub mod udm;
 pub mod wm;
@@ -0,0 +1,258 @@
+use crate::co;
+use crate::decl::*;
+use crate::msg::*;
+use crate::prelude::*;
+
+/// [`PGM_FORWARDMOUSE`](https://learn.microsoft.com/en-us/windows/win32/controls/pgm-forwardmouse)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct ForwardMouse {
+	pub forward: bool,
+}
+
+impl MsgSend for ForwardMouse {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PGM::FORWARDMOUSE.into(),
+			wparam: self.forward as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PGM_GETBKCOLOR`](https://learn.microsoft.com/en-us/windows/win32/controls/pgm-getbkcolor)
+/// message, which has no parameters.
+///
+/// Return type: `COLORREF`.
+pub struct GetBkColor {}
+
+impl MsgSend for GetBkColor {
+	type RetType = COLORREF;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		COLORREF::from_raw(v as _)
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PGM::GETBKCOLOR.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PGM_GETBORDER`](https://learn.microsoft.com/en-us/windows/win32/controls/pgm-getborder)
+/// message, which has no parameters.
+///
+/// Return type: `i32`.
+pub struct GetBorder {}
+
+impl MsgSend for GetBorder {
+	type RetType = i32;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		v as _
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PGM::GETBORDER.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PGM_GETBUTTONSIZE`](https://learn.microsoft.com/en-us/windows/win32/controls/pgm-getbuttonsize)
+/// message, which has no parameters.
+///
+/// Return type: `i32`.
+pub struct GetButtonSize {}
+
+impl MsgSend for GetButtonSize {
+	type RetType = i32;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		v as _
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PGM::GETBUTTONSIZE.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PGM_GETPOS`](https://learn.microsoft.com/en-us/windows/win32/controls/pgm-getpos)
+/// message, which has no parameters.
+///
+/// Return type: `i32`.
+pub struct GetPos {}
+
+impl MsgSend for GetPos {
+	type RetType = i32;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		v as _
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PGM::GETPOS.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PGM_RECALCSIZE`](https://learn.microsoft.com/en-us/windows/win32/controls/pgm-recalcsize)
+/// message, which has no parameters.
+///
+/// Return type: `()`.
+pub struct RecalcSize {}
+
+impl MsgSend for RecalcSize {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PGM::RECALCSIZE.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`PGM_SETBKCOLOR`](https://learn.microsoft.com/en-us/windows/win32/controls/pgm-setbkcolor)
+/// message parameters.
+///
+/// Return type: `COLORREF`.
+pub struct SetBkColor {
+	pub color: COLORREF,
+}
+
+impl MsgSend for SetBkColor {
+	type RetType = COLORREF;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		COLORREF::from_raw(v as _)
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PGM::SETBKCOLOR.into(),
+			wparam: 0,
+			lparam: u32::from(self.color) as _,
+		}
+	}
+}
+
+/// [`PGM_SETBORDER`](https://learn.microsoft.com/en-us/windows/win32/controls/pgm-setborder)
+/// message parameters.
+///
+/// Return type: `i32`.
+pub struct SetBorder {
+	pub border: i32,
+}
+
+impl MsgSend for SetBorder {
+	type RetType = i32;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		v as _
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PGM::SETBORDER.into(),
+			wparam: 0,
+			lparam: self.border as _,
+		}
+	}
+}
+
+/// [`PGM_SETBUTTONSIZE`](https://learn.microsoft.com/en-us/windows/win32/controls/pgm-setbuttonsize)
+/// message parameters.
+///
+/// Return type: `i32`.
+pub struct SetButtonSize {
+	pub size: i32,
+}
+
+impl MsgSend for SetButtonSize {
+	type RetType = i32;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		v as _
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PGM::SETBUTTONSIZE.into(),
+			wparam: 0,
+			lparam: self.size as _,
+		}
+	}
+}
+
+/// [`PGM_SETCHILD`](https://learn.microsoft.com/en-us/windows/win32/controls/pgm-setchild)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SetChild<'a> {
+	pub hchild: &'a HWND,
+}
+
+impl<'a> MsgSend for SetChild<'a> {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PGM::SETCHILD.into(),
+			wparam: 0,
+			lparam: self.hchild.ptr() as _,
+		}
+	}
+}
+
+/// [`PGM_SETPOS`](https://learn.microsoft.com/en-us/windows/win32/controls/pgm-setpos)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SetPos {
+	pub pos: i32,
+}
+
+impl MsgSend for SetPos {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::PGM::SETPOS.into(),
+			wparam: 0,
+			lparam: self.pos as _,
+		}
+	}
+}
@@ -0,0 +1,595 @@
+use crate::co;
+use crate::comctl::privs::*;
+use crate::decl::*;
+use crate::msg::*;
+use crate::prelude::*;
+use crate::user::privs::*;
+
+/// [`RB_DELETEBAND`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-deleteband)
+/// message parameters.
+///
+/// Return type: `SysResult<()>`.
+pub struct DeleteBand {
+	pub index: u32,
+}
+
+impl MsgSend for DeleteBand {
+	type RetType = SysResult<()>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		zero_as_badargs(v).map(|_| ())
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::DELETEBAND.into(),
+			wparam: self.index as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`RB_GETBANDBORDERS`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-getbandborders)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct GetBandBorders<'a> {
+	pub index: u32,
+	pub borders: &'a mut RECT,
+}
+
+impl<'a> MsgSend for GetBandBorders<'a> {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::GETBANDBORDERS.into(),
+			wparam: self.index as _,
+			lparam: self.borders as *mut _ as _,
+		}
+	}
+}
+
+/// [`RB_GETBANDCOUNT`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-getbandcount)
+/// message, which has no parameters.
+///
+/// Return type: `u32`.
+pub struct GetBandCount {}
+
+impl MsgSend for GetBandCount {
+	type RetType = u32;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		v as _
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::GETBANDCOUNT.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`RB_GETBANDINFO`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-getbandinfo)
+/// message parameters.
+///
+/// Return type: `SysResult<()>`.
+pub struct GetBandInfo<'a, 'b> {
+	pub index: u32,
+	pub info: &'a mut REBARBANDINFO<'b>,
+}
+
+impl<'a, 'b> MsgSend for GetBandInfo<'a, 'b> {
+	type RetType = SysResult<()>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		zero_as_badargs(v).map(|_| ())
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::GETBANDINFO.into(),
+			wparam: self.index as _,
+			lparam: self.info as *mut _ as _,
+		}
+	}
+}
+
+/// [`RB_GETBARHEIGHT`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-getbarheight)
+/// message, which has no parameters.
+///
+/// Return type: `u32`.
+pub struct GetBarHeight {}
+
+impl MsgSend for GetBarHeight {
+	type RetType = u32;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		v as _
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::GETBARHEIGHT.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`RB_GETBARINFO`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-getbarinfo)
+/// message parameters.
+///
+/// Return type: `SysResult<()>`.
+pub struct GetBarInfo<'a> {
+	pub info: &'a mut REBARINFO,
+}
+
+impl<'a> MsgSend for GetBarInfo<'a> {
+	type RetType = SysResult<()>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		zero_as_badargs(v).map(|_| ())
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::GETBARINFO.into(),
+			wparam: 0,
+			lparam: self.info as *mut _ as _,
+		}
+	}
+}
+
+/// [`RB_GETBKCOLOR`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-getbkcolor)
+/// message, which has no parameters.
+///
+/// Return type: `Option<COLORREF>`.
+pub struct GetBkColor {}
+
+impl MsgSend for GetBkColor {
+	type RetType = Option<COLORREF>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		match v as u32 {
+			CLR_DEFAULT => None,
+			v => Some(COLORREF::from_raw(v)),
+		}
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::GETBKCOLOR.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`RB_GETRECT`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-getrect)
+/// message parameters.
+///
+/// Return type: `SysResult<()>`.
+pub struct GetRect<'a> {
+	pub index: u32,
+	pub rect: &'a mut RECT,
+}
+
+impl<'a> MsgSend for GetRect<'a> {
+	type RetType = SysResult<()>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		zero_as_badargs(v).map(|_| ())
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::GETRECT.into(),
+			wparam: self.index as _,
+			lparam: self.rect as *mut _ as _,
+		}
+	}
+}
+
+/// [`RB_GETROWCOUNT`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-getrowcount)
+/// message, which has no parameters.
+///
+/// Return type: `u32`.
+pub struct GetRowCount {}
+
+impl MsgSend for GetRowCount {
+	type RetType = u32;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		v as _
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::GETROWCOUNT.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`RB_GETROWHEIGHT`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-getrowheight)
+/// message parameters.
+///
+/// Return type: `u32`.
+pub struct GetRowHeight {
+	pub index: u32,
+}
+
+impl MsgSend for GetRowHeight {
+	type RetType = u32;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		v as _
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::GETROWHEIGHT.into(),
+			wparam: self.index as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`RB_GETTEXTCOLOR`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-gettextcolor)
+/// message, which has no parameters.
+///
+/// Return type: `Option<COLORREF>`.
+pub struct GetTextColor {}
+
+impl MsgSend for GetTextColor {
+	type RetType = Option<COLORREF>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		match v as u32 {
+			CLR_DEFAULT => None,
+			v => Some(COLORREF::from_raw(v)),
+		}
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::GETTEXTCOLOR.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`RB_HITTEST`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-hittest)
+/// message parameters.
+///
+/// Return type: `Option<u32>`.
+pub struct HitTest<'a> {
+	pub info: &'a mut RBHITTESTINFO,
+}
+
+impl<'a> MsgSend for HitTest<'a> {
+	type RetType = Option<u32>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		minus1_as_none(v).map(|i| i as _)
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::HITTEST.into(),
+			wparam: 0,
+			lparam: self.info as *mut _ as _,
+		}
+	}
+}
+
+/// [`RB_IDTOINDEX`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-idtoindex)
+/// message parameters.
+///
+/// Return type: `Option<u32>`.
+pub struct IdToIndex {
+	pub id: u16,
+}
+
+impl MsgSend for IdToIndex {
+	type RetType = Option<u32>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		minus1_as_none(v).map(|i| i as _)
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::IDTOINDEX.into(),
+			wparam: self.id as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`RB_INSERTBAND`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-insertband)
+/// message parameters.
+///
+/// If `index` is `None`, the band is added at the last position.
+///
+/// Return type: `SysResult<()>`.
+pub struct InsertBand<'a, 'b> {
+	pub index: Option<u32>,
+	pub info: &'a REBARBANDINFO<'b>,
+}
+
+impl<'a, 'b> MsgSend for InsertBand<'a, 'b> {
+	type RetType = SysResult<()>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		zero_as_badargs(v).map(|_| ())
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::INSERTBAND.into(),
+			wparam: self.index.map_or(-1, |idx| idx as i32) as _,
+			lparam: self.info as *const _ as _,
+		}
+	}
+}
+
+/// [`RB_MAXIMIZEBAND`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-maximizeband)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct MaximizeBand {
+	pub index: u32,
+	pub ideal_width: bool,
+}
+
+impl MsgSend for MaximizeBand {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::MAXIMIZEBAND.into(),
+			wparam: self.index as _,
+			lparam: self.ideal_width as _,
+		}
+	}
+}
+
+/// [`RB_MINIMIZEBAND`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-minimizeband)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct MinimizeBand {
+	pub index: u32,
+}
+
+impl MsgSend for MinimizeBand {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::MINIMIZEBAND.into(),
+			wparam: self.index as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`RB_MOVEBAND`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-moveband)
+/// message parameters.
+///
+/// Return type: `SysResult<()>`.
+pub struct MoveBand {
+	pub from: u32,
+	pub to: u32,
+}
+
+impl MsgSend for MoveBand {
+	type RetType = SysResult<()>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		zero_as_badargs(v).map(|_| ())
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::MOVEBAND.into(),
+			wparam: self.from as _,
+			lparam: self.to as _,
+		}
+	}
+}
+
+/// [`RB_SETBANDINFO`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-setbandinfo)
+/// message parameters.
+///
+/// Return type: `SysResult<()>`.
+pub struct SetBandInfo<'a, 'b> {
+	pub index: u32,
+	pub info: &'a REBARBANDINFO<'b>,
+}
+
+impl<'a, 'b> MsgSend for SetBandInfo<'a, 'b> {
+	type RetType = SysResult<()>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		zero_as_badargs(v).map(|_| ())
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::SETBANDINFO.into(),
+			wparam: self.index as _,
+			lparam: self.info as *const _ as _,
+		}
+	}
+}
+
+/// [`RB_SETBANDWIDTH`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-setbandwidth)
+/// message parameters.
+///
+/// Return type: `SysResult<()>`.
+pub struct SetBandWidth {
+	pub index: u32,
+	pub width: u32,
+}
+
+impl MsgSend for SetBandWidth {
+	type RetType = SysResult<()>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		zero_as_badargs(v).map(|_| ())
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::SETBANDWIDTH.into(),
+			wparam: self.index as _,
+			lparam: self.width as _,
+		}
+	}
+}
+
+/// [`RB_SETBARINFO`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-setbarinfo)
+/// message parameters.
+///
+/// Return type: `SysResult<()>`.
+pub struct SetBarInfo<'a> {
+	pub info: &'a REBARINFO,
+}
+
+impl<'a> MsgSend for SetBarInfo<'a> {
+	type RetType = SysResult<()>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		zero_as_badargs(v).map(|_| ())
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::SETBARINFO.into(),
+			wparam: 0,
+			lparam: self.info as *const _ as _,
+		}
+	}
+}
+
+/// [`RB_SETBKCOLOR`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-setbkcolor)
+/// message parameters.
+///
+/// Return type: `Option<COLORREF>`.
+pub struct SetBkColor {
+	pub color: Option<COLORREF>,
+}
+
+impl MsgSend for SetBkColor {
+	type RetType = Option<COLORREF>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		match v as u32 {
+			CLR_DEFAULT => None,
+			v => Some(COLORREF::from_raw(v)),
+		}
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::SETBKCOLOR.into(),
+			wparam: 0,
+			lparam: self.color.map_or(CLR_DEFAULT, |color| color.into()) as _,
+		}
+	}
+}
+
+/// [`RB_SETTEXTCOLOR`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-settextcolor)
+/// message parameters.
+///
+/// Return type: `Option<COLORREF>`.
+pub struct SetTextColor {
+	pub color: Option<COLORREF>,
+}
+
+impl MsgSend for SetTextColor {
+	type RetType = Option<COLORREF>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		match v as u32 {
+			CLR_DEFAULT => None,
+			v => Some(COLORREF::from_raw(v)),
+		}
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::SETTEXTCOLOR.into(),
+			wparam: 0,
+			lparam: self.color.map_or(CLR_DEFAULT, |color| color.into()) as _,
+		}
+	}
+}
+
+/// [`RB_SHOWBAND`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-showband)
+/// message parameters.
+///
+/// Return type: `SysResult<()>`.
+pub struct ShowBand {
+	pub index: u32,
+	pub show: bool,
+}
+
+impl MsgSend for ShowBand {
+	type RetType = SysResult<()>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		zero_as_badargs(v).map(|_| ())
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::SHOWBAND.into(),
+			wparam: self.index as _,
+			lparam: self.show as _,
+		}
+	}
+}
+
+/// [`RB_SIZETORECT`](https://learn.microsoft.com/en-us/windows/win32/controls/rb-sizetorect)
+/// message parameters.
+///
+/// Return type: `bool`.
+pub struct SizeToRect<'a> {
+	pub rect: &'a mut RECT,
+}
+
+impl<'a> MsgSend for SizeToRect<'a> {
+	type RetType = bool;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		v != 0
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::RB::SIZETORECT.into(),
+			wparam: 0,
+			lparam: self.rect as *mut _ as _,
+		}
+	}
+}
@@ -230,7 +230,7 @@ impl MsgSend for DeleteButton {
 
 	fn as_generic_wm(&mut self) -> WndMsg {
 		WndMsg {
-			msg_id: co::TBM::CHECKBUTTON.into(),
+			msg_id: co::TBM::DELETEBUTTON.into(),
 			wparam: self.btn_index as _,
 			lparam: 0,
 		}
@@ -919,14 +919,14 @@ impl<'a> MsgSend for GetString<'a> {
 /// [`TB_GETSTYLE`](https://learn.microsoft.com/en-us/windows/win32/controls/tb-getstyle)
 /// message, which has no parameters.
 ///
-/// Return type: `co::BTNS`.
+/// Return type: `co::TBSTYLE`.
 pub struct GetStyle {}
 
 impl MsgSend for GetStyle {
-	type RetType = co::BTNS;
+	type RetType = co::TBSTYLE;
 
 	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
-		co::BTNS::from_raw(v as _)
+		co::TBSTYLE::from_raw(v as _)
 	}
 
 	fn as_generic_wm(&mut self) -> WndMsg {
@@ -2013,7 +2013,7 @@ impl<'a> MsgSend for SetRows<'a> {
 /// Return type: `SysResult<()>`.
 pub struct SetState {
 	pub btn_cmd_id: u16,
-	pub state: co::BTNS,
+	pub state: co::TBSTATE,
 }
 
 impl MsgSend for SetState {
@@ -2037,7 +2037,7 @@ impl MsgSend for SetState {
 ///
 /// Return type: `()`.
 pub struct SetStyle {
-	pub style: co::BTNS,
+	pub style: co::TBSTYLE,
 }
 
 impl MsgSend for SetStyle {
@@ -0,0 +1,773 @@
+use crate::co;
+use crate::decl::*;
+use crate::msg::*;
+use crate::prelude::*;
+use crate::user::privs::*;
+
+/// [`TTM_ACTIVATE`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-activate)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct Activate {
+	pub activate: bool,
+}
+
+impl MsgSend for Activate {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::ACTIVATE.into(),
+			wparam: self.activate as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`TTM_ADDTOOL`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-addtool)
+/// message parameters.
+///
+/// Return type: `SysResult<()>`.
+pub struct AddTool<'a, 'b> {
+	pub info: &'a TTTOOLINFO<'b>,
+}
+
+impl<'a, 'b> MsgSend for AddTool<'a, 'b> {
+	type RetType = SysResult<()>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		zero_as_badargs(v).map(|_| ())
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::ADDTOOL.into(),
+			wparam: 0,
+			lparam: self.info as *const _ as _,
+		}
+	}
+}
+
+/// [`TTM_ADJUSTRECT`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-adjustrect)
+/// message parameters.
+///
+/// Return type: `SysResult<()>`.
+pub struct AdjustRect<'a> {
+	pub larger: bool,
+	pub rect: &'a mut RECT,
+}
+
+impl<'a> MsgSend for AdjustRect<'a> {
+	type RetType = SysResult<()>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		zero_as_badargs(v).map(|_| ())
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::ADJUSTRECT.into(),
+			wparam: self.larger as _,
+			lparam: self.rect as *mut _ as _,
+		}
+	}
+}
+
+/// [`TTM_DELTOOL`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-deltool)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct DelTool<'a, 'b> {
+	pub info: &'a TTTOOLINFO<'b>,
+}
+
+impl<'a, 'b> MsgSend for DelTool<'a, 'b> {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::DELTOOL.into(),
+			wparam: 0,
+			lparam: self.info as *const _ as _,
+		}
+	}
+}
+
+/// [`TTM_ENUMTOOLS`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-enumtools)
+/// message parameters.
+///
+/// Return type: `bool`.
+pub struct EnumTools<'a, 'b> {
+	pub index: u32,
+	pub info: &'a mut TTTOOLINFO<'b>,
+}
+
+impl<'a, 'b> MsgSend for EnumTools<'a, 'b> {
+	type RetType = bool;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		v != 0
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::ENUMTOOLS.into(),
+			wparam: self.index as _,
+			lparam: self.info as *mut _ as _,
+		}
+	}
+}
+
+/// [`TTM_GETBUBBLESIZE`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-getbubblesize)
+/// message parameters.
+///
+/// Return type: `SIZE`.
+pub struct GetBubbleSize<'a, 'b> {
+	pub info: &'a TTTOOLINFO<'b>,
+}
+
+impl<'a, 'b> MsgSend for GetBubbleSize<'a, 'b> {
+	type RetType = SIZE;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		SIZE::from(v as u32)
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::GETBUBBLESIZE.into(),
+			wparam: 0,
+			lparam: self.info as *const _ as _,
+		}
+	}
+}
+
+/// [`TTM_GETCURRENTTOOL`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-getcurrenttool)
+/// message parameters.
+///
+/// Return type: `bool`.
+pub struct GetCurrentTool<'a, 'b> {
+	pub info: Option<&'a mut TTTOOLINFO<'b>>,
+}
+
+impl<'a, 'b> MsgSend for GetCurrentTool<'a, 'b> {
+	type RetType = bool;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		v != 0
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::GETCURRENTTOOL.into(),
+			wparam: 0,
+			lparam: self.info.as_mut().map_or(0, |info| info as *mut _ as _),
+		}
+	}
+}
+
+/// [`TTM_GETDELAYTIME`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-getdelaytime)
+/// message parameters.
+///
+/// Return type: `u32`.
+pub struct GetDelayTime {
+	pub which: co::TTDT,
+}
+
+impl MsgSend for GetDelayTime {
+	type RetType = u32;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		v as _
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::GETDELAYTIME.into(),
+			wparam: self.which.raw() as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`TTM_GETMARGIN`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-getmargin)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct GetMargin<'a> {
+	pub rect: &'a mut RECT,
+}
+
+impl<'a> MsgSend for GetMargin<'a> {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::GETMARGIN.into(),
+			wparam: 0,
+			lparam: self.rect as *mut _ as _,
+		}
+	}
+}
+
+/// [`TTM_GETMAXTIPWIDTH`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-getmaxtipwidth)
+/// message parameters.
+///
+/// Return type: `Option<u32>`.
+pub struct GetMaxTipWidth {}
+
+impl MsgSend for GetMaxTipWidth {
+	type RetType = Option<u32>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		match v as i32 {
+			-1 => None,
+			w => Some(w as _),
+		}
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::GETMAXTIPWIDTH.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`TTM_GETTEXT`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-gettext)
+/// message parameters.
+///
+/// Return type: `()`.
+///
+/// The `lpszText` of the [`TTTOOLINFO`](crate::TTTOOLINFO) must point to a
+/// buffer with at least `buf_len` chars.
+pub struct GetText<'a, 'b> {
+	pub buf_len: u32,
+	pub info: &'a mut TTTOOLINFO<'b>,
+}
+
+impl<'a, 'b> MsgSend for GetText<'a, 'b> {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::GETTEXT.into(),
+			wparam: self.buf_len as _,
+			lparam: self.info as *mut _ as _,
+		}
+	}
+}
+
+/// [`TTM_GETTIPBKCOLOR`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-gettipbkcolor)
+/// message, which has no parameters.
+///
+/// Return type: `COLORREF`.
+pub struct GetTipBkColor {}
+
+impl MsgSend for GetTipBkColor {
+	type RetType = COLORREF;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		COLORREF::from_raw(v as _)
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::GETTIPBKCOLOR.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`TTM_GETTIPTEXTCOLOR`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-gettiptextcolor)
+/// message, which has no parameters.
+///
+/// Return type: `COLORREF`.
+pub struct GetTipTextColor {}
+
+impl MsgSend for GetTipTextColor {
+	type RetType = COLORREF;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		COLORREF::from_raw(v as _)
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::GETTIPTEXTCOLOR.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`TTM_GETTITLE`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-gettitle)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct GetTitle<'a, 'b> {
+	pub info: &'a mut TTGETTITLE<'b>,
+}
+
+impl<'a, 'b> MsgSend for GetTitle<'a, 'b> {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::GETTITLE.into(),
+			wparam: 0,
+			lparam: self.info as *mut _ as _,
+		}
+	}
+}
+
+/// [`TTM_GETTOOLCOUNT`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-gettoolcount)
+/// message, which has no parameters.
+///
+/// Return type: `u32`.
+pub struct GetToolCount {}
+
+impl MsgSend for GetToolCount {
+	type RetType = u32;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		v as _
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::GETTOOLCOUNT.into(),
+			wparam: 0,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`TTM_GETTOOLINFO`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-gettoolinfo)
+/// message parameters.
+///
+/// Return type: `SysResult<()>`.
+pub struct GetToolInfo<'a, 'b> {
+	pub info: &'a mut TTTOOLINFO<'b>,
+}
+
+impl<'a, 'b> MsgSend for GetToolInfo<'a, 'b> {
+	type RetType = SysResult<()>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		zero_as_badargs(v).map(|_| ())
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::GETTOOLINFO.into(),
+			wparam: 0,
+			lparam: self.info as *mut _ as _,
+		}
+	}
+}
+
+/// [`TTM_HITTEST`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-hittest)
+/// message parameters.
+///
+/// Return type: `bool`.
+pub struct HitTest<'a, 'b> {
+	pub info: &'a mut TTHITTESTINFO<'b>,
+}
+
+impl<'a, 'b> MsgSend for HitTest<'a, 'b> {
+	type RetType = bool;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		v != 0
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::HITTEST.into(),
+			wparam: 0,
+			lparam: self.info as *mut _ as _,
+		}
+	}
+}
+
+/// [`TTM_NEWTOOLRECT`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-newtoolrect)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct NewToolRect<'a, 'b> {
+	pub info: &'a TTTOOLINFO<'b>,
+}
+
+impl<'a, 'b> MsgSend for NewToolRect<'a, 'b> {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::NEWTOOLRECT.into(),
+			wparam: 0,
+			lparam: self.info as *const _ as _,
+		}
+	}
+}
+
+pub_struct_msg_empty! { Pop: co::TTM::POP.into();
+	/// [`TTM_POP`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-pop)
+}
+
+pub_struct_msg_empty! { Popup: co::TTM::POPUP.into();
+	/// [`TTM_POPUP`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-popup)
+}
+
+/// [`TTM_RELAYEVENT`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-relayevent)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct RelayEvent<'a> {
+	pub msg: &'a MSG,
+}
+
+impl<'a> MsgSend for RelayEvent<'a> {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::RELAYEVENT.into(),
+			wparam: 0,
+			lparam: self.msg as *const _ as _,
+		}
+	}
+}
+
+/// [`TTM_SETDELAYTIME`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-setdelaytime)
+/// message parameters.
+///
+/// Return type: `()`.
+///
+/// If `time_ms` is `None`, the default delay time is restored.
+pub struct SetDelayTime {
+	pub which: co::TTDT,
+	pub time_ms: Option<u16>,
+}
+
+impl MsgSend for SetDelayTime {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::SETDELAYTIME.into(),
+			wparam: self.which.raw() as _,
+			lparam: self.time_ms.map_or(-1, |ms| ms as _),
+		}
+	}
+}
+
+/// [`TTM_SETMARGIN`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-setmargin)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SetMargin<'a> {
+	pub rect: &'a RECT,
+}
+
+impl<'a> MsgSend for SetMargin<'a> {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::SETMARGIN.into(),
+			wparam: 0,
+			lparam: self.rect as *const _ as _,
+		}
+	}
+}
+
+/// [`TTM_SETMAXTIPWIDTH`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-setmaxtipwidth)
+/// message parameters.
+///
+/// Return type: `Option<u32>`.
+///
+/// If `width` is `None`, any width is allowed. Setting a width enables
+/// multi-line tooltips, breaking the text at line breaks and word boundaries.
+pub struct SetMaxTipWidth {
+	pub width: Option<u32>,
+}
+
+impl MsgSend for SetMaxTipWidth {
+	type RetType = Option<u32>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		match v as i32 {
+			-1 => None,
+			w => Some(w as _),
+		}
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::SETMAXTIPWIDTH.into(),
+			wparam: 0,
+			lparam: self.width.map_or(-1, |w| w as _),
+		}
+	}
+}
+
+/// [`TTM_SETTIPBKCOLOR`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-settipbkcolor)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SetTipBkColor {
+	pub color: COLORREF,
+}
+
+impl MsgSend for SetTipBkColor {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::SETTIPBKCOLOR.into(),
+			wparam: u32::from(self.color) as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`TTM_SETTIPTEXTCOLOR`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-settiptextcolor)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SetTipTextColor {
+	pub color: COLORREF,
+}
+
+impl MsgSend for SetTipTextColor {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::SETTIPTEXTCOLOR.into(),
+			wparam: u32::from(self.color) as _,
+			lparam: 0,
+		}
+	}
+}
+
+/// [`TTM_SETTITLE`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-settitle)
+/// message parameters.
+///
+/// Return type: `SysResult<()>`.
+pub struct SetTitle {
+	pub icon: co::TTI,
+	pub title: WString,
+}
+
+impl MsgSend for SetTitle {
+	type RetType = SysResult<()>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		zero_as_badargs(v).map(|_| ())
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::SETTITLE.into(),
+			wparam: self.icon.raw() as _,
+			lparam: self.title.as_ptr() as _,
+		}
+	}
+}
+
+/// [`TTM_SETTOOLINFO`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-settoolinfo)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SetToolInfo<'a, 'b> {
+	pub info: &'a TTTOOLINFO<'b>,
+}
+
+impl<'a, 'b> MsgSend for SetToolInfo<'a, 'b> {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::SETTOOLINFO.into(),
+			wparam: 0,
+			lparam: self.info as *const _ as _,
+		}
+	}
+}
+
+/// [`TTM_SETWINDOWTHEME`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-setwindowtheme)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SetWindowTheme {
+	pub visual_style: WString,
+}
+
+impl MsgSend for SetWindowTheme {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::SETWINDOWTHEME.into(),
+			wparam: 0,
+			lparam: self.visual_style.as_ptr() as _,
+		}
+	}
+}
+
+/// [`TTM_TRACKACTIVATE`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-trackactivate)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct TrackActivate<'a, 'b> {
+	pub activate: bool,
+	pub info: &'a TTTOOLINFO<'b>,
+}
+
+impl<'a, 'b> MsgSend for TrackActivate<'a, 'b> {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::TRACKACTIVATE.into(),
+			wparam: self.activate as _,
+			lparam: self.info as *const _ as _,
+		}
+	}
+}
+
+/// [`TTM_TRACKPOSITION`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-trackposition)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct TrackPosition {
+	pub coords: POINT,
+}
+
+impl MsgSend for TrackPosition {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::TRACKPOSITION.into(),
+			wparam: 0,
+			lparam: u32::from(self.coords) as _,
+		}
+	}
+}
+
+pub_struct_msg_empty! { Update: co::TTM::UPDATE.into();
+	/// [`TTM_UPDATE`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-update)
+}
+
+/// [`TTM_UPDATETIPTEXT`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-updatetiptext)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct UpdateTipText<'a, 'b> {
+	pub info: &'a TTTOOLINFO<'b>,
+}
+
+impl<'a, 'b> MsgSend for UpdateTipText<'a, 'b> {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::UPDATETIPTEXT.into(),
+			wparam: 0,
+			lparam: self.info as *const _ as _,
+		}
+	}
+}
+
+/// [`TTM_WINDOWFROMPOINT`](https://learn.microsoft.com/en-us/windows/win32/controls/ttm-windowfrompoint)
+/// message parameters.
+///
+/// Return type: `Option<HWND>`.
+pub struct WindowFromPoint<'a> {
+	pub coords: &'a POINT,
+}
+
+impl<'a> MsgSend for WindowFromPoint<'a> {
+	type RetType = Option<HWND>;
+
+	unsafe fn isize_to_ret(&self, v: isize) -> Self::RetType {
+		zero_as_none(v).map(|p| HWND::from_ptr(p as _))
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::TTM::WINDOWFROMPOINT.into(),
+			wparam: 0,
+			lparam: self.coords as *const _ as _,
+		}
+	}
+}
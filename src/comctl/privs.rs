@@ -10,6 +10,7 @@ const_values_num_privs! {
 	I_IMAGECALLBACK isize = -1
 	I_IMAGENONE isize = -2
 	L_MAX_URL_LENGTH usize = 2048 + 32 + 4
+	LPSTR_TEXTCALLBACK isize = -1
 	MAX_LINKID_TEXT usize = 48
 }
 
@@ -30,12 +31,15 @@ const_values_num_privs! {
 	MCM_FIRST u32 = 0x1000
 	MCN_FIRST i32 = -746
 	NM_FIRST i32 = 0
+	PGM_FIRST u32 = 0x1400
+	PGN_FIRST i32 = -900
 	RBN_FIRST i32 = -831
 	SBN_FIRST i32 = -880
 	TBN_FIRST i32 = -700
 	TCM_FIRST u32 = 0x1300
 	TCN_FIRST i32 = -550
 	TRBN_FIRST i32 = -1501
+	TTN_FIRST i32 = -520
 	TVM_FIRST u32 = 0x1100
 	TVN_FIRST i32 = -400
 	UDN_FIRST i32 = -721
@@ -27,6 +27,47 @@ pub struct BUTTON_SPLITINFO {
 	pub size: SIZE,
 }
 
+/// [`CHARFORMAT2`](https://learn.microsoft.com/en-us/windows/win32/api/richedit/ns-richedit-charformat2w_1)
+/// struct.
+#[repr(C)]
+pub struct CHARFORMAT2 {
+	cbSize: u32,
+	pub dwMask: co::CFM,
+	pub dwEffects: co::CFE,
+	pub yHeight: i32,
+	pub yOffset: i32,
+	pub crTextColor: COLORREF,
+	pub bCharSet: u8,
+	pub bPitchAndFamily: u8,
+	szFaceName: [u16; 32], // LF_FACESIZE
+	pub wWeight: u16,
+	pub sSpacing: i16,
+	pub crBackColor: COLORREF,
+	pub lcid: LCID,
+	dwReserved: u32,
+	pub sStyle: i16,
+	pub wKerning: u16,
+	pub bUnderlineType: u8,
+	pub bAnimation: u8,
+	pub bRevAuthor: u8,
+	pub bUnderlineColor: u8,
+}
+
+impl_default!(CHARFORMAT2, cbSize);
+
+impl CHARFORMAT2 {
+	pub_fn_string_arr_get_set!(szFaceName, set_szFaceName);
+}
+
+/// [`CHARRANGE`](https://learn.microsoft.com/en-us/windows/win32/api/richedit/ns-richedit-charrange)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct CHARRANGE {
+	pub cpMin: i32,
+	pub cpMax: i32,
+}
+
 /// [`COLORSCHEME`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-colorscheme)
 /// struct.
 #[repr(C)]
@@ -74,6 +115,30 @@ impl<'a, 'b> EDITBALLOONTIP<'a, 'b> {
 	pub_fn_string_ptr_get_set!('b, pszText, set_pszText);
 }
 
+/// [`EDITSTREAM`](https://learn.microsoft.com/en-us/windows/win32/api/richedit/ns-richedit-editstream)
+/// struct.
+#[repr(C, packed(4))]
+pub struct EDITSTREAM {
+	pub dwCookie: usize,
+	pub dwError: u32,
+	pub pfnCallback: Option<EDITSTREAMCALLBACK>,
+}
+
+impl_default!(EDITSTREAM);
+
+/// [`ENLINK`](https://learn.microsoft.com/en-us/windows/win32/api/richedit/ns-richedit-enlink)
+/// struct.
+#[repr(C, packed(4))]
+pub struct ENLINK {
+	pub nmhdr: NMHDR,
+	pub msg: co::WM,
+	pub wParam: usize,
+	pub lParam: isize,
+	pub chrg: CHARRANGE,
+}
+
+impl_default!(ENLINK);
+
 /// [`HDITEM`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-hditemw)
 /// struct.
 #[repr(C)]
@@ -138,6 +203,16 @@ pub struct INITCOMMONCONTROLSEX {
 
 impl_default!(INITCOMMONCONTROLSEX, dwSize);
 
+/// [`LHITTESTINFO`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-lhittestinfo)
+/// struct.
+#[repr(C)]
+pub struct LHITTESTINFO {
+	pub pt: POINT,
+	pub item: LITEM,
+}
+
+impl_default!(LHITTESTINFO);
+
 /// [`LITEM`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-litem)
 /// struct.
 #[repr(C)]
@@ -580,6 +655,18 @@ impl MONTHDAYSTATE {
 	}
 }
 
+/// [`MSGFILTER`](https://learn.microsoft.com/en-us/windows/win32/api/richedit/ns-richedit-msgfilter)
+/// struct.
+#[repr(C, packed(4))]
+pub struct MSGFILTER {
+	pub nmhdr: NMHDR,
+	pub msg: co::WM,
+	pub wParam: usize,
+	pub lParam: isize,
+}
+
+impl_default!(MSGFILTER);
+
 /// [`NMBCDROPDOWN`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmbcdropdown)
 /// struct.
 #[repr(C)]
@@ -787,6 +874,16 @@ impl NMHDR {
 	pub const fn set_idFrom(&mut self, val: u16) {
 		self.idFrom = val as _
 	}
+
+	/// Returns the `idFrom` field as a window handle.
+	///
+	/// This is the case of notifications sent by tooltips whose tools were
+	/// added with [`TTF::IDISHWND`](crate::co::TTF::IDISHWND), where `idFrom`
+	/// is the handle of the tool window.
+	#[must_use]
+	pub fn idFrom_hwnd(&self) -> HWND {
+		unsafe { HWND::from_ptr(self.idFrom as _) }
+	}
 }
 
 /// Notification code returned in [`NMHDR`](crate::NMHDR) struct. This code is
@@ -1067,6 +1164,78 @@ pub struct NMMOUSE {
 	pub dwHitInfo: isize,
 }
 
+/// [`NMPGCALCSIZE`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmpgcalcsize)
+/// struct.
+#[repr(C)]
+pub struct NMPGCALCSIZE {
+	pub hdr: NMHDR,
+	pub dwFlag: co::PGF,
+	pub iWidth: i32,
+	pub iHeight: i32,
+}
+
+/// [`NMPGSCROLL`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmpgscroll)
+/// struct.
+#[repr(C, packed(1))]
+pub struct NMPGSCROLL {
+	pub hdr: NMHDR,
+	pub fwKeys: co::MK,
+	pub rcParent: RECT,
+	pub iDir: co::PGF,
+	pub iXpos: i32,
+	pub iYpos: i32,
+	pub iScroll: i32,
+}
+
+/// [`NMRBAUTOSIZE`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmrbautosize)
+/// struct.
+#[repr(C)]
+pub struct NMRBAUTOSIZE {
+	pub hdr: NMHDR,
+	fChanged: BOOL,
+	pub rcTarget: RECT,
+	pub rcActual: RECT,
+}
+
+impl NMRBAUTOSIZE {
+	pub_fn_bool_get_set!(fChanged, set_fChanged);
+}
+
+/// [`NMREBAR`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmrebar)
+/// struct.
+#[repr(C)]
+pub struct NMREBAR {
+	pub hdr: NMHDR,
+	pub dwMask: co::RBNM,
+	pub uBand: u32,
+	pub fStyle: co::RBBS,
+	pub wID: u32,
+	pub lParam: isize,
+}
+
+/// [`NMREBARCHEVRON`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmrebarchevron)
+/// struct.
+#[repr(C)]
+pub struct NMREBARCHEVRON {
+	pub hdr: NMHDR,
+	pub uBand: u32,
+	pub wID: u32,
+	pub lParam: isize,
+	pub rc: RECT,
+	pub lParamNM: isize,
+}
+
+/// [`NMREBARCHILDSIZE`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmrebarchildsize)
+/// struct.
+#[repr(C)]
+pub struct NMREBARCHILDSIZE {
+	pub hdr: NMHDR,
+	pub uBand: u32,
+	pub wID: u32,
+	pub rcChild: RECT,
+	pub rcBand: RECT,
+}
+
 /// [`NMTRBTHUMBPOSCHANGING`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmtrbthumbposchanging)
 /// struct.
 #[repr(C)]
@@ -1085,6 +1254,35 @@ pub struct NMSELCHANGE {
 	pub stSelEnd: SYSTEMTIME,
 }
 
+/// [`NMTBGETINFOTIP`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmtbgetinfotipw)
+/// struct.
+#[repr(C)]
+pub struct NMTBGETINFOTIP<'a> {
+	pub hdr: NMHDR,
+	pszText: *mut u16,
+	cchTextMax: i32,
+	pub iItem: i32,
+	pub lParam: isize,
+
+	_pszText: PhantomData<&'a mut u16>,
+}
+
+impl_default!(NMTBGETINFOTIP, 'a);
+
+impl<'a> NMTBGETINFOTIP<'a> {
+	pub_fn_string_buf_get_set!('a, pszText, set_pszText, raw_pszText, cchTextMax);
+}
+
+/// [`NMTBHOTITEM`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmtbhotitem)
+/// struct.
+#[repr(C)]
+pub struct NMTBHOTITEM {
+	pub hdr: NMHDR,
+	pub idOld: i32,
+	pub idNew: i32,
+	pub dwFlags: co::HICF,
+}
+
 /// [`NMTCKEYDOWN`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmtckeydown)
 /// struct.
 #[repr(C)]
@@ -1096,6 +1294,26 @@ pub struct NMTCKEYDOWN {
 
 impl_default!(NMTCKEYDOWN);
 
+/// [`NMTOOLBAR`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmtoolbarw)
+/// struct.
+#[repr(C)]
+pub struct NMTOOLBAR<'a, 'b> {
+	pub hdr: NMHDR,
+	pub iItem: i32,
+	pub tbButton: TBBUTTON<'a>,
+	cchText: i32,
+	pszText: *mut u16,
+	pub rcButton: RECT,
+
+	_pszText: PhantomData<&'b mut u16>,
+}
+
+impl_default!(NMTOOLBAR, 'a, 'b);
+
+impl<'a, 'b> NMTOOLBAR<'a, 'b> {
+	pub_fn_string_buf_get_set!('b, pszText, set_pszText, raw_pszText, cchText);
+}
+
 /// [`NMTREEVIEW`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmtreevieww)
 /// struct.
 #[repr(C)]
@@ -1107,6 +1325,27 @@ pub struct NMTREEVIEW<'a, 'b> {
 	pub ptDrag: POINT,
 }
 
+/// [`NMTTDISPINFO`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmttdispinfow)
+/// struct.
+#[repr(C)]
+pub struct NMTTDISPINFO<'a> {
+	pub hdr: NMHDR,
+	lpszText: *mut u16,
+	szText: [u16; 80],
+	pub hinst: HINSTANCE,
+	pub uFlags: co::TTF,
+	pub lParam: isize,
+
+	_lpszText: PhantomData<&'a mut u16>,
+}
+
+impl_default!(NMTTDISPINFO, 'a);
+
+impl<'a> NMTTDISPINFO<'a> {
+	pub_fn_string_ptr_get_set!('a, lpszText, set_lpszText);
+	pub_fn_string_arr_get_set!(szText, set_szText);
+}
+
 /// [`NMTVCUSTOMDRAW`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-nmtvcustomdraw)
 /// stuct.
 #[repr(C)]
@@ -1147,6 +1386,60 @@ pub struct NMVIEWCHANGE {
 	pub dwNewView: co::MCMV,
 }
 
+/// [`PARAFORMAT2`](https://learn.microsoft.com/en-us/windows/win32/api/richedit/ns-richedit-paraformat2_1)
+/// struct.
+#[repr(C)]
+pub struct PARAFORMAT2 {
+	cbSize: u32,
+	pub dwMask: co::PFM,
+	pub wNumbering: u16,
+	wReserved: u16,
+	pub dxStartIndent: i32,
+	pub dxRightIndent: i32,
+	pub dxOffset: i32,
+	pub wAlignment: co::PFA,
+	cTabCount: i16,
+	rgxTabs: [i32; 32],
+	pub dySpaceBefore: i32,
+	pub dySpaceAfter: i32,
+	pub dyLineSpacing: i32,
+	pub sStyle: i16,
+	pub bLineSpacingRule: u8,
+	pub bOutlineLevel: u8,
+	pub wShadingWeight: u16,
+	pub wShadingStyle: u16,
+	pub wNumberingStart: u16,
+	pub wNumberingStyle: u16,
+	pub wNumberingTab: u16,
+	pub wBorderSpace: u16,
+	pub wBorderWidth: u16,
+	pub wBorders: u16,
+}
+
+impl_default!(PARAFORMAT2, cbSize);
+
+impl PARAFORMAT2 {
+	/// Returns the `rgxTabs` field, with up to 32 tab stops.
+	#[must_use]
+	pub fn rgxTabs(&self) -> &[i32] {
+		&self.rgxTabs[..self.cTabCount as usize]
+	}
+
+	/// Sets the `rgxTabs` field, with up to 32 tab stops.
+	///
+	/// Fails with
+	/// [`ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER) if
+	/// `tabs` has more than 32 elements.
+	pub fn set_rgxTabs(&mut self, tabs: &[i32]) -> SysResult<()> {
+		if tabs.len() > self.rgxTabs.len() {
+			return Err(co::ERROR::INVALID_PARAMETER);
+		}
+		self.rgxTabs[..tabs.len()].copy_from_slice(tabs);
+		self.cTabCount = tabs.len() as _;
+		Ok(())
+	}
+}
+
 /// [`PBRANGE`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-pbrange)
 /// struct.
 #[repr(C)]
@@ -1334,6 +1627,85 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g> PROPSHEETPAGE<'a, 'b, 'c, 'd, 'e, 'f, 'g> {
 	}
 }
 
+/// [`RBHITTESTINFO`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-rbhittestinfo)
+/// struct.
+#[repr(C)]
+pub struct RBHITTESTINFO {
+	pub pt: POINT,
+	pub flags: co::RBHT,
+	pub iBand: i32,
+}
+
+impl_default!(RBHITTESTINFO);
+
+/// [`REBARBANDINFO`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-rebarbandinfow)
+/// struct.
+#[repr(C)]
+pub struct REBARBANDINFO<'a> {
+	cbSize: u32,
+	pub fMask: co::RBBIM,
+	pub fStyle: co::RBBS,
+	pub clrFore: COLORREF,
+	pub clrBack: COLORREF,
+	lpText: *mut u16,
+	cch: u32,
+	pub iImage: i32,
+	pub hwndChild: HWND,
+	pub cxMinChild: u32,
+	pub cyMinChild: u32,
+	pub cx: u32,
+	pub hbmBack: HBITMAP,
+	pub wID: u32,
+	pub cyChild: u32,
+	pub cyMaxChild: u32,
+	pub cyIntegral: u32,
+	pub cxIdeal: u32,
+	pub lParam: isize,
+	pub cxHeader: u32,
+	pub rcChevronLocation: RECT,
+	pub uChevronState: co::STATE_SYSTEM,
+
+	_lpText: PhantomData<&'a mut u16>,
+}
+
+impl_default!(REBARBANDINFO, cbSize, 'a);
+
+impl<'a> REBARBANDINFO<'a> {
+	pub_fn_string_buf_get_set!('a, lpText, set_lpText, raw_lpText, cch);
+}
+
+/// [`REBARINFO`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-rebarinfo)
+/// struct.
+#[repr(C)]
+pub struct REBARINFO {
+	cbSize: u32,
+	pub fMask: co::RBIM,
+	pub himl: HIMAGELIST,
+}
+
+impl_default!(REBARINFO, cbSize);
+
+/// [`REQRESIZE`](https://learn.microsoft.com/en-us/windows/win32/api/richedit/ns-richedit-reqresize)
+/// struct.
+#[repr(C, packed(4))]
+pub struct REQRESIZE {
+	pub nmhdr: NMHDR,
+	pub rc: RECT,
+}
+
+impl_default!(REQRESIZE);
+
+/// [`SELCHANGE`](https://learn.microsoft.com/en-us/windows/win32/api/richedit/ns-richedit-selchange)
+/// struct.
+#[repr(C, packed(4))]
+pub struct SELCHANGE {
+	pub nmhdr: NMHDR,
+	pub chrg: CHARRANGE,
+	pub seltyp: co::SEL,
+}
+
+impl_default!(SELCHANGE);
+
 /// [`TBADDBITMAP`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-tbaddbitmap)
 /// struct.
 #[repr(C)]
@@ -1556,6 +1928,90 @@ impl<'a> TCITEM<'a> {
 	pub_fn_string_buf_get_set!('a, pszText, set_pszText, raw_pszText, cchTextMax);
 }
 
+/// [`TTGETTITLE`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-ttgettitle)
+/// struct.
+#[repr(C)]
+pub struct TTGETTITLE<'a> {
+	dwSize: u32,
+	pub uTitleBitmap: co::TTI,
+	cch: u32,
+	pszTitle: *mut u16,
+
+	_pszTitle: PhantomData<&'a mut u16>,
+}
+
+impl_default!(TTGETTITLE, dwSize, 'a);
+
+impl<'a> TTGETTITLE<'a> {
+	pub_fn_string_buf_get_set!('a, pszTitle, set_pszTitle, raw_pszTitle, cch);
+}
+
+/// [`TTHITTESTINFO`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-tthittestinfow)
+/// struct.
+#[repr(C)]
+pub struct TTHITTESTINFO<'a> {
+	pub hwnd: HWND,
+	pub pt: POINT,
+	pub ti: TTTOOLINFO<'a>,
+}
+
+impl<'a> Default for TTHITTESTINFO<'a> {
+	fn default() -> Self {
+		Self {
+			hwnd: HWND::NULL,
+			pt: POINT::default(),
+			ti: TTTOOLINFO::default(), // cbSize set
+		}
+	}
+}
+
+/// [`TTTOOLINFO`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-tttoolinfow)
+/// struct.
+#[repr(C)]
+pub struct TTTOOLINFO<'a> {
+	cbSize: u32,
+	pub uFlags: co::TTF,
+	pub hwnd: HWND,
+	pub uId: usize,
+	pub rect: RECT,
+	pub hinst: HINSTANCE,
+	lpszText: *mut u16,
+	pub lParam: isize,
+	lpReserved: *mut std::ffi::c_void,
+
+	_lpszText: PhantomData<&'a mut u16>,
+}
+
+impl_default!(TTTOOLINFO, cbSize, 'a);
+
+impl<'a> TTTOOLINFO<'a> {
+	/// Returns the `lpszText` field, if any.
+	///
+	/// Returns `None` also if the field is `LPSTR_TEXTCALLBACK`, set with
+	/// [`set_lpszText_callback`](crate::TTTOOLINFO::set_lpszText_callback).
+	#[must_use]
+	pub fn lpszText(&self) -> Option<String> {
+		if self.lpszText as isize == LPSTR_TEXTCALLBACK {
+			None
+		} else {
+			unsafe { self.lpszText.as_mut() }
+				.map(|psz| unsafe { WString::from_wchars_nullt(psz) }.to_string())
+		}
+	}
+
+	/// Sets the `lpszText` field.
+	pub fn set_lpszText(&mut self, buf: Option<&'a mut WString>) {
+		self.lpszText = buf.map_or(std::ptr::null_mut(), |buf| unsafe { buf.as_mut_ptr() });
+	}
+
+	/// Sets the `lpszText` field to `LPSTR_TEXTCALLBACK`, so the tooltip will
+	/// send [`TTN::GETDISPINFO`](crate::co::TTN::GETDISPINFO) notifications to
+	/// retrieve the text.
+	pub fn set_lpszText_callback(&mut self) {
+		self.lpszText = LPSTR_TEXTCALLBACK as _;
+	}
+}
+
 /// [`TVHITTESTINFO`](https://learn.microsoft.com/en-us/windows/win32/api/commctrl/ns-commctrl-tvhittestinfo)
 /// struct.
 #[repr(C)]
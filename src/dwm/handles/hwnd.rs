@@ -56,4 +56,36 @@ pub trait dwm_Hwnd: uxtheme_Hwnd {
 			ffi::DwmSetIconicThumbnail(self.ptr(), hbmp.ptr(), sit_flags.unwrap_or_default().raw())
 		})
 	}
+
+	/// [`DwmSetWindowAttribute`](https://learn.microsoft.com/en-us/windows/win32/api/dwmapi/nf-dwmapi-dwmsetwindowattribute)
+	/// function.
+	///
+	/// # Safety
+	///
+	/// The type of `value` must match the attribute being set.
+	///
+	/// # Examples
+	///
+	/// Enabling the dark title bar:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hwnd: w::HWND; // initialized somewhere
+	/// # let hwnd = w::HWND::NULL;
+	///
+	/// let dark: i32 = 1; // BOOL
+	/// unsafe {
+	///     hwnd.DwmSetWindowAttribute(co::DWMWA::USE_IMMERSIVE_DARK_MODE, &dark)?;
+	/// }
+	/// # w::HrResult::Ok(())
+	/// ```
+	unsafe fn DwmSetWindowAttribute<T>(&self, attr: co::DWMWA, value: &T) -> HrResult<()> {
+		ok_to_hrresult(ffi::DwmSetWindowAttribute(
+			self.ptr(),
+			attr.raw(),
+			value as *const _ as _,
+			std::mem::size_of::<T>() as _,
+		))
+	}
 }
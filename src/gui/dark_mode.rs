@@ -0,0 +1,305 @@
+use std::cell::UnsafeCell;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::gui::privs::*;
+use crate::msg::*;
+use crate::prelude::*;
+
+/// Color theme applied by a [`DarkMode`](crate::gui::DarkMode).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+	/// Always light, which is the default look of the windows.
+	Light,
+	/// Always dark.
+	Dark,
+	/// Follows the "Choose your default app mode" system setting, switching
+	/// automatically when it changes.
+	System,
+}
+
+struct DarkModeObj {
+	is_dlg: IsDlg,
+	hwnd: UnsafeCell<HWND>,
+	theme: UnsafeCell<Theme>,
+	dark: UnsafeCell<bool>, // theme currently applied to the window
+	brush_bg: UnsafeCell<Option<DeleteObjectGuard<HBRUSH>>>,
+	brush_field: UnsafeCell<Option<DeleteObjectGuard<HBRUSH>>>,
+	_pin: PhantomPinned,
+}
+
+/// Applies a light or dark theme to a parent window and its child controls.
+///
+/// When dark, the window receives an immersive dark title bar through
+/// [`HWND::DwmSetWindowAttribute`](crate::prelude::dwm_Hwnd::DwmSetWindowAttribute),
+/// the controls receive the dark variants of their visual styles through
+/// [`HWND::SetWindowTheme`](crate::prelude::uxtheme_Hwnd::SetWindowTheme),
+/// and the backgrounds are painted by handling the `WM_CTLCOLOR*` messages.
+///
+/// The `WM_CTLCOLOR*` and `WM_ERASEBKGND` messages of the parent window are
+/// handled only if you don't handle them yourself, regardless of the order the
+/// handlers are added; if you do, painting these backgrounds is up to you.
+///
+/// **Note:** To use this struct, enable the `advapi` and `dwm`
+/// [Cargo features](https://doc.rust-lang.org/cargo/reference/features.html#the-features-section).
+#[derive(Clone)]
+pub struct DarkMode(Pin<Arc<DarkModeObj>>);
+
+unsafe impl Send for DarkMode {}
+
+impl DarkMode {
+	const COLOR_BG: COLORREF = COLORREF::new(0x20, 0x20, 0x20);
+	const COLOR_FIELD: COLORREF = COLORREF::new(0x19, 0x19, 0x19);
+	const COLOR_TEXT: COLORREF = COLORREF::new(0xf0, 0xf0, 0xf0);
+
+	/// Instantiates a new `DarkMode` object, applying the theme when the parent
+	/// window is created.
+	///
+	/// Since the controls are themed when the parent window is created, the
+	/// `DarkMode` must be instantiated after them. Controls created afterwards
+	/// will be themed only when the theme changes.
+	///
+	/// Note that only top-level windows are notified when the system setting
+	/// changes, so [`Theme::System`](crate::gui::Theme::System) won't switch
+	/// child windows automatically.
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window was already created – that is, you cannot
+	/// dynamically create a `DarkMode` in an event closure.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, gui};
+	///
+	/// let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// let btn = gui::Button::new(&wnd, gui::ButtonOpts::default());
+	///
+	/// let _dark_mode = gui::DarkMode::new(&wnd, gui::Theme::System);
+	///
+	/// wnd.run_main(None)?;
+	/// # w::AnyResult::Ok(())
+	/// ```
+	#[must_use]
+	pub fn new(parent: &(impl GuiParent + 'static), theme: Theme) -> Self {
+		let new_self = Self(Arc::pin(DarkModeObj {
+			is_dlg: parent.as_ref().is_dlg(),
+			hwnd: UnsafeCell::new(HWND::NULL),
+			theme: UnsafeCell::new(theme),
+			dark: UnsafeCell::new(false),
+			brush_bg: UnsafeCell::new(None),
+			brush_field: UnsafeCell::new(None),
+			_pin: PhantomPinned,
+		}));
+
+		new_self.default_message_handlers(parent);
+		new_self
+	}
+
+	fn default_message_handlers(&self, parent: &(impl GuiParent + 'static)) {
+		let self2 = self.clone();
+		let parent2 = parent.clone();
+		parent
+			.as_ref()
+			.after_on()
+			.wm(parent.as_ref().is_dlg().create_msg(), move |_| {
+				*unsafe { &mut *self2.0.hwnd.get() } = unsafe { parent2.hwnd().raw_copy() };
+				self2.apply()?;
+				Ok(0) // ignored
+			});
+
+		let self2 = self.clone();
+		parent
+			.as_ref()
+			.before_on()
+			.wm(co::WM::SETTINGCHANGE, move |p| {
+				let p = unsafe { wm::SettingChange::from_generic_wm(p) };
+				if self2.theme() == Theme::System
+					&& !p.area.is_null()
+					&& unsafe { WString::from_wchars_nullt(p.area) }.to_string()
+						== "ImmersiveColorSet"
+				{
+					self2.apply()?; // the user switched the system app mode
+				}
+				Ok(0) // ignored
+			});
+
+		let self2 = self.clone();
+		parent.as_ref().before_on().wm(co::WM::DESTROY, move |_| {
+			*unsafe { &mut *self2.0.brush_bg.get() } = None;
+			*unsafe { &mut *self2.0.brush_field.get() } = None;
+			Ok(0) // ignored
+		});
+
+		for (msg, is_field) in [
+			(co::WM::CTLCOLORBTN, false),
+			(co::WM::CTLCOLORDLG, false),
+			(co::WM::CTLCOLOREDIT, true),
+			(co::WM::CTLCOLORLISTBOX, true),
+			(co::WM::CTLCOLORSCROLLBAR, false),
+			(co::WM::CTLCOLORSTATIC, false),
+		] {
+			let self2 = self.clone();
+			parent.as_ref().fallback_on().wm(msg, move |p| {
+				if !self2.is_dark() {
+					return Ok(self2.def_proc(p));
+				}
+				let hdc = unsafe { HDC::from_ptr(p.wparam as _) };
+				hdc.SetTextColor(Self::COLOR_TEXT)?;
+				let hbrush = if is_field {
+					hdc.SetBkColor(Self::COLOR_FIELD)?;
+					unsafe { &*self2.0.brush_field.get() }
+						.as_ref()
+						.unwrap()
+						.ptr()
+				} else {
+					hdc.SetBkColor(Self::COLOR_BG)?;
+					unsafe { &*self2.0.brush_bg.get() }.as_ref().unwrap().ptr()
+				};
+				Ok(hbrush as _)
+			});
+		}
+
+		if let IsDlg::No = parent.as_ref().is_dlg() {
+			// Dialogs paint their background with WM_CTLCOLORDLG.
+			let self2 = self.clone();
+			parent
+				.as_ref()
+				.fallback_on()
+				.wm(co::WM::ERASEBKGND, move |p| {
+					if !self2.is_dark() {
+						return Ok(self2.def_proc(p));
+					}
+					let hdc = unsafe { HDC::from_ptr(p.wparam as _) };
+					let hbrush = unsafe { &*self2.0.brush_bg.get() }.as_ref().unwrap();
+					hdc.FillRect(self2.hwnd().GetClientRect()?, hbrush)?;
+					Ok(1) // background erased
+				});
+		}
+	}
+
+	/// Default processing of a message not handled because the theme is light.
+	#[must_use]
+	fn def_proc(&self, p: WndMsg) -> isize {
+		match self.0.is_dlg {
+			IsDlg::Yes => 0, // FALSE, let the dialog manager handle it
+			IsDlg::No => unsafe { self.hwnd().DefWindowProc(p) },
+		}
+	}
+
+	/// Applies the current theme, if it differs from the one already applied.
+	fn apply(&self) -> AnyResult<()> {
+		let dark = match self.theme() {
+			Theme::Light => false,
+			Theme::Dark => true,
+			Theme::System => Self::is_system_dark(),
+		};
+		if dark == self.is_dark() {
+			return Ok(()); // windows are created light, so nothing to do
+		}
+
+		if dark && unsafe { &*self.0.brush_bg.get() }.is_none() {
+			*unsafe { &mut *self.0.brush_bg.get() } =
+				Some(HBRUSH::CreateSolidBrush(Self::COLOR_BG)?);
+			*unsafe { &mut *self.0.brush_field.get() } =
+				Some(HBRUSH::CreateSolidBrush(Self::COLOR_FIELD)?);
+		}
+		*unsafe { &mut *self.0.dark.get() } = dark;
+
+		let hwnd = self.hwnd();
+		let use_dark = dark as i32; // BOOL
+		unsafe {
+			if hwnd
+				.DwmSetWindowAttribute(co::DWMWA::USE_IMMERSIVE_DARK_MODE, &use_dark)
+				.is_err()
+			{
+				// Windows 10 builds before 20H1 used an undocumented attribute
+				// value; child windows fail both calls, which is harmless.
+				hwnd.DwmSetWindowAttribute(co::DWMWA::from_raw(19), &use_dark)
+					.ok();
+			}
+		}
+
+		Self::set_control_theme(hwnd, dark)?; // window own scroll bars
+		let mut res = AnyResult::Ok(());
+		hwnd.EnumChildWindows(|hchild| {
+			res = Self::set_control_theme(&hchild, dark);
+			res.is_ok() // stop on error
+		});
+		res?;
+
+		let rc = hwnd.ScreenToClientRc(hwnd.GetWindowRect()?)?; // include the title bar
+		hwnd.RedrawWindow(
+			rc,
+			&HRGN::NULL,
+			co::RDW::ERASE | co::RDW::FRAME | co::RDW::INVALIDATE | co::RDW::ALLCHILDREN,
+		)?;
+		Ok(())
+	}
+
+	fn set_control_theme(hwnd: &HWND, dark: bool) -> AnyResult<()> {
+		let is_field = matches!(hwnd.GetClassName()?.as_str(), "ComboBox" | "Edit");
+		hwnd.SetWindowTheme(
+			match (dark, is_field) {
+				(true, true) => "DarkMode_CFD",
+				(true, false) => "DarkMode_Explorer",
+				(false, true) => "CFD",
+				(false, false) => "Explorer",
+			},
+			None,
+		)?;
+		Ok(())
+	}
+
+	/// Returns the handle of the parent window.
+	///
+	/// Note that the handle is null until the parent window is created.
+	#[must_use]
+	pub fn hwnd(&self) -> &HWND {
+		unsafe { &*self.0.hwnd.get() }
+	}
+
+	/// Returns whether the dark theme is currently applied to the window.
+	#[must_use]
+	pub fn is_dark(&self) -> bool {
+		unsafe { *self.0.dark.get() }
+	}
+
+	/// Returns whether the system is set to use dark mode for applications,
+	/// as read from the `AppsUseLightTheme` value of the registry.
+	///
+	/// If the value cannot be read, as in older versions of Windows, returns
+	/// `false`.
+	#[must_use]
+	pub fn is_system_dark() -> bool {
+		matches!(
+			HKEY::CURRENT_USER.RegGetValue(
+				Some("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize"),
+				Some("AppsUseLightTheme"),
+				co::RRF::RT_REG_DWORD,
+			),
+			Ok(RegistryValue::Dword(0)),
+		)
+	}
+
+	/// Replaces the theme. If the parent window is already created, the new
+	/// theme is immediately applied to it and to its current child controls.
+	pub fn set_theme(&self, theme: Theme) -> AnyResult<()> {
+		*unsafe { &mut *self.0.theme.get() } = theme;
+		if *self.hwnd() != HWND::NULL {
+			self.apply()?;
+		}
+		Ok(())
+	}
+
+	/// Returns the current theme.
+	#[must_use]
+	pub fn theme(&self) -> Theme {
+		unsafe { *self.0.theme.get() }
+	}
+}
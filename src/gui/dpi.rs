@@ -1,5 +1,6 @@
 use crate::co;
 use crate::decl::*;
+use crate::kernel::ffi_types::*;
 use crate::prelude::*;
 
 /// Global horizontal and vertical system DPI factor.
@@ -21,15 +22,15 @@ fn cache_dpi() {
 /// [`HDC::GetDeviceCaps`](crate::prelude::gdi_Hdc::GetDeviceCaps).
 pub fn dpi_x(x_val: i32) -> i32 {
 	cache_dpi();
-	MulDiv(x_val.into(), unsafe { DPI }.0, 96)
+	MulDiv(x_val, unsafe { DPI }.0, 96)
 }
 
 /// Returns the value adjusted according to the current vertical system DPI,
 /// retrieved with
 /// [`HDC::GetDeviceCaps`](crate::prelude::gdi_Hdc::GetDeviceCaps).
-pub fn dpi_y(x_val: i32) -> i32 {
+pub fn dpi_y(y_val: i32) -> i32 {
 	cache_dpi();
-	MulDiv(x_val.into(), unsafe { DPI }.0, 96)
+	MulDiv(y_val, unsafe { DPI }.1, 96)
 }
 
 /// Returns the values adjusted according to the current horizontal and vertical
@@ -38,3 +39,105 @@ pub fn dpi_y(x_val: i32) -> i32 {
 pub fn dpi(x_val: i32, y_val: i32) -> (i32, i32) {
 	(dpi_x(x_val), dpi_y(y_val))
 }
+
+/// Functions which are not available in all Windows versions, thus loaded
+/// dynamically from user32.dll; `None` if not loaded yet.
+static mut USER32_PROCS: Option<User32Procs> = None;
+
+#[derive(Clone, Copy)]
+struct User32Procs {
+	get_dpi_for_window: Option<extern "system" fn(HANDLE) -> u32>, // Windows 10 1607
+	set_process_dpi_awareness_context: Option<extern "system" fn(isize) -> BOOL>, // Windows 10 1703
+}
+
+fn user32_procs() -> User32Procs {
+	if let Some(procs) = unsafe { USER32_PROCS } {
+		return procs;
+	}
+	let proc_addr = |name: &str| {
+		HINSTANCE::GetModuleHandle(Some("user32.dll"))
+			.and_then(|hinst| hinst.GetProcAddress(name))
+			.ok()
+	};
+	let procs = User32Procs {
+		get_dpi_for_window: proc_addr("GetDpiForWindow")
+			.map(|p| unsafe { std::mem::transmute::<_, extern "system" fn(HANDLE) -> u32>(p) }),
+		set_process_dpi_awareness_context: proc_addr("SetProcessDpiAwarenessContext")
+			.map(|p| unsafe { std::mem::transmute::<_, extern "system" fn(isize) -> BOOL>(p) }),
+	};
+	unsafe {
+		USER32_PROCS = Some(procs);
+	}
+	procs
+}
+
+/// Returns the system DPI, retrieved with
+/// [`HDC::GetDeviceCaps`](crate::prelude::gdi_Hdc::GetDeviceCaps), which,
+/// unlike [`GetDpiForSystem`](crate::GetDpiForSystem), is available in all
+/// Windows versions.
+#[must_use]
+pub(in crate::gui) fn system_dpi() -> u32 {
+	cache_dpi();
+	unsafe { DPI }.1 as _
+}
+
+/// Returns the DPI of the window, or the system DPI if the window was not
+/// created yet, or if `GetDpiForWindow` is not available.
+#[must_use]
+pub(in crate::gui) fn hwnd_dpi(hwnd: &HWND) -> u32 {
+	match user32_procs().get_dpi_for_window {
+		Some(get_dpi_for_window) if *hwnd != HWND::NULL => match get_dpi_for_window(hwnd.ptr()) {
+			0 => system_dpi(), // invalid window
+			dpi => dpi,
+		},
+		_ => system_dpi(),
+	}
+}
+
+/// Makes the process per-monitor DPI aware if the system supports it, otherwise
+/// system DPI aware.
+pub(in crate::gui) fn set_process_dpi_aware() -> SysResult<()> {
+	if let Some(set_context) = user32_procs().set_process_dpi_awareness_context {
+		// Fails if the awareness was already set, like in the manifest.
+		set_context(co::DPI_AWARENESS_CONTEXT::PER_MONITOR_AWARE_V2.raw());
+	} else if IsWindowsVistaOrGreater()? {
+		SetProcessDPIAware()?;
+	}
+	Ok(())
+}
+
+fn wnd_dpi(wnd: &impl GuiWindow) -> i32 {
+	hwnd_dpi(wnd.hwnd()) as _
+}
+
+/// Returns the value adjusted according to the current horizontal DPI of the
+/// given window, retrieved with
+/// [`HWND::GetDpiForWindow`](crate::prelude::user_Hwnd::GetDpiForWindow).
+///
+/// If the window was not created yet, the system DPI is used.
+pub fn dpi_x_wnd(wnd: &impl GuiWindow, x_val: i32) -> i32 {
+	MulDiv(x_val, wnd_dpi(wnd), 96)
+}
+
+/// Returns the value adjusted according to the current vertical DPI of the
+/// given window, retrieved with
+/// [`HWND::GetDpiForWindow`](crate::prelude::user_Hwnd::GetDpiForWindow).
+///
+/// If the window was not created yet, the system DPI is used.
+pub fn dpi_y_wnd(wnd: &impl GuiWindow, y_val: i32) -> i32 {
+	MulDiv(y_val, wnd_dpi(wnd), 96)
+}
+
+/// Returns the values adjusted according to the current horizontal and vertical
+/// DPI of the given window, retrieved with
+/// [`HWND::GetDpiForWindow`](crate::prelude::user_Hwnd::GetDpiForWindow).
+///
+/// If the window was not created yet, the system DPI is used.
+///
+/// Unlike [`dpi`](crate::gui::dpi), which always uses the system DPI, this
+/// function follows the window when it's moved to a monitor with a different
+/// DPI.
+pub fn dpi_wnd(wnd: &impl GuiWindow, x_val: i32, y_val: i32) -> (i32, i32) {
+	let dpi = wnd_dpi(wnd);
+	(MulDiv(x_val, dpi, 96), MulDiv(y_val, dpi, 96))
+}
@@ -0,0 +1,175 @@
+use std::cell::UnsafeCell;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::gui::events::*;
+use crate::prelude::*;
+
+struct DropTargetObj {
+	hwnd: UnsafeCell<HWND>,
+	drop_target: UnsafeCell<Option<IDropTarget>>, // registered window keeps a pointer to it
+	data_obj: UnsafeCell<Option<IDataObject>>,    // data being dragged over the window
+	events: DropTargetEvents,
+	_pin: PhantomPinned,
+}
+
+/// Registers a window, which can be a parent window or a control, as an OLE
+/// drop target, so it can receive data – like files or text – dragged from
+/// other windows and applications.
+///
+/// The window is registered with
+/// [`HWND::RegisterDragDrop`](crate::prelude::ole_Hwnd::RegisterDragDrop) when
+/// the parent window is created, and revoked when it's destroyed.
+///
+/// Note that you must call [`OleInitialize`](crate::OleInitialize) before
+/// running the main window, otherwise the registration will fail.
+#[derive(Clone)]
+pub struct DropTarget(Pin<Arc<DropTargetObj>>);
+
+unsafe impl Send for DropTarget {}
+
+impl DropTarget {
+	/// Instantiates a new `DropTarget` object, registering `target` when
+	/// `parent` is created.
+	///
+	/// To register the parent window itself, pass it as both arguments. If
+	/// `target` is a control, the `DropTarget` must be instantiated after the
+	/// control.
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window was already created – that is, you cannot
+	/// dynamically create a `DropTarget` in an event closure.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let _ole_guard = w::OleInitialize()?;
+	///
+	/// let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// let list = gui::ListView::<()>::new(&wnd, gui::ListViewOpts::default());
+	///
+	/// let drop_target = gui::DropTarget::new(&wnd, &list);
+	/// drop_target.on().drop(move |d| {
+	///     for file in d.files()? {
+	///         println!("Dropped: {}", file);
+	///     }
+	///     Ok(co::DROPEFFECT::COPY)
+	/// });
+	///
+	/// wnd.run_main(None)?;
+	/// # w::AnyResult::Ok(())
+	/// ```
+	#[must_use]
+	pub fn new(
+		parent: &(impl GuiParent + 'static),
+		target: &(impl GuiWindow + Clone + 'static),
+	) -> Self {
+		let new_self = Self(Arc::pin(DropTargetObj {
+			hwnd: UnsafeCell::new(HWND::NULL),
+			drop_target: UnsafeCell::new(None),
+			data_obj: UnsafeCell::new(None),
+			events: DropTargetEvents::new(),
+			_pin: PhantomPinned,
+		}));
+
+		new_self.default_message_handlers(parent, target);
+		new_self
+	}
+
+	fn default_message_handlers(
+		&self,
+		parent: &(impl GuiParent + 'static),
+		target: &(impl GuiWindow + Clone + 'static),
+	) {
+		let self2 = self.clone();
+		let target2 = target.clone();
+		parent
+			.as_ref()
+			.before_on()
+			.wm(parent.as_ref().is_dlg().create_msg(), move |_| {
+				*unsafe { &mut *self2.0.hwnd.get() } = unsafe { target2.hwnd().raw_copy() };
+				*unsafe { &mut *self2.0.drop_target.get() } = Some(self2.create_drop_target());
+
+				let drop_target = unsafe { &*self2.0.drop_target.get() }.as_ref().unwrap();
+				self2.hwnd().RegisterDragDrop(drop_target)?;
+				Ok(0) // ignored
+			});
+
+		let self2 = self.clone();
+		parent.as_ref().before_on().wm(co::WM::DESTROY, move |_| {
+			self2.hwnd().RevokeDragDrop().ok(); // the registration may have failed
+			*unsafe { &mut *self2.0.data_obj.get() } = None;
+			*unsafe { &mut *self2.0.drop_target.get() } = None; // releases the closures
+			Ok(0) // ignored
+		});
+	}
+
+	fn create_drop_target(&self) -> IDropTarget {
+		let drop_target = IDropTarget::new_impl();
+
+		let self2 = self.clone();
+		drop_target.DragEnter(move |data_obj, key_state, pt, effect| {
+			*unsafe { &mut *self2.0.data_obj.get() } = Some(data_obj.clone());
+			let dd = DragData::new(data_obj, key_state, self2.hwnd().ScreenToClient(pt)?, *effect);
+			*effect = self2.0.events.process_drag_enter(&dd)? & dd.allowed;
+			Ok(())
+		});
+
+		let self2 = self.clone();
+		drop_target.DragOver(move |key_state, pt, effect| {
+			match unsafe { &*self2.0.data_obj.get() } {
+				Some(data_obj) => {
+					let pt = self2.hwnd().ScreenToClient(pt)?;
+					let dd = DragData::new(data_obj, key_state, pt, *effect);
+					*effect = self2.0.events.process_drag_over(&dd)? & dd.allowed;
+				},
+				None => *effect = co::DROPEFFECT::NONE,
+			}
+			Ok(())
+		});
+
+		let self2 = self.clone();
+		drop_target.DragLeave(move || {
+			*unsafe { &mut *self2.0.data_obj.get() } = None;
+			self2.0.events.process_drag_leave()
+		});
+
+		let self2 = self.clone();
+		drop_target.Drop(move |data_obj, key_state, pt, effect| {
+			*unsafe { &mut *self2.0.data_obj.get() } = None;
+			let dd = DragData::new(data_obj, key_state, self2.hwnd().ScreenToClient(pt)?, *effect);
+			*effect = self2.0.events.process_drop(&dd)? & dd.allowed;
+			Ok(())
+		});
+
+		drop_target
+	}
+
+	/// Exposes the drop target events.
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window is already created. Events must be set
+	/// before parent window creation.
+	#[must_use]
+	pub fn on(&self) -> &DropTargetEvents {
+		if *self.hwnd() != HWND::NULL {
+			panic!("Cannot add event after window creation.");
+		}
+		&self.0.events
+	}
+
+	/// Returns the handle of the registered window.
+	///
+	/// Note that the handle is null until the parent window is created.
+	#[must_use]
+	pub fn hwnd(&self) -> &HWND {
+		unsafe { &*self.0.hwnd.get() }
+	}
+}
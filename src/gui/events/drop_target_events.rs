@@ -0,0 +1,222 @@
+use std::cell::UnsafeCell;
+
+use crate::co;
+use crate::decl::*;
+use crate::prelude::*;
+
+/// Data being dragged over a [`DropTarget`](crate::gui::DropTarget), passed
+/// to the [`DropTargetEvents`](crate::gui::events::DropTargetEvents)
+/// handlers.
+pub struct DragData<'a> {
+	data_obj: &'a IDataObject,
+	/// State of the modifier keys and mouse buttons.
+	pub key_state: co::MK,
+	/// Cursor position, in client coordinates of the target window.
+	pub pt: POINT,
+	/// Effects allowed by the drag source. The effect returned by the handler
+	/// is masked by these.
+	pub allowed: co::DROPEFFECT,
+}
+
+impl<'a> DragData<'a> {
+	#[must_use]
+	pub(in crate::gui) const fn new(
+		data_obj: &'a IDataObject,
+		key_state: co::MK,
+		pt: POINT,
+		allowed: co::DROPEFFECT,
+	) -> Self {
+		Self { data_obj, key_state, pt, allowed }
+	}
+
+	/// Returns the underlying data object, which can be used to retrieve data
+	/// in formats not covered by the other methods.
+	#[must_use]
+	pub const fn data_object(&self) -> &IDataObject {
+		self.data_obj
+	}
+
+	/// Retrieves the raw data in the given format, delivered as
+	/// [`TYMED::HGLOBAL`](crate::co::TYMED::HGLOBAL).
+	#[must_use]
+	pub fn data(&self, format: co::CF) -> HrResult<Vec<u8>> {
+		let medium = unsafe { self.data_obj.GetData(&Self::formatetc(format))? };
+		let hglobal = unsafe { medium.ptr_hglobal() }.ok_or(co::HRESULT::DV_E_TYMED)?;
+		let block = hglobal.GlobalLock().map_err(|err| err.to_hresult())?;
+		Ok(block.as_slice().to_vec()) // the memory is freed by the medium guard
+	}
+
+	/// Retrieves the list of dragged files, stored as
+	/// [`CF::HDROP`](crate::co::CF::HDROP).
+	#[must_use]
+	pub fn files(&self) -> HrResult<Vec<String>> {
+		let data = self.data(co::CF::HDROP)?;
+		DropFiles::parse(&data)
+			.map(|df| df.files)
+			.map_err(|err| err.to_hresult())
+	}
+
+	/// Tells whether the data is available in the given format.
+	#[must_use]
+	pub fn has_format(&self, format: co::CF) -> bool {
+		self.data_obj.QueryGetData(&Self::formatetc(format)).is_ok()
+	}
+
+	/// Retrieves the dragged text, stored as
+	/// [`CF::UNICODETEXT`](crate::co::CF::UNICODETEXT).
+	#[must_use]
+	pub fn text(&self) -> HrResult<String> {
+		let data = self.data(co::CF::UNICODETEXT)?;
+		let wchars = data
+			.chunks_exact(2)
+			.map(|b| u16::from_le_bytes([b[0], b[1]]))
+			.take_while(|ch| *ch != 0)
+			.collect::<Vec<_>>();
+		Ok(WString::from_wchars_slice(&wchars).to_string())
+	}
+
+	#[must_use]
+	fn formatetc(format: co::CF) -> FORMATETC<'static> {
+		let mut fetc = FORMATETC::default();
+		fetc.cfFormat = format;
+		fetc.dwAspect = co::DVASPECT::CONTENT;
+		fetc.tymed = co::TYMED::HGLOBAL;
+		fetc
+	}
+}
+
+type DragFunc = Box<dyn Fn(&DragData) -> AnyResult<co::DROPEFFECT>>;
+type LeaveFunc = Box<dyn Fn() -> AnyResult<()>>;
+
+/// Exposes drop target events.
+///
+/// The events are sent by OLE while data is dragged over the window.
+///
+/// You cannot directly instantiate this object, it is created internally by the
+/// [`DropTarget`](crate::gui::DropTarget).
+pub struct DropTargetEvents {
+	drag_enter: UnsafeCell<Option<DragFunc>>,
+	drag_over: UnsafeCell<Option<DragFunc>>,
+	drag_leave: UnsafeCell<Option<LeaveFunc>>,
+	drop: UnsafeCell<Option<DragFunc>>,
+}
+
+impl DropTargetEvents {
+	#[must_use]
+	pub(in crate::gui) const fn new() -> Self {
+		Self {
+			drag_enter: UnsafeCell::new(None),
+			drag_over: UnsafeCell::new(None),
+			drag_leave: UnsafeCell::new(None),
+			drop: UnsafeCell::new(None),
+		}
+	}
+
+	pub(in crate::gui) fn process_drag_enter(&self, dd: &DragData) -> AnyResult<co::DROPEFFECT> {
+		match unsafe { &*self.drag_enter.get() } {
+			Some(func) => func(dd),
+			None => Ok(Self::default_effect(dd)),
+		}
+	}
+
+	pub(in crate::gui) fn process_drag_over(&self, dd: &DragData) -> AnyResult<co::DROPEFFECT> {
+		match unsafe { &*self.drag_over.get() } {
+			Some(func) => func(dd),
+			None => Ok(Self::default_effect(dd)),
+		}
+	}
+
+	pub(in crate::gui) fn process_drag_leave(&self) -> AnyResult<()> {
+		match unsafe { &*self.drag_leave.get() } {
+			Some(func) => func(),
+			None => Ok(()),
+		}
+	}
+
+	pub(in crate::gui) fn process_drop(&self, dd: &DragData) -> AnyResult<co::DROPEFFECT> {
+		match unsafe { &*self.drop.get() } {
+			Some(func) => func(dd),
+			None => Ok(co::DROPEFFECT::NONE),
+		}
+	}
+
+	#[must_use]
+	fn default_effect(dd: &DragData) -> co::DROPEFFECT {
+		if dd.allowed.has(co::DROPEFFECT::COPY) {
+			co::DROPEFFECT::COPY
+		} else {
+			co::DROPEFFECT::NONE
+		}
+	}
+
+	/// [`IDropTarget::DragEnter`](https://learn.microsoft.com/en-us/windows/win32/api/oleidl/nf-oleidl-idroptarget-dragenter)
+	/// event, sent when the cursor first enters the window while dragging.
+	///
+	/// The handler returns the effect to be performed if the data is dropped.
+	/// If no handler is set,
+	/// [`DROPEFFECT::COPY`](crate::co::DROPEFFECT::COPY) is used.
+	///
+	/// # Examples
+	///
+	/// Accepting only files:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let drop_target: gui::DropTarget; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let drop_target = gui::DropTarget::new(&wnd, &wnd);
+	///
+	/// drop_target.on().drag_enter(|d| {
+	///     Ok(if d.has_format(co::CF::HDROP) {
+	///         co::DROPEFFECT::COPY
+	///     } else {
+	///         co::DROPEFFECT::NONE
+	///     })
+	/// });
+	/// ```
+	pub fn drag_enter<F>(&self, func: F) -> &Self
+	where
+		F: Fn(&DragData) -> AnyResult<co::DROPEFFECT> + 'static,
+	{
+		*unsafe { &mut *self.drag_enter.get() } = Some(Box::new(func));
+		self
+	}
+
+	/// [`IDropTarget::DragLeave`](https://learn.microsoft.com/en-us/windows/win32/api/oleidl/nf-oleidl-idroptarget-dragleave)
+	/// event, sent when the cursor leaves the window, or the drag is cancelled.
+	pub fn drag_leave<F>(&self, func: F) -> &Self
+	where
+		F: Fn() -> AnyResult<()> + 'static,
+	{
+		*unsafe { &mut *self.drag_leave.get() } = Some(Box::new(func));
+		self
+	}
+
+	/// [`IDropTarget::DragOver`](https://learn.microsoft.com/en-us/windows/win32/api/oleidl/nf-oleidl-idroptarget-dragover)
+	/// event, sent repeatedly while the cursor moves over the window.
+	///
+	/// The handler returns the effect to be performed if the data is dropped at
+	/// the current position. If no handler is set,
+	/// [`DROPEFFECT::COPY`](crate::co::DROPEFFECT::COPY) is used.
+	pub fn drag_over<F>(&self, func: F) -> &Self
+	where
+		F: Fn(&DragData) -> AnyResult<co::DROPEFFECT> + 'static,
+	{
+		*unsafe { &mut *self.drag_over.get() } = Some(Box::new(func));
+		self
+	}
+
+	/// [`IDropTarget::Drop`](https://learn.microsoft.com/en-us/windows/win32/api/oleidl/nf-oleidl-idroptarget-drop)
+	/// event, sent when the data is dropped into the window.
+	///
+	/// The handler returns the effect actually performed, which is reported
+	/// back to the drag source.
+	pub fn drop<F>(&self, func: F) -> &Self
+	where
+		F: Fn(&DragData) -> AnyResult<co::DROPEFFECT> + 'static,
+	{
+		*unsafe { &mut *self.drop.get() } = Some(Box::new(func));
+		self
+	}
+}
@@ -0,0 +1,43 @@
+use crate::co;
+use crate::decl::*;
+use crate::gui::{events::*, privs::*};
+
+/// Exposes IP address control
+/// [notifications](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-ip-address-control-reference-notifications).
+///
+/// These event methods are just proxies to the
+/// [`WindowEvents`](crate::gui::events::WindowEvents) of the parent window, who
+/// is the real responsible for the child event handling.
+///
+/// You cannot directly instantiate this object, it is created internally by the
+/// control.
+pub struct IpAddressEvents(BaseCtrlEvents);
+
+impl IpAddressEvents {
+	#[must_use]
+	pub(in crate::gui) fn new(parent: &impl AsRef<BaseWnd>, ctrl_id: u16) -> Self {
+		Self(BaseCtrlEvents::new(parent, ctrl_id))
+	}
+
+	pub_fn_cmd_noparm_noret! { en_change, co::EN::CHANGE;
+		/// [`EN_CHANGE`](https://learn.microsoft.com/en-us/windows/win32/controls/en-change)
+		/// command notification.
+	}
+
+	pub_fn_cmd_noparm_noret! { en_kill_focus, co::EN::KILLFOCUS;
+		/// [`EN_KILLFOCUS`](https://learn.microsoft.com/en-us/windows/win32/controls/en-killfocus)
+		/// command notification.
+	}
+
+	pub_fn_cmd_noparm_noret! { en_set_focus, co::EN::SETFOCUS;
+		/// [`EN_SETFOCUS`](https://learn.microsoft.com/en-us/windows/win32/controls/en-setfocus)
+		/// command notification.
+	}
+
+	pub_fn_nfy_withmutparm_noret! { ipn_field_changed, co::IPN::FIELDCHANGED, NMIPADDRESS;
+		/// [`IPN_FIELDCHANGED`](https://learn.microsoft.com/en-us/windows/win32/controls/ipn-fieldchanged)
+		/// notification.
+		///
+		/// The new value can be modified by changing `iValue`.
+	}
+}
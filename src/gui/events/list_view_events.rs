@@ -222,6 +222,10 @@ impl ListViewEvents {
 
 	/// [`LVN_ODFINDITEM`](https://learn.microsoft.com/en-us/windows/win32/controls/lvn-odfinditem)
 	/// notification.
+	///
+	/// By default, this notification is forwarded to the
+	/// [`ListViewDataSource`](crate::prelude::ListViewDataSource), if any.
+	/// Handling it replaces this default behavior.
 	pub fn lvn_od_find_item<F>(&self, func: F) -> &Self
 	where
 		F: Fn(&mut NMLVFINDITEM) -> AnyResult<Option<u32>> + 'static,
@@ -4,16 +4,26 @@ mod base_ctrl_events;
 mod button_events;
 mod combo_box_events;
 mod date_time_picker_events;
+mod drop_target_events;
 mod edit_events;
 mod header_events;
+mod hotkey_events;
+mod ip_address_events;
 mod label_events;
 mod list_box_events;
 mod list_view_events;
 mod month_calendar_events;
+mod pager_events;
 mod radio_group_events;
+mod rebar_events;
+mod rich_edit_events;
 mod status_bar_events;
+mod sys_link_events;
 mod tab_events;
+mod toolbar_events;
 mod trackbar_events;
+#[cfg(feature = "shell")]
+mod tray_icon_events;
 mod tree_view_events;
 mod up_down_events;
 mod window_events;
@@ -23,16 +33,26 @@ pub(in crate::gui) use base_ctrl_events::BaseCtrlEvents;
 pub use button_events::ButtonEvents;
 pub use combo_box_events::ComboBoxEvents;
 pub use date_time_picker_events::DateTimePickerEvents;
+pub use drop_target_events::{DragData, DropTargetEvents};
 pub use edit_events::EditEvents;
 pub use header_events::HeaderEvents;
+pub use hotkey_events::HotkeyEvents;
+pub use ip_address_events::IpAddressEvents;
 pub use label_events::LabelEvents;
 pub use list_box_events::ListBoxEvents;
 pub use list_view_events::ListViewEvents;
 pub use month_calendar_events::MonthCalendarEvents;
+pub use pager_events::PagerEvents;
 pub use radio_group_events::RadioGroupEvents;
+pub use rebar_events::RebarEvents;
+pub use rich_edit_events::RichEditEvents;
 pub use status_bar_events::StatusBarEvents;
+pub use sys_link_events::SysLinkEvents;
 pub use tab_events::TabEvents;
+pub use toolbar_events::ToolbarEvents;
 pub use trackbar_events::TrackbarEvents;
+#[cfg(feature = "shell")]
+pub use tray_icon_events::TrayIconEvents;
 pub use tree_view_events::TreeViewEvents;
 pub use up_down_events::UpDownEvents;
 pub use window_events::WindowEvents;
@@ -0,0 +1,36 @@
+use crate::co;
+use crate::decl::*;
+use crate::gui::{events::*, privs::*};
+
+/// Exposes pager control
+/// [notifications](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-pager-control-reference-notifications).
+///
+/// These event methods are just proxies to the
+/// [`WindowEvents`](crate::gui::events::WindowEvents) of the parent window, who
+/// is the real responsible for the child event handling.
+///
+/// You cannot directly instantiate this object, it is created internally by the
+/// control.
+pub struct PagerEvents(BaseCtrlEvents);
+
+impl PagerEvents {
+	#[must_use]
+	pub(in crate::gui) fn new(parent: &impl AsRef<BaseWnd>, ctrl_id: u16) -> Self {
+		Self(BaseCtrlEvents::new(parent, ctrl_id))
+	}
+
+	pub_fn_nfy_withmutparm_noret! { pgn_calc_size, co::PGN::CALCSIZE, NMPGCALCSIZE;
+		/// [`PGN_CALCSIZE`](https://learn.microsoft.com/en-us/windows/win32/controls/pgn-calcsize)
+		/// notification.
+		///
+		/// Set `iWidth` or `iHeight`, according to `dwFlag`, to the size of
+		/// the contained window.
+	}
+
+	pub_fn_nfy_withmutparm_noret! { pgn_scroll, co::PGN::SCROLL, NMPGSCROLL;
+		/// [`PGN_SCROLL`](https://learn.microsoft.com/en-us/windows/win32/controls/pgn-scroll)
+		/// notification.
+		///
+		/// The amount to scroll can be changed by setting `iScroll`.
+	}
+}
@@ -0,0 +1,143 @@
+use crate::co;
+use crate::decl::*;
+use crate::gui::{events::*, privs::*};
+
+/// Exposes rich edit control
+/// [notifications](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-rich-edit-control-reference-notifications).
+///
+/// Most notifications are sent only if their flag is present in the event mask
+/// of the control, set with
+/// [`RichEdit::set_event_mask`](crate::gui::RichEdit::set_event_mask).
+///
+/// These event methods are just proxies to the
+/// [`WindowEvents`](crate::gui::events::WindowEvents) of the parent window, who
+/// is the real responsible for the child event handling.
+///
+/// You cannot directly instantiate this object, it is created internally by the
+/// control.
+pub struct RichEditEvents(BaseCtrlEvents);
+
+impl RichEditEvents {
+	#[must_use]
+	pub(in crate::gui) fn new(parent: &impl AsRef<BaseWnd>, ctrl_id: u16) -> Self {
+		Self(BaseCtrlEvents::new(parent, ctrl_id))
+	}
+
+	pub_fn_cmd_noparm_noret! { en_change, co::EN::CHANGE;
+		/// [`EN_CHANGE`](https://learn.microsoft.com/en-us/windows/win32/controls/en-change)
+		/// command notification.
+		///
+		/// Requires [`ENM::CHANGE`](crate::co::ENM::CHANGE) in the event mask.
+	}
+
+	pub_fn_nfy_noparm_noret! { en_drag_drop_done, co::REN::DRAGDROPDONE;
+		/// [`EN_DRAGDROPDONE`](https://learn.microsoft.com/en-us/windows/win32/controls/en-dragdropdone)
+		/// notification.
+		///
+		/// Requires [`ENM::DRAGDROPDONE`](crate::co::ENM::DRAGDROPDONE) in the
+		/// event mask.
+	}
+
+	pub_fn_cmd_noparm_noret! { en_h_scroll, co::EN::HSCROLL;
+		/// [`EN_HSCROLL`](https://learn.microsoft.com/en-us/windows/win32/controls/en-hscroll)
+		/// command notification.
+		///
+		/// Requires [`ENM::SCROLL`](crate::co::ENM::SCROLL) in the event mask.
+	}
+
+	pub_fn_cmd_noparm_noret! { en_kill_focus, co::EN::KILLFOCUS;
+		/// [`EN_KILLFOCUS`](https://learn.microsoft.com/en-us/windows/win32/controls/en-killfocus)
+		/// command notification.
+	}
+
+	pub_fn_nfy_withparm_boolret! { en_link, co::REN::LINK, ENLINK;
+		/// [`EN_LINK`](https://learn.microsoft.com/en-us/windows/win32/controls/en-link)
+		/// notification.
+		///
+		/// Requires [`ENM::LINK`](crate::co::ENM::LINK) in the event mask, and
+		/// URL detection enabled with
+		/// [`RichEdit::auto_url_detect`](crate::gui::RichEdit::auto_url_detect).
+		///
+		/// Return `true` to prevent the control from processing the mouse or
+		/// keyboard message.
+		///
+		/// # Examples
+		///
+		/// Opening the clicked URL with the default browser:
+		///
+		/// ```no_run
+		/// use winsafe::{self as w, prelude::*, co, gui};
+		///
+		/// let wnd: gui::WindowMain; // initialized somewhere
+		/// let txt: gui::RichEdit;
+		/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+		/// # let txt = gui::RichEdit::new(&wnd, gui::RichEditOpts::default());
+		///
+		/// let txt2 = txt.clone();
+		/// txt.on().en_link(
+		///     move |p: &w::ENLINK| -> w::AnyResult<bool> {
+		///         if p.msg == co::WM::LBUTTONUP {
+		///             let chrg = p.chrg;
+		///             let url = txt2.text()?
+		///                 .encode_utf16()
+		///                 .skip(chrg.cpMin as _)
+		///                 .take((chrg.cpMax - chrg.cpMin) as _)
+		///                 .collect::<Vec<_>>();
+		///             println!("Clicked: {}", String::from_utf16_lossy(&url));
+		///         }
+		///         Ok(false)
+		///     },
+		/// );
+		/// ```
+	}
+
+	pub_fn_cmd_noparm_noret! { en_max_text, co::EN::MAXTEXT;
+		/// [`EN_MAXTEXT`](https://learn.microsoft.com/en-us/windows/win32/controls/en-maxtext)
+		/// command notification.
+	}
+
+	pub_fn_nfy_withparm_boolret! { en_msg_filter, co::REN::MSGFILTER, MSGFILTER;
+		/// [`EN_MSGFILTER`](https://learn.microsoft.com/en-us/windows/win32/controls/en-msgfilter)
+		/// notification.
+		///
+		/// Requires [`ENM::KEYEVENTS`](crate::co::ENM::KEYEVENTS) or
+		/// [`ENM::MOUSEEVENTS`](crate::co::ENM::MOUSEEVENTS) in the event mask.
+		///
+		/// Return `true` to prevent the control from processing the message.
+	}
+
+	pub_fn_nfy_withparm_noret! { en_request_resize, co::REN::REQUESTRESIZE, REQRESIZE;
+		/// [`EN_REQUESTRESIZE`](https://learn.microsoft.com/en-us/windows/win32/controls/en-requestresize)
+		/// notification.
+		///
+		/// Requires [`ENM::REQUESTRESIZE`](crate::co::ENM::REQUESTRESIZE) in the
+		/// event mask.
+	}
+
+	pub_fn_nfy_withparm_noret! { en_sel_change, co::REN::SELCHANGE, SELCHANGE;
+		/// [`EN_SELCHANGE`](https://learn.microsoft.com/en-us/windows/win32/controls/en-selchange)
+		/// notification.
+		///
+		/// Requires [`ENM::SELCHANGE`](crate::co::ENM::SELCHANGE) in the event
+		/// mask.
+	}
+
+	pub_fn_cmd_noparm_noret! { en_set_focus, co::EN::SETFOCUS;
+		/// [`EN_SETFOCUS`](https://learn.microsoft.com/en-us/windows/win32/controls/en-setfocus)
+		/// command notification.
+	}
+
+	pub_fn_cmd_noparm_noret! { en_update, co::EN::UPDATE;
+		/// [`EN_UPDATE`](https://learn.microsoft.com/en-us/windows/win32/controls/en-update)
+		/// command notification.
+		///
+		/// Requires [`ENM::UPDATE`](crate::co::ENM::UPDATE) in the event mask.
+	}
+
+	pub_fn_cmd_noparm_noret! { en_v_scroll, co::EN::VSCROLL;
+		/// [`EN_VSCROLL`](https://learn.microsoft.com/en-us/windows/win32/controls/en-vscroll)
+		/// command notification.
+		///
+		/// Requires [`ENM::SCROLL`](crate::co::ENM::SCROLL) in the event mask.
+	}
+}
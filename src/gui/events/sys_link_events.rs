@@ -0,0 +1,35 @@
+use crate::co;
+use crate::decl::*;
+use crate::gui::{events::*, privs::*};
+
+/// Exposes SysLink control
+/// [notifications](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-syslink-control-reference-notifications).
+///
+/// These event methods are just proxies to the
+/// [`WindowEvents`](crate::gui::events::WindowEvents) of the parent window, who
+/// is the real responsible for the child event handling.
+///
+/// You cannot directly instantiate this object, it is created internally by the
+/// control.
+pub struct SysLinkEvents(BaseCtrlEvents);
+
+impl SysLinkEvents {
+	#[must_use]
+	pub(in crate::gui) fn new(parent: &impl AsRef<BaseWnd>, ctrl_id: u16) -> Self {
+		Self(BaseCtrlEvents::new(parent, ctrl_id))
+	}
+
+	pub_fn_nfy_withparm_noret! { nm_click, co::NM::CLICK, NMLINK;
+		/// [`NM_CLICK`](https://learn.microsoft.com/en-us/windows/win32/controls/nm-click-syslink)
+		/// notification, sent when the user clicks a link.
+		///
+		/// The clicked link is described by `item`, whose `iLink`, `szID` and
+		/// `szUrl` are filled.
+	}
+
+	pub_fn_nfy_withparm_noret! { nm_return, co::NM::RETURN, NMLINK;
+		/// [`NM_RETURN`](https://learn.microsoft.com/en-us/windows/win32/controls/nm-return-syslink-)
+		/// notification, sent when the user presses <kbd>Enter</kbd> on a
+		/// focused link.
+	}
+}
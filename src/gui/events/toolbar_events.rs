@@ -0,0 +1,188 @@
+use crate::co;
+use crate::decl::*;
+use crate::gui::{events::*, privs::*};
+
+/// Exposes toolbar control
+/// [notifications](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-toolbar-control-reference-notifications).
+///
+/// These event methods are just proxies to the
+/// [`WindowEvents`](crate::gui::events::WindowEvents) of the parent window, who
+/// is the real responsible for the child event handling.
+///
+/// You cannot directly instantiate this object, it is created internally by the
+/// control.
+pub struct ToolbarEvents(BaseCtrlEvents);
+
+impl ToolbarEvents {
+	#[must_use]
+	pub(in crate::gui) fn new(parent: &impl AsRef<BaseWnd>, ctrl_id: u16) -> Self {
+		Self(BaseCtrlEvents::new(parent, ctrl_id))
+	}
+
+	/// [`BN_CLICKED`](https://learn.microsoft.com/en-us/windows/win32/controls/bn-clicked)
+	/// command notification, sent when the button with the given command ID is
+	/// clicked.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, gui};
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// let toolbar: gui::Toolbar;
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let toolbar = gui::Toolbar::new(&wnd, gui::ToolbarOpts::default());
+	///
+	/// const ID_OPEN: u16 = 2001;
+	///
+	/// toolbar.on().bn_clicked(ID_OPEN,
+	///     move || -> w::AnyResult<()> {
+	///         println!("Open clicked.");
+	///         Ok(())
+	///     },
+	/// );
+	/// ```
+	pub fn bn_clicked<F>(&self, cmd_id: u16, func: F) -> &Self
+	where
+		F: Fn() -> AnyResult<()> + 'static,
+	{
+		self.0.wm_command_id(cmd_id, co::BN::CLICKED, func);
+		self
+	}
+
+	pub_fn_nfy_withparm_boolret! { nm_click, co::NM::CLICK, NMMOUSE;
+		/// [`NM_CLICK`](https://learn.microsoft.com/en-us/windows/win32/controls/nm-click-toolbar)
+		/// notification.
+	}
+
+	/// [`NM_CUSTOMDRAW`](https://learn.microsoft.com/en-us/windows/win32/controls/nm-customdraw-toolbar)
+	/// notification.
+	pub fn nm_custom_draw<F>(&self, func: F) -> &Self
+	where
+		F: Fn(&mut NMCUSTOMDRAW) -> AnyResult<co::CDRF> + 'static,
+	{
+		self.0.wm_notify(co::NM::CUSTOMDRAW, move |p| {
+			Ok(func(unsafe { p.cast_nmhdr_mut::<NMCUSTOMDRAW>() })?.raw() as _)
+		});
+		self
+	}
+
+	pub_fn_nfy_withparm_boolret! { nm_dbl_clk, co::NM::DBLCLK, NMMOUSE;
+		/// [`NM_DBLCLK`](https://learn.microsoft.com/en-us/windows/win32/controls/nm-dblclk-toolbar)
+		/// notification.
+	}
+
+	pub_fn_nfy_withparm_boolret! { nm_r_click, co::NM::RCLICK, NMMOUSE;
+		/// [`NM_RCLICK`](https://learn.microsoft.com/en-us/windows/win32/controls/nm-rclick-toolbar)
+		/// notification.
+	}
+
+	pub_fn_nfy_withparm_boolret! { nm_r_dbl_clk, co::NM::RDBLCLK, NMMOUSE;
+		/// [`NM_RDBLCLK`](https://learn.microsoft.com/en-us/windows/win32/controls/nm-rdblclk-toolbar)
+		/// notification.
+	}
+
+	pub_fn_nfy_noparm_noret! { nm_released_capture, co::NM::RELEASEDCAPTURE;
+		/// [`NM_RELEASEDCAPTURE`](https://learn.microsoft.com/en-us/windows/win32/controls/nm-releasedcapture-toolbar-)
+		/// notification.
+	}
+
+	pub_fn_nfy_noparm_noret! { tbn_begin_adjust, co::TBN::BEGINADJUST;
+		/// [`TBN_BEGINADJUST`](https://learn.microsoft.com/en-us/windows/win32/controls/tbn-beginadjust)
+		/// notification.
+	}
+
+	pub_fn_nfy_withparm_noret! { tbn_begin_drag, co::TBN::BEGINDRAG, NMTOOLBAR;
+		/// [`TBN_BEGINDRAG`](https://learn.microsoft.com/en-us/windows/win32/controls/tbn-begindrag)
+		/// notification.
+	}
+
+	pub_fn_nfy_withparm_noret! { tbn_deleting_button, co::TBN::DELETINGBUTTON, NMTOOLBAR;
+		/// [`TBN_DELETINGBUTTON`](https://learn.microsoft.com/en-us/windows/win32/controls/tbn-deletingbutton)
+		/// notification.
+	}
+
+	pub_fn_nfy_withparm_noret! { tbn_drag_out, co::TBN::DRAGOUT, NMTOOLBAR;
+		/// [`TBN_DRAGOUT`](https://learn.microsoft.com/en-us/windows/win32/controls/tbn-dragout)
+		/// notification.
+	}
+
+	/// [`TBN_DROPDOWN`](https://learn.microsoft.com/en-us/windows/win32/controls/tbn-dropdown)
+	/// notification.
+	///
+	/// # Examples
+	///
+	/// Displaying a popup menu below the dropdown button:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// let toolbar: gui::Toolbar;
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let toolbar = gui::Toolbar::new(&wnd, gui::ToolbarOpts::default());
+	/// let hmenu: w::HMENU; // initialized somewhere
+	/// # let hmenu = w::HMENU::NULL;
+	///
+	/// let wnd2 = wnd.clone();
+	/// let toolbar2 = toolbar.clone();
+	/// toolbar.on().tbn_drop_down(
+	///     move |p: &w::NMTOOLBAR| -> w::AnyResult<co::TBDDRET> {
+	///         let pos = w::POINT::new(p.rcButton.left, p.rcButton.bottom);
+	///         hmenu.track_popup_menu_at_point(pos, wnd2.hwnd(), toolbar2.hwnd())?;
+	///         Ok(co::TBDDRET::DEFAULT)
+	///     },
+	/// );
+	/// ```
+	pub fn tbn_drop_down<F>(&self, func: F) -> &Self
+	where
+		F: Fn(&NMTOOLBAR) -> AnyResult<co::TBDDRET> + 'static,
+	{
+		self.0.wm_notify(co::TBN::DROPDOWN, move |p| {
+			Ok(func(unsafe { p.cast_nmhdr::<NMTOOLBAR>() })?.raw() as _)
+		});
+		self
+	}
+
+	pub_fn_nfy_noparm_noret! { tbn_end_adjust, co::TBN::ENDADJUST;
+		/// [`TBN_ENDADJUST`](https://learn.microsoft.com/en-us/windows/win32/controls/tbn-endadjust)
+		/// notification.
+	}
+
+	pub_fn_nfy_withparm_noret! { tbn_end_drag, co::TBN::ENDDRAG, NMTOOLBAR;
+		/// [`TBN_ENDDRAG`](https://learn.microsoft.com/en-us/windows/win32/controls/tbn-enddrag)
+		/// notification.
+	}
+
+	pub_fn_nfy_withmutparm_noret! { tbn_get_info_tip, co::TBN::GETINFOTIP, NMTBGETINFOTIP;
+		/// [`TBN_GETINFOTIP`](https://learn.microsoft.com/en-us/windows/win32/controls/tbn-getinfotip)
+		/// notification.
+	}
+
+	pub_fn_nfy_withparm_boolret! { tbn_hot_item_change, co::TBN::HOTITEMCHANGE, NMTBHOTITEM;
+		/// [`TBN_HOTITEMCHANGE`](https://learn.microsoft.com/en-us/windows/win32/controls/tbn-hotitemchange)
+		/// notification.
+		///
+		/// Return `true` to prevent the hot item from changing.
+	}
+
+	pub_fn_nfy_withparm_boolret! { tbn_query_delete, co::TBN::QUERYDELETE, NMTOOLBAR;
+		/// [`TBN_QUERYDELETE`](https://learn.microsoft.com/en-us/windows/win32/controls/tbn-querydelete)
+		/// notification.
+	}
+
+	pub_fn_nfy_withparm_boolret! { tbn_query_insert, co::TBN::QUERYINSERT, NMTOOLBAR;
+		/// [`TBN_QUERYINSERT`](https://learn.microsoft.com/en-us/windows/win32/controls/tbn-queryinsert)
+		/// notification.
+	}
+
+	pub_fn_nfy_noparm_noret! { tbn_reset, co::TBN::RESET;
+		/// [`TBN_RESET`](https://learn.microsoft.com/en-us/windows/win32/controls/tbn-reset)
+		/// notification.
+	}
+
+	pub_fn_nfy_noparm_noret! { tbn_toolbar_change, co::TBN::TOOLBARCHANGE;
+		/// [`TBN_TOOLBARCHANGE`](https://learn.microsoft.com/en-us/windows/win32/controls/tbn-toolbarchange)
+		/// notification.
+	}
+}
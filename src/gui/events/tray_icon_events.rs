@@ -0,0 +1,126 @@
+use std::cell::UnsafeCell;
+
+use crate::co;
+use crate::decl::*;
+
+struct StorageTray {
+	id: u32, // NIN or WM value
+	fun: Box<dyn Fn(POINT) -> AnyResult<()>>,
+}
+
+/// Exposes tray icon events.
+///
+/// The events are sent by the notification area to the parent window, which
+/// forwards them to the icon.
+///
+/// You cannot directly instantiate this object, it is created internally by the
+/// [`TrayIcon`](crate::gui::TrayIcon).
+///
+/// **Note:** To use this struct, enable the `shell`
+/// [Cargo feature](https://doc.rust-lang.org/cargo/reference/features.html#the-features-section).
+pub struct TrayIconEvents {
+	evts: UnsafeCell<Vec<StorageTray>>,
+}
+
+impl TrayIconEvents {
+	#[must_use]
+	pub(in crate::gui) const fn new() -> Self {
+		Self { evts: UnsafeCell::new(Vec::new()) }
+	}
+
+	pub(in crate::gui) fn process(&self, event: u32, pos: POINT) -> AnyResult<()> {
+		let evts = unsafe { &*self.evts.get() };
+		for obj in evts.iter().filter(|obj| obj.id == event) {
+			(obj.fun)(pos)?; // stop on error
+		}
+		Ok(())
+	}
+
+	fn add<F>(&self, event: u32, func: F) -> &Self
+	where
+		F: Fn(POINT) -> AnyResult<()> + 'static,
+	{
+		unsafe { &mut *self.evts.get() }.push(StorageTray { id: event, fun: Box::new(func) });
+		self
+	}
+
+	/// [`NIN_SELECT`](https://learn.microsoft.com/en-us/windows/win32/api/shellapi/nf-shellapi-shell_notifyiconw)
+	/// and `NIN_KEYSELECT` events, sent when the icon is clicked with the left
+	/// mouse button, or selected with the keyboard.
+	///
+	/// The handler receives the screen coordinates of the event.
+	pub fn click<F>(&self, func: F) -> &Self
+	where
+		F: Fn(POINT) -> AnyResult<()> + 'static,
+	{
+		let func = std::rc::Rc::new(func);
+		self.add(co::NIN::SELECT.raw(), {
+			let func = func.clone();
+			move |pt| func(pt)
+		});
+		self.add(co::NIN::KEYSELECT.raw(), move |pt| func(pt))
+	}
+
+	/// `WM_LBUTTONDBLCLK` event, sent when the icon is double-clicked with the
+	/// left mouse button.
+	///
+	/// Note that a [`click`](crate::gui::events::TrayIconEvents::click) event
+	/// is also sent for the first click.
+	///
+	/// The handler receives the screen coordinates of the event.
+	pub fn dbl_click<F>(&self, func: F) -> &Self
+	where
+		F: Fn(POINT) -> AnyResult<()> + 'static,
+	{
+		self.add(co::WM::LBUTTONDBLCLK.raw(), func)
+	}
+
+	/// `WM_CONTEXTMENU` event, sent when the icon is clicked with the right
+	/// mouse button, or when the context menu key is pressed.
+	///
+	/// The handler receives the screen coordinates where the menu should be
+	/// shown, which can be passed to
+	/// [`TrayIcon::track_menu`](crate::gui::TrayIcon::track_menu).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, gui};
+	///
+	/// let tray: gui::TrayIcon; // initialized somewhere
+	/// let hmenu: w::HMENU;
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let tray = gui::TrayIcon::new(&wnd, gui::TrayIconOpts::default());
+	/// # let hmenu = w::HMENU::NULL;
+	///
+	/// let tray2 = tray.clone();
+	/// tray.on().context_menu(move |pos| {
+	///     tray2.track_menu(&hmenu, pos)?;
+	///     Ok(())
+	/// });
+	/// ```
+	pub fn context_menu<F>(&self, func: F) -> &Self
+	where
+		F: Fn(POINT) -> AnyResult<()> + 'static,
+	{
+		self.add(co::WM::CONTEXTMENU.raw(), func)
+	}
+
+	/// `NIN_BALLOONUSERCLICK` event, sent when the balloon notification is
+	/// clicked.
+	pub fn balloon_click<F>(&self, func: F) -> &Self
+	where
+		F: Fn() -> AnyResult<()> + 'static,
+	{
+		self.add(co::NIN::BALLOONUSERCLICK.raw(), move |_| func())
+	}
+
+	/// `NIN_BALLOONTIMEOUT` event, sent when the balloon notification is
+	/// dismissed by the user or because of a timeout.
+	pub fn balloon_timeout<F>(&self, func: F) -> &Self
+	where
+		F: Fn() -> AnyResult<()> + 'static,
+	{
+		self.add(co::NIN::BALLOONTIMEOUT.raw(), move |_| func())
+	}
+}
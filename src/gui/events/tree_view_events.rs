@@ -19,6 +19,16 @@ impl TreeViewEvents {
 		Self(BaseCtrlEvents::new(parent, ctrl_id))
 	}
 
+	pub_fn_nfy_withparm_noret! { tvn_begin_drag, co::TVN::BEGINDRAG, NMTREEVIEW;
+		/// [`TVN_BEGINDRAG`](https://learn.microsoft.com/en-us/windows/win32/controls/tvn-begindrag)
+		/// notification.
+	}
+
+	pub_fn_nfy_withparm_noret! { tvn_begin_r_drag, co::TVN::BEGINRDRAG, NMTREEVIEW;
+		/// [`TVN_BEGINRDRAG`](https://learn.microsoft.com/en-us/windows/win32/controls/tvn-beginrdrag)
+		/// notification.
+	}
+
 	pub_fn_nfy_withparm_noret! { tvn_delete_item, co::TVN::DELETEITEM, NMTREEVIEW;
 		/// [`TVN_DELETEITEM`](https://learn.microsoft.com/en-us/windows/win32/controls/tvn-deleteitem)
 		/// notification.
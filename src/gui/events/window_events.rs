@@ -372,6 +372,14 @@ impl WindowEvents {
 		/// message.
 	}
 
+	pub_fn_wm_noparm_noret! { wm_clipboard_update, co::WM::CLIPBOARDUPDATE;
+		/// [`WM_CLIPBOARDUPDATE`](https://learn.microsoft.com/en-us/windows/win32/dataxchg/wm-clipboardupdate)
+		/// message.
+		///
+		/// Sent only after the window is registered with
+		/// [`HWND::AddClipboardFormatListener`](crate::prelude::user_Hwnd::AddClipboardFormatListener).
+	}
+
 	pub_fn_wm_noparm_noret! { wm_close, co::WM::CLOSE;
 		/// [`WM_CLOSE`](https://learn.microsoft.com/en-us/windows/win32/winmsg/wm-close)
 		/// message.
@@ -481,6 +489,11 @@ impl WindowEvents {
 		/// ```
 	}
 
+	pub_fn_wm_noparm_noret! { wm_destroy_clipboard, co::WM::DESTROYCLIPBOARD;
+		/// [`WM_DESTROYCLIPBOARD`](https://learn.microsoft.com/en-us/windows/win32/dataxchg/wm-destroyclipboard)
+		/// message.
+	}
+
 	pub_fn_wm_withparm_noret! { wm_device_change, co::WM::DEVICECHANGE, wm::DeviceChange;
 		/// [`WM_DEVICECHANGE`](https://learn.microsoft.com/en-us/windows/win32/devio/wm-devicechange)
 		/// message.
@@ -491,6 +504,16 @@ impl WindowEvents {
 		/// message.
 	}
 
+	pub_fn_wm_withparm_noret! { wm_dpi_changed, co::WM::DPICHANGED, wm::DpiChanged;
+		/// [`WM_DPICHANGED`](https://learn.microsoft.com/en-us/windows/win32/hidpi/wm-dpichanged)
+		/// message.
+		///
+		/// Before this event is called, the window is already moved to the
+		/// suggested rectangle, and its child controls are already rescaled,
+		/// along with their fonts and image lists. Use this event to rescale
+		/// any other DPI-dependent resources.
+	}
+
 	#[cfg(feature = "shell")]
 	pub_fn_wm_withparm_noret! { wm_drop_files, co::WM::DROPFILES, wm::DropFiles;
 		/// [`WM_DROPFILES`](https://learn.microsoft.com/en-us/windows/win32/shell/wm-dropfiles)
@@ -908,6 +931,42 @@ impl WindowEvents {
 		/// [`WM_RBUTTONUP`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-rbuttonup)
 	}
 
+	pub_fn_wm_noparm_noret! { wm_render_all_formats, co::WM::RENDERALLFORMATS;
+		/// [`WM_RENDERALLFORMATS`](https://learn.microsoft.com/en-us/windows/win32/dataxchg/wm-renderallformats)
+		/// message.
+		///
+		/// Unlike [`wm_render_format`](crate::gui::events::WindowEvents::wm_render_format),
+		/// the clipboard must be opened before rendering the formats.
+	}
+
+	pub_fn_wm_withparm_noret! { wm_render_format, co::WM::RENDERFORMAT, wm::RenderFormat;
+		/// [`WM_RENDERFORMAT`](https://learn.microsoft.com/en-us/windows/win32/dataxchg/wm-renderformat)
+		/// message, sent when a format set with
+		/// [`HCLIPBOARD::SetClipboardDataDelayed`](crate::prelude::user_Hclipboard::SetClipboardDataDelayed)
+		/// is requested.
+		///
+		/// The clipboard is already open, so the data must be rendered directly
+		/// with [`HCLIPBOARD::NULL`](crate::HCLIPBOARD::NULL).
+		///
+		/// # Examples
+		///
+		/// ```no_run
+		/// use winsafe::{self as w, prelude::*, co, gui, msg};
+		///
+		/// let wnd: gui::WindowMain; // initialized somewhere
+		/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+		///
+		/// wnd.on().wm_render_format(
+		///     move |p: msg::wm::RenderFormat| -> w::AnyResult<()> {
+		///         if p.format == co::CF::UNICODETEXT {
+		///             w::HCLIPBOARD::NULL.set_text("rendered on demand")?;
+		///         }
+		///         Ok(())
+		///     },
+		/// );
+		/// ```
+	}
+
 	pub_fn_wm_withparm_boolret! { wm_set_cursor, co::WM::SETCURSOR, wm::SetCursor;
 		/// [`WM_SETCURSOR`](https://learn.microsoft.com/en-us/windows/win32/menurc/wm-setcursor)
 		/// message.
@@ -945,6 +1004,11 @@ impl WindowEvents {
 		/// message.
 	}
 
+	pub_fn_wm_withparm_noret! { wm_setting_change, co::WM::SETTINGCHANGE, wm::SettingChange;
+		/// [`WM_SETTINGCHANGE`](https://learn.microsoft.com/en-us/windows/win32/winmsg/wm-settingchange)
+		/// message.
+	}
+
 	pub_fn_wm_withparm_noret! { wm_show_window, co::WM::SHOWWINDOW, wm::ShowWindow;
 		/// [`WM_SHOWWINDOW`](https://learn.microsoft.com/en-us/windows/win32/winmsg/wm-showwindow)
 		/// message.
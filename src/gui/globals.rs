@@ -56,47 +56,57 @@ pub(in crate::gui) mod ui_font {
 	use crate::co;
 	use crate::decl::*;
 	use crate::guard::*;
+	use crate::gui::{hwnd_dpi, system_dpi};
 	use crate::msg::*;
 	use crate::prelude::*;
 
-	/// Global UI font object.
-	static mut UI_HFONT: Option<DeleteObjectGuard<HFONT>> = None;
+	/// Global UI font objects, one for each DPI.
+	static mut UI_HFONTS: Vec<(u32, DeleteObjectGuard<HFONT>)> = Vec::new();
 
-	// Returns the global UI font, creating it of not yet.
+	// Returns the global UI font for the system DPI, creating it of not yet.
 	pub(in crate::gui) fn get() -> SysResult<HFONT> {
-		Ok(unsafe {
-			match &*&raw const UI_HFONT {
-				None => {
-					// not created yet
-					let mut ncm = NONCLIENTMETRICS::default();
-					SystemParametersInfo(
-						co::SPI::GETNONCLIENTMETRICS,
-						std::mem::size_of::<NONCLIENTMETRICS>() as _,
-						&mut ncm,
-						co::SPIF::NoValue,
-					)?;
-					let font = HFONT::CreateFontIndirect(&ncm.lfMenuFont)?;
-					let ret_font = font.raw_copy();
-					UI_HFONT = Some(font);
-					ret_font
-				},
-				Some(font) => font.raw_copy(),
-			}
-		})
+		get_for_dpi(system_dpi())
 	}
 
-	/// Sets the global UI font on the given window.
+	// Returns the global UI font for the given DPI, creating it of not yet.
+	pub(in crate::gui) fn get_for_dpi(dpi: u32) -> SysResult<HFONT> {
+		let fonts = unsafe { (&raw mut UI_HFONTS).as_mut() }.unwrap(); // never null
+		if let Some((_, font)) = fonts.iter().find(|(font_dpi, _)| *font_dpi == dpi) {
+			return Ok(unsafe { font.raw_copy() });
+		}
+
+		// Not created yet. System metrics are given in system DPI.
+		let mut ncm = NONCLIENTMETRICS::default();
+		unsafe {
+			SystemParametersInfo(
+				co::SPI::GETNONCLIENTMETRICS,
+				std::mem::size_of::<NONCLIENTMETRICS>() as _,
+				&mut ncm,
+				co::SPIF::NoValue,
+			)?;
+		}
+		ncm.lfMenuFont.lfHeight = MulDiv(ncm.lfMenuFont.lfHeight, dpi as _, system_dpi() as _);
+		let font = HFONT::CreateFontIndirect(&ncm.lfMenuFont)?;
+		let ret_font = unsafe { font.raw_copy() };
+		fonts.push((dpi, font));
+		Ok(ret_font)
+	}
+
+	/// Sets the global UI font on the given window, according to its DPI.
 	pub(in crate::gui) fn set(hwnd: &HWND) -> SysResult<()> {
 		unsafe {
-			hwnd.SendMessage(wm::SetFont { hfont: get()?, redraw: true });
+			hwnd.SendMessage(wm::SetFont {
+				hfont: get_for_dpi(hwnd_dpi(hwnd))?,
+				redraw: true,
+			});
 		}
 		Ok(())
 	}
 
-	/// Frees the global UI font object.
+	/// Frees the global UI font objects.
 	pub(in crate::gui) fn delete() {
 		unsafe {
-			UI_HFONT = None; // https://users.rust-lang.org/t/why-drop-trait-not-called-when-use-global-static
+			(&raw mut UI_HFONTS).as_mut().unwrap().clear(); // https://users.rust-lang.org/t/why-drop-trait-not-called-when-use-global-static
 		}
 	}
 }
@@ -126,6 +136,39 @@ pub(in crate::gui) mod auto_id {
 	}
 }
 
+pub(in crate::gui) mod msftedit {
+	use crate::decl::*;
+	use crate::prelude::*;
+
+	/// Tells whether Msftedit.dll was already loaded.
+	static mut LOADED: bool = false;
+
+	/// Loads Msftedit.dll, which registers the rich edit window class, if not
+	/// yet. The library is kept loaded until the program exits.
+	pub(in crate::gui) fn load() -> SysResult<()> {
+		unsafe {
+			if !LOADED {
+				let _ = HINSTANCE::LoadLibrary("Msftedit.dll")?.leak();
+				LOADED = true;
+			}
+		}
+		Ok(())
+	}
+}
+
+pub(in crate::gui) mod comctl_classes {
+	use crate::co;
+	use crate::decl::*;
+
+	/// Registers the given common control window classes, which are not
+	/// registered by [`InitCommonControls`](crate::InitCommonControls).
+	pub(in crate::gui) fn register(icc: co::ICC) -> SysResult<()> {
+		let mut icce = INITCOMMONCONTROLSEX::default();
+		icce.icc = icc;
+		InitCommonControlsEx(&icce)
+	}
+}
+
 pub(in crate::gui) mod text_calc {
 	use crate::co;
 	use crate::decl::*;
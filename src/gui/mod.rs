@@ -1,11 +1,16 @@
 #![doc = include_str!("gui.md")]
 #![cfg(feature = "gui")]
 
+#[cfg(all(feature = "advapi", feature = "dwm"))]
+mod dark_mode;
 mod dpi;
+mod drop_target;
 mod globals;
 mod iterators;
 mod msg_error;
 mod native_controls;
+#[cfg(feature = "shell")]
+mod tray_icon;
 mod windows;
 
 pub mod events;
@@ -18,8 +23,13 @@ pub(in crate::gui) mod privs {
 	pub(in crate::gui) use super::windows::privs::*;
 }
 
+#[cfg(all(feature = "advapi", feature = "dwm"))]
+pub use dark_mode::{DarkMode, Theme};
 pub use dpi::*;
+pub use drop_target::DropTarget;
 pub use msg_error::MsgError;
 pub use native_controls::collections;
 pub use native_controls::decl::*;
+#[cfg(feature = "shell")]
+pub use tray_icon::{TrayIcon, TrayIconOpts};
 pub use windows::decl::*;
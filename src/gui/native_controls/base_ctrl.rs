@@ -76,7 +76,11 @@ impl BaseCtrl {
 				pos,
 				size,
 				Some(hparent),
-				IdMenu::Id(self.ctrl_id),
+				if style.has(co::WS::POPUP) {
+					IdMenu::None // popups, like tooltips, cannot have an ID
+				} else {
+					IdMenu::Id(self.ctrl_id)
+				},
 				&hparent.hinstance(),
 				None,
 			)?
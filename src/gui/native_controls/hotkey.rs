@@ -0,0 +1,198 @@
+use std::any::Any;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::gui::{events::*, privs::*, *};
+use crate::msg::*;
+use crate::prelude::*;
+
+struct HotkeyObj {
+	base: BaseCtrl,
+	events: HotkeyEvents,
+	_pin: PhantomPinned,
+}
+
+native_ctrl! { Hotkey: HotkeyObj => HotkeyEvents;
+	/// Native
+	/// [hot key](https://learn.microsoft.com/en-us/windows/win32/controls/hot-key-controls)
+	/// control, which lets the user enter a combination of keystrokes.
+}
+
+impl Hotkey {
+	/// Instantiates a new `Hotkey` object, to be created on the parent window
+	/// with
+	/// [`HWND::CreateWindowEx`](crate::prelude::user_Hwnd::CreateWindowEx).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window was already created – that is, you cannot
+	/// dynamically create a `Hotkey` in an event closure.
+	#[must_use]
+	pub fn new(parent: &(impl GuiParent + 'static), opts: HotkeyOpts) -> Self {
+		let ctrl_id = auto_id::set_if_zero(opts.ctrl_id);
+		let new_self = Self(Arc::pin(HotkeyObj {
+			base: BaseCtrl::new(ctrl_id),
+			events: HotkeyEvents::new(parent, ctrl_id),
+			_pin: PhantomPinned,
+		}));
+
+		let self2 = new_self.clone();
+		let parent2 = parent.clone();
+		parent
+			.as_ref()
+			.before_on()
+			.wm(parent.as_ref().is_dlg().create_msg(), move |_| {
+				comctl_classes::register(co::ICC::HOTKEY_CLASS)?;
+				self2.0.base.create_window(
+					opts.window_ex_style,
+					"msctls_hotkey32",
+					None,
+					opts.window_style,
+					opts.position.into(),
+					opts.size.into(),
+					&parent2,
+				)?;
+				ui_font::set(self2.hwnd())?;
+				if let Some((invalid_combos, default_modifiers)) = opts.rules {
+					self2.set_rules(invalid_combos, default_modifiers);
+				}
+				if let Some((vkey_code, modifiers)) = opts.hotkey {
+					self2.set_hotkey(vkey_code, modifiers);
+				}
+				parent2
+					.as_ref()
+					.add_to_layout(self2.hwnd(), opts.resize_behavior)?;
+				Ok(0) // ignored
+			});
+
+		new_self
+	}
+
+	/// Instantiates a new `Hotkey` object, to be loaded from a dialog resource
+	/// with [`HWND::GetDlgItem`](crate::prelude::user_Hwnd::GetDlgItem).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent dialog was already created – that is, you cannot
+	/// dynamically create a `Hotkey` in an event closure.
+	///
+	/// Panics if the hot key window class cannot be registered.
+	#[must_use]
+	pub fn new_dlg(
+		parent: &(impl GuiParent + 'static),
+		ctrl_id: u16,
+		resize_behavior: (Horz, Vert),
+	) -> Self {
+		comctl_classes::register(co::ICC::HOTKEY_CLASS)
+			.expect("Hot key window class could not be registered.");
+
+		let new_self = Self(Arc::pin(HotkeyObj {
+			base: BaseCtrl::new(ctrl_id),
+			events: HotkeyEvents::new(parent, ctrl_id),
+			_pin: PhantomPinned,
+		}));
+
+		let self2 = new_self.clone();
+		let parent2 = parent.clone();
+		parent.as_ref().before_on().wm_init_dialog(move |_| {
+			self2.0.base.assign_dlg(&parent2)?;
+			parent2
+				.as_ref()
+				.add_to_layout(self2.hwnd(), resize_behavior)?;
+			Ok(true) // ignored
+		});
+
+		new_self
+	}
+
+	/// Retrieves the virtual key code and the modifiers of the current hot key
+	/// by sending an [`hkm::GetHotKey`](crate::msg::hkm::GetHotKey) message.
+	///
+	/// If no key was entered, the virtual key code will be zero.
+	#[must_use]
+	pub fn hotkey(&self) -> (co::VK, co::HOTKEYF) {
+		unsafe { self.hwnd().SendMessage(hkm::GetHotKey {}) }
+	}
+
+	/// Sets the virtual key code and the modifiers of the current hot key by
+	/// sending an [`hkm::SetHotKey`](crate::msg::hkm::SetHotKey) message.
+	pub fn set_hotkey(&self, vkey_code: co::VK, modifiers: co::HOTKEYF) {
+		unsafe {
+			self.hwnd()
+				.SendMessage(hkm::SetHotKey { vkey_code, modifiers });
+		}
+	}
+
+	/// Defines the invalid key combinations, and the modifiers to be used
+	/// instead when the user enters one of them, by sending an
+	/// [`hkm::SetRules`](crate::msg::hkm::SetRules) message.
+	pub fn set_rules(&self, invalid_combos: co::HKCOMB, default_modifiers: co::HOTKEYF) {
+		unsafe {
+			self.hwnd()
+				.SendMessage(hkm::SetRules { invalid_combos, default_modifiers });
+		}
+	}
+}
+
+/// Options to create a [`Hotkey`](crate::gui::Hotkey) programmatically with
+/// [`Hotkey::new`](crate::gui::Hotkey::new).
+pub struct HotkeyOpts {
+	/// Left and top position coordinates of control within parent's client
+	/// area, to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `gui::dpi(0, 0)`.
+	pub position: (i32, i32),
+	/// Width and height of control to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `gui::dpi(120, 23)`.
+	pub size: (i32, i32),
+	/// Window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS::CHILD | WS::GROUP | WS::TABSTOP | WS::VISIBLE`.
+	pub window_style: co::WS,
+	/// Extended window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS_EX::LEFT | WS_EX::CLIENTEDGE`.
+	pub window_ex_style: co::WS_EX,
+
+	/// The control ID.
+	///
+	/// Defaults to an auto-generated ID.
+	pub ctrl_id: u16,
+	/// Horizontal and vertical behavior of the control when the parent window
+	/// is resized.
+	///
+	/// Defaults to `(gui::Horz::None, gui::Vert::None)`.
+	pub resize_behavior: (Horz, Vert),
+
+	/// Initial virtual key code and modifiers.
+	///
+	/// Defaults to `None`.
+	pub hotkey: Option<(co::VK, co::HOTKEYF)>,
+	/// Invalid key combinations, and the modifiers to be used instead.
+	///
+	/// Defaults to `None`.
+	pub rules: Option<(co::HKCOMB, co::HOTKEYF)>,
+}
+
+impl Default for HotkeyOpts {
+	fn default() -> Self {
+		Self {
+			position: dpi(0, 0),
+			size: dpi(120, 23),
+			window_style: co::WS::CHILD | co::WS::GROUP | co::WS::TABSTOP | co::WS::VISIBLE,
+			window_ex_style: co::WS_EX::LEFT | co::WS_EX::CLIENTEDGE,
+			ctrl_id: 0,
+			resize_behavior: (Horz::None, Vert::None),
+			hotkey: None,
+			rules: None,
+		}
+	}
+}
@@ -0,0 +1,238 @@
+use std::any::Any;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::gui::{events::*, privs::*, *};
+use crate::msg::*;
+use crate::prelude::*;
+
+struct IpAddressObj {
+	base: BaseCtrl,
+	events: IpAddressEvents,
+	_pin: PhantomPinned,
+}
+
+native_ctrl! { IpAddress: IpAddressObj => IpAddressEvents;
+	/// Native
+	/// [IP address](https://learn.microsoft.com/en-us/windows/win32/controls/ip-address-controls)
+	/// control, which lets the user enter an IPv4 address in four fields.
+}
+
+impl IpAddress {
+	/// Instantiates a new `IpAddress` object, to be created on the parent
+	/// window with
+	/// [`HWND::CreateWindowEx`](crate::prelude::user_Hwnd::CreateWindowEx).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window was already created – that is, you cannot
+	/// dynamically create an `IpAddress` in an event closure.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, gui};
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	///
+	/// let ip = gui::IpAddress::new(
+	///     &wnd,
+	///     gui::IpAddressOpts {
+	///         address: Some([192, 168, 0, 1]),
+	///         ..Default::default()
+	///     },
+	/// );
+	///
+	/// ip.on().ipn_field_changed(|p: &mut w::NMIPADDRESS| {
+	///     println!("Field {} is now {}", p.iField, p.iValue);
+	///     Ok(())
+	/// });
+	/// ```
+	#[must_use]
+	pub fn new(parent: &(impl GuiParent + 'static), opts: IpAddressOpts) -> Self {
+		let ctrl_id = auto_id::set_if_zero(opts.ctrl_id);
+		let new_self = Self(Arc::pin(IpAddressObj {
+			base: BaseCtrl::new(ctrl_id),
+			events: IpAddressEvents::new(parent, ctrl_id),
+			_pin: PhantomPinned,
+		}));
+
+		let self2 = new_self.clone();
+		let parent2 = parent.clone();
+		parent
+			.as_ref()
+			.before_on()
+			.wm(parent.as_ref().is_dlg().create_msg(), move |_| {
+				comctl_classes::register(co::ICC::INTERNET_CLASSES)?;
+				self2.0.base.create_window(
+					opts.window_ex_style,
+					"SysIPAddress32",
+					None,
+					opts.window_style,
+					opts.position.into(),
+					opts.size.into(),
+					&parent2,
+				)?;
+				ui_font::set(self2.hwnd())?;
+				if let Some(address) = opts.address {
+					self2.set_address(address);
+				}
+				parent2
+					.as_ref()
+					.add_to_layout(self2.hwnd(), opts.resize_behavior)?;
+				Ok(0) // ignored
+			});
+
+		new_self
+	}
+
+	/// Instantiates a new `IpAddress` object, to be loaded from a dialog
+	/// resource with
+	/// [`HWND::GetDlgItem`](crate::prelude::user_Hwnd::GetDlgItem).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent dialog was already created – that is, you cannot
+	/// dynamically create an `IpAddress` in an event closure.
+	///
+	/// Panics if the IP address window class cannot be registered.
+	#[must_use]
+	pub fn new_dlg(
+		parent: &(impl GuiParent + 'static),
+		ctrl_id: u16,
+		resize_behavior: (Horz, Vert),
+	) -> Self {
+		comctl_classes::register(co::ICC::INTERNET_CLASSES)
+			.expect("IP address window class could not be registered.");
+
+		let new_self = Self(Arc::pin(IpAddressObj {
+			base: BaseCtrl::new(ctrl_id),
+			events: IpAddressEvents::new(parent, ctrl_id),
+			_pin: PhantomPinned,
+		}));
+
+		let self2 = new_self.clone();
+		let parent2 = parent.clone();
+		parent.as_ref().before_on().wm_init_dialog(move |_| {
+			self2.0.base.assign_dlg(&parent2)?;
+			parent2
+				.as_ref()
+				.add_to_layout(self2.hwnd(), resize_behavior)?;
+			Ok(true) // ignored
+		});
+
+		new_self
+	}
+
+	/// Retrieves the four fields of the address, from left to right, by
+	/// sending an [`ipm::GetAddress`](crate::msg::ipm::GetAddress) message.
+	///
+	/// Blank fields are returned as zero; use
+	/// [`is_blank`](crate::gui::IpAddress::is_blank) to check whether all of
+	/// them are blank.
+	#[must_use]
+	pub fn address(&self) -> [u8; 4] {
+		let mut packed = 0u32;
+		unsafe {
+			self.hwnd()
+				.SendMessage(ipm::GetAddress { address: &mut packed });
+		}
+		packed.to_be_bytes()
+	}
+
+	/// Clears all the fields by sending an
+	/// [`ipm::ClearAddress`](crate::msg::ipm::ClearAddress) message.
+	pub fn clear(&self) {
+		unsafe {
+			self.hwnd().SendMessage(ipm::ClearAddress {});
+		}
+	}
+
+	/// Sets the focus to the given zero-based field by sending an
+	/// [`ipm::SetFocus`](crate::msg::ipm::SetFocus) message.
+	pub fn focus_field(&self, field: u8) {
+		unsafe {
+			self.hwnd().SendMessage(ipm::SetFocus { field });
+		}
+	}
+
+	/// Tells whether all the fields are blank by sending an
+	/// [`ipm::IsBlank`](crate::msg::ipm::IsBlank) message.
+	#[must_use]
+	pub fn is_blank(&self) -> bool {
+		unsafe { self.hwnd().SendMessage(ipm::IsBlank {}) }
+	}
+
+	/// Sets the four fields of the address, from left to right, by sending an
+	/// [`ipm::SetAddress`](crate::msg::ipm::SetAddress) message.
+	pub fn set_address(&self, address: [u8; 4]) {
+		unsafe {
+			self.hwnd().SendMessage(ipm::SetAddress { address });
+		}
+	}
+
+	/// Sets the minimum and maximum values of the given zero-based field by
+	/// sending an [`ipm::SetRange`](crate::msg::ipm::SetRange) message.
+	pub fn set_field_range(&self, field: u8, min: u8, max: u8) -> SysResult<()> {
+		unsafe { self.hwnd().SendMessage(ipm::SetRange { field, min, max }) }
+	}
+}
+
+/// Options to create an [`IpAddress`](crate::gui::IpAddress) programmatically
+/// with [`IpAddress::new`](crate::gui::IpAddress::new).
+pub struct IpAddressOpts {
+	/// Left and top position coordinates of control within parent's client
+	/// area, to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `gui::dpi(0, 0)`.
+	pub position: (i32, i32),
+	/// Width and height of control to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `gui::dpi(130, 23)`.
+	pub size: (i32, i32),
+	/// Window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS::CHILD | WS::GROUP | WS::TABSTOP | WS::VISIBLE`.
+	pub window_style: co::WS,
+	/// Extended window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS_EX::LEFT`.
+	pub window_ex_style: co::WS_EX,
+
+	/// The control ID.
+	///
+	/// Defaults to an auto-generated ID.
+	pub ctrl_id: u16,
+	/// Horizontal and vertical behavior of the control when the parent window
+	/// is resized.
+	///
+	/// Defaults to `(gui::Horz::None, gui::Vert::None)`.
+	pub resize_behavior: (Horz, Vert),
+
+	/// Initial address, from left to right.
+	///
+	/// Defaults to `None`, which leaves all the fields blank.
+	pub address: Option<[u8; 4]>,
+}
+
+impl Default for IpAddressOpts {
+	fn default() -> Self {
+		Self {
+			position: dpi(0, 0),
+			size: dpi(130, 23),
+			window_style: co::WS::CHILD | co::WS::GROUP | co::WS::TABSTOP | co::WS::VISIBLE,
+			window_ex_style: co::WS_EX::LEFT,
+			ctrl_id: 0,
+			resize_behavior: (Horz::None, Vert::None),
+			address: None,
+		}
+	}
+}
@@ -6,6 +6,7 @@ use std::rc::Rc;
 use std::sync::Arc;
 
 use crate::co;
+use crate::comctl::privs::*;
 use crate::decl::*;
 use crate::guard::*;
 use crate::gui::{collections::*, events::*, privs::*, *};
@@ -17,6 +18,7 @@ struct ListViewObj<T> {
 	events: ListViewEvents,
 	context_menu: Option<DestroyMenuGuard>, // the context menu itself is the 1st submenu
 	header: UnsafeCell<Option<Header>>, // if doesn't exist, will be set to None on WM_CREATE and WM_INITDIALOG
+	data_source: UnsafeCell<Option<Rc<dyn ListViewDataSource>>>, // only for LVS::OWNERDATA
 	_pin: PhantomPinned,
 	_data: PhantomData<T>,
 }
@@ -56,6 +58,7 @@ impl<T> ListView<T> {
 			events: ListViewEvents::new(parent, ctrl_id),
 			context_menu,
 			header: UnsafeCell::new(Some(Header::from_list_view(parent))), // initially does exist
+			data_source: UnsafeCell::new(None),
 			_pin: PhantomPinned,
 			_data: PhantomData,
 		}));
@@ -88,6 +91,7 @@ impl<T> ListView<T> {
 				for (text, cx) in opts.columns.iter() {
 					self2.cols().add(text, *cx)?;
 				}
+				self2.refresh_data()?; // if data source was set before creation
 				parent2
 					.as_ref()
 					.add_to_layout(self2.hwnd(), opts.resize_behavior)?;
@@ -133,6 +137,7 @@ impl<T> ListView<T> {
 					.expect("Invalid ListView context menu ID")
 			}),
 			header: UnsafeCell::new(Some(Header::from_list_view(parent))), // initially does exist
+			data_source: UnsafeCell::new(None),
 			_pin: PhantomPinned,
 			_data: PhantomData,
 		}));
@@ -151,6 +156,7 @@ impl<T> ListView<T> {
 			{
 				*unsafe { &mut *self2.0.header.get() } = None; // no header, delete it
 			}
+			self2.refresh_data()?; // if data source was set before creation
 			parent2
 				.as_ref()
 				.add_to_layout(self2.hwnd(), resize_behavior)?;
@@ -210,6 +216,59 @@ impl<T> ListView<T> {
 				Ok(0) // ignored
 			});
 
+		let self2 = self.clone();
+		parent
+			.as_ref()
+			.before_on()
+			.wm_notify(self.ctrl_id(), co::LVN::GETDISPINFO, move |p| {
+				if let Some(source) = self2.data_source() {
+					let item = &mut unsafe { p.cast_nmhdr_mut::<NMLVDISPINFO>() }.item;
+					let row = item.iItem as u32;
+
+					if item.mask.has(co::LVIF::TEXT) {
+						let (psz, cch) = item.raw_pszText();
+						if !psz.is_null() && cch > 0 {
+							let buf = unsafe { std::slice::from_raw_parts_mut(psz, cch as _) };
+							WString::from_str(source.text(row, item.iSubItem as _))
+								.copy_to_slice(buf);
+						}
+					}
+					if item.mask.has(co::LVIF::IMAGE) {
+						item.iImage = source.image(row).map_or(I_IMAGENONE as _, |idx| idx as _);
+					}
+					if item.mask.has(co::LVIF::STATE) {
+						item.state = source.state(row) & item.stateMask;
+					}
+				}
+				Ok(0) // ignored
+			});
+
+		let self2 = self.clone();
+		parent
+			.as_ref()
+			.before_on()
+			.wm_notify(self.ctrl_id(), co::LVN::ODCACHEHINT, move |p| {
+				if let Some(source) = self2.data_source() {
+					let nmlvch = unsafe { p.cast_nmhdr::<NMLVCACHEHINT>() };
+					source.cache_hint(nmlvch.iFrom as _, nmlvch.iTo as _);
+				}
+				Ok(0) // ignored
+			});
+
+		// The return value is meaningful, so this one can be overriden by the user.
+		let self2 = self.clone();
+		self.on().lvn_od_find_item(move |p| {
+			Ok(match (self2.data_source(), p.lvfi.psz()) {
+				(Some(source), Some(text)) if p.lvfi.flags.has(co::LVFI::STRING) => source.find(
+					&text,
+					p.iStart.max(0) as _,
+					p.lvfi.flags.has(co::LVFI::PARTIAL),
+					p.lvfi.flags.has(co::LVFI::WRAP),
+				),
+				_ => None,
+			})
+		});
+
 		let self2 = self.clone();
 		parent
 			.as_ref()
@@ -308,6 +367,51 @@ impl<T> ListView<T> {
 		unsafe { self.hwnd().SendMessage(lvm::GetView {}) }
 	}
 
+	/// Returns the data source of a virtual list view, if any.
+	///
+	/// The data source is shared, so it stays alive even if it's replaced by
+	/// [`set_data_source`](crate::gui::ListView::set_data_source) while in use.
+	#[must_use]
+	pub fn data_source(&self) -> Option<Rc<dyn ListViewDataSource>> {
+		unsafe { &*self.0.data_source.get() }.clone()
+	}
+
+	/// Starts an OLE drag and drop operation with the given data, using the
+	/// default [`IDropSource`](crate::IDropSource) behavior. Usually called in
+	/// the [`lvn_begin_drag`](crate::gui::events::ListViewEvents::lvn_begin_drag)
+	/// event.
+	///
+	/// This method will block until the operation ends. Returns the effect
+	/// performed by the drop target, or
+	/// [`DROPEFFECT::NONE`](crate::co::DROPEFFECT::NONE) if the operation was
+	/// cancelled.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let my_list: gui::ListView; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let my_list = gui::ListView::<()>::new(&wnd, gui::ListViewOpts::default());
+	///
+	/// let my_list2 = my_list.clone();
+	/// my_list.on().lvn_begin_drag(move |p| {
+	///     let text = my_list2.items().get(p.iItem as _).text(0);
+	///     let data_obj = w::DataObject::new_impl();
+	///     data_obj.set_text(&text);
+	///     my_list2.do_drag_drop(&data_obj, co::DROPEFFECT::COPY)?;
+	///     Ok(())
+	/// });
+	/// ```
+	pub fn do_drag_drop(
+		&self,
+		data: &DataObject,
+		ok_effects: co::DROPEFFECT,
+	) -> HrResult<co::DROPEFFECT> {
+		DoDragDrop(data, &IDropSource::new_impl(), ok_effects)
+	}
+
 	/// Returns the embedded [`Header`](crate::gui::Header) of the list view, if
 	/// any.
 	///
@@ -361,12 +465,58 @@ impl<T> ListView<T> {
 		ListViewItems::new(self)
 	}
 
+	/// Informs the control of the current number of rows of the data source, if
+	/// any, by sending an [`lvm::SetItemCount`](crate::msg::lvm::SetItemCount)
+	/// message, then redraws all the visible rows.
+	///
+	/// Call this method whenever the data source changes.
+	///
+	/// Does nothing if the control is not created yet, or if it has no data
+	/// source.
+	pub fn refresh_data(&self) -> SysResult<()> {
+		if *self.hwnd() != HWND::NULL {
+			if let Some(source) = self.data_source() {
+				self.items()
+					.set_count(source.row_count(), Some(co::LVSICF::NOSCROLL))?;
+				self.hwnd().InvalidateRect(None, true)?;
+			}
+		}
+		Ok(())
+	}
+
 	/// Sets the current view by sending an
 	/// [`lvm::SetView`](crate::msg::lvm::SetView) message.
 	pub fn set_current_view(&self, view: co::LV_VIEW) -> SysResult<()> {
 		unsafe { self.hwnd().SendMessage(lvm::SetView { view }) }
 	}
 
+	/// Sets the data source of a virtual list view, replacing the previous one,
+	/// if any, and calls
+	/// [`refresh_data`](crate::gui::ListView::refresh_data).
+	///
+	/// The data source is queried with
+	/// [`LVN_GETDISPINFO`](https://learn.microsoft.com/en-us/windows/win32/controls/lvn-getdispinfo),
+	/// [`LVN_ODCACHEHINT`](https://learn.microsoft.com/en-us/windows/win32/controls/lvn-odcachehint)
+	/// and
+	/// [`LVN_ODFINDITEM`](https://learn.microsoft.com/en-us/windows/win32/controls/lvn-odfinditem)
+	/// notifications. If you handle
+	/// [`lvn_od_find_item`](crate::gui::events::ListViewEvents::lvn_od_find_item)
+	/// yourself, the data source won't be searched.
+	///
+	/// # Panics
+	///
+	/// Panics if the control was created without
+	/// [`LVS::OWNERDATA`](crate::co::LVS::OWNERDATA) style.
+	pub fn set_data_source(&self, source: impl ListViewDataSource + 'static) -> SysResult<()> {
+		if *self.hwnd() != HWND::NULL && !co::LVS::from(self.hwnd().style()).has(co::LVS::OWNERDATA)
+		{
+			panic!("Data source requires a ListView with LVS::OWNERDATA style.");
+		}
+		let prev = unsafe { (*self.0.data_source.get()).replace(Rc::new(source)) };
+		drop(prev); // dropped only after the new one is set, in case its Drop reenters
+		self.refresh_data()
+	}
+
 	/// Sets or unsets the given extended list view styles by sending an
 	/// [`lvm::SetExtendedListViewStyle`](crate::msg::lvm::SetExtendedListViewStyle)
 	/// message.
@@ -429,6 +579,10 @@ pub struct ListViewOpts {
 	/// [`LVS::SHAREIMAGELISTS`](crate::co::LVS::SHAREIMAGELISTS) style will
 	/// always be added.
 	///
+	/// For a virtual list view, add [`LVS::OWNERDATA`](crate::co::LVS::OWNERDATA)
+	/// and set a data source with
+	/// [`ListView::set_data_source`](crate::gui::ListView::set_data_source).
+	///
 	/// Defaults to `LVS::REPORT | LVS::NOSORTHEADER | LVS::SHOWSELALWAYS | LVS::SHAREIMAGELISTS`.
 	pub control_style: co::LVS,
 	/// Extended list view styles to be
@@ -8,6 +8,8 @@ mod edit;
 mod header;
 mod header_item;
 mod header_items;
+mod hotkey;
+mod ip_address;
 mod label;
 mod list_box;
 mod list_box_items;
@@ -17,15 +19,23 @@ mod list_view_cols;
 mod list_view_item;
 mod list_view_items;
 mod month_calendar;
+mod pager;
 mod progress_bar;
 mod radio_button;
 mod radio_group;
+mod rebar;
+mod rich_edit;
 mod status_bar;
 mod status_bar_part;
 mod status_bar_parts;
+mod sys_link;
 mod tab;
 mod tab_item;
 mod tab_items;
+mod toolbar;
+mod toolbar_button;
+mod toolbar_buttons;
+mod tooltip;
 mod trackbar;
 mod tree_view;
 mod tree_view_item;
@@ -44,19 +54,28 @@ pub mod decl {
 	pub use super::edit::{Edit, EditOpts};
 	pub use super::header::{Header, HeaderOpts};
 	pub use super::header_item::{HeaderArrow, HeaderItem, HeaderJustify};
+	pub use super::hotkey::{Hotkey, HotkeyOpts};
+	pub use super::ip_address::{IpAddress, IpAddressOpts};
 	pub use super::label::{Label, LabelOpts};
 	pub use super::list_box::{ListBox, ListBoxOpts};
 	pub use super::list_view::{ListView, ListViewOpts};
 	pub use super::list_view_col::ListViewCol;
 	pub use super::list_view_item::ListViewItem;
 	pub use super::month_calendar::{MonthCalendar, MonthCalendarOpts};
+	pub use super::pager::{Pager, PagerOpts};
 	pub use super::progress_bar::{ProgressBar, ProgressBarOpts};
 	pub use super::radio_button::{RadioButton, RadioButtonOpts};
 	pub use super::radio_group::RadioGroup;
+	pub use super::rebar::{Rebar, RebarOpts};
+	pub use super::rich_edit::{RichEdit, RichEditOpts};
 	pub use super::status_bar::{SbPart, StatusBar};
 	pub use super::status_bar_part::StatusBarPart;
+	pub use super::sys_link::{SysLink, SysLinkOpts};
 	pub use super::tab::{Tab, TabOpts};
 	pub use super::tab_item::TabItem;
+	pub use super::toolbar::{Toolbar, ToolbarOpts};
+	pub use super::toolbar_button::ToolbarButton;
+	pub use super::tooltip::{Tooltip, TooltipOpts};
 	pub use super::trackbar::{Trackbar, TrackbarOpts};
 	pub use super::tree_view::{TreeView, TreeViewOpts};
 	pub use super::tree_view_item::TreeViewItem;
@@ -74,5 +93,6 @@ pub mod collections {
 	pub use super::list_view_items::ListViewItems;
 	pub use super::status_bar_parts::StatusBarParts;
 	pub use super::tab_items::TabItems;
+	pub use super::toolbar_buttons::ToolbarButtons;
 	pub use super::tree_view_items::TreeViewItems;
 }
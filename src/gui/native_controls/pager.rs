@@ -0,0 +1,259 @@
+use std::any::Any;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::gui::{events::*, privs::*, *};
+use crate::msg::*;
+use crate::prelude::*;
+
+struct PagerObj {
+	base: BaseCtrl,
+	events: PagerEvents,
+	_pin: PhantomPinned,
+}
+
+native_ctrl! { Pager: PagerObj => PagerEvents;
+	/// Native
+	/// [pager](https://learn.microsoft.com/en-us/windows/win32/controls/pager-controls)
+	/// control, which scrolls a contained window that is larger than the
+	/// available area, usually a [`Toolbar`](crate::gui::Toolbar).
+	///
+	/// The size of the contained window must be informed in the
+	/// [`pgn_calc_size`](crate::gui::events::PagerEvents::pgn_calc_size)
+	/// event.
+}
+
+impl Pager {
+	/// Instantiates a new `Pager` object, to be created on the parent window
+	/// with
+	/// [`HWND::CreateWindowEx`](crate::prelude::user_Hwnd::CreateWindowEx).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window was already created – that is, you cannot
+	/// dynamically create a `Pager` in an event closure.
+	///
+	/// # Examples
+	///
+	/// Scrolling a toolbar horizontally:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// let toolbar: gui::Toolbar;
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let toolbar = gui::Toolbar::new(&wnd, gui::ToolbarOpts::default());
+	///
+	/// let pager = gui::Pager::new(&wnd, gui::PagerOpts::default());
+	///
+	/// let toolbar2 = toolbar.clone();
+	/// pager.on().pgn_calc_size(move |p: &mut w::NMPGCALCSIZE| {
+	///     let rc = toolbar2.hwnd().GetWindowRect()?;
+	///     if p.dwFlag == co::PGF::CALCWIDTH {
+	///         p.iWidth = rc.right - rc.left;
+	///     } else {
+	///         p.iHeight = rc.bottom - rc.top;
+	///     }
+	///     Ok(())
+	/// });
+	///
+	/// let pager2 = pager.clone();
+	/// let toolbar2 = toolbar.clone();
+	/// wnd.on().wm_create(move |_| {
+	///     pager2.set_child(&toolbar2)?;
+	///     Ok(0)
+	/// });
+	/// ```
+	#[must_use]
+	pub fn new(parent: &(impl GuiParent + 'static), opts: PagerOpts) -> Self {
+		let ctrl_id = auto_id::set_if_zero(opts.ctrl_id);
+		let new_self = Self(Arc::pin(PagerObj {
+			base: BaseCtrl::new(ctrl_id),
+			events: PagerEvents::new(parent, ctrl_id),
+			_pin: PhantomPinned,
+		}));
+
+		let self2 = new_self.clone();
+		let parent2 = parent.clone();
+		parent
+			.as_ref()
+			.before_on()
+			.wm(parent.as_ref().is_dlg().create_msg(), move |_| {
+				comctl_classes::register(co::ICC::PAGESCROLLER_CLASS)?;
+				self2.0.base.create_window(
+					opts.window_ex_style,
+					"SysPager",
+					None,
+					opts.window_style | opts.control_style.into(),
+					opts.position.into(),
+					opts.size.into(),
+					&parent2,
+				)?;
+				parent2
+					.as_ref()
+					.add_to_layout(self2.hwnd(), opts.resize_behavior)?;
+				Ok(0) // ignored
+			});
+
+		new_self
+	}
+
+	/// Instantiates a new `Pager` object, to be loaded from a dialog resource
+	/// with [`HWND::GetDlgItem`](crate::prelude::user_Hwnd::GetDlgItem).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent dialog was already created – that is, you cannot
+	/// dynamically create a `Pager` in an event closure.
+	///
+	/// Panics if the pager window class cannot be registered.
+	#[must_use]
+	pub fn new_dlg(
+		parent: &(impl GuiParent + 'static),
+		ctrl_id: u16,
+		resize_behavior: (Horz, Vert),
+	) -> Self {
+		comctl_classes::register(co::ICC::PAGESCROLLER_CLASS)
+			.expect("Pager window class could not be registered.");
+
+		let new_self = Self(Arc::pin(PagerObj {
+			base: BaseCtrl::new(ctrl_id),
+			events: PagerEvents::new(parent, ctrl_id),
+			_pin: PhantomPinned,
+		}));
+
+		let self2 = new_self.clone();
+		let parent2 = parent.clone();
+		parent.as_ref().before_on().wm_init_dialog(move |_| {
+			self2.0.base.assign_dlg(&parent2)?;
+			parent2
+				.as_ref()
+				.add_to_layout(self2.hwnd(), resize_behavior)?;
+			Ok(true) // ignored
+		});
+
+		new_self
+	}
+
+	/// Retrieves the size of the scroll buttons by sending a
+	/// [`pgm::GetButtonSize`](crate::msg::pgm::GetButtonSize) message.
+	#[must_use]
+	pub fn button_size(&self) -> i32 {
+		unsafe { self.hwnd().SendMessage(pgm::GetButtonSize {}) }
+	}
+
+	/// Retrieves the current scroll position by sending a
+	/// [`pgm::GetPos`](crate::msg::pgm::GetPos) message.
+	#[must_use]
+	pub fn pos(&self) -> i32 {
+		unsafe { self.hwnd().SendMessage(pgm::GetPos {}) }
+	}
+
+	/// Forces the pager to recalculate the size of the contained window by
+	/// sending a [`pgm::RecalcSize`](crate::msg::pgm::RecalcSize) message,
+	/// which will fire a
+	/// [`pgn_calc_size`](crate::gui::events::PagerEvents::pgn_calc_size)
+	/// event.
+	pub fn recalc_size(&self) {
+		unsafe {
+			self.hwnd().SendMessage(pgm::RecalcSize {});
+		}
+	}
+
+	/// Sets the background color by sending a
+	/// [`pgm::SetBkColor`](crate::msg::pgm::SetBkColor) message.
+	///
+	/// Returns the previous color.
+	pub fn set_bk_color(&self, color: COLORREF) -> COLORREF {
+		unsafe { self.hwnd().SendMessage(pgm::SetBkColor { color }) }
+	}
+
+	/// Sets the size of the scroll buttons by sending a
+	/// [`pgm::SetButtonSize`](crate::msg::pgm::SetButtonSize) message.
+	///
+	/// Returns the previous size.
+	pub fn set_button_size(&self, size: i32) -> i32 {
+		unsafe { self.hwnd().SendMessage(pgm::SetButtonSize { size }) }
+	}
+
+	/// Makes the given control the contained window of the pager, by calling
+	/// [`HWND::SetParent`](crate::prelude::user_Hwnd::SetParent) and sending a
+	/// [`pgm::SetChild`](crate::msg::pgm::SetChild) message.
+	///
+	/// The control must be already created.
+	pub fn set_child(&self, child: &impl GuiControl) -> SysResult<()> {
+		child.hwnd().SetParent(self.hwnd())?;
+		unsafe {
+			self.hwnd()
+				.SendMessage(pgm::SetChild { hchild: child.hwnd() });
+		}
+		Ok(())
+	}
+
+	/// Sets the current scroll position by sending a
+	/// [`pgm::SetPos`](crate::msg::pgm::SetPos) message.
+	pub fn set_pos(&self, pos: i32) {
+		unsafe {
+			self.hwnd().SendMessage(pgm::SetPos { pos });
+		}
+	}
+}
+
+/// Options to create a [`Pager`](crate::gui::Pager) programmatically with
+/// [`Pager::new`](crate::gui::Pager::new).
+pub struct PagerOpts {
+	/// Left and top position coordinates of control within parent's client
+	/// area, to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `gui::dpi(0, 0)`.
+	pub position: (i32, i32),
+	/// Width and height of control to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `gui::dpi(200, 30)`.
+	pub size: (i32, i32),
+	/// Pager styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `PGS::HORZ`.
+	pub control_style: co::PGS,
+	/// Window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS::CHILD | WS::VISIBLE`.
+	pub window_style: co::WS,
+	/// Extended window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS_EX::LEFT`.
+	pub window_ex_style: co::WS_EX,
+
+	/// The control ID.
+	///
+	/// Defaults to an auto-generated ID.
+	pub ctrl_id: u16,
+	/// Horizontal and vertical behavior of the control when the parent window
+	/// is resized.
+	///
+	/// Defaults to `(gui::Horz::None, gui::Vert::None)`.
+	pub resize_behavior: (Horz, Vert),
+}
+
+impl Default for PagerOpts {
+	fn default() -> Self {
+		Self {
+			position: dpi(0, 0),
+			size: dpi(200, 30),
+			control_style: co::PGS::HORZ,
+			window_style: co::WS::CHILD | co::WS::VISIBLE,
+			window_ex_style: co::WS_EX::LEFT,
+			ctrl_id: 0,
+			resize_behavior: (Horz::None, Vert::None),
+		}
+	}
+}
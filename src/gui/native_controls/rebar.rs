@@ -0,0 +1,307 @@
+use std::any::Any;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::gui::{events::*, privs::*};
+use crate::msg::*;
+use crate::prelude::*;
+
+struct RebarObj {
+	base: BaseCtrl,
+	events: RebarEvents,
+	_pin: PhantomPinned,
+}
+
+native_ctrl! { Rebar: RebarObj => RebarEvents;
+	/// Native
+	/// [rebar](https://learn.microsoft.com/en-us/windows/win32/controls/rebar-controls)
+	/// control, which holds other controls in movable bands.
+	///
+	/// The rebar will align itself to the top of the parent window, and it will
+	/// be automatically resized when the parent is resized.
+}
+
+impl Rebar {
+	/// Instantiates a new `Rebar` object, to be created on the parent window
+	/// with
+	/// [`HWND::CreateWindowEx`](crate::prelude::user_Hwnd::CreateWindowEx).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window was already created – that is, you cannot
+	/// dynamically create a `Rebar` in an event closure.
+	///
+	/// # Examples
+	///
+	/// A rebar holding a toolbar and a combo box:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	///
+	/// let rebar = gui::Rebar::new(&wnd, gui::RebarOpts::default());
+	/// let toolbar = gui::Toolbar::new(
+	///     &wnd,
+	///     gui::ToolbarOpts {
+	///         control_style: co::TBSTYLE::FLAT
+	///             | co::TBSTYLE::NORESIZE
+	///             | co::TBSTYLE::NOPARENTALIGN
+	///             | co::TBSTYLE::NODIVIDER,
+	///         ..Default::default()
+	///     },
+	/// );
+	/// let cmb = gui::ComboBox::new(&wnd, gui::ComboBoxOpts::default());
+	///
+	/// let rebar2 = rebar.clone();
+	/// let toolbar2 = toolbar.clone();
+	/// let cmb2 = cmb.clone();
+	/// wnd.on().wm_create(move |_| {
+	///     rebar2.add_band("", &toolbar2, co::RBBS::CHILDEDGE | co::RBBS::GRIPPERALWAYS)?;
+	///     rebar2.add_band("Find:", &cmb2, co::RBBS::CHILDEDGE)?;
+	///     Ok(0)
+	/// });
+	/// ```
+	#[must_use]
+	pub fn new(parent: &(impl GuiParent + 'static), opts: RebarOpts) -> Self {
+		let ctrl_id = auto_id::set_if_zero(opts.ctrl_id);
+		let new_self = Self(Arc::pin(RebarObj {
+			base: BaseCtrl::new(ctrl_id),
+			events: RebarEvents::new(parent, ctrl_id),
+			_pin: PhantomPinned,
+		}));
+
+		let self2 = new_self.clone();
+		let parent2 = parent.clone();
+		parent
+			.as_ref()
+			.before_on()
+			.wm(parent.as_ref().is_dlg().create_msg(), move |_| {
+				comctl_classes::register(co::ICC::COOL_CLASSES)?;
+				self2.0.base.create_window(
+					opts.window_ex_style,
+					"ReBarWindow32",
+					None,
+					opts.window_style | opts.control_style.into(),
+					POINT::default(),
+					SIZE::default(),
+					&parent2,
+				)?;
+				ui_font::set(self2.hwnd())?;
+				Ok(0) // ignored
+			});
+
+		new_self.default_message_handlers(parent);
+		new_self
+	}
+
+	/// Instantiates a new `Rebar` object, to be loaded from a dialog resource
+	/// with [`HWND::GetDlgItem`](crate::prelude::user_Hwnd::GetDlgItem).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent dialog was already created – that is, you cannot
+	/// dynamically create a `Rebar` in an event closure.
+	///
+	/// Panics if the rebar window class cannot be registered.
+	#[must_use]
+	pub fn new_dlg(parent: &(impl GuiParent + 'static), ctrl_id: u16) -> Self {
+		comctl_classes::register(co::ICC::COOL_CLASSES)
+			.expect("Rebar window class could not be registered.");
+
+		let new_self = Self(Arc::pin(RebarObj {
+			base: BaseCtrl::new(ctrl_id),
+			events: RebarEvents::new(parent, ctrl_id),
+			_pin: PhantomPinned,
+		}));
+
+		let self2 = new_self.clone();
+		let parent2 = parent.clone();
+		parent.as_ref().before_on().wm_init_dialog(move |_| {
+			self2.0.base.assign_dlg(&parent2)?;
+			Ok(true) // ignored
+		});
+
+		new_self.default_message_handlers(parent);
+		new_self
+	}
+
+	fn default_message_handlers(&self, parent: &impl AsRef<BaseWnd>) {
+		let self2 = self.clone();
+		parent.as_ref().before_on().wm_size(move |mut p| {
+			if p.request != co::SIZE_R::MINIMIZED && *self2.hwnd() != HWND::NULL {
+				unsafe {
+					self2.hwnd().SendMessage(p.as_generic_wm()); // send WM_SIZE to rebar, so it realigns itself to parent
+				}
+			}
+			Ok(())
+		});
+	}
+
+	/// Appends a new band holding the given control, by sending a
+	/// [`rb::InsertBand`](crate::msg::rb::InsertBand) message. Returns the
+	/// zero-based index of the new band.
+	///
+	/// The control must be already created; its current size is used as the
+	/// minimum size of the band. The control becomes a child of the rebar.
+	pub fn add_band(&self, text: &str, child: &impl GuiControl, style: co::RBBS) -> SysResult<u32> {
+		let rc = child.hwnd().GetWindowRect()?;
+		let mut wtext = WString::from_str(text);
+
+		let mut rbbi = REBARBANDINFO::default();
+		rbbi.fMask = co::RBBIM::STYLE
+			| co::RBBIM::CHILD
+			| co::RBBIM::CHILDSIZE
+			| co::RBBIM::SIZE
+			| co::RBBIM::ID;
+		if !text.is_empty() {
+			rbbi.fMask |= co::RBBIM::TEXT;
+			rbbi.set_lpText(Some(&mut wtext));
+		}
+		rbbi.fStyle = style;
+		rbbi.hwndChild = unsafe { child.hwnd().raw_copy() };
+		rbbi.cxMinChild = 0;
+		rbbi.cyMinChild = (rc.bottom - rc.top) as _;
+		rbbi.cx = (rc.right - rc.left) as _;
+		rbbi.wID = child.ctrl_id() as _;
+
+		unsafe {
+			self.hwnd()
+				.SendMessage(rb::InsertBand { index: None, info: &rbbi })?;
+		}
+		Ok(self.band_count() - 1)
+	}
+
+	/// Retrieves the number of bands by sending a
+	/// [`rb::GetBandCount`](crate::msg::rb::GetBandCount) message.
+	#[must_use]
+	pub fn band_count(&self) -> u32 {
+		unsafe { self.hwnd().SendMessage(rb::GetBandCount {}) }
+	}
+
+	/// Retrieves the zero-based index of the band with the given ID, if any,
+	/// by sending an [`rb::IdToIndex`](crate::msg::rb::IdToIndex) message.
+	///
+	/// Bands added with [`add_band`](crate::gui::Rebar::add_band) have the
+	/// same ID of their child control.
+	#[must_use]
+	pub fn band_index(&self, id: u16) -> Option<u32> {
+		unsafe { self.hwnd().SendMessage(rb::IdToIndex { id }) }
+	}
+
+	/// Retrieves the bounding rectangle of the given band, relative to the
+	/// rebar, by sending an [`rb::GetRect`](crate::msg::rb::GetRect) message.
+	#[must_use]
+	pub fn band_rect(&self, index: u32) -> SysResult<RECT> {
+		let mut rc = RECT::default();
+		unsafe {
+			self.hwnd()
+				.SendMessage(rb::GetRect { index, rect: &mut rc })?;
+		}
+		Ok(rc)
+	}
+
+	/// Retrieves the height of the rebar by sending an
+	/// [`rb::GetBarHeight`](crate::msg::rb::GetBarHeight) message.
+	#[must_use]
+	pub fn bar_height(&self) -> u32 {
+		unsafe { self.hwnd().SendMessage(rb::GetBarHeight {}) }
+	}
+
+	/// Deletes the given band by sending an
+	/// [`rb::DeleteBand`](crate::msg::rb::DeleteBand) message.
+	///
+	/// The child control is not destroyed.
+	pub fn delete_band(&self, index: u32) -> SysResult<()> {
+		unsafe { self.hwnd().SendMessage(rb::DeleteBand { index }) }
+	}
+
+	/// Resizes the given band to its largest size by sending an
+	/// [`rb::MaximizeBand`](crate::msg::rb::MaximizeBand) message.
+	pub fn maximize_band(&self, index: u32) {
+		unsafe {
+			self.hwnd()
+				.SendMessage(rb::MaximizeBand { index, ideal_width: false });
+		}
+	}
+
+	/// Resizes the given band to its smallest size by sending an
+	/// [`rb::MinimizeBand`](crate::msg::rb::MinimizeBand) message.
+	pub fn minimize_band(&self, index: u32) {
+		unsafe {
+			self.hwnd().SendMessage(rb::MinimizeBand { index });
+		}
+	}
+
+	/// Moves a band from one zero-based index to another by sending an
+	/// [`rb::MoveBand`](crate::msg::rb::MoveBand) message.
+	pub fn move_band(&self, from: u32, to: u32) -> SysResult<()> {
+		unsafe { self.hwnd().SendMessage(rb::MoveBand { from, to }) }
+	}
+
+	/// Retrieves the number of rows of bands by sending an
+	/// [`rb::GetRowCount`](crate::msg::rb::GetRowCount) message.
+	#[must_use]
+	pub fn row_count(&self) -> u32 {
+		unsafe { self.hwnd().SendMessage(rb::GetRowCount {}) }
+	}
+
+	/// Sets the background color by sending an
+	/// [`rb::SetBkColor`](crate::msg::rb::SetBkColor) message.
+	///
+	/// If `color` is `None`, the default color is used.
+	pub fn set_bk_color(&self, color: Option<COLORREF>) {
+		unsafe {
+			self.hwnd().SendMessage(rb::SetBkColor { color });
+		}
+	}
+
+	/// Shows or hides the given band by sending an
+	/// [`rb::ShowBand`](crate::msg::rb::ShowBand) message.
+	pub fn show_band(&self, index: u32, show: bool) -> SysResult<()> {
+		unsafe { self.hwnd().SendMessage(rb::ShowBand { index, show }) }
+	}
+}
+
+/// Options to create a [`Rebar`](crate::gui::Rebar) programmatically with
+/// [`Rebar::new`](crate::gui::Rebar::new).
+pub struct RebarOpts {
+	/// Rebar styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `RBS::VARHEIGHT | RBS::BANDBORDERS`.
+	pub control_style: co::RBS,
+	/// Window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS::CHILD | WS::VISIBLE | WS::CLIPCHILDREN | WS::CLIPSIBLINGS`.
+	pub window_style: co::WS,
+	/// Extended window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS_EX::LEFT | WS_EX::TOOLWINDOW`.
+	pub window_ex_style: co::WS_EX,
+
+	/// The control ID.
+	///
+	/// Defaults to an auto-generated ID.
+	pub ctrl_id: u16,
+}
+
+impl Default for RebarOpts {
+	fn default() -> Self {
+		Self {
+			control_style: co::RBS::VARHEIGHT | co::RBS::BANDBORDERS,
+			window_style: co::WS::CHILD
+				| co::WS::VISIBLE
+				| co::WS::CLIPCHILDREN
+				| co::WS::CLIPSIBLINGS,
+			window_ex_style: co::WS_EX::LEFT | co::WS_EX::TOOLWINDOW,
+			ctrl_id: 0,
+		}
+	}
+}
@@ -0,0 +1,521 @@
+use std::any::Any;
+use std::io::{Read, Write};
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::gui::{events::*, privs::*, *};
+use crate::msg::*;
+use crate::prelude::*;
+
+struct RichEditObj {
+	base: BaseCtrl,
+	events: RichEditEvents,
+	_pin: PhantomPinned,
+}
+
+native_ctrl! { RichEdit: RichEditObj => RichEditEvents;
+	/// Native
+	/// [rich edit](https://learn.microsoft.com/en-us/windows/win32/controls/about-rich-edit-controls)
+	/// control, which supports character and paragraph formatting. Not to be
+	/// confused with the simpler [edit](crate::gui::Edit) control.
+	///
+	/// The control is created with the `MSFTEDIT_CLASS` window class, and the
+	/// Msftedit.dll library is automatically loaded.
+}
+
+impl RichEdit {
+	/// Instantiates a new `RichEdit` object, to be created on the parent
+	/// window with
+	/// [`HWND::CreateWindowEx`](crate::prelude::user_Hwnd::CreateWindowEx).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window was already created – that is, you cannot
+	/// dynamically create a `RichEdit` in an event closure.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	///
+	/// let txt = gui::RichEdit::new(
+	///     &wnd,
+	///     gui::RichEditOpts {
+	///         position: gui::dpi(10, 10),
+	///         width: gui::dpi_x(300),
+	///         height: gui::dpi_y(200),
+	///         auto_url_detect: co::AURL::ENABLEURL,
+	///         ..Default::default()
+	///     },
+	/// );
+	/// ```
+	#[must_use]
+	pub fn new(parent: &(impl GuiParent + 'static), opts: RichEditOpts) -> Self {
+		let ctrl_id = auto_id::set_if_zero(opts.ctrl_id);
+		let new_self = Self(Arc::pin(RichEditObj {
+			base: BaseCtrl::new(ctrl_id),
+			events: RichEditEvents::new(parent, ctrl_id),
+			_pin: PhantomPinned,
+		}));
+
+		let self2 = new_self.clone();
+		let parent2 = parent.clone();
+		parent
+			.as_ref()
+			.before_on()
+			.wm(parent.as_ref().is_dlg().create_msg(), move |_| {
+				msftedit::load()?;
+				self2.0.base.create_window(
+					opts.window_ex_style,
+					"RICHEDIT50W",
+					Some(&opts.text),
+					opts.window_style | opts.control_style.into(),
+					opts.position.into(),
+					SIZE::new(opts.width, opts.height),
+					&parent2,
+				)?;
+				ui_font::set(self2.hwnd())?;
+				self2.set_event_mask(opts.event_mask);
+				if opts.auto_url_detect != co::AURL::DISABLE {
+					self2.auto_url_detect(opts.auto_url_detect)?;
+				}
+				parent2
+					.as_ref()
+					.add_to_layout(self2.hwnd(), opts.resize_behavior)?;
+				Ok(0) // ignored
+			});
+
+		new_self
+	}
+
+	/// Instantiates a new `RichEdit` object, to be loaded from a dialog
+	/// resource with
+	/// [`HWND::GetDlgItem`](crate::prelude::user_Hwnd::GetDlgItem).
+	///
+	/// Msftedit.dll is loaded right away, so the dialog can create the control.
+	///
+	/// # Panics
+	///
+	/// Panics if the parent dialog was already created – that is, you cannot
+	/// dynamically create a `RichEdit` in an event closure.
+	///
+	/// Panics if Msftedit.dll cannot be loaded.
+	#[must_use]
+	pub fn new_dlg(
+		parent: &(impl GuiParent + 'static),
+		ctrl_id: u16,
+		resize_behavior: (Horz, Vert),
+		event_mask: co::ENM,
+	) -> Self {
+		msftedit::load().expect("Msftedit.dll could not be loaded.");
+
+		let new_self = Self(Arc::pin(RichEditObj {
+			base: BaseCtrl::new(ctrl_id),
+			events: RichEditEvents::new(parent, ctrl_id),
+			_pin: PhantomPinned,
+		}));
+
+		let self2 = new_self.clone();
+		let parent2 = parent.clone();
+		parent.as_ref().before_on().wm_init_dialog(move |_| {
+			self2.0.base.assign_dlg(&parent2)?;
+			self2.set_event_mask(event_mask);
+			parent2
+				.as_ref()
+				.add_to_layout(self2.hwnd(), resize_behavior)?;
+			Ok(true) // ignored
+		});
+
+		new_self
+	}
+
+	/// Appends text at the end of the control, optionally with the given
+	/// character formatting, by sending [`em::ExSetSel`](crate::msg::em::ExSetSel),
+	/// [`em::SetCharFormat`](crate::msg::em::SetCharFormat) and
+	/// [`em::ReplaceSel`](crate::msg::em::ReplaceSel) messages.
+	///
+	/// The current selection is lost.
+	///
+	/// # Examples
+	///
+	/// Appending a red line of text:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let txt: gui::RichEdit; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let txt = gui::RichEdit::new(&wnd, gui::RichEditOpts::default());
+	///
+	/// let mut cf = w::CHARFORMAT2::default();
+	/// cf.dwMask = co::CFM::COLOR;
+	/// cf.crTextColor = w::COLORREF::new(0xff, 0x00, 0x00);
+	///
+	/// txt.append_text("Error: file not found.\r\n", Some(&cf))?;
+	/// # w::SysResult::Ok(())
+	/// ```
+	pub fn append_text(&self, text: &str, format: Option<&CHARFORMAT2>) -> SysResult<()> {
+		self.set_selection(-1, -1);
+		if let Some(format) = format {
+			self.set_char_format(co::SCF::SELECTION, format)?;
+		}
+		unsafe {
+			self.hwnd().SendMessage(em::ReplaceSel {
+				can_be_undone: false,
+				replacement_text: WString::from_str(text),
+			});
+		}
+		Ok(())
+	}
+
+	/// Enables or disables the automatic detection of URLs by sending an
+	/// [`em::AutoUrlDetect`](crate::msg::em::AutoUrlDetect) message.
+	///
+	/// To receive the [`en_link`](crate::gui::events::RichEditEvents::en_link)
+	/// notifications, the event mask must include
+	/// [`ENM::LINK`](crate::co::ENM::LINK).
+	pub fn auto_url_detect(&self, flags: co::AURL) -> SysResult<()> {
+		unsafe { self.hwnd().SendMessage(em::AutoUrlDetect { flags }) }
+	}
+
+	/// Retrieves the character formatting by sending an
+	/// [`em::GetCharFormat`](crate::msg::em::GetCharFormat) message.
+	///
+	/// The `scope` can be [`SCF::DEFAULT`](crate::co::SCF::DEFAULT) or
+	/// [`SCF::SELECTION`](crate::co::SCF::SELECTION).
+	#[must_use]
+	pub fn char_format(&self, scope: co::SCF) -> CHARFORMAT2 {
+		let mut cf = CHARFORMAT2::default();
+		unsafe {
+			self.hwnd()
+				.SendMessage(em::GetCharFormat { scope, format: &mut cf });
+		}
+		cf
+	}
+
+	/// Retrieves the event mask by sending an
+	/// [`em::GetEventMask`](crate::msg::em::GetEventMask) message.
+	#[must_use]
+	pub fn event_mask(&self) -> co::ENM {
+		unsafe { self.hwnd().SendMessage(em::GetEventMask {}) }
+	}
+
+	/// Hides or shows the selection by sending an
+	/// [`em::HideSelection`](crate::msg::em::HideSelection) message.
+	pub fn hide_selection(&self, hide: bool) {
+		unsafe {
+			self.hwnd().SendMessage(em::HideSelection { hide });
+		}
+	}
+
+	/// Limits the number of characters that can be typed by sending an
+	/// [`em::ExLimitText`](crate::msg::em::ExLimitText) message.
+	///
+	/// If `None`, the default limit of 64K characters is set.
+	pub fn limit_text(&self, max_chars: Option<u32>) {
+		unsafe {
+			self.hwnd().SendMessage(em::ExLimitText { max_chars });
+		}
+	}
+
+	/// Retrieves the paragraph formatting of the current selection by sending
+	/// an [`em::GetParaFormat`](crate::msg::em::GetParaFormat) message.
+	#[must_use]
+	pub fn para_format(&self) -> PARAFORMAT2 {
+		let mut pf = PARAFORMAT2::default();
+		unsafe {
+			self.hwnd()
+				.SendMessage(em::GetParaFormat { format: &mut pf });
+		}
+		pf
+	}
+
+	/// Retrieves the zero-based start and end character positions of the
+	/// current selection by sending an
+	/// [`em::ExGetSel`](crate::msg::em::ExGetSel) message.
+	#[must_use]
+	pub fn selection(&self) -> CHARRANGE {
+		let mut range = CHARRANGE::default();
+		unsafe {
+			self.hwnd().SendMessage(em::ExGetSel { range: &mut range });
+		}
+		range
+	}
+
+	/// Sets the background color by sending an
+	/// [`em::SetBkgndColor`](crate::msg::em::SetBkgndColor) message.
+	///
+	/// If `None`, the system window color is used.
+	pub fn set_bk_color(&self, color: Option<COLORREF>) {
+		unsafe {
+			self.hwnd().SendMessage(em::SetBkgndColor { color });
+		}
+	}
+
+	/// Sets the character formatting by sending an
+	/// [`em::SetCharFormat`](crate::msg::em::SetCharFormat) message.
+	///
+	/// Only the attributes specified in
+	/// [`CHARFORMAT2::dwMask`](crate::CHARFORMAT2) are changed.
+	pub fn set_char_format(&self, scope: co::SCF, format: &CHARFORMAT2) -> SysResult<()> {
+		unsafe { self.hwnd().SendMessage(em::SetCharFormat { scope, format }) }
+	}
+
+	/// Sets the event mask by sending an
+	/// [`em::SetEventMask`](crate::msg::em::SetEventMask) message.
+	///
+	/// The rich edit control will only send the notifications whose flags are
+	/// present in the mask.
+	///
+	/// Returns the previous event mask.
+	pub fn set_event_mask(&self, mask: co::ENM) -> co::ENM {
+		unsafe { self.hwnd().SendMessage(em::SetEventMask { mask }) }
+	}
+
+	/// Sets the paragraph formatting of the current selection by sending an
+	/// [`em::SetParaFormat`](crate::msg::em::SetParaFormat) message.
+	///
+	/// Only the attributes specified in
+	/// [`PARAFORMAT2::dwMask`](crate::PARAFORMAT2) are changed.
+	pub fn set_para_format(&self, format: &PARAFORMAT2) -> SysResult<()> {
+		unsafe { self.hwnd().SendMessage(em::SetParaFormat { format }) }
+	}
+
+	/// Sets the selection by sending an
+	/// [`em::ExSetSel`](crate::msg::em::ExSetSel) message.
+	///
+	/// If `start` is `0` and `end` is `-1`, all the text is selected. If
+	/// `start` is `-1`, the selection is removed.
+	pub fn set_selection(&self, start: i32, end: i32) {
+		unsafe {
+			self.hwnd().SendMessage(em::ExSetSel {
+				range: &CHARRANGE { cpMin: start, cpMax: end },
+			});
+		}
+	}
+
+	/// Sets the text by calling
+	/// [`HWND::SetWindowText`](crate::prelude::user_Hwnd::SetWindowText).
+	pub fn set_text(&self, text: &str) -> SysResult<()> {
+		self.hwnd().SetWindowText(text)?;
+		Ok(())
+	}
+
+	/// Replaces the contents of the control – or the current selection, if
+	/// [`SF::SFF_SELECTION`](crate::co::SF::SFF_SELECTION) is given – with the
+	/// data read from `source`, by sending an
+	/// [`em::StreamIn`](crate::msg::em::StreamIn) message.
+	///
+	/// The `format` is usually [`SF::RTF`](crate::co::SF::RTF) or
+	/// [`SF::TEXT`](crate::co::SF::TEXT).
+	///
+	/// Returns the number of characters read.
+	///
+	/// # Examples
+	///
+	/// Loading an RTF file:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let txt: gui::RichEdit; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let txt = gui::RichEdit::new(&wnd, gui::RichEditOpts::default());
+	///
+	/// let mut f = std::fs::File::open("C:\\Temp\\notes.rtf")?;
+	/// txt.stream_in(co::SF::RTF, &mut f)?;
+	/// # w::AnyResult::Ok(())
+	/// ```
+	pub fn stream_in(&self, format: co::SF, source: &mut impl Read) -> SysResult<u32> {
+		let mut source: &mut dyn Read = source;
+		let mut es = EDITSTREAM {
+			dwCookie: &mut source as *mut &mut dyn Read as _,
+			dwError: 0,
+			pfnCallback: Some(stream_in_proc),
+		};
+
+		let num_chars = unsafe {
+			self.hwnd()
+				.SendMessage(em::StreamIn { format, stream: &mut es })
+		};
+		match es.dwError {
+			0 => Ok(num_chars),
+			err => Err(unsafe { co::ERROR::from_raw(err) }),
+		}
+	}
+
+	/// Writes the contents of the control – or the current selection, if
+	/// [`SF::SFF_SELECTION`](crate::co::SF::SFF_SELECTION) is given – into
+	/// `dest`, by sending an [`em::StreamOut`](crate::msg::em::StreamOut)
+	/// message.
+	///
+	/// The `format` is usually [`SF::RTF`](crate::co::SF::RTF) or
+	/// [`SF::TEXT`](crate::co::SF::TEXT).
+	///
+	/// Returns the number of characters written.
+	///
+	/// # Examples
+	///
+	/// Retrieving the RTF contents as a `String`:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let txt: gui::RichEdit; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let txt = gui::RichEdit::new(&wnd, gui::RichEditOpts::default());
+	///
+	/// let mut rtf = Vec::<u8>::new();
+	/// txt.stream_out(co::SF::RTF, &mut rtf)?;
+	/// let rtf = String::from_utf8_lossy(&rtf);
+	/// # w::SysResult::Ok(())
+	/// ```
+	pub fn stream_out(&self, format: co::SF, dest: &mut impl Write) -> SysResult<u32> {
+		let mut dest: &mut dyn Write = dest;
+		let mut es = EDITSTREAM {
+			dwCookie: &mut dest as *mut &mut dyn Write as _,
+			dwError: 0,
+			pfnCallback: Some(stream_out_proc),
+		};
+
+		let num_chars = unsafe {
+			self.hwnd()
+				.SendMessage(em::StreamOut { format, stream: &mut es })
+		};
+		match es.dwError {
+			0 => Ok(num_chars),
+			err => Err(unsafe { co::ERROR::from_raw(err) }),
+		}
+	}
+
+	/// Retrieves the text by calling
+	/// [`HWND::GetWindowText`](crate::prelude::user_Hwnd::GetWindowText).
+	#[must_use]
+	pub fn text(&self) -> SysResult<String> {
+		self.hwnd().GetWindowText()
+	}
+}
+
+extern "system" fn stream_in_proc(cookie: usize, pb: *mut u8, cb: i32, pcb: *mut i32) -> u32 {
+	let source = unsafe { &mut *(cookie as *mut &mut dyn Read) };
+	let buf = unsafe { std::slice::from_raw_parts_mut(pb, cb as _) };
+	match source.read(buf) {
+		Ok(num_bytes) => {
+			unsafe { *pcb = num_bytes as _ }; // zero bytes means end of stream
+			0
+		},
+		Err(e) => e
+			.raw_os_error()
+			.map_or(co::ERROR::READ_FAULT.raw(), |code| code as _),
+	}
+}
+
+extern "system" fn stream_out_proc(cookie: usize, pb: *mut u8, cb: i32, pcb: *mut i32) -> u32 {
+	let dest = unsafe { &mut *(cookie as *mut &mut dyn Write) };
+	let buf = unsafe { std::slice::from_raw_parts(pb, cb as _) };
+	match dest.write_all(buf) {
+		Ok(_) => {
+			unsafe { *pcb = cb };
+			0
+		},
+		Err(e) => e
+			.raw_os_error()
+			.map_or(co::ERROR::WRITE_FAULT.raw(), |code| code as _),
+	}
+}
+
+/// Options to create a [`RichEdit`](crate::gui::RichEdit) programmatically
+/// with [`RichEdit::new`](crate::gui::RichEdit::new).
+pub struct RichEditOpts {
+	/// Text of the control to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to empty string.
+	pub text: String,
+	/// Left and top position coordinates of control within parent's client
+	/// area, to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `gui::dpi(0, 0)`.
+	pub position: (i32, i32),
+	/// Control width to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `gui::dpi_x(200)`.
+	pub width: i32,
+	/// Control height to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `gui::dpi_y(100)`.
+	pub height: i32,
+	/// Edit styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `ES::MULTILINE | ES::WANTRETURN | ES::AUTOVSCROLL | ES::NOHIDESEL`.
+	///
+	/// Suggestions:
+	/// * add `ES::READONLY` for a log viewer;
+	/// * add `ES::DISABLENOSCROLL` to always display the scroll bars.
+	pub control_style: co::ES,
+	/// Window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS::CHILD | WS::GROUP | WS::TABSTOP | WS::VISIBLE | WS::VSCROLL`.
+	pub window_style: co::WS,
+	/// Extended window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS_EX::LEFT | WS_EX::CLIENTEDGE`.
+	pub window_ex_style: co::WS_EX,
+
+	/// The control ID.
+	///
+	/// Defaults to an auto-generated ID.
+	pub ctrl_id: u16,
+	/// Horizontal and vertical behavior of the control when the parent window
+	/// is resized.
+	///
+	/// Defaults to `(gui::Horz::None, gui::Vert::None)`.
+	pub resize_behavior: (Horz, Vert),
+
+	/// Notifications to be sent by the control. A notification whose flag is
+	/// not present won't be fired.
+	///
+	/// Defaults to `ENM::CHANGE | ENM::LINK`.
+	pub event_mask: co::ENM,
+	/// Automatic URL detection.
+	///
+	/// Defaults to `AURL::DISABLE`.
+	pub auto_url_detect: co::AURL,
+}
+
+impl Default for RichEditOpts {
+	fn default() -> Self {
+		Self {
+			text: "".to_owned(),
+			position: dpi(0, 0),
+			width: dpi_x(200),
+			height: dpi_y(100),
+			control_style: co::ES::MULTILINE
+				| co::ES::WANTRETURN
+				| co::ES::AUTOVSCROLL
+				| co::ES::NOHIDESEL,
+			window_style: co::WS::CHILD
+				| co::WS::GROUP
+				| co::WS::TABSTOP
+				| co::WS::VISIBLE
+				| co::WS::VSCROLL,
+			window_ex_style: co::WS_EX::LEFT | co::WS_EX::CLIENTEDGE,
+			ctrl_id: 0,
+			resize_behavior: (Horz::None, Vert::None),
+			event_mask: co::ENM::CHANGE | co::ENM::LINK,
+			auto_url_detect: co::AURL::DISABLE,
+		}
+	}
+}
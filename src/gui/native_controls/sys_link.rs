@@ -0,0 +1,266 @@
+use std::any::Any;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::gui::{events::*, privs::*, *};
+use crate::msg::*;
+use crate::prelude::*;
+
+struct SysLinkObj {
+	base: BaseCtrl,
+	events: SysLinkEvents,
+	_pin: PhantomPinned,
+}
+
+native_ctrl! { SysLink: SysLinkObj => SysLinkEvents;
+	/// Native
+	/// [SysLink](https://learn.microsoft.com/en-us/windows/win32/controls/syslink-control-entry)
+	/// control, which renders a text with one or more hyperlinks, marked with
+	/// `<a>` tags.
+}
+
+impl SysLink {
+	/// Instantiates a new `SysLink` object, to be created on the parent window
+	/// with
+	/// [`HWND::CreateWindowEx`](crate::prelude::user_Hwnd::CreateWindowEx).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window was already created – that is, you cannot
+	/// dynamically create a `SysLink` in an event closure.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, gui};
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	///
+	/// let link = gui::SysLink::new(
+	///     &wnd,
+	///     gui::SysLinkOpts {
+	///         text: "Visit <a href=\"https://www.rust-lang.org\">Rust</a> or \
+	///             <a id=\"about\">about</a>.".to_owned(),
+	///         ..Default::default()
+	///     },
+	/// );
+	///
+	/// link.on().nm_click(|p: &w::NMLINK| {
+	///     println!("Clicked: id \"{}\", URL \"{}\"", p.item.szID(), p.item.szUrl());
+	///     Ok(())
+	/// });
+	/// ```
+	#[must_use]
+	pub fn new(parent: &(impl GuiParent + 'static), opts: SysLinkOpts) -> Self {
+		let ctrl_id = auto_id::set_if_zero(opts.ctrl_id);
+		let new_self = Self(Arc::pin(SysLinkObj {
+			base: BaseCtrl::new(ctrl_id),
+			events: SysLinkEvents::new(parent, ctrl_id),
+			_pin: PhantomPinned,
+		}));
+
+		let self2 = new_self.clone();
+		let parent2 = parent.clone();
+		parent
+			.as_ref()
+			.before_on()
+			.wm(parent.as_ref().is_dlg().create_msg(), move |_| {
+				comctl_classes::register(co::ICC::LINK_CLASS)?;
+				self2.0.base.create_window(
+					opts.window_ex_style,
+					"SysLink",
+					Some(&opts.text),
+					opts.window_style | opts.control_style.into(),
+					opts.position.into(),
+					opts.size.into(),
+					&parent2,
+				)?;
+				ui_font::set(self2.hwnd())?;
+				parent2
+					.as_ref()
+					.add_to_layout(self2.hwnd(), opts.resize_behavior)?;
+				Ok(0) // ignored
+			});
+
+		new_self
+	}
+
+	/// Instantiates a new `SysLink` object, to be loaded from a dialog resource
+	/// with [`HWND::GetDlgItem`](crate::prelude::user_Hwnd::GetDlgItem).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent dialog was already created – that is, you cannot
+	/// dynamically create a `SysLink` in an event closure.
+	///
+	/// Panics if the SysLink window class cannot be registered.
+	#[must_use]
+	pub fn new_dlg(
+		parent: &(impl GuiParent + 'static),
+		ctrl_id: u16,
+		resize_behavior: (Horz, Vert),
+	) -> Self {
+		comctl_classes::register(co::ICC::LINK_CLASS)
+			.expect("SysLink window class could not be registered.");
+
+		let new_self = Self(Arc::pin(SysLinkObj {
+			base: BaseCtrl::new(ctrl_id),
+			events: SysLinkEvents::new(parent, ctrl_id),
+			_pin: PhantomPinned,
+		}));
+
+		let self2 = new_self.clone();
+		let parent2 = parent.clone();
+		parent.as_ref().before_on().wm_init_dialog(move |_| {
+			self2.0.base.assign_dlg(&parent2)?;
+			parent2
+				.as_ref()
+				.add_to_layout(self2.hwnd(), resize_behavior)?;
+			Ok(true) // ignored
+		});
+
+		new_self
+	}
+
+	/// Retrieves the preferred size of the control for the given maximum
+	/// width, by sending an [`lm::GetIdealSize`](crate::msg::lm::GetIdealSize)
+	/// message.
+	#[must_use]
+	pub fn ideal_size(&self, max_width: u32) -> SIZE {
+		let mut sz = SIZE::default();
+		unsafe {
+			self.hwnd()
+				.SendMessage(lm::GetIdealSize { max_width, size: &mut sz });
+		}
+		sz
+	}
+
+	/// Retrieves the ID, URL and state of the link at the given zero-based
+	/// index by sending an [`lm::GetItem`](crate::msg::lm::GetItem) message.
+	#[must_use]
+	pub fn link(&self, index: u32) -> SysResult<LITEM> {
+		let mut li = LITEM::default();
+		li.mask = co::LIF::ITEMINDEX | co::LIF::STATE | co::LIF::ITEMID | co::LIF::URL;
+		li.iLink = index as _;
+		li.stateMask = co::LIS::FOCUSED
+			| co::LIS::ENABLED
+			| co::LIS::VISITED
+			| co::LIS::HOTTRACK
+			| co::LIS::DEFAULTCOLORS;
+
+		unsafe {
+			self.hwnd().SendMessage(lm::GetItem { item: &mut li })?;
+		}
+		Ok(li)
+	}
+
+	/// Sets or clears the given states of the link at the given zero-based
+	/// index by sending an [`lm::SetItem`](crate::msg::lm::SetItem) message.
+	///
+	/// # Examples
+	///
+	/// Marking the first link as visited:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let link: gui::SysLink; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let link = gui::SysLink::new(&wnd, gui::SysLinkOpts::default());
+	///
+	/// link.set_link_state(0, co::LIS::VISITED, true)?;
+	/// # w::SysResult::Ok(())
+	/// ```
+	pub fn set_link_state(&self, index: u32, state: co::LIS, set: bool) -> SysResult<()> {
+		let mut li = LITEM::default();
+		li.mask = co::LIF::ITEMINDEX | co::LIF::STATE;
+		li.iLink = index as _;
+		li.state = if set { state } else { co::LIS::default() };
+		li.stateMask = state;
+
+		unsafe { self.hwnd().SendMessage(lm::SetItem { item: &li }) }
+	}
+
+	/// Sets the text by calling
+	/// [`HWND::SetWindowText`](crate::prelude::user_Hwnd::SetWindowText).
+	///
+	/// The links must be marked with `<a>` tags.
+	pub fn set_text(&self, text: &str) -> SysResult<()> {
+		self.hwnd().SetWindowText(text)
+	}
+
+	/// Retrieves the text, including the `<a>` tags, by calling
+	/// [`HWND::GetWindowText`](crate::prelude::user_Hwnd::GetWindowText).
+	#[must_use]
+	pub fn text(&self) -> SysResult<String> {
+		self.hwnd().GetWindowText()
+	}
+}
+
+/// Options to create a [`SysLink`](crate::gui::SysLink) programmatically with
+/// [`SysLink::new`](crate::gui::SysLink::new).
+pub struct SysLinkOpts {
+	/// Text of the control to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// The links must be marked with `<a>` tags, optionally with `href` and
+	/// `id` attributes.
+	///
+	/// Defaults to empty string.
+	pub text: String,
+	/// Left and top position coordinates of control within parent's client
+	/// area, to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `gui::dpi(0, 0)`.
+	pub position: (i32, i32),
+	/// Width and height of control to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `gui::dpi(200, 20)`.
+	pub size: (i32, i32),
+	/// SysLink styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `LWS::NoValue`.
+	pub control_style: co::LWS,
+	/// Window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS::CHILD | WS::GROUP | WS::TABSTOP | WS::VISIBLE`.
+	pub window_style: co::WS,
+	/// Extended window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS_EX::LEFT`.
+	pub window_ex_style: co::WS_EX,
+
+	/// The control ID.
+	///
+	/// Defaults to an auto-generated ID.
+	pub ctrl_id: u16,
+	/// Horizontal and vertical behavior of the control when the parent window
+	/// is resized.
+	///
+	/// Defaults to `(gui::Horz::None, gui::Vert::None)`.
+	pub resize_behavior: (Horz, Vert),
+}
+
+impl Default for SysLinkOpts {
+	fn default() -> Self {
+		Self {
+			text: "".to_owned(),
+			position: dpi(0, 0),
+			size: dpi(200, 20),
+			control_style: co::LWS::NoValue,
+			window_style: co::WS::CHILD | co::WS::GROUP | co::WS::TABSTOP | co::WS::VISIBLE,
+			window_ex_style: co::WS_EX::LEFT,
+			ctrl_id: 0,
+			resize_behavior: (Horz::None, Vert::None),
+		}
+	}
+}
@@ -0,0 +1,285 @@
+use std::any::Any;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::gui::{collections::*, events::*, privs::*};
+use crate::msg::*;
+use crate::prelude::*;
+
+struct ToolbarObj {
+	base: BaseCtrl,
+	events: ToolbarEvents,
+	_pin: PhantomPinned,
+}
+
+native_ctrl! { Toolbar: ToolbarObj => ToolbarEvents;
+	/// Native
+	/// [toolbar](https://learn.microsoft.com/en-us/windows/win32/controls/toolbar-control-overview)
+	/// control, which has one or more buttons.
+	///
+	/// Unless [`TBSTYLE::NOPARENTALIGN`](crate::co::TBSTYLE::NOPARENTALIGN) is
+	/// specified, the toolbar will align itself to the parent window, and it
+	/// will be automatically resized when the parent is resized.
+}
+
+impl Toolbar {
+	/// Instantiates a new `Toolbar` object, to be created on the parent window
+	/// with
+	/// [`HWND::CreateWindowEx`](crate::prelude::user_Hwnd::CreateWindowEx).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window was already created – that is, you cannot
+	/// dynamically create a `Toolbar` in an event closure.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	///
+	/// const ID_OPEN: u16 = 2001;
+	/// const ID_SAVE: u16 = 2002;
+	///
+	/// let toolbar = gui::Toolbar::new(
+	///     &wnd,
+	///     gui::ToolbarOpts {
+	///         buttons: vec![
+	///             (ID_OPEN, "&Open".to_owned(), None, co::BTNS::BUTTON),
+	///             (ID_SAVE, "&Save".to_owned(), None, co::BTNS::DROPDOWN),
+	///         ],
+	///         ..Default::default()
+	///     },
+	/// );
+	///
+	/// toolbar.on().bn_clicked(ID_OPEN, move || {
+	///     println!("Open clicked.");
+	///     Ok(())
+	/// });
+	/// ```
+	#[must_use]
+	pub fn new(parent: &(impl GuiParent + 'static), opts: ToolbarOpts) -> Self {
+		let ctrl_id = auto_id::set_if_zero(opts.ctrl_id);
+		let new_self = Self(Arc::pin(ToolbarObj {
+			base: BaseCtrl::new(ctrl_id),
+			events: ToolbarEvents::new(parent, ctrl_id),
+			_pin: PhantomPinned,
+		}));
+
+		let self2 = new_self.clone();
+		let parent2 = parent.clone();
+		parent
+			.as_ref()
+			.before_on()
+			.wm(parent.as_ref().is_dlg().create_msg(), move |_| {
+				self2.0.base.create_window(
+					opts.window_ex_style,
+					"ToolbarWindow32",
+					None,
+					opts.window_style | opts.control_style.into(),
+					POINT::default(),
+					SIZE::default(),
+					&parent2,
+				)?;
+				self2.init_struct_size();
+				if opts.control_ex_style != co::TBSTYLE_EX::NoValue {
+					self2.set_extended_style(true, opts.control_ex_style);
+				}
+				for (cmd_id, text, image, style) in opts.buttons.iter() {
+					if style.has(co::BTNS::SEP) {
+						self2.buttons().add_separator()?;
+					} else {
+						self2.buttons().add(*cmd_id, text, *image, *style)?;
+					}
+				}
+				self2.auto_size();
+				Ok(0) // ignored
+			});
+
+		new_self.default_message_handlers(parent);
+		new_self
+	}
+
+	/// Instantiates a new `Toolbar` object, to be loaded from a dialog
+	/// resource with
+	/// [`HWND::GetDlgItem`](crate::prelude::user_Hwnd::GetDlgItem).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent dialog was already created – that is, you cannot
+	/// dynamically create a `Toolbar` in an event closure.
+	#[must_use]
+	pub fn new_dlg(parent: &(impl GuiParent + 'static), ctrl_id: u16) -> Self {
+		let new_self = Self(Arc::pin(ToolbarObj {
+			base: BaseCtrl::new(ctrl_id),
+			events: ToolbarEvents::new(parent, ctrl_id),
+			_pin: PhantomPinned,
+		}));
+
+		let self2 = new_self.clone();
+		let parent2 = parent.clone();
+		parent.as_ref().before_on().wm_init_dialog(move |_| {
+			self2.0.base.assign_dlg(&parent2)?;
+			self2.init_struct_size();
+			Ok(true) // ignored
+		});
+
+		new_self.default_message_handlers(parent);
+		new_self
+	}
+
+	fn init_struct_size(&self) {
+		unsafe {
+			self.hwnd().SendMessage(tbm::ButtonStructSize {
+				size: std::mem::size_of::<TBBUTTON>() as _,
+			});
+		}
+	}
+
+	fn default_message_handlers(&self, parent: &impl AsRef<BaseWnd>) {
+		let self2 = self.clone();
+		parent.as_ref().before_on().wm_size(move |p| {
+			if p.request != co::SIZE_R::MINIMIZED && *self2.hwnd() != HWND::NULL {
+				self2.auto_size(); // realign to parent
+			}
+			Ok(())
+		});
+
+		let self2 = self.clone();
+		parent.as_ref().after_on().wm_destroy(move || {
+			if let Some(hil) = self2.image_list() {
+				let _ = unsafe { ImageListDestroyGuard::new(hil.raw_copy()) }; // destroy the image list
+			}
+			Ok(())
+		});
+	}
+
+	/// Resizes the toolbar to fit its buttons, realigning it to the parent
+	/// window, by sending a [`tbm::AutoSize`](crate::msg::tbm::AutoSize)
+	/// message.
+	///
+	/// This method is automatically called when the parent window is resized.
+	pub fn auto_size(&self) {
+		unsafe {
+			self.hwnd().SendMessage(tbm::AutoSize {});
+		}
+	}
+
+	/// Button methods.
+	#[must_use]
+	pub const fn buttons(&self) -> ToolbarButtons<'_> {
+		ToolbarButtons::new(self)
+	}
+
+	/// Retrieves the extended toolbar styles by sending a
+	/// [`tbm::GetExtendedStyle`](crate::msg::tbm::GetExtendedStyle) message.
+	#[must_use]
+	pub fn extended_style(&self) -> co::TBSTYLE_EX {
+		unsafe { self.hwnd().SendMessage(tbm::GetExtendedStyle {}) }
+	}
+
+	/// Retrieves the image list by sending a
+	/// [`tbm::GetImageList`](crate::msg::tbm::GetImageList) message.
+	///
+	/// The image list is owned by the control.
+	#[must_use]
+	pub fn image_list(&self) -> Option<&HIMAGELIST> {
+		unsafe { self.hwnd().SendMessage(tbm::GetImageList {}) }.map(|hil| {
+			let hil_ptr = &hil as *const HIMAGELIST;
+			unsafe { &*hil_ptr }
+		})
+	}
+
+	/// Sets or unsets the given extended toolbar styles by sending
+	/// [`tbm::GetExtendedStyle`](crate::msg::tbm::GetExtendedStyle) and
+	/// [`tbm::SetExtendedStyle`](crate::msg::tbm::SetExtendedStyle) messages.
+	pub fn set_extended_style(&self, set: bool, ex_style: co::TBSTYLE_EX) {
+		let cur_style = self.extended_style();
+		unsafe {
+			self.hwnd().SendMessage(tbm::SetExtendedStyle {
+				style: if set { cur_style | ex_style } else { cur_style & !ex_style },
+			});
+		}
+	}
+
+	/// Sets the image list by sending a
+	/// [`tbm::SetImageList`](crate::msg::tbm::SetImageList) message, and
+	/// resizes the toolbar.
+	///
+	/// The image list will be owned by the control. Returns the previous one,
+	/// if any.
+	pub fn set_image_list(
+		&self,
+		himagelist: ImageListDestroyGuard,
+	) -> Option<ImageListDestroyGuard> {
+		let mut himagelist = himagelist;
+		let hil = himagelist.leak();
+
+		let prev = unsafe {
+			self.hwnd()
+				.SendMessage(tbm::SetImageList { himagelist: hil })
+				.map(|prev_hil| ImageListDestroyGuard::new(prev_hil))
+		};
+		self.auto_size();
+		prev
+	}
+}
+
+/// Options to create a [`Toolbar`](crate::gui::Toolbar) programmatically with
+/// [`Toolbar::new`](crate::gui::Toolbar::new).
+pub struct ToolbarOpts {
+	/// Toolbar styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `TBSTYLE::FLAT | TBSTYLE::TOOLTIPS`.
+	pub control_style: co::TBSTYLE,
+	/// Extended toolbar styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `TBSTYLE_EX::DRAWDDARROWS`.
+	pub control_ex_style: co::TBSTYLE_EX,
+	/// Window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS::CHILD | WS::VISIBLE`.
+	pub window_style: co::WS,
+	/// Extended window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS_EX::LEFT`.
+	pub window_ex_style: co::WS_EX,
+
+	/// The control ID.
+	///
+	/// Defaults to an auto-generated ID.
+	pub ctrl_id: u16,
+
+	/// Command ID, text, zero-based image index and style of the buttons to be
+	/// added. A button with [`BTNS::SEP`](crate::co::BTNS::SEP) style is added
+	/// as a separator, ignoring the other values.
+	///
+	/// The images are taken from the image list set with
+	/// [`Toolbar::set_image_list`](crate::gui::Toolbar::set_image_list).
+	///
+	/// Defaults to none.
+	pub buttons: Vec<(u16, String, Option<u32>, co::BTNS)>,
+}
+
+impl Default for ToolbarOpts {
+	fn default() -> Self {
+		Self {
+			control_style: co::TBSTYLE::FLAT | co::TBSTYLE::TOOLTIPS,
+			control_ex_style: co::TBSTYLE_EX::DRAWDDARROWS,
+			window_style: co::WS::CHILD | co::WS::VISIBLE,
+			window_ex_style: co::WS_EX::LEFT,
+			ctrl_id: 0,
+			buttons: Vec::new(),
+		}
+	}
+}
@@ -0,0 +1,248 @@
+use crate::co;
+use crate::decl::*;
+use crate::gui::*;
+use crate::msg::*;
+use crate::prelude::*;
+
+/// A single button of a [`Toolbar`](crate::gui::Toolbar) control.
+///
+/// **Note:** Each object keeps the zero-based index of a button. If new buttons
+/// are added/removed from the toolbar control, the object may then point to a
+/// different button.
+///
+/// You cannot directly instantiate this object, it is created internally by the
+/// control.
+#[derive(Clone, Copy)]
+pub struct ToolbarButton<'a> {
+	owner: &'a Toolbar,
+	index: u32,
+}
+
+impl<'a> ToolbarButton<'a> {
+	#[must_use]
+	pub(in crate::gui) const fn new(owner: &'a Toolbar, index: u32) -> Self {
+		Self { owner, index }
+	}
+
+	/// Checks or unchecks the button by sending a
+	/// [`tbm::CheckButton`](crate::msg::tbm::CheckButton) message.
+	///
+	/// Returns the same button, so further operations can be chained.
+	pub fn check(&self, check: bool) -> SysResult<Self> {
+		unsafe {
+			self.owner
+				.hwnd()
+				.SendMessage(tbm::CheckButton { btn_cmd_id: self.cmd_id()?, check })?;
+		}
+		Ok(*self)
+	}
+
+	/// Retrieves the command ID of the button by sending a
+	/// [`tbm::GetButton`](crate::msg::tbm::GetButton) message.
+	#[must_use]
+	pub fn cmd_id(&self) -> SysResult<u16> {
+		Ok(self.info()?.idCommand as _)
+	}
+
+	/// Deletes the button by sending a
+	/// [`tbm::DeleteButton`](crate::msg::tbm::DeleteButton) message.
+	pub fn delete(&self) -> SysResult<()> {
+		unsafe {
+			self.owner
+				.hwnd()
+				.SendMessage(tbm::DeleteButton { btn_index: self.index })
+		}
+	}
+
+	/// Enables or disables the button by sending a
+	/// [`tbm::EnableButton`](crate::msg::tbm::EnableButton) message.
+	///
+	/// Returns the same button, so further operations can be chained.
+	pub fn enable(&self, enable: bool) -> SysResult<Self> {
+		unsafe {
+			self.owner
+				.hwnd()
+				.SendMessage(tbm::EnableButton { btn_cmd_id: self.cmd_id()?, enable })?;
+		}
+		Ok(*self)
+	}
+
+	/// Hides or shows the button by sending a
+	/// [`tbm::HideButton`](crate::msg::tbm::HideButton) message.
+	///
+	/// Returns the same button, so further operations can be chained.
+	pub fn hide(&self, hide: bool) -> SysResult<Self> {
+		unsafe {
+			self.owner
+				.hwnd()
+				.SendMessage(tbm::HideButton { cmd_id: self.cmd_id()?, hide })?;
+		}
+		Ok(*self)
+	}
+
+	/// Retrieves the zero-based index of the image in the toolbar image list,
+	/// if any, by sending a [`tbm::GetButton`](crate::msg::tbm::GetButton)
+	/// message.
+	#[must_use]
+	pub fn image(&self) -> SysResult<Option<u32>> {
+		let tbb = self.info()?;
+		Ok(if tbb.iBitmap < 0 || tbb.fsStyle.has(co::BTNS::SEP) {
+			None
+		} else {
+			Some(tbb.iBitmap as _)
+		})
+	}
+
+	/// Returns the zero-based index of the button.
+	#[must_use]
+	pub const fn index(&self) -> u32 {
+		self.index
+	}
+
+	/// Tells whether the button is checked by sending a
+	/// [`tbm::IsButtonChecked`](crate::msg::tbm::IsButtonChecked) message.
+	#[must_use]
+	pub fn is_checked(&self) -> SysResult<bool> {
+		let cmd_id = self.cmd_id()?;
+		Ok(unsafe {
+			self.owner
+				.hwnd()
+				.SendMessage(tbm::IsButtonChecked { cmd_id })
+		})
+	}
+
+	/// Tells whether the button is enabled by sending a
+	/// [`tbm::IsButtonEnabled`](crate::msg::tbm::IsButtonEnabled) message.
+	#[must_use]
+	pub fn is_enabled(&self) -> SysResult<bool> {
+		let cmd_id = self.cmd_id()?;
+		Ok(unsafe {
+			self.owner
+				.hwnd()
+				.SendMessage(tbm::IsButtonEnabled { cmd_id })
+		})
+	}
+
+	/// Tells whether the button is hidden by sending a
+	/// [`tbm::IsButtonHidden`](crate::msg::tbm::IsButtonHidden) message.
+	#[must_use]
+	pub fn is_hidden(&self) -> SysResult<bool> {
+		let cmd_id = self.cmd_id()?;
+		Ok(unsafe {
+			self.owner
+				.hwnd()
+				.SendMessage(tbm::IsButtonHidden { cmd_id })
+		})
+	}
+
+	/// Retrieves the bounding rectangle of the button, relative to the
+	/// toolbar, by sending a [`tbm::GetItemRect`](crate::msg::tbm::GetItemRect)
+	/// message.
+	///
+	/// This is useful to display a popup menu below a dropdown button.
+	#[must_use]
+	pub fn rect(&self) -> SysResult<RECT> {
+		let mut rc = RECT::default();
+		unsafe {
+			self.owner
+				.hwnd()
+				.SendMessage(tbm::GetItemRect { btn_index: self.index, rect: &mut rc })?;
+		}
+		Ok(rc)
+	}
+
+	/// Sets the zero-based index of the image in the toolbar image list, or
+	/// removes it, by sending a
+	/// [`tbm::SetButtonInfo`](crate::msg::tbm::SetButtonInfo) message.
+	///
+	/// Returns the same button, so further operations can be chained.
+	pub fn set_image(&self, image: Option<u32>) -> SysResult<Self> {
+		let mut tbbi = TBBUTTONINFO::default();
+		tbbi.dwMask = co::TBIF::IMAGE | co::TBIF::BYINDEX;
+		tbbi.iImage = isize::from(image.map_or(IdxCbNone::None, IdxCbNone::Idx)) as _;
+
+		unsafe {
+			self.owner.hwnd().SendMessage(tbm::SetButtonInfo {
+				btn_cmd_id: self.index as _, // TBIF::BYINDEX
+				info: &tbbi,
+			})?;
+		}
+		Ok(*self)
+	}
+
+	/// Sets the state flags by sending a
+	/// [`tbm::SetState`](crate::msg::tbm::SetState) message.
+	///
+	/// Returns the same button, so further operations can be chained.
+	pub fn set_state(&self, state: co::TBSTATE) -> SysResult<Self> {
+		unsafe {
+			self.owner
+				.hwnd()
+				.SendMessage(tbm::SetState { btn_cmd_id: self.cmd_id()?, state })?;
+		}
+		Ok(*self)
+	}
+
+	/// Sets the text by sending a
+	/// [`tbm::SetButtonInfo`](crate::msg::tbm::SetButtonInfo) message.
+	///
+	/// Returns the same button, so further operations can be chained.
+	pub fn set_text(&self, text: &str) -> SysResult<Self> {
+		let mut wtext = WString::from_str(text);
+		let mut tbbi = TBBUTTONINFO::default();
+		tbbi.dwMask = co::TBIF::TEXT | co::TBIF::BYINDEX;
+		tbbi.set_pszText(Some(&mut wtext));
+
+		unsafe {
+			self.owner.hwnd().SendMessage(tbm::SetButtonInfo {
+				btn_cmd_id: self.index as _, // TBIF::BYINDEX
+				info: &tbbi,
+			})?;
+		}
+		self.owner.auto_size(); // button width may have changed
+		Ok(*self)
+	}
+
+	/// Retrieves the state flags by sending a
+	/// [`tbm::GetButton`](crate::msg::tbm::GetButton) message.
+	#[must_use]
+	pub fn state(&self) -> SysResult<co::TBSTATE> {
+		Ok(self.info()?.fsState)
+	}
+
+	/// Retrieves the style flags by sending a
+	/// [`tbm::GetButton`](crate::msg::tbm::GetButton) message.
+	#[must_use]
+	pub fn style(&self) -> SysResult<co::BTNS> {
+		Ok(self.info()?.fsStyle)
+	}
+
+	/// Retrieves the text by sending a
+	/// [`tbm::GetButtonInfo`](crate::msg::tbm::GetButtonInfo) message.
+	#[must_use]
+	pub fn text(&self) -> SysResult<String> {
+		let mut buf = WString::new_alloc_buf(128); // arbitrary
+		let mut tbbi = TBBUTTONINFO::default();
+		tbbi.dwMask = co::TBIF::TEXT | co::TBIF::BYINDEX;
+		tbbi.set_pszText(Some(&mut buf));
+
+		unsafe {
+			self.owner.hwnd().SendMessage(tbm::GetButtonInfo {
+				btn_cmd_id: self.index as _, // TBIF::BYINDEX
+				info: &mut tbbi,
+			})?;
+		}
+		Ok(buf.to_string())
+	}
+
+	#[must_use]
+	fn info(&self) -> SysResult<TBBUTTON<'_>> {
+		let mut tbb = TBBUTTON::default();
+		unsafe {
+			self.owner
+				.hwnd()
+				.SendMessage(tbm::GetButton { btn_index: self.index, info: &mut tbb })?;
+		}
+		Ok(tbb)
+	}
+}
@@ -0,0 +1,163 @@
+use crate::co;
+use crate::decl::*;
+use crate::gui::{iterators::*, *};
+use crate::msg::*;
+use crate::prelude::*;
+
+/// Exposes the button methods of a [`Toolbar`](crate::gui::Toolbar) control.
+///
+/// You cannot directly instantiate this object, it is created internally by the
+/// control.
+pub struct ToolbarButtons<'a> {
+	owner: &'a Toolbar,
+}
+
+impl<'a> ToolbarButtons<'a> {
+	#[must_use]
+	pub(in crate::gui) const fn new(owner: &'a Toolbar) -> Self {
+		Self { owner }
+	}
+
+	/// Appends a new button by sending a
+	/// [`tbm::AddButtons`](crate::msg::tbm::AddButtons) message, and returns
+	/// the newly added button.
+	///
+	/// The `image` is the zero-based index in the toolbar image list, if any.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let my_toolbar: gui::Toolbar; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let my_toolbar = gui::Toolbar::new(&wnd, gui::ToolbarOpts::default());
+	///
+	/// const ID_BOLD: u16 = 3001;
+	///
+	/// let btn = my_toolbar.buttons().add(ID_BOLD, "Bold", Some(0), co::BTNS::CHECK)?;
+	/// btn.check(true)?;
+	/// # w::SysResult::Ok(())
+	/// ```
+	pub fn add(
+		&self,
+		cmd_id: u16,
+		text: &str,
+		image: Option<u32>,
+		style: co::BTNS,
+	) -> SysResult<ToolbarButton<'a>> {
+		let mut wtext = IdxStr::Str(WString::from_str(text));
+		let mut tbb = Self::new_button(cmd_id, image, style);
+		tbb.set_iString(&mut wtext);
+
+		unsafe {
+			self.owner
+				.hwnd()
+				.SendMessage(tbm::AddButtons { buttons: &mut [tbb] })?;
+		}
+		Ok(self.get(self.count() - 1))
+	}
+
+	/// Appends a new separator by sending a
+	/// [`tbm::AddButtons`](crate::msg::tbm::AddButtons) message, and returns
+	/// the newly added separator.
+	pub fn add_separator(&self) -> SysResult<ToolbarButton<'a>> {
+		let mut tbb = Self::new_button(0, None, co::BTNS::SEP);
+		tbb.iBitmap = 0; // separator default width
+		unsafe {
+			self.owner
+				.hwnd()
+				.SendMessage(tbm::AddButtons { buttons: &mut [tbb] })?;
+		}
+		Ok(self.get(self.count() - 1))
+	}
+
+	/// Retrieves the button with the given command ID, if any, by sending a
+	/// [`tbm::CommandToIndex`](crate::msg::tbm::CommandToIndex) message.
+	#[must_use]
+	pub fn by_cmd_id(&self, cmd_id: u16) -> Option<ToolbarButton<'a>> {
+		unsafe {
+			self.owner
+				.hwnd()
+				.SendMessage(tbm::CommandToIndex { btn_cmd_id: cmd_id })
+		}
+		.map(|idx| self.get(idx))
+	}
+
+	/// Retrieves the total number of buttons, including separators, by sending
+	/// a [`tbm::ButtonCount`](crate::msg::tbm::ButtonCount) message.
+	#[must_use]
+	pub fn count(&self) -> u32 {
+		unsafe { self.owner.hwnd().SendMessage(tbm::ButtonCount {}) }
+	}
+
+	/// Deletes all buttons by sending
+	/// [`tbm::DeleteButton`](crate::msg::tbm::DeleteButton) messages.
+	pub fn delete_all(&self) -> SysResult<()> {
+		for _ in 0..self.count() {
+			self.get(0).delete()?;
+		}
+		Ok(())
+	}
+
+	/// Retrieves the button at the given zero-based position.
+	///
+	/// **Note:** This method is cheap – even if `index` is beyond the range of
+	/// existing buttons, an object will still be returned. However, operations
+	/// upon this object will fail.
+	#[must_use]
+	pub const fn get(&self, index: u32) -> ToolbarButton<'a> {
+		ToolbarButton::new(self.owner, index)
+	}
+
+	/// Inserts a new button at the given zero-based position by sending a
+	/// [`tbm::InsertButton`](crate::msg::tbm::InsertButton) message, and
+	/// returns the newly inserted button.
+	///
+	/// The `image` is the zero-based index in the toolbar image list, if any.
+	pub fn insert(
+		&self,
+		index: u32,
+		cmd_id: u16,
+		text: &str,
+		image: Option<u32>,
+		style: co::BTNS,
+	) -> SysResult<ToolbarButton<'a>> {
+		let mut wtext = IdxStr::Str(WString::from_str(text));
+		let mut tbb = Self::new_button(cmd_id, image, style);
+		tbb.set_iString(&mut wtext);
+
+		unsafe {
+			self.owner
+				.hwnd()
+				.SendMessage(tbm::InsertButton { index, button: &tbb })?;
+		}
+		Ok(self.get(index))
+	}
+
+	/// Returns an iterator over all buttons, including separators.
+	#[must_use]
+	pub fn iter(&self) -> impl DoubleEndedIterator<Item = ToolbarButton<'a>> + 'a {
+		ToolbarButtonIter::new(self.owner)
+	}
+
+	/// Returns the last button, if any.
+	pub fn last(&self) -> Option<ToolbarButton<'a>> {
+		let count = self.count();
+		if count > 0 {
+			Some(self.get(count - 1))
+		} else {
+			None
+		}
+	}
+
+	#[must_use]
+	fn new_button<'b>(cmd_id: u16, image: Option<u32>, style: co::BTNS) -> TBBUTTON<'b> {
+		let mut tbb = TBBUTTON::default();
+		tbb.iBitmap = isize::from(image.map_or(IdxCbNone::None, IdxCbNone::Idx)) as _;
+		tbb.idCommand = cmd_id as _;
+		tbb.fsState = co::TBSTATE::ENABLED;
+		tbb.fsStyle = style;
+		tbb
+	}
+}
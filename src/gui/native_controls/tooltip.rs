@@ -0,0 +1,346 @@
+use std::any::Any;
+use std::cell::UnsafeCell;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::gui::{events::*, privs::*};
+use crate::msg::*;
+use crate::prelude::*;
+
+type DynTextFn = Box<dyn Fn() -> AnyResult<String>>;
+
+struct TooltipObj {
+	base: BaseCtrl,
+	dyn_texts: UnsafeCell<Vec<(HWND, DynTextFn)>>, // tools with TTN_GETDISPINFO text
+	dyn_buf: UnsafeCell<WString>,                  // text returned in the last TTN_GETDISPINFO
+	_pin: PhantomPinned,
+}
+
+native_ctrl! { Tooltip: TooltipObj;
+	/// Native
+	/// [tooltip](https://learn.microsoft.com/en-us/windows/win32/controls/tooltip-controls)
+	/// control, which displays a small popup text when the mouse hovers other
+	/// controls.
+	///
+	/// A single `Tooltip` can serve any number of controls of the same parent
+	/// window. Each control is registered as a tool with
+	/// [`add_tool`](crate::gui::Tooltip::add_tool), or with the
+	/// [`GuiControl::set_tooltip`](crate::prelude::GuiControl::set_tooltip)
+	/// convenience method.
+}
+
+impl Tooltip {
+	/// Instantiates a new `Tooltip` object, to be created on the parent window
+	/// with
+	/// [`HWND::CreateWindowEx`](crate::prelude::user_Hwnd::CreateWindowEx).
+	///
+	/// The tools can be added once the parent window is created, that is,
+	/// within [`wm_create`](crate::gui::events::WindowEvents::wm_create) or
+	/// [`wm_init_dialog`](crate::gui::events::WindowEvents::wm_init_dialog).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window was already created – that is, you cannot
+	/// dynamically create a `Tooltip` in an event closure.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// let btn: gui::Button;
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let btn = gui::Button::new(&wnd, gui::ButtonOpts::default());
+	///
+	/// let tooltip = gui::Tooltip::new(
+	///     &wnd,
+	///     gui::TooltipOpts {
+	///         control_style: co::TTS::ALWAYSTIP | co::TTS::NOPREFIX | co::TTS::BALLOON,
+	///         max_tip_width: Some(300), // enables multi-line text
+	///         ..Default::default()
+	///     },
+	/// );
+	///
+	/// let btn2 = btn.clone();
+	/// let tooltip2 = tooltip.clone();
+	/// wnd.on().wm_create(move |_| {
+	///     btn2.set_tooltip(&tooltip2, "Saves the file.\nShortcut: Ctrl+S")?;
+	///     Ok(0)
+	/// });
+	/// ```
+	#[must_use]
+	pub fn new(parent: &(impl GuiParent + 'static), opts: TooltipOpts) -> Self {
+		let ctrl_id = auto_id::set_if_zero(opts.ctrl_id);
+		let new_self = Self(Arc::pin(TooltipObj {
+			base: BaseCtrl::new(ctrl_id),
+			dyn_texts: UnsafeCell::new(Vec::new()),
+			dyn_buf: UnsafeCell::new(WString::new()),
+			_pin: PhantomPinned,
+		}));
+
+		let self2 = new_self.clone();
+		let parent2 = parent.clone();
+		parent
+			.as_ref()
+			.before_on()
+			.wm(parent.as_ref().is_dlg().create_msg(), move |_| {
+				self2.0.base.create_window(
+					opts.window_ex_style,
+					"tooltips_class32",
+					None,
+					opts.window_style | opts.control_style.into(),
+					POINT::default(),
+					SIZE::default(),
+					&parent2,
+				)?;
+				if opts.max_tip_width.is_some() {
+					self2.set_max_tip_width(opts.max_tip_width);
+				}
+				Ok(0) // ignored
+			});
+
+		new_self.default_message_handlers(parent);
+		new_self
+	}
+
+	fn default_message_handlers(&self, parent: &impl AsRef<BaseWnd>) {
+		let self2 = self.clone();
+		parent.as_ref().before_on().wm(co::WM::NOTIFY, move |p| {
+			let wm_nfy = unsafe { wm::Notify::from_generic_wm(p) };
+			if wm_nfy.nmhdr.hwndFrom != *self2.hwnd()
+				|| wm_nfy.nmhdr.code != co::TTN::GETDISPINFO.into()
+			{
+				return Ok(0); // not ours
+			}
+
+			let hctrl = wm_nfy.nmhdr.idFrom_hwnd();
+			let dyn_texts = unsafe { &*self2.0.dyn_texts.get() };
+			if let Some((_, func)) = dyn_texts.iter().find(|(h, _)| *h == hctrl) {
+				let buf = unsafe { &mut *self2.0.dyn_buf.get() };
+				*buf = WString::from_str(&func()?);
+				let nmtdi = unsafe { wm_nfy.cast_nmhdr_mut::<NMTTDISPINFO>() };
+				nmtdi.set_lpszText(Some(buf));
+			}
+			Ok(0) // ignored
+		});
+	}
+
+	/// Activates or deactivates the tooltip by sending a
+	/// [`ttm::Activate`](crate::msg::ttm::Activate) message.
+	///
+	/// A deactivated tooltip won't be displayed.
+	pub fn activate(&self, activate: bool) {
+		unsafe {
+			self.hwnd().SendMessage(ttm::Activate { activate });
+		}
+	}
+
+	/// Registers the control as a tool, with the given text, by sending a
+	/// [`ttm::AddTool`](crate::msg::ttm::AddTool) message.
+	///
+	/// Line breaks are displayed only if a maximum width was set with
+	/// [`set_max_tip_width`](crate::gui::Tooltip::set_max_tip_width).
+	pub fn add_tool(&self, ctrl: &impl GuiControl, text: &str) -> SysResult<()> {
+		self.add_tool_hwnd(ctrl.hwnd(), text)
+	}
+
+	/// Registers the control as a tool whose text is retrieved by calling the
+	/// given closure each time the tooltip is about to be displayed, by sending
+	/// a [`ttm::AddTool`](crate::msg::ttm::AddTool) message.
+	///
+	/// Internally, the closure is called upon
+	/// [`TTN_GETDISPINFO`](https://learn.microsoft.com/en-us/windows/win32/controls/ttn-getdispinfo)
+	/// notifications.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, gui};
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// let txt: gui::Edit;
+	/// let tooltip: gui::Tooltip;
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let txt = gui::Edit::new(&wnd, gui::EditOpts::default());
+	/// # let tooltip = gui::Tooltip::new(&wnd, gui::TooltipOpts::default());
+	///
+	/// let txt2 = txt.clone();
+	/// tooltip.add_tool_dyn(&txt, move || {
+	///     Ok(format!("{} chars typed.", txt2.text()?.len()))
+	/// })?;
+	/// # w::SysResult::Ok(())
+	/// ```
+	pub fn add_tool_dyn<F>(&self, ctrl: &impl GuiControl, func: F) -> SysResult<()>
+	where
+		F: Fn() -> AnyResult<String> + 'static,
+	{
+		let mut ti = self.tool_info(ctrl.hwnd())?;
+		ti.set_lpszText_callback();
+		unsafe {
+			self.hwnd().SendMessage(ttm::AddTool { info: &ti })?;
+		}
+		self.remove_dyn(ctrl.hwnd());
+		unsafe { &mut *self.0.dyn_texts.get() }
+			.push((unsafe { ctrl.hwnd().raw_copy() }, Box::new(func)));
+		Ok(())
+	}
+
+	/// Unregisters the control as a tool by sending a
+	/// [`ttm::DelTool`](crate::msg::ttm::DelTool) message.
+	pub fn del_tool(&self, ctrl: &impl GuiControl) -> SysResult<()> {
+		let ti = self.tool_info(ctrl.hwnd())?;
+		unsafe {
+			self.hwnd().SendMessage(ttm::DelTool { info: &ti });
+		}
+		self.remove_dyn(ctrl.hwnd());
+		Ok(())
+	}
+
+	/// Sets the maximum width of the tooltip, in pixels, by sending a
+	/// [`ttm::SetMaxTipWidth`](crate::msg::ttm::SetMaxTipWidth) message.
+	///
+	/// Setting a width enables multi-line tooltips: the text is broken at line
+	/// breaks and at word boundaries. If `None`, any width is allowed and the
+	/// text is displayed in a single line.
+	///
+	/// Returns the previous width.
+	pub fn set_max_tip_width(&self, width: Option<u32>) -> Option<u32> {
+		unsafe { self.hwnd().SendMessage(ttm::SetMaxTipWidth { width }) }
+	}
+
+	/// Sets the given delay time, in milliseconds, by sending a
+	/// [`ttm::SetDelayTime`](crate::msg::ttm::SetDelayTime) message.
+	///
+	/// If `time_ms` is `None`, the default delay time is restored.
+	pub fn set_delay_time(&self, which: co::TTDT, time_ms: Option<u16>) {
+		unsafe {
+			self.hwnd()
+				.SendMessage(ttm::SetDelayTime { which, time_ms });
+		}
+	}
+
+	/// Sets the title and the icon displayed above the text by sending a
+	/// [`ttm::SetTitle`](crate::msg::ttm::SetTitle) message.
+	pub fn set_title(&self, icon: co::TTI, title: &str) -> SysResult<()> {
+		unsafe {
+			self.hwnd()
+				.SendMessage(ttm::SetTitle { icon, title: WString::from_str(title) })
+		}
+	}
+
+	/// Sets the text of a control already registered as a tool by sending a
+	/// [`ttm::UpdateTipText`](crate::msg::ttm::UpdateTipText) message.
+	///
+	/// If the control had its text retrieved by a closure, the closure is
+	/// discarded.
+	pub fn set_tool_text(&self, ctrl: &impl GuiControl, text: &str) -> SysResult<()> {
+		self.set_tool_text_hwnd(ctrl.hwnd(), text)
+	}
+
+	/// Retrieves the number of registered tools by sending a
+	/// [`ttm::GetToolCount`](crate::msg::ttm::GetToolCount) message.
+	#[must_use]
+	pub fn tool_count(&self) -> u32 {
+		unsafe { self.hwnd().SendMessage(ttm::GetToolCount {}) }
+	}
+
+	/// Registers the window as a tool, or updates its text if it's already
+	/// registered.
+	pub(in crate::gui) fn add_or_update_tool_hwnd(
+		&self,
+		hctrl: &HWND,
+		text: &str,
+	) -> SysResult<()> {
+		let mut ti = self.tool_info(hctrl)?;
+		if unsafe { self.hwnd().SendMessage(ttm::GetToolInfo { info: &mut ti }) }.is_ok() {
+			self.set_tool_text_hwnd(hctrl, text)
+		} else {
+			self.add_tool_hwnd(hctrl, text)
+		}
+	}
+
+	fn add_tool_hwnd(&self, hctrl: &HWND, text: &str) -> SysResult<()> {
+		let mut wtext = WString::from_str(text);
+		let mut ti = self.tool_info(hctrl)?;
+		ti.set_lpszText(Some(&mut wtext));
+		unsafe {
+			self.hwnd().SendMessage(ttm::AddTool { info: &ti })?;
+		}
+		self.remove_dyn(hctrl);
+		Ok(())
+	}
+
+	fn set_tool_text_hwnd(&self, hctrl: &HWND, text: &str) -> SysResult<()> {
+		let mut wtext = WString::from_str(text);
+		let mut ti = self.tool_info(hctrl)?;
+		ti.set_lpszText(Some(&mut wtext));
+		unsafe {
+			self.hwnd().SendMessage(ttm::UpdateTipText { info: &ti });
+		}
+		self.remove_dyn(hctrl);
+		Ok(())
+	}
+
+	fn remove_dyn(&self, hctrl: &HWND) {
+		unsafe { &mut *self.0.dyn_texts.get() }.retain(|(h, _)| h != hctrl);
+	}
+
+	#[must_use]
+	fn tool_info<'a>(&self, hctrl: &HWND) -> SysResult<TTTOOLINFO<'a>> {
+		let mut ti = TTTOOLINFO::default();
+		ti.uFlags = co::TTF::IDISHWND | co::TTF::SUBCLASS;
+		ti.hwnd = self.hwnd().GetParent()?; // the owner, which receives TTN_GETDISPINFO
+		ti.uId = hctrl.ptr() as _;
+		Ok(ti)
+	}
+}
+
+/// Options to create a [`Tooltip`](crate::gui::Tooltip) programmatically with
+/// [`Tooltip::new`](crate::gui::Tooltip::new).
+pub struct TooltipOpts {
+	/// Tooltip styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Add [`TTS::BALLOON`](crate::co::TTS::BALLOON) to display the tooltip as
+	/// a cartoon-style balloon.
+	///
+	/// Defaults to `TTS::ALWAYSTIP | TTS::NOPREFIX`.
+	pub control_style: co::TTS,
+	/// Window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS::POPUP`.
+	pub window_style: co::WS,
+	/// Extended window styles to be
+	/// [created](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw).
+	///
+	/// Defaults to `WS_EX::TOPMOST`.
+	pub window_ex_style: co::WS_EX,
+
+	/// The control ID.
+	///
+	/// Defaults to an auto-generated ID.
+	pub ctrl_id: u16,
+
+	/// Maximum width of the tooltip, in pixels. Setting a width enables
+	/// multi-line tooltips.
+	///
+	/// Defaults to `None`.
+	pub max_tip_width: Option<u32>,
+}
+
+impl Default for TooltipOpts {
+	fn default() -> Self {
+		Self {
+			control_style: co::TTS::ALWAYSTIP | co::TTS::NOPREFIX,
+			window_style: co::WS::POPUP,
+			window_ex_style: co::WS_EX::TOPMOST,
+			ctrl_id: 0,
+			max_tip_width: None,
+		}
+	}
+}
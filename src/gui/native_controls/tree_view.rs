@@ -180,6 +180,23 @@ impl<T> TreeView<T> {
 		Ok(TreeViewItem::new(self, new_hitem))
 	}
 
+	/// Starts an OLE drag and drop operation with the given data, using the
+	/// default [`IDropSource`](crate::IDropSource) behavior. Usually called in
+	/// the [`tvn_begin_drag`](crate::gui::events::TreeViewEvents::tvn_begin_drag)
+	/// event.
+	///
+	/// This method will block until the operation ends. Returns the effect
+	/// performed by the drop target, or
+	/// [`DROPEFFECT::NONE`](crate::co::DROPEFFECT::NONE) if the operation was
+	/// cancelled.
+	pub fn do_drag_drop(
+		&self,
+		data: &DataObject,
+		ok_effects: co::DROPEFFECT,
+	) -> HrResult<co::DROPEFFECT> {
+		DoDragDrop(data, &IDropSource::new_impl(), ok_effects)
+	}
+
 	/// Retrieves a reference to one of the associated image lists by sending a
 	/// [`tvm::GetImageList`](crate::msg::tvm::GetImageList) message.
 	///
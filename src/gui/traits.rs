@@ -1,7 +1,8 @@
 use std::any::Any;
 
+use crate::co;
 use crate::decl::*;
-use crate::gui::{events::*, privs::*};
+use crate::gui::{events::*, privs::*, Grid, Tooltip};
 use crate::msg::*;
 use crate::prelude::*;
 
@@ -73,6 +74,44 @@ pub trait GuiParent: GuiWindow + Clone + AsRef<BaseWnd> {
 		self.as_ref().on()
 	}
 
+	/// Sets a [`Grid`](crate::gui::Grid) which will arrange its controls
+	/// whenever the window is resized, replacing any previous one. The controls
+	/// are immediately arranged within the current client area.
+	///
+	/// Since the controls must be already created, this method is usually
+	/// called within [`wm_create`](crate::gui::events::WindowEvents::wm_create)
+	/// or [`wm_init_dialog`](crate::gui::events::WindowEvents::wm_init_dialog).
+	/// The controls should have `(Horz::None, Vert::None)` as their resize
+	/// behavior.
+	///
+	/// # Panics
+	///
+	/// Panics if the window was not created yet.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, gui};
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// let lst: gui::ListBox;
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// # let lst = gui::ListBox::new(&wnd, gui::ListBoxOpts::default());
+	///
+	/// let wnd2 = wnd.clone();
+	/// wnd.on().wm_create(move |_| {
+	///     wnd2.set_grid(
+	///         gui::Grid::new(&[], &[])
+	///             .margin(gui::Margin::uniform(gui::dpi_x(10)))
+	///             .push(&lst),
+	///     )?;
+	///     Ok(0)
+	/// });
+	/// ```
+	fn set_grid(&self, grid: Grid) -> SysResult<()> {
+		self.as_ref().set_grid(grid)
+	}
+
 	/// This method calls [`std::thread::spawn`], but it allows the returning of
 	/// an error value. This error value will be forwarded to the original UI
 	/// thread, allowing it to be caught at
@@ -241,4 +280,132 @@ pub trait GuiControl: GuiWindow {
 		}
 		Ok(())
 	}
+
+	/// Sets the text displayed by the given [`Tooltip`](crate::gui::Tooltip)
+	/// when the mouse hovers this control, registering the control as a tool
+	/// if needed.
+	///
+	/// The control and the tooltip must have the same parent window, and both
+	/// must be already created.
+	fn set_tooltip(&self, tooltip: &Tooltip, text: &str) -> SysResult<()> {
+		tooltip.add_or_update_tool_hwnd(self.hwnd(), text)
+	}
+}
+
+/// Provides the data of a virtual [`ListView`](crate::gui::ListView) – that
+/// is, a list view created with [`LVS::OWNERDATA`](crate::co::LVS::OWNERDATA)
+/// style –, which queries the rows on demand, so only the visible ones are
+/// ever retrieved.
+///
+/// The data source is set with
+/// [`ListView::set_data_source`](crate::gui::ListView::set_data_source).
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co, gui};
+///
+/// struct LogRows {
+///     lines: Vec<(String, String)>,
+/// }
+///
+/// impl ListViewDataSource for LogRows {
+///     fn row_count(&self) -> u32 {
+///         self.lines.len() as _
+///     }
+///
+///     fn text(&self, row: u32, col: u32) -> String {
+///         let (time, msg) = &self.lines[row as usize];
+///         if col == 0 { time.clone() } else { msg.clone() }
+///     }
+/// }
+///
+/// let wnd: gui::WindowMain; // initialized somewhere
+/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+///
+/// let list = gui::ListView::<()>::new(
+///     &wnd,
+///     gui::ListViewOpts {
+///         control_style: co::LVS::REPORT | co::LVS::OWNERDATA,
+///         columns: vec![("Time".to_owned(), 120), ("Message".to_owned(), 400)],
+///         ..Default::default()
+///     },
+/// );
+///
+/// let list2 = list.clone();
+/// wnd.on().wm_create(move |_| {
+///     list2.set_data_source(LogRows { lines: Vec::new() })?;
+///     Ok(0)
+/// });
+/// ```
+pub trait ListViewDataSource {
+	/// Returns the total number of rows.
+	///
+	/// The control is informed of the number of rows when the data source is
+	/// set, and whenever
+	/// [`ListView::refresh_data`](crate::gui::ListView::refresh_data) is called.
+	#[must_use]
+	fn row_count(&self) -> u32;
+
+	/// Returns the text of the given cell.
+	#[must_use]
+	fn text(&self, row: u32, col: u32) -> String;
+
+	/// Returns the zero-based index of the row image, within the image list of
+	/// the current view, if any.
+	///
+	/// Defaults to `None`.
+	#[must_use]
+	fn image(&self, row: u32) -> Option<u32> {
+		let _ = row;
+		None
+	}
+
+	/// Returns the state of the row. Only the bits requested by the control are
+	/// used – usually [`LVIS::STATEIMAGEMASK`](crate::co::LVIS::STATEIMAGEMASK)
+	/// and [`LVIS::OVERLAYMASK`](crate::co::LVIS::OVERLAYMASK), since the
+	/// selection and focus are kept by the control itself.
+	///
+	/// Defaults to `LVIS::NoValue`.
+	#[must_use]
+	fn state(&self, row: u32) -> co::LVIS {
+		let _ = row;
+		co::LVIS::NoValue
+	}
+
+	/// Called when the control is about to display the given inclusive range of
+	/// rows, so they can be loaded in advance.
+	///
+	/// Defaults to doing nothing.
+	fn cache_hint(&self, first: u32, last: u32) {
+		let _ = (first, last);
+	}
+
+	/// Searches for the row whose text of the first column matches the given
+	/// text, starting at the given row, usually when the user types in the
+	/// control. If `partial`, the row text must only start with the given
+	/// text. If `wrap`, the search continues from the first row when reaching
+	/// the end.
+	///
+	/// Defaults to a case-insensitive linear search, calling
+	/// [`text`](crate::prelude::ListViewDataSource::text) for each row.
+	#[must_use]
+	fn find(&self, text: &str, start: u32, partial: bool, wrap: bool) -> Option<u32> {
+		let count = self.row_count();
+		let text = text.to_lowercase();
+		let rows = (start..count).chain(0..if wrap { start.min(count) } else { 0 });
+		for row in rows {
+			let row_text = self.text(row, 0).to_lowercase();
+			if (partial && row_text.starts_with(&text)) || row_text == text {
+				return Some(row);
+			}
+		}
+		None
+	}
 }
@@ -0,0 +1,310 @@
+use std::cell::UnsafeCell;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::co;
+use crate::decl::*;
+use crate::gui::{events::*, privs::*};
+use crate::msg::*;
+use crate::prelude::*;
+
+struct TrayIconObj {
+	id: u16,
+	callback_msg: co::WM,
+	taskbar_created_msg: co::WM,
+	hparent: UnsafeCell<HWND>,
+	hicon: UnsafeCell<HICON>,
+	tooltip: UnsafeCell<String>,
+	events: TrayIconEvents,
+	_pin: PhantomPinned,
+}
+
+/// An icon in the notification area of the taskbar, also known as system tray,
+/// which is managed through
+/// [`Shell_NotifyIcon`](crate::Shell_NotifyIcon).
+///
+/// The icon is added when the parent window is created, and removed when it's
+/// destroyed. If Explorer restarts, the icon is automatically added again.
+///
+/// **Note:** To use this struct, enable the `shell`
+/// [Cargo feature](https://doc.rust-lang.org/cargo/reference/features.html#the-features-section).
+#[derive(Clone)]
+pub struct TrayIcon(Pin<Arc<TrayIconObj>>);
+
+unsafe impl Send for TrayIcon {}
+
+impl TrayIcon {
+	/// Instantiates a new `TrayIcon` object, to be added to the notification
+	/// area when the parent window is created.
+	///
+	/// The parent is usually a [`WindowMain`](crate::gui::WindowMain) or a
+	/// [`WindowMessageOnly`](crate::gui::WindowMessageOnly). Note that a
+	/// message-only window doesn't receive broadcast messages, so the icon
+	/// won't be restored after an Explorer restart.
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window was already created – that is, you cannot
+	/// dynamically create a `TrayIcon` in an event closure.
+	///
+	/// Panics if the callback messages cannot be registered.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co, gui};
+	///
+	/// let wnd: gui::WindowMain; // initialized somewhere
+	/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+	/// let hicon = w::HINSTANCE::NULL.LoadIcon(w::IdIdiStr::Idi(co::IDI::APPLICATION))?;
+	///
+	/// let tray = gui::TrayIcon::new(
+	///     &wnd,
+	///     gui::TrayIconOpts {
+	///         icon: unsafe { hicon.raw_copy() },
+	///         tooltip: "My application".to_owned(),
+	///         ..Default::default()
+	///     },
+	/// );
+	///
+	/// let tray2 = tray.clone();
+	/// tray.on().dbl_click(move |_| {
+	///     tray2.show_balloon("Hello", "You double-clicked me.", co::NIIF::INFO, None)?;
+	///     Ok(())
+	/// });
+	/// # w::AnyResult::Ok(())
+	/// ```
+	#[must_use]
+	pub fn new(parent: &(impl GuiParent + 'static), opts: TrayIconOpts) -> Self {
+		let callback_msg = RegisterWindowMessage("winsafe_TrayIcon")
+			.expect("Tray icon callback message could not be registered.");
+		let taskbar_created_msg = RegisterWindowMessage("TaskbarCreated")
+			.expect("TaskbarCreated message could not be registered.");
+
+		let new_self = Self(Arc::pin(TrayIconObj {
+			id: auto_id::set_if_zero(opts.id),
+			callback_msg: unsafe { co::WM::from_raw(callback_msg) },
+			taskbar_created_msg: unsafe { co::WM::from_raw(taskbar_created_msg) },
+			hparent: UnsafeCell::new(HWND::NULL),
+			hicon: UnsafeCell::new(opts.icon),
+			tooltip: UnsafeCell::new(opts.tooltip),
+			events: TrayIconEvents::new(),
+			_pin: PhantomPinned,
+		}));
+
+		new_self.default_message_handlers(parent);
+		new_self
+	}
+
+	fn default_message_handlers(&self, parent: &(impl GuiParent + 'static)) {
+		let self2 = self.clone();
+		let parent2 = parent.clone();
+		parent
+			.as_ref()
+			.before_on()
+			.wm(parent.as_ref().is_dlg().create_msg(), move |_| {
+				*unsafe { &mut *self2.0.hparent.get() } = unsafe { parent2.hwnd().raw_copy() };
+				self2.add()?;
+				Ok(0) // ignored
+			});
+
+		let self2 = self.clone();
+		parent
+			.as_ref()
+			.before_on()
+			.wm(self.0.taskbar_created_msg, move |_| {
+				self2.add()?; // Explorer restarted, the icon is gone
+				Ok(0)
+			});
+
+		let self2 = self.clone();
+		parent.as_ref().before_on().wm(co::WM::DESTROY, move |_| {
+			let nid = self2.nid(unsafe { co::NIF::from_raw(0) });
+			Shell_NotifyIcon(co::NIM::DELETE, &nid).ok(); // the icon may be already gone
+			Ok(0) // ignored
+		});
+
+		let self2 = self.clone();
+		parent.as_ref().before_on().wm(co::WM::TIMER, move |p| {
+			if p.wparam == self2.timer_id() {
+				self2.hparent().KillTimer(self2.timer_id())?;
+				self2.hide_balloon()?;
+			}
+			Ok(0) // ignored
+		});
+
+		let self2 = self.clone();
+		parent
+			.as_ref()
+			.before_on()
+			.wm(self.0.callback_msg, move |p| {
+				if HIWORD(p.lparam as _) != self2.0.id {
+					return Ok(0); // not ours
+				}
+				let event = LOWORD(p.lparam as _) as u32; // NIN or WM value
+				let pos = POINT::new(
+					LOWORD(p.wparam as _) as i16 as _,
+					HIWORD(p.wparam as _) as i16 as _,
+				);
+				self2.0.events.process(event, pos)?;
+				Ok(0)
+			});
+	}
+
+	/// Exposes the tray icon events.
+	///
+	/// # Panics
+	///
+	/// Panics if the parent window is already created. Events must be set
+	/// before parent window creation.
+	#[must_use]
+	pub fn on(&self) -> &TrayIconEvents {
+		if *self.hparent() != HWND::NULL {
+			panic!("Cannot add event after window creation.");
+		}
+		&self.0.events
+	}
+
+	/// Returns the icon ID, which is unique within the parent window.
+	#[must_use]
+	pub fn id(&self) -> u16 {
+		self.0.id
+	}
+
+	/// Replaces the icon.
+	///
+	/// The icon is not destroyed by the `TrayIcon`, so it must be kept alive
+	/// while it's in use.
+	pub fn set_icon(&self, hicon: &HICON) -> HrResult<()> {
+		*unsafe { &mut *self.0.hicon.get() } = unsafe { hicon.raw_copy() };
+		self.modify(self.nid(co::NIF::ICON))
+	}
+
+	/// Replaces the tooltip text, shown when the mouse hovers the icon. The
+	/// text is truncated at 127 characters.
+	pub fn set_tooltip(&self, text: &str) -> HrResult<()> {
+		*unsafe { &mut *self.0.tooltip.get() } = text.to_owned();
+		self.modify(self.nid(co::NIF::TIP | co::NIF::SHOWTIP))
+	}
+
+	/// Shows a balloon notification, which is displayed as a toast on Windows
+	/// 10 and later.
+	///
+	/// The `icon` is one of the [`NIIF`](crate::co::NIIF) icon values, like
+	/// [`NIIF::INFO`](crate::co::NIIF::INFO), optionally combined with
+	/// [`NIIF::NOSOUND`](crate::co::NIIF::NOSOUND) and
+	/// [`NIIF::LARGE_ICON`](crate::co::NIIF::LARGE_ICON).
+	/// [`NIIF::USER`](crate::co::NIIF::USER) shows the tray icon itself.
+	///
+	/// If `timeout_ms` is given, the notification is hidden after this time,
+	/// unless the system hides it before. Otherwise, the system decides how
+	/// long it's shown.
+	pub fn show_balloon(
+		&self,
+		title: &str,
+		text: &str,
+		icon: co::NIIF,
+		timeout_ms: Option<u32>,
+	) -> AnyResult<()> {
+		let mut nid = self.nid(co::NIF::INFO);
+		nid.set_szInfoTitle(title);
+		nid.set_szInfo(text);
+		nid.dwInfoFlags = icon;
+		self.modify(nid)?;
+
+		if let Some(timeout_ms) = timeout_ms {
+			self.hparent().SetTimer(self.timer_id(), timeout_ms, None)?;
+		}
+		Ok(())
+	}
+
+	/// Hides the balloon notification being shown, if any.
+	pub fn hide_balloon(&self) -> HrResult<()> {
+		self.modify(self.nid(co::NIF::INFO)) // an empty text hides the balloon
+	}
+
+	/// Shows a popup menu at the given screen coordinates, usually the ones
+	/// received in the [`context_menu`](crate::gui::events::TrayIconEvents::context_menu)
+	/// event. The chosen command is sent to the parent window as a
+	/// `WM_COMMAND` message.
+	///
+	/// This method will block until the menu disappears.
+	pub fn track_menu(&self, hmenu: &HMENU, pos: POINT) -> SysResult<()> {
+		let hparent = self.hparent();
+		hparent.SetForegroundWindow(); // so the menu is closed when clicking outside it
+		hmenu.TrackPopupMenu(co::TPM::RIGHTBUTTON, pos, hparent)?;
+		unsafe {
+			hparent.PostMessage(wm::Null {})?; // necessary according to TrackPopupMenu docs
+		}
+		Ok(())
+	}
+
+	fn hparent(&self) -> &HWND {
+		unsafe { &*self.0.hparent.get() }
+	}
+
+	fn timer_id(&self) -> usize {
+		&*self.0 as *const _ as _ // unique among all tray icons
+	}
+
+	fn nid(&self, flags: co::NIF) -> NOTIFYICONDATA {
+		let mut nid = NOTIFYICONDATA::default();
+		nid.hWnd = unsafe { self.hparent().raw_copy() };
+		nid.uID = self.0.id as _;
+		nid.uFlags = flags;
+		if flags.has(co::NIF::MESSAGE) {
+			nid.uCallbackMessage = self.0.callback_msg;
+		}
+		if flags.has(co::NIF::ICON) {
+			nid.hIcon = unsafe { (*self.0.hicon.get()).raw_copy() };
+		}
+		if flags.has(co::NIF::TIP) {
+			nid.set_szTip(unsafe { &*self.0.tooltip.get() });
+		}
+		nid
+	}
+
+	fn add(&self) -> HrResult<()> {
+		let mut nid = self.nid(co::NIF::MESSAGE | co::NIF::ICON | co::NIF::TIP | co::NIF::SHOWTIP);
+		Shell_NotifyIcon(co::NIM::ADD, &nid)?;
+
+		nid.uVersion = 4; // NOTIFYICON_VERSION_4
+		Shell_NotifyIcon(co::NIM::SETVERSION, &nid)
+	}
+
+	fn modify(&self, nid: NOTIFYICONDATA) -> HrResult<()> {
+		Shell_NotifyIcon(co::NIM::MODIFY, &nid)
+	}
+}
+
+/// Options to create a [`TrayIcon`](crate::gui::TrayIcon) with
+/// [`TrayIcon::new`](crate::gui::TrayIcon::new).
+pub struct TrayIconOpts {
+	/// The icon to be displayed.
+	///
+	/// The icon is not destroyed by the `TrayIcon`, so it must be kept alive
+	/// while it's in use.
+	///
+	/// Defaults to `HICON::NULL`.
+	pub icon: HICON,
+	/// Text of the tooltip shown when the mouse hovers the icon. The text is
+	/// truncated at 127 characters.
+	///
+	/// Defaults to empty string.
+	pub tooltip: String,
+	/// The icon ID, which must be unique within the parent window.
+	///
+	/// Defaults to an auto-generated ID.
+	pub id: u16,
+}
+
+impl Default for TrayIconOpts {
+	fn default() -> Self {
+		Self {
+			icon: HICON::NULL,
+			tooltip: String::new(),
+			id: 0,
+		}
+	}
+}
@@ -16,6 +16,7 @@ pub(in crate::gui) struct BaseWnd {
 	layout: Layout,
 	before_events: WindowEvents,
 	user_events: WindowEvents,
+	fallback_events: WindowEvents,
 	after_events: WindowEvents,
 }
 
@@ -30,6 +31,7 @@ impl BaseWnd {
 			layout: Layout::new(),
 			before_events: WindowEvents::new(is_dlg),
 			user_events: WindowEvents::new(is_dlg),
+			fallback_events: WindowEvents::new(is_dlg),
 			after_events: WindowEvents::new(is_dlg),
 		};
 		new_self.default_message_handlers();
@@ -45,6 +47,7 @@ impl BaseWnd {
 		&self.hwnd
 	}
 	pub(in crate::gui) fn set_hwnd(&mut self, hwnd: HWND) {
+		self.layout.set_parent(&hwnd);
 		self.hwnd = hwnd
 	}
 
@@ -60,6 +63,13 @@ impl BaseWnd {
 		&self.user_events // user events can be overriden; only the last one is executed
 	}
 	#[must_use]
+	pub(in crate::gui) fn fallback_on(&self) -> &WindowEvents {
+		if self.hwnd != HWND::NULL {
+			panic!("Cannot add event after window creation.");
+		}
+		&self.fallback_events // executed in place of the user events, if there's none for the message
+	}
+	#[must_use]
 	pub(in crate::gui) fn after_on(&self) -> &WindowEvents {
 		&self.after_events
 	}
@@ -68,7 +78,9 @@ impl BaseWnd {
 		self.before_events.process_all_messages(p)
 	}
 	pub(in crate::gui) fn process_user_message(&self, p: WndMsg) -> Option<AnyResult<isize>> {
-		self.user_events.process_last_message(p)
+		self.user_events
+			.process_last_message(p)
+			.or_else(|| self.fallback_events.process_last_message(p))
 	}
 	pub(in crate::gui) fn process_after_messages(&self, p: WndMsg) -> AnyResult<bool> {
 		self.after_events.process_all_messages(p)
@@ -77,6 +89,7 @@ impl BaseWnd {
 	pub(in crate::gui) fn clear_messages(&self) {
 		self.before_events.clear();
 		self.user_events.clear();
+		self.fallback_events.clear();
 		self.after_events.clear();
 	}
 
@@ -88,6 +101,10 @@ impl BaseWnd {
 		self.layout.add_child(&self.hwnd, hchild, resize_behavior)
 	}
 
+	pub(in crate::gui) fn set_grid(&self, grid: Grid) -> SysResult<()> {
+		self.layout.set_grid(&self.hwnd, grid)
+	}
+
 	pub(in crate::gui) fn spawn_thread<F>(&self, func: F)
 	where
 		F: FnOnce() -> AnyResult<()> + Send + 'static,
@@ -142,6 +159,35 @@ impl BaseWnd {
 			Ok(())
 		});
 
+		let is_dlg = self.is_dlg;
+		let layout = self.layout.clone();
+		self.before_events.wm(co::WM::DPICHANGED, move |p| {
+			// Dialogs are moved and rescaled by the system.
+			let p = unsafe { wm::DpiChanged::from_generic_wm(p) };
+			match is_dlg {
+				IsDlg::Yes => layout.rescale_dpi(false, None)?,
+				IsDlg::No => layout.rescale_dpi(true, Some(*p.suggested_rect))?,
+			}
+			Ok(0)
+		});
+
+		let layout = self.layout.clone();
+		self.before_events
+			.wm(co::WM::DPICHANGED_AFTERPARENT, move |_| {
+				layout.rescale_dpi(matches!(is_dlg, IsDlg::No), None)?; // child window, already moved by its parent
+				Ok(0)
+			});
+
+		if matches!(is_dlg, IsDlg::No) {
+			// Controls were created with system DPI coordinates, but the window
+			// may have been created in a monitor with a different DPI.
+			let layout = self.layout.clone();
+			self.after_events.wm_create(move |_| {
+				layout.rescale_dpi(true, None)?;
+				Ok(0)
+			});
+		}
+
 		self.before_events.wm(Self::WM_UI_THREAD, |p| {
 			// WPARAM is just an additional safety check.
 			if unsafe { co::WM::from_raw(p.wparam as _) } == Self::WM_UI_THREAD {
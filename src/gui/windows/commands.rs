@@ -0,0 +1,557 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::prelude::*;
+
+/// A keyboard shortcut, which is a virtual key with optional modifiers, used
+/// by [`Commands`](crate::gui::Commands).
+///
+/// A shortcut can be parsed from and formatted to text like `"Ctrl+Shift+S"`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+	/// The virtual key.
+	pub key: co::VK,
+	/// Whether the Ctrl key must be held down.
+	pub ctrl: bool,
+	/// Whether the Shift key must be held down.
+	pub shift: bool,
+	/// Whether the Alt key must be held down.
+	pub alt: bool,
+}
+
+impl std::fmt::Display for Shortcut {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.ctrl {
+			write!(f, "Ctrl+")?;
+		}
+		if self.shift {
+			write!(f, "Shift+")?;
+		}
+		if self.alt {
+			write!(f, "Alt+")?;
+		}
+		match Self::key_name(self.key) {
+			Some(name) => write!(f, "{}", name),
+			None => write!(f, "0x{:02x}", self.key.raw()),
+		}
+	}
+}
+
+impl std::str::FromStr for Shortcut {
+	type Err = co::ERROR;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::parse(s)
+	}
+}
+
+impl Shortcut {
+	/// Names of the keys which are not letters, digits or function keys.
+	const KEY_NAMES: [(&'static str, co::VK); 30] = [
+		("Backspace", co::VK::BACK),
+		("Tab", co::VK::TAB),
+		("Enter", co::VK::RETURN),
+		("Pause", co::VK::PAUSE),
+		("Esc", co::VK::ESCAPE),
+		("Space", co::VK::SPACE),
+		("PgUp", co::VK::PRIOR),
+		("PgDn", co::VK::NEXT),
+		("End", co::VK::END),
+		("Home", co::VK::HOME),
+		("Left", co::VK::LEFT),
+		("Up", co::VK::UP),
+		("Right", co::VK::RIGHT),
+		("Down", co::VK::DOWN),
+		("PrtSc", co::VK::SNAPSHOT),
+		("Ins", co::VK::INSERT),
+		("Del", co::VK::DELETE),
+		("Num*", co::VK::MULTIPLY),
+		("Num+", co::VK::ADD),
+		("Num-", co::VK::SUBTRACT),
+		("Num.", co::VK::DECIMAL),
+		("Num/", co::VK::DIVIDE),
+		("+", co::VK::OEM_PLUS),
+		(",", co::VK::OEM_COMMA),
+		("-", co::VK::OEM_MINUS),
+		(".", co::VK::OEM_PERIOD),
+		(";", co::VK::OEM_1),
+		("/", co::VK::OEM_2),
+		("[", co::VK::OEM_4),
+		("]", co::VK::OEM_6),
+	];
+
+	/// Alternative names accepted when parsing.
+	const KEY_ALIASES: [(&'static str, co::VK); 8] = [
+		("Escape", co::VK::ESCAPE),
+		("Return", co::VK::RETURN),
+		("PageUp", co::VK::PRIOR),
+		("PageDown", co::VK::NEXT),
+		("Insert", co::VK::INSERT),
+		("Delete", co::VK::DELETE),
+		("Plus", co::VK::OEM_PLUS),
+		("Minus", co::VK::OEM_MINUS),
+	];
+
+	/// Creates a new `Shortcut`.
+	#[must_use]
+	pub const fn new(key: co::VK, ctrl: bool, shift: bool, alt: bool) -> Self {
+		Self { key, ctrl, shift, alt }
+	}
+
+	/// Parses a shortcut text like `"Ctrl+Shift+S"`, `"Alt+F4"` or `"Del"`.
+	///
+	/// Modifiers are `Ctrl`, `Shift` and `Alt`, in any order. Keys are
+	/// letters, digits, `F1` to `F24`, and names like `Enter`, `Esc`, `Del`,
+	/// `PgUp` or `Num+`. Names are case-insensitive.
+	///
+	/// Returns [`ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER)
+	/// if the text cannot be parsed.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, gui};
+	///
+	/// let sc = gui::Shortcut::parse("ctrl+shift+s")?;
+	/// assert_eq!(sc.key.raw(), b'S' as u16);
+	/// assert!(sc.ctrl && sc.shift && !sc.alt);
+	/// assert_eq!(sc.to_string(), "Ctrl+Shift+S");
+	/// # w::SysResult::Ok(())
+	/// ```
+	pub fn parse(text: &str) -> SysResult<Self> {
+		let mut sc = Self::new(co::VK::NoValue, false, false, false);
+		let mut rest = text.trim();
+
+		loop {
+			let (part, after) = match rest.find('+') {
+				Some(0) | None => (rest, ""), // the key itself may be "+"
+				Some(idx) if idx == rest.len() - 1 => (rest, ""), // like "Num+"
+				Some(idx) => (&rest[..idx], &rest[idx + 1..]),
+			};
+			let part = part.trim();
+			if after.is_empty() {
+				sc.key = Self::parse_key(part).ok_or(co::ERROR::INVALID_PARAMETER)?;
+				return Ok(sc);
+			}
+
+			let modifier =
+				if part.eq_ignore_ascii_case("Ctrl") || part.eq_ignore_ascii_case("Control") {
+					&mut sc.ctrl
+				} else if part.eq_ignore_ascii_case("Shift") {
+					&mut sc.shift
+				} else if part.eq_ignore_ascii_case("Alt") {
+					&mut sc.alt
+				} else {
+					return Err(co::ERROR::INVALID_PARAMETER);
+				};
+			if *modifier {
+				return Err(co::ERROR::INVALID_PARAMETER); // repeated modifier
+			}
+			*modifier = true;
+			rest = after;
+		}
+	}
+
+	/// Returns the [`ACCEL`](crate::ACCEL) of this shortcut for the given
+	/// command ID.
+	#[must_use]
+	pub fn to_accel(&self, cmd_id: u16) -> ACCEL {
+		let mut flags = co::ACCELF::VIRTKEY;
+		if self.ctrl {
+			flags |= co::ACCELF::CONTROL;
+		}
+		if self.shift {
+			flags |= co::ACCELF::SHIFT;
+		}
+		if self.alt {
+			flags |= co::ACCELF::ALT;
+		}
+		ACCEL { fVirt: flags, key: self.key, cmd: cmd_id }
+	}
+
+	/// Returns the shortcut of an [`ACCEL`](crate::ACCEL), if it has the
+	/// [`ACCELF::VIRTKEY`](crate::co::ACCELF::VIRTKEY) flag.
+	#[must_use]
+	pub fn from_accel(accel: &ACCEL) -> Option<Self> {
+		if !accel.fVirt.has(co::ACCELF::VIRTKEY) {
+			return None;
+		}
+		Some(Self::new(
+			accel.key,
+			accel.fVirt.has(co::ACCELF::CONTROL),
+			accel.fVirt.has(co::ACCELF::SHIFT),
+			accel.fVirt.has(co::ACCELF::ALT),
+		))
+	}
+
+	fn parse_key(name: &str) -> Option<co::VK> {
+		let bytes = name.as_bytes();
+		if bytes.len() == 1 && bytes[0].is_ascii_alphanumeric() {
+			return Some(unsafe { co::VK::from_raw(bytes[0].to_ascii_uppercase() as _) });
+		}
+		if bytes.len() > 1 && (bytes[0] == b'F' || bytes[0] == b'f') {
+			if let Ok(n @ 1..=24) = name[1..].parse::<u16>() {
+				return Some(unsafe { co::VK::from_raw(co::VK::F1.raw() + n - 1) });
+			}
+		}
+		if let Some(digit) = name
+			.strip_prefix("Num")
+			.or_else(|| name.strip_prefix("num"))
+			.filter(|d| d.len() == 1 && d.as_bytes()[0].is_ascii_digit())
+		{
+			let n = (digit.as_bytes()[0] - b'0') as u16;
+			return Some(unsafe { co::VK::from_raw(co::VK::NUMPAD0.raw() + n) });
+		}
+		Self::KEY_NAMES
+			.iter()
+			.chain(Self::KEY_ALIASES.iter())
+			.find(|(key_name, _)| key_name.eq_ignore_ascii_case(name))
+			.map(|(_, vk)| *vk)
+	}
+
+	fn key_name(vk: co::VK) -> Option<String> {
+		let raw = vk.raw();
+		if (0x30..=0x39).contains(&raw) || (0x41..=0x5a).contains(&raw) {
+			return Some(char::from(raw as u8).to_string());
+		}
+		if (co::VK::F1.raw()..=co::VK::F24.raw()).contains(&raw) {
+			return Some(format!("F{}", raw - co::VK::F1.raw() + 1));
+		}
+		if (co::VK::NUMPAD0.raw()..=co::VK::NUMPAD0.raw() + 9).contains(&raw) {
+			return Some(format!("Num{}", raw - co::VK::NUMPAD0.raw()));
+		}
+		Self::KEY_NAMES
+			.iter()
+			.find(|(_, key_vk)| *key_vk == vk)
+			.map(|(name, _)| (*name).to_owned())
+	}
+}
+
+/// Menu entry, parameter of
+/// [`Commands::menu_bar`](crate::gui::Commands::menu_bar) and
+/// [`Commands::popup_menu`](crate::gui::Commands::popup_menu).
+pub enum MenuDef<'a> {
+	/// A registered command, by its ID.
+	Cmd(u16),
+	/// A separator.
+	Separator,
+	/// A submenu, with its entry text and its entries.
+	Submenu(&'a str, &'a [MenuDef<'a>]),
+}
+
+struct Command {
+	id: u16,
+	text: String,
+	shortcut: Option<Shortcut>,
+	enabled: bool,
+	checked: bool,
+	func: Rc<dyn Fn() -> AnyResult<()>>,
+}
+
+struct CommandsObj {
+	cmds: Vec<Command>,
+	hmenus: Vec<HMENU>, // all menus built so far, which receive the state updates
+}
+
+/// Declarative registry of commands, each one with an ID, a menu text, an
+/// optional keyboard shortcut and a handler closure.
+///
+/// From the registered commands, `Commands` builds the menu bar, the context
+/// menus and the accelerator table, and dispatches both menu and accelerator
+/// `WM_COMMAND` messages to the handlers. The enabled and checked states of
+/// each command are propagated to all menus built by it, and disabled commands
+/// are not dispatched.
+///
+/// The object is cheaply cloneable, so it can be captured by closures.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, gui, seq_ids};
+///
+/// seq_ids! {
+///     ID_FILE_OPEN = 2001;
+///     ID_FILE_SAVE
+///     ID_FILE_EXIT
+///     ID_VIEW_WRAP
+/// }
+///
+/// let cmds = gui::Commands::new();
+/// cmds.add(ID_FILE_OPEN, "&Open...", Some("Ctrl+O"), || Ok(()))?
+///     .add(ID_FILE_SAVE, "&Save", Some("Ctrl+S"), || Ok(()))?
+///     .add(ID_FILE_EXIT, "E&xit", Some("Alt+F4"), || Ok(()))?
+///     .add(ID_VIEW_WRAP, "&Word wrap", None, {
+///         let cmds = cmds.clone();
+///         move || {
+///             cmds.set_checked(ID_VIEW_WRAP, !cmds.is_checked(ID_VIEW_WRAP));
+///             Ok(())
+///         }
+///     })?;
+///
+/// use gui::MenuDef::*;
+/// let wnd = gui::WindowMain::new(gui::WindowMainOpts {
+///     menu: cmds.menu_bar(&[
+///         Submenu("&File", &[Cmd(ID_FILE_OPEN), Cmd(ID_FILE_SAVE), Separator, Cmd(ID_FILE_EXIT)]),
+///         Submenu("&View", &[Cmd(ID_VIEW_WRAP)]),
+///     ])?,
+///     accel_table: Some(cmds.accel_table()?),
+///     ..Default::default()
+/// });
+/// cmds.attach(&wnd);
+/// # w::AnyResult::Ok(())
+/// ```
+#[derive(Clone)]
+pub struct Commands(Rc<RefCell<CommandsObj>>);
+
+impl Default for Commands {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Commands {
+	/// Creates a new, empty `Commands`.
+	#[must_use]
+	pub fn new() -> Self {
+		Self(Rc::new(RefCell::new(CommandsObj { cmds: Vec::new(), hmenus: Vec::new() })))
+	}
+
+	/// Registers a new command, enabled and unchecked.
+	///
+	/// The `text` is the menu entry text, which may contain `&` mnemonics. The
+	/// `shortcut` text is parsed with
+	/// [`Shortcut::parse`](crate::gui::Shortcut::parse), and it's also shown
+	/// in the menu entry.
+	///
+	/// Returns [`ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER)
+	/// if the shortcut cannot be parsed, or
+	/// [`ERROR::ALREADY_EXISTS`](crate::co::ERROR::ALREADY_EXISTS) if the
+	/// command ID or the shortcut is already registered.
+	pub fn add<F>(
+		&self,
+		cmd_id: u16,
+		text: &str,
+		shortcut: Option<&str>,
+		func: F,
+	) -> SysResult<&Self>
+	where
+		F: Fn() -> AnyResult<()> + 'static,
+	{
+		let shortcut = shortcut.map(Shortcut::parse).transpose()?;
+
+		let mut obj = self.0.borrow_mut();
+		if obj
+			.cmds
+			.iter()
+			.any(|cmd| cmd.id == cmd_id || (shortcut.is_some() && cmd.shortcut == shortcut))
+		{
+			return Err(co::ERROR::ALREADY_EXISTS);
+		}
+
+		obj.cmds.push(Command {
+			id: cmd_id,
+			text: text.to_owned(),
+			shortcut,
+			enabled: true,
+			checked: false,
+			func: Rc::new(func),
+		});
+		Ok(self)
+	}
+
+	/// Returns the keyboard shortcut of the given command, if any.
+	#[must_use]
+	pub fn shortcut(&self, cmd_id: u16) -> Option<Shortcut> {
+		self.0
+			.borrow()
+			.cmds
+			.iter()
+			.find(|cmd| cmd.id == cmd_id)
+			.and_then(|cmd| cmd.shortcut)
+	}
+
+	/// Returns the ID of the command bound to the given keyboard shortcut, if
+	/// any.
+	#[must_use]
+	pub fn command_of(&self, shortcut: Shortcut) -> Option<u16> {
+		self.0
+			.borrow()
+			.cmds
+			.iter()
+			.find(|cmd| cmd.shortcut == Some(shortcut))
+			.map(|cmd| cmd.id)
+	}
+
+	/// Returns whether the given command is enabled.
+	///
+	/// # Panics
+	///
+	/// Panics if the command ID is not registered.
+	#[must_use]
+	pub fn is_enabled(&self, cmd_id: u16) -> bool {
+		self.with_cmd(cmd_id, |cmd| cmd.enabled)
+	}
+
+	/// Returns whether the given command is checked.
+	///
+	/// # Panics
+	///
+	/// Panics if the command ID is not registered.
+	#[must_use]
+	pub fn is_checked(&self, cmd_id: u16) -> bool {
+		self.with_cmd(cmd_id, |cmd| cmd.checked)
+	}
+
+	/// Enables or disables the given command, updating all menus built by this
+	/// object. A disabled command is not dispatched, even by its accelerator.
+	///
+	/// # Panics
+	///
+	/// Panics if the command ID is not registered.
+	pub fn set_enabled(&self, cmd_id: u16, enabled: bool) {
+		self.with_cmd(cmd_id, |cmd| cmd.enabled = enabled);
+		self.for_each_menu(|hmenu| {
+			hmenu.EnableMenuItem(IdPos::Id(cmd_id), enabled).ok();
+		});
+	}
+
+	/// Checks or unchecks the given command, updating all menus built by this
+	/// object.
+	///
+	/// # Panics
+	///
+	/// Panics if the command ID is not registered.
+	pub fn set_checked(&self, cmd_id: u16, checked: bool) {
+		self.with_cmd(cmd_id, |cmd| cmd.checked = checked);
+		self.for_each_menu(|hmenu| {
+			hmenu.CheckMenuItem(IdPos::Id(cmd_id), checked).ok();
+		});
+	}
+
+	/// Builds a menu bar with the given entries, to be used as
+	/// [`WindowMainOpts::menu`](crate::gui::WindowMainOpts::menu), or set with
+	/// [`HWND::SetMenu`](crate::prelude::user_Hwnd::SetMenu).
+	///
+	/// **Note:** If not attached to a window, the menu must be destroyed with
+	/// [`HMENU::DestroyMenu`](crate::prelude::user_Hmenu::DestroyMenu).
+	///
+	/// # Panics
+	///
+	/// Panics if a command ID is not registered.
+	pub fn menu_bar(&self, entries: &[MenuDef]) -> SysResult<HMENU> {
+		let hmenu = HMENU::CreateMenu()?;
+		self.build_menu(&hmenu, entries)?;
+		self.0.borrow_mut().hmenus.push(unsafe { hmenu.raw_copy() });
+		Ok(hmenu)
+	}
+
+	/// Builds a context menu with the given entries, which can be shown with
+	/// [`HMENU::track_popup_menu_at_point`](crate::prelude::user_Hmenu::track_popup_menu_at_point).
+	///
+	/// # Panics
+	///
+	/// Panics if a command ID is not registered.
+	pub fn popup_menu(&self, entries: &[MenuDef]) -> SysResult<DestroyMenuGuard> {
+		let hmenu = unsafe { DestroyMenuGuard::new(HMENU::CreatePopupMenu()?) };
+		self.build_menu(&hmenu, entries)?;
+		self.0.borrow_mut().hmenus.push(unsafe { hmenu.raw_copy() });
+		Ok(hmenu)
+	}
+
+	/// Creates an accelerator table with the shortcuts of all registered
+	/// commands, to be used as
+	/// [`WindowMainOpts::accel_table`](crate::gui::WindowMainOpts::accel_table).
+	pub fn accel_table(&self) -> SysResult<DestroyAcceleratorTableGuard> {
+		let accels = self
+			.0
+			.borrow()
+			.cmds
+			.iter()
+			.filter_map(|cmd| cmd.shortcut.map(|sc| sc.to_accel(cmd.id)))
+			.collect::<Vec<_>>();
+		HACCEL::CreateAcceleratorTable(&accels)
+	}
+
+	/// Dispatches the menu and accelerator
+	/// [`wm_command_accel_menu`](crate::gui::events::WindowEvents::wm_command_accel_menu)
+	/// events of the window to the handlers of all registered commands.
+	///
+	/// Commands registered after this call are not dispatched.
+	///
+	/// # Panics
+	///
+	/// Panics if the window is already created. Events must be set before
+	/// window creation.
+	pub fn attach(&self, wnd: &impl GuiParent) {
+		let ids = self
+			.0
+			.borrow()
+			.cmds
+			.iter()
+			.map(|cmd| cmd.id)
+			.collect::<Vec<_>>();
+		for cmd_id in ids {
+			let self2 = self.clone();
+			wnd.on().wm_command_accel_menu(cmd_id, move || {
+				let func = {
+					let obj = self2.0.borrow();
+					let cmd = obj
+						.cmds
+						.iter()
+						.find(|cmd| cmd.id == cmd_id)
+						.expect("Registered commands are never removed.");
+					if !cmd.enabled {
+						return Ok(()); // the accelerator of a disabled command is ignored
+					}
+					cmd.func.clone() // release the borrow, the handler may change the state
+				};
+				func()
+			});
+		}
+	}
+
+	fn with_cmd<T>(&self, cmd_id: u16, func: impl FnOnce(&mut Command) -> T) -> T {
+		let mut obj = self.0.borrow_mut();
+		match obj.cmds.iter_mut().find(|cmd| cmd.id == cmd_id) {
+			Some(cmd) => func(cmd),
+			None => panic!("Command ID {} not registered.", cmd_id),
+		}
+	}
+
+	/// Calls the function on each built menu. A menu may not contain the
+	/// command, so the errors of the menu functions are ignored.
+	fn for_each_menu(&self, func: impl Fn(&HMENU)) {
+		let mut obj = self.0.borrow_mut();
+		obj.hmenus.retain(|hmenu| hmenu.IsMenu()); // forget destroyed menus
+		obj.hmenus.iter().for_each(func);
+	}
+
+	fn build_menu(&self, hmenu: &HMENU, entries: &[MenuDef]) -> SysResult<()> {
+		for entry in entries.iter() {
+			match entry {
+				MenuDef::Cmd(cmd_id) => {
+					let (text, enabled, checked) = self.with_cmd(*cmd_id, |cmd| {
+						let text = match cmd.shortcut {
+							Some(sc) => format!("{}\t{}", cmd.text, sc),
+							None => cmd.text.clone(),
+						};
+						(text, cmd.enabled, cmd.checked)
+					});
+					hmenu.append_item(&[MenuItem::Entry(*cmd_id, &text)])?;
+					hmenu.EnableMenuItem(IdPos::Id(*cmd_id), enabled)?;
+					hmenu.CheckMenuItem(IdPos::Id(*cmd_id), checked)?;
+				},
+				MenuDef::Separator => hmenu.append_item(&[MenuItem::Separator])?,
+				MenuDef::Submenu(text, sub_entries) => {
+					let hsub = HMENU::CreatePopupMenu()?;
+					self.build_menu(&hsub, sub_entries)?;
+					hmenu.append_item(&[MenuItem::Submenu(&hsub, text)])?;
+				},
+			}
+		}
+		Ok(())
+	}
+}
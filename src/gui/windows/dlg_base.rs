@@ -4,12 +4,34 @@ use crate::gui::privs::*;
 use crate::msg::*;
 use crate::prelude::*;
 
+/// Where a dialog is loaded from.
+pub(in crate::gui) enum DlgSrc {
+	/// Dialog resource ID.
+	Id(u16),
+	/// Serialized `DLGTEMPLATEEX`, stored as `u32` to keep it 32-bit aligned.
+	/// The serialization error, if any, is returned when the dialog is created.
+	Template(SysResult<Vec<u32>>),
+}
+
+impl DlgSrc {
+	#[must_use]
+	pub(in crate::gui) fn from_template(template: &DlgTemplate) -> Self {
+		Self::Template(template.serialize().map(|mut bytes| {
+			bytes.resize((bytes.len() + 3) & !3, 0x00);
+			bytes
+				.chunks_exact(4)
+				.map(|ch4| u32::from_le_bytes([ch4[0], ch4[1], ch4[2], ch4[3]]))
+				.collect()
+		}))
+	}
+}
+
 /// Base to all dialog windows.
 ///
 /// Owns the window procedure for all dialog windows.
 pub(in crate::gui) struct DlgBase {
 	base: BaseWnd,
-	dlg_id: u16,
+	dlg_src: DlgSrc,
 }
 
 impl Drop for DlgBase {
@@ -24,8 +46,8 @@ impl Drop for DlgBase {
 
 impl DlgBase {
 	#[must_use]
-	pub(in crate::gui) fn new(dlg_id: u16) -> Self {
-		Self { base: BaseWnd::new(IsDlg::Yes), dlg_id }
+	pub(in crate::gui) fn new(dlg_src: DlgSrc) -> Self {
+		Self { base: BaseWnd::new(IsDlg::Yes), dlg_src }
 	}
 
 	#[must_use]
@@ -39,12 +61,20 @@ impl DlgBase {
 		}
 		unsafe {
 			// The hwnd member is saved in WM_INITDIALOG processing in dlg_proc.
-			hinst.CreateDialogParam(
-				IdStr::Id(self.dlg_id),
-				None,
-				Self::dlg_proc,
-				Some(self as *const _ as _), // pointer to object itself
-			)?;
+			match &self.dlg_src {
+				DlgSrc::Id(dlg_id) => hinst.CreateDialogParam(
+					IdStr::Id(*dlg_id),
+					None,
+					Self::dlg_proc,
+					Some(self as *const _ as _), // pointer to object itself
+				)?,
+				DlgSrc::Template(buf) => hinst.CreateDialogIndirectParam(
+					&*(buf.as_ref().map_err(|e| *e)?.as_ptr() as *const DLGTEMPLATE),
+					None,
+					Self::dlg_proc,
+					Some(self as *const _ as _),
+				)?,
+			};
 		}
 		Ok(())
 	}
@@ -57,12 +87,20 @@ impl DlgBase {
 			panic!("Cannot create dialog twice.");
 		}
 		unsafe {
-			hinst.DialogBoxParam(
-				IdStr::Id(self.dlg_id),
-				Some(hparent),
-				Self::dlg_proc,
-				Some(self as *const _ as _), // pointer to object itself
-			)?;
+			match &self.dlg_src {
+				DlgSrc::Id(dlg_id) => hinst.DialogBoxParam(
+					IdStr::Id(*dlg_id),
+					Some(hparent),
+					Self::dlg_proc,
+					Some(self as *const _ as _), // pointer to object itself
+				)?,
+				DlgSrc::Template(buf) => hinst.DialogBoxIndirectParam(
+					&*(buf.as_ref().map_err(|e| *e)?.as_ptr() as *const DLGTEMPLATE),
+					Some(hparent),
+					Self::dlg_proc,
+					Some(self as *const _ as _),
+				)?,
+			};
 		}
 		Ok(())
 	}
@@ -21,14 +21,14 @@ impl DlgControl {
 	#[must_use]
 	pub(in crate::gui) fn new(
 		parent: &(impl GuiParent + 'static),
-		dlg_id: u16,
+		dlg_src: DlgSrc,
 		position: (i32, i32),
 		resize_behavior: (Horz, Vert),
 		ctrl_id: Option<u16>,
 	) -> Self {
 		let ctrl_id2 = ctrl_id.unwrap_or_else(|| auto_id::next());
 		let new_self = Self(Arc::pin(DlgControlObj {
-			dlg_base: DlgBase::new(dlg_id),
+			dlg_base: DlgBase::new(dlg_src),
 			ctrl_id: ctrl_id2,
 			_pin: PhantomPinned,
 		}));
@@ -21,12 +21,12 @@ pub(in crate::gui) struct DlgMain(Pin<Arc<DlgMainObj>>);
 impl DlgMain {
 	#[must_use]
 	pub(in crate::gui) fn new(
-		dlg_id: u16,
+		dlg_src: DlgSrc,
 		icon_id: Option<u16>,
 		accel_tbl_id: Option<u16>,
 	) -> Self {
 		let new_self = Self(Arc::pin(DlgMainObj {
-			dlg_base: DlgBase::new(dlg_id),
+			dlg_base: DlgBase::new(dlg_src),
 			icon_id,
 			accel_tbl_id,
 			_pin: PhantomPinned,
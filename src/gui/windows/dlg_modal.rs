@@ -18,9 +18,9 @@ pub(in crate::gui) struct DlgModal(Pin<Arc<DlgModalObj>>);
 
 impl DlgModal {
 	#[must_use]
-	pub(in crate::gui) fn new(dlg_id: u16) -> Self {
+	pub(in crate::gui) fn new(dlg_src: DlgSrc) -> Self {
 		let new_self = Self(Arc::pin(DlgModalObj {
-			dlg_base: DlgBase::new(dlg_id),
+			dlg_base: DlgBase::new(dlg_src),
 			_pin: PhantomPinned,
 		}));
 		new_self.default_message_handlers();
@@ -20,11 +20,11 @@ impl DlgModeless {
 	#[must_use]
 	pub(in crate::gui) fn new(
 		parent: &(impl GuiParent + 'static),
-		dlg_id: u16,
+		dlg_src: DlgSrc,
 		position: (i32, i32),
 	) -> Self {
 		let new_self = Self(Arc::pin(DlgModelessObj {
-			dlg_base: DlgBase::new(dlg_id),
+			dlg_base: DlgBase::new(dlg_src),
 			_pin: PhantomPinned,
 		}));
 
@@ -0,0 +1,373 @@
+use super::grid_solver::{self, CellSpec, GridSpec};
+use crate::co;
+use crate::decl::*;
+use crate::prelude::*;
+
+/// Size of a row or a column of a [`Grid`](crate::gui::Grid).
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Track {
+	/// Fixed size, in pixels.
+	Fixed(i32),
+	/// Size of the largest child in the track. The size of a control is its
+	/// size when it was added to the grid; the size of a nested grid is the sum
+	/// of its tracks.
+	///
+	/// Children spanning more than one track are not taken into account.
+	Auto,
+	/// Proportional share of the space left by the fixed and automatic tracks,
+	/// according to the given weight. When measuring a nested grid, it counts
+	/// as the size of its largest child.
+	Fill(u32),
+}
+
+/// Empty space around the children of a [`Grid`](crate::gui::Grid), in pixels.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Margin {
+	pub left: i32,
+	pub top: i32,
+	pub right: i32,
+	pub bottom: i32,
+}
+
+impl Margin {
+	/// Creates a new `Margin` with the given values.
+	#[must_use]
+	pub const fn new(left: i32, top: i32, right: i32, bottom: i32) -> Self {
+		Self { left, top, right, bottom }
+	}
+
+	/// Creates a new `Margin` with the same value on all sides.
+	#[must_use]
+	pub const fn uniform(value: i32) -> Self {
+		Self::new(value, value, value, value)
+	}
+}
+
+/// Placement of a child within a [`Grid`](crate::gui::Grid).
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GridCell {
+	/// Zero-based row index.
+	///
+	/// Defaults to `0`.
+	pub row: u32,
+	/// Zero-based column index.
+	///
+	/// Defaults to `0`.
+	pub col: u32,
+	/// Number of rows spanned by the child.
+	///
+	/// Defaults to `1`.
+	pub row_span: u32,
+	/// Number of columns spanned by the child.
+	///
+	/// Defaults to `1`.
+	pub col_span: u32,
+	/// Minimum width and height of the child, even if the cell is smaller.
+	///
+	/// Defaults to `(0, 0)`.
+	pub min_size: (i32, i32),
+	/// Maximum width and height of the child, even if the cell is larger.
+	///
+	/// Defaults to `None`, meaning the child fills the whole cell.
+	pub max_size: Option<(i32, i32)>,
+}
+
+impl Default for GridCell {
+	fn default() -> Self {
+		Self::at(0, 0)
+	}
+}
+
+impl GridCell {
+	/// Creates a new `GridCell` at the given zero-based row and column, with
+	/// default values for the other fields.
+	#[must_use]
+	pub const fn at(row: u32, col: u32) -> Self {
+		Self {
+			row,
+			col,
+			row_span: 1,
+			col_span: 1,
+			min_size: (0, 0),
+			max_size: None,
+		}
+	}
+}
+
+enum GridChild {
+	Ctrl { hchild: HWND, sz_orig: Option<SIZE> }, // original size filled at 1st arrangement
+	Grid(Grid),
+}
+
+/// Declarative layout which arranges controls in rows and columns, and which
+/// can be nested within other grids.
+///
+/// The grid is applied to a window with
+/// [`GuiParent::set_grid`](crate::prelude::GuiParent::set_grid), and it's
+/// rearranged whenever the window is resized. Controls placed in a grid should
+/// have `(Horz::None, Vert::None)` as their resize behavior.
+///
+/// # Examples
+///
+/// A multi-line text filling the window, with two buttons at the bottom right:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, gui};
+///
+/// let wnd: gui::WindowMain; // initialized somewhere
+/// let txt: gui::Edit;
+/// let btn_ok: gui::Button;
+/// let btn_cancel: gui::Button;
+/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+/// # let txt = gui::Edit::new(&wnd, gui::EditOpts::default());
+/// # let btn_ok = gui::Button::new(&wnd, gui::ButtonOpts::default());
+/// # let btn_cancel = gui::Button::new(&wnd, gui::ButtonOpts::default());
+///
+/// let wnd2 = wnd.clone();
+/// wnd.on().wm_create(move |_| {
+///     use gui::Track::*;
+///
+///     let buttons = gui::Grid::hstack(&[Fill(1), Auto, Auto])
+///         .spacing(gui::dpi_x(8), 0)
+///         .add(&btn_ok, gui::GridCell::at(0, 1))
+///         .add(&btn_cancel, gui::GridCell::at(0, 2));
+///
+///     wnd2.set_grid(
+///         gui::Grid::vstack(&[Fill(1), Auto])
+///             .margin(gui::Margin::uniform(gui::dpi_x(10)))
+///             .spacing(0, gui::dpi_y(8))
+///             .push(&txt)
+///             .push_grid(buttons),
+///     )?;
+///     Ok(0)
+/// });
+/// ```
+pub struct Grid {
+	rows: Vec<Track>,
+	cols: Vec<Track>,
+	margin: Margin,
+	spacing: SIZE,
+	children: Vec<(GridChild, GridCell)>,
+	next_cell: u32, // used by push()
+}
+
+impl Grid {
+	/// Creates a new, empty `Grid` with the given rows and columns.
+	///
+	/// If no rows or no columns are given, a single
+	/// [`Track::Fill`](crate::gui::Track::Fill) is assumed.
+	#[must_use]
+	pub fn new(rows: &[Track], cols: &[Track]) -> Self {
+		Self {
+			rows: rows.to_vec(),
+			cols: cols.to_vec(),
+			margin: Margin::default(),
+			spacing: SIZE::default(),
+			children: Vec::new(),
+			next_cell: 0,
+		}
+	}
+
+	/// Creates a new, empty `Grid` with a single row and the given columns, so
+	/// children are stacked horizontally with
+	/// [`push`](crate::gui::Grid::push).
+	#[must_use]
+	pub fn hstack(cols: &[Track]) -> Self {
+		Self::new(&[Track::Fill(1)], cols)
+	}
+
+	/// Creates a new, empty `Grid` with a single column and the given rows, so
+	/// children are stacked vertically with
+	/// [`push`](crate::gui::Grid::push).
+	#[must_use]
+	pub fn vstack(rows: &[Track]) -> Self {
+		Self::new(rows, &[Track::Fill(1)])
+	}
+
+	/// Sets the empty space around the children.
+	#[must_use]
+	pub const fn margin(mut self, margin: Margin) -> Self {
+		self.margin = margin;
+		self
+	}
+
+	/// Sets the empty space between columns and between rows.
+	#[must_use]
+	pub const fn spacing(mut self, horz: i32, vert: i32) -> Self {
+		self.spacing = SIZE::new(horz, vert);
+		self
+	}
+
+	/// Adds a control at the given cell.
+	///
+	/// # Panics
+	///
+	/// Panics if the control was not created yet. Child controls are created
+	/// along with their parent window, so the grid must be built within the
+	/// parent's [`wm_create`](crate::gui::events::WindowEvents::wm_create) or
+	/// [`wm_init_dialog`](crate::gui::events::WindowEvents::wm_init_dialog)
+	/// handlers, and not right after the control is instantiated.
+	#[must_use]
+	pub fn add(mut self, ctrl: &impl GuiControl, cell: GridCell) -> Self {
+		if *ctrl.hwnd() == HWND::NULL {
+			panic!("Cannot add a control to a grid before its creation.");
+		}
+		self.children.push((
+			GridChild::Ctrl {
+				hchild: unsafe { ctrl.hwnd().raw_copy() },
+				sz_orig: None,
+			},
+			cell,
+		));
+		self
+	}
+
+	/// Adds a nested grid at the given cell.
+	#[must_use]
+	pub fn add_grid(mut self, grid: Grid, cell: GridCell) -> Self {
+		self.children.push((GridChild::Grid(grid), cell));
+		self
+	}
+
+	/// Adds a control at the next cell, filling the rows from left to right,
+	/// then from top to bottom.
+	///
+	/// # Panics
+	///
+	/// Panics if the control was not created yet. Child controls are created
+	/// along with their parent window, so the grid must be built within the
+	/// parent's [`wm_create`](crate::gui::events::WindowEvents::wm_create) or
+	/// [`wm_init_dialog`](crate::gui::events::WindowEvents::wm_init_dialog)
+	/// handlers, and not right after the control is instantiated.
+	#[must_use]
+	pub fn push(self, ctrl: &impl GuiControl) -> Self {
+		let cell = self.next_cell();
+		let mut new_self = self.add(ctrl, cell);
+		new_self.next_cell += 1;
+		new_self
+	}
+
+	/// Adds a nested grid at the next cell, filling the rows from left to
+	/// right, then from top to bottom.
+	#[must_use]
+	pub fn push_grid(self, grid: Grid) -> Self {
+		let cell = self.next_cell();
+		let mut new_self = self.add_grid(grid, cell);
+		new_self.next_cell += 1;
+		new_self
+	}
+
+	#[must_use]
+	fn next_cell(&self) -> GridCell {
+		let num_cols = self.cols.len().max(1) as u32;
+		GridCell::at(self.next_cell / num_cols, self.next_cell % num_cols)
+	}
+
+	/// Returns the number of controls, including the ones in nested grids.
+	#[must_use]
+	pub(in crate::gui) fn ctrl_count(&self) -> usize {
+		self.children
+			.iter()
+			.map(|(child, _)| match child {
+				GridChild::Ctrl { .. } => 1,
+				GridChild::Grid(grid) => grid.ctrl_count(),
+			})
+			.sum()
+	}
+
+	#[must_use]
+	fn spec(&self) -> GridSpec<'_> {
+		GridSpec {
+			rows: &self.rows,
+			cols: &self.cols,
+			margin: self.margin,
+			spacing: self.spacing,
+		}
+	}
+
+	fn cell_specs(&mut self) -> SysResult<Vec<CellSpec>> {
+		self.children
+			.iter_mut()
+			.map(|(child, cell)| {
+				let pref = match child {
+					GridChild::Ctrl { hchild, sz_orig } => match sz_orig {
+						Some(sz) => *sz,
+						None => {
+							let rc = hchild.GetWindowRect()?;
+							let sz = SIZE::new(rc.right - rc.left, rc.bottom - rc.top);
+							*sz_orig = Some(sz); // save control original size
+							sz
+						},
+					},
+					GridChild::Grid(grid) => grid.measure()?,
+				};
+				Ok(CellSpec {
+					row: cell.row,
+					col: cell.col,
+					row_span: cell.row_span,
+					col_span: cell.col_span,
+					pref,
+					min: SIZE::new(cell.min_size.0, cell.min_size.1),
+					max: cell.max_size.map(|(cx, cy)| SIZE::new(cx, cy)),
+				})
+			})
+			.collect()
+	}
+
+	fn measure(&mut self) -> SysResult<SIZE> {
+		let cells = self.cell_specs()?;
+		Ok(grid_solver::measure(&self.spec(), &cells))
+	}
+
+	/// Rescales all sizes, including the ones of nested grids, after a DPI
+	/// change.
+	pub(in crate::gui) fn rescale(&mut self, old_dpi: i32, new_dpi: i32) {
+		let scale = |v: i32| MulDiv(v, new_dpi, old_dpi);
+		let scale_sz = |(cx, cy): (i32, i32)| (scale(cx), scale(cy));
+
+		for track in self.rows.iter_mut().chain(self.cols.iter_mut()) {
+			if let Track::Fixed(sz) = track {
+				*sz = scale(*sz);
+			}
+		}
+		self.margin = Margin::new(
+			scale(self.margin.left),
+			scale(self.margin.top),
+			scale(self.margin.right),
+			scale(self.margin.bottom),
+		);
+		self.spacing = SIZE::new(scale(self.spacing.cx), scale(self.spacing.cy));
+
+		for (child, cell) in self.children.iter_mut() {
+			cell.min_size = scale_sz(cell.min_size);
+			cell.max_size = cell.max_size.map(scale_sz);
+			match child {
+				GridChild::Ctrl { sz_orig, .. } => {
+					*sz_orig = sz_orig.map(|sz| SIZE::new(scale(sz.cx), scale(sz.cy)))
+				},
+				GridChild::Grid(grid) => grid.rescale(old_dpi, new_dpi),
+			}
+		}
+	}
+
+	/// Positions all controls, including the ones in nested grids, within the
+	/// given area.
+	pub(in crate::gui) fn arrange(&mut self, area: RECT, hdwp: &mut HDWP) -> SysResult<()> {
+		let cells = self.cell_specs()?;
+		let rects = grid_solver::arrange(&self.spec(), &cells, area);
+
+		for ((child, _), rc) in self.children.iter_mut().zip(rects) {
+			match child {
+				GridChild::Ctrl { hchild, .. } => hdwp.DeferWindowPos(
+					hchild,
+					HwndPlace::None,
+					POINT::new(rc.left, rc.top),
+					SIZE::new(rc.right - rc.left, rc.bottom - rc.top),
+					co::SWP::NOZORDER,
+				)?,
+				GridChild::Grid(grid) => grid.arrange(rc, hdwp)?,
+			}
+		}
+		Ok(())
+	}
+}
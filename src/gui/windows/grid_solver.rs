@@ -0,0 +1,391 @@
+//! Pure geometry calculations of [`Grid`](crate::gui::Grid), which don't touch
+//! any window.
+
+use crate::decl::*;
+use crate::gui::*;
+
+/// Position and size constraints of a single child, as seen by the solver.
+#[derive(Clone, Copy)]
+pub(in crate::gui) struct CellSpec {
+	pub(in crate::gui) row: u32,
+	pub(in crate::gui) col: u32,
+	pub(in crate::gui) row_span: u32,
+	pub(in crate::gui) col_span: u32,
+	pub(in crate::gui) pref: SIZE,
+	pub(in crate::gui) min: SIZE,
+	pub(in crate::gui) max: Option<SIZE>,
+}
+
+/// Geometry of a grid, without its children.
+#[derive(Clone, Copy)]
+pub(in crate::gui) struct GridSpec<'a> {
+	pub(in crate::gui) rows: &'a [Track],
+	pub(in crate::gui) cols: &'a [Track],
+	pub(in crate::gui) margin: Margin,
+	pub(in crate::gui) spacing: SIZE,
+}
+
+/// An empty list of tracks is treated as a single track filling the area.
+const SINGLE_FILL: [Track; 1] = [Track::Fill(1)];
+
+#[derive(Clone, Copy)]
+enum Axis {
+	Horz,
+	Vert,
+}
+
+impl<'a> GridSpec<'a> {
+	#[must_use]
+	fn tracks(&self, axis: Axis) -> &'a [Track] {
+		let tracks = match axis {
+			Axis::Horz => self.cols,
+			Axis::Vert => self.rows,
+		};
+		if tracks.is_empty() {
+			&SINGLE_FILL
+		} else {
+			tracks
+		}
+	}
+
+	#[must_use]
+	const fn spacing(&self, axis: Axis) -> i32 {
+		match axis {
+			Axis::Horz => self.spacing.cx,
+			Axis::Vert => self.spacing.cy,
+		}
+	}
+
+	#[must_use]
+	const fn margins(&self, axis: Axis) -> i32 {
+		match axis {
+			Axis::Horz => self.margin.left + self.margin.right,
+			Axis::Vert => self.margin.top + self.margin.bottom,
+		}
+	}
+
+	/// Returns the zero-based first track and the number of spanned tracks,
+	/// clamped to the existing tracks.
+	#[must_use]
+	fn span(&self, cell: &CellSpec, axis: Axis) -> (usize, usize) {
+		let num_tracks = self.tracks(axis).len();
+		let (first, span) = match axis {
+			Axis::Horz => (cell.col as usize, cell.col_span as usize),
+			Axis::Vert => (cell.row as usize, cell.row_span as usize),
+		};
+		let first = first.min(num_tracks - 1);
+		(first, span.clamp(1, num_tracks - first))
+	}
+
+	/// Preferred size of each track: fixed tracks have their own size, the
+	/// others have the largest preferred size among the children which span
+	/// only them.
+	#[must_use]
+	fn track_prefs(&self, cells: &[CellSpec], axis: Axis) -> Vec<i32> {
+		let tracks = self.tracks(axis);
+		let mut prefs = tracks
+			.iter()
+			.map(|track| match track {
+				Track::Fixed(sz) => (*sz).max(0),
+				Track::Auto | Track::Fill(_) => 0,
+			})
+			.collect::<Vec<_>>();
+
+		for cell in cells.iter() {
+			let (first, span) = self.span(cell, axis);
+			if span == 1 && !matches!(tracks[first], Track::Fixed(_)) {
+				let pref = clamp_size(cell.pref, cell.min, cell.max);
+				let pref = match axis {
+					Axis::Horz => pref.cx,
+					Axis::Vert => pref.cy,
+				};
+				prefs[first] = prefs[first].max(pref);
+			}
+		}
+		prefs
+	}
+
+	/// Computes the offset and the size of each track, relative to the start
+	/// of the available length, which already excludes the margins.
+	#[must_use]
+	fn solve_tracks(&self, cells: &[CellSpec], axis: Axis, available: i32) -> Vec<(i32, i32)> {
+		let tracks = self.tracks(axis);
+		let prefs = self.track_prefs(cells, axis);
+		let spacing = self.spacing(axis);
+
+		let mut remaining = available - spacing * (tracks.len() as i32 - 1);
+		let mut total_weight = 0u64;
+		for (track, pref) in tracks.iter().zip(prefs.iter()) {
+			match track {
+				Track::Fixed(_) | Track::Auto => remaining -= pref,
+				Track::Fill(weight) => total_weight += *weight as u64,
+			}
+		}
+		let remaining = remaining.max(0) as u64;
+
+		// Cumulative rounding, so the fill tracks sum exactly the remaining space.
+		let mut acc_weight = 0u64;
+		let mut offset = 0;
+		tracks
+			.iter()
+			.zip(prefs.iter())
+			.map(|(track, pref)| {
+				let sz = match track {
+					Track::Fixed(_) | Track::Auto => *pref,
+					Track::Fill(weight) => {
+						let total_weight = total_weight.max(1); // all weights may be zero
+						let before = remaining * acc_weight / total_weight;
+						acc_weight += *weight as u64;
+						(remaining * acc_weight / total_weight - before) as i32
+					},
+				};
+				let ret = (offset, sz);
+				offset += sz + spacing;
+				ret
+			})
+			.collect()
+	}
+}
+
+/// Clamps the size between the minimum and the maximum, if any.
+#[must_use]
+fn clamp_size(sz: SIZE, min: SIZE, max: Option<SIZE>) -> SIZE {
+	let (max_cx, max_cy) = max.map_or((i32::MAX, i32::MAX), |max| (max.cx, max.cy));
+	SIZE::new(sz.cx.min(max_cx).max(min.cx), sz.cy.min(max_cy).max(min.cy))
+}
+
+/// Computes the preferred size of a grid, which is the sum of the preferred
+/// sizes of its tracks, plus spacing and margins.
+#[must_use]
+pub(in crate::gui) fn measure(grid: &GridSpec, cells: &[CellSpec]) -> SIZE {
+	let sum = |axis: Axis| -> i32 {
+		let prefs = grid.track_prefs(cells, axis);
+		prefs.iter().sum::<i32>()
+			+ grid.spacing(axis) * (prefs.len() as i32 - 1)
+			+ grid.margins(axis)
+	};
+	SIZE::new(sum(Axis::Horz), sum(Axis::Vert))
+}
+
+/// Computes the rectangle of each child within the given area, in the same
+/// order of `cells`.
+///
+/// Children are anchored at the top-left corner of their cells, and their
+/// sizes are clamped to their minimum and maximum sizes.
+#[must_use]
+pub(in crate::gui) fn arrange(grid: &GridSpec, cells: &[CellSpec], area: RECT) -> Vec<RECT> {
+	let inner_left = area.left + grid.margin.left;
+	let inner_top = area.top + grid.margin.top;
+	let cols =
+		grid.solve_tracks(cells, Axis::Horz, area.right - area.left - grid.margins(Axis::Horz));
+	let rows =
+		grid.solve_tracks(cells, Axis::Vert, area.bottom - area.top - grid.margins(Axis::Vert));
+
+	let span_rect = |tracks: &[(i32, i32)], first: usize, span: usize| -> (i32, i32) {
+		let (start, _) = tracks[first];
+		let (last_off, last_sz) = tracks[first + span - 1];
+		(start, last_off + last_sz - start)
+	};
+
+	cells
+		.iter()
+		.map(|cell| {
+			let (col, col_span) = grid.span(cell, Axis::Horz);
+			let (row, row_span) = grid.span(cell, Axis::Vert);
+			let (x, cx) = span_rect(&cols, col, col_span);
+			let (y, cy) = span_rect(&rows, row, row_span);
+			let sz = clamp_size(SIZE::new(cx, cy), cell.min, cell.max);
+			RECT {
+				left: inner_left + x,
+				top: inner_top + y,
+				right: inner_left + x + sz.cx,
+				bottom: inner_top + y + sz.cy,
+			}
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn cell(row: u32, col: u32, row_span: u32, col_span: u32, pref: (i32, i32)) -> CellSpec {
+		CellSpec {
+			row,
+			col,
+			row_span,
+			col_span,
+			pref: SIZE::new(pref.0, pref.1),
+			min: SIZE::default(),
+			max: None,
+		}
+	}
+
+	fn grid<'a>(rows: &'a [Track], cols: &'a [Track]) -> GridSpec<'a> {
+		GridSpec {
+			rows,
+			cols,
+			margin: Margin::default(),
+			spacing: SIZE::default(),
+		}
+	}
+
+	fn area(cx: i32, cy: i32) -> RECT {
+		RECT { left: 0, top: 0, right: cx, bottom: cy }
+	}
+
+	fn ltrb(rcs: &[RECT]) -> Vec<(i32, i32, i32, i32)> {
+		rcs.iter()
+			.map(|rc| (rc.left, rc.top, rc.right, rc.bottom))
+			.collect()
+	}
+
+	#[test]
+	fn fixed_auto_and_fill_tracks() {
+		let cols = [Track::Fixed(100), Track::Auto, Track::Fill(1)];
+		let cells =
+			[cell(0, 0, 1, 1, (10, 10)), cell(0, 1, 1, 1, (50, 20)), cell(0, 2, 1, 1, (10, 10))];
+		let rcs = arrange(&grid(&[], &cols), &cells, area(400, 300));
+		assert_eq!(ltrb(&rcs), [(0, 0, 100, 300), (100, 0, 150, 300), (150, 0, 400, 300)],);
+	}
+
+	#[test]
+	fn auto_track_takes_largest_child() {
+		let rows = [Track::Auto, Track::Fill(1)];
+		let cells =
+			[cell(0, 0, 1, 1, (10, 25)), cell(0, 0, 1, 1, (10, 40)), cell(1, 0, 1, 1, (10, 10))];
+		let rcs = arrange(&grid(&rows, &[]), &cells, area(100, 100));
+		assert_eq!(ltrb(&rcs), [(0, 0, 100, 40), (0, 0, 100, 40), (0, 40, 100, 100)]);
+	}
+
+	#[test]
+	fn fill_tracks_are_proportional_and_sum_exactly() {
+		let cols = [Track::Fill(1), Track::Fill(2)];
+		let cells = [cell(0, 0, 1, 1, (0, 0)), cell(0, 1, 1, 1, (0, 0))];
+		let rcs = arrange(&grid(&[], &cols), &cells, area(100, 10));
+		assert_eq!(ltrb(&rcs), [(0, 0, 33, 10), (33, 0, 100, 10)]);
+
+		let cols = [Track::Fill(1), Track::Fill(1), Track::Fill(1)];
+		let cells = [cell(0, 0, 1, 1, (0, 0)), cell(0, 1, 1, 1, (0, 0)), cell(0, 2, 1, 1, (0, 0))];
+		let rcs = arrange(&grid(&[], &cols), &cells, area(100, 10));
+		assert_eq!(rcs[2].right, 100);
+		assert_eq!(rcs.iter().map(|rc| rc.right - rc.left).sum::<i32>(), 100,);
+	}
+
+	#[test]
+	fn star_tracks_share_what_is_left() {
+		let cols = [Track::Fixed(40), Track::Fill(3), Track::Auto, Track::Fill(1)];
+		let cells = [cell(0, 2, 1, 1, (20, 0)), cell(0, 1, 1, 1, (0, 0))];
+		let rcs = arrange(&grid(&[], &cols), &cells, area(260, 10));
+		// 260 - 40 - 20 = 200 left, split in 150 and 50
+		assert_eq!(ltrb(&rcs), [(190, 0, 210, 10), (40, 0, 190, 10)]);
+	}
+
+	#[test]
+	fn no_room_left_for_fill_tracks() {
+		let cols = [Track::Fixed(80), Track::Fill(1)];
+		let cells = [cell(0, 1, 1, 1, (0, 0))];
+		let rcs = arrange(&grid(&[], &cols), &cells, area(50, 10));
+		assert_eq!(ltrb(&rcs), [(80, 0, 80, 10)]);
+	}
+
+	#[test]
+	fn margins_and_spacing() {
+		let cols = [Track::Fill(1), Track::Fill(1)];
+		let rows = [Track::Fixed(20), Track::Fill(1)];
+		let g = GridSpec {
+			rows: &rows,
+			cols: &cols,
+			margin: Margin::new(10, 5, 10, 5),
+			spacing: SIZE::new(4, 2),
+		};
+		let cells = [cell(0, 0, 1, 1, (0, 0)), cell(1, 1, 1, 1, (0, 0))];
+		let rcs = arrange(
+			&g,
+			&cells,
+			RECT {
+				left: 100,
+				top: 100,
+				right: 210,
+				bottom: 200,
+			},
+		);
+		// inner width 90 - 4 spacing = 86, two columns of 43
+		// inner height 90 - 2 spacing - 20 fixed = 68
+		assert_eq!(ltrb(&rcs), [(110, 105, 153, 125), (157, 127, 200, 195)]);
+	}
+
+	#[test]
+	fn spans_cover_tracks_and_spacing() {
+		let cols = [Track::Fixed(30), Track::Fixed(40), Track::Fill(1)];
+		let g = GridSpec {
+			rows: &[],
+			cols: &cols,
+			margin: Margin::default(),
+			spacing: SIZE::new(5, 0),
+		};
+		let cells = [cell(0, 0, 1, 2, (0, 0)), cell(0, 1, 1, 2, (0, 0))];
+		let rcs = arrange(&g, &cells, area(200, 10));
+		assert_eq!(ltrb(&rcs), [(0, 0, 75, 10), (35, 0, 200, 10)]);
+	}
+
+	#[test]
+	fn spanning_children_dont_size_auto_tracks() {
+		let cols = [Track::Auto, Track::Auto];
+		let cells = [cell(0, 0, 1, 2, (200, 10)), cell(0, 1, 1, 1, (20, 10))];
+		let rcs = arrange(&grid(&[], &cols), &cells, area(300, 10));
+		assert_eq!(ltrb(&rcs), [(0, 0, 20, 10), (0, 0, 20, 10)]);
+	}
+
+	#[test]
+	fn spans_are_clamped_to_existing_tracks() {
+		let cols = [Track::Fixed(10), Track::Fixed(20)];
+		let cells = [cell(0, 5, 1, 3, (0, 0)), cell(0, 0, 1, 0, (0, 0))];
+		let rcs = arrange(&grid(&[], &cols), &cells, area(100, 10));
+		assert_eq!(ltrb(&rcs), [(10, 0, 30, 10), (0, 0, 10, 10)]);
+	}
+
+	#[test]
+	fn min_and_max_sizes() {
+		let mut small = cell(0, 0, 1, 1, (0, 0));
+		small.max = Some(SIZE::new(50, 30));
+		let mut big = cell(0, 1, 1, 1, (0, 0));
+		big.min = SIZE::new(150, 120);
+		let cols = [Track::Fill(1), Track::Fill(1)];
+		let rcs = arrange(&grid(&[], &cols), &[small, big], area(200, 100));
+		assert_eq!(ltrb(&rcs), [(0, 0, 50, 30), (100, 0, 250, 120)]);
+	}
+
+	#[test]
+	fn empty_tracks_fill_the_area() {
+		let g = GridSpec {
+			rows: &[],
+			cols: &[],
+			margin: Margin::new(1, 2, 3, 4),
+			spacing: SIZE::new(10, 10),
+		};
+		let rcs = arrange(&g, &[cell(0, 0, 1, 1, (5, 5))], area(100, 50));
+		assert_eq!(ltrb(&rcs), [(1, 2, 97, 46)]);
+	}
+
+	#[test]
+	fn measure_sums_tracks_spacing_and_margins() {
+		let cols = [Track::Fixed(100), Track::Auto, Track::Fill(1)];
+		let rows = [Track::Auto, Track::Auto];
+		let g = GridSpec {
+			rows: &rows,
+			cols: &cols,
+			margin: Margin::new(1, 2, 3, 4),
+			spacing: SIZE::new(5, 6),
+		};
+		let mut clamped = cell(1, 2, 1, 1, (70, 500));
+		clamped.max = Some(SIZE::new(1000, 40));
+		let cells = [
+			cell(0, 1, 1, 1, (50, 20)),
+			clamped,
+			cell(0, 0, 2, 3, (999, 999)), // spanning, ignored
+		];
+		let sz = measure(&g, &cells);
+		assert_eq!((sz.cx, sz.cy), (100 + 50 + 70 + 5 * 2 + 4, 20 + 40 + 6 + 6));
+	}
+}
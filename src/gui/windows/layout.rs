@@ -5,6 +5,7 @@ use std::sync::Arc;
 
 use crate::co;
 use crate::decl::*;
+use crate::gui::{privs::*, *};
 use crate::msg::*;
 use crate::prelude::*;
 
@@ -48,8 +49,11 @@ struct ChildInfo {
 }
 
 struct LayoutObj {
+	hparent: UnsafeCell<HWND>,
+	dpi: UnsafeCell<u32>, // DPI the current coordinates refer to
 	ctrls: UnsafeCell<Vec<ChildInfo>>,
 	sz_parent_orig: UnsafeCell<Option<SIZE>>, // original parent client area, filled at WM_CREATE/INITDIALOG
+	grid: UnsafeCell<Option<Grid>>,
 	_pin: PhantomPinned,
 }
 
@@ -61,12 +65,24 @@ impl Layout {
 	#[must_use]
 	pub(in crate::gui) fn new() -> Self {
 		Self(Arc::pin(LayoutObj {
+			hparent: UnsafeCell::new(HWND::NULL),
+			dpi: UnsafeCell::new(0),
 			ctrls: UnsafeCell::new(Vec::new()),
 			sz_parent_orig: UnsafeCell::new(None),
+			grid: UnsafeCell::new(None),
 			_pin: PhantomPinned,
 		}))
 	}
 
+	/// Stores the parent window handle, when it's created or destroyed.
+	///
+	/// Since controls are created with coordinates in system DPI, this is the
+	/// initial DPI assumed for the parent.
+	pub(in crate::gui) fn set_parent(&self, hparent: &HWND) {
+		*unsafe { &mut *self.0.hparent.get() } = unsafe { hparent.raw_copy() };
+		*unsafe { &mut *self.0.dpi.get() } = if *hparent == HWND::NULL { 0 } else { system_dpi() };
+	}
+
 	/// Adds a new child control to the internal list, so this control will have
 	/// its position and size rearranged when requested.
 	pub(in crate::gui) fn add_child(
@@ -102,23 +118,68 @@ impl Layout {
 		Ok(())
 	}
 
+	/// Sets the grid which will arrange its controls, replacing any previous
+	/// one, and immediately arranges them within the parent client area.
+	pub(in crate::gui) fn set_grid(&self, hparent: &HWND, grid: Grid) -> SysResult<()> {
+		if *hparent == HWND::NULL {
+			panic!("Cannot set a grid before window creation.");
+		}
+
+		*unsafe { &mut *self.0.grid.get() } = Some(grid);
+
+		let rc_parent = hparent.GetClientRect()?;
+		self.rearrange(wm::Size {
+			request: co::SIZE_R::RESTORED,
+			client_area: SIZE::new(rc_parent.right, rc_parent.bottom),
+		})
+	}
+
 	/// Rearranges all child controls to fit the new width/height of parent
 	/// window.
 	pub(in crate::gui) fn rearrange(&self, p: wm::Size) -> SysResult<()> {
-		let ctrls = unsafe { &mut *self.0.ctrls.get() };
-		if ctrls.is_empty() // no controls
-			|| p.request == co::SIZE_R::MINIMIZED
-		{
+		if p.request == co::SIZE_R::MINIMIZED {
 			return Ok(()); // we're minimized
 		}
 
+		let ctrls = unsafe { &mut *self.0.ctrls.get() };
+		let grid = unsafe { &mut *self.0.grid.get() };
+		let grid_count = grid.as_ref().map_or(0, |grid| grid.ctrl_count());
+		if ctrls.is_empty() && grid_count == 0 {
+			return Ok(()); // no controls
+		}
+
+		let mut hdwp = HDWP::BeginDeferWindowPos((ctrls.len() + grid_count) as _)?;
+
+		if !ctrls.is_empty() {
+			self.rearrange_anchored(&mut hdwp, ctrls, p.client_area)?;
+		}
+
+		if let Some(grid) = grid {
+			grid.arrange(
+				RECT {
+					left: 0,
+					top: 0,
+					right: p.client_area.cx,
+					bottom: p.client_area.cy,
+				},
+				&mut hdwp,
+			)?;
+		}
+
+		Ok(())
+	}
+
+	fn rearrange_anchored(
+		&self,
+		hdwp: &mut HDWP,
+		ctrls: &mut [ChildInfo],
+		client_area: SIZE,
+	) -> SysResult<()> {
 		let sz_parent_orig = match unsafe { &mut *self.0.sz_parent_orig.get() } {
 			Some(sz) => *sz,
 			None => panic!("Original parent client area was not saved."),
 		};
 
-		let mut hdwp = HDWP::BeginDeferWindowPos(ctrls.len() as _)?;
-
 		for ctrl in ctrls.iter_mut() {
 			let mut uflags = co::SWP::NOZORDER;
 			if ctrl.horz == Horz::Repos && ctrl.vert == Vert::Repos {
@@ -144,24 +205,24 @@ impl Layout {
 				HwndPlace::None,
 				POINT::new(
 					match ctrl.horz {
-						Horz::Repos => p.client_area.cx - sz_parent_orig.cx + rc_orig.left,
+						Horz::Repos => client_area.cx - sz_parent_orig.cx + rc_orig.left,
 						_ => rc_orig.left, // keep original x pos
 					},
 					match ctrl.vert {
-						Vert::Repos => p.client_area.cy - sz_parent_orig.cy + rc_orig.top,
+						Vert::Repos => client_area.cy - sz_parent_orig.cy + rc_orig.top,
 						_ => rc_orig.top, // keep original y pos
 					},
 				),
 				SIZE::new(
 					match ctrl.horz {
 						Horz::Resize => {
-							p.client_area.cx - sz_parent_orig.cx + rc_orig.right - rc_orig.left
+							client_area.cx - sz_parent_orig.cx + rc_orig.right - rc_orig.left
 						},
 						_ => rc_orig.right - rc_orig.left, // keep original width
 					},
 					match ctrl.vert {
 						Vert::Resize => {
-							p.client_area.cy - sz_parent_orig.cy + rc_orig.bottom - rc_orig.top
+							client_area.cy - sz_parent_orig.cy + rc_orig.bottom - rc_orig.top
 						},
 						_ => rc_orig.bottom - rc_orig.top, // keep original height
 					},
@@ -172,4 +233,158 @@ impl Layout {
 
 		Ok(())
 	}
+
+	/// Rescales the direct child controls of the parent, along with their fonts
+	/// and image lists, if the parent DPI is different from the DPI the
+	/// coordinates currently refer to.
+	///
+	/// If `move_ctrls` is false, the control positions are assumed to be
+	/// already rescaled by the system, like in dialogs. If `rc_parent` is
+	/// given, the parent is moved to it.
+	pub(in crate::gui) fn rescale_dpi(
+		&self,
+		move_ctrls: bool,
+		rc_parent: Option<RECT>,
+	) -> AnyResult<()> {
+		let hparent = unsafe { &*self.0.hparent.get() };
+		let dpi = unsafe { &mut *self.0.dpi.get() };
+		let (old_dpi, new_dpi) = (*dpi, hwnd_dpi(hparent));
+		if old_dpi == 0 || new_dpi == 0 || old_dpi == new_dpi {
+			return Ok(()); // parent not created, or nothing to do
+		}
+		*dpi = new_dpi;
+		let scale = |v: i32| MulDiv(v, new_dpi as _, old_dpi as _);
+
+		let mut hchildren = Vec::<HWND>::new();
+		let mut hnext = hparent.GetWindow(co::GW::CHILD).ok();
+		while let Some(hchild) = hnext {
+			hnext = hchild.GetWindow(co::GW::HWNDNEXT).ok();
+			hchildren.push(hchild);
+		}
+
+		if move_ctrls && !hchildren.is_empty() {
+			let mut hdwp = HDWP::BeginDeferWindowPos(hchildren.len() as _)?;
+			for hchild in hchildren.iter() {
+				let rc = hparent.ScreenToClientRc(hchild.GetWindowRect()?)?;
+				hdwp.DeferWindowPos(
+					hchild,
+					HwndPlace::None,
+					POINT::new(scale(rc.left), scale(rc.top)),
+					SIZE::new(scale(rc.right - rc.left), scale(rc.bottom - rc.top)),
+					co::SWP::NOZORDER | co::SWP::NOACTIVATE,
+				)?;
+			}
+		}
+
+		let old_font = ui_font::get_for_dpi(old_dpi)?;
+		let mut rescaled_hils = Vec::<HIMAGELIST>::new(); // image lists can be shared among controls
+		let new_font = ui_font::get_for_dpi(new_dpi)?;
+		for hchild in hchildren.iter() {
+			if unsafe { hchild.SendMessage(wm::GetFont {}) } == Some(unsafe { old_font.raw_copy() })
+			{
+				unsafe {
+					hchild.SendMessage(wm::SetFont { hfont: new_font.raw_copy(), redraw: true });
+				}
+			}
+			rescale_image_lists(hchild, old_dpi as _, new_dpi as _, &mut rescaled_hils)?;
+		}
+
+		let ctrls = unsafe { &mut *self.0.ctrls.get() };
+		for ctrl in ctrls.iter_mut() {
+			if let Some(rc) = &mut ctrl.rc_orig {
+				*rc = RECT {
+					left: scale(rc.left),
+					top: scale(rc.top),
+					right: scale(rc.right),
+					bottom: scale(rc.bottom),
+				};
+			}
+		}
+		if let Some(sz) = unsafe { &mut *self.0.sz_parent_orig.get() } {
+			*sz = SIZE::new(scale(sz.cx), scale(sz.cy));
+		}
+		if let Some(grid) = unsafe { &mut *self.0.grid.get() } {
+			grid.rescale(old_dpi as _, new_dpi as _);
+		}
+
+		if let Some(rc) = rc_parent {
+			hparent.SetWindowPos(
+				HwndPlace::None,
+				POINT::new(rc.left, rc.top),
+				SIZE::new(rc.right - rc.left, rc.bottom - rc.top),
+				co::SWP::NOZORDER | co::SWP::NOACTIVATE,
+			)?;
+		}
+
+		let rc_client = hparent.GetClientRect()?;
+		self.rearrange(wm::Size {
+			request: co::SIZE_R::RESTORED,
+			client_area: SIZE::new(rc_client.right, rc_client.bottom),
+		})?;
+		Ok(())
+	}
+}
+
+/// Rescales, in place, the image lists of the common controls which have them.
+///
+/// Image lists already in `rescaled` are only set again, and the newly rescaled
+/// ones are appended to it.
+fn rescale_image_lists(
+	hctrl: &HWND,
+	old_dpi: i32,
+	new_dpi: i32,
+	rescaled: &mut Vec<HIMAGELIST>,
+) -> AnyResult<()> {
+	let mut rescale = |hil: Option<HIMAGELIST>| -> HrResult<Option<HIMAGELIST>> {
+		if let Some(hil) = hil.as_ref().filter(|hil| !rescaled.contains(hil)) {
+			let sz = hil.GetIconSize()?;
+			let icons = (0..hil.GetImageCount())
+				.map(|i| hil.GetIcon(i, co::ILD::NORMAL))
+				.collect::<HrResult<Vec<_>>>()?;
+			hil.SetIconSize(SIZE::new(
+				MulDiv(sz.cx, new_dpi, old_dpi),
+				MulDiv(sz.cy, new_dpi, old_dpi),
+			))?; // removes all images
+			for icon in icons.iter() {
+				hil.AddIcon(icon)?;
+			}
+			rescaled.push(unsafe { hil.raw_copy() });
+		}
+		Ok(hil)
+	};
+
+	// The image lists are set again, so the controls update their metrics.
+	unsafe {
+		match hctrl.GetClassName()?.as_str() {
+			"SysListView32" => {
+				for kind in [co::LVSIL::NORMAL, co::LVSIL::SMALL, co::LVSIL::STATE] {
+					if let Some(hil) = rescale(hctrl.SendMessage(lvm::GetImageList { kind }))? {
+						hctrl.SendMessage(lvm::SetImageList { kind, himagelist: Some(hil) });
+					}
+				}
+			},
+			"SysTreeView32" => {
+				for kind in [co::TVSIL::NORMAL, co::TVSIL::STATE] {
+					if let Some(hil) = rescale(hctrl.SendMessage(tvm::GetImageList { kind }))? {
+						hctrl.SendMessage(tvm::SetImageList { kind, himagelist: Some(hil) });
+					}
+				}
+			},
+			"SysHeader32" => {
+				for kind in [co::HDSIL::NORMAL, co::HDSIL::STATE] {
+					if let Some(hil) = rescale(hctrl.SendMessage(hdm::GetImageList { kind }))? {
+						hctrl.SendMessage(hdm::SetImageList { kind, himagelist: Some(hil) });
+					}
+				}
+			},
+			"ToolbarWindow32" => {
+				if let Some(hil) = rescale(hctrl.SendMessage(tbm::GetImageList {}))? {
+					hctrl.SendMessage(tbm::SetImageList { himagelist: hil });
+					hctrl.SendMessage(tbm::AutoSize {});
+				}
+			},
+			_ => {},
+		}
+	}
+	Ok(())
 }
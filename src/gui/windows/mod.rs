@@ -1,9 +1,12 @@
 mod base_wnd;
+mod commands;
 mod dlg_base;
 mod dlg_control;
 mod dlg_main;
 mod dlg_modal;
 mod dlg_modeless;
+mod grid;
+mod grid_solver;
 mod layout;
 mod raw_base;
 mod raw_control;
@@ -19,7 +22,7 @@ mod window_modeless;
 
 pub(in crate::gui) mod privs {
 	pub(in crate::gui) use super::base_wnd::BaseWnd;
-	pub(in crate::gui) use super::dlg_base::DlgBase;
+	pub(in crate::gui) use super::dlg_base::{DlgBase, DlgSrc};
 	pub(in crate::gui) use super::dlg_control::DlgControl;
 	pub(in crate::gui) use super::dlg_main::DlgMain;
 	pub(in crate::gui) use super::dlg_modal::DlgModal;
@@ -33,6 +36,8 @@ pub(in crate::gui) mod privs {
 }
 
 pub mod decl {
+	pub use super::commands::{Commands, MenuDef, Shortcut};
+	pub use super::grid::{Grid, GridCell, Margin, Track};
 	pub use super::layout::{Horz, Vert};
 	pub use super::raw_opts::*;
 	pub use super::window_control::WindowControl;
@@ -86,6 +86,47 @@ impl WindowControl {
 		if *parent.hwnd() != HWND::NULL {
 			panic!("Cannot create a custom child control after the parent window is created.");
 		}
-		Self(RawDlg::Dlg(DlgControl::new(parent, dlg_id, position, resize_behavior, ctrl_id)))
+		Self(RawDlg::Dlg(DlgControl::new(
+			parent,
+			DlgSrc::Id(dlg_id),
+			position,
+			resize_behavior,
+			ctrl_id,
+		)))
+	}
+
+	/// Instantiates a new `WindowControl` object, to be created from an
+	/// in-memory dialog template with
+	/// [`HINSTANCE::CreateDialogIndirectParam`](crate::prelude::user_Hinstance::CreateDialogIndirectParam).
+	///
+	/// The template should have the [`WS::CHILD`](crate::co::WS::CHILD) and
+	/// [`DS::CONTROL`](crate::co::DS::CONTROL) styles.
+	///
+	/// If the parent window is a dialog, position is in Dialog Template Units;
+	/// otherwise in pixels, which will be multiplied to match current system
+	/// DPI.
+	///
+	/// # Panics
+	///
+	/// Panics if the parent dialog was already created – that is, you cannot
+	/// dynamically create a `WindowControl` in an event closure.
+	#[must_use]
+	pub fn new_dlg_template(
+		parent: &(impl GuiParent + 'static),
+		template: &DlgTemplate,
+		position: (i32, i32),
+		resize_behavior: (Horz, Vert),
+		ctrl_id: Option<u16>,
+	) -> Self {
+		if *parent.hwnd() != HWND::NULL {
+			panic!("Cannot create a custom child control after the parent window is created.");
+		}
+		Self(RawDlg::Dlg(DlgControl::new(
+			parent,
+			DlgSrc::from_template(template),
+			position,
+			resize_behavior,
+			ctrl_id,
+		)))
 	}
 }
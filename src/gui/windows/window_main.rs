@@ -55,7 +55,22 @@ impl WindowMain {
 	/// [`HINSTANCE::CreateDialogParam`](crate::prelude::user_Hinstance::CreateDialogParam).
 	#[must_use]
 	pub fn new_dlg(dlg_id: u16, icon_id: Option<u16>, accel_tbl_id: Option<u16>) -> Self {
-		Self(RawDlg::Dlg(DlgMain::new(dlg_id, icon_id, accel_tbl_id)))
+		Self(RawDlg::Dlg(DlgMain::new(DlgSrc::Id(dlg_id), icon_id, accel_tbl_id)))
+	}
+
+	/// Instantiates a new `WindowMain` object, to be created from an in-memory
+	/// dialog template with
+	/// [`HINSTANCE::CreateDialogIndirectParam`](crate::prelude::user_Hinstance::CreateDialogIndirectParam).
+	///
+	/// The icon and the accelerator table are still loaded from the
+	/// resources.
+	#[must_use]
+	pub fn new_dlg_template(
+		template: &DlgTemplate,
+		icon_id: Option<u16>,
+		accel_tbl_id: Option<u16>,
+	) -> Self {
+		Self(RawDlg::Dlg(DlgMain::new(DlgSrc::from_template(template), icon_id, accel_tbl_id)))
 	}
 
 	/// Physically creates the window, then runs the main application loop. This
@@ -68,9 +83,7 @@ impl WindowMain {
 	///
 	/// Panics if the window is already created.
 	pub fn run_main(&self, cmd_show: Option<co::SW>) -> AnyResult<i32> {
-		if IsWindowsVistaOrGreater()? {
-			SetProcessDPIAware()?;
-		}
+		set_process_dpi_aware()?;
 
 		InitCommonControls();
 
@@ -52,7 +52,15 @@ impl WindowModal {
 	/// [`HINSTANCE::DialogBoxParam`](crate::prelude::user_Hinstance::DialogBoxParam).
 	#[must_use]
 	pub fn new_dlg(dlg_id: u16) -> Self {
-		Self(RawDlg::Dlg(DlgModal::new(dlg_id)))
+		Self(RawDlg::Dlg(DlgModal::new(DlgSrc::Id(dlg_id))))
+	}
+
+	/// Instantiates a new `WindowModal` object, to be created from an
+	/// in-memory dialog template with
+	/// [`HINSTANCE::DialogBoxIndirectParam`](crate::prelude::user_Hinstance::DialogBoxIndirectParam).
+	#[must_use]
+	pub fn new_dlg_template(template: &DlgTemplate) -> Self {
+		Self(RawDlg::Dlg(DlgModal::new(DlgSrc::from_template(template))))
 	}
 
 	/// Physically creates the window, then runs the modal loop. This method
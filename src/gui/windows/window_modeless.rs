@@ -69,7 +69,27 @@ impl WindowModeless {
 		if *parent.hwnd() != HWND::NULL {
 			panic!("Cannot create a modeless window after the parent window is created.");
 		}
-		Self(RawDlg::Dlg(DlgModeless::new(parent, dlg_id, position)))
+		Self(RawDlg::Dlg(DlgModeless::new(parent, DlgSrc::Id(dlg_id), position)))
+	}
+
+	/// Instantiates a new `WindowModeless` object, to be created from an
+	/// in-memory dialog template with
+	/// [`HINSTANCE::CreateDialogIndirectParam`](crate::prelude::user_Hinstance::CreateDialogIndirectParam).
+	///
+	/// # Panics
+	///
+	/// Panics if the parent dialog was already created – that is, you cannot
+	/// dynamically create a `WindowModeless` in an event closure.
+	#[must_use]
+	pub fn new_dlg_template(
+		parent: &(impl GuiParent + 'static),
+		template: &DlgTemplate,
+		position: (i32, i32),
+	) -> Self {
+		if *parent.hwnd() != HWND::NULL {
+			panic!("Cannot create a modeless window after the parent window is created.");
+		}
+		Self(RawDlg::Dlg(DlgModeless::new(parent, DlgSrc::from_template(template), position)))
 	}
 
 	/// Closes the window by posting a [`WM_CLOSE`](crate::msg::wm::Close)
@@ -452,3 +452,37 @@ where
 		}
 	}
 }
+
+pub(in crate::kernel) struct TextReaderLinesIter<'a> {
+	reader: &'a mut TextReader,
+	no_more: bool,
+}
+
+impl<'a> Iterator for TextReaderLinesIter<'a> {
+	type Item = SysResult<String>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.no_more {
+			return None;
+		}
+
+		match self.reader.read_line() {
+			Err(e) => {
+				self.no_more = true; // prevent further iterations
+				Some(Err(e))
+			},
+			Ok(None) => {
+				self.no_more = true;
+				None
+			},
+			Ok(Some(line)) => Some(Ok(line)),
+		}
+	}
+}
+
+impl<'a> TextReaderLinesIter<'a> {
+	#[must_use]
+	pub(in crate::kernel) fn new(reader: &'a mut TextReader) -> Self {
+		Self { reader, no_more: false }
+	}
+}
@@ -0,0 +1,852 @@
+use crate::co;
+use crate::decl::*;
+
+/// Incremental decoder from raw bytes into UTF-16, which keeps the state
+/// between successive chunks of data.
+pub(in crate::kernel) struct Decoder {
+	encoding: Encoding,
+	state: DecoderState,
+}
+
+enum DecoderState {
+	Stateless,
+	Scsu(ScsuState),
+	Bocu1(Bocu1State),
+}
+
+impl Decoder {
+	/// Creates a new decoder. If `after_bom` is true, the state is set as if
+	/// the BOM had just been decoded.
+	#[must_use]
+	pub(in crate::kernel) fn new(encoding: Encoding, after_bom: bool) -> SysResult<Self> {
+		let state = match encoding {
+			Encoding::Unknown => return Err(co::ERROR::INVALID_PARAMETER),
+			Encoding::Scsu => DecoderState::Scsu(ScsuState::new()),
+			Encoding::Bocu1 => DecoderState::Bocu1(Bocu1State::new(after_bom)),
+			_ => DecoderState::Stateless,
+		};
+		Ok(Self { encoding, state })
+	}
+
+	/// Decodes the data, appending the chars to `dest`, and returns how many
+	/// bytes were consumed. Bytes of an incomplete sequence at the end are not
+	/// consumed, unless `is_last` is true, when they're either discarded or
+	/// reported as an error, depending on the encoding.
+	pub(in crate::kernel) fn decode(
+		&mut self,
+		src: &[u8],
+		dest: &mut Vec<u16>,
+		is_last: bool,
+	) -> SysResult<usize> {
+		match &mut self.state {
+			DecoderState::Scsu(scsu) => scsu.decode(src, dest, is_last),
+			DecoderState::Bocu1(bocu1) => bocu1.decode(src, dest, is_last),
+			DecoderState::Stateless => match self.encoding {
+				Encoding::Ansi => {
+					dest.extend(src.iter().map(|ch| *ch as u16)); // raw u8 to u16 conversion
+					Ok(src.len())
+				},
+				Encoding::Win1252 => {
+					if !src.is_empty() {
+						dest.extend(MultiByteToWideChar(
+							co::CP::WINDOWS_1252,
+							co::MBC::NoValue,
+							src,
+						)?);
+					}
+					Ok(src.len())
+				},
+				Encoding::Utf8 => {
+					let complete_len = if is_last { src.len() } else { utf8_complete_len(src) };
+					if complete_len > 0 {
+						dest.extend(MultiByteToWideChar(
+							co::CP::UTF8,
+							co::MBC::ERR_INVALID_CHARS,
+							&src[..complete_len],
+						)?);
+					}
+					Ok(complete_len)
+				},
+				Encoding::Utf16be | Encoding::Utf16le => {
+					let is_big_endian = self.encoding == Encoding::Utf16be;
+					dest.extend(src.chunks_exact(2).map(|ch2| {
+						let ch2 = [ch2[0], ch2[1]];
+						if is_big_endian {
+							u16::from_be_bytes(ch2)
+						} else {
+							u16::from_le_bytes(ch2)
+						}
+					}));
+					Ok(if is_last {
+						src.len() // if odd number of bytes, discard last one
+					} else {
+						src.len() - src.len() % 2
+					})
+				},
+				Encoding::Utf32be | Encoding::Utf32le => {
+					let is_big_endian = self.encoding == Encoding::Utf32be;
+					for ch4 in src.chunks_exact(4) {
+						let ch4 = [ch4[0], ch4[1], ch4[2], ch4[3]];
+						push_code_point(
+							dest,
+							if is_big_endian {
+								u32::from_be_bytes(ch4)
+							} else {
+								u32::from_le_bytes(ch4)
+							},
+						)?;
+					}
+					if is_last && src.len() % 4 != 0 {
+						Err(co::ERROR::NO_UNICODE_TRANSLATION) // truncated char
+					} else {
+						Ok(src.len() - src.len() % 4)
+					}
+				},
+				Encoding::Scsu | Encoding::Bocu1 | Encoding::Unknown => unreachable!(),
+			},
+		}
+	}
+}
+
+/// Incremental encoder from UTF-16 into raw bytes, which keeps the state
+/// between successive chunks of data.
+pub(in crate::kernel) struct Encoder {
+	encoding: Encoding,
+	state: EncoderState,
+	pending_high: Option<u16>, // high surrogate at the end of the previous chunk
+}
+
+enum EncoderState {
+	Stateless,
+	Scsu(ScsuState),
+	Bocu1(Bocu1State),
+}
+
+impl Encoder {
+	/// Creates a new encoder.
+	///
+	/// The BOM is not written by the encoder; note that a BOCU-1 BOM must be
+	/// followed by a reset byte, so the encoder state remains the initial one.
+	#[must_use]
+	pub(in crate::kernel) fn new(encoding: Encoding) -> SysResult<Self> {
+		let state = match encoding {
+			Encoding::Unknown => return Err(co::ERROR::INVALID_PARAMETER),
+			Encoding::Scsu => EncoderState::Scsu(ScsuState::new()),
+			Encoding::Bocu1 => EncoderState::Bocu1(Bocu1State::new(false)),
+			_ => EncoderState::Stateless,
+		};
+		Ok(Self { encoding, state, pending_high: None })
+	}
+
+	/// Encodes the chars, appending the bytes to `dest`. A high surrogate at
+	/// the end is kept until the next call, unless `is_last` is true.
+	///
+	/// Fails with
+	/// [`ERROR::NO_UNICODE_TRANSLATION`](crate::co::ERROR::NO_UNICODE_TRANSLATION)
+	/// if a char cannot be represented in the encoding.
+	pub(in crate::kernel) fn encode(
+		&mut self,
+		src: &[u16],
+		dest: &mut Vec<u8>,
+		is_last: bool,
+	) -> SysResult<()> {
+		let mut chars = Vec::<u16>::with_capacity(src.len() + 1);
+		chars.extend(self.pending_high.take());
+		chars.extend_from_slice(src);
+		if !is_last {
+			if let Some(last) = chars.last().copied() {
+				if (0xd800..=0xdbff).contains(&last) {
+					self.pending_high = chars.pop(); // wait for the low surrogate
+				}
+			}
+		}
+
+		match self.encoding {
+			Encoding::Ansi => {
+				for ch in chars.iter() {
+					if *ch > 0xff {
+						return Err(co::ERROR::NO_UNICODE_TRANSLATION);
+					}
+					dest.push(*ch as _);
+				}
+			},
+			Encoding::Win1252 => {
+				if !chars.is_empty() {
+					let mut used_default_char = false;
+					dest.extend(WideCharToMultiByte(
+						co::CP::WINDOWS_1252,
+						co::WC::NO_BEST_FIT_CHARS,
+						&chars,
+						None,
+						Some(&mut used_default_char),
+					)?);
+					if used_default_char {
+						return Err(co::ERROR::NO_UNICODE_TRANSLATION);
+					}
+				}
+			},
+			Encoding::Utf8 => {
+				if !chars.is_empty() {
+					dest.extend(WideCharToMultiByte(
+						co::CP::UTF8,
+						co::WC::ERR_INVALID_CHARS,
+						&chars,
+						None,
+						None,
+					)?);
+				}
+			},
+			Encoding::Utf16be => chars.iter().for_each(|ch| dest.extend(ch.to_be_bytes())),
+			Encoding::Utf16le => chars.iter().for_each(|ch| dest.extend(ch.to_le_bytes())),
+			Encoding::Utf32be | Encoding::Utf32le | Encoding::Scsu | Encoding::Bocu1 => {
+				for cp in char::decode_utf16(chars.iter().copied()) {
+					let cp = cp.map_err(|_| co::ERROR::NO_UNICODE_TRANSLATION)? as u32;
+					match &mut self.state {
+						EncoderState::Scsu(scsu) => scsu.encode(cp, dest),
+						EncoderState::Bocu1(bocu1) => bocu1.encode(cp, dest),
+						EncoderState::Stateless => {
+							dest.extend(if self.encoding == Encoding::Utf32be {
+								cp.to_be_bytes()
+							} else {
+								cp.to_le_bytes()
+							})
+						},
+					}
+				}
+			},
+			Encoding::Unknown => unreachable!(),
+		}
+		Ok(())
+	}
+}
+
+/// Returns the length of the data without the incomplete UTF-8 sequence at its
+/// end, if any.
+#[must_use]
+pub(in crate::kernel) fn utf8_complete_len(src: &[u8]) -> usize {
+	for back in 1..=src.len().min(4) {
+		let ch = src[src.len() - back];
+		if ch & 0xc0 != 0x80 {
+			// not a continuation byte
+			let seq_len = match ch {
+				0xc0..=0xdf => 2,
+				0xe0..=0xef => 3,
+				0xf0..=0xf7 => 4,
+				_ => 1,
+			};
+			return if seq_len > back { src.len() - back } else { src.len() };
+		}
+	}
+	src.len() // only continuation bytes, they're invalid anyway
+}
+
+/// Appends the code point as one or two UTF-16 chars.
+fn push_code_point(dest: &mut Vec<u16>, cp: u32) -> SysResult<()> {
+	let ch = char::from_u32(cp).ok_or(co::ERROR::NO_UNICODE_TRANSLATION)?;
+	let mut buf = [0u16; 2];
+	dest.extend_from_slice(ch.encode_utf16(&mut buf));
+	Ok(())
+}
+
+/// State of the
+/// [SCSU](https://www.unicode.org/reports/tr6/) decoder and encoder.
+struct ScsuState {
+	windows: [u32; 8],
+	active: usize,
+	unicode_mode: bool,
+	next_window: usize, // encoder only: next dynamic window to be redefined
+}
+
+impl ScsuState {
+	const STATIC_WINDOWS: [u32; 8] =
+		[0x0000, 0x0080, 0x0100, 0x0300, 0x2000, 0x2080, 0x2100, 0x3000];
+	const DYNAMIC_WINDOWS: [u32; 8] =
+		[0x0080, 0x00c0, 0x0400, 0x0600, 0x0900, 0x3040, 0x30a0, 0xff00];
+
+	const SQ0: u8 = 0x01;
+	const SDX: u8 = 0x0b;
+	const SQU: u8 = 0x0e;
+	const SCU: u8 = 0x0f;
+	const SC0: u8 = 0x10;
+	const SD0: u8 = 0x18;
+	const UC0: u8 = 0xe0;
+	const UD0: u8 = 0xe8;
+	const UQU: u8 = 0xf0;
+	const UDX: u8 = 0xf1;
+
+	#[must_use]
+	const fn new() -> Self {
+		Self {
+			windows: Self::DYNAMIC_WINDOWS,
+			active: 0,
+			unicode_mode: false,
+			next_window: 1,
+		}
+	}
+
+	/// Converts a window offset index into the actual offset.
+	#[must_use]
+	fn index_to_offset(index: u8) -> SysResult<u32> {
+		Ok(match index {
+			0x01..=0x67 => index as u32 * 0x80,
+			0x68..=0xa7 => index as u32 * 0x80 + 0xac00,
+			0xf9 => 0x00c0,
+			0xfa => 0x0250,
+			0xfb => 0x0370,
+			0xfc => 0x0530,
+			0xfd => 0x3040,
+			0xfe => 0x30a0,
+			0xff => 0xff60,
+			_ => return Err(co::ERROR::NO_UNICODE_TRANSLATION), // reserved
+		})
+	}
+
+	/// Converts a code point into a window offset index, if possible.
+	#[must_use]
+	fn offset_to_index(cp: u32) -> Option<u8> {
+		match cp {
+			0x0080..=0x33ff => Some((cp >> 7) as _),
+			0xe000..=0xffff => Some(((cp - 0xac00) >> 7) as _),
+			_ => None,
+		}
+	}
+
+	fn decode(&mut self, src: &[u8], dest: &mut Vec<u16>, is_last: bool) -> SysResult<usize> {
+		let mut i = 0;
+		while i < src.len() {
+			let tag = src[i];
+			let arg_len = if self.unicode_mode {
+				match tag {
+					Self::UC0..=0xe7 => 0,
+					Self::UD0..=0xef => 1,
+					Self::UQU | Self::UDX => 2,
+					_ => 1, // high byte of a char
+				}
+			} else {
+				match tag {
+					Self::SQ0..=0x08 | Self::SD0..=0x1f => 1,
+					Self::SDX | Self::SQU => 2,
+					_ => 0,
+				}
+			};
+
+			if i + arg_len >= src.len() && arg_len > 0 {
+				if is_last {
+					return Err(co::ERROR::NO_UNICODE_TRANSLATION); // truncated sequence
+				}
+				break; // wait for more data
+			}
+			let args = &src[i + 1..i + 1 + arg_len];
+
+			if self.unicode_mode {
+				match tag {
+					Self::UC0..=0xe7 => {
+						self.active = (tag - Self::UC0) as _;
+						self.unicode_mode = false;
+					},
+					Self::UD0..=0xef => {
+						self.active = (tag - Self::UD0) as _;
+						self.windows[self.active] = Self::index_to_offset(args[0])?;
+						self.unicode_mode = false;
+					},
+					Self::UQU => dest.push(u16::from_be_bytes([args[0], args[1]])),
+					Self::UDX => {
+						self.define_extended(args[0], args[1]);
+						self.unicode_mode = false;
+					},
+					0xf2 => return Err(co::ERROR::NO_UNICODE_TRANSLATION), // reserved
+					_ => dest.push(u16::from_be_bytes([tag, args[0]])),
+				}
+			} else {
+				match tag {
+					0x00 | 0x09 | 0x0a | 0x0d | 0x20..=0x7f => dest.push(tag as _),
+					Self::SQ0..=0x08 => {
+						let window = (tag - Self::SQ0) as usize;
+						push_code_point(
+							dest,
+							if args[0] < 0x80 {
+								Self::STATIC_WINDOWS[window] + args[0] as u32
+							} else {
+								self.windows[window] + (args[0] - 0x80) as u32
+							},
+						)?;
+					},
+					Self::SDX => self.define_extended(args[0], args[1]),
+					0x0c => return Err(co::ERROR::NO_UNICODE_TRANSLATION), // reserved
+					Self::SQU => dest.push(u16::from_be_bytes([args[0], args[1]])),
+					Self::SCU => self.unicode_mode = true,
+					Self::SC0..=0x17 => self.active = (tag - Self::SC0) as _,
+					Self::SD0..=0x1f => {
+						self.active = (tag - Self::SD0) as _;
+						self.windows[self.active] = Self::index_to_offset(args[0])?;
+					},
+					0x80..=0xff => {
+						push_code_point(dest, self.windows[self.active] + (tag - 0x80) as u32)?
+					},
+				}
+			}
+			i += 1 + arg_len;
+		}
+		Ok(i)
+	}
+
+	fn define_extended(&mut self, hi: u8, lo: u8) {
+		self.active = (hi >> 5) as _;
+		self.windows[self.active] = 0x10000 + ((((hi & 0x1f) as u32) << 8) | lo as u32) * 0x80;
+	}
+
+	#[must_use]
+	fn is_direct(cp: u32) -> bool {
+		matches!(cp, 0x00 | 0x09 | 0x0a | 0x0d | 0x20..=0x7f)
+	}
+
+	#[must_use]
+	fn window_of(&self, cp: u32) -> Option<usize> {
+		if self.windows[self.active] <= cp && cp < self.windows[self.active] + 0x80 {
+			Some(self.active) // prefer the currently active window
+		} else {
+			self.windows
+				.iter()
+				.position(|off| *off <= cp && cp < *off + 0x80)
+		}
+	}
+
+	fn encode(&mut self, cp: u32, dest: &mut Vec<u8>) {
+		if self.unicode_mode {
+			if (0x3400..0xe000).contains(&cp) {
+				let [hi, lo] = (cp as u16).to_be_bytes(); // CJK and Hangul stay in Unicode mode
+				dest.extend([hi, lo]);
+				return;
+			}
+			dest.push(Self::UC0 + self.active as u8); // back to single-byte mode
+			self.unicode_mode = false;
+		}
+
+		if Self::is_direct(cp) {
+			dest.push(cp as _);
+		} else if cp < 0x20 {
+			dest.extend([Self::SQ0, cp as _]); // other C0 controls, quoted from static window 0
+		} else if let Some(window) = self.window_of(cp) {
+			if window != self.active {
+				dest.push(Self::SC0 + window as u8);
+				self.active = window;
+			}
+			dest.push((0x80 + cp - self.windows[window]) as _);
+		} else if let Some(index) = Self::offset_to_index(cp) {
+			let window = self.take_next_window();
+			self.windows[window] = Self::index_to_offset(index).unwrap();
+			self.active = window;
+			dest.extend([Self::SD0 + window as u8, index, (0x80 + cp - self.windows[window]) as _]);
+		} else if cp >= 0x10000 {
+			let window = self.take_next_window();
+			let block = (cp - 0x10000) >> 7;
+			self.windows[window] = 0x10000 + block * 0x80;
+			self.active = window;
+			dest.extend([
+				Self::SDX,
+				((window as u8) << 5) | (block >> 8) as u8,
+				block as u8,
+				(0x80 + cp - self.windows[window]) as _,
+			]);
+		} else {
+			dest.push(Self::SCU); // CJK and Hangul, switch to Unicode mode
+			self.unicode_mode = true;
+			let [hi, lo] = (cp as u16).to_be_bytes();
+			dest.extend([hi, lo]); // high byte is never in the 0xe0-0xf2 tag range here
+		}
+	}
+
+	#[must_use]
+	fn take_next_window(&mut self) -> usize {
+		let window = self.next_window;
+		self.next_window = if window == 7 { 1 } else { window + 1 }; // keep window 0 for Latin-1
+		window
+	}
+}
+
+/// State of the
+/// [BOCU-1](https://www.unicode.org/notes/tn6/) decoder and encoder.
+struct Bocu1State {
+	prev: i32,
+	count: i32, // decoder only: number of trail bytes still expected
+	diff: i32,  // decoder only: partial difference
+}
+
+impl Bocu1State {
+	const ASCII_PREV: i32 = 0x40;
+	const MIN: i32 = 0x21;
+	const MIDDLE: i32 = 0x90;
+	const RESET: u8 = 0xff;
+
+	const TRAIL_CONTROLS_COUNT: i32 = 20;
+	const TRAIL_BYTE_OFFSET: i32 = Self::MIN - Self::TRAIL_CONTROLS_COUNT;
+	const TRAIL_COUNT: i32 = (0xff - Self::MIN + 1) + Self::TRAIL_CONTROLS_COUNT;
+
+	const SINGLE: i32 = 64;
+	const LEAD_2: i32 = 43;
+	const LEAD_3: i32 = 3;
+
+	const REACH_POS_1: i32 = Self::SINGLE - 1;
+	const REACH_NEG_1: i32 = -Self::SINGLE;
+	const REACH_POS_2: i32 = Self::REACH_POS_1 + Self::LEAD_2 * Self::TRAIL_COUNT;
+	const REACH_NEG_2: i32 = Self::REACH_NEG_1 - Self::LEAD_2 * Self::TRAIL_COUNT;
+	const REACH_POS_3: i32 =
+		Self::REACH_POS_2 + Self::LEAD_3 * Self::TRAIL_COUNT * Self::TRAIL_COUNT;
+	const REACH_NEG_3: i32 =
+		Self::REACH_NEG_2 - Self::LEAD_3 * Self::TRAIL_COUNT * Self::TRAIL_COUNT;
+
+	const START_POS_2: i32 = Self::MIDDLE + Self::REACH_POS_1 + 1;
+	const START_POS_3: i32 = Self::START_POS_2 + Self::LEAD_2;
+	const START_POS_4: i32 = Self::START_POS_3 + Self::LEAD_3;
+	const START_NEG_2: i32 = Self::MIDDLE + Self::REACH_NEG_1;
+	const START_NEG_3: i32 = Self::START_NEG_2 - Self::LEAD_2;
+
+	/// Trail values 0-19 are stored in these C0 control bytes.
+	const TRAIL_TO_BYTE: [u8; 20] = [
+		0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18,
+		0x19, 0x1c, 0x1d, 0x1e, 0x1f,
+	];
+
+	#[must_use]
+	const fn new(after_bom: bool) -> Self {
+		Self {
+			prev: if after_bom { Self::prev_of(0xfeff) } else { Self::ASCII_PREV },
+			count: 0,
+			diff: 0,
+		}
+	}
+
+	/// Computes the new "previous" code point, which is the middle of the
+	/// script block of the given code point.
+	#[must_use]
+	const fn prev_of(cp: i32) -> i32 {
+		if 0x3040 <= cp && cp <= 0x309f {
+			0x3070 // Hiragana
+		} else if 0x4e00 <= cp && cp <= 0x9fa5 {
+			0x4e00 - Self::REACH_NEG_2 // CJK Unihan
+		} else if 0xac00 <= cp && cp <= 0xd7a3 {
+			(0xd7a3 + 0xac00) / 2 // Hangul
+		} else {
+			(cp & !0x7f) + Self::ASCII_PREV
+		}
+	}
+
+	#[must_use]
+	fn byte_to_trail(b: u8) -> Option<i32> {
+		if b <= 0x20 {
+			Self::TRAIL_TO_BYTE
+				.iter()
+				.position(|t| *t == b)
+				.map(|t| t as _)
+		} else {
+			Some(b as i32 - Self::TRAIL_BYTE_OFFSET)
+		}
+	}
+
+	#[must_use]
+	fn trail_to_byte(t: i32) -> u8 {
+		if t >= Self::TRAIL_CONTROLS_COUNT {
+			(t + Self::TRAIL_BYTE_OFFSET) as _
+		} else {
+			Self::TRAIL_TO_BYTE[t as usize]
+		}
+	}
+
+	fn decode(&mut self, src: &[u8], dest: &mut Vec<u16>, is_last: bool) -> SysResult<usize> {
+		for b in src.iter().copied() {
+			if self.count == 0 {
+				let bi = b as i32;
+				if b <= 0x20 {
+					if b != 0x20 {
+						self.prev = Self::ASCII_PREV; // C0 controls reset the state, space doesn't
+					}
+					dest.push(b as _);
+				} else if (Self::START_NEG_2..Self::START_POS_2).contains(&bi) {
+					let cp = self.prev + (bi - Self::MIDDLE); // single-byte difference
+					push_code_point(dest, cp as _)?;
+					self.prev = Self::prev_of(cp);
+				} else if b == Self::RESET {
+					self.prev = Self::ASCII_PREV;
+				} else if bi >= Self::START_NEG_2 {
+					if bi < Self::START_POS_3 {
+						self.diff =
+							(bi - Self::START_POS_2) * Self::TRAIL_COUNT + Self::REACH_POS_1 + 1;
+						self.count = 1;
+					} else if bi < Self::START_POS_4 {
+						self.diff = (bi - Self::START_POS_3)
+							* Self::TRAIL_COUNT * Self::TRAIL_COUNT
+							+ Self::REACH_POS_2 + 1;
+						self.count = 2;
+					} else {
+						self.diff = Self::REACH_POS_3 + 1;
+						self.count = 3;
+					}
+				} else if bi >= Self::START_NEG_3 {
+					self.diff = (bi - Self::START_NEG_2) * Self::TRAIL_COUNT + Self::REACH_NEG_1;
+					self.count = 1;
+				} else if bi > Self::MIN {
+					self.diff = (bi - Self::START_NEG_3) * Self::TRAIL_COUNT * Self::TRAIL_COUNT
+						+ Self::REACH_NEG_2;
+					self.count = 2;
+				} else {
+					self.diff = -Self::TRAIL_COUNT * Self::TRAIL_COUNT * Self::TRAIL_COUNT
+						+ Self::REACH_NEG_3;
+					self.count = 3;
+				}
+			} else {
+				let t = Self::byte_to_trail(b).ok_or(co::ERROR::NO_UNICODE_TRANSLATION)?;
+				match self.count {
+					1 => {
+						let cp = self.prev + self.diff + t;
+						if !(0..=0x10ffff).contains(&cp) {
+							return Err(co::ERROR::NO_UNICODE_TRANSLATION);
+						}
+						push_code_point(dest, cp as _)?;
+						self.prev = Self::prev_of(cp);
+					},
+					2 => self.diff += t * Self::TRAIL_COUNT,
+					_ => self.diff += t * Self::TRAIL_COUNT * Self::TRAIL_COUNT,
+				}
+				self.count -= 1;
+			}
+		}
+
+		if is_last && self.count != 0 {
+			Err(co::ERROR::NO_UNICODE_TRANSLATION) // truncated sequence
+		} else {
+			Ok(src.len())
+		}
+	}
+
+	fn encode(&mut self, cp: u32, dest: &mut Vec<u8>) {
+		let cp = cp as i32;
+		if cp <= 0x20 {
+			if cp != 0x20 {
+				self.prev = Self::ASCII_PREV; // C0 controls reset the state, space doesn't
+			}
+			dest.push(cp as _);
+			return;
+		}
+
+		let mut diff = cp - self.prev;
+		self.prev = Self::prev_of(cp);
+
+		let mut trails = [0u8; 3]; // least significant first
+		let (lead, num_trails) = if diff >= Self::REACH_NEG_1 {
+			if diff <= Self::REACH_POS_1 {
+				(Self::MIDDLE + diff, 0)
+			} else if diff <= Self::REACH_POS_2 {
+				diff -= Self::REACH_POS_1 + 1;
+				trails[0] = Self::trail_to_byte(diff % Self::TRAIL_COUNT);
+				(Self::START_POS_2 + diff / Self::TRAIL_COUNT, 1)
+			} else if diff <= Self::REACH_POS_3 {
+				diff -= Self::REACH_POS_2 + 1;
+				for trail in trails[..2].iter_mut() {
+					*trail = Self::trail_to_byte(diff % Self::TRAIL_COUNT);
+					diff /= Self::TRAIL_COUNT;
+				}
+				(Self::START_POS_3 + diff, 2)
+			} else {
+				diff -= Self::REACH_POS_3 + 1;
+				for trail in trails.iter_mut() {
+					*trail = Self::trail_to_byte(diff % Self::TRAIL_COUNT);
+					diff /= Self::TRAIL_COUNT;
+				}
+				(Self::START_POS_4, 3)
+			}
+		} else if diff >= Self::REACH_NEG_2 {
+			diff -= Self::REACH_NEG_1;
+			trails[0] = Self::trail_to_byte(diff.rem_euclid(Self::TRAIL_COUNT));
+			(Self::START_NEG_2 + diff.div_euclid(Self::TRAIL_COUNT), 1)
+		} else if diff >= Self::REACH_NEG_3 {
+			diff -= Self::REACH_NEG_2;
+			for trail in trails[..2].iter_mut() {
+				*trail = Self::trail_to_byte(diff.rem_euclid(Self::TRAIL_COUNT));
+				diff = diff.div_euclid(Self::TRAIL_COUNT);
+			}
+			(Self::START_NEG_3 + diff, 2)
+		} else {
+			diff -= Self::REACH_NEG_3;
+			for trail in trails.iter_mut() {
+				*trail = Self::trail_to_byte(diff.rem_euclid(Self::TRAIL_COUNT));
+				diff = diff.div_euclid(Self::TRAIL_COUNT);
+			}
+			(Self::MIN, 3)
+		};
+
+		dest.push(lead as _);
+		dest.extend(trails[..num_trails].iter().rev());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn utf16(text: &str) -> Vec<u16> {
+		text.encode_utf16().collect()
+	}
+
+	fn decode(encoding: Encoding, after_bom: bool, src: &[u8]) -> SysResult<Vec<u16>> {
+		let mut dest = Vec::new();
+		let consumed = Decoder::new(encoding, after_bom)?.decode(src, &mut dest, true)?;
+		assert_eq!(consumed, src.len());
+		Ok(dest)
+	}
+
+	/// Feeds the data in chunks of the given size, keeping the unconsumed
+	/// bytes for the next chunk, like `TextReader` does.
+	fn decode_chunked(encoding: Encoding, src: &[u8], chunk_sz: usize) -> SysResult<Vec<u16>> {
+		let mut decoder = Decoder::new(encoding, false)?;
+		let mut dest = Vec::new();
+		let mut pending = Vec::<u8>::new();
+		for chunk in src.chunks(chunk_sz) {
+			pending.extend_from_slice(chunk);
+			let consumed = decoder.decode(&pending, &mut dest, false)?;
+			pending.drain(..consumed);
+		}
+		decoder.decode(&pending, &mut dest, true)?;
+		Ok(dest)
+	}
+
+	fn encode(encoding: Encoding, text: &str) -> SysResult<Vec<u8>> {
+		let mut dest = Vec::new();
+		Encoder::new(encoding)?.encode(&utf16(text), &mut dest, true)?;
+		Ok(dest)
+	}
+
+	const MULTI_SCRIPT: &str =
+		"Hello, Мир! Ελληνικά, עברית, 日本語のテキスト, 한국어, \u{1f600} \u{10400}.";
+
+	#[test]
+	fn scsu_uts6_samples() {
+		// Samples from UTS #6, section 9.
+		assert_eq!(
+			decode(Encoding::Scsu, false, &[0xd6, 0x6c, 0x20, 0x66, 0x6c, 0x69, 0x65, 0xdf, 0x74]),
+			Ok(utf16("Öl fließt")),
+		);
+		assert_eq!(
+			decode(Encoding::Scsu, false, &[0x12, 0x9c, 0xbe, 0xc1, 0xba, 0xb2, 0xb0]),
+			Ok(utf16("Москва")),
+		);
+
+		#[rustfmt::skip]
+		let japanese = [
+			0x08, 0x00, 0x1b, 0x4c, 0xea, 0x16, 0xca, 0xd3, 0x94, 0x0f, 0x53, 0xef,
+			0x61, 0x1b, 0xe5, 0x84, 0xc4, 0x0f, 0x53, 0xef, 0x61, 0x1b, 0xe5, 0x84,
+			0xc4, 0x16, 0xca, 0xd3, 0x94, 0x08, 0x02,
+		];
+		assert_eq!(
+			decode(Encoding::Scsu, false, &japanese),
+			Ok(utf16("\u{3000}♪リンゴ可愛いや可愛いやリンゴ。")),
+		);
+		assert_eq!(
+			decode_chunked(Encoding::Scsu, &japanese, 1),
+			Ok(utf16("\u{3000}♪リンゴ可愛いや可愛いやリンゴ。"))
+		);
+	}
+
+	#[test]
+	fn scsu_round_trip() {
+		assert_eq!(
+			encode(Encoding::Scsu, "Öl fließt"),
+			Ok(vec![0xd6, 0x6c, 0x20, 0x66, 0x6c, 0x69, 0x65, 0xdf, 0x74]),
+		);
+
+		let bytes = encode(Encoding::Scsu, MULTI_SCRIPT).unwrap();
+		assert_eq!(decode(Encoding::Scsu, false, &bytes), Ok(utf16(MULTI_SCRIPT)));
+		for chunk_sz in 1..=5 {
+			assert_eq!(decode_chunked(Encoding::Scsu, &bytes, chunk_sz), Ok(utf16(MULTI_SCRIPT)));
+		}
+	}
+
+	#[test]
+	fn bocu1_bom() {
+		assert_eq!(encode(Encoding::Bocu1, "\u{feff}"), Ok(vec![0xfb, 0xee, 0x28]));
+		assert_eq!(decode(Encoding::Bocu1, false, &[0xfb, 0xee, 0x28]), Ok(vec![0xfeff]));
+
+		// Decoding after a skipped BOM must give the same result as decoding
+		// the BOM itself.
+		let bytes = encode(Encoding::Bocu1, "\u{feff}Мир").unwrap();
+		assert_eq!(&bytes[..3], &[0xfb, 0xee, 0x28]);
+		assert_eq!(decode(Encoding::Bocu1, true, &bytes[3..]), Ok(utf16("Мир")));
+	}
+
+	#[test]
+	fn bocu1_round_trip() {
+		let bytes = encode(Encoding::Bocu1, MULTI_SCRIPT).unwrap();
+		assert_eq!(decode(Encoding::Bocu1, false, &bytes), Ok(utf16(MULTI_SCRIPT)));
+		for chunk_sz in 1..=5 {
+			assert_eq!(decode_chunked(Encoding::Bocu1, &bytes, chunk_sz), Ok(utf16(MULTI_SCRIPT)));
+		}
+	}
+
+	#[test]
+	fn utf32_surrogate_pairs() {
+		let text = "a\u{1f600}";
+		let le = [0x61, 0x00, 0x00, 0x00, 0x00, 0xf6, 0x01, 0x00];
+		let be = [0x00, 0x00, 0x00, 0x61, 0x00, 0x01, 0xf6, 0x00];
+
+		assert_eq!(decode(Encoding::Utf32le, false, &le), Ok(vec![0x0061, 0xd83d, 0xde00]));
+		assert_eq!(decode(Encoding::Utf32be, false, &be), Ok(vec![0x0061, 0xd83d, 0xde00]));
+		assert_eq!(decode_chunked(Encoding::Utf32le, &le, 3), Ok(utf16(text)));
+		assert_eq!(encode(Encoding::Utf32le, text), Ok(le.to_vec()));
+		assert_eq!(encode(Encoding::Utf32be, text), Ok(be.to_vec()));
+
+		// Surrogate pair split between two calls.
+		let mut encoder = Encoder::new(Encoding::Utf32be).unwrap();
+		let mut dest = Vec::new();
+		encoder.encode(&[0x0061, 0xd83d], &mut dest, false).unwrap();
+		assert_eq!(dest, &be[..4]);
+		encoder.encode(&[0xde00], &mut dest, true).unwrap();
+		assert_eq!(dest, be);
+	}
+
+	#[test]
+	fn utf8_split_sequence() {
+		let bytes = "a日\u{1f600}".as_bytes(); // 1 + 3 + 4 bytes
+		assert_eq!(utf8_complete_len(bytes), 8);
+		assert_eq!(utf8_complete_len(&bytes[..7]), 4);
+		assert_eq!(utf8_complete_len(&bytes[..5]), 4);
+		assert_eq!(utf8_complete_len(&bytes[..4]), 4);
+		assert_eq!(utf8_complete_len(&bytes[..3]), 1);
+		assert_eq!(utf8_complete_len(&bytes[..2]), 1);
+		assert_eq!(utf8_complete_len(&bytes[..1]), 1);
+		assert_eq!(utf8_complete_len(&[]), 0);
+		assert_eq!(utf8_complete_len(&[0x80, 0x80]), 2);
+	}
+
+	#[test]
+	fn malformed() {
+		let err = Some(co::ERROR::NO_UNICODE_TRANSLATION);
+
+		assert_eq!(decode(Encoding::Utf32le, false, &[0x61, 0x00, 0x00]).err(), err); // truncated
+		assert_eq!(decode(Encoding::Utf32le, false, &[0x00, 0xd8, 0x00, 0x00]).err(), err); // surrogate
+		assert_eq!(decode(Encoding::Utf32be, false, &[0x00, 0x11, 0x00, 0x00]).err(), err); // beyond U+10FFFF
+
+		assert_eq!(decode(Encoding::Scsu, false, &[0x0c]).err(), err); // reserved tag
+		assert_eq!(decode(Encoding::Scsu, false, &[0x0f, 0xf2]).err(), err); // reserved tag in Unicode mode
+		assert_eq!(decode(Encoding::Scsu, false, &[0x18, 0x00]).err(), err); // reserved window offset
+		assert_eq!(decode(Encoding::Scsu, false, &[0x0e, 0x04]).err(), err); // truncated SQU
+		assert_eq!(decode(Encoding::Scsu, false, &[0x0b, 0x00]).err(), err); // truncated SDX
+		let mut dest = Vec::new();
+		let consumed = Decoder::new(Encoding::Scsu, false).unwrap().decode(
+			&[0x41, 0x0e, 0x04],
+			&mut dest,
+			false,
+		);
+		assert_eq!(consumed, Ok(1)); // incomplete sequence kept for the next chunk
+		assert_eq!(dest, utf16("A"));
+
+		assert_eq!(decode(Encoding::Bocu1, false, &[0xfb, 0xee]).err(), err); // truncated
+
+		assert_eq!(encode(Encoding::Ansi, "日").err(), err);
+		let mut dest = Vec::new();
+		let res = Encoder::new(Encoding::Utf32le)
+			.unwrap()
+			.encode(&[0xd800], &mut dest, true);
+		assert_eq!(res.err(), err); // unpaired surrogate
+		assert!(Decoder::new(Encoding::Unknown, false).err() == Some(co::ERROR::INVALID_PARAMETER));
+		assert!(Encoder::new(Encoding::Unknown).err() == Some(co::ERROR::INVALID_PARAMETER));
+	}
+}
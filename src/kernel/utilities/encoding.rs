@@ -1,5 +1,3 @@
-use std::cmp::Ordering;
-
 /// String encodings.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Encoding {
@@ -66,49 +64,39 @@ impl Encoding {
 		}
 	}
 
+	/// Returns the [BOM](https://en.wikipedia.org/wiki/Byte_order_mark) of
+	/// the encoding, or an empty slice if the encoding has no BOM.
+	///
+	/// Note that, for [`Bocu1`](crate::Encoding::Bocu1), a reset byte is
+	/// usually written after the BOM, as recommended by the specification.
 	#[must_use]
-	fn guess_bom(data: &[u8]) -> Option<(Self, usize)> {
-		let has_bom = |bom_bytes: &[u8]| -> bool {
-			data.len() >= bom_bytes.len()
-				&& data[..bom_bytes.len()].cmp(bom_bytes) == Ordering::Equal
-		};
-
-		const UTF8: [u8; 3] = [0xef, 0xbb, 0xbf]; // UTF-8 BOM
-		if has_bom(&UTF8) {
-			return Some((Self::Utf8, UTF8.len()));
-		}
-
-		const UTF16BE: [u8; 2] = [0xfe, 0xff];
-		if has_bom(&UTF16BE) {
-			return Some((Self::Utf16be, UTF16BE.len()));
-		}
-
-		const UTF16LE: [u8; 2] = [0xff, 0xfe];
-		if has_bom(&UTF16LE) {
-			return Some((Self::Utf16le, UTF16LE.len()));
-		}
-
-		const UTF32BE: [u8; 4] = [0x00, 0x00, 0xfe, 0xff];
-		if has_bom(&UTF32BE) {
-			return Some((Self::Utf32be, UTF32BE.len()));
-		}
-
-		const UTF32LE: [u8; 4] = [0xff, 0xfe, 0x00, 0x00];
-		if has_bom(&UTF32LE) {
-			return Some((Self::Utf32le, UTF32LE.len()));
-		}
-
-		const SCSU: [u8; 3] = [0x0e, 0xfe, 0xff];
-		if has_bom(&SCSU) {
-			return Some((Self::Scsu, SCSU.len()));
-		}
-
-		const BOCU1: [u8; 3] = [0xfb, 0xee, 0x28];
-		if has_bom(&BOCU1) {
-			return Some((Self::Bocu1, BOCU1.len()));
+	pub const fn bom(&self) -> &'static [u8] {
+		match self {
+			Self::Utf8 => &[0xef, 0xbb, 0xbf],
+			Self::Utf16be => &[0xfe, 0xff],
+			Self::Utf16le => &[0xff, 0xfe],
+			Self::Utf32be => &[0x00, 0x00, 0xfe, 0xff],
+			Self::Utf32le => &[0xff, 0xfe, 0x00, 0x00],
+			Self::Scsu => &[0x0e, 0xfe, 0xff],
+			Self::Bocu1 => &[0xfb, 0xee, 0x28],
+			Self::Unknown | Self::Ansi | Self::Win1252 => &[],
 		}
+	}
 
-		None // no BOM found
+	#[must_use]
+	fn guess_bom(data: &[u8]) -> Option<(Self, usize)> {
+		[
+			Self::Utf8,
+			Self::Utf32be,
+			Self::Utf32le, // must come before UTF-16 LE, whose BOM is a prefix of this one
+			Self::Utf16be,
+			Self::Utf16le,
+			Self::Scsu,
+			Self::Bocu1,
+		]
+		.iter()
+		.find(|enc| data.starts_with(enc.bom()))
+		.map(|enc| (*enc, enc.bom().len()))
 	}
 
 	#[must_use]
@@ -1,6 +1,8 @@
+mod codec;
 mod encoding;
 mod file;
 mod file_mapped;
+mod text_file;
 mod w_string;
 
 pub mod path;
@@ -8,4 +10,5 @@ pub mod path;
 pub use encoding::Encoding;
 pub use file::{File, FileAccess};
 pub use file_mapped::FileMapped;
+pub use text_file::{LineEnding, TextReader, TextWriter};
 pub use w_string::WString;
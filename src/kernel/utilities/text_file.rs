@@ -0,0 +1,528 @@
+use crate::co;
+use crate::decl::*;
+use crate::kernel::iterators::*;
+use crate::kernel::utilities::codec::{utf8_complete_len, Decoder, Encoder};
+use crate::prelude::*;
+
+/// Line endings used by [`TextWriter`](crate::TextWriter).
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+	/// Windows line ending, `\r\n`.
+	#[default]
+	CrLf,
+	/// Unix line ending, `\n`.
+	Lf,
+	/// Classic Mac OS line ending, `\r`.
+	Cr,
+}
+
+impl LineEnding {
+	/// Returns the line ending as a string.
+	#[must_use]
+	pub const fn as_str(&self) -> &'static str {
+		match self {
+			Self::CrLf => "\r\n",
+			Self::Lf => "\n",
+			Self::Cr => "\r",
+		}
+	}
+}
+
+enum TextSrc {
+	File(File, Vec<u8>),       // raw bytes not decoded yet
+	Mapped(FileMapped, usize), // offset of the bytes not decoded yet
+}
+
+/// Reads a text file line by line, decoding the lines lazily, so that big
+/// files are never loaded entirely in memory.
+///
+/// The encoding is detected with [`Encoding::guess`](crate::Encoding::guess)
+/// over the first chunk of the file, and the lines can end with `\r\n`, `\n`
+/// or `\r`.
+///
+/// # Examples
+///
+/// Reading a text file line by line:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let mut reader = w::TextReader::open(
+///     "C:\\Temp\\foo.txt",
+///     w::FileAccess::ExistingReadOnly,
+/// )?;
+/// println!("Encoding: {}", reader.encoding());
+///
+/// for line in reader.iter_lines() {
+///     let line = line?;
+///     println!("{}", line);
+/// }
+/// # w::SysResult::Ok(())
+/// ```
+pub struct TextReader {
+	src: TextSrc,
+	encoding: Encoding,
+	decoder: Decoder,
+	chars: Vec<u16>,
+	pos: usize, // index of the first char not returned yet
+	eof: bool,  // all the source has been decoded
+}
+
+impl TextReader {
+	/// Number of bytes read from the file at once.
+	const CHUNK_SZ: usize = 64 * 1024;
+
+	/// Opens a file with the desired access, and reads it with
+	/// [`File`](crate::File).
+	#[must_use]
+	pub fn open(file_path: &str, access: FileAccess) -> SysResult<Self> {
+		Self::from_file(File::open(file_path, access)?)
+	}
+
+	/// Reads the file from its beginning.
+	#[must_use]
+	pub fn from_file(file: File) -> SysResult<Self> {
+		file.set_pointer_offset(0)?;
+		let mut first = vec![0x00; Self::CHUNK_SZ];
+		let num_read = file.read_buffer(&mut first)? as usize;
+		first.truncate(num_read);
+
+		let (encoding, sz_bom) = Self::guess_chunk(&first, num_read < Self::CHUNK_SZ);
+		first.drain(..sz_bom);
+		Self::new(TextSrc::File(file, first), encoding, sz_bom)
+	}
+
+	/// Reads the memory-mapped file from its beginning.
+	#[must_use]
+	pub fn from_file_mapped(file: FileMapped) -> SysResult<Self> {
+		let chunk_end = Self::CHUNK_SZ.min(file.size() as _);
+		let (encoding, sz_bom) =
+			Self::guess_chunk(&file.as_slice()[..chunk_end], chunk_end == file.size() as usize);
+		Self::new(TextSrc::Mapped(file, sz_bom), encoding, sz_bom)
+	}
+
+	#[must_use]
+	fn new(src: TextSrc, encoding: Encoding, sz_bom: usize) -> SysResult<Self> {
+		Ok(Self {
+			src,
+			encoding,
+			decoder: Decoder::new(encoding, sz_bom > 0)?,
+			chars: Vec::with_capacity(Self::CHUNK_SZ),
+			pos: 0,
+			eof: false,
+		})
+	}
+
+	#[must_use]
+	fn guess_chunk(chunk: &[u8], is_whole_file: bool) -> (Encoding, usize) {
+		if is_whole_file {
+			Encoding::guess(chunk)
+		} else {
+			Encoding::guess(&chunk[..utf8_complete_len(chunk)]) // don't let a cut char spoil UTF-8 detection
+		}
+	}
+
+	/// Returns the encoding detected when the file was opened.
+	#[must_use]
+	pub const fn encoding(&self) -> Encoding {
+		self.encoding
+	}
+
+	/// Returns an iterator over the remaining lines of the file, which calls
+	/// [`TextReader::read_line`](crate::TextReader::read_line) on each
+	/// iteration.
+	#[must_use]
+	pub fn iter_lines(&mut self) -> impl Iterator<Item = SysResult<String>> + '_ {
+		TextReaderLinesIter::new(self)
+	}
+
+	/// Reads the next line, without its line ending. Returns `None` when there
+	/// are no more lines.
+	///
+	/// Fails with
+	/// [`ERROR::NO_UNICODE_TRANSLATION`](crate::co::ERROR::NO_UNICODE_TRANSLATION)
+	/// if the file contains malformed data, as described in
+	/// [`WString::parse_as`](crate::WString::parse_as), or if a line contains
+	/// an unpaired UTF-16 surrogate, which cannot be stored in a [`String`].
+	#[must_use]
+	pub fn read_line(&mut self) -> SysResult<Option<String>> {
+		let mut search_from = self.pos;
+		loop {
+			let found = self.chars[search_from..]
+				.iter()
+				.position(|ch| *ch == 0x000d || *ch == 0x000a)
+				.map(|idx| search_from + idx);
+
+			match found {
+				Some(idx) => {
+					if self.chars[idx] == 0x000d && idx + 1 == self.chars.len() && !self.eof {
+						let rel_idx = idx - self.pos; // chars will be compacted
+						self.fill()?; // we need the next char to know if it's \r\n
+						search_from = self.pos + rel_idx;
+						continue;
+					}
+					let line = Self::chars_to_string(&self.chars[self.pos..idx])?;
+					self.pos =
+						if self.chars[idx] == 0x000d && self.chars.get(idx + 1) == Some(&0x000a) {
+							idx + 2
+						} else {
+							idx + 1
+						};
+					return Ok(Some(line));
+				},
+				None => {
+					if self.eof {
+						return if self.pos < self.chars.len() {
+							let line = Self::chars_to_string(&self.chars[self.pos..])?;
+							self.pos = self.chars.len();
+							Ok(Some(line)) // last line has no line ending
+						} else {
+							Ok(None)
+						};
+					}
+					let rel_len = self.chars.len() - self.pos; // chars will be compacted
+					self.fill()?;
+					search_from = self.pos + rel_len;
+				},
+			}
+		}
+	}
+
+	#[must_use]
+	fn chars_to_string(chars: &[u16]) -> SysResult<String> {
+		String::from_utf16(chars).map_err(|_| co::ERROR::NO_UNICODE_TRANSLATION)
+	}
+
+	/// Decodes another chunk of the file, discarding the chars already
+	/// returned.
+	fn fill(&mut self) -> SysResult<()> {
+		self.chars.drain(..self.pos);
+		self.pos = 0;
+
+		match &mut self.src {
+			TextSrc::File(file, raw) => {
+				let prev_len = raw.len();
+				raw.resize(prev_len + Self::CHUNK_SZ, 0x00);
+				let num_read = file.read_buffer(&mut raw[prev_len..])? as usize;
+				raw.truncate(prev_len + num_read);
+
+				let is_last = num_read == 0;
+				let consumed = self.decoder.decode(raw, &mut self.chars, is_last)?;
+				raw.drain(..consumed);
+				self.eof = is_last;
+			},
+			TextSrc::Mapped(file, offset) => {
+				let data = file.as_slice();
+				let chunk_end = data.len().min(*offset + Self::CHUNK_SZ);
+				let is_last = chunk_end == data.len();
+				*offset +=
+					self.decoder
+						.decode(&data[*offset..chunk_end], &mut self.chars, is_last)?;
+				self.eof = is_last;
+			},
+		}
+		Ok(())
+	}
+}
+
+/// Writes text into a file, encoding it with the given
+/// [`Encoding`](crate::Encoding). The bytes are buffered, and written when the
+/// buffer is full, when [`flush`](crate::TextWriter::flush) is called, or when
+/// the object goes out of scope.
+///
+/// # Examples
+///
+/// Writing lines in a new UTF-16 file:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let mut writer = w::TextWriter::open(
+///     "C:\\Temp\\foo.txt",
+///     w::FileAccess::CreateRW,
+///     w::Encoding::Utf16le,
+///     true,
+/// )?;
+/// writer.set_line_ending(w::LineEnding::Lf);
+///
+/// writer.write_line("First line")?;
+/// writer.write_line("Second line")?;
+/// writer.flush()?;
+/// # w::SysResult::Ok(())
+/// ```
+pub struct TextWriter {
+	file: File,
+	encoder: Encoder,
+	line_ending: LineEnding,
+	buf: Vec<u8>,
+}
+
+impl Drop for TextWriter {
+	fn drop(&mut self) {
+		let _ = self.flush(); // ignore errors
+	}
+}
+
+impl TextWriter {
+	/// Number of buffered bytes which triggers a write.
+	const BUF_SZ: usize = 64 * 1024;
+
+	/// Opens a file with the desired access; if the file already has content,
+	/// the text will be appended to it.
+	///
+	/// If `with_bom` is true and the file is empty, the encoding's
+	/// [BOM](https://en.wikipedia.org/wiki/Byte_order_mark) is written first.
+	///
+	/// Fails with [`ERROR::ACCESS_DENIED`](crate::co::ERROR::ACCESS_DENIED) if
+	/// [`FileAccess::ExistingReadOnly`](crate::FileAccess::ExistingReadOnly) is
+	/// passed.
+	#[must_use]
+	pub fn open(
+		file_path: &str,
+		access: FileAccess,
+		encoding: Encoding,
+		with_bom: bool,
+	) -> SysResult<Self> {
+		if access == FileAccess::ExistingReadOnly {
+			return Err(co::ERROR::ACCESS_DENIED);
+		}
+		let file = File::open(file_path, access)?;
+		file.hfile()
+			.SetFilePointerEx(0, co::FILE_STARTING_POINT::END)?;
+		Self::from_file(file, encoding, with_bom)
+	}
+
+	/// Writes at the current file pointer offset.
+	///
+	/// If `with_bom` is true and the file pointer is at the beginning of the
+	/// file, the encoding's
+	/// [BOM](https://en.wikipedia.org/wiki/Byte_order_mark) is written first.
+	#[must_use]
+	pub fn from_file(file: File, encoding: Encoding, with_bom: bool) -> SysResult<Self> {
+		let mut new_self = Self {
+			encoder: Encoder::new(encoding)?,
+			file,
+			line_ending: LineEnding::default(),
+			buf: Vec::with_capacity(Self::BUF_SZ),
+		};
+		if with_bom && new_self.file.pointer_offset()? == 0 {
+			new_self.buf.extend_from_slice(encoding.bom());
+			if encoding == Encoding::Bocu1 {
+				new_self.buf.push(0xff); // reset byte, so the BOM doesn't affect the state
+			}
+		}
+		Ok(new_self)
+	}
+
+	/// Returns the underlying file.
+	#[must_use]
+	pub const fn file(&self) -> &File {
+		&self.file
+	}
+
+	/// Writes all the buffered bytes to the file.
+	pub fn flush(&mut self) -> SysResult<()> {
+		self.encoder.encode(&[], &mut self.buf, true)?; // any pending char
+		if !self.buf.is_empty() {
+			self.file.write(&self.buf)?;
+			self.buf.clear();
+		}
+		Ok(())
+	}
+
+	/// Returns the line ending appended by
+	/// [`write_line`](crate::TextWriter::write_line), which defaults to
+	/// [`LineEnding::CrLf`](crate::LineEnding::CrLf).
+	#[must_use]
+	pub const fn line_ending(&self) -> LineEnding {
+		self.line_ending
+	}
+
+	/// Sets the line ending appended by
+	/// [`write_line`](crate::TextWriter::write_line).
+	pub fn set_line_ending(&mut self, line_ending: LineEnding) {
+		self.line_ending = line_ending;
+	}
+
+	/// Encodes and writes the text, as it is.
+	///
+	/// Fails with
+	/// [`ERROR::NO_UNICODE_TRANSLATION`](crate::co::ERROR::NO_UNICODE_TRANSLATION)
+	/// if a char cannot be represented in the encoding.
+	pub fn write(&mut self, text: &str) -> SysResult<()> {
+		let chars = text.encode_utf16().collect::<Vec<_>>();
+		self.encoder.encode(&chars, &mut self.buf, false)?;
+		if self.buf.len() >= Self::BUF_SZ {
+			self.file.write(&self.buf)?;
+			self.buf.clear();
+		}
+		Ok(())
+	}
+
+	/// Encodes and writes the text, followed by the line ending.
+	pub fn write_line(&mut self, text: &str) -> SysResult<()> {
+		self.write(text)?;
+		self.write(self.line_ending.as_str())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const CHUNK_SZ: usize = TextReader::CHUNK_SZ;
+
+	/// File offsets where the decoded data is split. The chunk read to guess
+	/// the encoding is decoded along with the second one, so the first split
+	/// happens after two chunks.
+	const SPLITS: [usize; 2] = [CHUNK_SZ, 2 * CHUNK_SZ];
+
+	/// Returns the path of a temporary file, unique for the test.
+	fn temp_path(name: &str) -> String {
+		std::env::temp_dir()
+			.join(format!("winsafe_text_{}_{}.txt", name, std::process::id()))
+			.to_str()
+			.unwrap()
+			.to_owned()
+	}
+
+	/// Writes the data to a temporary file, and reads all its lines back.
+	fn read_lines(name: &str, data: &[u8]) -> (Encoding, Vec<String>) {
+		let path = temp_path(name);
+		std::fs::write(&path, data).unwrap();
+		let res = {
+			let mut reader = TextReader::open(&path, FileAccess::ExistingReadOnly).unwrap();
+			let lines = reader.iter_lines().collect::<SysResult<Vec<_>>>().unwrap();
+			(reader.encoding(), lines)
+		}; // file closed before being removed
+		std::fs::remove_file(&path).unwrap();
+		res
+	}
+
+	fn lines(lines: &[&str]) -> Vec<String> {
+		lines.iter().map(|line| line.to_string()).collect()
+	}
+
+	#[test]
+	fn crlf_across_chunks() {
+		for split in SPLITS {
+			// \r is the last char before the split, \n the first one after it.
+			let first = "a".repeat(split - 1);
+			let (encoding, read) = read_lines("crlf8", format!("{}\r\nb\r\n", first).as_bytes());
+			assert!(encoding == Encoding::Utf8);
+			assert_eq!(read, lines(&[&first, "b"]));
+
+			let first = "a".repeat(split / 2 - 2); // BOM takes 2 bytes
+			let mut data = Encoding::Utf16le.bom().to_vec();
+			format!("{}\r\nb", first)
+				.encode_utf16()
+				.for_each(|ch| data.extend(ch.to_le_bytes()));
+			let (encoding, read) = read_lines("crlf16", &data);
+			assert!(encoding == Encoding::Utf16le);
+			assert_eq!(read, lines(&[&first, "b"]));
+		}
+	}
+
+	#[test]
+	fn lone_cr() {
+		let (_, read) = read_lines("cr", b"a\rb\r\nc\nd\r\re\r");
+		assert_eq!(read, lines(&["a", "b", "c", "d", "", "e"]));
+
+		for split in SPLITS {
+			// \r right before the split, not followed by \n.
+			let first = "a".repeat(split - 1);
+			let (_, read) = read_lines("cr_split", format!("{}\rb", first).as_bytes());
+			assert_eq!(read, lines(&[&first, "b"]));
+		}
+	}
+
+	#[test]
+	fn no_final_line_ending() {
+		assert_eq!(read_lines("end1", b"a\r\nb").1, lines(&["a", "b"]));
+		assert_eq!(read_lines("end2", b"a\r\nb\r\n").1, lines(&["a", "b"]));
+		assert_eq!(read_lines("end3", b"a").1, lines(&["a"]));
+		assert_eq!(read_lines("end4", b"").1, lines(&[]));
+
+		let last = "z".repeat(2 * CHUNK_SZ + 10);
+		assert_eq!(read_lines("end5", format!("a\n{}", last).as_bytes()).1, lines(&["a", &last]));
+	}
+
+	#[test]
+	fn bom_only_at_start() {
+		let (encoding, read) = read_lines("bom1", b"\xef\xbb\xbfa\r\nb");
+		assert!(encoding == Encoding::Utf8);
+		assert_eq!(read, lines(&["a", "b"]));
+
+		for split in SPLITS {
+			// The data after the split starts with the bytes of the BOM, which
+			// are now a regular U+FEFF char.
+			let mut data = Encoding::Utf8.bom().to_vec();
+			data.extend("x".repeat(split - data.len()).as_bytes());
+			data.extend(b"\xef\xbb\xbfy");
+			let (encoding, read) = read_lines("bom2", &data);
+			assert!(encoding == Encoding::Utf8);
+			assert_eq!(read, lines(&[&format!("{}\u{feff}y", "x".repeat(split - 3))]));
+		}
+	}
+
+	#[test]
+	fn utf8_across_chunks() {
+		for split in SPLITS {
+			for before in 1..4 {
+				// The 4-byte char starts `before` bytes before the split.
+				let first = format!("é{}\u{1f600}", "a".repeat(split - 2 - before));
+				let (encoding, read) = read_lines("utf8", format!("{}\nb", first).as_bytes());
+				assert!(encoding == Encoding::Utf8);
+				assert_eq!(read, lines(&[&first, "b"]));
+			}
+		}
+	}
+
+	#[test]
+	fn writer_round_trip() {
+		let text = ["first", "Москва, 日本語 \u{1f600}", "", "last"];
+		let encodings = [
+			Encoding::Utf8,
+			Encoding::Utf16le,
+			Encoding::Utf16be,
+			Encoding::Utf32le,
+			Encoding::Utf32be,
+			Encoding::Scsu,
+			Encoding::Bocu1,
+		];
+
+		for line_ending in [LineEnding::CrLf, LineEnding::Lf, LineEnding::Cr] {
+			for encoding in encodings {
+				let path = temp_path("writer");
+				{
+					let mut writer =
+						TextWriter::open(&path, FileAccess::CreateRW, encoding, true).unwrap();
+					writer.set_line_ending(line_ending);
+					for line in text.iter() {
+						writer.write_line(line).unwrap();
+					}
+				} // flushed when dropped
+
+				let data = std::fs::read(&path).unwrap();
+				assert!(data.starts_with(encoding.bom()));
+				if encoding == Encoding::Utf8 {
+					let expected =
+						text.iter()
+							.fold(Encoding::Utf8.bom().to_vec(), |mut acc, line| {
+								acc.extend(line.as_bytes());
+								acc.extend(line_ending.as_str().as_bytes());
+								acc
+							});
+					assert_eq!(data, expected);
+				}
+
+				let mut reader = TextReader::open(&path, FileAccess::ExistingReadOnly).unwrap();
+				assert!(reader.encoding() == encoding);
+				let read = reader.iter_lines().collect::<SysResult<Vec<_>>>().unwrap();
+				assert_eq!(read, lines(&text));
+				drop(reader);
+				std::fs::remove_file(&path).unwrap();
+			}
+		}
+	}
+}
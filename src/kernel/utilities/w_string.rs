@@ -4,6 +4,7 @@ use crate::co;
 use crate::decl::*;
 use crate::guard::*;
 use crate::kernel::ffi;
+use crate::kernel::utilities::codec::{Decoder, Encoder};
 use crate::prelude::*;
 
 /// Stores a `[u16]` buffer for a null-terminated
@@ -268,14 +269,71 @@ impl WString {
 		}
 	}
 
+	/// Serializes the string into raw bytes with the given encoding,
+	/// optionally prepending the encoding's
+	/// [BOM](https://en.wikipedia.org/wiki/Byte_order_mark). The terminating
+	/// null is not included.
+	///
+	/// This is the reverse operation of
+	/// [`WString::parse`](crate::WString::parse) and
+	/// [`WString::parse_as`](crate::WString::parse_as).
+	///
+	/// Fails with
+	/// [`ERROR::NO_UNICODE_TRANSLATION`](crate::co::ERROR::NO_UNICODE_TRANSLATION)
+	/// if a char cannot be represented in the encoding, or
+	/// [`ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER) if
+	/// [`Encoding::Unknown`](crate::Encoding::Unknown) is passed.
+	///
+	/// # Examples
+	///
+	/// Saving a text file in the same encoding it was opened with:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let f = w::File::open(
+	///     "C:\\Temp\\foo.txt",
+	///     w::FileAccess::ExistingRW,
+	/// )?;
+	/// let raw_bytes = f.read_all()?;
+	/// let (encoding, sz_bom) = w::Encoding::guess(&raw_bytes);
+	///
+	/// let text = w::WString::parse(&raw_bytes)?.to_string();
+	/// let new_text = text.replace("foo", "bar");
+	///
+	/// f.erase_and_write(
+	///     &w::WString::from_str(&new_text).encode(encoding, sz_bom > 0)?,
+	/// )?;
+	/// # w::SysResult::Ok(())
+	/// ```
+	#[must_use]
+	pub fn encode(&self, encoding: Encoding, with_bom: bool) -> SysResult<Vec<u8>> {
+		let mut encoder = Encoder::new(encoding)?;
+		let mut buf = Vec::<u8>::with_capacity(self.buf_len() * 2);
+		if with_bom {
+			buf.extend_from_slice(encoding.bom());
+			if encoding == Encoding::Bocu1 {
+				buf.push(0xff); // reset byte, so the BOM doesn't affect the state
+			}
+		}
+
+		let len = self
+			.as_slice()
+			.iter()
+			.take_while(|ch| **ch != 0x0000)
+			.count();
+		encoder.encode(&self.as_slice()[..len], &mut buf, true)?;
+		Ok(buf)
+	}
+
 	/// Guesses the encoding with [`Encoding::guess`](crate::Encoding::guess)
 	/// and parses the data as a string.
 	///
 	/// If you're sure the data has UTF-8 encoding, you can also use the
 	/// built-in [`String::from_utf8`](std::string::String::from_utf8).
 	///
-	/// To serialize the string back into UTF-8 bytes, use the built-in
-	/// [`String::into_bytes`](std::string::String::into_bytes).
+	/// To serialize the string back into raw bytes, use
+	/// [`WString::encode`](crate::WString::encode).
 	///
 	/// # Examples
 	///
@@ -295,52 +353,32 @@ impl WString {
 	/// ```
 	#[must_use]
 	pub fn parse(data: &[u8]) -> SysResult<Self> {
-		let mut data = data;
-		if data.is_empty() {
-			return Ok(Self::new()); // nothing to parse
-		}
-
-		let (encoding, sz_bom) = Encoding::guess(data);
-		data = &data[sz_bom..]; // skip BOM, if any
-
-		Ok(Self::from_wchars_slice(&match encoding {
-			Encoding::Ansi => Self::parse_ansi(data),
-			Encoding::Win1252 => MultiByteToWideChar(co::CP::WINDOWS_1252, co::MBC::NoValue, data)?,
-			Encoding::Utf8 => MultiByteToWideChar(co::CP::UTF8, co::MBC::NoValue, data)?,
-			Encoding::Utf16be => Self::parse_utf16(data, true),
-			Encoding::Utf16le => Self::parse_utf16(data, false),
-			Encoding::Utf32be
-			| Encoding::Utf32le
-			| Encoding::Scsu
-			| Encoding::Bocu1
-			| Encoding::Unknown => panic!("Encoding {} not implemented.", encoding),
-		}))
-	}
-
-	fn parse_ansi(data: &[u8]) -> Vec<u16> {
-		data.iter()
-			.take_while(|ch| **ch != 0x0000) // ignore terminating null, if any
-			.map(|ch| *ch as u16) // raw u8 to u16 conversion
-			.collect()
-	}
-
-	fn parse_utf16(data: &[u8], is_big_endian: bool) -> Vec<u16> {
-		let data = if data.len() % 2 == 1 {
-			&data[..data.len() - 1] // if odd number of bytes, discard last one
-		} else {
-			data
-		};
+		let (encoding, _) = Encoding::guess(data);
+		Self::parse_as(data, encoding)
+	}
 
-		data.chunks(2)
-			.take_while(|ch2| **ch2 != [0x00, 0x00]) // ignore terminating null, if any
-			.map(|ch2| {
-				if is_big_endian {
-					u16::from_be_bytes(ch2.try_into().unwrap())
-				} else {
-					u16::from_le_bytes(ch2.try_into().unwrap())
-				}
-			})
-			.collect()
+	/// Parses the data as a string with the given encoding. If the data starts
+	/// with the encoding's
+	/// [BOM](https://en.wikipedia.org/wiki/Byte_order_mark), it is skipped.
+	///
+	/// The string ends at the first null char, if any.
+	///
+	/// Fails with
+	/// [`ERROR::NO_UNICODE_TRANSLATION`](crate::co::ERROR::NO_UNICODE_TRANSLATION)
+	/// if the data is malformed – that is, invalid UTF-8, UTF-32, SCSU or
+	/// BOCU-1 sequences; ANSI and Windows-1252 data is always decoded, and an
+	/// unpaired UTF-16 surrogate is kept as it is. Fails with
+	/// [`ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER) if
+	/// [`Encoding::Unknown`](crate::Encoding::Unknown) is passed.
+	#[must_use]
+	pub fn parse_as(data: &[u8], encoding: Encoding) -> SysResult<Self> {
+		let has_bom = !encoding.bom().is_empty() && data.starts_with(encoding.bom());
+		let data = if has_bom { &data[encoding.bom().len()..] } else { data };
+
+		let mut decoder = Decoder::new(encoding, has_bom)?;
+		let mut buf = Vec::<u16>::with_capacity(data.len());
+		decoder.decode(data, &mut buf, true)?;
+		Ok(Self::from_wchars_slice(&buf))
 	}
 }
 
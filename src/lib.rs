@@ -3,7 +3,7 @@
 #![cfg_attr(any(), rustfmt::skip)]
 
 // Declarations of macros used throughout the library.
-// No macros are public, except seq_ids.
+// No macros are public, except reg_settings and seq_ids.
 
 #[macro_use] mod macros;
 
@@ -164,6 +164,24 @@ pub mod msg {
 		pub use super::super::comctl::messages::hdm::*;
 	}
 
+	#[cfg(feature = "comctl")]
+	pub mod hkm {
+		//! Hot key control
+		//! [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-hot-key-control-reference-messages),
+		//! whose constants have [`HKM`](crate::co::HKM) prefix.
+
+		pub use super::super::comctl::messages::hkm::*;
+	}
+
+	#[cfg(feature = "comctl")]
+	pub mod ipm {
+		//! IP address control
+		//! [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-ip-address-control-reference-messages),
+		//! whose constants have [`IPM`](crate::co::IPM) prefix.
+
+		pub use super::super::comctl::messages::ipm::*;
+	}
+
 	#[cfg(feature = "user")]
 	pub mod lb {
 		//! ListBox control
@@ -173,6 +191,15 @@ pub mod msg {
 		pub use super::super::user::messages::lb::*;
 	}
 
+	#[cfg(feature = "comctl")]
+	pub mod lm {
+		//! SysLink control
+		//! [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-syslink-control-reference-messages),
+		//! whose constants have [`LM`](crate::co::LM) prefix.
+
+		pub use super::super::comctl::messages::lm::*;
+	}
+
 	#[cfg(feature = "comctl")]
 	pub mod lvm {
 		//! List view control
@@ -200,6 +227,24 @@ pub mod msg {
 		pub use super::super::comctl::messages::pbm::*;
 	}
 
+	#[cfg(feature = "comctl")]
+	pub mod pgm {
+		//! Pager control
+		//! [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-pager-control-reference-messages),
+		//! whose constants have [`PGM`](crate::co::PGM) prefix.
+
+		pub use super::super::comctl::messages::pgm::*;
+	}
+
+	#[cfg(feature = "comctl")]
+	pub mod rb {
+		//! Rebar control
+		//! [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-rebar-control-reference-messages),
+		//! whose constants have [`RB`](crate::co::RB) prefix.
+
+		pub use super::super::comctl::messages::rb::*;
+	}
+
 	#[cfg(feature = "comctl")]
 	pub mod sb {
 		//! Status bar control
@@ -248,6 +293,15 @@ pub mod msg {
 		pub use super::super::comctl::messages::trbm::*;
 	}
 
+	#[cfg(feature = "comctl")]
+	pub mod ttm {
+		//! Tooltip control
+		//! [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-tooltip-control-reference-messages),
+		//! whose constants have [`TTM`](crate::co::TTM) prefix.
+
+		pub use super::super::comctl::messages::ttm::*;
+	}
+
 	#[cfg(feature = "comctl")]
 	pub mod tvm {
 		//! Tree view control
@@ -119,6 +119,38 @@ macro_rules! com_interface_userdef_iunknown_methods {
 			co::HRESULT::E_NOTIMPL.raw()
 		}
 
+		com_interface_userdef_iunknown_methods!(@addref_release $impl);
+	};
+
+	// QueryInterface answers IUnknown and the given interface, what is needed
+	// when the object is marshaled to other processes. Since the caller may
+	// keep the returned pointer after the wrapper object is gone, it points to
+	// a heap cell stored in the `pp` field, which must be freed on drop.
+	($impl:ident, $iid:expr) => {
+		fn QueryInterface(p: COMPTR, riid: PCVOID, ppv: *mut COMPTR) -> HRES {
+			let riid = unsafe { &*(riid as *const co::IID) };
+			if *riid == <crate::decl::IUnknown as crate::prelude::ole_IUnknown>::IID || *riid == $iid {
+				let mut box_impl = box_impl_of::<Self>(p);
+				if box_impl.pp.is_null() {
+					box_impl.pp = Box::into_raw(Box::new(unsafe { *(p as *mut *mut Self) }));
+				}
+				Self::AddRef(p);
+				unsafe {
+					*ppv = box_impl.pp as _;
+				}
+				co::HRESULT::S_OK.raw()
+			} else {
+				unsafe {
+					*ppv = std::ptr::null_mut();
+				}
+				co::HRESULT::E_NOINTERFACE.raw()
+			}
+		}
+
+		com_interface_userdef_iunknown_methods!(@addref_release $impl);
+	};
+
+	(@addref_release $impl:ident) => {
 		fn AddRef(p: COMPTR) -> u32 {
 			let box_impl = box_impl_of::<Self>(p);
 			let cc = box_impl
@@ -23,6 +23,10 @@ mod handles;
 #[macro_use]
 mod messages;
 
+#[cfg(feature = "advapi")]
+#[macro_use]
+mod reg_settings;
+
 #[macro_use]
 mod seq_ids;
 
@@ -0,0 +1,70 @@
+/// Declares a struct and implements
+/// [`RegSettings`](crate::prelude::RegSettings) for it, mapping each field to a
+/// registry value or to a sub key.
+///
+/// Each field is followed by `=> value "Name"`, which binds it with
+/// [`RegBinder::value`](crate::RegBinder::value), or by `=> sub_key "Name"`,
+/// which binds it with [`RegBinder::sub_key`](crate::RegBinder::sub_key). The
+/// fields are loaded and saved in the order they are declared.
+///
+/// Since [`RegSettings`](crate::prelude::RegSettings) requires
+/// [`Default`](std::default::Default), which provides the values of missing
+/// fields, the struct must either derive or implement it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, reg_settings};
+///
+/// reg_settings! {
+///     #[derive(Default)]
+///     struct WindowSettings {
+///         width: u32 => value "Width",
+///         height: u32 => value "Height",
+///         maximized: bool => value "Maximized",
+///     }
+/// }
+///
+/// reg_settings! {
+///     #[derive(Default)]
+///     pub struct AppSettings {
+///         /// Most recent files first.
+///         pub recent_files: Vec<String> => value "RecentFiles",
+///         window: WindowSettings => sub_key "Window",
+///     }
+/// }
+///
+/// let mut hkcu = w::HKEY::CURRENT_USER;
+///
+/// let mut settings = AppSettings::load_from(&hkcu, "Software\\MyApp")?;
+/// settings.recent_files.insert(0, "C:\\Temp\\foo.txt".to_owned());
+/// settings.save_to(&mut hkcu, "Software\\MyApp")?;
+/// # w::SysResult::Ok(())
+/// ```
+#[macro_export]
+macro_rules! reg_settings {
+	(
+		$( #[$meta:meta] )*
+		$vis:vis struct $name:ident {
+			$(
+				$( #[$fmeta:meta] )*
+				$fvis:vis $field:ident : $fty:ty => $kind:ident $reg_name:literal
+			),* $(,)?
+		}
+	) => {
+		$( #[$meta] )*
+		$vis struct $name {
+			$(
+				$( #[$fmeta] )*
+				$fvis $field: $fty,
+			)*
+		}
+
+		impl $crate::prelude::RegSettings for $name {
+			fn bind(&mut self, binder: &mut $crate::RegBinder) -> $crate::SysResult<()> {
+				$( binder.$kind($reg_name, &mut self.$field)?; )*
+				Ok(())
+			}
+		}
+	};
+}
@@ -0,0 +1,159 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use std::sync::atomic::AtomicU32;
+
+use crate::co;
+use crate::decl::*;
+use crate::kernel::ffi_types::*;
+use crate::ole::{privs::*, vts::*};
+use crate::prelude::*;
+
+com_interface_userdef! { IDropSource, IDropSourceImpl: "00000121-0000-0000-c000-000000000046";
+	/// [`IDropSource`](https://learn.microsoft.com/en-us/windows/win32/api/oleidl/nn-oleidl-idropsource)
+	/// COM interface.
+	///
+	/// If no closures are set, the default behavior is used: the drag is
+	/// cancelled when ESC is pressed, the data is dropped when the mouse button
+	/// is released, and the default cursors are shown.
+	///
+	/// Automatically calls
+	/// [`Release`](https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release)
+	/// when the object goes out of scope.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let data_obj = w::DataObject::new_impl();
+	/// data_obj.set_text("dragged text");
+	///
+	/// let drop_source = w::IDropSource::new_impl();
+	/// let effect = w::DoDragDrop(
+	///     &data_obj,
+	///     &drop_source,
+	///     co::DROPEFFECT::COPY | co::DROPEFFECT::MOVE,
+	/// )?;
+	/// # w::HrResult::Ok(())
+	/// ```
+}
+
+impl IDropSource {
+	fn_com_userdef_closure! { GiveFeedback: Fn(co::DROPEFFECT) -> AnyResult<co::HRESULT>;
+		/// [`IDropSource::GiveFeedback`](https://learn.microsoft.com/en-us/windows/win32/api/oleidl/nf-oleidl-idropsource-givefeedback)
+		/// method.
+		///
+		/// The closure must return
+		/// [`HRESULT::S_OK`](crate::co::HRESULT::S_OK) if it has set the
+		/// cursor itself, or
+		/// [`HRESULT::DRAGDROP_S_USEDEFAULTCURSORS`](crate::co::HRESULT::DRAGDROP_S_USEDEFAULTCURSORS).
+	}
+
+	fn_com_userdef_closure! { QueryContinueDrag: Fn(bool, co::MK) -> AnyResult<co::HRESULT>;
+		/// [`IDropSource::QueryContinueDrag`](https://learn.microsoft.com/en-us/windows/win32/api/oleidl/nf-oleidl-idropsource-querycontinuedrag)
+		/// method.
+		///
+		/// The closure receives whether ESC was pressed, and the state of the
+		/// modifier keys and mouse buttons. It must return
+		/// [`HRESULT::S_OK`](crate::co::HRESULT::S_OK) to continue,
+		/// [`HRESULT::DRAGDROP_S_DROP`](crate::co::HRESULT::DRAGDROP_S_DROP) to
+		/// drop, or
+		/// [`HRESULT::DRAGDROP_S_CANCEL`](crate::co::HRESULT::DRAGDROP_S_CANCEL)
+		/// to cancel the operation.
+		///
+		/// # Examples
+		///
+		/// Dropping only when the right mouse button is released:
+		///
+		/// ```no_run
+		/// use winsafe::{self as w, prelude::*, co};
+		///
+		/// let drop_source = w::IDropSource::new_impl();
+		///
+		/// drop_source.QueryContinueDrag(
+		///     |escape: bool, key_st: co::MK| -> w::AnyResult<co::HRESULT> {
+		///         Ok(if escape {
+		///             co::HRESULT::DRAGDROP_S_CANCEL
+		///         } else if !key_st.has(co::MK::RBUTTON) {
+		///             co::HRESULT::DRAGDROP_S_DROP
+		///         } else {
+		///             co::HRESULT::S_OK
+		///         })
+		///     },
+		/// );
+		/// ```
+	}
+}
+
+type QueryContinueDragFunc = Box<dyn Fn(bool, co::MK) -> AnyResult<co::HRESULT>>;
+type GiveFeedbackFunc = Box<dyn Fn(co::DROPEFFECT) -> AnyResult<co::HRESULT>>;
+
+#[repr(C)]
+pub struct IDropSourceImpl {
+	vt: IDropSourceVT,
+	counter: AtomicU32,
+	pp: *mut *mut Self, // stable COM pointer returned by QueryInterface
+	QueryContinueDrag: Option<QueryContinueDragFunc>,
+	GiveFeedback: Option<GiveFeedbackFunc>,
+}
+
+impl Drop for IDropSourceImpl {
+	fn drop(&mut self) {
+		if !self.pp.is_null() {
+			let _ = unsafe { Box::from_raw(self.pp) }; // free the COM pointer cell
+		}
+	}
+}
+
+impl IDropSourceImpl {
+	fn new() -> Self {
+		Self {
+			vt: IDropSourceVT {
+				IUnknownVT: IUnknownVT {
+					QueryInterface: Self::QueryInterface,
+					AddRef: Self::AddRef,
+					Release: Self::Release,
+				},
+				QueryContinueDrag: Self::QueryContinueDrag,
+				GiveFeedback: Self::GiveFeedback,
+			},
+			counter: AtomicU32::new(1),
+			pp: std::ptr::null_mut(),
+			QueryContinueDrag: None,
+			GiveFeedback: None,
+		}
+	}
+
+	com_interface_userdef_iunknown_methods!(Self, <IDropSource as ole_IUnknown>::IID);
+
+	fn QueryContinueDrag(p: COMPTR, fEscapePressed: BOOL, grfKeyState: u32) -> HRES {
+		let box_impl = box_impl_of::<Self>(p);
+		let escape = fEscapePressed != 0;
+		let mk = unsafe { co::MK::from_raw(grfKeyState as _) };
+		match &box_impl.QueryContinueDrag {
+			Some(func) => anyresult_to_hresult(func(escape, mk)).unwrap_or_else(|hr| hr),
+			None => {
+				if escape {
+					co::HRESULT::DRAGDROP_S_CANCEL
+				} else if !mk.has(co::MK::LBUTTON) && !mk.has(co::MK::RBUTTON) {
+					co::HRESULT::DRAGDROP_S_DROP // mouse button released
+				} else {
+					co::HRESULT::S_OK
+				}
+			},
+		}
+		.raw()
+	}
+
+	fn GiveFeedback(p: COMPTR, dwEffect: u32) -> HRES {
+		let box_impl = box_impl_of::<Self>(p);
+		match &box_impl.GiveFeedback {
+			Some(func) => {
+				let fx = unsafe { co::DROPEFFECT::from_raw(dwEffect) };
+				anyresult_to_hresult(func(fx)).unwrap_or_else(|hr| hr)
+			},
+			None => co::HRESULT::DRAGDROP_S_USEDEFAULTCURSORS,
+		}
+		.raw()
+	}
+}
@@ -112,6 +112,7 @@ impl IDropTarget {
 pub struct IDropTargetImpl {
 	vt: IDropTargetVT,
 	counter: AtomicU32,
+	pp: *mut *mut Self, // stable COM pointer returned by QueryInterface
 	DragEnter:
 		Option<Box<dyn Fn(&IDataObject, co::MK, POINT, &mut co::DROPEFFECT) -> AnyResult<()>>>,
 	DragOver: Option<Box<dyn Fn(co::MK, POINT, &mut co::DROPEFFECT) -> AnyResult<()>>>,
@@ -119,6 +120,14 @@ pub struct IDropTargetImpl {
 	Drop: Option<Box<dyn Fn(&IDataObject, co::MK, POINT, &mut co::DROPEFFECT) -> AnyResult<()>>>,
 }
 
+impl Drop for IDropTargetImpl {
+	fn drop(&mut self) {
+		if !self.pp.is_null() {
+			let _ = unsafe { Box::from_raw(self.pp) }; // free the COM pointer cell
+		}
+	}
+}
+
 impl IDropTargetImpl {
 	fn new() -> Self {
 		Self {
@@ -134,6 +143,7 @@ impl IDropTargetImpl {
 				Drop: Self::Drop,
 			},
 			counter: AtomicU32::new(1),
+			pp: std::ptr::null_mut(),
 			DragEnter: None,
 			DragOver: None,
 			DragLeave: None,
@@ -141,7 +151,7 @@ impl IDropTargetImpl {
 		}
 	}
 
-	com_interface_userdef_iunknown_methods!(Self);
+	com_interface_userdef_iunknown_methods!(Self, <IDropTarget as ole_IUnknown>::IID);
 
 	fn DragEnter(
 		p: COMPTR,
@@ -1,6 +1,7 @@
 mod iadvisesink;
 mod ibindctx;
 mod idataobject;
+mod idropsource;
 mod idroptarget;
 mod imoniker;
 mod ipersist;
@@ -16,6 +17,7 @@ pub mod decl {
 	pub use super::iadvisesink::IAdviseSink;
 	pub use super::ibindctx::IBindCtx;
 	pub use super::idataobject::IDataObject;
+	pub use super::idropsource::IDropSource;
 	pub use super::idroptarget::IDropTarget;
 	pub use super::imoniker::IMoniker;
 	pub use super::ipersist::IPersist;
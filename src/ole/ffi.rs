@@ -18,6 +18,7 @@ extern_sys! { "ole32";
 	CreateItemMoniker(PCSTR, PCSTR, *mut COMPTR) -> HRES
 	CreateObjrefMoniker(COMPTR, *mut COMPTR) -> HRES
 	CreatePointerMoniker(COMPTR, *mut COMPTR) -> HRES
+	DoDragDrop(COMPTR, COMPTR, u32, *mut u32) -> HRES
 	OleInitialize(PVOID) -> HRES
 	OleUninitialize()
 	RegisterDragDrop(HANDLE, COMPTR) -> HRES
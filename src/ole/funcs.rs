@@ -322,6 +322,55 @@ pub fn CreatePointerMoniker(unk: &impl ole_IUnknown) -> HrResult<IMoniker> {
 		.map(|_| queried)
 }
 
+/// [`DoDragDrop`](https://learn.microsoft.com/en-us/windows/win32/api/ole2/nf-ole2-dodragdrop)
+/// function.
+///
+/// This function will block until the drag and drop operation ends. Returns
+/// the effect performed by the drop target, or
+/// [`DROPEFFECT::NONE`](crate::co::DROPEFFECT::NONE) if the operation was
+/// cancelled.
+///
+/// Note that you must call [`OleInitialize`](crate::OleInitialize) before
+/// this function.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let data_obj = w::DataObject::new_impl();
+/// data_obj.set_files(&["C:\\Temp\\foo.txt"]);
+///
+/// let effect = w::DoDragDrop(
+///     &data_obj,
+///     &w::IDropSource::new_impl(),
+///     co::DROPEFFECT::COPY,
+/// )?;
+/// if effect != co::DROPEFFECT::NONE {
+///     println!("File dropped.");
+/// }
+/// # w::HrResult::Ok(())
+/// ```
+pub fn DoDragDrop(
+	data_obj: &impl ole_IDataObject,
+	drop_source: &IDropSource,
+	ok_effects: co::DROPEFFECT,
+) -> HrResult<co::DROPEFFECT> {
+	let mut effect = u32::default();
+	match unsafe {
+		co::HRESULT::from_raw(ffi::DoDragDrop(
+			data_obj.ptr(),
+			drop_source.ptr(),
+			ok_effects.raw(),
+			&mut effect,
+		))
+	} {
+		co::HRESULT::DRAGDROP_S_DROP => Ok(unsafe { co::DROPEFFECT::from_raw(effect) }),
+		co::HRESULT::DRAGDROP_S_CANCEL => Ok(co::DROPEFFECT::NONE),
+		hr => Err(hr),
+	}
+}
+
 /// [`OleInitialize`](https://learn.microsoft.com/en-us/windows/win32/api/ole2/nf-ole2-oleinitialize)
 /// function, which calls [`CoInitializeEx`](crate::CoInitializeEx) and enables
 /// OLE operations.
@@ -5,6 +5,7 @@ mod com_interfaces;
 mod funcs;
 mod handles;
 mod structs;
+mod utilities;
 
 pub mod co;
 pub(in crate::ole) mod ffi;
@@ -17,6 +18,7 @@ pub mod decl {
 	pub use super::com_interfaces::decl::*;
 	pub use super::funcs::*;
 	pub use super::structs::*;
+	pub use super::utilities::*;
 
 	handle! { HMETAFILEPICT;
 		/// Handle to a
@@ -0,0 +1,327 @@
+#![allow(non_snake_case)]
+
+use std::sync::atomic::AtomicU32;
+
+use crate::co;
+use crate::decl::*;
+use crate::kernel::ffi_types::*;
+use crate::ole::{privs::*, vts::*};
+use crate::prelude::*;
+
+com_interface_userdef! { DataObject, DataObjectImpl: "0000010e-0000-0000-c000-000000000046";
+	/// Custom implementation of the
+	/// [`IDataObject`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-idataobject)
+	/// COM interface, which offers data in one or more clipboard formats,
+	/// usually to start a drag and drop operation with
+	/// [`DoDragDrop`](crate::DoDragDrop).
+	///
+	/// The data is stored in memory, and delivered as
+	/// [`TYMED::HGLOBAL`](crate::co::TYMED::HGLOBAL).
+	///
+	/// Automatically calls
+	/// [`Release`](https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release)
+	/// when the object goes out of scope.
+	///
+	/// # Examples
+	///
+	/// Offering text and a file list at once:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let data_obj = w::DataObject::new_impl();
+	/// data_obj
+	///     .set_text("C:\\Temp\\foo.txt")
+	///     .set_files(&["C:\\Temp\\foo.txt"]);
+	/// ```
+}
+
+impl ole_IDataObject for DataObject {}
+
+impl DataObject {
+	/// Stores raw data in the given format, replacing any data previously
+	/// stored in this format.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let cf_custom = w::RegisterClipboardFormat("MyApp.Data")?;
+	///
+	/// let data_obj = w::DataObject::new_impl();
+	/// data_obj.set_data(cf_custom, &[1, 2, 3]);
+	/// # w::SysResult::Ok(())
+	/// ```
+	pub fn set_data(&self, format: co::CF, data: &[u8]) -> &Self {
+		let mut box_impl = std::mem::ManuallyDrop::new(unsafe { Box::from_raw(self.0) });
+		match box_impl.formats.iter_mut().find(|(cf, _)| *cf == format) {
+			Some((_, stored)) => *stored = data.to_vec(),
+			None => box_impl.formats.push((format, data.to_vec())),
+		}
+		self
+	}
+
+	/// Stores a list of files as [`CF::HDROP`](crate::co::CF::HDROP), which
+	/// can be dropped into Windows Explorer.
+	///
+	/// The data is serialized with
+	/// [`DropFiles::serialize`](crate::DropFiles::serialize).
+	pub fn set_files(&self, files: &[impl AsRef<str>]) -> &Self {
+		self.set_data(co::CF::HDROP, &DropFiles::new(files).serialize())
+	}
+
+	/// Stores an HTML fragment in the registered `"HTML Format"`.
+	///
+	/// The data is serialized with
+	/// [`HtmlFormat::serialize`](crate::HtmlFormat::serialize).
+	pub fn set_html(&self, html: &HtmlFormat) -> SysResult<&Self> {
+		let cf_html = RegisterClipboardFormat("HTML Format")?;
+		Ok(self.set_data(cf_html, &html.serialize()))
+	}
+
+	/// Stores text as [`CF::UNICODETEXT`](crate::co::CF::UNICODETEXT).
+	pub fn set_text(&self, text: &str) -> &Self {
+		let data = text
+			.encode_utf16()
+			.chain(std::iter::once(0)) // null-terminated
+			.flat_map(|ch| ch.to_le_bytes())
+			.collect::<Vec<_>>();
+		self.set_data(co::CF::UNICODETEXT, &data)
+	}
+}
+
+#[repr(C)]
+pub struct DataObjectImpl {
+	vt: IDataObjectVT,
+	counter: AtomicU32,
+	pp: *mut *mut Self, // stable COM pointer returned by QueryInterface
+	formats: Vec<(co::CF, Vec<u8>)>,
+}
+
+impl Drop for DataObjectImpl {
+	fn drop(&mut self) {
+		if !self.pp.is_null() {
+			let _ = unsafe { Box::from_raw(self.pp) }; // free the COM pointer cell
+		}
+	}
+}
+
+impl DataObjectImpl {
+	fn new() -> Self {
+		Self {
+			vt: IDataObjectVT {
+				IUnknownVT: IUnknownVT {
+					QueryInterface: Self::QueryInterface,
+					AddRef: Self::AddRef,
+					Release: Self::Release,
+				},
+				GetData: Self::GetData,
+				GetDataHere: Self::GetDataHere,
+				QueryGetData: Self::QueryGetData,
+				GetCanonicalFormatEtc: Self::GetCanonicalFormatEtc,
+				SetData: Self::SetData,
+				EnumFormatEtc: Self::EnumFormatEtc,
+				DAdvise: Self::DAdvise,
+				DUnadvise: Self::DUnadvise,
+				EnumDAdvise: Self::EnumDAdvise,
+			},
+			counter: AtomicU32::new(1),
+			pp: std::ptr::null_mut(),
+			formats: Vec::new(),
+		}
+	}
+
+	com_interface_userdef_iunknown_methods!(Self, <IDataObject as ole_IUnknown>::IID);
+
+	fn find(&self, pformatetc: PVOID) -> HrResult<&[u8]> {
+		let fetc = unsafe { &*(pformatetc as *const FORMATETC) };
+		if fetc.dwAspect != co::DVASPECT::CONTENT {
+			Err(co::HRESULT::DV_E_DVASPECT)
+		} else if fetc.tymed.raw() & co::TYMED::HGLOBAL.raw() == 0 {
+			Err(co::HRESULT::DV_E_TYMED) // we only deliver HGLOBAL
+		} else {
+			self.formats
+				.iter()
+				.find(|(cf, _)| *cf == fetc.cfFormat)
+				.map(|(_, data)| data.as_slice())
+				.ok_or(co::HRESULT::DV_E_FORMATETC)
+		}
+	}
+
+	fn GetData(p: COMPTR, pformatetcIn: PVOID, pmedium: PVOID) -> HRES {
+		let box_impl = box_impl_of::<Self>(p);
+		hrresult_to_hres(box_impl.find(pformatetcIn).and_then(|data| {
+			let mut hglobal = HGLOBAL::GlobalAlloc(co::GMEM::MOVEABLE, data.len().max(1))
+				.map_err(|err| err.to_hresult())?;
+			{
+				let mut block = hglobal.GlobalLock().map_err(|err| err.to_hresult())?;
+				block.as_mut_slice()[..data.len()].copy_from_slice(data);
+			}
+			let medium = unsafe { &mut *(pmedium as *mut STGMEDIUM) };
+			medium.tymed = co::TYMED::HGLOBAL;
+			medium.ptr = hglobal.leak().ptr() as _; // the receiver will free the memory
+			medium.pUnkForRelease = std::ptr::null_mut();
+			Ok(())
+		}))
+	}
+
+	fn GetDataHere(_p: COMPTR, _pformatetc: PVOID, _pmedium: PVOID) -> HRES {
+		co::HRESULT::E_NOTIMPL.raw()
+	}
+
+	fn QueryGetData(p: COMPTR, pformatetc: PVOID) -> HRES {
+		let box_impl = box_impl_of::<Self>(p);
+		hrresult_to_hres(box_impl.find(pformatetc))
+	}
+
+	fn GetCanonicalFormatEtc(_p: COMPTR, _pformatectIn: PVOID, _pformatetcOut: PVOID) -> HRES {
+		co::HRESULT::E_NOTIMPL.raw()
+	}
+
+	fn SetData(_p: COMPTR, _pformatetc: PVOID, _pmedium: PVOID, _fRelease: BOOL) -> HRES {
+		co::HRESULT::E_NOTIMPL.raw()
+	}
+
+	fn EnumFormatEtc(p: COMPTR, dwDirection: u32, ppenumFormatEtc: *mut COMPTR) -> HRES {
+		let box_impl = box_impl_of::<Self>(p);
+		if dwDirection != 1 {
+			// DATADIR_SET is not supported
+			unsafe {
+				*ppenumFormatEtc = std::ptr::null_mut();
+			}
+			return co::HRESULT::E_NOTIMPL.raw();
+		}
+		let formats = box_impl.formats.iter().map(|(cf, _)| *cf).collect();
+		unsafe {
+			*ppenumFormatEtc = EnumFormatEtcImpl::new_comptr(formats, 0);
+		}
+		co::HRESULT::S_OK.raw()
+	}
+
+	fn DAdvise(
+		_p: COMPTR,
+		_pformatetc: PVOID,
+		_advf: u32,
+		_pAdvSink: COMPTR,
+		_pdwConnection: *mut u32,
+	) -> HRES {
+		co::HRESULT::OLE_E_ADVISENOTSUPPORTED.raw()
+	}
+
+	fn DUnadvise(_p: COMPTR, _dwConnection: u32) -> HRES {
+		co::HRESULT::OLE_E_ADVISENOTSUPPORTED.raw()
+	}
+
+	fn EnumDAdvise(_p: COMPTR, _ppenumAdvise: *mut COMPTR) -> HRES {
+		co::HRESULT::OLE_E_ADVISENOTSUPPORTED.raw()
+	}
+}
+
+/// Implementation of
+/// [`IEnumFORMATETC`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-ienumformatetc),
+/// returned by `DataObject::EnumFormatEtc`.
+///
+/// Since there's no wrapper object to hold the COM pointer, it's stored in a
+/// separated heap cell, which is freed along with the object.
+#[repr(C)]
+struct EnumFormatEtcImpl {
+	vt: IEnumFORMATETCVT,
+	counter: AtomicU32,
+	pp: *mut *mut Self,
+	formats: Vec<co::CF>,
+	current: usize,
+}
+
+impl Drop for EnumFormatEtcImpl {
+	fn drop(&mut self) {
+		let _ = unsafe { Box::from_raw(self.pp) }; // free the COM pointer cell
+	}
+}
+
+impl EnumFormatEtcImpl {
+	#[must_use]
+	fn new_comptr(formats: Vec<co::CF>, current: usize) -> COMPTR {
+		let p = Box::into_raw(Box::new(Self {
+			vt: IEnumFORMATETCVT {
+				IUnknownVT: IUnknownVT {
+					QueryInterface: Self::QueryInterface,
+					AddRef: Self::AddRef,
+					Release: Self::Release,
+				},
+				Next: Self::Next,
+				Skip: Self::Skip,
+				Reset: Self::Reset,
+				Clone: Self::Clone,
+			},
+			counter: AtomicU32::new(1),
+			pp: std::ptr::null_mut(),
+			formats,
+			current,
+		}));
+		let pp = Box::into_raw(Box::new(p));
+		unsafe {
+			(*p).pp = pp;
+		}
+		pp as _
+	}
+
+	com_interface_userdef_iunknown_methods!(Self, unsafe {
+		co::IID::from_raw("00000103-0000-0000-c000-000000000046")
+	});
+
+	fn Next(p: COMPTR, celt: u32, rgelt: PVOID, pceltFetched: *mut u32) -> HRES {
+		let mut box_impl = box_impl_of::<Self>(p);
+		let rgelt = rgelt as *mut FORMATETC;
+		let mut fetched = 0;
+
+		while fetched < celt as usize && box_impl.current < box_impl.formats.len() {
+			let mut fetc = FORMATETC::default();
+			fetc.cfFormat = box_impl.formats[box_impl.current];
+			fetc.dwAspect = co::DVASPECT::CONTENT;
+			fetc.tymed = co::TYMED::HGLOBAL;
+			unsafe {
+				rgelt.add(fetched).write(fetc);
+			}
+			fetched += 1;
+			box_impl.current += 1;
+		}
+
+		if !pceltFetched.is_null() {
+			unsafe {
+				*pceltFetched = fetched as _;
+			}
+		}
+		if fetched == celt as usize {
+			co::HRESULT::S_OK.raw()
+		} else {
+			co::HRESULT::S_FALSE.raw()
+		}
+	}
+
+	fn Skip(p: COMPTR, celt: u32) -> HRES {
+		let mut box_impl = box_impl_of::<Self>(p);
+		let remaining = box_impl.formats.len() - box_impl.current;
+		if celt as usize <= remaining {
+			box_impl.current += celt as usize;
+			co::HRESULT::S_OK.raw()
+		} else {
+			box_impl.current = box_impl.formats.len();
+			co::HRESULT::S_FALSE.raw()
+		}
+	}
+
+	fn Reset(p: COMPTR) -> HRES {
+		let mut box_impl = box_impl_of::<Self>(p);
+		box_impl.current = 0;
+		co::HRESULT::S_OK.raw()
+	}
+
+	fn Clone(p: COMPTR, ppenum: *mut COMPTR) -> HRES {
+		let box_impl = box_impl_of::<Self>(p);
+		unsafe {
+			*ppenum = Self::new_comptr(box_impl.formats.clone(), box_impl.current);
+		}
+		co::HRESULT::S_OK.raw()
+	}
+}
@@ -0,0 +1,3 @@
+mod data_object;
+
+pub use data_object::DataObject;
@@ -41,6 +41,13 @@ pub struct IDataObjectVT {
 	pub EnumDAdvise: fn(COMPTR, *mut COMPTR) -> HRES,
 }
 
+#[repr(C)]
+pub struct IDropSourceVT {
+	pub IUnknownVT: IUnknownVT,
+	pub QueryContinueDrag: fn(COMPTR, BOOL, u32) -> HRES,
+	pub GiveFeedback: fn(COMPTR, u32) -> HRES,
+}
+
 #[repr(C)]
 pub struct IDropTargetVT {
 	pub IUnknownVT: IUnknownVT,
@@ -50,6 +57,15 @@ pub struct IDropTargetVT {
 	pub Drop: fn(COMPTR, COMPTR, u32, u64, *mut u32) -> HRES,
 }
 
+#[repr(C)]
+pub struct IEnumFORMATETCVT {
+	pub IUnknownVT: IUnknownVT,
+	pub Next: fn(COMPTR, u32, PVOID, *mut u32) -> HRES,
+	pub Skip: fn(COMPTR, u32) -> HRES,
+	pub Reset: fn(COMPTR) -> HRES,
+	pub Clone: fn(COMPTR, *mut COMPTR) -> HRES,
+}
+
 #[repr(C)]
 pub struct IMonikerVT {
 	pub IPersistStreamVT: IPersistStreamVT,
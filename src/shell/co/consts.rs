@@ -229,6 +229,24 @@ const_bitflag! { NIIF: u32;
 	RESPECT_QUIET_TIME 0x0000_0080
 }
 
+const_ordinary! { NIN: u32;
+	/// Notification icon events, sent in the low-order word of the
+	/// [`NOTIFYICONDATA`](crate::NOTIFYICONDATA) `uCallbackMessage` `lParam`
+	/// (`u32`).
+	///
+	/// Besides these, the mouse messages like
+	/// [`WM::CONTEXTMENU`](crate::co::WM::CONTEXTMENU) are also sent.
+	=>
+	SELECT 0x0400
+	KEYSELECT 0x0401
+	BALLOONSHOW 0x0402
+	BALLOONHIDE 0x0403
+	BALLOONTIMEOUT 0x0404
+	BALLOONUSERCLICK 0x0405
+	POPUPOPEN 0x0406
+	POPUPCLOSE 0x0407
+}
+
 const_ordinary! { NIM: u32;
 	/// [`Shell_NotifyIcon`](crate::Shell_NotifyIcon) `message` (`u32`).
 	=>
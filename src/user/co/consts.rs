@@ -1075,6 +1075,17 @@ const_ordinary! { DLGID: u16;
 	CONTINUE 11
 }
 
+const_ordinary! { DPI_AWARENESS_CONTEXT: isize;
+	/// [`DPI_AWARENESS_CONTEXT`](https://learn.microsoft.com/en-us/windows/win32/hidpi/dpi-awareness-context)
+	/// pseudo handles (`isize`).
+	=>
+	UNAWARE -1
+	SYSTEM_AWARE -2
+	PER_MONITOR_AWARE -3
+	PER_MONITOR_AWARE_V2 -4
+	UNAWARE_GDISCALED -5
+}
+
 const_ordinary! { DPI_HOSTING_BEHAVIOR: u32;
 	/// [`DPI_HOSTING_BEHAVIOR`](https://learn.microsoft.com/en-us/windows/win32/api/windef/ne-windef-dpi_hosting_behavior)
 	/// enumeration (`u32`).
@@ -1084,6 +1095,57 @@ const_ordinary! { DPI_HOSTING_BEHAVIOR: u32;
 	MIXED 1
 }
 
+const_ws! { DS: u32;
+	/// Dialog box
+	/// [styles](https://learn.microsoft.com/en-us/windows/win32/dlgbox/dialog-box-styles)
+	/// (`u32`).
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	/// Indicates that the coordinates of the dialog box are screen coordinates.
+	/// If this style is not specified, the coordinates are client coordinates.
+	ABSALIGN 0x01
+	/// This style is obsolete and is included for compatibility with 16-bit
+	/// versions of Windows.
+	SYSMODAL 0x02
+	/// Causes the dialog box to use the `SYSTEM_FIXED_FONT` instead of the
+	/// default `SYSTEM_FONT`.
+	FIXEDSYS 0x0008
+	/// Creates the dialog box even if errors occur. For example, if a child
+	/// window cannot be created or if the system cannot create a special data
+	/// segment for an edit control, the dialog box is created anyway.
+	NOFAILCREATE 0x0010
+	/// Applies to 16-bit applications only.
+	LOCALEDIT 0x20
+	/// Indicates that the header of the dialog box template contains
+	/// additional data specifying the font to use for text in the client area
+	/// and controls of the dialog box.
+	SETFONT 0x40
+	/// Creates a dialog box with a modal dialog-box frame that can be combined
+	/// with a title bar and window menu by specifying the
+	/// [`WS::CAPTION`](crate::co::WS::CAPTION) and
+	/// [`WS::SYSMENU`](crate::co::WS::SYSMENU) styles.
+	MODALFRAME 0x80
+	/// Suppresses `WM_ENTERIDLE` messages that the system would otherwise send
+	/// to the owner of the dialog box while the dialog box is displayed.
+	NOIDLEMSG 0x100
+	/// Causes the system to use the `SetForegroundWindow` function to bring
+	/// the dialog box to the foreground.
+	SETFOREGROUND 0x200
+	/// Creates a dialog box that works well as a child window of another
+	/// dialog box, much like a page in a property sheet.
+	CONTROL 0x0400
+	/// Centers the dialog box in the working area of the monitor that contains
+	/// the owner window.
+	CENTER 0x0800
+	/// Centers the dialog box on the mouse cursor.
+	CENTERMOUSE 0x1000
+	/// Includes a question mark in the title bar of the dialog box.
+	CONTEXTHELP 0x2000
+	/// Indicates that the dialog box should use the system font.
+	SHELLFONT Self::SETFONT.0 | Self::FIXEDSYS.0
+}
+
 const_bitflag! { DT: u32;
 	/// [`HDC::DrawText`](crate::prelude::user_Hdc::DrawText) `format` (`u32`).
 	=>
@@ -3242,6 +3304,7 @@ const_ordinary! { WM: u32;
 	SYSCOLORCHANGE 0x0015
 	SHOWWINDOW 0x0018
 	WININICHANGE 0x001a
+	SETTINGCHANGE Self::WININICHANGE.0
 	DEVMODECHANGE 0x001b
 	ACTIVATEAPP 0x001c
 	FONTCHANGE 0x001d
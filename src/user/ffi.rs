@@ -19,6 +19,7 @@ extern_sys! { "user32";
 }
 
 extern_sys! { "user32";
+	AddClipboardFormatListener(HANDLE) -> BOOL
 	AdjustWindowRectEx(PVOID, u32, BOOL, u32) -> BOOL
 	AdjustWindowRectExForDpi(PVOID, u32, BOOL, u32, u32) -> BOOL
 	AllowSetForegroundWindow(u32) -> BOOL
@@ -43,9 +44,11 @@ extern_sys! { "user32";
 	CloseDesktop(HANDLE) -> BOOL
 	CloseWindow(HANDLE) -> BOOL
 	CopyIcon(HANDLE) -> HANDLE
+	CountClipboardFormats() -> i32
 	CreateAcceleratorTableW(PCVOID, i32) -> HANDLE
 	CreateDesktopExW(PCSTR, PCSTR, PCVOID, u32, u32, PVOID, u32, PVOID) -> HANDLE
 	CreateDesktopW(PCSTR, PCSTR, PCVOID, u32, u32, PVOID) -> HANDLE
+	CreateDialogIndirectParamW(HANDLE, PCVOID, HANDLE, PFUNC, isize) -> HANDLE
 	CreateDialogParamW(HANDLE, PCSTR, HANDLE, PFUNC, isize) -> HANDLE
 	CreateMenu() -> HANDLE
 	CreatePopupMenu() -> HANDLE
@@ -75,6 +78,7 @@ extern_sys! { "user32";
 	EndDialog(HANDLE, isize) -> BOOL
 	EndMenu() -> BOOL
 	EndPaint(HANDLE, PCVOID) -> BOOL
+	EnumClipboardFormats(u32) -> u32
 	EnumChildWindows(HANDLE, PFUNC, isize) -> BOOL
 	EnumDisplayDevicesW(PCSTR, u32, PVOID, u32) -> BOOL
 	EnumDisplayMonitors(HANDLE, PCVOID, PFUNC, isize) -> BOOL
@@ -99,6 +103,8 @@ extern_sys! { "user32";
 	GetClassNameW(HANDLE, PSTR, i32) -> i32
 	GetClientRect(HANDLE, PVOID) -> BOOL
 	GetClipboardData(u32) -> HANDLE
+	GetClipboardFormatNameW(u32, PSTR, i32) -> i32
+	GetClipboardOwner() -> HANDLE
 	GetClipboardSequenceNumber() -> u32
 	GetClipCursor(PVOID) -> BOOL
 	GetCursor() -> HANDLE
@@ -112,6 +118,7 @@ extern_sys! { "user32";
 	GetDlgCtrlID(HANDLE) -> i32
 	GetDlgItem(HANDLE, i32) -> HANDLE
 	GetDoubleClickTime() -> u32
+	GetDpiForSystem() -> u32
 	GetDpiForWindow(HANDLE) -> u32
 	GetFocus() -> HANDLE
 	GetForegroundWindow() -> HANDLE
@@ -176,6 +183,7 @@ extern_sys! { "user32";
 	InvalidateRect(HANDLE, PCVOID, BOOL) -> BOOL
 	InvalidateRgn(HANDLE, HANDLE, BOOL) -> BOOL
 	InvertRect(HANDLE, PCVOID) -> BOOL
+	IsClipboardFormatAvailable(u32) -> BOOL
 	IsChild(HANDLE, HANDLE) -> BOOL
 	IsDialogMessageW(HANDLE, PVOID) -> BOOL
 	IsGUIThread(BOOL) -> BOOL
@@ -219,10 +227,12 @@ extern_sys! { "user32";
 	RealGetWindowClassW(HANDLE, PSTR, i32) -> u32
 	RedrawWindow(HANDLE, PCVOID, HANDLE, u32) -> BOOL
 	RegisterClassExW(PCVOID) -> u16
+	RegisterClipboardFormatW(PCSTR) -> u32
 	RegisterHotKey(HANDLE, i32, u32, u32) -> BOOL
 	RegisterWindowMessageW(PCSTR) -> u32
 	ReleaseCapture() -> BOOL
 	ReleaseDC(HANDLE, HANDLE) -> i32
+	RemoveClipboardFormatListener(HANDLE) -> BOOL
 	RemoveMenu(HANDLE, u32, u32) -> BOOL
 	ScreenToClient(HANDLE, PVOID) -> BOOL
 	ScrollWindowEx(HANDLE, i32, i32, PCVOID, PCVOID, HANDLE, PVOID, u32) -> i32
@@ -249,6 +259,7 @@ extern_sys! { "user32";
 	SetPhysicalCursorPos(i32, i32) -> BOOL
 	SetProcessDefaultLayout(u32) -> BOOL
 	SetProcessDPIAware() -> BOOL
+	SetProcessDpiAwarenessContext(isize) -> BOOL
 	SetScrollInfo(HANDLE, i32, PCVOID, BOOL) -> i32
 	SetScrollPos(HANDLE, i32, i32, BOOL) -> i32
 	SetScrollRange(HANDLE, i32, i32, i32, BOOL) -> BOOL
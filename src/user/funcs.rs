@@ -222,6 +222,20 @@ pub unsafe fn DispatchMessage(msg: &MSG) -> isize {
 	ffi::DispatchMessageW(msg as *const _ as _)
 }
 
+/// [`CountClipboardFormats`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-countclipboardformats)
+/// function.
+#[must_use]
+pub fn CountClipboardFormats() -> SysResult<u32> {
+	SetLastError(co::ERROR::SUCCESS);
+	match unsafe { ffi::CountClipboardFormats() } {
+		0 => match GetLastError() {
+			co::ERROR::SUCCESS => Ok(0), // the clipboard is actually empty
+			err => Err(err),
+		},
+		n => Ok(n as _),
+	}
+}
+
 /// [`EndMenu`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-endmenu)
 /// function.
 pub fn EndMenu() -> SysResult<()> {
@@ -465,6 +479,28 @@ pub fn GetCaretPos() -> SysResult<POINT> {
 	bool_to_sysresult(unsafe { ffi::GetCaretPos(&mut pt as *mut _ as _) }).map(|_| pt)
 }
 
+/// [`GetClipboardFormatName`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getclipboardformatnamew)
+/// function.
+///
+/// Returns an error for the predefined formats, which have no name.
+#[must_use]
+pub fn GetClipboardFormatName(format: co::CF) -> SysResult<String> {
+	let mut buf = WString::new_alloc_buf(256 + 1);
+	match unsafe {
+		ffi::GetClipboardFormatNameW(format.raw() as _, buf.as_mut_ptr(), buf.buf_len() as _)
+	} {
+		0 => Err(GetLastError()),
+		_ => Ok(buf.to_string()),
+	}
+}
+
+/// [`GetClipboardOwner`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getclipboardowner)
+/// function.
+#[must_use]
+pub fn GetClipboardOwner() -> Option<HWND> {
+	ptr_to_option_handle(unsafe { ffi::GetClipboardOwner() })
+}
+
 /// [`GetClipCursor`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getclipcursor)
 /// function.
 #[must_use]
@@ -501,6 +537,13 @@ pub fn GetDoubleClickTime() -> u32 {
 	unsafe { ffi::GetDoubleClickTime() }
 }
 
+/// [`GetDpiForSystem`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getdpiforsystem)
+/// function.
+#[must_use]
+pub fn GetDpiForSystem() -> u32 {
+	unsafe { ffi::GetDpiForSystem() }
+}
+
 /// [`GetGUIThreadInfo`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getguithreadinfo)
 /// function.
 ///
@@ -674,6 +717,15 @@ pub fn IntersectRect(src1: RECT, src2: RECT) -> SysResult<RECT> {
 	.map(|_| dest)
 }
 
+/// [`IsClipboardFormatAvailable`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-isclipboardformatavailable)
+/// function.
+///
+/// The clipboard doesn't need to be open to call this function.
+#[must_use]
+pub fn IsClipboardFormatAvailable(format: co::CF) -> bool {
+	unsafe { ffi::IsClipboardFormatAvailable(format.raw() as _) != 0 }
+}
+
 /// [`IsGUIThread`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-isguithread)
 /// function.
 pub fn IsGUIThread(convert_to_gui_thread: bool) -> SysResult<bool> {
@@ -791,6 +843,35 @@ pub unsafe fn RegisterClassEx(wcx: &WNDCLASSEX) -> SysResult<ATOM> {
 	}
 }
 
+/// [`RegisterClipboardFormat`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-registerclipboardformatw)
+/// function.
+///
+/// If the format is already registered, its existing identifier is returned,
+/// so this function can be used to retrieve formats registered by other
+/// applications, like `"HTML Format"`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let cf_custom = w::RegisterClipboardFormat("MyApp.Data")?;
+///
+/// let hwnd: w::HWND; // initialized somewhere
+/// # let hwnd = w::HWND::NULL;
+/// let hclip = hwnd.OpenClipboard()?;
+/// hclip.EmptyClipboard()?;
+/// hclip.SetClipboardData(cf_custom, &[1, 2, 3])?;
+/// # w::SysResult::Ok(())
+/// ```
+#[must_use]
+pub fn RegisterClipboardFormat(name: &str) -> SysResult<co::CF> {
+	match unsafe { ffi::RegisterClipboardFormatW(WString::from_str(name).as_ptr()) } {
+		0 => Err(GetLastError()),
+		id => Ok(unsafe { co::CF::from_raw(id as _) }),
+	}
+}
+
 /// [`RegisterWindowMessage`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-registerwindowmessagew)
 /// function.
 #[must_use]
@@ -906,6 +987,12 @@ pub fn SetProcessDPIAware() -> SysResult<()> {
 	bool_to_sysresult(unsafe { ffi::SetProcessDPIAware() })
 }
 
+/// [`SetProcessDpiAwarenessContext`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setprocessdpiawarenesscontext)
+/// function.
+pub fn SetProcessDpiAwarenessContext(value: co::DPI_AWARENESS_CONTEXT) -> SysResult<()> {
+	bool_to_sysresult(unsafe { ffi::SetProcessDpiAwarenessContext(value.raw()) })
+}
+
 /// [`SetSysColors`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setsyscolors)
 /// function.
 ///
@@ -4,7 +4,7 @@ use crate::co;
 use crate::decl::*;
 use crate::kernel::privs::*;
 use crate::prelude::*;
-use crate::user::ffi;
+use crate::user::{ffi, iterators::*};
 
 handle! { HCLIPBOARD;
 	/// Handle to the
@@ -25,6 +25,199 @@ impl user_Hclipboard for HCLIPBOARD {}
 /// use winsafe::prelude::*;
 /// ```
 pub trait user_Hclipboard: Handle {
+	/// Retrieves the image in the clipboard as the contents of a `.bmp` file,
+	/// which can be directly written to disk.
+	///
+	/// The data is read from [`CF::DIBV5`](crate::co::CF::DIBV5), which
+	/// preserves transparency, or from [`CF::DIB`](crate::co::CF::DIB).
+	///
+	/// Returns [`ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA) if the
+	/// bitmap header is malformed.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hclip = w::HWND::NULL.OpenClipboard()?;
+	/// if w::IsClipboardFormatAvailable(co::CF::DIB) {
+	///     std::fs::write("C:\\Temp\\pasted.bmp", hclip.bitmap()?)?;
+	/// }
+	/// # w::AnyResult::Ok(())
+	/// ```
+	#[must_use]
+	fn bitmap(&self) -> SysResult<Vec<u8>> {
+		let dib = if IsClipboardFormatAvailable(co::CF::DIBV5) {
+			self.GetClipboardData(co::CF::DIBV5)?
+		} else {
+			self.GetClipboardData(co::CF::DIB)?
+		};
+		dib_to_bmp(&dib)
+	}
+
+	/// Retrieves the list of files in the clipboard, stored as
+	/// [`CF::HDROP`](crate::co::CF::HDROP), which is usually placed by Windows
+	/// Explorer when files are copied.
+	///
+	/// The data is parsed with [`DropFiles::parse`](crate::DropFiles::parse).
+	#[must_use]
+	fn files(&self) -> SysResult<Vec<String>> {
+		let data = self.GetClipboardData(co::CF::HDROP)?;
+		DropFiles::parse(&data).map(|df| df.files)
+	}
+
+	/// Retrieves the HTML fragment in the clipboard, stored in the registered
+	/// `"HTML Format"`.
+	///
+	/// The data is parsed with [`HtmlFormat::parse`](crate::HtmlFormat::parse).
+	#[must_use]
+	fn html(&self) -> SysResult<HtmlFormat> {
+		let cf_html = RegisterClipboardFormat("HTML Format")?;
+		let data = self.GetClipboardData(cf_html)?;
+		HtmlFormat::parse(&data)
+	}
+
+	/// Returns an iterator over the formats currently available in the
+	/// clipboard, in the order they were placed, by calling
+	/// [`EnumClipboardFormats`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-enumclipboardformats)
+	/// repeatedly.
+	///
+	/// Formats synthesized by the system are included after the original ones.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let hclip = w::HWND::NULL.OpenClipboard()?;
+	/// for format in hclip.iter_formats() {
+	///     let format = format?;
+	///     let name = w::GetClipboardFormatName(format)
+	///         .unwrap_or_else(|_| "(predefined)".to_owned());
+	///     println!("{} {}", format, name);
+	/// }
+	/// # w::SysResult::Ok(())
+	/// ```
+	#[must_use]
+	fn iter_formats(&self) -> impl Iterator<Item = SysResult<co::CF>> + '_ {
+		HclipboardIterformats::new(self)
+	}
+
+	/// Retrieves the RTF text in the clipboard, stored in the registered
+	/// `"Rich Text Format"`.
+	#[must_use]
+	fn rtf(&self) -> SysResult<String> {
+		let cf_rtf = RegisterClipboardFormat("Rich Text Format")?;
+		let data = self.GetClipboardData(cf_rtf)?;
+		let len = data.iter().position(|b| *b == 0).unwrap_or(data.len());
+		Ok(String::from_utf8_lossy(&data[..len]).into_owned())
+	}
+
+	/// Places an image into the clipboard, given the contents of a `.bmp`
+	/// file.
+	///
+	/// The data is stored as [`CF::DIBV5`](crate::co::CF::DIBV5) if the file
+	/// has a `BITMAPV5HEADER`, otherwise as [`CF::DIB`](crate::co::CF::DIB).
+	/// The system synthesizes the other formats.
+	///
+	/// Returns [`ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA) if the
+	/// data is not a `.bmp` file.
+	fn set_bitmap(&self, bmp: &[u8]) -> SysResult<()> {
+		if bmp.len() < BMP_FILE_HEADER_SZ + 4 || &bmp[..2] != b"BM" {
+			return Err(co::ERROR::INVALID_DATA);
+		}
+		let dib = &bmp[BMP_FILE_HEADER_SZ..];
+		let format = if read_u32(dib, 0)? as usize == BITMAPV5HEADER_SZ {
+			co::CF::DIBV5
+		} else {
+			co::CF::DIB
+		};
+		self.SetClipboardData(format, dib)
+	}
+
+	/// Places a list of files into the clipboard, as
+	/// [`CF::HDROP`](crate::co::CF::HDROP), so they can be pasted in Windows
+	/// Explorer.
+	///
+	/// The data is serialized with
+	/// [`DropFiles::serialize`](crate::DropFiles::serialize).
+	fn set_files(&self, files: &[impl AsRef<str>]) -> SysResult<()> {
+		self.SetClipboardData(co::CF::HDROP, &DropFiles::new(files).serialize())
+	}
+
+	/// Places an HTML fragment into the clipboard, in the registered
+	/// `"HTML Format"`.
+	///
+	/// The data is serialized with
+	/// [`HtmlFormat::serialize`](crate::HtmlFormat::serialize).
+	fn set_html(&self, html: &HtmlFormat) -> SysResult<()> {
+		let cf_html = RegisterClipboardFormat("HTML Format")?;
+		self.SetClipboardData(cf_html, &html.serialize())
+	}
+
+	/// Places RTF text into the clipboard, in the registered
+	/// `"Rich Text Format"`.
+	fn set_rtf(&self, rtf: &str) -> SysResult<()> {
+		let cf_rtf = RegisterClipboardFormat("Rich Text Format")?;
+		let data = rtf
+			.bytes()
+			.chain(std::iter::once(0)) // null-terminated
+			.collect::<Vec<_>>();
+		self.SetClipboardData(cf_rtf, &data)
+	}
+
+	/// Places text into the clipboard, as
+	/// [`CF::UNICODETEXT`](crate::co::CF::UNICODETEXT). The system synthesizes
+	/// the [`CF::TEXT`](crate::co::CF::TEXT) and
+	/// [`CF::OEMTEXT`](crate::co::CF::OEMTEXT) formats.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let hwnd: w::HWND; // initialized somewhere
+	/// # let hwnd = w::HWND::NULL;
+	///
+	/// let hclip = hwnd.OpenClipboard()?;
+	/// hclip.EmptyClipboard()?;
+	/// hclip.set_text("Hello, world")?;
+	/// # w::SysResult::Ok(())
+	/// ```
+	fn set_text(&self, text: &str) -> SysResult<()> {
+		let data = text
+			.encode_utf16()
+			.chain(std::iter::once(0)) // null-terminated
+			.flat_map(|ch| ch.to_le_bytes())
+			.collect::<Vec<_>>();
+		self.SetClipboardData(co::CF::UNICODETEXT, &data)
+	}
+
+	/// Retrieves the text in the clipboard, stored as
+	/// [`CF::UNICODETEXT`](crate::co::CF::UNICODETEXT).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hclip = w::HWND::NULL.OpenClipboard()?;
+	/// if w::IsClipboardFormatAvailable(co::CF::UNICODETEXT) {
+	///     println!("{}", hclip.text()?);
+	/// }
+	/// # w::SysResult::Ok(())
+	/// ```
+	#[must_use]
+	fn text(&self) -> SysResult<String> {
+		let data = self.GetClipboardData(co::CF::UNICODETEXT)?;
+		let wchars = data
+			.chunks_exact(2)
+			.map(|b| u16::from_le_bytes([b[0], b[1]]))
+			.take_while(|ch| *ch != 0)
+			.collect::<Vec<_>>();
+		Ok(WString::from_wchars_slice(&wchars).to_string())
+	}
+
 	/// [`EmptyClipboard`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-emptyclipboard)
 	/// function.
 	fn EmptyClipboard(&self) -> SysResult<()> {
@@ -108,4 +301,75 @@ pub trait user_Hclipboard: Handle {
 		})?;
 		Ok(())
 	}
+
+	/// [`SetClipboardData`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setclipboarddata)
+	/// function, passing a null data handle to enable
+	/// [delayed rendering](https://learn.microsoft.com/en-us/windows/win32/dataxchg/clipboard-operations#delayed-rendering).
+	///
+	/// The actual data must be provided later, when the clipboard owner window
+	/// receives [`wm::RenderFormat`](crate::msg::wm::RenderFormat) or
+	/// [`wm::RenderAllFormats`](crate::msg::wm::RenderAllFormats).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hwnd: w::HWND; // initialized somewhere
+	/// # let hwnd = w::HWND::NULL;
+	///
+	/// let hclip = hwnd.OpenClipboard()?;
+	/// hclip.EmptyClipboard()?;
+	/// hclip.SetClipboardDataDelayed(co::CF::UNICODETEXT)?;
+	/// # w::SysResult::Ok(())
+	/// ```
+	fn SetClipboardDataDelayed(&self, format: co::CF) -> SysResult<()> {
+		SetLastError(co::ERROR::SUCCESS);
+		unsafe { ffi::SetClipboardData(format.raw() as _, std::ptr::null_mut()) };
+		match GetLastError() {
+			co::ERROR::SUCCESS => Ok(()), // a null return is expected
+			err => Err(err),
+		}
+	}
+}
+
+const BMP_FILE_HEADER_SZ: usize = 14; // sizeof(BITMAPFILEHEADER)
+const BITMAPINFOHEADER_SZ: usize = 40;
+const BITMAPV5HEADER_SZ: usize = 124;
+
+fn read_u32(data: &[u8], off: usize) -> SysResult<u32> {
+	data.get(off..off + 4)
+		.map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+		.ok_or(co::ERROR::INVALID_DATA)
+}
+
+/// Prepends a `BITMAPFILEHEADER` to a packed DIB, computing the offset to the
+/// pixel bits.
+fn dib_to_bmp(dib: &[u8]) -> SysResult<Vec<u8>> {
+	let header_sz = read_u32(dib, 0)? as usize;
+	if header_sz < BITMAPINFOHEADER_SZ {
+		return Err(co::ERROR::INVALID_DATA); // BITMAPCOREHEADER is not supported
+	}
+	let bit_count = read_u32(dib, 12)? >> 16; // biBitCount is the high word
+	let compression = read_u32(dib, 16)?;
+	let clr_used = read_u32(dib, 32)? as usize;
+
+	let masks_sz = match (header_sz, compression) {
+		(BITMAPINFOHEADER_SZ, 3) => 12, // BI_BITFIELDS
+		(BITMAPINFOHEADER_SZ, 6) => 16, // BI_ALPHABITFIELDS
+		_ => 0,                         // masks are part of the larger headers
+	};
+	let num_colors = match (clr_used, bit_count) {
+		(0, 1..=8) => 1 << bit_count,
+		(n, _) => n,
+	};
+	let off_bits = BMP_FILE_HEADER_SZ + header_sz + masks_sz + num_colors * 4;
+
+	let mut bmp = Vec::<u8>::with_capacity(BMP_FILE_HEADER_SZ + dib.len());
+	bmp.extend_from_slice(b"BM");
+	bmp.extend_from_slice(&((BMP_FILE_HEADER_SZ + dib.len()) as u32).to_le_bytes()); // bfSize
+	bmp.extend_from_slice(&0u32.to_le_bytes()); // bfReserved1 and bfReserved2
+	bmp.extend_from_slice(&(off_bits as u32).to_le_bytes());
+	bmp.extend_from_slice(dib);
+	Ok(bmp)
 }
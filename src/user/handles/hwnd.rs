@@ -85,6 +85,18 @@ pub trait user_Hwnd: Handle {
 		unsafe { co::WS_EX::from_raw(self.GetWindowLongPtr(co::GWLP::EXSTYLE) as _) }
 	}
 
+	/// [`AddClipboardFormatListener`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-addclipboardformatlistener)
+	/// function.
+	///
+	/// After this call, the window receives a
+	/// [`wm::ClipboardUpdate`](crate::msg::wm::ClipboardUpdate) message
+	/// whenever the contents of the clipboard change, until
+	/// [`HWND::RemoveClipboardFormatListener`](crate::prelude::user_Hwnd::RemoveClipboardFormatListener)
+	/// is called.
+	fn AddClipboardFormatListener(&self) -> SysResult<()> {
+		bool_to_sysresult(unsafe { ffi::AddClipboardFormatListener(self.ptr()) })
+	}
+
 	/// [`ArrangeIconicWindows`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-arrangeiconicwindows)
 	/// function.
 	fn ArrangeIconicWindows(&self) -> SysResult<u32> {
@@ -1265,6 +1277,12 @@ pub trait user_Hwnd: Handle {
 		})
 	}
 
+	/// [`RemoveClipboardFormatListener`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-removeclipboardformatlistener)
+	/// function.
+	fn RemoveClipboardFormatListener(&self) -> SysResult<()> {
+		bool_to_sysresult(unsafe { ffi::RemoveClipboardFormatListener(self.ptr()) })
+	}
+
 	/// [`ScreenToClient`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-screentoclient)
 	/// function.
 	///
@@ -54,6 +54,44 @@ impl<'a> EnumdisplaydevicesIter<'a> {
 	}
 }
 
+pub(in crate::user) struct HclipboardIterformats<'a, H>
+where
+	H: user_Hclipboard,
+{
+	_hclip: &'a H,
+	current: u32,
+}
+
+impl<'a, H> Iterator for HclipboardIterformats<'a, H>
+where
+	H: user_Hclipboard,
+{
+	type Item = SysResult<co::CF>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		match unsafe { ffi::EnumClipboardFormats(self.current) } {
+			0 => match GetLastError() {
+				co::ERROR::SUCCESS => None, // no more formats
+				err => Some(Err(err)),      // actual error
+			},
+			format => {
+				self.current = format;
+				Some(Ok(unsafe { co::CF::from_raw(format as _) }))
+			},
+		}
+	}
+}
+
+impl<'a, H> HclipboardIterformats<'a, H>
+where
+	H: user_Hclipboard,
+{
+	#[must_use]
+	pub(in crate::user) fn new(hclip: &'a H) -> Self {
+		Self { _hclip: hclip, current: 0 }
+	}
+}
+
 pub(in crate::user) struct HmenuIteritems<'a, H>
 where
 	H: user_Hmenu,
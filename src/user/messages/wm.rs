@@ -156,6 +156,10 @@ pub_struct_msg_empty_handleable! { ChildActivate: co::WM::CHILDACTIVATE;
 	/// [`WM_CHILDACTIVATE`](https://learn.microsoft.com/en-us/windows/win32/winmsg/wm-childactivate)
 }
 
+pub_struct_msg_empty_handleable! { ClipboardUpdate: co::WM::CLIPBOARDUPDATE;
+	/// [`WM_CLIPBOARDUPDATE`](https://learn.microsoft.com/en-us/windows/win32/dataxchg/wm-clipboardupdate)
+}
+
 pub_struct_msg_empty_handleable! { Close: co::WM::CLOSE;
 	/// [`WM_CLOSE`](https://learn.microsoft.com/en-us/windows/win32/winmsg/wm-close)
 }
@@ -310,6 +314,10 @@ pub_struct_msg_empty_handleable! { Destroy: co::WM::DESTROY;
 	/// [`WM_DESTROY`](https://learn.microsoft.com/en-us/windows/win32/winmsg/wm-destroy)
 }
 
+pub_struct_msg_empty_handleable! { DestroyClipboard: co::WM::DESTROYCLIPBOARD;
+	/// [`WM_DESTROYCLIPBOARD`](https://learn.microsoft.com/en-us/windows/win32/dataxchg/wm-destroyclipboard)
+}
+
 /// [`WM_DEVICECHANGE`](https://learn.microsoft.com/en-us/windows/win32/devio/wm-devicechange)
 /// message parameters.
 ///
@@ -344,6 +352,42 @@ impl<'a> MsgSendRecv for DeviceChange<'a> {
 	}
 }
 
+/// [`WM_DPICHANGED`](https://learn.microsoft.com/en-us/windows/win32/hidpi/wm-dpichanged)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct DpiChanged<'a> {
+	pub dpi_x: u16,
+	pub dpi_y: u16,
+	pub suggested_rect: &'a RECT,
+}
+
+impl<'a> MsgSend for DpiChanged<'a> {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::WM::DPICHANGED,
+			wparam: MAKEDWORD(self.dpi_x, self.dpi_y) as _,
+			lparam: self.suggested_rect as *const _ as _,
+		}
+	}
+}
+
+impl<'a> MsgSendRecv for DpiChanged<'a> {
+	unsafe fn from_generic_wm(p: WndMsg) -> Self {
+		Self {
+			dpi_x: LOWORD(p.wparam as _),
+			dpi_y: HIWORD(p.wparam as _),
+			suggested_rect: &*(p.lparam as *const _),
+		}
+	}
+}
+
 /// [`WM_ENABLE`](https://learn.microsoft.com/en-us/windows/win32/winmsg/wm-enable)
 /// message parameters.
 ///
@@ -1509,6 +1553,40 @@ pub_struct_msg_button! { RButtonUp: co::WM::RBUTTONUP;
 	/// [`WM_RBUTTONUP`](https://learn.microsoft.com/en-us/windows/win32/inputdev/wm-rbuttonup)
 }
 
+pub_struct_msg_empty_handleable! { RenderAllFormats: co::WM::RENDERALLFORMATS;
+	/// [`WM_RENDERALLFORMATS`](https://learn.microsoft.com/en-us/windows/win32/dataxchg/wm-renderallformats)
+}
+
+/// [`WM_RENDERFORMAT`](https://learn.microsoft.com/en-us/windows/win32/dataxchg/wm-renderformat)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct RenderFormat {
+	pub format: co::CF,
+}
+
+impl MsgSend for RenderFormat {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::WM::RENDERFORMAT,
+			wparam: self.format.raw() as _,
+			lparam: 0,
+		}
+	}
+}
+
+impl MsgSendRecv for RenderFormat {
+	unsafe fn from_generic_wm(p: WndMsg) -> Self {
+		Self { format: co::CF::from_raw(p.wparam as _) }
+	}
+}
+
 /// [`WM_SETCURSOR`](https://learn.microsoft.com/en-us/windows/win32/menurc/wm-setcursor)
 /// message parameters.
 ///
@@ -1697,6 +1775,40 @@ impl MsgSendRecv for SetText {
 	}
 }
 
+/// [`WM_SETTINGCHANGE`](https://learn.microsoft.com/en-us/windows/win32/winmsg/wm-settingchange)
+/// message parameters.
+///
+/// Return type: `()`.
+pub struct SettingChange {
+	pub flag: co::SPI,
+	pub area: *const u16, // can't be WString because this message can be received
+}
+
+impl MsgSend for SettingChange {
+	type RetType = ();
+
+	unsafe fn isize_to_ret(&self, _: isize) -> Self::RetType {
+		()
+	}
+
+	fn as_generic_wm(&mut self) -> WndMsg {
+		WndMsg {
+			msg_id: co::WM::SETTINGCHANGE,
+			wparam: self.flag.raw() as _,
+			lparam: self.area as _,
+		}
+	}
+}
+
+impl MsgSendRecv for SettingChange {
+	unsafe fn from_generic_wm(p: WndMsg) -> Self {
+		Self {
+			flag: co::SPI::from_raw(p.wparam as _),
+			area: p.lparam as _,
+		}
+	}
+}
+
 /// [`WM_SHOWWINDOW`](https://learn.microsoft.com/en-us/windows/win32/winmsg/wm-showwindow)
 /// message parameters.
 ///
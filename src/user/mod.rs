@@ -7,6 +7,7 @@ mod handles;
 mod msg_traits;
 mod proc;
 mod structs;
+mod utilities;
 
 pub mod co;
 pub(in crate::user) mod ffi;
@@ -21,6 +22,7 @@ pub mod decl {
 	pub use super::funcs::*;
 	pub use super::handles::decl::*;
 	pub use super::structs::*;
+	pub use super::utilities::*;
 }
 
 pub mod traits {
@@ -0,0 +1,345 @@
+use crate::co;
+use crate::decl::*;
+
+/// Owned, pure-Rust representation of the
+/// [HTML clipboard format](https://learn.microsoft.com/en-us/windows/win32/dataxchg/html-clipboard-format),
+/// registered as `"HTML Format"`.
+///
+/// The format consists of an UTF-8 HTML document preceded by a textual header,
+/// which stores the byte offsets of the document and of the fragment being
+/// copied. These offsets are computed automatically by
+/// [`serialize`](crate::HtmlFormat::serialize).
+///
+/// # Examples
+///
+/// ```
+/// use winsafe::{self as w, prelude::*};
+///
+/// let html = w::HtmlFormat::new("<b>Hello</b>, world");
+/// let raw_bytes = html.serialize();
+///
+/// let parsed = w::HtmlFormat::parse(&raw_bytes)?;
+/// assert_eq!(parsed.fragment, "<b>Hello</b>, world");
+/// # w::SysResult::Ok(())
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct HtmlFormat {
+	/// The HTML fragment being copied.
+	pub fragment: String,
+	/// Optional URL of the document the fragment came from.
+	pub source_url: Option<String>,
+}
+
+impl HtmlFormat {
+	const START_FRAGMENT: &'static str = "<!--StartFragment-->";
+	const END_FRAGMENT: &'static str = "<!--EndFragment-->";
+
+	/// Creates a new `HtmlFormat` with the given fragment and no source URL.
+	#[must_use]
+	pub fn new(fragment: &str) -> Self {
+		Self {
+			fragment: fragment.to_owned(),
+			source_url: None,
+		}
+	}
+
+	/// Parses the raw contents of an HTML clipboard data block.
+	///
+	/// Header lines which are not `name:value` pairs are ignored.
+	///
+	/// Returns [`ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA) if the
+	/// header is malformed, or if the fragment offsets are out of bounds.
+	#[must_use]
+	pub fn parse(data: &[u8]) -> SysResult<Self> {
+		let data = match data.iter().position(|b| *b == 0) {
+			Some(idx) => &data[..idx], // ignore the terminating null
+			None => data,
+		};
+
+		let mut start_fragment = None;
+		let mut end_fragment = None;
+		let mut source_url = None;
+		let mut off = 0;
+
+		while off < data.len() && data[off] != b'<' {
+			let line_end = data[off..]
+				.iter()
+				.position(|b| *b == b'\n' || *b == b'\r')
+				.map(|idx| off + idx)
+				.unwrap_or(data.len());
+			let line =
+				std::str::from_utf8(&data[off..line_end]).map_err(|_| co::ERROR::INVALID_DATA)?;
+			match line.split_once(':') {
+				Some(("StartFragment", val)) => start_fragment = Some(Self::parse_offset(val)?),
+				Some(("EndFragment", val)) => end_fragment = Some(Self::parse_offset(val)?),
+				Some(("SourceURL", val)) => source_url = Some(val.to_owned()),
+				_ => {}, // other header fields and malformed lines are ignored
+			}
+
+			off = line_end;
+			while off < data.len() && (data[off] == b'\n' || data[off] == b'\r') {
+				off += 1;
+			}
+		}
+
+		let start_fragment = start_fragment.ok_or(co::ERROR::INVALID_DATA)?;
+		let end_fragment = end_fragment.ok_or(co::ERROR::INVALID_DATA)?;
+		if start_fragment > end_fragment || end_fragment > data.len() {
+			return Err(co::ERROR::INVALID_DATA);
+		}
+
+		Ok(Self {
+			fragment: std::str::from_utf8(&data[start_fragment..end_fragment])
+				.map_err(|_| co::ERROR::INVALID_DATA)?
+				.to_owned(),
+			source_url,
+		})
+	}
+
+	/// Serializes the fragment into the raw contents of an HTML clipboard data
+	/// block, wrapping it into a minimal HTML document.
+	///
+	/// The returned bytes are null-terminated.
+	#[must_use]
+	pub fn serialize(&self) -> Vec<u8> {
+		// Offsets are written with a fixed width, so the header length is known
+		// before they are computed.
+		let header = |start_html: usize, end_html: usize, start_frag: usize, end_frag: usize| {
+			let mut text = format!(
+				"Version:0.9\r\n\
+				StartHTML:{:010}\r\n\
+				EndHTML:{:010}\r\n\
+				StartFragment:{:010}\r\n\
+				EndFragment:{:010}\r\n",
+				start_html, end_html, start_frag, end_frag,
+			);
+			if let Some(source_url) = &self.source_url {
+				text.push_str(&format!("SourceURL:{}\r\n", source_url));
+			}
+			text
+		};
+
+		let prefix = format!("<html>\r\n<body>\r\n{}", Self::START_FRAGMENT);
+		let suffix = format!("{}\r\n</body>\r\n</html>", Self::END_FRAGMENT);
+
+		let start_html = header(0, 0, 0, 0).len();
+		let start_fragment = start_html + prefix.len();
+		let end_fragment = start_fragment + self.fragment.len();
+		let end_html = end_fragment + suffix.len();
+
+		let mut buf = Vec::<u8>::with_capacity(end_html + 1);
+		buf.extend_from_slice(
+			header(start_html, end_html, start_fragment, end_fragment).as_bytes(),
+		);
+		buf.extend_from_slice(prefix.as_bytes());
+		buf.extend_from_slice(self.fragment.as_bytes());
+		buf.extend_from_slice(suffix.as_bytes());
+		buf.push(0x00); // terminating null
+		buf
+	}
+
+	fn parse_offset(val: &str) -> SysResult<usize> {
+		val.trim()
+			.parse::<usize>()
+			.map_err(|_| co::ERROR::INVALID_DATA)
+	}
+}
+
+/// Owned, pure-Rust representation of a
+/// [`DROPFILES`](https://learn.microsoft.com/en-us/windows/win32/api/shlobj_core/ns-shlobj_core-dropfiles)
+/// block, which is the content of the
+/// [`CF::HDROP`](crate::co::CF::HDROP) clipboard format.
+///
+/// The block consists of a fixed-size header followed by a list of
+/// null-terminated file paths, ending with an additional null.
+///
+/// # Examples
+///
+/// ```
+/// use winsafe::{self as w, prelude::*};
+///
+/// let drop_files = w::DropFiles::new(&["C:\\Temp\\a.txt", "C:\\Temp\\b.txt"]);
+/// let raw_bytes = drop_files.serialize();
+///
+/// let parsed = w::DropFiles::parse(&raw_bytes)?;
+/// assert_eq!(parsed.files.len(), 2);
+/// # w::SysResult::Ok(())
+/// ```
+#[derive(Clone, Default)]
+pub struct DropFiles {
+	/// Full paths of the files.
+	pub files: Vec<String>,
+	/// Drop point, in client coordinates of the target window, or in screen
+	/// coordinates if `non_client` is `true`.
+	pub pt: POINT,
+	/// Whether `pt` is in the non-client area of the window.
+	pub non_client: bool,
+}
+
+impl DropFiles {
+	const HEADER_SZ: usize = 20; // sizeof(DROPFILES)
+
+	/// Creates a new `DropFiles` with the given files, and a zero drop point.
+	#[must_use]
+	pub fn new(files: &[impl AsRef<str>]) -> Self {
+		Self {
+			files: files.iter().map(|f| f.as_ref().to_owned()).collect(),
+			..Default::default()
+		}
+	}
+
+	/// Parses the raw contents of a `DROPFILES` block.
+	///
+	/// Both wide and ANSI file lists are accepted. ANSI names are decoded as
+	/// UTF-8, not with the system code page, so this is lossy: any non-ASCII
+	/// character is either misread or replaced with `U+FFFD`. Only the wide
+	/// list, which is always written by
+	/// [`serialize`](crate::DropFiles::serialize), round-trips exactly.
+	///
+	/// Returns [`ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA) if the
+	/// data is malformed.
+	#[must_use]
+	pub fn parse(data: &[u8]) -> SysResult<Self> {
+		let read_u32 = |off: usize| {
+			data.get(off..off + 4)
+				.map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+				.ok_or(co::ERROR::INVALID_DATA)
+		};
+
+		let files_off = read_u32(0)? as usize;
+		let pt = POINT::new(read_u32(4)? as i32, read_u32(8)? as i32);
+		let non_client = read_u32(12)? != 0;
+		let is_wide = read_u32(16)? != 0;
+		let list = data.get(files_off..).ok_or(co::ERROR::INVALID_DATA)?;
+
+		let files = if is_wide {
+			list.chunks_exact(2)
+				.map(|b| u16::from_le_bytes([b[0], b[1]]))
+				.collect::<Vec<_>>()
+				.split(|ch| *ch == 0)
+				.take_while(|name| !name.is_empty()) // two consecutive nulls end the list
+				.map(String::from_utf16_lossy)
+				.collect()
+		} else {
+			list.split(|ch| *ch == 0)
+				.take_while(|name| !name.is_empty())
+				.map(|name| String::from_utf8_lossy(name).into_owned())
+				.collect()
+		};
+
+		Ok(Self { files, pt, non_client })
+	}
+
+	/// Serializes the files into the raw contents of a `DROPFILES` block, with
+	/// a wide file list.
+	#[must_use]
+	pub fn serialize(&self) -> Vec<u8> {
+		let mut buf = Vec::<u8>::with_capacity(
+			Self::HEADER_SZ + self.files.iter().map(|f| (f.len() + 1) * 2).sum::<usize>() + 2,
+		);
+
+		buf.extend_from_slice(&(Self::HEADER_SZ as u32).to_le_bytes()); // pFiles
+		buf.extend_from_slice(&self.pt.x.to_le_bytes());
+		buf.extend_from_slice(&self.pt.y.to_le_bytes());
+		buf.extend_from_slice(&(self.non_client as u32).to_le_bytes()); // fNC
+		buf.extend_from_slice(&1u32.to_le_bytes()); // fWide
+
+		for file in self.files.iter() {
+			for ch in file.encode_utf16().chain(std::iter::once(0)) {
+				buf.extend_from_slice(&ch.to_le_bytes());
+			}
+		}
+		buf.extend_from_slice(&0u16.to_le_bytes()); // list terminator
+		buf
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn html_round_trip() {
+		let html = HtmlFormat {
+			fragment: "<p>Olá, <i>mundo</i></p>".to_owned(),
+			source_url: Some("https://example.com/a?b=c".to_owned()),
+		};
+		let bytes = html.serialize();
+		assert_eq!(bytes.last(), Some(&0x00));
+		assert!(HtmlFormat::parse(&bytes).unwrap() == html);
+	}
+
+	#[test]
+	fn html_offsets() {
+		let bytes = HtmlFormat::new("<b>x</b>").serialize();
+		let text = std::str::from_utf8(&bytes[..bytes.len() - 1]).unwrap();
+		let offset = |name: &str| {
+			let line = text.lines().find(|line| line.starts_with(name)).unwrap();
+			line[name.len() + 1..].parse::<usize>().unwrap()
+		};
+		assert!(text[offset("StartHTML")..].starts_with("<html>"));
+		assert_eq!(offset("EndHTML"), text.len());
+		assert_eq!(&text[offset("StartFragment")..offset("EndFragment")], "<b>x</b>");
+	}
+
+	#[test]
+	fn html_parse_foreign_header() {
+		let text = "Version:1.0\n\
+			Some line without a colon\n\
+			StartHTML:-1\n\
+			EndHTML:-1\n\
+			StartFragment:0000000142\n\
+			EndFragment:0000000145\n\
+			<html><body><!--StartFragment-->abc<!--EndFragment--></body></html>";
+		let html = HtmlFormat::parse(text.as_bytes()).unwrap();
+		assert_eq!(html.fragment, "abc");
+		assert_eq!(html.source_url, None);
+	}
+
+	#[test]
+	fn html_parse_malformed() {
+		let missing_end = "Version:0.9\r\nStartFragment:10\r\n<html></html>";
+		assert!(HtmlFormat::parse(missing_end.as_bytes()) == Err(co::ERROR::INVALID_DATA));
+
+		let out_of_bounds = "StartFragment:10\r\nEndFragment:999\r\n<html></html>";
+		assert!(HtmlFormat::parse(out_of_bounds.as_bytes()) == Err(co::ERROR::INVALID_DATA));
+	}
+
+	#[test]
+	fn drop_files_round_trip() {
+		let drop_files = DropFiles {
+			files: vec!["C:\\Temp\\a.txt".to_owned(), "C:\\Temp\\ção.txt".to_owned()],
+			pt: POINT::new(10, -20),
+			non_client: true,
+		};
+		let bytes = drop_files.serialize();
+		assert_eq!(&bytes[..4], &[0x14, 0x00, 0x00, 0x00]); // pFiles
+		assert_eq!(&bytes[bytes.len() - 4..], &[0x00; 4]); // last null and list terminator
+
+		let parsed = DropFiles::parse(&bytes).unwrap();
+		assert_eq!(parsed.files, drop_files.files);
+		assert!(parsed.pt == drop_files.pt);
+		assert!(parsed.non_client);
+	}
+
+	#[test]
+	fn drop_files_parse_ansi() {
+		let mut bytes = Vec::<u8>::new();
+		for v in [20u32, 0, 0, 0, 0] {
+			bytes.extend_from_slice(&v.to_le_bytes()); // fWide is false
+		}
+		bytes.extend_from_slice(b"C:\\a.txt\0C:\\\xe7.txt\0\0"); // 0xe7 is "ç" in Windows-1252
+
+		let parsed = DropFiles::parse(&bytes).unwrap();
+		assert_eq!(parsed.files, ["C:\\a.txt", "C:\\\u{fffd}.txt"]);
+	}
+
+	#[test]
+	fn drop_files_parse_malformed() {
+		assert!(DropFiles::parse(&[0x14, 0x00, 0x00]).is_err());
+
+		let mut bytes = DropFiles::new(&["a"]).serialize();
+		bytes[0] = 0xff; // pFiles out of bounds
+		assert!(DropFiles::parse(&bytes).is_err());
+	}
+}
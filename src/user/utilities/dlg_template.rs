@@ -0,0 +1,440 @@
+use crate::co;
+use crate::decl::*;
+
+/// Font of a [`DlgTemplate`](crate::DlgTemplate), used by the dialog and all
+/// its controls.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DlgFont {
+	/// Point size of the font.
+	///
+	/// Defaults to `8`.
+	pub point_size: u16,
+	/// Weight of the font, from `0` to `1000`.
+	///
+	/// Defaults to `400` (normal).
+	pub weight: u16,
+	/// Whether the font is italic.
+	///
+	/// Defaults to `false`.
+	pub italic: bool,
+	/// Character set of the font.
+	///
+	/// Defaults to `1` (`DEFAULT_CHARSET`).
+	pub charset: u8,
+	/// Name of the typeface.
+	///
+	/// Defaults to `"MS Shell Dlg"`, which is mapped to the system font.
+	pub face_name: String,
+}
+
+impl Default for DlgFont {
+	fn default() -> Self {
+		Self {
+			point_size: 8,
+			weight: 400,
+			italic: false,
+			charset: 1,
+			face_name: "MS Shell Dlg".to_owned(),
+		}
+	}
+}
+
+/// A control of a [`DlgTemplate`](crate::DlgTemplate), serialized as a
+/// [`DLGITEMTEMPLATEEX`](https://learn.microsoft.com/en-us/windows/win32/dlgbox/dlgitemtemplateex)
+/// block.
+#[derive(Clone)]
+pub struct DlgItemTemplate {
+	/// Help context identifier.
+	///
+	/// Defaults to `0`.
+	pub help_id: u32,
+	/// Extended window styles.
+	///
+	/// Defaults to `WS_EX::NoValue`.
+	pub ex_style: co::WS_EX,
+	/// Window styles, which can be combined with the specific control styles.
+	///
+	/// Defaults to `WS::CHILD | WS::VISIBLE`.
+	pub style: co::WS,
+	/// Position, in dialog units.
+	///
+	/// Defaults to `(0, 0)`.
+	pub pos: (i16, i16),
+	/// Size, in dialog units.
+	///
+	/// Defaults to `(0, 0)`.
+	pub size: (i16, i16),
+	/// Control ID.
+	///
+	/// Defaults to `0`.
+	pub id: u32,
+	/// Window class name. The predefined classes `"BUTTON"`, `"EDIT"`,
+	/// `"STATIC"`, `"LISTBOX"`, `"SCROLLBAR"` and `"COMBOBOX"` are written as
+	/// ordinals, like the resource compiler does.
+	///
+	/// Defaults to `"STATIC"`.
+	pub class_name: String,
+	/// Text of the control, or the ID of a resource, like an icon of a static
+	/// control.
+	///
+	/// Defaults to an empty string.
+	pub text: IdStr,
+	/// Creation data passed to the control in `WM_CREATE`.
+	///
+	/// Defaults to empty.
+	pub creation_data: Vec<u8>,
+}
+
+impl Default for DlgItemTemplate {
+	fn default() -> Self {
+		Self {
+			help_id: 0,
+			ex_style: co::WS_EX::NoValue,
+			style: co::WS::CHILD | co::WS::VISIBLE,
+			pos: (0, 0),
+			size: (0, 0),
+			id: 0,
+			class_name: "STATIC".to_owned(),
+			text: IdStr::from_str(""),
+			creation_data: Vec::new(),
+		}
+	}
+}
+
+impl DlgItemTemplate {
+	/// Creates a new control with the given class name, text, ID, position,
+	/// size and styles, which are added to `WS::CHILD | WS::VISIBLE`; the other
+	/// fields are set to their defaults.
+	#[must_use]
+	pub fn new(
+		class_name: &str,
+		text: &str,
+		id: u16,
+		pos: (i16, i16),
+		size: (i16, i16),
+		style: impl Into<co::WS>,
+	) -> Self {
+		Self {
+			style: co::WS::CHILD | co::WS::VISIBLE | style.into(),
+			pos,
+			size,
+			id: id as _,
+			class_name: class_name.to_owned(),
+			text: IdStr::from_str(text),
+			..Default::default()
+		}
+	}
+
+	/// Returns the ordinal of a predefined window class, if any.
+	#[must_use]
+	fn class_ordinal(class_name: &str) -> Option<u16> {
+		const CLASSES: [(&str, u16); 6] = [
+			("BUTTON", 0x0080),
+			("EDIT", 0x0081),
+			("STATIC", 0x0082),
+			("LISTBOX", 0x0083),
+			("SCROLLBAR", 0x0084),
+			("COMBOBOX", 0x0085),
+		];
+		CLASSES
+			.iter()
+			.find(|(name, _)| name.eq_ignore_ascii_case(class_name))
+			.map(|(_, ord)| *ord)
+	}
+
+	fn write(&self, buf: &mut Vec<u8>) -> SysResult<()> {
+		align4(buf);
+		write_u32(buf, self.help_id);
+		write_u32(buf, self.ex_style.raw());
+		write_u32(buf, self.style.raw());
+		for v in [self.pos.0, self.pos.1, self.size.0, self.size.1] {
+			buf.extend_from_slice(&v.to_le_bytes());
+		}
+		write_u32(buf, self.id);
+		match Self::class_ordinal(&self.class_name) {
+			Some(ord) => write_sz_or_ord(buf, &IdStr::Id(ord)),
+			None => write_str(buf, &self.class_name),
+		}
+		write_sz_or_ord(buf, &self.text);
+		write_u16(buf, len_u16(self.creation_data.len())?);
+		buf.extend_from_slice(&self.creation_data);
+		Ok(())
+	}
+}
+
+/// Owned, pure-Rust representation of a dialog box, which is serialized as a
+/// [`DLGTEMPLATEEX`](https://learn.microsoft.com/en-us/windows/win32/dlgbox/dlgtemplateex)
+/// block followed by its controls.
+///
+/// The serialized block has the same layout of an
+/// [`RT::DIALOG`](crate::co::RT::DIALOG) resource, so it can be used to create
+/// dialogs without a resource compiler.
+///
+/// # Examples
+///
+/// A dialog with a label, a text box and an OK button, shown as a modal:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co, gui};
+///
+/// let mut tmpl = w::DlgTemplate::new(
+///     "Enter your name",
+///     (0, 0),
+///     (160, 60),
+///     co::WS::POPUP | co::WS::CAPTION | co::WS::SYSMENU | co::DS::MODALFRAME.into(),
+/// );
+/// tmpl.items.push(w::DlgItemTemplate::new(
+///     "STATIC", "Name:", 1001, (8, 10), (30, 8), co::SS::LEFT));
+/// tmpl.items.push(w::DlgItemTemplate::new(
+///     "EDIT", "", 1002, (40, 8), (110, 12), co::ES::AUTOHSCROLL | co::WS::BORDER.into()));
+/// tmpl.items.push(w::DlgItemTemplate::new(
+///     "BUTTON", "OK", co::DLGID::OK.raw(), (100, 40), (50, 14), co::BS::DEFPUSHBUTTON));
+///
+/// let wnd: gui::WindowMain; // initialized somewhere
+/// # let wnd = gui::WindowMain::new(gui::WindowMainOpts::default());
+/// let dlg = gui::WindowModal::new_dlg_template(&tmpl);
+/// dlg.show_modal(&wnd)?;
+/// # w::AnyResult::Ok(())
+/// ```
+#[derive(Clone)]
+pub struct DlgTemplate {
+	/// Help context identifier.
+	///
+	/// Defaults to `0`.
+	pub help_id: u32,
+	/// Extended window styles.
+	///
+	/// Defaults to `WS_EX::NoValue`.
+	pub ex_style: co::WS_EX,
+	/// Window styles, which can be combined with
+	/// [`DS`](crate::co::DS) styles.
+	///
+	/// [`DS::SETFONT`](crate::co::DS::SETFONT) is automatically added when a
+	/// [`font`](crate::DlgTemplate::font) is set, and removed otherwise.
+	///
+	/// Defaults to `WS::POPUP | WS::CAPTION | WS::SYSMENU | DS::MODALFRAME`.
+	pub style: co::WS,
+	/// Position, in dialog units.
+	///
+	/// Defaults to `(0, 0)`.
+	pub pos: (i16, i16),
+	/// Size, in dialog units.
+	///
+	/// Defaults to `(0, 0)`.
+	pub size: (i16, i16),
+	/// Menu resource, if any.
+	///
+	/// Defaults to `None`.
+	pub menu: Option<IdStr>,
+	/// Window class, if other than the predefined dialog class.
+	///
+	/// Defaults to `None`.
+	pub class_name: Option<IdStr>,
+	/// Title of the dialog.
+	///
+	/// Defaults to an empty string.
+	pub title: String,
+	/// Font of the dialog, if any.
+	///
+	/// Defaults to `Some(DlgFont::default())`.
+	pub font: Option<DlgFont>,
+	/// The controls of the dialog, in tab order.
+	///
+	/// Defaults to empty.
+	pub items: Vec<DlgItemTemplate>,
+}
+
+impl Default for DlgTemplate {
+	fn default() -> Self {
+		Self {
+			help_id: 0,
+			ex_style: co::WS_EX::NoValue,
+			style: co::WS::POPUP | co::WS::CAPTION | co::WS::SYSMENU | co::DS::MODALFRAME.into(),
+			pos: (0, 0),
+			size: (0, 0),
+			menu: None,
+			class_name: None,
+			title: String::new(),
+			font: Some(DlgFont::default()),
+			items: Vec::new(),
+		}
+	}
+}
+
+impl DlgTemplate {
+	/// Creates a new dialog with the given title, position, size and styles,
+	/// and the default font, without controls.
+	#[must_use]
+	pub fn new(title: &str, pos: (i16, i16), size: (i16, i16), style: co::WS) -> Self {
+		Self {
+			style,
+			pos,
+			size,
+			title: title.to_owned(),
+			..Default::default()
+		}
+	}
+
+	/// Serializes the object into a binary `DLGTEMPLATEEX` block, which can be
+	/// written as an [`RT::DIALOG`](crate::co::RT::DIALOG) resource with
+	/// [`HUPDATERSRC::UpdateResource`](crate::prelude::kernel_Hupdatersrc::UpdateResource).
+	///
+	/// Note that the dialog creation functions require a 32-bit aligned
+	/// block, which is not guaranteed by a `Vec<u8>`.
+	///
+	/// Fails with [`co::ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER)
+	/// if there are more than 65535 items, or if the creation data of an item
+	/// is longer than 65535 bytes, since these counts are stored as `u16`.
+	#[must_use]
+	pub fn serialize(&self) -> SysResult<Vec<u8>> {
+		let mut buf = Vec::<u8>::with_capacity(256);
+
+		let style = if self.font.is_some() {
+			self.style | co::DS::SETFONT.into()
+		} else {
+			unsafe { co::WS::from_raw(self.style.raw() & !co::DS::SETFONT.raw()) }
+		};
+
+		write_u16(&mut buf, 1); // dlgVer
+		write_u16(&mut buf, 0xffff); // signature
+		write_u32(&mut buf, self.help_id);
+		write_u32(&mut buf, self.ex_style.raw());
+		write_u32(&mut buf, style.raw());
+		write_u16(&mut buf, len_u16(self.items.len())?);
+		for v in [self.pos.0, self.pos.1, self.size.0, self.size.1] {
+			buf.extend_from_slice(&v.to_le_bytes());
+		}
+		match &self.menu {
+			Some(menu) => write_sz_or_ord(&mut buf, menu),
+			None => write_u16(&mut buf, 0x0000),
+		}
+		match &self.class_name {
+			Some(class_name) => write_sz_or_ord(&mut buf, class_name),
+			None => write_u16(&mut buf, 0x0000),
+		}
+		write_str(&mut buf, &self.title);
+
+		if let Some(font) = &self.font {
+			write_u16(&mut buf, font.point_size);
+			write_u16(&mut buf, font.weight);
+			buf.push(font.italic as _);
+			buf.push(font.charset);
+			write_str(&mut buf, &font.face_name);
+		}
+
+		for item in self.items.iter() {
+			item.write(&mut buf)?;
+		}
+		Ok(buf)
+	}
+}
+
+/// Converts a count into the `u16` stored in the block.
+fn len_u16(len: usize) -> SysResult<u16> {
+	u16::try_from(len).map_err(|_| co::ERROR::INVALID_PARAMETER)
+}
+
+fn align4(buf: &mut Vec<u8>) {
+	buf.resize((buf.len() + 3) & !3, 0x00);
+}
+
+fn write_u16(buf: &mut Vec<u8>, v: u16) {
+	buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn write_u32(buf: &mut Vec<u8>, v: u32) {
+	buf.extend_from_slice(&v.to_le_bytes());
+}
+
+/// Writes a null-terminated UTF-16 string.
+fn write_str(buf: &mut Vec<u8>, s: &str) {
+	s.encode_utf16()
+		.chain(std::iter::once(0x0000))
+		.for_each(|ch| write_u16(buf, ch));
+}
+
+/// Writes an ordinal, prefixed with `0xffff`, or a null-terminated string.
+fn write_sz_or_ord(buf: &mut Vec<u8>, v: &IdStr) {
+	match v {
+		IdStr::Id(id) => {
+			write_u16(buf, 0xffff);
+			write_u16(buf, *id);
+		},
+		IdStr::Str(ws) => write_str(buf, &ws.to_string()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn serialize_bytes() {
+		let mut tmpl = DlgTemplate::new("AB", (1, 2), (30, 40), co::WS::POPUP);
+		tmpl.font = Some(DlgFont {
+			face_name: "X".to_owned(),
+			..Default::default()
+		});
+		tmpl.items
+			.push(DlgItemTemplate::new("button", "OK", 1, (3, 4), (5, 6), co::WS::TABSTOP));
+
+		#[rustfmt::skip]
+		let expected: &[u8] = &[
+			0x01, 0x00, 0xff, 0xff, // dlgVer, signature
+			0x00, 0x00, 0x00, 0x00, // helpID
+			0x00, 0x00, 0x00, 0x00, // exStyle
+			0x40, 0x00, 0x00, 0x80, // style: WS_POPUP | DS_SETFONT
+			0x01, 0x00, // cDlgItems
+			0x01, 0x00, 0x02, 0x00, 0x1e, 0x00, 0x28, 0x00, // x, y, cx, cy
+			0x00, 0x00, // menu
+			0x00, 0x00, // windowClass
+			0x41, 0x00, 0x42, 0x00, 0x00, 0x00, // title
+			0x08, 0x00, 0x90, 0x01, 0x00, 0x01, // pointsize, weight, italic, charset
+			0x58, 0x00, 0x00, 0x00, // typeface
+			0x00, 0x00, // padding
+			0x00, 0x00, 0x00, 0x00, // helpID
+			0x00, 0x00, 0x00, 0x00, // exStyle
+			0x00, 0x00, 0x01, 0x50, // style: WS_CHILD | WS_VISIBLE | WS_TABSTOP
+			0x03, 0x00, 0x04, 0x00, 0x05, 0x00, 0x06, 0x00, // x, y, cx, cy
+			0x01, 0x00, 0x00, 0x00, // id
+			0xff, 0xff, 0x80, 0x00, // windowClass: BUTTON ordinal
+			0x4f, 0x00, 0x4b, 0x00, 0x00, 0x00, // title
+			0x00, 0x00, // extraCount
+		];
+		assert_eq!(tmpl.serialize().unwrap(), expected);
+	}
+
+	#[test]
+	fn serialize_without_font() {
+		let tmpl = DlgTemplate {
+			style: co::WS::POPUP | co::DS::SETFONT.into(),
+			font: None,
+			..Default::default()
+		};
+		let bytes = tmpl.serialize().unwrap();
+		assert_eq!(&bytes[12..16], &[0x00, 0x00, 0x00, 0x80]); // DS_SETFONT removed
+		assert_eq!(bytes.len(), 32); // header ends with the empty title
+	}
+
+	#[test]
+	fn serialize_too_many_items() {
+		let mut tmpl = DlgTemplate {
+			items: vec![DlgItemTemplate::default(); 0xffff],
+			..Default::default()
+		};
+		assert!(tmpl.serialize().is_ok());
+
+		tmpl.items.push(DlgItemTemplate::default());
+		assert_eq!(tmpl.serialize().unwrap_err(), co::ERROR::INVALID_PARAMETER);
+	}
+
+	#[test]
+	fn serialize_too_much_creation_data() {
+		let mut tmpl = DlgTemplate::default();
+		tmpl.items.push(DlgItemTemplate {
+			creation_data: vec![0x00; 0x1_0000],
+			..Default::default()
+		});
+		assert_eq!(tmpl.serialize().unwrap_err(), co::ERROR::INVALID_PARAMETER);
+	}
+}
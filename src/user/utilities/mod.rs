@@ -0,0 +1,10 @@
+mod clipboard_formats;
+mod dlg_template;
+mod rc_compiler;
+mod rc_lexer;
+mod rc_symbols;
+mod res_file;
+
+pub use clipboard_formats::{DropFiles, HtmlFormat};
+pub use dlg_template::{DlgFont, DlgItemTemplate, DlgTemplate};
+pub use res_file::{ResEntry, ResFile};
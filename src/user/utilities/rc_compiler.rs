@@ -0,0 +1,1216 @@
+use std::path::Path;
+
+use super::rc_lexer::{self, Cursor, TokKind};
+use crate::co;
+use crate::decl::*;
+
+impl ResFile {
+	/// Compiles a resource script (`.rc`) file in pure Rust, without calling
+	/// any native function or external tool.
+	///
+	/// Included files, icons and other referenced files are searched relative
+	/// to the script directory. SDK headers like `windows.h` don't need to be
+	/// present, because the symbols commonly used in scripts are built-in.
+	///
+	/// The supported subset of the resource script syntax is:
+	///
+	/// * preprocessor: `#include`, `#define` and `#undef` of object-like
+	///   macros, `#if`, `#ifdef`, `#ifndef`, `#elif`, `#else` and `#endif`;
+	///   included `.h` and `.c` files have only their directives processed;
+	/// * `LANGUAGE`, `VERSION` and `CHARACTERISTICS` statements;
+	/// * `DIALOG` and `DIALOGEX`, both compiled as `DLGTEMPLATEEX` with
+	///   [`DlgTemplate`](crate::DlgTemplate), with generic and predefined
+	///   controls;
+	/// * `MENU` and `MENUEX`;
+	/// * `ACCELERATORS`;
+	/// * `STRINGTABLE`;
+	/// * `ICON` and `BITMAP`;
+	/// * `VERSIONINFO`, which requires the `version` feature;
+	/// * `RCDATA`, `HTML`, `RT_MANIFEST` and user-defined resources, either
+	///   loaded from a file or declared inline.
+	///
+	/// Resources are given the `0x0409` (English, United States) language,
+	/// unless a `LANGUAGE` statement is present.
+	///
+	/// Errors are returned as text with the file and line where they occurred.
+	pub fn compile(rc_path: &str) -> AnyResult<Self> {
+		let data = std::fs::read(rc_path).map_err(|e| format!("{}: {}", rc_path, e))?;
+		let text = rc_lexer::decode_script(&data, rc_path)?;
+		let base_dir = Path::new(rc_path)
+			.parent()
+			.filter(|dir| !dir.as_os_str().is_empty())
+			.unwrap_or(Path::new("."));
+		RcCompiler::compile(&text, rc_path, base_dir)
+	}
+
+	/// Compiles the text of a resource script, like
+	/// [`compile`](crate::ResFile::compile). Included and referenced files are
+	/// searched relative to `base_dir`.
+	pub fn compile_str(script: &str, base_dir: &str) -> AnyResult<Self> {
+		RcCompiler::compile(script, "<script>", Path::new(base_dir))
+	}
+}
+
+/// Attributes which apply to a single resource.
+#[derive(Clone, Copy)]
+struct ResAttrs {
+	lang: LANGID,
+	version: u32,
+	characteristics: u32,
+}
+
+struct MenuNode {
+	text: String,
+	id: u32,
+	mft: u32,     // MENUEX only
+	mfs: u32,     // MENUEX only
+	help_id: u32, // MENUEX only
+	flags: u16,   // MENU only
+	children: Option<Vec<MenuNode>>,
+}
+
+struct RcCompiler<'a> {
+	cur: Cursor<'a>,
+	base_dir: &'a Path,
+	lang: LANGID,
+	next_icon_id: u16,
+	strings: Vec<(ResAttrs, u16, [Option<String>; 16])>, // bundles of 16 strings
+	res: ResFile,
+}
+
+impl<'a> RcCompiler<'a> {
+	const WS_CHILD_VISIBLE: i64 = 0x5000_0000;
+	const WS_TABSTOP: i64 = 0x0001_0000;
+	const WS_GROUP: i64 = 0x0002_0000;
+	const WS_BORDER: i64 = 0x0080_0000;
+	const MEMORY_KWS: [&'static str; 9] = [
+		"PRELOAD",
+		"LOADONCALL",
+		"FIXED",
+		"MOVEABLE",
+		"DISCARDABLE",
+		"PURE",
+		"IMPURE",
+		"SHARED",
+		"NONSHARED",
+	];
+
+	fn compile(script: &str, file_name: &str, base_dir: &Path) -> AnyResult<ResFile> {
+		let lexed = rc_lexer::preprocess(script, file_name, base_dir)?;
+		let mut compiler = RcCompiler {
+			cur: Cursor::new(&lexed.toks, &lexed.files),
+			base_dir,
+			lang: LANGID::new(co::LANG::ENGLISH, co::SUBLANG::ENGLISH_US),
+			next_icon_id: 1,
+			strings: Vec::new(),
+			res: ResFile::new(),
+		};
+
+		while !compiler.cur.at_end() {
+			compiler.statement()?;
+		}
+		compiler.flush_strings();
+		Ok(compiler.res)
+	}
+
+	fn statement(&mut self) -> AnyResult<()> {
+		if self.cur.eat_kw("LANGUAGE") {
+			self.lang = self.language()?;
+			return Ok(());
+		}
+		if self.cur.eat_kw("STRINGTABLE") {
+			return self.string_table();
+		}
+
+		let name = self.res_name()?;
+		let kw = match self.cur.peek() {
+			Some(TokKind::Ident(id)) => id.to_ascii_uppercase(),
+			Some(TokKind::Num { val, .. }) => {
+				let rt = unsafe { co::RT::from_raw(*val as _) };
+				self.cur.next();
+				return self.user_data(name, RtStr::Rt(rt));
+			},
+			Some(TokKind::Str { val, .. }) => {
+				let rt = RtStr::from_str(&val.to_ascii_uppercase());
+				self.cur.next();
+				return self.user_data(name, rt);
+			},
+			_ => return self.cur.err("expected resource type"),
+		};
+		self.cur.next();
+
+		match kw.as_str() {
+			"DIALOG" | "DIALOGEX" => self.dialog(name),
+			"MENU" => self.menu(name, false),
+			"MENUEX" => self.menu(name, true),
+			"ACCELERATORS" => self.accelerators(name),
+			"ICON" => self.icon(name),
+			"BITMAP" => self.bitmap(name),
+			"VERSIONINFO" => self.version_info(name),
+			"RCDATA" => self.user_data(name, RtStr::Rt(co::RT::RCDATA)),
+			"HTML" => self.user_data(name, RtStr::Rt(co::RT::HTML)),
+			"MESSAGETABLE" => self.user_data(name, RtStr::Rt(co::RT::MESSAGETABLE)),
+			"CURSOR" | "FONT" | "DLGINIT" | "TOOLBAR" | "TEXTINCLUDE" | "DLGINCLUDE" => {
+				self.cur.err(&format!("{} resources are not supported", kw))
+			},
+			_ => self.user_data(name, RtStr::from_str(&kw)),
+		}
+	}
+
+	/// Parses a resource name, which is an ordinal or a string.
+	fn res_name(&mut self) -> AnyResult<IdStr> {
+		match self.cur.next() {
+			Some(TokKind::Num { val, .. }) => Ok(IdStr::Id(*val as _)),
+			Some(TokKind::Ident(id)) => Ok(IdStr::from_str(&id.to_ascii_uppercase())),
+			Some(TokKind::Str { val, .. }) => Ok(IdStr::from_str(&val.to_ascii_uppercase())),
+			_ => self.cur.err("expected resource name"),
+		}
+	}
+
+	/// Parses a file name, quoted or not, and reads the file.
+	fn file_data(&mut self) -> AnyResult<Vec<u8>> {
+		let file_name = match self.cur.peek() {
+			Some(TokKind::Str { val, .. }) | Some(TokKind::Ident(val)) => val.clone(),
+			_ => return self.cur.err("expected file name"),
+		};
+		let path = match rc_lexer::find_file(&file_name, &[self.base_dir]) {
+			Some(path) => path,
+			None => return self.cur.err(&format!("cannot find file {}", file_name)),
+		};
+		self.cur.next();
+		std::fs::read(&path).map_err(|e| format!("{}: {}", path.display(), e).into())
+	}
+
+	/// Parses the arguments of a `LANGUAGE` statement.
+	fn language(&mut self) -> AnyResult<LANGID> {
+		let lang = self.cur.expr()?;
+		self.cur.expect_punct(",")?;
+		let sublang = self.cur.expr()?;
+		Ok(unsafe { LANGID::from_raw((((sublang as u16) << 10) | lang as u16) as _) })
+	}
+
+	/// Skips memory flags, which are obsolete.
+	fn memory_flags(&mut self) {
+		while Self::MEMORY_KWS.iter().any(|kw| self.cur.is_kw(kw)) {
+			self.cur.next();
+		}
+	}
+
+	/// Parses an optional statement common to all resources, returning `false`
+	/// if there is none.
+	fn common_stmt(&mut self, attrs: &mut ResAttrs) -> AnyResult<bool> {
+		if self.cur.eat_kw("LANGUAGE") {
+			attrs.lang = self.language()?;
+		} else if self.cur.eat_kw("VERSION") {
+			attrs.version = self.cur.expr()? as _;
+		} else if self.cur.eat_kw("CHARACTERISTICS") {
+			attrs.characteristics = self.cur.expr()? as _;
+		} else {
+			return Ok(false);
+		}
+		Ok(true)
+	}
+
+	#[must_use]
+	const fn attrs(&self) -> ResAttrs {
+		ResAttrs {
+			lang: self.lang,
+			version: 0,
+			characteristics: 0,
+		}
+	}
+
+	fn push(&mut self, res_type: co::RT, name: IdStr, attrs: ResAttrs, data: Vec<u8>) {
+		self.push_rt(RtStr::Rt(res_type), name, attrs, data);
+	}
+
+	fn push_rt(&mut self, res_type: RtStr, name: IdStr, attrs: ResAttrs, data: Vec<u8>) {
+		self.res.entries.push(ResEntry {
+			res_type,
+			name,
+			lang: attrs.lang,
+			version: attrs.version,
+			characteristics: attrs.characteristics,
+			data,
+		});
+	}
+
+	/// Parses an optional argument, preceded by a comma.
+	fn opt_arg(&mut self) -> AnyResult<Option<i64>> {
+		if !self.cur.eat_punct(",") || self.cur.is_punct(",") {
+			return Ok(None); // also an empty argument
+		}
+		Ok(Some(self.cur.expr()?))
+	}
+
+	/// Parses an optional style list, preceded by a comma, which is combined
+	/// with the base value.
+	fn opt_style(&mut self, base: i64) -> AnyResult<Option<i64>> {
+		if !self.cur.eat_punct(",") || self.cur.is_punct(",") {
+			return Ok(None);
+		}
+		Ok(Some(self.cur.style(base)?))
+	}
+
+	fn string_table(&mut self) -> AnyResult<()> {
+		self.memory_flags();
+		let mut attrs = self.attrs();
+		while self.common_stmt(&mut attrs)? {}
+
+		self.cur.expect_begin()?;
+		while !self.cur.eat_end() {
+			let id = self.cur.expr()? as u16;
+			self.cur.eat_punct(",");
+			let text = self.cur.expect_str()?;
+
+			let block = id / 16 + 1;
+			let idx = match self
+				.strings
+				.iter()
+				.position(|(a, b, _)| a.lang == attrs.lang && *b == block)
+			{
+				Some(idx) => idx,
+				None => {
+					self.strings.push((attrs, block, Default::default()));
+					self.strings.len() - 1
+				},
+			};
+			let slot = &mut self.strings[idx].2[(id % 16) as usize];
+			if slot.is_some() {
+				return self.cur.err(&format!("duplicated string ID {}", id));
+			}
+			*slot = Some(text);
+		}
+		Ok(())
+	}
+
+	/// Writes the string bundles as `RT::STRING` resources.
+	fn flush_strings(&mut self) {
+		for (attrs, block, strs) in std::mem::take(&mut self.strings).into_iter() {
+			let mut data = Vec::<u8>::new();
+			for s in strs.iter() {
+				let str16 = s
+					.as_deref()
+					.unwrap_or("")
+					.encode_utf16()
+					.collect::<Vec<_>>();
+				data.extend_from_slice(&(str16.len() as u16).to_le_bytes());
+				str16
+					.iter()
+					.for_each(|ch| data.extend_from_slice(&ch.to_le_bytes()));
+			}
+			self.push(co::RT::STRING, IdStr::Id(block), attrs, data);
+		}
+	}
+
+	fn dialog(&mut self, name: IdStr) -> AnyResult<()> {
+		self.memory_flags();
+		let mut attrs = self.attrs();
+		let mut tmpl = DlgTemplate { font: None, ..Default::default() };
+
+		let x = self.cur.expr()?;
+		self.cur.expect_punct(",")?;
+		let y = self.cur.expr()?;
+		self.cur.expect_punct(",")?;
+		let cx = self.cur.expr()?;
+		self.cur.expect_punct(",")?;
+		let cy = self.cur.expr()?;
+		tmpl.pos = (x as _, y as _);
+		tmpl.size = (cx as _, cy as _);
+		tmpl.help_id = self.opt_arg()?.unwrap_or(0) as _;
+
+		let mut style = 0x8088_0000; // WS_POPUP | WS_BORDER | WS_SYSMENU
+		let mut has_caption = false;
+		loop {
+			if self.cur.eat_kw("STYLE") {
+				style = self.cur.style(0)?;
+			} else if self.cur.eat_kw("EXSTYLE") {
+				tmpl.ex_style = unsafe { co::WS_EX::from_raw(self.cur.style(0)? as _) };
+			} else if self.cur.eat_kw("CAPTION") {
+				tmpl.title = self.cur.expect_str()?;
+				has_caption = true;
+			} else if self.cur.eat_kw("FONT") {
+				let point_size = self.cur.expr()? as _;
+				self.cur.expect_punct(",")?;
+				let face_name = self.cur.expect_str()?;
+				let weight = self.opt_arg()?.unwrap_or(400) as _;
+				let italic = self.opt_arg()?.unwrap_or(0) != 0;
+				let charset = self.opt_arg()?.unwrap_or(1) as _;
+				tmpl.font = Some(DlgFont {
+					point_size,
+					weight,
+					italic,
+					charset,
+					face_name,
+				});
+			} else if self.cur.eat_kw("MENU") {
+				tmpl.menu = Some(self.res_name()?);
+			} else if self.cur.eat_kw("CLASS") {
+				tmpl.class_name = Some(self.res_name()?);
+			} else if !self.common_stmt(&mut attrs)? {
+				break;
+			}
+		}
+		if has_caption {
+			style |= 0x00c0_0000; // WS_CAPTION
+		}
+		tmpl.style = unsafe { co::WS::from_raw(style as _) };
+
+		self.cur.expect_begin()?;
+		while !self.cur.eat_end() {
+			let item = self.dlg_item()?;
+			tmpl.items.push(item);
+		}
+
+		self.push(co::RT::DIALOG, name, attrs, tmpl.serialize()?);
+		Ok(())
+	}
+
+	/// Parses the text of a control, which can also be a resource ordinal.
+	fn ctrl_text(&mut self) -> AnyResult<IdStr> {
+		match self.cur.next() {
+			Some(TokKind::Str { val, .. }) => Ok(IdStr::from_str(val)),
+			Some(TokKind::Num { val, .. }) => Ok(IdStr::Id(*val as _)),
+			Some(TokKind::Ident(id)) => Ok(IdStr::from_str(id)),
+			_ => self.cur.err("expected control text"),
+		}
+	}
+
+	/// Parses `x, y, cx, cy` of a control, preceded by a comma.
+	fn ctrl_rect(&mut self, with_size: bool) -> AnyResult<((i16, i16), (i16, i16))> {
+		let mut vals = [0i64; 4];
+		let count = if with_size { 4 } else { 2 };
+		for val in vals.iter_mut().take(count) {
+			self.cur.expect_punct(",")?;
+			*val = self.cur.expr()?;
+		}
+		Ok(((vals[0] as _, vals[1] as _), (vals[2] as _, vals[3] as _)))
+	}
+
+	fn dlg_item(&mut self) -> AnyResult<DlgItemTemplate> {
+		// Keyword, class, default style and whether the control has a text.
+		const PREDEFINED: [(&str, &str, i64, bool); 17] = [
+			("LTEXT", "STATIC", RcCompiler::WS_GROUP, true),
+			("CTEXT", "STATIC", 0x01 | RcCompiler::WS_GROUP, true),
+			("RTEXT", "STATIC", 0x02 | RcCompiler::WS_GROUP, true),
+			("PUSHBUTTON", "BUTTON", RcCompiler::WS_TABSTOP, true),
+			("DEFPUSHBUTTON", "BUTTON", 0x01 | RcCompiler::WS_TABSTOP, true),
+			("CHECKBOX", "BUTTON", 0x02 | RcCompiler::WS_TABSTOP, true),
+			("AUTOCHECKBOX", "BUTTON", 0x03 | RcCompiler::WS_TABSTOP, true),
+			("RADIOBUTTON", "BUTTON", 0x04, true),
+			("STATE3", "BUTTON", 0x05 | RcCompiler::WS_TABSTOP, true),
+			("AUTO3STATE", "BUTTON", 0x06 | RcCompiler::WS_TABSTOP, true),
+			("GROUPBOX", "BUTTON", 0x07, true),
+			("AUTORADIOBUTTON", "BUTTON", 0x09, true),
+			("PUSHBOX", "BUTTON", 0x0a | RcCompiler::WS_TABSTOP, true),
+			("EDITTEXT", "EDIT", RcCompiler::WS_BORDER | RcCompiler::WS_TABSTOP, false),
+			("COMBOBOX", "COMBOBOX", 0x01 | RcCompiler::WS_TABSTOP, false),
+			("LISTBOX", "LISTBOX", 0x01 | RcCompiler::WS_BORDER, false),
+			("SCROLLBAR", "SCROLLBAR", 0x00, false),
+		];
+
+		let kw = match self.cur.next() {
+			Some(TokKind::Ident(id)) => id.to_ascii_uppercase(),
+			_ => return self.cur.err("expected control statement"),
+		};
+		let mut item = DlgItemTemplate::default();
+
+		if kw == "CONTROL" {
+			item.text = self.ctrl_text()?;
+			self.cur.expect_punct(",")?;
+			item.id = self.cur.expr()? as _;
+			self.cur.expect_punct(",")?;
+			item.class_name = match self.cur.next() {
+				Some(TokKind::Str { val, .. }) | Some(TokKind::Ident(val)) => val.clone(),
+				Some(TokKind::Num { val, .. }) => match val {
+					0x80 => "BUTTON",
+					0x81 => "EDIT",
+					0x82 => "STATIC",
+					0x83 => "LISTBOX",
+					0x84 => "SCROLLBAR",
+					0x85 => "COMBOBOX",
+					_ => return self.cur.err("unknown control class ordinal"),
+				}
+				.to_owned(),
+				_ => return self.cur.err("expected control class"),
+			};
+			self.cur.expect_punct(",")?;
+			let style = self.cur.style(Self::WS_CHILD_VISIBLE)?;
+			item.style = unsafe { co::WS::from_raw(style as _) };
+			(item.pos, item.size) = self.ctrl_rect(true)?;
+		} else {
+			let (class_name, def_style, has_text, with_size) = if kw == "ICON" {
+				("STATIC", 0x03, true, false) // SS_ICON, size is optional
+			} else {
+				match PREDEFINED.iter().find(|(k, ..)| *k == kw) {
+					Some((_, class_name, def_style, has_text)) => {
+						(*class_name, *def_style, *has_text, true)
+					},
+					None => return self.cur.err(&format!("unknown control statement {}", kw)),
+				}
+			};
+			item.class_name = class_name.to_owned();
+			if has_text {
+				item.text = self.ctrl_text()?;
+				self.cur.expect_punct(",")?;
+			} else {
+				item.text = IdStr::from_str("");
+			}
+			item.id = self.cur.expr()? as _;
+			(item.pos, item.size) = self.ctrl_rect(with_size)?;
+			if !with_size {
+				if let Some(cx) = self.opt_arg()? {
+					self.cur.expect_punct(",")?;
+					item.size = (cx as _, self.cur.expr()? as _);
+				}
+			}
+
+			let base = Self::WS_CHILD_VISIBLE | def_style;
+			let style = self.opt_style(base)?.unwrap_or(base);
+			item.style = unsafe { co::WS::from_raw(style as _) };
+			if let Some(ex_style) = self.opt_style(0)? {
+				item.ex_style = unsafe { co::WS_EX::from_raw(ex_style as _) };
+			}
+			item.help_id = self.opt_arg()?.unwrap_or(0) as _;
+		}
+
+		if kw == "CONTROL" {
+			if let Some(ex_style) = self.opt_style(0)? {
+				item.ex_style = unsafe { co::WS_EX::from_raw(ex_style as _) };
+			}
+			item.help_id = self.opt_arg()?.unwrap_or(0) as _;
+		}
+
+		if self.cur.is_kw("BEGIN") || self.cur.is_punct("{") {
+			item.creation_data = self.raw_data()?;
+		}
+		Ok(item)
+	}
+
+	fn menu(&mut self, name: IdStr, is_ex: bool) -> AnyResult<()> {
+		self.memory_flags();
+		let mut attrs = self.attrs();
+		while self.common_stmt(&mut attrs)? {}
+
+		let nodes = self.menu_items(is_ex)?;
+		let mut data = Vec::<u8>::new();
+		if is_ex {
+			data.extend_from_slice(&1u16.to_le_bytes()); // wVersion
+			data.extend_from_slice(&4u16.to_le_bytes()); // wOffset
+			data.extend_from_slice(&0u32.to_le_bytes()); // dwHelpId
+			Self::write_menu_ex(&mut data, &nodes);
+			self.push(co::RT::MENU, name, attrs, data);
+		} else {
+			data.extend_from_slice(&0u32.to_le_bytes()); // versionNumber and offset
+			Self::write_menu(&mut data, &nodes);
+			self.push(co::RT::MENU, name, attrs, data);
+		}
+		Ok(())
+	}
+
+	fn menu_items(&mut self, is_ex: bool) -> AnyResult<Vec<MenuNode>> {
+		const OPTIONS: [(&str, u16); 6] = [
+			("GRAYED", 0x0001),
+			("INACTIVE", 0x0002),
+			("CHECKED", 0x0008),
+			("MENUBARBREAK", 0x0020),
+			("MENUBREAK", 0x0040),
+			("HELP", 0x4000),
+		];
+
+		let mut nodes = Vec::<MenuNode>::new();
+		self.cur.expect_begin()?;
+		while !self.cur.eat_end() {
+			let is_popup = if self.cur.eat_kw("POPUP") {
+				true
+			} else if self.cur.eat_kw("MENUITEM") {
+				false
+			} else {
+				return self.cur.err("expected MENUITEM or POPUP");
+			};
+
+			let mut node = MenuNode {
+				text: String::new(),
+				id: 0,
+				mft: 0,
+				mfs: 0,
+				help_id: 0,
+				flags: 0,
+				children: None,
+			};
+
+			if !is_popup && self.cur.eat_kw("SEPARATOR") {
+				node.mft = 0x0800; // MFT_SEPARATOR
+				nodes.push(node);
+				continue;
+			}
+			node.text = self.cur.expect_str()?;
+
+			if is_ex {
+				node.id = self.opt_arg()?.unwrap_or(0) as _;
+				node.mft = self.opt_arg()?.unwrap_or(0) as _;
+				node.mfs = self.opt_arg()?.unwrap_or(0) as _;
+				if is_popup {
+					node.help_id = self.opt_arg()?.unwrap_or(0) as _;
+				}
+			} else {
+				if !is_popup {
+					self.cur.expect_punct(",")?;
+					node.id = self.cur.expr()? as _;
+				}
+				loop {
+					let has_comma = self.cur.eat_punct(",");
+					match OPTIONS.iter().find(|(kw, _)| self.cur.is_kw(kw)) {
+						Some((_, flag)) => {
+							self.cur.next();
+							node.flags |= flag;
+						},
+						None if has_comma => return self.cur.err("expected menu option"),
+						None => break,
+					}
+				}
+			}
+
+			if is_popup {
+				node.children = Some(self.menu_items(is_ex)?);
+			}
+			nodes.push(node);
+		}
+		Ok(nodes)
+	}
+
+	fn write_menu(buf: &mut Vec<u8>, nodes: &[MenuNode]) {
+		for (idx, node) in nodes.iter().enumerate() {
+			let mut flags = node.flags;
+			if node.children.is_some() {
+				flags |= 0x0010; // MF_POPUP
+			}
+			if idx == nodes.len() - 1 {
+				flags |= 0x0080; // MF_END
+			}
+			buf.extend_from_slice(&flags.to_le_bytes());
+			if node.children.is_none() {
+				buf.extend_from_slice(&(node.id as u16).to_le_bytes());
+			}
+			write_str(buf, &node.text);
+			if let Some(children) = &node.children {
+				Self::write_menu(buf, children);
+			}
+		}
+	}
+
+	fn write_menu_ex(buf: &mut Vec<u8>, nodes: &[MenuNode]) {
+		for (idx, node) in nodes.iter().enumerate() {
+			align4(buf);
+			buf.extend_from_slice(&node.mft.to_le_bytes());
+			buf.extend_from_slice(&node.mfs.to_le_bytes());
+			buf.extend_from_slice(&node.id.to_le_bytes());
+			let mut res_info = 0u16;
+			if idx == nodes.len() - 1 {
+				res_info |= 0x0080; // last item
+			}
+			if node.children.is_some() {
+				res_info |= 0x0001; // popup
+			}
+			buf.extend_from_slice(&res_info.to_le_bytes());
+			write_str(buf, &node.text);
+
+			if let Some(children) = &node.children {
+				align4(buf);
+				buf.extend_from_slice(&node.help_id.to_le_bytes());
+				Self::write_menu_ex(buf, children);
+			}
+		}
+	}
+
+	fn accelerators(&mut self, name: IdStr) -> AnyResult<()> {
+		const OPTIONS: [(&str, u16); 6] = [
+			("VIRTKEY", 0x01),
+			("NOINVERT", 0x02),
+			("SHIFT", 0x04),
+			("CONTROL", 0x08),
+			("ALT", 0x10),
+			("ASCII", 0x00),
+		];
+
+		self.memory_flags();
+		let mut attrs = self.attrs();
+		while self.common_stmt(&mut attrs)? {}
+
+		let mut entries = Vec::<(u16, u16, u16)>::new(); // flags, key, cmd
+		self.cur.expect_begin()?;
+		while !self.cur.eat_end() {
+			let event = match self.cur.peek() {
+				Some(TokKind::Str { val, .. }) => {
+					self.cur.next();
+					Some(val.clone())
+				},
+				_ => None,
+			};
+			let key_num = match event {
+				Some(_) => 0,
+				None => self.cur.expr()?,
+			};
+			self.cur.expect_punct(",")?;
+			let cmd = self.cur.expr()? as u16;
+
+			let mut flags = 0u16;
+			while self.cur.eat_punct(",") {
+				match OPTIONS.iter().find(|(kw, _)| self.cur.is_kw(kw)) {
+					Some((_, flag)) => {
+						self.cur.next();
+						flags |= flag;
+					},
+					None => return self.cur.err("expected accelerator option"),
+				}
+			}
+
+			let key = match event {
+				Some(event) => {
+					let chars = event.chars().collect::<Vec<_>>();
+					match chars.as_slice() {
+						['^', ch] => (ch.to_ascii_uppercase() as u16).wrapping_sub(0x40), // control char
+						[ch] if flags & 0x01 != 0 => ch.to_ascii_uppercase() as u16,
+						[ch] => *ch as u16,
+						_ => return self.cur.err("invalid accelerator key"),
+					}
+				},
+				None => key_num as u16,
+			};
+			entries.push((flags, key, cmd));
+		}
+
+		let mut data = Vec::<u8>::with_capacity(entries.len() * 8);
+		for (idx, (flags, key, cmd)) in entries.iter().enumerate() {
+			let flags = if idx == entries.len() - 1 { flags | 0x80 } else { *flags }; // last entry
+			for v in [flags, *key, *cmd, 0] {
+				data.extend_from_slice(&v.to_le_bytes());
+			}
+		}
+		self.push(co::RT::ACCELERATOR, name, attrs, data);
+		Ok(())
+	}
+
+	fn icon(&mut self, name: IdStr) -> AnyResult<()> {
+		self.memory_flags();
+		let attrs = self.attrs();
+		let ico = self.file_data()?;
+
+		let read_u16 = |off: usize| {
+			ico.get(off..off + 2)
+				.map(|b| u16::from_le_bytes([b[0], b[1]]))
+		};
+		let read_u32 = |off: usize| {
+			ico.get(off..off + 4)
+				.map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+		};
+		let count = match (read_u16(0), read_u16(2), read_u16(4)) {
+			(Some(0), Some(1), Some(count)) => count,
+			_ => return self.cur.err("invalid icon file"),
+		};
+
+		let mut group = Vec::<u8>::with_capacity(6 + count as usize * 14);
+		group.extend_from_slice(&ico[..6]); // idReserved, idType, idCount
+
+		for i in 0..count as usize {
+			let entry_off = 6 + i * 16;
+			let (bytes_in_res, image_off) =
+				match (read_u32(entry_off + 8), read_u32(entry_off + 12)) {
+					(Some(sz), Some(off)) => (sz as usize, off as usize),
+					_ => return self.cur.err("invalid icon file"),
+				};
+			let image = match ico.get(image_off..image_off + bytes_in_res) {
+				Some(image) => image.to_vec(),
+				None => return self.cur.err("invalid icon file"),
+			};
+
+			let icon_id = self.next_icon_id;
+			self.next_icon_id += 1;
+			self.push(co::RT::ICON, IdStr::Id(icon_id), attrs, image);
+
+			group.extend_from_slice(&ico[entry_off..entry_off + 12]); // all fields but dwImageOffset
+			group.extend_from_slice(&icon_id.to_le_bytes());
+		}
+
+		self.push(co::RT::GROUP_ICON, name, attrs, group);
+		Ok(())
+	}
+
+	fn bitmap(&mut self, name: IdStr) -> AnyResult<()> {
+		self.memory_flags();
+		let attrs = self.attrs();
+		let bmp = self.file_data()?;
+		if bmp.len() < 14 || &bmp[..2] != b"BM" {
+			return self.cur.err("invalid bitmap file");
+		}
+		self.push(co::RT::BITMAP, name, attrs, bmp[14..].to_vec()); // skip BITMAPFILEHEADER
+		Ok(())
+	}
+
+	fn user_data(&mut self, name: IdStr, res_type: RtStr) -> AnyResult<()> {
+		self.memory_flags();
+		let mut attrs = self.attrs();
+		while self.common_stmt(&mut attrs)? {}
+
+		let data = if self.cur.is_kw("BEGIN") || self.cur.is_punct("{") {
+			self.raw_data()?
+		} else {
+			self.file_data()?
+		};
+		self.push_rt(res_type, name, attrs, data);
+		Ok(())
+	}
+
+	/// Parses a `BEGIN`/`END` block of raw data: numbers are written as 16-bit
+	/// values, or as 32-bit values with the `L` suffix; strings are written as
+	/// UTF-8, or as UTF-16 with the `L` prefix, without terminating nulls.
+	fn raw_data(&mut self) -> AnyResult<Vec<u8>> {
+		let mut data = Vec::<u8>::new();
+		self.cur.expect_begin()?;
+		while !self.cur.eat_end() {
+			match self.cur.peek() {
+				Some(TokKind::Str { val, wide }) => {
+					if *wide {
+						val.encode_utf16()
+							.for_each(|ch| data.extend_from_slice(&ch.to_le_bytes()));
+					} else {
+						data.extend_from_slice(val.as_bytes());
+					}
+					self.cur.next();
+				},
+				Some(TokKind::Num { long, .. }) => {
+					let long = *long;
+					let val = self.cur.expr()?;
+					if long {
+						data.extend_from_slice(&(val as u32).to_le_bytes());
+					} else {
+						data.extend_from_slice(&(val as u16).to_le_bytes());
+					}
+				},
+				_ => return self.cur.err("expected number or string"),
+			}
+			self.cur.eat_punct(",");
+		}
+		Ok(data)
+	}
+
+	#[cfg(not(feature = "version"))]
+	fn version_info(&mut self, _name: IdStr) -> AnyResult<()> {
+		self.cur
+			.err("VERSIONINFO resources require the \"version\" feature")
+	}
+
+	#[cfg(feature = "version")]
+	fn version_info(&mut self, name: IdStr) -> AnyResult<()> {
+		self.memory_flags();
+		let attrs = self.attrs();
+		let mut vi = VersionInfo::new();
+		let mut fixed = VS_FIXEDFILEINFO::default();
+		fixed.dwStrucVersion = 0x0001_0000;
+
+		loop {
+			if self.cur.eat_kw("FILEVERSION") {
+				fixed.set_dwFileVersion(self.version_quad()?);
+			} else if self.cur.eat_kw("PRODUCTVERSION") {
+				fixed.set_dwProductVersion(self.version_quad()?);
+			} else if self.cur.eat_kw("FILEFLAGSMASK") {
+				fixed.dwFileFlagsMask = unsafe { co::VS_FF::from_raw(self.cur.expr()? as _) };
+			} else if self.cur.eat_kw("FILEFLAGS") {
+				fixed.dwFileFlags = unsafe { co::VS_FF::from_raw(self.cur.expr()? as _) };
+			} else if self.cur.eat_kw("FILEOS") {
+				fixed.dwFileOS = unsafe { co::VOS::from_raw(self.cur.expr()? as _) };
+			} else if self.cur.eat_kw("FILETYPE") {
+				fixed.dwFileType = unsafe { co::VFT::from_raw(self.cur.expr()? as _) };
+			} else if self.cur.eat_kw("FILESUBTYPE") {
+				fixed.dwFileSubtype = unsafe { co::VFT2::from_raw(self.cur.expr()? as _) };
+			} else {
+				break;
+			}
+		}
+		vi.fixed = Some(fixed);
+
+		self.cur.expect_begin()?;
+		while !self.cur.eat_end() {
+			if !self.cur.eat_kw("BLOCK") {
+				return self.cur.err("expected BLOCK");
+			}
+			let block_name = self.cur.expect_str()?;
+			self.cur.expect_begin()?;
+
+			if block_name.eq_ignore_ascii_case("StringFileInfo") {
+				while !self.cur.eat_end() {
+					if !self.cur.eat_kw("BLOCK") {
+						return self.cur.err("expected BLOCK");
+					}
+					let key = self.cur.expect_str()?;
+					let lang_cp = match u32::from_str_radix(&key, 16) {
+						Ok(n) if key.len() == 8 => unsafe {
+							(LANGID::from_raw((n >> 16) as _), co::CP::from_raw(n as _))
+						},
+						_ => return self.cur.err("invalid language and code page"),
+					};
+					let mut table = VersionStringTable::new(lang_cp);
+
+					self.cur.expect_begin()?;
+					while !self.cur.eat_end() {
+						if !self.cur.eat_kw("VALUE") {
+							return self.cur.err("expected VALUE");
+						}
+						let key = self.cur.expect_str()?;
+						let mut value = String::new();
+						while self.cur.eat_punct(",") {
+							value.push_str(&self.cur.expect_str()?);
+						}
+						let value = value.trim_end_matches('\0'); // null is added when serializing
+						table.entries.push((key, value.to_owned()));
+					}
+					vi.string_tables.push(table);
+				}
+			} else if block_name.eq_ignore_ascii_case("VarFileInfo") {
+				while !self.cur.eat_end() {
+					if !self.cur.eat_kw("VALUE") {
+						return self.cur.err("expected VALUE");
+					}
+					let key = self.cur.expect_str()?;
+					if !key.eq_ignore_ascii_case("Translation") {
+						return self.cur.err("expected Translation value");
+					}
+					while self.cur.eat_punct(",") {
+						let lang = self.cur.expr()?;
+						self.cur.expect_punct(",")?;
+						let cp = self.cur.expr()?;
+						vi.translations.push(unsafe {
+							(LANGID::from_raw(lang as _), co::CP::from_raw(cp as _))
+						});
+					}
+				}
+			} else {
+				return self.cur.err(&format!("unknown block {}", block_name));
+			}
+		}
+
+		self.push(co::RT::VERSION, name, attrs, vi.serialize()?);
+		Ok(())
+	}
+
+	/// Parses up to 4 comma-separated version numbers.
+	#[cfg(feature = "version")]
+	fn version_quad(&mut self) -> AnyResult<[u16; 4]> {
+		let mut quad = [0u16; 4];
+		quad[0] = self.cur.expr()? as _;
+		for part in quad.iter_mut().skip(1) {
+			match self.opt_arg()? {
+				Some(val) => *part = val as _,
+				None => break,
+			}
+		}
+		Ok(quad)
+	}
+}
+
+fn align4(buf: &mut Vec<u8>) {
+	buf.resize((buf.len() + 3) & !3, 0x00);
+}
+
+/// Writes a null-terminated UTF-16 string.
+fn write_str(buf: &mut Vec<u8>, s: &str) {
+	s.encode_utf16()
+		.chain(std::iter::once(0x0000))
+		.for_each(|ch| buf.extend_from_slice(&ch.to_le_bytes()));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn compile(script: &str) -> ResFile {
+		ResFile::compile_str(script, ".").unwrap()
+	}
+
+	/// Creates a temporary directory with the given files.
+	fn temp_dir(name: &str, files: &[(&str, &[u8])]) -> std::path::PathBuf {
+		let dir = std::env::temp_dir().join(format!("winsafe_rc_{}_{}", name, std::process::id()));
+		std::fs::create_dir_all(&dir).unwrap();
+		for (file_name, contents) in files.iter() {
+			std::fs::write(dir.join(file_name), contents).unwrap();
+		}
+		dir
+	}
+
+	fn str16(s: &str) -> Vec<u8> {
+		let mut buf = Vec::new();
+		write_str(&mut buf, s);
+		buf
+	}
+
+	fn assert_entry(entry: &ResEntry, res_type: co::RT, name: IdStr) {
+		assert_eq!(entry.res_type.to_string(), RtStr::Rt(res_type).to_string());
+		assert_eq!(entry.name.to_string(), name.to_string());
+	}
+
+	#[test]
+	fn dialog() {
+		let res = compile(
+			r#"
+			1 DIALOGEX 0, 0, 100, 50
+			STYLE DS_MODALFRAME | WS_POPUP
+			CAPTION "T"
+			FONT 8, "X"
+			BEGIN
+				DEFPUSHBUTTON "OK", IDOK, 5, 6, 7, 8
+			END
+			"#,
+		);
+
+		let mut tmpl = DlgTemplate {
+			size: (100, 50),
+			style: unsafe { co::WS::from_raw(0x80c0_0080) }, // WS_POPUP | WS_CAPTION | DS_MODALFRAME
+			title: "T".to_owned(),
+			font: Some(DlgFont {
+				face_name: "X".to_owned(),
+				..Default::default()
+			}),
+			..Default::default()
+		};
+		tmpl.items.push(DlgItemTemplate::new(
+			"BUTTON",
+			"OK",
+			1,
+			(5, 6),
+			(7, 8),
+			co::WS::TABSTOP | unsafe { co::WS::from_raw(0x01) }, // BS_DEFPUSHBUTTON
+		));
+
+		assert_eq!(res.entries.len(), 1);
+		assert_entry(&res.entries[0], co::RT::DIALOG, IdStr::Id(1));
+		assert_eq!(res.entries[0].data, tmpl.serialize().unwrap());
+	}
+
+	#[test]
+	fn menu() {
+		let res = compile(
+			r#"
+			2 MENU
+			BEGIN
+				POPUP "&File"
+				BEGIN
+					MENUITEM "E&xit", 100, GRAYED
+				END
+			END
+			"#,
+		);
+
+		let mut expected = vec![0x00, 0x00, 0x00, 0x00]; // versionNumber, offset
+		expected.extend_from_slice(&[0x90, 0x00]); // MF_POPUP | MF_END
+		expected.extend(str16("&File"));
+		expected.extend_from_slice(&[0x81, 0x00, 0x64, 0x00]); // MF_GRAYED | MF_END, ID
+		expected.extend(str16("E&xit"));
+
+		assert_eq!(res.entries.len(), 1);
+		assert_entry(&res.entries[0], co::RT::MENU, IdStr::Id(2));
+		assert_eq!(res.entries[0].data, expected);
+	}
+
+	#[test]
+	fn menu_ex() {
+		let res = compile(
+			r#"
+			3 MENUEX
+			BEGIN
+				POPUP "F", 10, 0, 0, 5
+				BEGIN
+					MENUITEM "A", 11, MFT_STRING, MFS_CHECKED
+				END
+			END
+			"#,
+		);
+
+		#[rustfmt::skip]
+		let expected: &[u8] = &[
+			0x01, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, // wVersion, wOffset, dwHelpId
+			0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // dwType, dwState
+			0x0a, 0x00, 0x00, 0x00, 0x81, 0x00, // uId, bResInfo: popup | last
+			0x46, 0x00, 0x00, 0x00, // szText
+			0x00, 0x00, // padding
+			0x05, 0x00, 0x00, 0x00, // dwHelpId
+			0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, // dwType, dwState: MFS_CHECKED
+			0x0b, 0x00, 0x00, 0x00, 0x80, 0x00, // uId, bResInfo: last
+			0x41, 0x00, 0x00, 0x00, // szText
+		];
+
+		assert_eq!(res.entries.len(), 1);
+		assert_entry(&res.entries[0], co::RT::MENU, IdStr::Id(3));
+		assert_eq!(res.entries[0].data, expected);
+	}
+
+	#[test]
+	fn accelerators() {
+		let res = compile(
+			r#"
+			4 ACCELERATORS
+			BEGIN
+				"^C", 200
+				VK_F1, 201, VIRTKEY, CONTROL
+			END
+			"#,
+		);
+
+		#[rustfmt::skip]
+		let expected: &[u8] = &[
+			0x00, 0x00, 0x03, 0x00, 0xc8, 0x00, 0x00, 0x00, // Ctrl+C as ASCII
+			0x89, 0x00, 0x70, 0x00, 0xc9, 0x00, 0x00, 0x00, // FVIRTKEY | FCONTROL | last, VK_F1
+		];
+
+		assert_eq!(res.entries.len(), 1);
+		assert_entry(&res.entries[0], co::RT::ACCELERATOR, IdStr::Id(4));
+		assert_eq!(res.entries[0].data, expected);
+	}
+
+	#[test]
+	fn string_table() {
+		let res = compile(
+			r#"
+			STRINGTABLE
+			BEGIN
+				1, "ab"
+				17 "c"
+			END
+			"#,
+		);
+
+		let bundle = |idx: usize, s: &str| {
+			let mut data = vec![0x00; 32];
+			let mut str_data = (s.len() as u16).to_le_bytes().to_vec();
+			s.encode_utf16()
+				.for_each(|ch| str_data.extend_from_slice(&ch.to_le_bytes()));
+			data.splice(idx * 2..idx * 2 + 2, str_data);
+			data
+		};
+
+		assert_eq!(res.entries.len(), 2);
+		assert_entry(&res.entries[0], co::RT::STRING, IdStr::Id(1)); // IDs 0 to 15
+		assert_eq!(res.entries[0].data, bundle(1, "ab"));
+		assert_entry(&res.entries[1], co::RT::STRING, IdStr::Id(2)); // IDs 16 to 31
+		assert_eq!(res.entries[1].data, bundle(1, "c"));
+	}
+
+	#[test]
+	fn string_table_duplicated() {
+		let script = "STRINGTABLE BEGIN 1, \"a\" 1, \"b\" END";
+		assert!(ResFile::compile_str(script, ".").is_err());
+	}
+
+	#[test]
+	fn icon() {
+		#[rustfmt::skip]
+		let ico: &[u8] = &[
+			0x00, 0x00, 0x01, 0x00, 0x01, 0x00, // idReserved, idType, idCount
+			0x10, 0x10, 0x00, 0x00, 0x01, 0x00, 0x20, 0x00, // 16x16, 1 plane, 32 bpp
+			0x04, 0x00, 0x00, 0x00, 0x16, 0x00, 0x00, 0x00, // dwBytesInRes, dwImageOffset
+			0x01, 0x02, 0x03, 0x04, // image
+		];
+		let dir = temp_dir("icon", &[("app.ico", ico)]);
+		let res = ResFile::compile_str("5 ICON \"app.ico\"", dir.to_str().unwrap()).unwrap();
+		std::fs::remove_dir_all(&dir).unwrap();
+
+		let mut group = ico[..18].to_vec(); // header and entry, without dwImageOffset
+		group.extend_from_slice(&[0x01, 0x00]); // nId
+
+		assert_eq!(res.entries.len(), 2);
+		assert_entry(&res.entries[0], co::RT::ICON, IdStr::Id(1));
+		assert_eq!(res.entries[0].data, &ico[22..]);
+		assert_entry(&res.entries[1], co::RT::GROUP_ICON, IdStr::Id(5));
+		assert_eq!(res.entries[1].data, group);
+	}
+
+	#[test]
+	fn bitmap() {
+		let mut bmp = b"BM".to_vec();
+		bmp.extend_from_slice(&[0x00; 12]); // rest of BITMAPFILEHEADER
+		bmp.extend_from_slice(&[0x09, 0x08, 0x07]);
+		let dir = temp_dir("bitmap", &[("img.bmp", &bmp)]);
+		let res = ResFile::compile_str("IDB_IMG BITMAP img.bmp", dir.to_str().unwrap()).unwrap();
+		std::fs::remove_dir_all(&dir).unwrap();
+
+		assert_eq!(res.entries.len(), 1);
+		assert_entry(&res.entries[0], co::RT::BITMAP, IdStr::from_str("IDB_IMG"));
+		assert_eq!(res.entries[0].data, &[0x09, 0x08, 0x07]);
+	}
+
+	#[cfg(feature = "version")]
+	#[test]
+	fn version_info() {
+		let res = compile(
+			r#"
+			VS_VERSION_INFO VERSIONINFO
+			FILEVERSION 1, 2, 3, 4
+			BEGIN
+				BLOCK "StringFileInfo"
+				BEGIN
+					BLOCK "040904b0"
+					BEGIN
+						VALUE "ProductName", "App\0"
+					END
+				END
+				BLOCK "VarFileInfo"
+				BEGIN
+					VALUE "Translation", 0x409, 1200
+				END
+			END
+			"#,
+		);
+
+		assert_eq!(res.entries.len(), 1);
+		assert_entry(&res.entries[0], co::RT::VERSION, IdStr::Id(1));
+
+		let lang_cp = (LANGID::new(co::LANG::ENGLISH, co::SUBLANG::ENGLISH_US), co::CP::UTF16);
+		let vi = VersionInfo::parse(&res.entries[0].data).unwrap();
+		assert_eq!(vi.fixed.unwrap().dwFileVersion(), [1, 2, 3, 4]);
+		assert_eq!(vi.str_val(lang_cp, "ProductName"), Some("App"));
+		assert!(vi.translations == [lang_cp]);
+	}
+
+	#[test]
+	fn user_data() {
+		let res = compile(
+			r#"
+			9 RCDATA
+			BEGIN
+				1, 2L, "ab", L"c"
+			END
+			10 MYTYPE { 3 }
+			"#,
+		);
+
+		assert_eq!(res.entries.len(), 2);
+		assert_entry(&res.entries[0], co::RT::RCDATA, IdStr::Id(9));
+		assert_eq!(
+			res.entries[0].data,
+			&[0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x61, 0x62, 0x63, 0x00],
+		);
+		assert_eq!(res.entries[1].res_type.to_string(), RtStr::from_str("MYTYPE").to_string());
+		assert_eq!(res.entries[1].data, &[0x03, 0x00]);
+	}
+
+	#[test]
+	fn attributes() {
+		let res = compile(
+			r#"
+			1 RCDATA { 0 }
+			LANGUAGE LANG_NEUTRAL, SUBLANG_NEUTRAL
+			2 RCDATA VERSION 7 CHARACTERISTICS 8 { 0 }
+			"#,
+		);
+
+		assert_eq!(res.entries.len(), 2);
+		assert_eq!(res.entries[0].lang.raw(), 0x0409);
+		assert_eq!(res.entries[1].lang.raw(), 0x0000);
+		assert_eq!(res.entries[1].version, 7);
+		assert_eq!(res.entries[1].characteristics, 8);
+	}
+
+	#[test]
+	fn unsupported() {
+		assert!(ResFile::compile_str("1 CURSOR \"a.cur\"", ".").is_err());
+	}
+}
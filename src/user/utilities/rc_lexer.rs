@@ -0,0 +1,693 @@
+//! Preprocessor and tokenizer of resource scripts, plus the expression
+//! evaluator shared by `#if` directives and resource statements.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::rc_symbols::{NUM_SYMBOLS, SDK_HEADERS, STR_SYMBOLS};
+use crate::decl::*;
+
+/// Multi-char punctuators must come first, so they're matched before their
+/// prefixes.
+const PUNCTS: [&str; 28] = [
+	"==", "!=", "<=", ">=", "&&", "||", "<<", ">>", ",", "|", "&", "^", "+", "-", "*", "/", "%",
+	"~", "!", "<", ">", "(", ")", "{", "}", "=", ";", ":",
+];
+
+#[derive(Clone, PartialEq)]
+pub(in crate::user) enum TokKind {
+	Ident(String),
+	Num { val: i64, long: bool },
+	Str { val: String, wide: bool },
+	Punct(&'static str),
+}
+
+#[derive(Clone)]
+pub(in crate::user) struct Tok {
+	pub(in crate::user) kind: TokKind,
+	pub(in crate::user) file: usize,
+	pub(in crate::user) line: usize,
+}
+
+/// Tokens of a whole script, after preprocessing.
+pub(in crate::user) struct Lexed {
+	pub(in crate::user) toks: Vec<Tok>,
+	pub(in crate::user) files: Vec<String>,
+}
+
+struct Cond {
+	active: bool,
+	taken: bool, // some branch was already taken
+}
+
+struct Preprocessor {
+	base_dir: PathBuf,
+	defines: HashMap<String, Vec<TokKind>>,
+	files: Vec<String>,
+	toks: Vec<Tok>,
+	depth: usize,
+}
+
+/// Preprocesses and tokenizes the script. Included files are searched relative
+/// to the including file, then relative to `base_dir`.
+pub(in crate::user) fn preprocess(
+	text: &str,
+	file_name: &str,
+	base_dir: &Path,
+) -> AnyResult<Lexed> {
+	let mut pp = Preprocessor {
+		base_dir: base_dir.to_path_buf(),
+		defines: HashMap::new(),
+		files: Vec::new(),
+		toks: Vec::new(),
+		depth: 0,
+	};
+	pp.define("RC_INVOKED", "1");
+	pp.define("_WIN32", "1");
+	pp.process(text, file_name, base_dir, false)?;
+	Ok(Lexed { toks: pp.toks, files: pp.files })
+}
+
+/// Decodes the raw contents of a script, which can be UTF-16 with a BOM or
+/// UTF-8.
+pub(in crate::user) fn decode_script(data: &[u8], file_name: &str) -> AnyResult<String> {
+	if data.len() >= 2 && data[0] == 0xff && data[1] == 0xfe {
+		let str16 = data[2..]
+			.chunks_exact(2)
+			.map(|ch2| u16::from_le_bytes([ch2[0], ch2[1]]))
+			.collect::<Vec<_>>();
+		return Ok(String::from_utf16_lossy(&str16));
+	}
+	let data = data.strip_prefix(&[0xef, 0xbb, 0xbf]).unwrap_or(data);
+	String::from_utf8(data.to_vec())
+		.map_err(|_| format!("{}: file is not UTF-8 or UTF-16", file_name).into())
+}
+
+impl Preprocessor {
+	fn define(&mut self, name: &str, value: &str) {
+		let value = tokenize_line(value).unwrap_or_default();
+		self.defines.insert(name.to_owned(), value);
+	}
+
+	fn process(
+		&mut self,
+		text: &str,
+		file_name: &str,
+		dir: &Path,
+		only_directives: bool,
+	) -> AnyResult<()> {
+		self.depth += 1;
+		if self.depth > 32 {
+			return Err(format!("{}: includes nested too deeply", file_name).into());
+		}
+		let file = self.files.len();
+		self.files.push(file_name.to_owned());
+		let err = |line: usize, msg: &str| -> AnyResult<()> {
+			Err(format!("{}({}): {}", file_name, line, msg).into())
+		};
+
+		let mut conds = Vec::<Cond>::new();
+		let mut in_comment = false;
+		let mut pending = String::new(); // line continuations
+		let mut pending_line = 0;
+
+		for (idx, raw_line) in text.lines().enumerate() {
+			if pending.is_empty() {
+				pending_line = idx + 1;
+			}
+			if let Some(part) = raw_line.strip_suffix('\\') {
+				pending.push_str(part);
+				continue;
+			}
+			pending.push_str(raw_line);
+			let line = strip_comments(&pending, &mut in_comment);
+			pending.clear();
+			let line_no = pending_line;
+
+			let active = conds.iter().all(|c| c.active);
+			let trimmed = line.trim();
+
+			if let Some(directive) = trimmed.strip_prefix('#') {
+				let directive = directive.trim_start();
+				let (name, rest) = directive
+					.split_once(|ch: char| {
+						ch.is_whitespace() || ch == '(' || ch == '"' || ch == '<'
+					})
+					.map_or((directive, ""), |(name, _)| (name, &directive[name.len()..]));
+				let rest = rest.trim();
+
+				match name {
+					"if" | "ifdef" | "ifndef" => {
+						let val = active
+							&& match name {
+								"ifdef" => self.defines.contains_key(rest),
+								"ifndef" => !self.defines.contains_key(rest),
+								_ => self.eval_cond(rest, file, line_no)?,
+							};
+						conds.push(Cond { active: val, taken: val || !active });
+					},
+					"elif" => match conds.last_mut() {
+						Some(cond) => {
+							cond.active = !cond.taken && self.eval_cond(rest, file, line_no)?;
+							cond.taken |= cond.active;
+						},
+						None => return err(line_no, "#elif without #if"),
+					},
+					"else" => match conds.last_mut() {
+						Some(cond) => {
+							cond.active = !cond.taken;
+							cond.taken = true;
+						},
+						None => return err(line_no, "#else without #if"),
+					},
+					"endif" => match conds.pop() {
+						Some(_) => {},
+						None => return err(line_no, "#endif without #if"),
+					},
+					_ if !active => {},
+					"define" => {
+						let name_len = rest
+							.find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+							.unwrap_or(rest.len());
+						let (def_name, value) = rest.split_at(name_len);
+						if def_name.is_empty() {
+							return err(line_no, "#define without a name");
+						}
+						if !value.starts_with('(') {
+							// Function-like macros are not supported, and ignored.
+							let value = tokenize_line(value)
+								.map_err(|e| format!("{}({}): {}", file_name, line_no, e))?;
+							self.defines.insert(def_name.to_owned(), value);
+						}
+					},
+					"undef" => {
+						self.defines.remove(rest);
+					},
+					"include" => {
+						let inc_name = rest.trim_matches(|ch| ch == '"' || ch == '<' || ch == '>');
+						let is_sdk = rest.starts_with('<')
+							|| SDK_HEADERS.iter().any(|h| h.eq_ignore_ascii_case(inc_name));
+						match find_file(inc_name, &[dir, self.base_dir.as_path()]) {
+							Some(inc_path) => {
+								let data = std::fs::read(&inc_path)
+									.map_err(|e| format!("{}: {}", inc_path.display(), e))?;
+								let inc_text = decode_script(&data, inc_name)?;
+								let ext = inc_path
+									.extension()
+									.map(|e| e.to_string_lossy().to_ascii_lowercase())
+									.unwrap_or_default();
+								let inc_dir = inc_path.parent().unwrap_or(dir).to_path_buf();
+								self.process(
+									&inc_text,
+									&inc_path.to_string_lossy(),
+									&inc_dir,
+									only_directives || ext == "h" || ext == "c",
+								)?;
+							},
+							None if is_sdk => {}, // symbols are built-in
+							None => {
+								return err(
+									line_no,
+									&format!("cannot find include file {}", inc_name),
+								)
+							},
+						}
+					},
+					"error" => return err(line_no, &format!("#error {}", rest)),
+					_ => {}, // #pragma and others are ignored
+				}
+				continue;
+			}
+
+			if !active || only_directives || trimmed.is_empty() {
+				continue;
+			}
+			let kinds =
+				tokenize_line(trimmed).map_err(|e| format!("{}({}): {}", file_name, line_no, e))?;
+			for kind in self.expand(kinds, 0) {
+				self.toks.push(Tok { kind, file, line: line_no });
+			}
+		}
+
+		if !conds.is_empty() {
+			return err(text.lines().count(), "#if without #endif");
+		}
+		self.depth -= 1;
+		Ok(())
+	}
+
+	/// Replaces defined macros and built-in symbols.
+	#[must_use]
+	fn expand(&self, kinds: Vec<TokKind>, depth: usize) -> Vec<TokKind> {
+		let mut out = Vec::with_capacity(kinds.len());
+		for kind in kinds.into_iter() {
+			match &kind {
+				TokKind::Ident(name) => {
+					if let Some(value) = self.defines.get(name).filter(|_| depth < 32) {
+						out.extend(self.expand(value.clone(), depth + 1));
+					} else if let Some((_, val)) = NUM_SYMBOLS.iter().find(|(n, _)| n == name) {
+						out.push(TokKind::Num { val: *val, long: false });
+					} else if let Some((_, val)) = STR_SYMBOLS.iter().find(|(n, _)| n == name) {
+						out.push(TokKind::Str { val: (*val).to_owned(), wide: false });
+					} else {
+						out.push(kind);
+					}
+				},
+				_ => out.push(kind),
+			}
+		}
+		out
+	}
+
+	/// Evaluates the expression of an `#if` or `#elif` directive.
+	fn eval_cond(&self, expr: &str, file: usize, line: usize) -> AnyResult<bool> {
+		let kinds =
+			tokenize_line(expr).map_err(|e| format!("{}({}): {}", self.files[file], line, e))?;
+
+		let mut replaced = Vec::with_capacity(kinds.len());
+		let mut iter = kinds.into_iter().peekable();
+		while let Some(kind) = iter.next() {
+			if kind == TokKind::Ident("defined".to_owned()) {
+				let parens = iter.next_if(|k| *k == TokKind::Punct("(")).is_some();
+				let is_def = match iter.next() {
+					Some(TokKind::Ident(name)) => self.defines.contains_key(&name),
+					_ => false,
+				};
+				if parens {
+					iter.next_if(|k| *k == TokKind::Punct(")"));
+				}
+				replaced.push(TokKind::Num { val: is_def as _, long: false });
+			} else {
+				replaced.push(kind);
+			}
+		}
+
+		let toks = self
+			.expand(replaced, 0)
+			.into_iter()
+			.map(|kind| match kind {
+				TokKind::Ident(_) => TokKind::Num { val: 0, long: false }, // undefined identifiers are zero
+				kind => kind,
+			})
+			.map(|kind| Tok { kind, file, line })
+			.collect::<Vec<_>>();
+
+		let mut cur = Cursor::new(&toks, &self.files);
+		let val = cur.expr()?;
+		if !cur.at_end() {
+			return cur.err("unexpected token in expression");
+		}
+		Ok(val != 0)
+	}
+}
+
+/// Searches a file relative to the given directories.
+#[must_use]
+pub(in crate::user) fn find_file(name: &str, dirs: &[&Path]) -> Option<PathBuf> {
+	let path = Path::new(name);
+	if path.is_absolute() {
+		return path.is_file().then(|| path.to_path_buf());
+	}
+	dirs.iter()
+		.map(|dir| dir.join(path))
+		.find(|full| full.is_file())
+}
+
+/// Removes `//` and `/* */` comments, keeping the state of block comments
+/// which span multiple lines.
+#[must_use]
+fn strip_comments(line: &str, in_comment: &mut bool) -> String {
+	let mut out = String::with_capacity(line.len());
+	let mut chars = line.chars().peekable();
+	let mut in_str = false;
+
+	while let Some(ch) = chars.next() {
+		if *in_comment {
+			if ch == '*' && chars.peek() == Some(&'/') {
+				chars.next();
+				*in_comment = false;
+				out.push(' ');
+			}
+		} else if in_str {
+			out.push(ch);
+			if ch == '\\' {
+				if let Some(next) = chars.next() {
+					out.push(next);
+				}
+			} else if ch == '"' {
+				in_str = false; // a doubled quote just reopens the string
+			}
+		} else if ch == '"' {
+			in_str = true;
+			out.push(ch);
+		} else if ch == '/' && chars.peek() == Some(&'/') {
+			break;
+		} else if ch == '/' && chars.peek() == Some(&'*') {
+			chars.next();
+			*in_comment = true;
+		} else {
+			out.push(ch);
+		}
+	}
+	out
+}
+
+/// Splits a single line into tokens; adjacent strings are concatenated.
+fn tokenize_line(line: &str) -> Result<Vec<TokKind>, String> {
+	let chars = line.chars().collect::<Vec<_>>();
+	let mut toks = Vec::<TokKind>::new();
+	let mut i = 0;
+
+	while i < chars.len() {
+		let ch = chars[i];
+		if ch.is_whitespace() {
+			i += 1;
+		} else if ch == '"' || (ch == 'L' && chars.get(i + 1) == Some(&'"')) {
+			let wide = ch == 'L';
+			if wide {
+				i += 1;
+			}
+			let (val, next) = read_str(&chars, i + 1)?;
+			i = next;
+			match toks.last_mut() {
+				Some(TokKind::Str { val: prev, wide: prev_wide }) => {
+					prev.push_str(&val);
+					*prev_wide |= wide;
+				},
+				_ => toks.push(TokKind::Str { val, wide }),
+			}
+		} else if ch == '\'' {
+			let (val, next) =
+				read_str(&chars[..], i + 1).map_err(|_| "unterminated char".to_owned())?;
+			i = next;
+			let val = val.chars().next().ok_or("empty char")? as i64;
+			toks.push(TokKind::Num { val, long: false });
+		} else if ch.is_ascii_digit() {
+			let start = i;
+			while i < chars.len() && chars[i].is_ascii_alphanumeric() {
+				i += 1;
+			}
+			let lit = chars[start..i].iter().collect::<String>();
+			toks.push(parse_num(&lit).ok_or_else(|| format!("invalid number {}", lit))?);
+		} else if ch.is_alphabetic() || ch == '_' {
+			let start = i;
+			while i < chars.len()
+				&& (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+			{
+				i += 1;
+			}
+			toks.push(TokKind::Ident(chars[start..i].iter().collect()));
+		} else {
+			let rest = chars[i..].iter().take(2).collect::<String>();
+			match PUNCTS.iter().find(|p| rest.starts_with(**p)) {
+				Some(p) => {
+					toks.push(TokKind::Punct(p));
+					i += p.len();
+				},
+				None => return Err(format!("unexpected character {}", ch)),
+			}
+		}
+	}
+	Ok(toks)
+}
+
+/// Reads a quoted string, starting right after the opening quote, returning
+/// the string and the index right after the closing quote.
+fn read_str(chars: &[char], start: usize) -> Result<(String, usize), String> {
+	let mut s = String::new();
+	let mut i = start;
+	let quote = chars[start - 1];
+
+	loop {
+		let ch = *chars.get(i).ok_or("unterminated string")?;
+		i += 1;
+		if ch == quote {
+			if chars.get(i) == Some(&quote) && quote == '"' {
+				s.push(quote); // doubled quote
+				i += 1;
+				continue;
+			}
+			return Ok((s, i));
+		}
+		if ch != '\\' {
+			s.push(ch);
+			continue;
+		}
+
+		let esc = *chars.get(i).ok_or("unterminated string")?;
+		i += 1;
+		match esc {
+			'n' => s.push('\n'),
+			'r' => s.push('\r'),
+			't' => s.push('\t'),
+			'a' => s.push('\x07'),
+			'x' | 'X' => {
+				let hex = chars[i..]
+					.iter()
+					.take(4)
+					.take_while(|ch| ch.is_ascii_hexdigit())
+					.collect::<String>();
+				i += hex.len();
+				let code = u32::from_str_radix(&hex, 16).map_err(|_| "invalid hex escape")?;
+				s.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+			},
+			'0'..='7' => {
+				let oct = chars[i - 1..]
+					.iter()
+					.take(3)
+					.take_while(|ch| ('0'..='7').contains(*ch))
+					.collect::<String>();
+				i += oct.len() - 1;
+				let code = u32::from_str_radix(&oct, 8).unwrap_or(0);
+				s.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+			},
+			other => s.push(other), // \\, \" and unknown escapes
+		}
+	}
+}
+
+#[must_use]
+fn parse_num(lit: &str) -> Option<TokKind> {
+	let lower = lit.to_ascii_lowercase();
+	let trimmed = lower.trim_end_matches(['l', 'u']);
+	let long = trimmed.len() != lower.len() && lower.contains('l');
+	let val = match trimmed.strip_prefix("0x") {
+		Some(hex) => i64::from_str_radix(hex, 16).ok()?,
+		None => trimmed.parse::<i64>().ok()?,
+	};
+	Some(TokKind::Num { val, long })
+}
+
+/// Cursor over a token stream, with the expression evaluator.
+pub(in crate::user) struct Cursor<'a> {
+	toks: &'a [Tok],
+	files: &'a [String],
+	pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+	#[must_use]
+	pub(in crate::user) const fn new(toks: &'a [Tok], files: &'a [String]) -> Self {
+		Self { toks, files, pos: 0 }
+	}
+
+	#[must_use]
+	pub(in crate::user) fn at_end(&self) -> bool {
+		self.pos >= self.toks.len()
+	}
+
+	#[must_use]
+	pub(in crate::user) fn peek(&self) -> Option<&'a TokKind> {
+		self.toks.get(self.pos).map(|tok| &tok.kind)
+	}
+
+	pub(in crate::user) fn next(&mut self) -> Option<&'a TokKind> {
+		let tok = self.peek();
+		if tok.is_some() {
+			self.pos += 1;
+		}
+		tok
+	}
+
+	/// Returns an error with the location of the current token.
+	pub(in crate::user) fn err<T>(&self, msg: &str) -> AnyResult<T> {
+		let tok = self.toks.get(self.pos).or_else(|| self.toks.last());
+		Err(match tok {
+			Some(tok) => format!("{}({}): {}", self.files[tok.file], tok.line, msg),
+			None => format!("{}: {}", self.files.first().map_or("", |f| f.as_str()), msg),
+		}
+		.into())
+	}
+
+	/// Tells whether the current token is the given keyword, case-insensitive.
+	#[must_use]
+	pub(in crate::user) fn is_kw(&self, kw: &str) -> bool {
+		matches!(self.peek(), Some(TokKind::Ident(id)) if id.eq_ignore_ascii_case(kw))
+	}
+
+	/// Consumes the current token if it's the given keyword.
+	pub(in crate::user) fn eat_kw(&mut self, kw: &str) -> bool {
+		let is = self.is_kw(kw);
+		if is {
+			self.pos += 1;
+		}
+		is
+	}
+
+	/// Tells whether the current token is the given punctuator.
+	#[must_use]
+	pub(in crate::user) fn is_punct(&self, p: &str) -> bool {
+		matches!(self.peek(), Some(TokKind::Punct(cur)) if *cur == p)
+	}
+
+	/// Consumes the current token if it's the given punctuator.
+	pub(in crate::user) fn eat_punct(&mut self, p: &str) -> bool {
+		let is = self.is_punct(p);
+		if is {
+			self.pos += 1;
+		}
+		is
+	}
+
+	pub(in crate::user) fn expect_punct(&mut self, p: &str) -> AnyResult<()> {
+		if self.eat_punct(p) {
+			Ok(())
+		} else {
+			self.err(&format!("expected {}", p))
+		}
+	}
+
+	/// Consumes `BEGIN` or `{`.
+	pub(in crate::user) fn expect_begin(&mut self) -> AnyResult<()> {
+		if self.eat_kw("BEGIN") || self.eat_punct("{") {
+			Ok(())
+		} else {
+			self.err("expected BEGIN")
+		}
+	}
+
+	/// Consumes `END` or `}`, if present.
+	pub(in crate::user) fn eat_end(&mut self) -> bool {
+		self.eat_kw("END") || self.eat_punct("}")
+	}
+
+	pub(in crate::user) fn expect_str(&mut self) -> AnyResult<String> {
+		match self.peek() {
+			Some(TokKind::Str { val, .. }) => {
+				self.pos += 1;
+				Ok(val.clone())
+			},
+			_ => self.err("expected string"),
+		}
+	}
+
+	/// Parses a full expression.
+	pub(in crate::user) fn expr(&mut self) -> AnyResult<i64> {
+		self.binary(1)
+	}
+
+	/// Parses a list of styles, which are ORed to the base value; the ones
+	/// preceded by `NOT` are removed from it.
+	pub(in crate::user) fn style(&mut self, base: i64) -> AnyResult<i64> {
+		let mut acc = base;
+		loop {
+			if self.eat_kw("NOT") {
+				acc &= !self.binary(Self::PREC_OR + 1)?;
+			} else {
+				acc |= self.binary(Self::PREC_OR + 1)?;
+			}
+			if !self.eat_punct("|") {
+				return Ok(acc);
+			}
+		}
+	}
+
+	const PREC_OR: u8 = 3;
+
+	#[must_use]
+	fn prec(op: &str) -> Option<u8> {
+		Some(match op {
+			"||" => 1,
+			"&&" => 2,
+			"|" => Self::PREC_OR,
+			"^" => 4,
+			"&" => 5,
+			"==" | "!=" => 6,
+			"<" | "<=" | ">" | ">=" => 7,
+			"<<" | ">>" => 8,
+			"+" | "-" => 9,
+			"*" | "/" | "%" => 10,
+			_ => return None,
+		})
+	}
+
+	fn binary(&mut self, min_prec: u8) -> AnyResult<i64> {
+		let mut lhs = self.unary()?;
+		loop {
+			let op = match self.peek() {
+				Some(TokKind::Punct(op)) => *op,
+				_ => return Ok(lhs),
+			};
+			let prec = match Self::prec(op) {
+				Some(prec) if prec >= min_prec => prec,
+				_ => return Ok(lhs),
+			};
+			self.pos += 1;
+
+			if op == "|" && self.eat_kw("NOT") {
+				lhs &= !self.binary(prec + 1)?; // "A | NOT B" removes B from A
+				continue;
+			}
+			let rhs = self.binary(prec + 1)?;
+			lhs = match op {
+				"||" => (lhs != 0 || rhs != 0) as _,
+				"&&" => (lhs != 0 && rhs != 0) as _,
+				"|" => lhs | rhs,
+				"^" => lhs ^ rhs,
+				"&" => lhs & rhs,
+				"==" => (lhs == rhs) as _,
+				"!=" => (lhs != rhs) as _,
+				"<" => (lhs < rhs) as _,
+				"<=" => (lhs <= rhs) as _,
+				">" => (lhs > rhs) as _,
+				">=" => (lhs >= rhs) as _,
+				"<<" => lhs.wrapping_shl(rhs as _),
+				">>" => lhs.wrapping_shr(rhs as _),
+				"+" => lhs.wrapping_add(rhs),
+				"-" => lhs.wrapping_sub(rhs),
+				"*" => lhs.wrapping_mul(rhs),
+				"/" | "%" if rhs == 0 => return self.err("division by zero"),
+				"/" => lhs / rhs,
+				_ => lhs % rhs,
+			};
+		}
+	}
+
+	fn unary(&mut self) -> AnyResult<i64> {
+		if self.eat_kw("NOT") || self.eat_punct("~") {
+			return Ok(!self.unary()?);
+		}
+		if self.eat_punct("!") {
+			return Ok((self.unary()? == 0) as _);
+		}
+		if self.eat_punct("-") {
+			return Ok(self.unary()?.wrapping_neg());
+		}
+		if self.eat_punct("+") {
+			return self.unary();
+		}
+		if self.eat_punct("(") {
+			let val = self.expr()?;
+			self.expect_punct(")")?;
+			return Ok(val);
+		}
+		match self.peek() {
+			Some(TokKind::Num { val, .. }) => {
+				self.pos += 1;
+				Ok(*val)
+			},
+			Some(TokKind::Ident(name)) => self.err(&format!("undefined symbol {}", name)),
+			_ => self.err("expected number"),
+		}
+	}
+}
@@ -0,0 +1,426 @@
+//! Symbols of the Windows SDK headers which are commonly used in resource
+//! scripts, so the scripts can be compiled without the headers.
+
+/// Numeric symbols.
+pub(in crate::user) const NUM_SYMBOLS: &[(&str, i64)] = &[
+	// Window styles.
+	("WS_OVERLAPPED", 0x0000_0000),
+	("WS_POPUP", 0x8000_0000),
+	("WS_CHILD", 0x4000_0000),
+	("WS_MINIMIZE", 0x2000_0000),
+	("WS_VISIBLE", 0x1000_0000),
+	("WS_DISABLED", 0x0800_0000),
+	("WS_CLIPSIBLINGS", 0x0400_0000),
+	("WS_CLIPCHILDREN", 0x0200_0000),
+	("WS_MAXIMIZE", 0x0100_0000),
+	("WS_CAPTION", 0x00c0_0000),
+	("WS_BORDER", 0x0080_0000),
+	("WS_DLGFRAME", 0x0040_0000),
+	("WS_VSCROLL", 0x0020_0000),
+	("WS_HSCROLL", 0x0010_0000),
+	("WS_SYSMENU", 0x0008_0000),
+	("WS_THICKFRAME", 0x0004_0000),
+	("WS_GROUP", 0x0002_0000),
+	("WS_TABSTOP", 0x0001_0000),
+	("WS_MINIMIZEBOX", 0x0002_0000),
+	("WS_MAXIMIZEBOX", 0x0001_0000),
+	("WS_SIZEBOX", 0x0004_0000),
+	("WS_OVERLAPPEDWINDOW", 0x00cf_0000),
+	("WS_POPUPWINDOW", 0x8088_0000),
+	// Extended window styles.
+	("WS_EX_DLGMODALFRAME", 0x0000_0001),
+	("WS_EX_NOPARENTNOTIFY", 0x0000_0004),
+	("WS_EX_TOPMOST", 0x0000_0008),
+	("WS_EX_ACCEPTFILES", 0x0000_0010),
+	("WS_EX_TRANSPARENT", 0x0000_0020),
+	("WS_EX_MDICHILD", 0x0000_0040),
+	("WS_EX_TOOLWINDOW", 0x0000_0080),
+	("WS_EX_WINDOWEDGE", 0x0000_0100),
+	("WS_EX_CLIENTEDGE", 0x0000_0200),
+	("WS_EX_CONTEXTHELP", 0x0000_0400),
+	("WS_EX_RIGHT", 0x0000_1000),
+	("WS_EX_RTLREADING", 0x0000_2000),
+	("WS_EX_LEFTSCROLLBAR", 0x0000_4000),
+	("WS_EX_CONTROLPARENT", 0x0001_0000),
+	("WS_EX_STATICEDGE", 0x0002_0000),
+	("WS_EX_APPWINDOW", 0x0004_0000),
+	("WS_EX_LAYERED", 0x0008_0000),
+	("WS_EX_NOINHERITLAYOUT", 0x0010_0000),
+	("WS_EX_LAYOUTRTL", 0x0040_0000),
+	("WS_EX_COMPOSITED", 0x0200_0000),
+	("WS_EX_NOACTIVATE", 0x0800_0000),
+	// Dialog styles.
+	("DS_ABSALIGN", 0x01),
+	("DS_SYSMODAL", 0x02),
+	("DS_3DLOOK", 0x04),
+	("DS_FIXEDSYS", 0x08),
+	("DS_NOFAILCREATE", 0x10),
+	("DS_LOCALEDIT", 0x20),
+	("DS_SETFONT", 0x40),
+	("DS_MODALFRAME", 0x80),
+	("DS_NOIDLEMSG", 0x100),
+	("DS_SETFOREGROUND", 0x200),
+	("DS_CONTROL", 0x400),
+	("DS_CENTER", 0x800),
+	("DS_CENTERMOUSE", 0x1000),
+	("DS_CONTEXTHELP", 0x2000),
+	("DS_SHELLFONT", 0x48),
+	// Static styles.
+	("SS_LEFT", 0x00),
+	("SS_CENTER", 0x01),
+	("SS_RIGHT", 0x02),
+	("SS_ICON", 0x03),
+	("SS_BLACKRECT", 0x04),
+	("SS_GRAYRECT", 0x05),
+	("SS_WHITERECT", 0x06),
+	("SS_BLACKFRAME", 0x07),
+	("SS_GRAYFRAME", 0x08),
+	("SS_WHITEFRAME", 0x09),
+	("SS_SIMPLE", 0x0b),
+	("SS_LEFTNOWORDWRAP", 0x0c),
+	("SS_BITMAP", 0x0e),
+	("SS_ETCHEDHORZ", 0x10),
+	("SS_ETCHEDVERT", 0x11),
+	("SS_ETCHEDFRAME", 0x12),
+	("SS_REALSIZECONTROL", 0x40),
+	("SS_NOPREFIX", 0x80),
+	("SS_NOTIFY", 0x100),
+	("SS_CENTERIMAGE", 0x200),
+	("SS_RIGHTJUST", 0x400),
+	("SS_SUNKEN", 0x1000),
+	("SS_ENDELLIPSIS", 0x4000),
+	("SS_PATHELLIPSIS", 0x8000),
+	("SS_WORDELLIPSIS", 0xc000),
+	// Button styles.
+	("BS_PUSHBUTTON", 0x00),
+	("BS_DEFPUSHBUTTON", 0x01),
+	("BS_CHECKBOX", 0x02),
+	("BS_AUTOCHECKBOX", 0x03),
+	("BS_RADIOBUTTON", 0x04),
+	("BS_3STATE", 0x05),
+	("BS_AUTO3STATE", 0x06),
+	("BS_GROUPBOX", 0x07),
+	("BS_AUTORADIOBUTTON", 0x09),
+	("BS_PUSHBOX", 0x0a),
+	("BS_OWNERDRAW", 0x0b),
+	("BS_SPLITBUTTON", 0x0c),
+	("BS_DEFSPLITBUTTON", 0x0d),
+	("BS_COMMANDLINK", 0x0e),
+	("BS_DEFCOMMANDLINK", 0x0f),
+	("BS_LEFTTEXT", 0x20),
+	("BS_ICON", 0x40),
+	("BS_BITMAP", 0x80),
+	("BS_LEFT", 0x100),
+	("BS_RIGHT", 0x200),
+	("BS_CENTER", 0x300),
+	("BS_TOP", 0x400),
+	("BS_BOTTOM", 0x800),
+	("BS_VCENTER", 0xc00),
+	("BS_PUSHLIKE", 0x1000),
+	("BS_MULTILINE", 0x2000),
+	("BS_NOTIFY", 0x4000),
+	("BS_FLAT", 0x8000),
+	// Edit styles.
+	("ES_LEFT", 0x0000),
+	("ES_CENTER", 0x0001),
+	("ES_RIGHT", 0x0002),
+	("ES_MULTILINE", 0x0004),
+	("ES_UPPERCASE", 0x0008),
+	("ES_LOWERCASE", 0x0010),
+	("ES_PASSWORD", 0x0020),
+	("ES_AUTOVSCROLL", 0x0040),
+	("ES_AUTOHSCROLL", 0x0080),
+	("ES_NOHIDESEL", 0x0100),
+	("ES_OEMCONVERT", 0x0400),
+	("ES_READONLY", 0x0800),
+	("ES_WANTRETURN", 0x1000),
+	("ES_NUMBER", 0x2000),
+	// List box styles.
+	("LBS_NOTIFY", 0x0001),
+	("LBS_SORT", 0x0002),
+	("LBS_NOREDRAW", 0x0004),
+	("LBS_MULTIPLESEL", 0x0008),
+	("LBS_OWNERDRAWFIXED", 0x0010),
+	("LBS_OWNERDRAWVARIABLE", 0x0020),
+	("LBS_HASSTRINGS", 0x0040),
+	("LBS_USETABSTOPS", 0x0080),
+	("LBS_NOINTEGRALHEIGHT", 0x0100),
+	("LBS_MULTICOLUMN", 0x0200),
+	("LBS_WANTKEYBOARDINPUT", 0x0400),
+	("LBS_EXTENDEDSEL", 0x0800),
+	("LBS_DISABLENOSCROLL", 0x1000),
+	("LBS_NODATA", 0x2000),
+	("LBS_NOSEL", 0x4000),
+	("LBS_STANDARD", 0x00a0_0003),
+	// Combo box styles.
+	("CBS_SIMPLE", 0x0001),
+	("CBS_DROPDOWN", 0x0002),
+	("CBS_DROPDOWNLIST", 0x0003),
+	("CBS_OWNERDRAWFIXED", 0x0010),
+	("CBS_OWNERDRAWVARIABLE", 0x0020),
+	("CBS_AUTOHSCROLL", 0x0040),
+	("CBS_OEMCONVERT", 0x0080),
+	("CBS_SORT", 0x0100),
+	("CBS_HASSTRINGS", 0x0200),
+	("CBS_NOINTEGRALHEIGHT", 0x0400),
+	("CBS_DISABLENOSCROLL", 0x0800),
+	("CBS_UPPERCASE", 0x2000),
+	("CBS_LOWERCASE", 0x4000),
+	// Scroll bar styles.
+	("SBS_HORZ", 0x0000),
+	("SBS_VERT", 0x0001),
+	// Common controls styles.
+	("LVS_ICON", 0x0000),
+	("LVS_REPORT", 0x0001),
+	("LVS_SMALLICON", 0x0002),
+	("LVS_LIST", 0x0003),
+	("LVS_SINGLESEL", 0x0004),
+	("LVS_SHOWSELALWAYS", 0x0008),
+	("LVS_SORTASCENDING", 0x0010),
+	("LVS_SORTDESCENDING", 0x0020),
+	("LVS_SHAREIMAGELISTS", 0x0040),
+	("LVS_NOLABELWRAP", 0x0080),
+	("LVS_AUTOARRANGE", 0x0100),
+	("LVS_EDITLABELS", 0x0200),
+	("LVS_OWNERDATA", 0x1000),
+	("LVS_NOSCROLL", 0x2000),
+	("LVS_NOCOLUMNHEADER", 0x4000),
+	("LVS_NOSORTHEADER", 0x8000),
+	("TVS_HASBUTTONS", 0x0001),
+	("TVS_HASLINES", 0x0002),
+	("TVS_LINESATROOT", 0x0004),
+	("TVS_EDITLABELS", 0x0008),
+	("TVS_DISABLEDRAGDROP", 0x0010),
+	("TVS_SHOWSELALWAYS", 0x0020),
+	("TVS_CHECKBOXES", 0x0100),
+	("TVS_TRACKSELECT", 0x0200),
+	("TVS_FULLROWSELECT", 0x1000),
+	("PBS_SMOOTH", 0x01),
+	("PBS_VERTICAL", 0x04),
+	("PBS_MARQUEE", 0x08),
+	("TBS_AUTOTICKS", 0x0001),
+	("TBS_VERT", 0x0002),
+	("TBS_HORZ", 0x0000),
+	("TBS_NOTICKS", 0x0010),
+	("TBS_BOTH", 0x0008),
+	("UDS_WRAP", 0x0001),
+	("UDS_SETBUDDYINT", 0x0002),
+	("UDS_ALIGNRIGHT", 0x0004),
+	("UDS_ALIGNLEFT", 0x0008),
+	("UDS_AUTOBUDDY", 0x0010),
+	("UDS_ARROWKEYS", 0x0020),
+	("UDS_HORZ", 0x0040),
+	("UDS_NOTHOUSANDS", 0x0080),
+	("TCS_MULTILINE", 0x0200),
+	("TCS_BUTTONS", 0x0100),
+	("DTS_SHORTDATEFORMAT", 0x0000),
+	("DTS_UPDOWN", 0x0001),
+	("DTS_SHOWNONE", 0x0002),
+	("DTS_LONGDATEFORMAT", 0x0004),
+	("DTS_TIMEFORMAT", 0x0009),
+	("SBARS_SIZEGRIP", 0x0100),
+	// Dialog box command IDs.
+	("IDOK", 1),
+	("IDCANCEL", 2),
+	("IDABORT", 3),
+	("IDRETRY", 4),
+	("IDIGNORE", 5),
+	("IDYES", 6),
+	("IDNO", 7),
+	("IDCLOSE", 8),
+	("IDHELP", 9),
+	("IDC_STATIC", -1),
+	// Menu types and states.
+	("MFT_STRING", 0x0000),
+	("MFT_BITMAP", 0x0004),
+	("MFT_MENUBARBREAK", 0x0020),
+	("MFT_MENUBREAK", 0x0040),
+	("MFT_OWNERDRAW", 0x0100),
+	("MFT_RADIOCHECK", 0x0200),
+	("MFT_SEPARATOR", 0x0800),
+	("MFT_RIGHTORDER", 0x2000),
+	("MFT_RIGHTJUSTIFY", 0x4000),
+	("MFS_ENABLED", 0x0000),
+	("MFS_UNCHECKED", 0x0000),
+	("MFS_UNHILITE", 0x0000),
+	("MFS_GRAYED", 0x0003),
+	("MFS_DISABLED", 0x0003),
+	("MFS_CHECKED", 0x0008),
+	("MFS_HILITE", 0x0080),
+	("MFS_DEFAULT", 0x1000),
+	// Virtual keys.
+	("VK_BACK", 0x08),
+	("VK_TAB", 0x09),
+	("VK_RETURN", 0x0d),
+	("VK_SHIFT", 0x10),
+	("VK_CONTROL", 0x11),
+	("VK_MENU", 0x12),
+	("VK_PAUSE", 0x13),
+	("VK_ESCAPE", 0x1b),
+	("VK_SPACE", 0x20),
+	("VK_PRIOR", 0x21),
+	("VK_NEXT", 0x22),
+	("VK_END", 0x23),
+	("VK_HOME", 0x24),
+	("VK_LEFT", 0x25),
+	("VK_UP", 0x26),
+	("VK_RIGHT", 0x27),
+	("VK_DOWN", 0x28),
+	("VK_INSERT", 0x2d),
+	("VK_DELETE", 0x2e),
+	("VK_HELP", 0x2f),
+	("VK_APPS", 0x5d),
+	("VK_NUMPAD0", 0x60),
+	("VK_NUMPAD1", 0x61),
+	("VK_NUMPAD2", 0x62),
+	("VK_NUMPAD3", 0x63),
+	("VK_NUMPAD4", 0x64),
+	("VK_NUMPAD5", 0x65),
+	("VK_NUMPAD6", 0x66),
+	("VK_NUMPAD7", 0x67),
+	("VK_NUMPAD8", 0x68),
+	("VK_NUMPAD9", 0x69),
+	("VK_MULTIPLY", 0x6a),
+	("VK_ADD", 0x6b),
+	("VK_SUBTRACT", 0x6d),
+	("VK_DECIMAL", 0x6e),
+	("VK_DIVIDE", 0x6f),
+	("VK_F1", 0x70),
+	("VK_F2", 0x71),
+	("VK_F3", 0x72),
+	("VK_F4", 0x73),
+	("VK_F5", 0x74),
+	("VK_F6", 0x75),
+	("VK_F7", 0x76),
+	("VK_F8", 0x77),
+	("VK_F9", 0x78),
+	("VK_F10", 0x79),
+	("VK_F11", 0x7a),
+	("VK_F12", 0x7b),
+	// Version information.
+	("VS_VERSION_INFO", 1),
+	("VS_FFI_FILEFLAGSMASK", 0x3f),
+	("VS_FF_DEBUG", 0x01),
+	("VS_FF_PRERELEASE", 0x02),
+	("VS_FF_PATCHED", 0x04),
+	("VS_FF_PRIVATEBUILD", 0x08),
+	("VS_FF_INFOINFERRED", 0x10),
+	("VS_FF_SPECIALBUILD", 0x20),
+	("VOS_UNKNOWN", 0x0000_0000),
+	("VOS_DOS", 0x0001_0000),
+	("VOS_NT", 0x0004_0000),
+	("VOS__WINDOWS32", 0x0000_0004),
+	("VOS_DOS_WINDOWS32", 0x0001_0004),
+	("VOS_NT_WINDOWS32", 0x0004_0004),
+	("VFT_UNKNOWN", 0),
+	("VFT_APP", 1),
+	("VFT_DLL", 2),
+	("VFT_DRV", 3),
+	("VFT_FONT", 4),
+	("VFT_VXD", 5),
+	("VFT_STATIC_LIB", 7),
+	("VFT2_UNKNOWN", 0),
+	// Resource types.
+	("RT_CURSOR", 1),
+	("RT_BITMAP", 2),
+	("RT_ICON", 3),
+	("RT_MENU", 4),
+	("RT_DIALOG", 5),
+	("RT_STRING", 6),
+	("RT_FONTDIR", 7),
+	("RT_FONT", 8),
+	("RT_ACCELERATOR", 9),
+	("RT_RCDATA", 10),
+	("RT_MESSAGETABLE", 11),
+	("RT_GROUP_CURSOR", 12),
+	("RT_GROUP_ICON", 14),
+	("RT_VERSION", 16),
+	("RT_HTML", 23),
+	("RT_MANIFEST", 24),
+	("CREATEPROCESS_MANIFEST_RESOURCE_ID", 1),
+	("ISOLATIONAWARE_MANIFEST_RESOURCE_ID", 2),
+	// Languages.
+	("LANG_NEUTRAL", 0x00),
+	("LANG_ARABIC", 0x01),
+	("LANG_CHINESE", 0x04),
+	("LANG_CZECH", 0x05),
+	("LANG_DANISH", 0x06),
+	("LANG_GERMAN", 0x07),
+	("LANG_GREEK", 0x08),
+	("LANG_ENGLISH", 0x09),
+	("LANG_SPANISH", 0x0a),
+	("LANG_FINNISH", 0x0b),
+	("LANG_FRENCH", 0x0c),
+	("LANG_HEBREW", 0x0d),
+	("LANG_HUNGARIAN", 0x0e),
+	("LANG_ITALIAN", 0x10),
+	("LANG_JAPANESE", 0x11),
+	("LANG_KOREAN", 0x12),
+	("LANG_DUTCH", 0x13),
+	("LANG_NORWEGIAN", 0x14),
+	("LANG_POLISH", 0x15),
+	("LANG_PORTUGUESE", 0x16),
+	("LANG_ROMANIAN", 0x18),
+	("LANG_RUSSIAN", 0x19),
+	("LANG_SWEDISH", 0x1d),
+	("LANG_TURKISH", 0x1f),
+	("LANG_UKRAINIAN", 0x22),
+	("SUBLANG_NEUTRAL", 0x00),
+	("SUBLANG_DEFAULT", 0x01),
+	("SUBLANG_SYS_DEFAULT", 0x02),
+	("SUBLANG_CHINESE_TRADITIONAL", 0x01),
+	("SUBLANG_CHINESE_SIMPLIFIED", 0x02),
+	("SUBLANG_ENGLISH_US", 0x01),
+	("SUBLANG_ENGLISH_UK", 0x02),
+	("SUBLANG_FRENCH", 0x01),
+	("SUBLANG_GERMAN", 0x01),
+	("SUBLANG_ITALIAN", 0x01),
+	("SUBLANG_PORTUGUESE", 0x02),
+	("SUBLANG_PORTUGUESE_BRAZILIAN", 0x01),
+	("SUBLANG_SPANISH", 0x01),
+	("SUBLANG_SPANISH_MODERN", 0x03),
+];
+
+/// String symbols, mostly window class names.
+pub(in crate::user) const STR_SYMBOLS: &[(&str, &str)] = &[
+	("WC_BUTTON", "Button"),
+	("WC_COMBOBOX", "ComboBox"),
+	("WC_COMBOBOXEX", "ComboBoxEx32"),
+	("WC_EDIT", "Edit"),
+	("WC_HEADER", "SysHeader32"),
+	("WC_IPADDRESS", "SysIPAddress32"),
+	("WC_LINK", "SysLink"),
+	("WC_LISTBOX", "ListBox"),
+	("WC_LISTVIEW", "SysListView32"),
+	("WC_PAGESCROLLER", "SysPager"),
+	("WC_SCROLLBAR", "ScrollBar"),
+	("WC_STATIC", "Static"),
+	("WC_TABCONTROL", "SysTabControl32"),
+	("WC_TREEVIEW", "SysTreeView32"),
+	("ANIMATE_CLASS", "SysAnimate32"),
+	("DATETIMEPICK_CLASS", "SysDateTimePick32"),
+	("HOTKEY_CLASS", "msctls_hotkey32"),
+	("MONTHCAL_CLASS", "SysMonthCal32"),
+	("PROGRESS_CLASS", "msctls_progress32"),
+	("REBARCLASSNAME", "ReBarWindow32"),
+	("STATUSCLASSNAME", "msctls_statusbar32"),
+	("TOOLBARCLASSNAME", "ToolbarWindow32"),
+	("TRACKBAR_CLASS", "msctls_trackbar32"),
+	("UPDOWN_CLASS", "msctls_updown32"),
+	("MSFTEDIT_CLASS", "RICHEDIT50W"),
+];
+
+/// SDK headers which are silently skipped when included but not found.
+pub(in crate::user) const SDK_HEADERS: &[&str] = &[
+	"afxres.h",
+	"commctrl.h",
+	"dlgs.h",
+	"ntverp.h",
+	"richedit.h",
+	"verrsrc.h",
+	"windows.h",
+	"winres.h",
+	"winresrc.h",
+	"winuser.h",
+	"winver.h",
+];
@@ -0,0 +1,334 @@
+use crate::co;
+use crate::decl::*;
+
+/// A single resource of a [`ResFile`](crate::ResFile).
+#[derive(Clone)]
+pub struct ResEntry {
+	/// Resource type.
+	pub res_type: RtStr,
+	/// Resource name.
+	pub name: IdStr,
+	/// Resource language.
+	pub lang: LANGID,
+	/// User-defined version number, set with the `VERSION` statement.
+	pub version: u32,
+	/// User-defined value, set with the `CHARACTERISTICS` statement.
+	pub characteristics: u32,
+	/// Raw data of the resource, in the same layout it will have in the
+	/// executable.
+	pub data: Vec<u8>,
+}
+
+/// Owned, pure-Rust representation of a compiled resource (`.res`) file, which
+/// is a sequence of resources, each one with its own header.
+///
+/// A `.res` file can be given to the linker, or its entries can be written
+/// directly into an executable with
+/// [`HUPDATERSRC::UpdateResource`](crate::prelude::kernel_Hupdatersrc::UpdateResource).
+///
+/// # Examples
+///
+/// Compiling a resource script in a build script, then passing it to the
+/// linker:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let out_dir = std::env::var("OUT_DIR")?;
+/// let res = w::ResFile::compile("resources/app.rc")?;
+/// std::fs::write(format!("{}/app.res", out_dir), res.serialize())?;
+/// println!("cargo:rustc-link-arg-bins={}/app.res", out_dir);
+/// # w::AnyResult::Ok(())
+/// ```
+///
+/// Writing the compiled resources into an existing executable:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let res = w::ResFile::compile("C:\\Temp\\app.rc")?;
+///
+/// let hupd = w::HUPDATERSRC::BeginUpdateResource("C:\\Temp\\foo.exe", false)?;
+/// for entry in res.entries.iter() {
+///     hupd.UpdateResource(
+///         entry.res_type.clone(),
+///         entry.name.clone(),
+///         entry.lang,
+///         &entry.data,
+///     )?;
+/// }
+/// # w::AnyResult::Ok(())
+/// ```
+#[derive(Clone, Default)]
+pub struct ResFile {
+	/// The resources, in the order they appear.
+	pub entries: Vec<ResEntry>,
+}
+
+impl ResFile {
+	const MEMORY_FLAGS: u16 = 0x1030; // MOVEABLE | PURE | DISCARDABLE
+
+	/// Creates a new, empty `ResFile`.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Parses the raw contents of a `.res` file.
+	///
+	/// Returns [`ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA) if the
+	/// data is malformed.
+	#[must_use]
+	pub fn parse(data: &[u8]) -> SysResult<Self> {
+		let mut new_self = Self::new();
+		let mut off = 0;
+
+		while off < data.len() {
+			let data_sz = read_u32(data, off)? as usize;
+			let header_sz = read_u32(data, off + 4)? as usize;
+			let data_off = off + header_sz;
+			if header_sz < 32 || data_off + data_sz > data.len() {
+				return Err(co::ERROR::INVALID_DATA);
+			}
+
+			let (res_type, name_off) = read_sz_or_ord(data, off + 8)?;
+			let (name, fields_off) = read_sz_or_ord(data, name_off)?;
+			let fields_off = align4(fields_off);
+			let lang = read_u16(data, fields_off + 6)?;
+			let version = read_u32(data, fields_off + 8)?;
+			let characteristics = read_u32(data, fields_off + 12)?;
+
+			let is_empty_header = data_sz == 0 && matches!(res_type, IdStr::Id(0));
+			if !is_empty_header {
+				new_self.entries.push(ResEntry {
+					res_type: match res_type {
+						IdStr::Id(rt) => RtStr::Rt(unsafe { co::RT::from_raw(rt) }),
+						IdStr::Str(ws) => RtStr::Str(ws),
+					},
+					name,
+					lang: unsafe { LANGID::from_raw(lang) },
+					version,
+					characteristics,
+					data: data[data_off..data_off + data_sz].to_vec(),
+				});
+			}
+			off = align4(data_off + data_sz);
+		}
+
+		Ok(new_self)
+	}
+
+	/// Serializes the resources into the raw contents of a `.res` file.
+	#[must_use]
+	pub fn serialize(&self) -> Vec<u8> {
+		let mut buf = Vec::<u8>::with_capacity(
+			self.entries
+				.iter()
+				.map(|entry| entry.data.len() + 64)
+				.sum::<usize>()
+				+ 32,
+		);
+
+		// The file starts with an empty entry, which identifies it as a 32-bit
+		// resource file.
+		Self::write_entry(&mut buf, &IdStr::Id(0), &IdStr::Id(0), 0, 0, 0, 0, &[]);
+
+		for entry in self.entries.iter() {
+			let res_type = match &entry.res_type {
+				RtStr::Rt(rt) => IdStr::Id(rt.raw()),
+				RtStr::Str(ws) => IdStr::Str(ws.clone()),
+			};
+			Self::write_entry(
+				&mut buf,
+				&res_type,
+				&entry.name,
+				Self::MEMORY_FLAGS,
+				entry.lang.raw(),
+				entry.version,
+				entry.characteristics,
+				&entry.data,
+			);
+		}
+		buf
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	fn write_entry(
+		buf: &mut Vec<u8>,
+		res_type: &IdStr,
+		name: &IdStr,
+		memory_flags: u16,
+		lang: u16,
+		version: u32,
+		characteristics: u32,
+		data: &[u8],
+	) {
+		buf.resize(align4(buf.len()), 0x00);
+		let start_off = buf.len();
+
+		buf.extend_from_slice(&(data.len() as u32).to_le_bytes());
+		buf.extend_from_slice(&0u32.to_le_bytes()); // HeaderSize, filled below
+		write_sz_or_ord(buf, res_type);
+		write_sz_or_ord(buf, name);
+		buf.resize(align4(buf.len()), 0x00);
+		buf.extend_from_slice(&0u32.to_le_bytes()); // DataVersion
+		buf.extend_from_slice(&memory_flags.to_le_bytes());
+		buf.extend_from_slice(&lang.to_le_bytes());
+		buf.extend_from_slice(&version.to_le_bytes());
+		buf.extend_from_slice(&characteristics.to_le_bytes());
+
+		let header_sz = ((buf.len() - start_off) as u32).to_le_bytes();
+		buf[start_off + 4..start_off + 8].copy_from_slice(&header_sz);
+
+		buf.extend_from_slice(data);
+		buf.resize(align4(buf.len()), 0x00);
+	}
+}
+
+#[must_use]
+const fn align4(off: usize) -> usize {
+	(off + 3) & !3
+}
+
+fn read_u16(data: &[u8], off: usize) -> SysResult<u16> {
+	data.get(off..off + 2)
+		.map(|b| u16::from_le_bytes([b[0], b[1]]))
+		.ok_or(co::ERROR::INVALID_DATA)
+}
+
+fn read_u32(data: &[u8], off: usize) -> SysResult<u32> {
+	data.get(off..off + 4)
+		.map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+		.ok_or(co::ERROR::INVALID_DATA)
+}
+
+/// Reads an ordinal, prefixed with `0xffff`, or a null-terminated string;
+/// returns the offset right past it.
+fn read_sz_or_ord(data: &[u8], off: usize) -> SysResult<(IdStr, usize)> {
+	if read_u16(data, off)? == 0xffff {
+		return Ok((IdStr::Id(read_u16(data, off + 2)?), off + 4));
+	}
+	let mut str16 = Vec::<u16>::with_capacity(16);
+	let mut cur_off = off;
+	loop {
+		let ch = read_u16(data, cur_off)?;
+		cur_off += 2;
+		if ch == 0x0000 {
+			break;
+		}
+		str16.push(ch);
+	}
+	let s = String::from_utf16(&str16).map_err(|_| co::ERROR::INVALID_DATA)?;
+	Ok((IdStr::from_str(&s), cur_off))
+}
+
+fn write_sz_or_ord(buf: &mut Vec<u8>, v: &IdStr) {
+	match v {
+		IdStr::Id(id) => {
+			buf.extend_from_slice(&0xffffu16.to_le_bytes());
+			buf.extend_from_slice(&id.to_le_bytes());
+		},
+		IdStr::Str(ws) => ws
+			.to_string()
+			.encode_utf16()
+			.chain(std::iter::once(0x0000))
+			.for_each(|ch| buf.extend_from_slice(&ch.to_le_bytes())),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn entry(res_type: RtStr, name: IdStr, lang: u16, data: &[u8]) -> ResEntry {
+		ResEntry {
+			res_type,
+			name,
+			lang: unsafe { LANGID::from_raw(lang) },
+			version: 0,
+			characteristics: 0,
+			data: data.to_vec(),
+		}
+	}
+
+	#[test]
+	fn serialize_empty() {
+		#[rustfmt::skip]
+		let expected: &[u8] = &[
+			0x00, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, // DataSize, HeaderSize
+			0xff, 0xff, 0x00, 0x00, 0xff, 0xff, 0x00, 0x00, // TYPE, NAME
+			0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // DataVersion, MemoryFlags, LanguageId
+			0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // Version, Characteristics
+		];
+		assert_eq!(ResFile::new().serialize(), expected);
+		assert!(ResFile::parse(expected).unwrap().entries.is_empty());
+	}
+
+	#[test]
+	fn serialize_entry() {
+		let mut res = ResFile::new();
+		res.entries.push(ResEntry {
+			version: 7,
+			characteristics: 8,
+			..entry(RtStr::from_str("AB"), IdStr::Id(5), 0x0409, &[0x11, 0x22, 0x33])
+		});
+
+		#[rustfmt::skip]
+		let expected: &[u8] = &[
+			0x03, 0x00, 0x00, 0x00, 0x24, 0x00, 0x00, 0x00, // DataSize, HeaderSize
+			0x41, 0x00, 0x42, 0x00, 0x00, 0x00, // TYPE
+			0xff, 0xff, 0x05, 0x00, // NAME
+			0x00, 0x00, // padding
+			0x00, 0x00, 0x00, 0x00, 0x30, 0x10, 0x09, 0x04, // DataVersion, MemoryFlags, LanguageId
+			0x07, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, // Version, Characteristics
+			0x11, 0x22, 0x33, 0x00, // data and padding
+		];
+		assert_eq!(&res.serialize()[32..], expected);
+	}
+
+	#[test]
+	fn round_trip() {
+		let mut res = ResFile::new();
+		res.entries
+			.push(entry(RtStr::Rt(co::RT::RCDATA), IdStr::Id(1), 0x0409, &[1, 2, 3]));
+		res.entries
+			.push(entry(RtStr::from_str("MYTYPE"), IdStr::from_str("ODD"), 0x0416, &[4]));
+		res.entries
+			.push(entry(RtStr::Rt(co::RT::HTML), IdStr::from_str("EVEN"), 0, &[]));
+		res.entries.push(ResEntry {
+			version: 0x1234_5678,
+			characteristics: 0x9abc_def0,
+			..entry(RtStr::Rt(co::RT::MANIFEST), IdStr::Id(0xffff), 0x0409, &[5; 9])
+		});
+
+		let bytes = res.serialize();
+		assert_eq!(bytes.len() % 4, 0);
+		let parsed = ResFile::parse(&bytes).unwrap();
+
+		assert_eq!(parsed.entries.len(), res.entries.len());
+		for (a, b) in res.entries.iter().zip(parsed.entries.iter()) {
+			assert_eq!(a.res_type.to_string(), b.res_type.to_string());
+			assert_eq!(a.name.to_string(), b.name.to_string());
+			assert_eq!(a.lang, b.lang);
+			assert_eq!(a.version, b.version);
+			assert_eq!(a.characteristics, b.characteristics);
+			assert_eq!(a.data, b.data);
+		}
+		assert_eq!(parsed.serialize(), bytes);
+	}
+
+	#[test]
+	fn parse_malformed() {
+		let mut res = ResFile::new();
+		res.entries
+			.push(entry(RtStr::Rt(co::RT::RCDATA), IdStr::Id(1), 0x0409, &[1, 2, 3, 4]));
+		let bytes = res.serialize();
+
+		let truncated = &bytes[..bytes.len() - 1];
+		assert!(matches!(ResFile::parse(truncated), Err(co::ERROR::INVALID_DATA)));
+
+		let mut small_header = bytes.clone();
+		small_header[36] = 0x10; // HeaderSize of the entry
+		assert!(matches!(ResFile::parse(&small_header), Err(co::ERROR::INVALID_DATA)));
+	}
+}
@@ -102,6 +102,39 @@ pub trait version_Hversioninfo: Handle {
 		}
 	}
 
+	/// Parses the whole version info block into an owned
+	/// [`VersionInfo`](crate::VersionInfo), which can be inspected and
+	/// modified without further native calls.
+	///
+	/// # Examples
+	///
+	/// Listing all string values of all string tables:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let exe_name = w::HINSTANCE::NULL.GetModuleFileName()?;
+	/// let hversion = w::HVERSIONINFO::GetFileVersionInfo(&exe_name)?;
+	/// let vi = hversion.to_version_info()?;
+	///
+	/// for table in vi.string_tables.iter() {
+	///     for (name, value) in table.entries.iter() {
+	///         println!("{}: {}", name, value);
+	///     }
+	/// }
+	/// # w::SysResult::Ok(())
+	/// ```
+	#[must_use]
+	fn to_version_info(&self) -> SysResult<VersionInfo> {
+		if self.ptr().is_null() {
+			return Err(co::ERROR::INVALID_HANDLE);
+		}
+		let block_sz = unsafe { *(self.ptr() as *const u16) }; // wLength field of VS_VERSIONINFO
+		VersionInfo::parse(unsafe {
+			std::slice::from_raw_parts(self.ptr() as *const u8, block_sz as _)
+		})
+	}
+
 	/// [`VerQueryValue`](https://learn.microsoft.com/en-us/windows/win32/api/winver/nf-winver-verqueryvaluew)
 	/// function.
 	///
@@ -6,6 +6,7 @@ use crate::decl::*;
 /// [`VS_FIXEDFILEINFO`](https://learn.microsoft.com/en-us/windows/win32/api/verrsrc/ns-verrsrc-vs_fixedfileinfo)
 /// struct.
 #[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct VS_FIXEDFILEINFO {
 	dwSignature: u32,
 	pub dwStrucVersion: u32,
@@ -25,12 +26,15 @@ pub struct VS_FIXEDFILEINFO {
 impl Default for VS_FIXEDFILEINFO {
 	fn default() -> Self {
 		let mut obj = unsafe { std::mem::zeroed::<Self>() };
-		obj.dwSignature = 0xfeef_04bd;
+		obj.dwSignature = Self::SIGNATURE;
 		obj
 	}
 }
 
 impl VS_FIXEDFILEINFO {
+	/// The value of the `dwSignature` field, which must always be present.
+	pub const SIGNATURE: u32 = 0xfeef_04bd;
+
 	/// Returns the `dwFileVersionMS` and `dwFileVersionLS` fields.
 	#[must_use]
 	pub const fn dwFileVersion(&self) -> [u16; 4] {
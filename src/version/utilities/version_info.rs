@@ -0,0 +1,582 @@
+use crate::co;
+use crate::decl::*;
+
+/// A string table from the `StringFileInfo` block of a
+/// [`VersionInfo`](crate::VersionInfo), which holds name/value pairs for a
+/// given language and code page.
+#[derive(Clone, PartialEq, Eq)]
+pub struct VersionStringTable {
+	/// Language and code page of the table, stored as an 8-digit hexadecimal
+	/// key in the binary block.
+	pub lang_and_cp: (LANGID, co::CP),
+	/// Name/value pairs, in the order they appear in the binary block.
+	pub entries: Vec<(String, String)>,
+}
+
+impl VersionStringTable {
+	/// Creates a new, empty string table.
+	#[must_use]
+	pub const fn new(lang_and_cp: (LANGID, co::CP)) -> Self {
+		Self { lang_and_cp, entries: Vec::new() }
+	}
+
+	/// Returns the value of the given name, if any.
+	#[must_use]
+	pub fn get(&self, name: &str) -> Option<&str> {
+		self.entries
+			.iter()
+			.find(|(n, _)| n == name)
+			.map(|(_, v)| v.as_str())
+	}
+
+	/// Sets the value of the given name, replacing the existing one, if any.
+	pub fn set(&mut self, name: &str, value: &str) {
+		match self.entries.iter_mut().find(|(n, _)| n == name) {
+			Some((_, v)) => *v = value.to_owned(),
+			None => self.entries.push((name.to_owned(), value.to_owned())),
+		}
+	}
+}
+
+/// Owned, pure-Rust representation of a
+/// [`VS_VERSIONINFO`](https://learn.microsoft.com/en-us/windows/win32/menurc/vs-versioninfo)
+/// resource block, which can be parsed from and serialized back to its binary
+/// layout.
+///
+/// Unlike [`HVERSIONINFO`](crate::HVERSIONINFO), this type doesn't call any
+/// native function, so it can read the raw bytes of a version resource from
+/// any source, and it can also produce one.
+///
+/// # Examples
+///
+/// Building a version resource and stamping it into an executable:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let lang_cp = (w::LANGID::new(co::LANG::ENGLISH, co::SUBLANG::ENGLISH_US), co::CP::UTF16);
+///
+/// let mut vi = w::VersionInfo::new();
+/// vi.fixed.get_or_insert_default().set_dwFileVersion([1, 2, 0, 0]);
+/// vi.set_str_val(lang_cp, "ProductName", "My app");
+/// vi.set_str_val(lang_cp, "FileVersion", "1.2.0.0");
+///
+/// let hupd = w::HUPDATERSRC::BeginUpdateResource("C:\\Temp\\foo.exe", false)?;
+/// hupd.UpdateResource(
+///     w::RtStr::Rt(co::RT::VERSION),
+///     w::IdStr::Id(1),
+///     lang_cp.0,
+///     &vi.serialize()?,
+/// )?;
+/// # w::SysResult::Ok(())
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct VersionInfo {
+	/// The fixed version block, if any.
+	pub fixed: Option<VS_FIXEDFILEINFO>,
+	/// The string tables of the `StringFileInfo` block, each one keyed by its
+	/// language and code page.
+	pub string_tables: Vec<VersionStringTable>,
+	/// The pairs of language and code page of the `VarFileInfo\Translation`
+	/// value.
+	pub translations: Vec<(LANGID, co::CP)>,
+}
+
+impl VersionInfo {
+	const KEY_ROOT: &'static str = "VS_VERSION_INFO";
+	const KEY_STRING_FILE_INFO: &'static str = "StringFileInfo";
+	const KEY_VAR_FILE_INFO: &'static str = "VarFileInfo";
+	const KEY_TRANSLATION: &'static str = "Translation";
+
+	/// Creates a new, empty object.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Parses a binary `VS_VERSIONINFO` block, like the raw data of a
+	/// [`RT::VERSION`](crate::co::RT::VERSION) resource.
+	///
+	/// Returns [`ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA) if the
+	/// data is malformed.
+	#[must_use]
+	pub fn parse(data: &[u8]) -> SysResult<Self> {
+		let root = Block::read(data, 0)?;
+		if root.key != Self::KEY_ROOT {
+			return Err(co::ERROR::INVALID_DATA);
+		}
+
+		let mut new_self = Self::new();
+
+		if !root.value.is_empty() {
+			if root.value.len() < std::mem::size_of::<VS_FIXEDFILEINFO>() {
+				return Err(co::ERROR::INVALID_DATA);
+			}
+			let signature = u32::from_le_bytes(root.value[..4].try_into().unwrap());
+			if signature != VS_FIXEDFILEINFO::SIGNATURE {
+				return Err(co::ERROR::INVALID_DATA);
+			}
+			new_self.fixed = Some(unsafe {
+				std::ptr::read_unaligned(root.value.as_ptr() as *const VS_FIXEDFILEINFO)
+			});
+		}
+
+		for child in root.children(data) {
+			let child = child?;
+			if child.key == Self::KEY_STRING_FILE_INFO {
+				for table in child.children(data) {
+					let table = table?;
+					let mut str_table =
+						VersionStringTable::new(Self::parse_lang_cp_key(&table.key)?);
+					for entry in table.children(data) {
+						let entry = entry?;
+						let value = entry.value_as_str();
+						str_table.entries.push((entry.key, value));
+					}
+					new_self.string_tables.push(str_table);
+				}
+			} else if child.key == Self::KEY_VAR_FILE_INFO {
+				for var in child.children(data) {
+					let var = var?;
+					if var.key == Self::KEY_TRANSLATION {
+						new_self
+							.translations
+							.extend(var.value.chunks_exact(4).map(|ch4| unsafe {
+								(
+									LANGID::from_raw(u16::from_le_bytes([ch4[0], ch4[1]])),
+									co::CP::from_raw(u16::from_le_bytes([ch4[2], ch4[3]])),
+								)
+							}));
+					}
+				}
+			}
+		}
+
+		Ok(new_self)
+	}
+
+	/// Serializes the object into a binary `VS_VERSIONINFO` block, which can
+	/// be written as an [`RT::VERSION`](crate::co::RT::VERSION) resource with
+	/// [`HUPDATERSRC::UpdateResource`](crate::prelude::kernel_Hupdatersrc::UpdateResource).
+	///
+	/// Returns [`ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA) if any
+	/// block or value exceeds the 64 KB limit of the binary format.
+	#[must_use]
+	pub fn serialize(&self) -> SysResult<Vec<u8>> {
+		let mut buf = Vec::<u8>::with_capacity(1024);
+
+		let fixed_bytes = self.fixed.as_ref().map_or(&[][..], |fixed| unsafe {
+			std::slice::from_raw_parts(
+				fixed as *const _ as *const u8,
+				std::mem::size_of::<VS_FIXEDFILEINFO>(),
+			)
+		});
+
+		Block::write(&mut buf, Self::KEY_ROOT, BlockType::Binary, fixed_bytes, |buf| {
+			if !self.string_tables.is_empty() {
+				Block::write(buf, Self::KEY_STRING_FILE_INFO, BlockType::Text, &[], |buf| {
+					for table in self.string_tables.iter() {
+						let key = Self::format_lang_cp_key(table.lang_and_cp);
+						Block::write(buf, &key, BlockType::Text, &[], |buf| {
+							for (name, value) in table.entries.iter() {
+								let value_bytes = value
+									.encode_utf16()
+									.chain(std::iter::once(0x0000)) // terminating null is part of the value
+									.flat_map(|ch| ch.to_le_bytes())
+									.collect::<Vec<_>>();
+								Block::write(buf, name, BlockType::Text, &value_bytes, |_| Ok(()))?;
+							}
+							Ok(())
+						})?;
+					}
+					Ok(())
+				})?;
+			}
+
+			if !self.translations.is_empty() {
+				Block::write(buf, Self::KEY_VAR_FILE_INFO, BlockType::Text, &[], |buf| {
+					let value_bytes = self
+						.translations
+						.iter()
+						.flat_map(|(lang, cp)| {
+							let (lang, cp) = (lang.raw().to_le_bytes(), cp.raw().to_le_bytes());
+							[lang[0], lang[1], cp[0], cp[1]]
+						})
+						.collect::<Vec<_>>();
+					Block::write(
+						buf,
+						Self::KEY_TRANSLATION,
+						BlockType::Binary,
+						&value_bytes,
+						|_| Ok(()),
+					)
+				})?;
+			}
+			Ok(())
+		})?;
+
+		Ok(buf)
+	}
+
+	/// Returns the string value of the given name, in the table of the given
+	/// language and code page, if any.
+	#[must_use]
+	pub fn str_val(&self, lang_and_cp: (LANGID, co::CP), name: &str) -> Option<&str> {
+		self.string_tables
+			.iter()
+			.find(|table| table.lang_and_cp == lang_and_cp)
+			.and_then(|table| table.get(name))
+	}
+
+	/// Sets the string value of the given name, in the table of the given
+	/// language and code page.
+	///
+	/// If the table doesn't exist, it will be created, and the language and
+	/// code page will also be added to the
+	/// [`translations`](crate::VersionInfo::translations), if not present yet.
+	pub fn set_str_val(&mut self, lang_and_cp: (LANGID, co::CP), name: &str, value: &str) {
+		let table = match self
+			.string_tables
+			.iter()
+			.position(|table| table.lang_and_cp == lang_and_cp)
+		{
+			Some(idx) => &mut self.string_tables[idx],
+			None => {
+				self.string_tables
+					.push(VersionStringTable::new(lang_and_cp));
+				self.string_tables.last_mut().unwrap()
+			},
+		};
+		table.set(name, value);
+
+		if !self.translations.contains(&lang_and_cp) {
+			self.translations.push(lang_and_cp);
+		}
+	}
+
+	#[must_use]
+	fn format_lang_cp_key(lang_and_cp: (LANGID, co::CP)) -> String {
+		format!("{:04x}{:04x}", lang_and_cp.0.raw(), lang_and_cp.1.raw())
+	}
+
+	#[must_use]
+	fn parse_lang_cp_key(key: &str) -> SysResult<(LANGID, co::CP)> {
+		if key.len() != 8 || !key.is_ascii() {
+			return Err(co::ERROR::INVALID_DATA);
+		}
+		let lang = u16::from_str_radix(&key[..4], 16).map_err(|_| co::ERROR::INVALID_DATA)?;
+		let cp = u16::from_str_radix(&key[4..], 16).map_err(|_| co::ERROR::INVALID_DATA)?;
+		Ok(unsafe { (LANGID::from_raw(lang), co::CP::from_raw(cp)) })
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BlockType {
+	Binary,
+	Text,
+}
+
+/// A generic block of the version resource, which has a header followed by
+/// the key, the value and the children blocks, all 32-bit aligned.
+struct Block<'a> {
+	key: String,
+	value: &'a [u8],
+	children_off: usize,
+	end_off: usize,
+}
+
+impl<'a> Block<'a> {
+	const HEADER_SZ: usize = 3 * std::mem::size_of::<u16>(); // wLength, wValueLength, wType
+
+	#[must_use]
+	const fn align4(off: usize) -> usize {
+		(off + 3) & !3
+	}
+
+	#[must_use]
+	fn read_u16(data: &[u8], off: usize) -> SysResult<u16> {
+		data.get(off..off + 2)
+			.map(|b| u16::from_le_bytes([b[0], b[1]]))
+			.ok_or(co::ERROR::INVALID_DATA)
+	}
+
+	/// Reads the block which starts at the given offset.
+	#[must_use]
+	fn read(data: &'a [u8], off: usize) -> SysResult<Self> {
+		let len = Self::read_u16(data, off)? as usize;
+		let value_len = Self::read_u16(data, off + 2)? as usize;
+		let block_type = match Self::read_u16(data, off + 4)? {
+			0 => BlockType::Binary,
+			1 => BlockType::Text,
+			_ => return Err(co::ERROR::INVALID_DATA),
+		};
+
+		let end_off = off + len;
+		if len < Self::HEADER_SZ || end_off > data.len() {
+			return Err(co::ERROR::INVALID_DATA);
+		}
+
+		let mut key16 = Vec::<u16>::with_capacity(32);
+		let mut cur_off = off + Self::HEADER_SZ;
+		loop {
+			if cur_off + 2 > end_off {
+				return Err(co::ERROR::INVALID_DATA); // key not null-terminated
+			}
+			let ch = Self::read_u16(data, cur_off)?;
+			cur_off += 2;
+			if ch == 0x0000 {
+				break;
+			}
+			key16.push(ch);
+		}
+		let key = String::from_utf16(&key16).map_err(|_| co::ERROR::INVALID_DATA)?;
+
+		let value_off = Self::align4(cur_off).min(end_off);
+		let value_sz = match block_type {
+			BlockType::Binary => value_len,
+			BlockType::Text => value_len * std::mem::size_of::<u16>(), // length is given in chars
+		}
+		.min(end_off - value_off); // some compilers count text lengths in bytes, so be lenient
+
+		Ok(Self {
+			key,
+			value: &data[value_off..value_off + value_sz],
+			children_off: Self::align4(value_off + value_sz),
+			end_off,
+		})
+	}
+
+	/// Returns an iterator over the children blocks.
+	#[must_use]
+	fn children(&self, data: &'a [u8]) -> impl Iterator<Item = SysResult<Block<'a>>> + 'a {
+		let mut cur_off = self.children_off;
+		let end_off = self.end_off;
+		let mut failed = false;
+
+		std::iter::from_fn(move || {
+			if failed || cur_off >= end_off {
+				return None;
+			}
+			match Block::read(&data[..end_off], cur_off) {
+				Ok(child) => {
+					cur_off = Self::align4(child.end_off);
+					Some(Ok(child))
+				},
+				Err(e) => {
+					failed = true;
+					Some(Err(e))
+				},
+			}
+		})
+	}
+
+	/// Interprets the value as a null-terminated UTF-16 string.
+	#[must_use]
+	fn value_as_str(&self) -> String {
+		let value16 = self
+			.value
+			.chunks_exact(2)
+			.map(|ch2| u16::from_le_bytes([ch2[0], ch2[1]]))
+			.take_while(|ch| *ch != 0x0000)
+			.collect::<Vec<_>>();
+		String::from_utf16_lossy(&value16)
+	}
+
+	/// Appends a block to the buffer, whose children are written by the
+	/// callback; `wLength` is computed afterwards.
+	///
+	/// Fails if a length doesn't fit in the `u16` fields of the header.
+	fn write(
+		buf: &mut Vec<u8>,
+		key: &str,
+		block_type: BlockType,
+		value: &[u8],
+		children: impl FnOnce(&mut Vec<u8>) -> SysResult<()>,
+	) -> SysResult<()> {
+		buf.resize(Self::align4(buf.len()), 0x00);
+		let start_off = buf.len();
+
+		let value_len = Self::len_u16(match block_type {
+			BlockType::Binary => value.len(),
+			BlockType::Text => value.len() / std::mem::size_of::<u16>(),
+		})?;
+
+		buf.extend_from_slice(&0u16.to_le_bytes()); // wLength, filled below
+		buf.extend_from_slice(&value_len.to_le_bytes());
+		buf.extend_from_slice(&(block_type as u16).to_le_bytes());
+		key.encode_utf16()
+			.chain(std::iter::once(0x0000))
+			.for_each(|ch| buf.extend_from_slice(&ch.to_le_bytes()));
+
+		buf.resize(Self::align4(buf.len()), 0x00);
+		buf.extend_from_slice(value);
+
+		children(buf)?;
+
+		let len = Self::len_u16(buf.len() - start_off)?.to_le_bytes();
+		buf[start_off..start_off + 2].copy_from_slice(&len);
+		Ok(())
+	}
+
+	/// Converts a length into the `u16` stored in the header.
+	fn len_u16(len: usize) -> SysResult<u16> {
+		u16::try_from(len).map_err(|_| co::ERROR::INVALID_DATA)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Fixed info with file version 1.2.3.4, a string table for en-US and
+	/// UTF-16, and its translation.
+	#[rustfmt::skip]
+	const BLOB: [u8; 308] = [
+		0x34, 0x01, 0x34, 0x00, 0x00, 0x00, 0x56, 0x00, 0x53, 0x00, 0x5f, 0x00, 0x56, 0x00, 0x45, 0x00,
+		0x52, 0x00, 0x53, 0x00, 0x49, 0x00, 0x4f, 0x00, 0x4e, 0x00, 0x5f, 0x00, 0x49, 0x00, 0x4e, 0x00,
+		0x46, 0x00, 0x4f, 0x00, 0x00, 0x00, 0x00, 0x00, 0xbd, 0x04, 0xef, 0xfe, 0x00, 0x00, 0x01, 0x00,
+		0x02, 0x00, 0x01, 0x00, 0x04, 0x00, 0x03, 0x00, 0x02, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+		0x3f, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00,
+		0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x94, 0x00, 0x00, 0x00,
+		0x01, 0x00, 0x53, 0x00, 0x74, 0x00, 0x72, 0x00, 0x69, 0x00, 0x6e, 0x00, 0x67, 0x00, 0x46, 0x00,
+		0x69, 0x00, 0x6c, 0x00, 0x65, 0x00, 0x49, 0x00, 0x6e, 0x00, 0x66, 0x00, 0x6f, 0x00, 0x00, 0x00,
+		0x70, 0x00, 0x00, 0x00, 0x01, 0x00, 0x30, 0x00, 0x34, 0x00, 0x30, 0x00, 0x39, 0x00, 0x30, 0x00,
+		0x34, 0x00, 0x62, 0x00, 0x30, 0x00, 0x00, 0x00, 0x28, 0x00, 0x04, 0x00, 0x01, 0x00, 0x50, 0x00,
+		0x72, 0x00, 0x6f, 0x00, 0x64, 0x00, 0x75, 0x00, 0x63, 0x00, 0x74, 0x00, 0x4e, 0x00, 0x61, 0x00,
+		0x6d, 0x00, 0x65, 0x00, 0x00, 0x00, 0x00, 0x00, 0x41, 0x00, 0x70, 0x00, 0x70, 0x00, 0x00, 0x00,
+		0x30, 0x00, 0x08, 0x00, 0x01, 0x00, 0x46, 0x00, 0x69, 0x00, 0x6c, 0x00, 0x65, 0x00, 0x56, 0x00,
+		0x65, 0x00, 0x72, 0x00, 0x73, 0x00, 0x69, 0x00, 0x6f, 0x00, 0x6e, 0x00, 0x00, 0x00, 0x00, 0x00,
+		0x31, 0x00, 0x2e, 0x00, 0x32, 0x00, 0x2e, 0x00, 0x33, 0x00, 0x2e, 0x00, 0x34, 0x00, 0x00, 0x00,
+		0x44, 0x00, 0x00, 0x00, 0x01, 0x00, 0x56, 0x00, 0x61, 0x00, 0x72, 0x00, 0x46, 0x00, 0x69, 0x00,
+		0x6c, 0x00, 0x65, 0x00, 0x49, 0x00, 0x6e, 0x00, 0x66, 0x00, 0x6f, 0x00, 0x00, 0x00, 0x00, 0x00,
+		0x24, 0x00, 0x04, 0x00, 0x00, 0x00, 0x54, 0x00, 0x72, 0x00, 0x61, 0x00, 0x6e, 0x00, 0x73, 0x00,
+		0x6c, 0x00, 0x61, 0x00, 0x74, 0x00, 0x69, 0x00, 0x6f, 0x00, 0x6e, 0x00, 0x00, 0x00, 0x00, 0x00,
+		0x09, 0x04, 0xb0, 0x04,
+	];
+
+	fn en_us() -> (LANGID, co::CP) {
+		(LANGID::new(co::LANG::ENGLISH, co::SUBLANG::ENGLISH_US), co::CP::UTF16)
+	}
+
+	fn pt_br() -> (LANGID, co::CP) {
+		(LANGID::new(co::LANG::PORTUGUESE, co::SUBLANG::PORTUGUESE_BRAZILIAN), co::CP::WINDOWS_1252)
+	}
+
+	#[test]
+	fn parse_known() {
+		let vi = VersionInfo::parse(&BLOB).unwrap();
+
+		let fixed = vi.fixed.unwrap();
+		assert_eq!(fixed.dwFileVersion(), [1, 2, 3, 4]);
+		assert_eq!(fixed.dwProductVersion(), [1, 2, 0, 0]);
+		assert_eq!(fixed.dwFileOS, co::VOS::NT_WINDOWS32);
+		assert_eq!(fixed.dwFileType, co::VFT::APP);
+
+		assert_eq!(vi.string_tables.len(), 1);
+		assert!(vi.string_tables[0].lang_and_cp == en_us());
+		assert_eq!(
+			vi.string_tables[0].entries,
+			[
+				("ProductName".to_owned(), "App".to_owned()),
+				("FileVersion".to_owned(), "1.2.3.4".to_owned()),
+			]
+		);
+		assert_eq!(vi.str_val(en_us(), "FileVersion"), Some("1.2.3.4"));
+		assert_eq!(vi.str_val(pt_br(), "FileVersion"), None);
+		assert!(vi.translations == [en_us()]);
+	}
+
+	#[test]
+	fn round_trip_known() {
+		let vi = VersionInfo::parse(&BLOB).unwrap();
+		let bytes = vi.serialize().unwrap();
+		assert_eq!(bytes, BLOB);
+		assert!(VersionInfo::parse(&bytes).unwrap() == vi);
+	}
+
+	#[test]
+	fn round_trip_tables() {
+		let mut vi = VersionInfo::new();
+		vi.fixed
+			.get_or_insert_default()
+			.set_dwFileVersion([5, 6, 7, 8]);
+		vi.set_str_val(en_us(), "ProductName", "Editor");
+		vi.set_str_val(pt_br(), "ProductName", "Editor de texto");
+		vi.set_str_val(en_us(), "CompanyName", "ACME");
+		vi.set_str_val(en_us(), "ProductName", "Text editor"); // replaced
+		vi.translations
+			.push(unsafe { (LANGID::from_raw(0), co::CP::from_raw(0)) });
+
+		let parsed = VersionInfo::parse(&vi.serialize().unwrap()).unwrap();
+		assert!(parsed == vi);
+		assert_eq!(parsed.fixed.unwrap().dwFileVersion(), [5, 6, 7, 8]);
+		assert_eq!(parsed.string_tables.len(), 2);
+		assert_eq!(parsed.str_val(en_us(), "ProductName"), Some("Text editor"));
+		assert_eq!(parsed.str_val(pt_br(), "ProductName"), Some("Editor de texto"));
+		assert_eq!(parsed.translations.len(), 3);
+	}
+
+	#[test]
+	fn round_trip_empty() {
+		let vi = VersionInfo::new();
+		let bytes = vi.serialize().unwrap();
+		assert_eq!(bytes.len(), 40); // header and key only
+		assert!(VersionInfo::parse(&bytes).unwrap() == vi);
+
+		let mut vi = VersionInfo::new();
+		vi.set_str_val(en_us(), "Comments", "");
+		vi.set_str_val(en_us(), "ProductName", "App");
+		let parsed = VersionInfo::parse(&vi.serialize().unwrap()).unwrap();
+		assert!(parsed.fixed.is_none());
+		assert_eq!(parsed.str_val(en_us(), "Comments"), Some(""));
+		assert_eq!(parsed.str_val(en_us(), "ProductName"), Some("App"));
+	}
+
+	#[test]
+	fn parse_value_without_null() {
+		// Some compilers write empty string values with no characters at all.
+		let mut buf = Vec::new();
+		Block::write(&mut buf, VersionInfo::KEY_ROOT, BlockType::Binary, &[], |buf| {
+			Block::write(buf, VersionInfo::KEY_STRING_FILE_INFO, BlockType::Text, &[], |buf| {
+				Block::write(buf, "040904b0", BlockType::Text, &[], |buf| {
+					Block::write(buf, "Comments", BlockType::Text, &[], |_| Ok(()))
+				})
+			})
+		})
+		.unwrap();
+
+		let vi = VersionInfo::parse(&buf).unwrap();
+		assert_eq!(vi.str_val(en_us(), "Comments"), Some(""));
+	}
+
+	#[test]
+	fn serialize_overflow() {
+		let mut vi = VersionInfo::new();
+		vi.set_str_val(en_us(), "Comments", &"x".repeat(40_000)); // block over 64 KB
+		assert_eq!(vi.serialize(), Err(co::ERROR::INVALID_DATA));
+
+		let mut vi = VersionInfo::new();
+		for i in 0..2_000 {
+			vi.set_str_val(en_us(), &format!("Name{}", i), "Some value"); // table over 64 KB
+		}
+		assert_eq!(vi.serialize(), Err(co::ERROR::INVALID_DATA));
+	}
+
+	#[test]
+	fn parse_malformed() {
+		assert_eq!(VersionInfo::parse(&[]).err(), Some(co::ERROR::INVALID_DATA));
+		assert_eq!(VersionInfo::parse(&BLOB[..100]).err(), Some(co::ERROR::INVALID_DATA)); // truncated
+
+		let mut bad_key = BLOB;
+		bad_key[6] = b'X';
+		assert_eq!(VersionInfo::parse(&bad_key).err(), Some(co::ERROR::INVALID_DATA));
+
+		let mut bad_signature = BLOB;
+		bad_signature[40] = 0;
+		assert_eq!(VersionInfo::parse(&bad_signature).err(), Some(co::ERROR::INVALID_DATA));
+
+		let mut bad_type = BLOB;
+		bad_type[4] = 2;
+		assert_eq!(VersionInfo::parse(&bad_type).err(), Some(co::ERROR::INVALID_DATA));
+	}
+}